@@ -4,6 +4,7 @@ API. A near-drop-in for common patterns; some advanced stdlib scenarios differ
 (see docs/compatibility.md).
 """
 
+import atexit
 import os
 import re
 import sys
@@ -45,6 +46,11 @@ _check_python_version()
 
 from . import logxide
 
+# Global state lives in the extension and is shared process-wide; refuse to run from
+# a subinterpreter rather than silently sharing (or crashing on) another
+# interpreter's handlers.
+logxide.logging.check_interpreter()
+
 __version__ = "0.2.2"
 
 from . import logxide as _logxide_ext
@@ -121,42 +127,95 @@ from .compat_handlers import (
     LoggingManager as LoggingManager,
 )
 from .compat_handlers import NullHandler as _CompatNullHandler
+from .handlers import (
+    AggregatingHandler as AggregatingHandler,
+)
+from .handlers import (
+    DatagramHandler as DatagramHandler,
+)
 from .handlers import (
     FileHandler as FileHandler,
 )
 from .handlers import (
     HTTPHandler as HTTPHandler,
 )
+from .handlers import (
+    LevelRoutedFileHandler as LevelRoutedFileHandler,
+)
 from .handlers import (
     MemoryHandler as MemoryHandler,
 )
 from .handlers import (
     OTLPHandler as OTLPHandler,
 )
+from .handlers import (
+    PipeHandler as PipeHandler,
+)
+from .handlers import (
+    QueueHandler as QueueHandler,
+)
+from .handlers import (
+    QueueListener as QueueListener,
+)
 from .handlers import (
     RotatingFileHandler as RotatingFileHandler,
 )
+from .handlers import (
+    SMTPHandler as SMTPHandler,
+)
+from .handlers import (
+    SocketHandler as SocketHandler,
+)
 from .handlers import (
     StreamHandler as StreamHandler,
 )
+from .handlers import (
+    SyslogHandler as SyslogHandler,
+)
+from .handlers import (
+    TimedRotatingFileHandler as TimedRotatingFileHandler,
+)
+from .handlers import (
+    WatchedFileHandler as WatchedFileHandler,
+)
+from .handlers import (
+    set_default_formatter as set_default_formatter,
+)
 
-# Rust handlers (direct access)
-RustFileHandler = _logxide_ext.FileHandler
+# Rust handlers (direct access; None when built without the matching cargo feature)
+RustFileHandler = getattr(_logxide_ext, "FileHandler", None)
 RustStreamHandler = _logxide_ext.StreamHandler
-RustRotatingFileHandler = _logxide_ext.RotatingFileHandler
-RustHTTPHandler = _logxide_ext.HTTPHandler
-RustOTLPHandler = _logxide_ext.OTLPHandler
+RustRotatingFileHandler = getattr(_logxide_ext, "RotatingFileHandler", None)
+RustTimedRotatingFileHandler = getattr(_logxide_ext, "TimedRotatingFileHandler", None)
+RustLevelRoutedFileHandler = getattr(_logxide_ext, "LevelRoutedFileHandler", None)
+RustHTTPHandler = getattr(_logxide_ext, "HTTPHandler", None)
+RustOTLPHandler = getattr(_logxide_ext, "OTLPHandler", None)
+RustDatagramHandler = getattr(_logxide_ext, "DatagramHandler", None)
+RustSyslogHandler = getattr(_logxide_ext, "SyslogHandler", None)
+RustSocketHandler = getattr(_logxide_ext, "SocketHandler", None)
+RustSMTPHandler = getattr(_logxide_ext, "SMTPHandler", None)
+RustPipeHandler = getattr(_logxide_ext, "PipeHandler", None)
 RustMemoryHandler = _logxide_ext.MemoryHandler
+RustQueueHandler = _logxide_ext.QueueHandler
+RustQueueListener = _logxide_ext.QueueListener
+RustAggregatingHandler = _logxide_ext.AggregatingHandler
 NullHandler = _CompatNullHandler
 
 # Rust formatters (direct access)
 try:
+    ColumnFormatter = _logxide_ext.ColumnFormatter
     ColorFormatter = _logxide_ext.ColorFormatter
     RustFormatter = _logxide_ext.Formatter
 except AttributeError:
     # Fallback if not yet built with new formatters
     pass
+JsonFormatter = getattr(_logxide_ext, "JsonFormatter", None)
 
+from .diagnostics import _restart_after_fork as _restart_diagnostics_after_fork
+from .diagnostics import (
+    configure_queue_diagnostics as configure_queue_diagnostics,
+)
+from . import handlers as _handlers
 from .logger_wrapper import (
     basicConfig as basicConfig,
 )
@@ -172,14 +231,63 @@ from .module_system import (
 from .module_system import (
     uninstall as uninstall,
 )
+from .stdio import (
+    redirect_stdio as redirect_stdio,
+)
+from .unhandled import (
+    capture_unhandled as capture_unhandled,
+)
 
 clear_handlers = logxide.logging.clear_handlers
 
 flush = logxide.logging.flush
 set_thread_name = logxide.logging.set_thread_name
+configure_tracebacks = logxide.logging.configure_tracebacks
+configure_exception_grouping = logxide.logging.configure_exception_grouping
+configure_message_templates = logxide.logging.configure_message_templates
+message_template = logxide.logging.message_template
+configure_extra_limits = logxide.logging.configure_extra_limits
+extra_limit_stats = logxide.logging.extra_limit_stats
+configure_secret_keys = logxide.logging.configure_secret_keys
+configure_run_id = logxide.logging.configure_run_id
+run_id = logxide.logging.run_id
+route = logxide.logging.route
+unroute = logxide.logging.unroute
+routes = logxide.logging.routes
+configure_threads = logxide.logging.configure_threads
+tune = logxide.logging.tune
+preset = logxide.logging.preset
+queue_stats = logxide.logging.queue_stats
+configure_workers = logxide.logging.configure_workers
+configure_console_dedup = logxide.logging.configure_console_dedup
+configure_caller_info = logxide.logging.configure_caller_info
+configure_crash_dump = logxide.logging.configure_crash_dump
+debug_dispatch = logxide.logging.debug_dispatch
+runtime_info = logxide.logging.runtime_info
+tail = logxide.logging.tail
+LogTail = logxide.logging.LogTail
+# None when built without the `json` cargo feature
+read_log_file = getattr(logxide.logging, "read_log_file", None)
+replay_log_file = getattr(logxide.logging, "replay_log_file", None)
+register_schema_migration = getattr(logxide.logging, "register_schema_migration", None)
+schema_migrations = getattr(logxide.logging, "schema_migrations", None)
+migrate_record = getattr(logxide.logging, "migrate_record", None)
+# None when built without the `file` cargo feature
+verify_log_file = getattr(logxide.logging, "verify_log_file", None)
+# None when built without the `watch` cargo feature
+watch_config = getattr(logxide.logging, "watch_config", None)
+ConfigWatcher = getattr(logxide.logging, "ConfigWatcher", None)
+# None when built without the `regex-filters` cargo feature
+configure_redaction = getattr(logxide.logging, "configure_redaction", None)
+RegexFilter = getattr(logxide.logging, "RegexFilter", None)
+Redactor = getattr(logxide.logging, "Redactor", None)
 PyLogger = logxide.logging.PyLogger
 Logger = PyLogger
 LogRecord = logxide.logging.LogRecord
+Filter = logxide.logging.Filter
+LevelRangeFilter = logxide.logging.LevelRangeFilter
+DedupFilter = logxide.logging.DedupFilter
+FieldProjection = logxide.logging.FieldProjection
 
 try:
     from .sentry_integration import (
@@ -192,6 +300,84 @@ except ImportError:
     SentryHandler = None
     auto_configure_sentry = None
 
+
+def reinitialize():
+    """
+    Tear down logxide's process-wide state and start over, as in a fresh interpreter.
+
+    Stops the queue diagnostics reporter and config watchers, removes the
+    capture_unhandled() hooks, puts back the streams redirect_stdio() replaced, closes
+    every handler (draining its queue), removes namespace routes, lifts disable(), and
+    resets every logger, the stdlib ones too once logxide is installed: no handlers or
+    filters, level NOTSET (root WARNING), propagating and enabled. Traceback,
+    exception-grouping, message-template, extra-limit, redaction, secret-key, run id,
+    worker, thread, preset, default-formatter, console de-duplication, caller-info,
+    crash dump and dispatch tracing options go back to their defaults and basicConfig()
+    may run again. Existing logger references stay valid.
+
+    Returns the new state generation.
+    """
+    from .module_system import _std_logging as std_logging
+
+    configure_queue_diagnostics(None)
+    capture_unhandled(False)
+    redirect_stdio(enabled=False)
+    generation = logxide.logging.reinitialize()
+    configure_tracebacks()
+    configure_exception_grouping(enabled=False)
+    configure_message_templates(enabled=False)
+    configure_extra_limits()
+    if configure_redaction is not None:
+        configure_redaction()
+    configure_secret_keys(enabled=False)
+    configure_run_id()
+    configure_workers(daemon=False)
+    configure_threads()
+    tune()
+    preset()
+    configure_console_dedup(enabled=False)
+    configure_crash_dump(None)
+    debug_dispatch(enabled=False)
+    disable(NOTSET)
+    _handlers._reset_default_formatters()
+
+    if hasattr(std_logging, "_logxide_installed"):
+        shells = [std_logging.root] + [
+            logger
+            for logger in std_logging.root.manager.loggerDict.values()
+            if not isinstance(logger, std_logging.PlaceHolder)
+        ]
+        for shell in shells:
+            shell.handlers.clear()
+            shell.filters.clear()
+            shell.propagate = True
+            shell.disabled = False
+            shell.setLevel(WARNING if shell is std_logging.root else NOTSET)
+    return generation
+
+
+# Registered once per process, however often the package is re-imported. Registered
+# after stdlib logging's own atexit hook, so it runs first: non-daemon network workers
+# drain before logging.shutdown() closes the handlers.
+_process_state = logxide.logging.process_state()
+if not _process_state.get("atexit_registered"):
+    _process_state["atexit_registered"] = True
+    atexit.register(logxide.logging.shutdown_workers)
+    if watch_config is not None:
+        atexit.register(logxide.logging.stop_config_watchers)
+    # A forked child (prefork servers, multiprocessing's fork start method) gets its
+    # own handler threads and connections; see docs/reference.md#fork-safety.
+    if hasattr(os, "register_at_fork"):
+
+        def _after_fork_in_child():
+            logxide.logging.after_fork_in_child()
+            _restart_diagnostics_after_fork()
+
+        os.register_at_fork(
+            before=logxide.logging.before_fork,
+            after_in_child=_after_fork_in_child,
+        )
+
 if "pytest" not in sys.modules and "PYTEST_CURRENT_TEST" not in os.environ:
     _install()
     sys.modules["logging"] = logging
@@ -2,8 +2,56 @@
 Type stubs for LogXide - High-performance logging for Python
 """
 
-from collections.abc import Callable
-from typing import IO, Any
+from collections.abc import Callable, Sequence
+from typing import Any
+
+from .handlers import AggregatingHandler as AggregatingHandler
+from .handlers import DatagramHandler as DatagramHandler
+from .handlers import FileHandler as FileHandler
+from .handlers import HTTPHandler as HTTPHandler
+from .handlers import LevelRoutedFileHandler as LevelRoutedFileHandler
+from .handlers import MemoryHandler as MemoryHandler
+from .handlers import OTLPHandler as OTLPHandler
+from .handlers import PipeHandler as PipeHandler
+from .handlers import QueueHandler as QueueHandler
+from .handlers import QueueListener as QueueListener
+from .handlers import RotatingFileHandler as RotatingFileHandler
+from .handlers import SMTPHandler as SMTPHandler
+from .handlers import SocketHandler as SocketHandler
+from .handlers import StreamHandler as StreamHandler
+from .handlers import SyslogHandler as SyslogHandler
+from .handlers import TimedRotatingFileHandler as TimedRotatingFileHandler
+from .handlers import WatchedFileHandler as WatchedFileHandler
+from .handlers import set_default_formatter as set_default_formatter
+# The Rust extension; logxide.pyi is generated by `cargo xtask stubs`.
+from .logxide import AggregatingHandler as RustAggregatingHandler
+from .logxide import ColorFormatter as ColorFormatter
+from .logxide import ColumnFormatter as ColumnFormatter
+from .logxide import ConfigWatcher as ConfigWatcher
+from .logxide import DatagramHandler as RustDatagramHandler
+from .logxide import FileHandler as RustFileHandler
+from .logxide import Formatter as RustFormatter
+from .logxide import HTTPHandler as RustHTTPHandler
+from .logxide import JsonFormatter as JsonFormatter
+from .logxide import LevelRoutedFileHandler as RustLevelRoutedFileHandler
+from .logxide import Level as Level
+from .logxide import LogRecord as LogRecord
+from .logxide import LogTail as LogTail
+from .logxide import MemoryHandler as RustMemoryHandler
+from .logxide import OTLPHandler as RustOTLPHandler
+from .logxide import PipeHandler as RustPipeHandler
+from .logxide import Progress as Progress
+from .logxide import PyLogger as PyLogger
+from .logxide import QueueHandler as RustQueueHandler
+from .logxide import QueueListener as RustQueueListener
+from .logxide import RotatingFileHandler as RustRotatingFileHandler
+from .logxide import SMTPHandler as RustSMTPHandler
+from .logxide import SocketHandler as RustSocketHandler
+from .logxide import StreamHandler as RustStreamHandler
+from .logxide import SyslogHandler as RustSyslogHandler
+from .logxide import TimedRotatingFileHandler as RustTimedRotatingFileHandler
+from .logxide import Timer as Timer
+from .logxide import logging as _ext_logging
 
 # Version information
 __version__: str
@@ -24,74 +72,92 @@ FATAL: int  # Alias for CRITICAL
 NOTSET: int
 
 # Type aliases
-LogLevel = int
+LogLevel = Level
 
-class PyLogger:
-    """High-performance logger implementation."""
+# Type alias for Logger
+Logger = PyLogger
+
+class Filter:
+    """Passes records from the logger `name` and its children, like `logging.Filter`.
+    Loggers run these filters in Rust."""
 
-    def __init__(self, name: str) -> None: ...
+    def __init__(self, name: str = "") -> None: ...
     @property
     def name(self) -> str: ...
     @property
-    def level(self) -> int: ...
+    def nlen(self) -> int: ...
+    def filter(self, record: Any) -> bool: ...
+
+class RegexFilter:
+    """Passes or drops records by matching a regex against `msg`, `name` or an extra
+    field. Loggers run these filters in Rust."""
+
+    def __init__(
+        self, pattern: str, field: str = "msg", mode: str = "include"
+    ) -> None: ...
     @property
-    def handlers(self) -> list: ...
+    def pattern(self) -> str: ...
     @property
-    def manager(self) -> Any: ...
+    def field(self) -> str: ...
     @property
-    def disabled(self) -> bool: ...
+    def mode(self) -> str: ...
+    def filter(self, record: Any) -> bool: ...
+
+class LevelRangeFilter:
+    """Passes records whose level is between `min_level` and `max_level`, both
+    included. Loggers and logxide handlers run these filters in Rust."""
+
+    def __init__(
+        self, min_level: int | str | None = None, max_level: int | str | None = None
+    ) -> None: ...
     @property
-    def filters(self) -> list: ...
-    def setLevel(self, level: LogLevel) -> None: ...
-    def getEffectiveLevel(self) -> int: ...
-    def addHandler(self, handler: Any) -> None: ...
-    def removeHandler(self, handler: Any) -> None: ...
-    def addFilter(self, filter: Any) -> None: ...
-    def removeFilter(self, filter: Any) -> None: ...
-    def disable(self, level: LogLevel) -> None: ...
-    def setFormatter(self, formatter: Any) -> None: ...
-    def getChild(self, suffix: str) -> PyLogger: ...
-    def isEnabledFor(self, level: LogLevel) -> bool: ...
-    def debug(self, msg: str, *args: Any, **kwargs: Any) -> None: ...
-    def info(self, msg: str, *args: Any, **kwargs: Any) -> None: ...
-    def warning(self, msg: str, *args: Any, **kwargs: Any) -> None: ...
-    def error(self, msg: str, *args: Any, **kwargs: Any) -> None: ...
-    def critical(self, msg: str, *args: Any, **kwargs: Any) -> None: ...
-    def exception(self, msg: str, *args: Any, **kwargs: Any) -> None: ...
-    def log(self, level: LogLevel, msg: str, *args: Any, **kwargs: Any) -> None: ...
-    def fatal(self, msg: str, *args: Any, **kwargs: Any) -> None: ...
-    def warn(self, msg: str, *args: Any, **kwargs: Any) -> None: ...
-    def hasHandlers(self) -> bool: ...
-    def getChildren(self) -> list: ...
-    def makeRecord(
-        self,
-        name: str,
-        level: int,
-        fn: str,
-        lno: int,
-        msg: str,
-        args: tuple,
-        exc_info: Any,
-    ) -> LogRecord: ...
-    def handle(self, record: LogRecord) -> None: ...
-    def callHandlers(self, record: LogRecord) -> None: ...
-    def findCaller(self, stack_info: bool = False) -> tuple[str, int, str, Any]: ...
+    def min_level(self) -> int: ...
     @property
-    def root(self) -> PyLogger: ...
-    def filter(self, record: LogRecord) -> bool: ...
+    def max_level(self) -> int | None: ...
+    def filter(self, record: Any) -> bool: ...
 
-# Type alias for Logger
-Logger = PyLogger
+class DedupFilter:
+    """Collapses consecutive identical messages: repeats within `window_seconds` are
+    dropped and logged as one summary record with a `dedup_count` extra."""
 
-class LogRecord:
-    """Basic LogRecord implementation for compatibility"""
+    def __init__(self, window_seconds: float) -> None: ...
+    @property
+    def window_seconds(self) -> float: ...
+    def filter(self, record: Any) -> bool: ...
 
-    pass
+class Redactor:
+    """Replaces emails, card numbers, SSNs, bearer tokens and matches of `rules` in
+    record messages, arguments and extras. Global with `configure_redaction()`, or
+    per handler with `addFilter()`; logxide handlers run it in Rust."""
 
-class Filter:
-    """Basic Filter implementation for compatibility"""
+    def __init__(
+        self,
+        detectors: Sequence[str] | None = None,
+        rules: Sequence[str] = (),
+        replacement: str = "[REDACTED]",
+    ) -> None: ...
+    @property
+    def detectors(self) -> list[str]: ...
+    @property
+    def rules(self) -> list[str]: ...
+    @property
+    def replacement(self) -> str: ...
+    def redact(self, text: str) -> str: ...
+    def filter(self, record: Any) -> bool: ...
 
-    pass
+class FieldProjection:
+    """Keeps the extras and optional fields (`exc_info`, `stack_info`, `taskName`) in
+    `include`, minus those in `exclude`, on each record a handler writes; added with
+    `addFilter()`, logxide handlers apply it in Rust to their own copy."""
+
+    def __init__(
+        self, include: Sequence[str] | None = None, exclude: Sequence[str] = ()
+    ) -> None: ...
+    @property
+    def include(self) -> list[str] | None: ...
+    @property
+    def exclude(self) -> list[str]: ...
+    def filter(self, record: Any) -> bool: ...
 
 class LoggerAdapter:
     """Basic LoggerAdapter implementation for compatibility"""
@@ -125,24 +191,6 @@ class Handler:
     def terminator(self) -> str: ...
     def __call__(self, record: LogRecord) -> None: ...
 
-class StreamHandler(Handler):
-    """Stream handler class."""
-
-    def __init__(self, stream: IO[str] | None = None) -> None: ...
-    def emit(self, record: LogRecord) -> None: ...
-    def flush(self) -> None: ...
-
-class FileHandler(StreamHandler):
-    """File handler class."""
-
-    def __init__(
-        self,
-        filename: str,
-        mode: str = "a",
-        encoding: str | None = None,
-        delay: bool = False,
-    ) -> None: ...
-
 class LoggingManager:
     """Mock logging manager for compatibility."""
 
@@ -158,75 +206,62 @@ class _LoggingModule:
     @staticmethod
     def basicConfig(**kwargs: Any) -> None: ...
     @staticmethod
-    def flush() -> None: ...
-    @staticmethod
     def register_python_handler(handler: Callable) -> None: ...
-    @staticmethod
-    def addLevelName(level: LogLevel, levelName: str) -> None: ...
-    @staticmethod
-    def getLevelName(level: LogLevel) -> str: ...
-    @staticmethod
-    def disable(level: LogLevel) -> None: ...
-    @staticmethod
-    def getLoggerClass() -> type[PyLogger]: ...
-    @staticmethod
-    def setLoggerClass(klass: type[PyLogger]) -> None: ...
-    @staticmethod
-    def captureWarnings(capture: bool) -> None: ...
-    @staticmethod
-    def makeLogRecord(dict: dict) -> LogRecord: ...
-    @staticmethod
-    def getLogRecordFactory() -> type[LogRecord]: ...
-    @staticmethod
-    def setLogRecordFactory(factory: type[LogRecord]) -> None: ...
-    @staticmethod
-    def getLevelNamesMapping() -> dict[str, int]: ...
-    @staticmethod
-    def getHandlerByName(name: str) -> Any: ...
-    @staticmethod
-    def getHandlerNames() -> list: ...
-
-    # Level constants
-    DEBUG: int
-    INFO: int
-    WARNING: int
-    WARN: int
-    ERROR: int
-    CRITICAL: int
-    FATAL: int
-    NOTSET: int
-
-    # Classes
-    PyLogger: type[PyLogger]
-    Logger: type[PyLogger]
-    LogRecord: type[LogRecord]
-    Filter: type[Filter]
-    LoggerAdapter: type[LoggerAdapter]
-    NullHandler: type[NullHandler]
-    Formatter: type[Formatter]
-    Handler: type[Handler]
-    StreamHandler: type[StreamHandler]
-    LoggingManager: type[LoggingManager]
-
-    # Attributes
-    root: PyLogger
-    lastResort: NullHandler
-    raiseExceptions: bool
-    BASIC_FORMAT: str
-
-# The main logging module instance
-logging: _LoggingModule
-
-# Core functions
-def getLogger(name: str | None = None) -> PyLogger: ...
-def basicConfig(**kwargs: Any) -> None: ...
-def flush() -> None: ...
-def register_python_handler(handler: Callable) -> None: ...
+
+clear_handlers = _ext_logging.clear_handlers
+flush = _ext_logging.flush
+set_thread_name = _ext_logging.set_thread_name
+configure_tracebacks = _ext_logging.configure_tracebacks
+configure_exception_grouping = _ext_logging.configure_exception_grouping
+configure_message_templates = _ext_logging.configure_message_templates
+message_template = _ext_logging.message_template
+configure_extra_limits = _ext_logging.configure_extra_limits
+extra_limit_stats = _ext_logging.extra_limit_stats
+configure_redaction = _ext_logging.configure_redaction
+configure_secret_keys = _ext_logging.configure_secret_keys
+configure_run_id = _ext_logging.configure_run_id
+run_id = _ext_logging.run_id
+route = _ext_logging.route
+unroute = _ext_logging.unroute
+routes = _ext_logging.routes
+configure_threads = _ext_logging.configure_threads
+tune = _ext_logging.tune
+preset = _ext_logging.preset
+queue_stats = _ext_logging.queue_stats
+configure_workers = _ext_logging.configure_workers
+configure_console_dedup = _ext_logging.configure_console_dedup
+configure_caller_info = _ext_logging.configure_caller_info
+configure_crash_dump = _ext_logging.configure_crash_dump
+debug_dispatch = _ext_logging.debug_dispatch
+shutdown_workers = _ext_logging.shutdown_workers
+runtime_info = _ext_logging.runtime_info
+tail = _ext_logging.tail
+read_log_file = _ext_logging.read_log_file
+replay_log_file = _ext_logging.replay_log_file
+register_schema_migration = _ext_logging.register_schema_migration
+schema_migrations = _ext_logging.schema_migrations
+migrate_record = _ext_logging.migrate_record
+verify_log_file = _ext_logging.verify_log_file
+watch_config = _ext_logging.watch_config
+
+def configure_queue_diagnostics(
+    interval: float | None = None,
+    level: LogLevel = 20,
+) -> None: ...
+def capture_unhandled(enabled: bool = True) -> None: ...
+def redirect_stdio(
+    level: LogLevel = 20,
+    logger: str = "stdout",
+    stderr_level: LogLevel = 40,
+    stderr_logger: str = "stderr",
+    enabled: bool = True,
+) -> None: ...
+def reinitialize() -> int: ...
 
 # Compatibility functions
 def addLevelName(level: LogLevel, levelName: str) -> None: ...
 def getLevelName(level: LogLevel) -> str: ...
-def disable(level: LogLevel) -> None: ...
+def disable(level: LogLevel = ...) -> None: ...
 def getLoggerClass() -> type[PyLogger]: ...
 def setLoggerClass(klass: type[PyLogger]) -> None: ...
 def captureWarnings(capture: bool) -> None: ...
@@ -0,0 +1,251 @@
+"""
+LogXide Command Line
+
+``python -m logxide`` works on log files written by logxide or the stdlib, with the
+extension's own parsers and formatters:
+
+    python -m logxide convert app.log -o app.jsonl --format "%(asctime)s %(message)s"
+    python -m logxide merge web.log worker.log -o all.log
+    python -m logxide stats app.log app.log.1
+    python -m logxide convert app.log --since 2024-05-01T12:00 --until 2024-05-01T13:00
+    python -m logxide verify app.log app.log.2024-05-01.gz
+
+Text logs are read in ``--format`` (default: what ``basicConfig()`` writes), or with a
+``--pattern`` regex whose named groups are the fields; JSON lines and ``.parquet``
+files are recognized on their own. ``convert --schema-version N`` upgrades historical
+records through the migrations that the ``--migrations`` modules register with
+``logxide.register_schema_migration()``. ``--since`` and ``--until`` keep the records
+created in that range, reading only that part of files written with ``index_every=``.
+``verify`` checks the checksum trailers of
+files written with ``checksum_every=`` and exits with status 1 when a segment fails.
+"""
+
+import argparse
+import datetime
+import importlib
+import json
+import sys
+
+from . import logxide
+
+_ext = logxide.logging
+
+# The output format `convert` picks from the destination's suffix.
+_SUFFIXES = {
+    ".json": "json",
+    ".jsonl": "json",
+    ".ndjson": "json",
+    ".parquet": "parquet",
+}
+
+
+def _add_format_options(parser):
+    parser.add_argument(
+        "--format",
+        help='format string of text logs (default "%%(levelname)s:%%(name)s:'
+        '%%(message)s")',
+    )
+    parser.add_argument(
+        "--datefmt",
+        help='date format of %%(asctime)s (default "%%Y-%%m-%%d %%H:%%M:%%S")',
+    )
+    parser.add_argument(
+        "--pattern",
+        help="regex with named groups to read text logs with, instead of --format",
+    )
+
+
+def _when(value):
+    """A --since/--until value: an ISO 8601 time (local unless it has an offset) or
+    seconds since the epoch."""
+    try:
+        return float(value)
+    except ValueError:
+        pass
+    try:
+        return datetime.datetime.fromisoformat(value).timestamp()
+    except ValueError:
+        raise argparse.ArgumentTypeError(
+            f"not an ISO 8601 time or seconds since the epoch: {value!r}"
+        ) from None
+
+
+def _add_range_options(parser):
+    parser.add_argument(
+        "--since",
+        type=_when,
+        metavar="TIME",
+        help="keep records created at or after TIME (ISO 8601 or epoch seconds)",
+    )
+    parser.add_argument(
+        "--until",
+        type=_when,
+        metavar="TIME",
+        help="keep records created at or before TIME",
+    )
+
+
+def _parser():
+    parser = argparse.ArgumentParser(
+        prog="python -m logxide", description="Work with log files."
+    )
+    commands = parser.add_subparsers(dest="command", required=True)
+
+    convert = commands.add_parser(
+        "convert", help="convert a log file between text, JSON lines and parquet"
+    )
+    convert.add_argument("src", help="log file to read")
+    convert.add_argument("-o", "--output", help="file to write (default: stdout)")
+    convert.add_argument(
+        "--to",
+        choices=["text", "json", "parquet"],
+        help="output format (default: from the output's suffix, else json)",
+    )
+    convert.add_argument(
+        "--schema-version",
+        type=int,
+        help="migrate every record to this schema version",
+    )
+    convert.add_argument(
+        "--migrations",
+        action="append",
+        default=[],
+        metavar="MODULE",
+        help="module to import first, which registers schema migrations (repeatable)",
+    )
+    _add_format_options(convert)
+    _add_range_options(convert)
+
+    merge = commands.add_parser(
+        "merge", help="merge log files into one, ordered by timestamp"
+    )
+    merge.add_argument("files", nargs="+", help="log files to merge")
+    merge.add_argument("-o", "--output", help="file to write (default: stdout)")
+    _add_format_options(merge)
+    _add_range_options(merge)
+
+    stats = commands.add_parser("stats", help="count records per logger and level")
+    stats.add_argument("files", nargs="+", help="log files to count")
+    stats.add_argument("--json", action="store_true", help="print the counts as JSON")
+    _add_format_options(stats)
+    _add_range_options(stats)
+
+    verify = commands.add_parser(
+        "verify", help="check the checksum trailers of log files (checksum_every=)"
+    )
+    verify.add_argument("files", nargs="+", help="log files to check")
+    verify.add_argument(
+        "--strict",
+        action="store_true",
+        help="also fail when lines are not covered by a trailer",
+    )
+    verify.add_argument("--json", action="store_true", help="print the reports as JSON")
+    return parser
+
+
+def _timestamp(created):
+    if created is None:
+        return "-"
+    return datetime.datetime.fromtimestamp(created).isoformat(" ", "seconds")
+
+
+def _print_stats(stats, out):
+    out.write(
+        f"{stats['records']} records, {_timestamp(stats['first'])} to "
+        f"{_timestamp(stats['last'])}\n"
+    )
+    levels = list(stats["levels"])
+    rows = [
+        [name or "(unnamed)", *(str(counts.get(level, 0)) for level in levels)]
+        + [str(sum(counts.values()))]
+        for name, counts in sorted(
+            stats["loggers"].items(), key=lambda item: -sum(item[1].values())
+        )
+    ]
+    header = ["logger", *levels, "total"]
+    widths = [max(len(row[i]) for row in [header, *rows]) for i in range(len(header))]
+    for row in [header, *rows]:
+        cells = [row[0].ljust(widths[0])]
+        cells += [cell.rjust(width) for cell, width in zip(row[1:], widths[1:])]
+        out.write("  ".join(cells).rstrip() + "\n")
+
+
+def _lines(first, last):
+    return f"line {first}" if first == last else f"lines {first}-{last}"
+
+
+def _print_verify(path, report, out):
+    summary = f"{report['segments']} segments, {report['records']} records verified"
+    if report["unverified"]:
+        unverified = ", ".join(_lines(*lines) for lines in report["unverified"])
+        summary += f"; not covered: {unverified}"
+    out.write(f"{path}: {'ok' if report['ok'] else 'CORRUPT'} ({summary})\n")
+    for corrupt in report["corrupt"]:
+        lines = _lines(corrupt["first_line"], corrupt["trailer_line"])
+        out.write(f"  {lines}: {corrupt['reason']}\n")
+
+
+def _verify(args, parser):
+    if not hasattr(_ext, "verify_log_file"):
+        parser.exit(1, f"{parser.prog}: this build has no 'file' feature\n")
+    reports = {}
+    try:
+        for path in args.files:
+            reports[path] = _ext.verify_log_file(path)
+    except (OSError, NotImplementedError) as e:
+        parser.exit(1, f"{parser.prog}: error: {e}\n")
+    if args.json:
+        sys.stdout.write(json.dumps(reports, indent=2) + "\n")
+    else:
+        for path, report in reports.items():
+            _print_verify(path, report, sys.stdout)
+    failed = any(
+        not report["ok"] or (args.strict and report["unverified"])
+        for report in reports.values()
+    )
+    return 1 if failed else 0
+
+
+def main(argv=None):
+    parser = _parser()
+    args = parser.parse_args(argv)
+    if args.command == "verify":
+        return _verify(args, parser)
+    if not hasattr(_ext, "convert_log_file"):
+        parser.exit(1, f"{parser.prog}: this build has no 'json' feature\n")
+    options = {
+        "format": args.format,
+        "datefmt": args.datefmt,
+        "pattern": args.pattern,
+        "since": args.since,
+        "until": args.until,
+    }
+    try:
+        if args.command == "convert":
+            to = args.to
+            if to is None:
+                suffix = "." + (args.output or "").rpartition(".")[2]
+                to = _SUFFIXES.get(suffix.lower(), "json")
+            for module in args.migrations:
+                importlib.import_module(module)
+            text = _ext.convert_log_file(
+                args.src, args.output, to, schema_version=args.schema_version, **options
+            )
+        elif args.command == "merge":
+            text = _ext.merge_log_files(args.files, args.output, **options)
+        else:
+            stats = _ext.log_file_stats(args.files, **options)
+            if args.json:
+                text = json.dumps(stats, indent=2) + "\n"
+            else:
+                _print_stats(stats, sys.stdout)
+                return 0
+    except (OSError, ValueError, NotImplementedError, ImportError) as e:
+        parser.exit(1, f"{parser.prog}: error: {e}\n")
+    if text is not None:
+        sys.stdout.write(text)
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -5,62 +5,83 @@ This module provides utility functions that maintain compatibility with
 Python's standard logging module.
 """
 
+import logging as _logging
+import os as _os
 import sys as _sys
 import warnings
 
 _getframe = _sys._getframe
 
+# Frames in logxide itself or in the stdlib's logging module are wrappers around the
+# call, never the caller (the stdlib's findCaller skips its own source file alike).
+_PACKAGE_DIR = _os.path.dirname(__file__) + _os.sep
+_LOGGING_SRCFILE = getattr(_logging, "_std_logging", _logging).__file__
 
-def _get_caller_info():
+
+def _caller_frame(stacklevel):
+    """The stacklevel-th frame outside logging.
+
+    Like the stdlib, a stacklevel deeper than the stack stops at the outermost frame.
+    """
     f = _getframe(1)
+    while True:
+        filename = f.f_code.co_filename
+        if filename != _LOGGING_SRCFILE and not filename.startswith(_PACKAGE_DIR):
+            stacklevel -= 1
+            if stacklevel <= 0:
+                return f
+        if f.f_back is None:
+            return f
+        f = f.f_back
+
+
+def _get_caller_info(stacklevel=1):
+    """(filename, funcName, lineno) of the frame `stacklevel` picks."""
+    f = _caller_frame(stacklevel)
     co = f.f_code
     return (co.co_filename, co.co_name, f.f_lineno)
 
 
-# Global level name registry
-_levelToName = {
-    50: "CRITICAL",
-    40: "ERROR",
-    30: "WARNING",
-    20: "INFO",
-    10: "DEBUG",
-    0: "NOTSET",
-}
-_nameToLevel = {
-    "CRITICAL": 50,
-    "FATAL": 50,
-    "ERROR": 40,
-    "WARN": 30,
-    "WARNING": 30,
-    "INFO": 20,
-    "DEBUG": 10,
-    "NOTSET": 0,
-}
+def _get_stack_info(stacklevel=1):
+    """The stack up to the frame `stacklevel` picks, as the stdlib's stack_info."""
+    import traceback
+
+    lines = traceback.format_stack(_caller_frame(stacklevel))
+    return ("Stack (most recent call last):\n" + "".join(lines)).rstrip("\n")
 
 
 def addLevelName(level, levelName):
-    """Add a level name - compatibility function"""
-    global _levelToName, _nameToLevel
-    _levelToName[level] = levelName
-    _nameToLevel[levelName.upper()] = level
+    """Name a level - compatibility function"""
+    import logging as std_logging
+
+    from . import logxide
+
+    std_logging = getattr(std_logging, "_std_logging", std_logging)
+    logxide.logging.addLevelName(level, levelName)
+    # Records created by the stdlib take their levelname from its own table.
+    getattr(std_logging, "_original_addLevelName", std_logging.addLevelName)(
+        level, levelName
+    )
 
 
 def getLevelName(level):
     """Get level name - compatibility function"""
-    global _levelToName, _nameToLevel
+    from . import logxide
 
-    # If it's a string, return the corresponding level number
-    if isinstance(level, str):
-        return _nameToLevel.get(level.upper(), f"Level {level}")
+    return logxide.logging.getLevelName(level)
 
-    # If it's a number, return the corresponding level name
-    return _levelToName.get(level, f"Level {level}")
 
+def disable(level=50):
+    """Drop records at or below `level` on every logger - compatibility function"""
+    import logging as std_logging
 
-def disable(level):
-    """Disable logging below the specified level - compatibility function"""
-    # For compatibility - not fully implemented
-    pass
+    from . import logxide
+
+    std_logging = getattr(std_logging, "_std_logging", std_logging)
+    level = std_logging._checkLevel(level)
+    logxide.logging.disable(level)
+    # The stdlib loggers check their manager's threshold in isEnabledFor().
+    getattr(std_logging, "_original_disable", std_logging.disable)(level)
 
 
 def getLoggerClass():
@@ -180,8 +201,9 @@ def getLevelNamesMapping():
     Returns:
         dict: A dictionary mapping level names to level numbers
     """
-    global _nameToLevel
-    return _nameToLevel.copy()
+    from . import logxide
+
+    return logxide.logging.getLevelNamesMapping()
 
 
 # Global handler registry
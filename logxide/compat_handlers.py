@@ -13,6 +13,8 @@ import sys
 import time
 import traceback
 
+from .compat_functions import getLevelName
+
 NOTSET = 0
 DEBUG = 10
 INFO = 20
@@ -136,11 +138,24 @@ class NullHandler:
 
 
 class Formatter:
-    def __init__(self, fmt=None, datefmt=None, style="%", validate=True, **kwargs):
-        self.fmt = fmt if fmt else "%(message)s"
+    def __init__(
+        self,
+        fmt=None,
+        datefmt=None,
+        style="%",
+        validate=True,
+        *,
+        defaults=None,
+        **kwargs,
+    ):
+        if style not in _STYLES:
+            raise ValueError(f"Style must be one of: {','.join(_STYLES)}")
+        self.fmt = fmt if fmt else _STYLES[style][0].default_format
         self.datefmt = datefmt
         self.style = style
         self.validate = validate
+        # Values for fields the record lacks, as in logging.Formatter (3.10+).
+        self._defaults = defaults
         self._kwargs = kwargs
         if isinstance(self.fmt, str):
             try:
@@ -157,6 +172,8 @@ class Formatter:
             record_dict = record.__dict__.copy()
         else:
             record_dict = {}
+        if self._defaults:
+            record_dict = {**self._defaults, **record_dict}
 
         if "message" not in record_dict or not record_dict["message"]:
             if hasattr(record, "getMessage"):
@@ -166,12 +183,15 @@ class Formatter:
             else:
                 record_dict["message"] = getattr(record, "msg", str(record))
 
-        if "asctime" not in record_dict and "%(asctime)" in self.fmt:
+        if "asctime" not in record_dict and "asctime" in self.fmt:
             record_dict["asctime"] = self.formatTime(record, self.datefmt)
 
         try:
-            s = self.fmt % record_dict
-            return s
+            if self.style == "{":
+                return self.fmt.format(**record_dict)
+            if self.style == "$":
+                return string.Template(self.fmt).substitute(record_dict)
+            return self.fmt % record_dict
         except (KeyError, ValueError, TypeError):
             return record_dict.get("message", str(record))
 
@@ -192,6 +212,12 @@ class Formatter:
             ct = getattr(record, "created", time.time())
 
         if datefmt:
+            # %f (microseconds) as the Rust formatters render it; time.strftime
+            # has no such directive.
+            micros = f"{round((ct - int(ct)) * 1_000_000) % 1_000_000:06d}"
+            datefmt = re.sub(
+                r"%[%f]", lambda m: micros if m.group() == "%f" else "%%", datefmt
+            )
             s = time.strftime(datefmt, time.localtime(ct))
         else:
             t = time.localtime(ct)
@@ -321,10 +347,6 @@ class Handler:
         return f"<{self.__class__.__name__} ({level})>"
 
 
-def getLevelName(level):
-    return _level_to_name.get(level, f"Level {level}")
-
-
 class StreamHandler(Handler):
     def __init__(self, stream=None):
         super().__init__()
@@ -454,10 +476,7 @@ class Filter:
     def filter(self, record):
         if self.nlen == 0:
             return True
-        if isinstance(record, dict):
-            record_name = record.get("name", "")
-        else:
-            record_name = getattr(record, "name", "")
+        record_name = getattr(record, "name", "")
         return bool(self.name == record_name or record_name.startswith(self.name + "."))
 
 
@@ -477,7 +496,7 @@ class LogRecord:
     ):
         self.name = name
         self.levelno = level
-        self.levelname = _level_to_name.get(level, f"Level {level}")
+        self.levelname = getLevelName(level)
         self.pathname = pathname
         self.filename = pathname.rsplit("/", 1)[-1] if pathname else ""
         self.module = self.filename.rsplit(".", 1)[0] if self.filename else ""
@@ -527,16 +546,8 @@ class LogRecord:
         )
 
 
-_start_time = time.time()
-
-_level_to_name = {
-    CRITICAL: "CRITICAL",
-    ERROR: "ERROR",
-    WARNING: "WARNING",
-    INFO: "INFO",
-    DEBUG: "DEBUG",
-    NOTSET: "NOTSET",
-}
+# relativeCreated counts from the stdlib logging import, as the native records do.
+_start_time = getattr(sys.modules.get("logging"), "_startTime", time.time())
 
 
 class LoggerAdapter:
@@ -591,6 +602,10 @@ class LoggerAdapter:
             msg, kwargs = self.process(msg, kwargs)
             self.logger.log(level, msg, *args, **kwargs)
 
+    def bind(self, **context):
+        """An adapter with the same extra over ``self.logger.bind(**context)``."""
+        return self.__class__(self.logger.bind(**context), self.extra, self.merge_extra)
+
     def isEnabledFor(self, level):
         return self.logger.isEnabledFor(level)
 
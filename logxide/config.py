@@ -4,17 +4,20 @@ Provides a drop-in replacement for `logging.config.dictConfig` that transparentl
 promotes standard library handlers into Zero-GIL LogXide native handlers.
 """
 
-import copy
-import logging.config
+from . import logxide
 
 # Map standard python handlers to their high-performance LogXide equivalents
 HANDLER_MAP = {
     "logging.FileHandler": "logxide.handlers.FileHandler",
     "logging.StreamHandler": "logxide.handlers.StreamHandler",
     "logging.handlers.RotatingFileHandler": "logxide.handlers.RotatingFileHandler",
+    "logging.handlers.WatchedFileHandler": "logxide.handlers.WatchedFileHandler",
     "logging.handlers.TimedRotatingFileHandler": (
         "logxide.handlers.TimedRotatingFileHandler"
     ),
+    "logging.handlers.SysLogHandler": "logxide.handlers.SyslogHandler",
+    "logging.handlers.SocketHandler": "logxide.handlers.SocketHandler",
+    "logging.handlers.SMTPHandler": "logxide.handlers.SMTPHandler",
 }
 
 
@@ -22,23 +25,26 @@ def dictConfig(config):
     """
     Configure logging using a dictionary.
 
-    This acts as a transparent proxy to `logging.config.dictConfig`. When it parses
-    the dictionary, it replaces standard Python `logging.FileHandler`, `StreamHandler`,
-    and `RotatingFileHandler` classes with LogXide's high-performance
-    native equivalents.
-    Custom formatters and configurations are natively passed down through PyO3 bindings.
+    Follows `logging.config.dictConfig` semantics, implemented in Rust so each
+    configured logger's native dispatch (handlers, level, propagate, disabled) is set
+    along with the stdlib logger. Standard Python `logging.FileHandler`,
+    `StreamHandler`, `RotatingFileHandler` and friends are replaced with LogXide's
+    high-performance native equivalents.
 
     Args:
         config (dict): A dictionary mapping configuration keys to values, matching
                        the standard Python `logging.config.dictConfig` schema.
     """
-    # Create a deep copy to prevent mutating the user's operational dictionary
-    cfg = copy.deepcopy(config)
+    # Copy only what gets rewritten: values such as streams passed by reference (or
+    # via cfg://) must reach the handlers as the same objects, as with the stdlib.
+    cfg = dict(config)
 
     if "handlers" in cfg and isinstance(cfg["handlers"], dict):
-        for _name, handler_config in cfg["handlers"].items():
+        cfg["handlers"] = dict(cfg["handlers"])
+        for name, handler_config in cfg["handlers"].items():
             if not isinstance(handler_config, dict):
                 continue
+            handler_config = cfg["handlers"][name] = dict(handler_config)
 
             class_name = handler_config.get("class")
 
@@ -50,14 +56,23 @@ def dictConfig(config):
                 handler_config["class"] = "logxide.handlers.StreamHandler"
             elif class_name == "logxide.RotatingFileHandler":
                 handler_config["class"] = "logxide.handlers.RotatingFileHandler"
+            elif class_name == "logxide.WatchedFileHandler":
+                handler_config["class"] = "logxide.handlers.WatchedFileHandler"
+            elif class_name == "logxide.LevelRoutedFileHandler":
+                handler_config["class"] = "logxide.handlers.LevelRoutedFileHandler"
             elif class_name == "logxide.HTTPHandler":
                 handler_config["class"] = "logxide.handlers.HTTPHandler"
             elif class_name == "logxide.OTLPHandler":
                 handler_config["class"] = "logxide.handlers.OTLPHandler"
+            elif class_name == "logxide.SyslogHandler":
+                handler_config["class"] = "logxide.handlers.SyslogHandler"
+            elif class_name == "logxide.SocketHandler":
+                handler_config["class"] = "logxide.handlers.SocketHandler"
+            elif class_name == "logxide.SMTPHandler":
+                handler_config["class"] = "logxide.handlers.SMTPHandler"
 
             # If it's a standard handler we support, seamlessly promote it
             elif class_name in HANDLER_MAP:
                 handler_config["class"] = HANDLER_MAP[class_name]
 
-    # Hand off the mutated config to the robust standard Python configuration parser
-    logging.config.dictConfig(cfg)
+    logxide.logging.dictConfig(cfg)
@@ -0,0 +1,99 @@
+"""
+LogXide Diagnostics Module
+
+Periodically logs the queue counters of every queued handler (StreamHandler,
+HTTPHandler, OTLPHandler) on the internal ``logxide.diagnostics`` logger, so
+queue pressure shows up in the same pipeline as the application's own logs. Extras
+cut by ``configure_extra_limits()`` are reported alongside.
+"""
+
+import threading
+
+from . import logger_wrapper, logxide
+
+DIAGNOSTICS_LOGGER = "logxide.diagnostics"
+
+# The reporter thread outlives a re-import of the package, so it is tracked in the
+# extension's process-wide state rather than in this module.
+_state = logxide.logging.process_state().setdefault(
+    "diagnostics", {"lock": threading.Lock(), "worker": None}
+)
+
+
+def _report(level):
+    logger = logger_wrapper.getLogger(DIAGNOSTICS_LOGGER)
+    for stats in logxide.logging.queue_stats():
+        logger.log(
+            level,
+            "%s queue: depth=%d/%d max_depth=%d enqueued=%d dequeued=%d dropped=%d",
+            stats["kind"],
+            stats["queue_depth"],
+            stats["queue_capacity"],
+            stats["max_queue_depth"],
+            stats["enqueued"],
+            stats["dequeued"],
+            stats["queue_dropped"],
+            extra={"queue_stats": stats},
+        )
+    limits = logxide.logging.extra_limit_stats()
+    if limits["records_limited"]:
+        logger.log(
+            level,
+            "extra limits: records_limited=%d keys_dropped=%d values_truncated=%d",
+            limits["records_limited"],
+            limits["keys_dropped"],
+            limits["values_truncated"],
+            extra={"extra_limit_stats": limits},
+        )
+
+
+def configure_queue_diagnostics(interval=None, level=20):
+    """
+    Start (or stop) periodic queue diagnostics.
+
+    Args:
+        interval: Seconds between reports. None stops the reporter.
+        level: Level the reports are logged at (default INFO).
+    """
+    if interval is not None and interval <= 0:
+        raise ValueError("interval must be a positive number of seconds or None")
+    if interval is not None and not logxide.logging.runtime_info()["threads"]:
+        raise RuntimeError(
+            "queue diagnostics need a background thread, which this platform lacks; "
+            "call logxide.queue_stats() directly instead"
+        )
+
+    with _state["lock"]:
+        if _state["worker"] is not None:
+            thread, stop = _state["worker"]
+            stop.set()
+            if thread is not threading.current_thread():
+                thread.join()
+            _state["worker"] = None
+
+        if interval is None:
+            return
+
+        stop = threading.Event()
+        generation = logxide.logging.generation()
+
+        def run():
+            # A reinitialize() retires the reporter even if nothing stops it.
+            while not stop.wait(interval):
+                if logxide.logging.generation() != generation:
+                    return
+                _report(level)
+
+        thread = threading.Thread(target=run, name="logxide-diagnostics", daemon=True)
+        _state["worker"] = (thread, stop)
+        _state["settings"] = (interval, level)
+        thread.start()
+
+
+def _restart_after_fork():
+    """Start the reporter again in a forked child, where its thread is gone."""
+    # The lock may have been held by a thread that did not survive the fork.
+    _state["lock"] = threading.Lock()
+    if _state["worker"] is not None:
+        _state["worker"] = None
+        configure_queue_diagnostics(*_state["settings"])
@@ -5,51 +5,181 @@ Compatibility handlers for LogXide.
 import contextlib
 import logging
 import logging.handlers
+import shlex
+import socket
 import sys
+import weakref
 
 from . import logxide
 
 
+def _native(namespace, name, feature):
+    """Look up a native class/function, naming the cargo feature it needs if absent."""
+    obj = getattr(namespace, name, None)
+    if obj is None:
+        raise NotImplementedError(
+            f"{name} is not available: logxide was built without the "
+            f"'{feature}' feature"
+        )
+    return obj
+
+
+def _record_transform_spec(spec):
+    """Accept a record_transform mapping, or YAML text describing one."""
+    if not isinstance(spec, str):
+        return spec
+    try:
+        import yaml
+    except ImportError:
+        raise ImportError(
+            "record_transform given as a YAML string needs PyYAML "
+            "(pip install pyyaml); pass a dict instead"
+        ) from None
+    return yaml.safe_load(spec)
+
+
+# Formatter per handler kind set by set_default_formatter(), and the handlers that
+# take it while they have no formatter of their own.
+_default_formatters = {"file": None, "stream": None}
+_default_formatter_kinds = {
+    "file": ("file",),
+    "stream": ("stream",),
+    "all": ("file", "stream"),
+}
+_default_formatter_users = weakref.WeakSet()
+
+
+def _adopt_default_formatter(handler, kind):
+    """Give a new handler the default formatter of its kind, if one is set."""
+    handler._formatter_kind = kind
+    handler._default_formatter = None
+    _default_formatter_users.add(handler)
+    fmt = _default_formatters[kind]
+    if fmt is not None and handler.formatter is None:
+        handler._default_formatter = fmt
+        handler.setFormatter(fmt)
+
+
+def set_default_formatter(formatter, handler_type="all"):
+    """
+    Format the records of handlers that have no formatter of their own.
+
+    Applies to the file handlers (FileHandler, WatchedFileHandler,
+    RotatingFileHandler, TimedRotatingFileHandler, LevelRoutedFileHandler) or
+    StreamHandlers created afterwards, and to existing ones still without a
+    formatter or using the previous default. A later setFormatter() on a handler
+    takes precedence.
+
+    Args:
+        formatter: A logxide or logging Formatter, or None to remove the default.
+        handler_type: "file", "stream" or "all" (both).
+    """
+    kinds = _default_formatter_kinds.get(handler_type)
+    if kinds is None:
+        raise ValueError(
+            f"handler_type must be 'file', 'stream' or 'all', not {handler_type!r}"
+        )
+    for kind in kinds:
+        _default_formatters[kind] = formatter
+    for handler in list(_default_formatter_users):
+        if handler._formatter_kind not in kinds:
+            continue
+        if handler.formatter is None or handler.formatter is handler._default_formatter:
+            handler._default_formatter = formatter
+            if handler.formatter is not formatter:
+                handler.setFormatter(formatter)
+
+
+def _reset_default_formatters():
+    """Forget set_default_formatter() (`logxide.reinitialize()`)."""
+    for kind in _default_formatters:
+        _default_formatters[kind] = None
+
+
+def _native_formatter_types():
+    """The Rust formatter classes a handler can use without calling into Python."""
+    return tuple(
+        cls
+        for cls in (
+            getattr(logxide, "Formatter", None),
+            getattr(logxide, "ColorFormatter", None),
+            getattr(logxide, "ColumnFormatter", None),
+            getattr(logxide, "JsonFormatter", None),
+        )
+        if cls is not None
+    )
+
+
+def _format_natively(fmt, record):
+    """Format a logging.LogRecord with a Rust formatter object."""
+    # Rust formatters take the native record, not a logging.LogRecord.
+    rust_record = _prepare_record_for_rust(record, native=True)
+    rust_record.exc_text = rust_record.exc_info
+    return fmt.format(rust_record)
+
+
 def _translatable(fmt):
     """Decide whether a Formatter can be rendered by the native Rust formatter.
 
-    Returns (ok, fmt_str, datefmt). ok is False for Formatter subclasses that
-    override format(), and for non-%(...) styles ({ or $), which fall back to
-    Python dispatch. Accepts BOTH stdlib logging.Formatter/PercentStyle and
+    Returns (ok, fmt_str, datefmt, style, defaults), style being "%", "{" or "$" and
+    defaults the Formatter's `defaults` dict or None. ok is False
+    for Formatter subclasses that override format(), for style classes other than
+    the stdlib's three, and for "{" or "$" format strings the native parser
+    rejects (format specs such as {msecs:03.0f}), which fall back to Python
+    dispatch. Accepts BOTH stdlib logging.Formatter/PercentStyle and
     logxide.compat_handlers variants by identity.
     """
     if fmt is None:
-        return (True, None, None)
+        return (True, None, None, "%", None)
 
     from . import compat_handlers as _compat
 
     allowed_format: set[object] = {_compat.Formatter.format}
-    allowed_style: set[object] = {_compat.PercentStyle}
+    style_chars: dict[object, str] = {
+        _compat.PercentStyle: "%",
+        _compat.StrFormatStyle: "{",
+        _compat.StringTemplateStyle: "$",
+    }
     with contextlib.suppress(AttributeError):
         allowed_format.add(logging.Formatter.format)
     with contextlib.suppress(AttributeError):
-        allowed_style.add(logging.PercentStyle)
+        style_chars[logging.PercentStyle] = "%"
+        style_chars[logging.StrFormatStyle] = "{"
+        style_chars[logging.StringTemplateStyle] = "$"
 
     if type(fmt).format not in allowed_format:
-        return (False, None, None)
+        return (False, None, None, "%", None)
 
     style = getattr(fmt, "_style", None)
     if style is not None:
         # stdlib-shaped Formatter: _style is a PercentStyle/StrFormatStyle/... object.
-        if type(style) not in allowed_style:
-            return (False, None, None)
+        if type(style) not in style_chars:
+            return (False, None, None, "%", None)
+        style_char = style_chars[type(style)]
+        defaults = getattr(style, "_defaults", None)
         fmt_str = (
-            getattr(fmt, "_fmt", None) or getattr(style, "_fmt", None) or "%(message)s"
+            getattr(fmt, "_fmt", None)
+            or getattr(style, "_fmt", None)
+            or type(style).default_format
         )
     else:
         # compat_handlers.Formatter: .style is the "%"/"{"/"$" character.
-        if getattr(fmt, "style", "%") != "%":
-            return (False, None, None)
+        style_char = getattr(fmt, "style", "%")
+        if style_char not in _compat._STYLES:
+            return (False, None, None, "%", None)
+        defaults = getattr(fmt, "_defaults", None)
         fmt_str = (
-            getattr(fmt, "fmt", None) or getattr(fmt, "_fmt", None) or "%(message)s"
+            getattr(fmt, "fmt", None)
+            or getattr(fmt, "_fmt", None)
+            or _compat._STYLES[style_char][0].default_format
         )
 
-    return (True, fmt_str, getattr(fmt, "datefmt", None))
+    if style_char != "%":
+        try:
+            logxide.logging.Formatter(fmt_str, style=style_char)
+        except ValueError:
+            return (False, None, None, "%", None)
+    return (True, fmt_str, getattr(fmt, "datefmt", None), style_char, defaults)
 
 
 def _prepare_record_for_rust(record, native=False):
@@ -63,16 +193,9 @@ def _prepare_record_for_rust(record, native=False):
         and isinstance(record.exc_info, tuple)
         and len(record.exc_info) == 3
     ):
-        import io
-        import traceback
-
-        sio = io.StringIO()
-        traceback.print_exception(
-            record.exc_info[0], record.exc_info[1], record.exc_info[2], None, sio
-        )
-        exc_info_str = sio.getvalue()
-        sio.close()
-        if exc_info_str.endswith("\n"):
+        # Rendered by Rust so configure_tracebacks() options apply here too.
+        exc_info_str = logxide.logging.format_exception(record.exc_info) or None
+        if exc_info_str and exc_info_str.endswith("\n"):
             exc_info_str = exc_info_str[:-1]
 
     func_name = getattr(record, "funcName", "") or getattr(record, "func_name", "")
@@ -104,6 +227,9 @@ def _prepare_record_for_rust(record, native=False):
     rust_record.thread_name = getattr(record, "threadName", "")
     rust_record.process = getattr(record, "process", 0)
     rust_record.process_name = getattr(record, "processName", "")
+    rust_record.ppid = getattr(record, "ppid", 0)
+    rust_record.thread_native_id = getattr(record, "thread_native_id", 0)
+    rust_record.seq = getattr(record, "seq", 0)
     rust_record.levelname = getattr(record, "levelname", "")
 
     # Extract extra attributes to the Rust LogRecord's extra dictionary
@@ -127,6 +253,9 @@ def _prepare_record_for_rust(record, native=False):
         "process",
         "processName",
         "process_name",
+        "ppid",
+        "thread_native_id",
+        "seq",
         "msg",
         "message",
         "args",
@@ -134,6 +263,10 @@ def _prepare_record_for_rust(record, native=False):
         "exc_text",
         "stack_info",
         "task_name",
+        # Set by logxide's own LogRecord: `sinfo` mirrors stack_info and `extra` is
+        # the raw extra= argument, not an attribute a user set.
+        "sinfo",
+        "extra",
     }
     for key, value in record.__dict__.items():
         if key not in standard_fields:
@@ -143,9 +276,35 @@ def _prepare_record_for_rust(record, native=False):
 
 
 class FileHandler(logging.FileHandler):
-    def __init__(self, filename, mode="a", encoding=None, delay=False, errors=None):
+    """
+    Rust-native drop-in for ``logging.FileHandler``.
+
+    ``checksum_every=N`` writes a Blake3 checksum trailer line after every N records
+    and on flush and close, which ``logxide.verify_log_file()`` checks later.
+    ``index_every=N`` adds the time and offset of every Nth record to a sidecar index,
+    ``<filename>.idx``, so reading a time range with ``since``/``until`` seeks to it.
+    """
+
+    # Reopen the file when it is moved or deleted (see WatchedFileHandler).
+    _watch = False
+
+    def __init__(
+        self,
+        filename,
+        mode="a",
+        encoding=None,
+        delay=False,
+        errors=None,
+        checksum_every=None,
+        index_every=None,
+    ):
         # Initialize inner handler first (before parent creates file handle)
-        self._inner = logxide.FileHandler(filename)
+        self._inner = _native(logxide, "FileHandler", "file")(
+            filename,
+            watch=self._watch,
+            checksum_every=checksum_every,
+            index_every=index_every,
+        )
         self._native = True
         super().__init__(filename, mode, encoding, delay, errors)
         # Close parent's file handle since we use Rust handler
@@ -153,16 +312,31 @@ class FileHandler(logging.FileHandler):
             self.stream.close()
             self.stream = None
         self._recompute_native()
+        _adopt_default_formatter(self, "file")
 
     def _recompute_native(self):
-        ok, fmt_str, datefmt = _translatable(self.formatter)
-        if ok and not self.filters:
-            self._inner.setFormatterSpec(fmt_str, datefmt)
+        native_fmt = isinstance(self.formatter, _native_formatter_types())
+        ok, fmt_str, datefmt, style, defaults = (
+            (True, None, None, "%", None)
+            if native_fmt
+            else _translatable(self.formatter)
+        )
+        if ok and self._inner.setFilters(self.filters):
+            if native_fmt:
+                self._inner.setFormatter(self.formatter)
+            else:
+                self._inner.setFormatterSpec(fmt_str, datefmt, style, defaults)
             self._native = True
         else:
+            self._inner.setFilters([])
             self._inner.setPythonDispatch()
             self._native = False
 
+    def format(self, record):
+        if isinstance(self.formatter, _native_formatter_types()):
+            return _format_natively(self.formatter, record)
+        return super().format(record)
+
     def setLevel(self, level):
         super().setLevel(level)
         self._inner.setLevel(level)
@@ -179,6 +353,17 @@ class FileHandler(logging.FileHandler):
         super().removeFilter(filter)
         self._recompute_native()
 
+    def setSanitize(self, policy=None, newlines=True):
+        """
+        Escape or strip control characters in the formatted text before it is written.
+
+        Args:
+            policy: "escape" (``\\x1b``), "strip", or None to write text unchanged
+            newlines: False to treat newlines and tabs as control characters too,
+                keeping each record on one line
+        """
+        self._inner.setSanitize(policy, newlines)
+
     def emit(self, record):
         try:
             if self._native:
@@ -214,24 +399,111 @@ class FileHandler(logging.FileHandler):
         """Flush the handler."""
         self._inner.flush()
 
+    def close(self):
+        # The stdlib only flushes while it has a stream open, and this one never does.
+        self.flush()
+        super().close()
+
+
+class WatchedFileHandler(FileHandler, logging.handlers.WatchedFileHandler):
+    """
+    A FileHandler for files rotated by an external tool such as logrotate.
+
+    Before each write the native handler compares the device and inode of the file at
+    `filename` with the file it has open, and reopens `filename` if it was moved or
+    deleted, like `logging.handlers.WatchedFileHandler`. A plain FileHandler would keep
+    writing to the rotated file. Windows has no inode numbers, so there it behaves like
+    FileHandler.
+    """
+
+    _watch = True
+
 
 class StreamHandler(logging.StreamHandler):
-    def __init__(self, stream=None):
+    """
+    Stream handler writing to stdout or stderr from a background thread.
+
+    Args:
+        stream: sys.stdout, or anything else for stderr (default)
+        formatter_tty: Formatter used while the stream is a terminal, e.g. a
+            ColorFormatter (default: None, always use the regular formatter)
+        formatter_plain: Formatter used otherwise, e.g. when redirected to a
+            file or pipe. Same as calling setFormatter() (default: None)
+        overflow: Policy when the writer thread falls behind: "drop_newest",
+            "drop_oldest" or "block" (default: None, the logxide.preset()
+            overflow, else "drop_newest")
+        block_timeout: Seconds "block" waits for room before dropping the
+            record (default: None, no limit)
+    """
+
+    def __init__(
+        self,
+        stream=None,
+        formatter_tty=None,
+        formatter_plain=None,
+        overflow=None,
+        block_timeout=None,
+    ):
         target = "stdout" if stream is sys.stdout else "stderr"
-        self._inner = logxide.StreamHandler(target)
+        self._inner = logxide.StreamHandler(target, overflow, block_timeout)
         self._native = True
+        self._formatter_tty = formatter_tty
         super().__init__(stream)
+        if formatter_plain is not None:
+            self.formatter = formatter_plain
         self._recompute_native()
+        _adopt_default_formatter(self, "stream")
 
     def _recompute_native(self):
-        ok, fmt_str, datefmt = _translatable(self.formatter)
-        if ok and not self.filters:
-            self._inner.setFormatterSpec(fmt_str, datefmt)
+        native_fmt = isinstance(self.formatter, _native_formatter_types())
+        ok, fmt_str, datefmt, style, defaults = (
+            (True, None, None, "%", None)
+            if native_fmt
+            else _translatable(self.formatter)
+        )
+        if ok and self._inner.setFilters(self.filters) and self._native_tty_formatter():
+            if native_fmt:
+                self._inner.setFormatter(self.formatter)
+            else:
+                self._inner.setFormatterSpec(fmt_str, datefmt, style, defaults)
             self._native = True
         else:
+            self._inner.setFilters([])
             self._inner.setPythonDispatch()
             self._native = False
 
+    def _native_tty_formatter(self):
+        """Hand formatter_tty to the native handler; False if it can't take it."""
+        fmt = self._formatter_tty
+        if fmt is None:
+            self._inner.setTtyFormatter(None)
+            return True
+        if isinstance(fmt, _native_formatter_types()):
+            self._inner.setTtyFormatter(fmt)
+            return True
+        ok, fmt_str, datefmt, style, defaults = _translatable(fmt)
+        if ok:
+            self._inner.setTtyFormatterSpec(fmt_str, datefmt, style, defaults)
+        return ok
+
+    def setTtyFormatter(self, fmt):
+        """
+        Set the formatter used while the stream is a terminal (None = always use
+        the regular formatter).
+        """
+        self._formatter_tty = fmt
+        self._recompute_native()
+
+    def format(self, record):
+        fmt = self.formatter
+        if self._formatter_tty is not None and self._inner.isTerminal():
+            fmt = self._formatter_tty
+        if isinstance(fmt, _native_formatter_types()):
+            return _format_natively(fmt, record)
+        if fmt is not self.formatter:
+            return fmt.format(record)
+        return super().format(record)
+
     def setLevel(self, level):
         super().setLevel(level)
         self._inner.setLevel(level)
@@ -248,12 +520,23 @@ class StreamHandler(logging.StreamHandler):
         super().removeFilter(filter)
         self._recompute_native()
 
+    def setSanitize(self, policy=None, newlines=True):
+        """
+        Escape or strip control characters in the formatted text before it is written.
+
+        Args:
+            policy: "escape" (``\\x1b``), "strip", or None to write text unchanged
+            newlines: False to treat newlines and tabs as control characters too,
+                keeping each record on one line
+        """
+        self._inner.setSanitize(policy, newlines)
+
     def emit(self, record):
         try:
             if self._native:
                 self._inner.emit(_prepare_record_for_rust(record, native=True))
             else:
-                if self.formatter:
+                if self.formatter or self._formatter_tty is not None:
                     record.msg = self.format(record)
                     record.args = None
                 self._inner.emit(_prepare_record_for_rust(record))
@@ -266,8 +549,47 @@ class StreamHandler(logging.StreamHandler):
         """
         self._inner.setErrorCallback(callback)
 
+    def get_metrics(self):
+        """
+        Return queue diagnostics for the background writer.
+
+        Keys: emitted, enqueued, dequeued, queue_dropped, queue_depth,
+        max_queue_depth, queue_capacity, deadline_records, late,
+        flush_latency_ms, sync_emitted.
+        """
+        return self._inner.get_metrics()
+
+    def setSyncLevel(self, level=None):
+        """
+        Write records at or above this level synchronously, bypassing the queue,
+        and flush before the log call returns. None (the default) turns this off.
+
+        Args:
+            level: Log level (e.g., logging.CRITICAL) or None
+        """
+        self._inner.setSyncLevel(level)
+
+    def getSyncLevel(self):
+        """
+        Get the current sync level.
+
+        Returns:
+            int | None: Current sync level, or None when records are always queued
+        """
+        return self._inner.getSyncLevel()
+
 
 class RotatingFileHandler(logging.handlers.RotatingFileHandler):
+    """
+    Rust-native drop-in for ``logging.handlers.RotatingFileHandler``.
+
+    On top of ``backupCount``, ``max_age_days`` deletes backups last modified more
+    than that many days ago, and ``max_total_bytes`` deletes the oldest backups while
+    the file and its backups take more than that, after each rotation.
+    ``checksum_every`` and ``index_every`` work as for FileHandler; each backup ends
+    with a checksum trailer and keeps its index.
+    """
+
     def __init__(
         self,
         filename,
@@ -277,9 +599,21 @@ class RotatingFileHandler(logging.handlers.RotatingFileHandler):
         encoding=None,
         delay=False,
         errors=None,
+        max_age_days=None,
+        max_total_bytes=None,
+        checksum_every=None,
+        index_every=None,
     ):
         # Initialize inner handler first (before parent creates file handle)
-        self._inner = logxide.RotatingFileHandler(filename, maxBytes, backupCount)
+        self._inner = _native(logxide, "RotatingFileHandler", "file")(
+            filename,
+            maxBytes,
+            backupCount,
+            max_age_days,
+            max_total_bytes,
+            checksum_every,
+            index_every,
+        )
         self._native = True
         super().__init__(filename, mode, maxBytes, backupCount, encoding, delay)
         # Close parent's file handle since we use Rust handler
@@ -287,16 +621,31 @@ class RotatingFileHandler(logging.handlers.RotatingFileHandler):
             self.stream.close()
             self.stream = None
         self._recompute_native()
+        _adopt_default_formatter(self, "file")
 
     def _recompute_native(self):
-        ok, fmt_str, datefmt = _translatable(self.formatter)
-        if ok and not self.filters:
-            self._inner.setFormatterSpec(fmt_str, datefmt)
+        native_fmt = isinstance(self.formatter, _native_formatter_types())
+        ok, fmt_str, datefmt, style, defaults = (
+            (True, None, None, "%", None)
+            if native_fmt
+            else _translatable(self.formatter)
+        )
+        if ok and self._inner.setFilters(self.filters):
+            if native_fmt:
+                self._inner.setFormatter(self.formatter)
+            else:
+                self._inner.setFormatterSpec(fmt_str, datefmt, style, defaults)
             self._native = True
         else:
+            self._inner.setFilters([])
             self._inner.setPythonDispatch()
             self._native = False
 
+    def format(self, record):
+        if isinstance(self.formatter, _native_formatter_types()):
+            return _format_natively(self.formatter, record)
+        return super().format(record)
+
     def setLevel(self, level):
         super().setLevel(level)
         self._inner.setLevel(level)
@@ -313,6 +662,17 @@ class RotatingFileHandler(logging.handlers.RotatingFileHandler):
         super().removeFilter(filter)
         self._recompute_native()
 
+    def setSanitize(self, policy=None, newlines=True):
+        """
+        Escape or strip control characters in the formatted text before it is written.
+
+        Args:
+            policy: "escape" (``\\x1b``), "strip", or None to write text unchanged
+            newlines: False to treat newlines and tabs as control characters too,
+                keeping each record on one line
+        """
+        self._inner.setSanitize(policy, newlines)
+
     def emit(self, record):
         try:
             if self._native:
@@ -348,169 +708,1443 @@ class RotatingFileHandler(logging.handlers.RotatingFileHandler):
         """Flush the handler."""
         self._inner.flush()
 
+    def close(self):
+        # The stdlib only flushes while it has a stream open, and this one never does.
+        self.flush()
+        super().close()
 
-class HTTPHandler(logging.Handler):
-    """
-    High-performance HTTP handler with batching and background transmission.
 
-    Args:
-        url: HTTP endpoint URL
-        headers: HTTP headers dict (e.g., {"Authorization": "Bearer token"})
-        capacity: Max buffer capacity (default: 10000)
-        batch_size: Records per batch (default: 1000)
-        flush_interval: Seconds between auto-flush (default: 30)
-        global_context: Dict of fields added to every record
-        transform_callback: Callable(records) -> transformed_records for custom JSON
-        context_provider: Callable() -> dict for dynamic context per batch
-        error_callback: Callable(error_msg) for HTTP failure handling
+class TimedRotatingFileHandler(logging.handlers.TimedRotatingFileHandler):
+    """
+    Rust-native drop-in for ``logging.handlers.TimedRotatingFileHandler``.
+
+    ``when``, ``interval``, ``backupCount``, ``utc`` and ``atTime`` behave as in
+    the stdlib, and backups get the same ``<filename>.<date>`` names. With
+    ``compress=True`` each backup is gzipped to ``<filename>.<date>.gz`` on a
+    background thread (needs the 'compression' feature). ``max_age_days``,
+    ``max_total_bytes``, ``checksum_every`` and ``index_every`` work as for
+    RotatingFileHandler; gzipped backups keep no index.
     """
 
     def __init__(
         self,
-        url,
-        headers=None,
-        capacity=10000,
-        batch_size=1000,
-        flush_interval=30,
-        global_context=None,
-        transform_callback=None,
-        context_provider=None,
-        error_callback=None,
-        overflow="block",
+        filename,
+        when="h",
+        interval=1,
+        backupCount=0,
+        encoding=None,
+        delay=False,
+        utc=False,
+        atTime=None,
+        errors=None,
+        compress=False,
+        max_age_days=None,
+        max_total_bytes=None,
+        checksum_every=None,
+        index_every=None,
     ):
-        super().__init__()
-        self._inner = logxide.HTTPHandler(
-            url,
-            headers=headers,
-            capacity=capacity,
-            batch_size=batch_size,
-            flush_interval=flush_interval,
-            global_context=global_context,
-            transform_callback=transform_callback,
-            context_provider=context_provider,
-            error_callback=error_callback,
-            overflow=overflow,
+        at_time = None
+        if atTime is not None:
+            at_time = atTime.hour * 3600 + atTime.minute * 60 + atTime.second
+        # Initialize inner handler first (before parent creates file handle)
+        self._inner = _native(logxide, "TimedRotatingFileHandler", "file")(
+            filename,
+            when,
+            interval,
+            backupCount,
+            utc,
+            at_time,
+            compress,
+            max_age_days,
+            max_total_bytes,
+            checksum_every,
+            index_every,
+        )
+        self._native = True
+        super().__init__(
+            filename, when, interval, backupCount, encoding, delay, utc, atTime
+        )
+        # Close parent's file handle since we use Rust handler
+        if hasattr(self, "stream") and self.stream:
+            self.stream.close()
+            self.stream = None
+        self._recompute_native()
+        _adopt_default_formatter(self, "file")
+
+    def _recompute_native(self):
+        native_fmt = isinstance(self.formatter, _native_formatter_types())
+        ok, fmt_str, datefmt, style, defaults = (
+            (True, None, None, "%", None)
+            if native_fmt
+            else _translatable(self.formatter)
         )
+        if ok and self._inner.setFilters(self.filters):
+            if native_fmt:
+                self._inner.setFormatter(self.formatter)
+            else:
+                self._inner.setFormatterSpec(fmt_str, datefmt, style, defaults)
+            self._native = True
+        else:
+            self._inner.setFilters([])
+            self._inner.setPythonDispatch()
+            self._native = False
+
+    def format(self, record):
+        if isinstance(self.formatter, _native_formatter_types()):
+            return _format_natively(self.formatter, record)
+        return super().format(record)
 
     def setLevel(self, level):
         super().setLevel(level)
         self._inner.setLevel(level)
 
-    def emit(self, record):
-        try:
-            if self.formatter:
-                record.msg = self.format(record)
-                record.args = None
-            rust_record = _prepare_record_for_rust(record)
-            self._inner.emit(rust_record)
-        except Exception:
-            self.handleError(record)
+    def setFormatter(self, fmt):
+        super().setFormatter(fmt)
+        self._recompute_native()
 
-    def flush(self):
-        self._inner.flush()
+    def addFilter(self, filter):
+        super().addFilter(filter)
+        self._recompute_native()
 
-    def close(self):
-        self._inner.shutdown()
-        super().close()
+    def removeFilter(self, filter):
+        super().removeFilter(filter)
+        self._recompute_native()
 
-    def get_metrics(self):
+    def setSanitize(self, policy=None, newlines=True):
         """
-        Return delivery accounting for this handler.
+        Escape or strip control characters in the formatted text before it is written.
 
-        Keys: emitted, sink_acknowledged, queue_dropped, delivery_failed, in_flight.
+        Args:
+            policy: "escape" (``\\x1b``), "strip", or None to write text unchanged
+            newlines: False to treat newlines and tabs as control characters too,
+                keeping each record on one line
         """
-        return self._inner.get_metrics()
+        self._inner.setSanitize(policy, newlines)
+
+    def emit(self, record):
+        try:
+            if self._native:
+                self._inner.emit(_prepare_record_for_rust(record, native=True))
+            else:
+                if self.formatter:
+                    record.msg = self.format(record)
+                    record.args = None
+                self._inner.emit(_prepare_record_for_rust(record))
+        except Exception:
+            self.handleError(record)
 
     def setFlushLevel(self, level):
         """
         Set the flush level. Records at or above this level trigger immediate flush.
         Default is ERROR (40).
-
-        Args:
-            level: Log level (e.g., logging.ERROR, logging.CRITICAL)
         """
         self._inner.setFlushLevel(level)
 
     def getFlushLevel(self):
         """
         Get the current flush level.
-
-        Returns:
-            int: Current flush level (e.g., 40 for ERROR)
         """
         return self._inner.getFlushLevel()
 
-
-class OTLPHandler(logging.Handler):
-    """
-    High-performance OTLP (OpenTelemetry) handler for log export.
-
-    Args:
-        url: OTLP endpoint URL (e.g., http://localhost:4318/v1/logs)
-        service_name: Service name for OTLP logs
-        headers: Optional HTTP headers dict
-    """
-
-    def __init__(
-        self,
-        url,
-        service_name,
-        headers=None,
-        overflow="block",
-    ):
-        super().__init__()
-        self._inner = logxide.OTLPHandler(
-            url=url, service_name=service_name, headers=headers, overflow=overflow
-        )
-
-    def setLevel(self, level):
-        super().setLevel(level)
-        self._inner.setLevel(level)
-
-    def emit(self, record):
-        try:
-            if self.formatter:
-                record.msg = self.format(record)
-                record.args = None
-            rust_record = _prepare_record_for_rust(record)
-            self._inner.emit(rust_record)
-        except Exception:
-            self.handleError(record)
+    def setErrorCallback(self, callback):
+        """
+        Set error callback for write failures.
+        """
+        self._inner.setErrorCallback(callback)
 
     def flush(self):
+        """Flush the handler."""
         self._inner.flush()
 
     def close(self):
-        self._inner.shutdown()
+        # The stdlib only flushes while it has a stream open, and this one never does.
+        self.flush()
         super().close()
 
-    def get_metrics(self):
-        """
-        Return delivery accounting for this handler.
 
-        Keys: emitted, sink_acknowledged, queue_dropped, delivery_failed, in_flight.
-        """
-        return self._inner.get_metrics()
+def _routed_file(spec):
+    """Build the file handler for one LevelRoutedFileHandler band."""
+    if isinstance(spec, (FileHandler, RotatingFileHandler, TimedRotatingFileHandler)):
+        return spec
+    if isinstance(spec, dict):
+        if "when" in spec:
+            return TimedRotatingFileHandler(**spec)
+        if spec.keys() & {"maxBytes", "backupCount", "max_age_days", "max_total_bytes"}:
+            return RotatingFileHandler(**spec)
+        return FileHandler(**spec)
+    return FileHandler(spec)
 
 
-class MemoryHandler(logging.Handler):
+class LevelRoutedFileHandler(logging.Handler):
     """
-    High-performance memory handler for testing and log capture.
-    Stores records in Rust native memory for maximum performance.
-
-    Provides pytest caplog-compatible properties:
-    - `.records`: List of LogRecord objects
-    - `.text`: All messages joined with newlines
-    - `.record_tuples`: List of (logger_name, level, message) tuples
+    Write each record to the file of its level band.
+
+    ``routes`` maps the lowest level of each band (an int or a level name) to its
+    file. A record goes to the band with the highest level at or below its own, so
+    ``{ERROR: "errors.log", INFO: "info.log", DEBUG: "debug.log"}`` writes WARNING
+    records to info.log. Records below every band are dropped.
+
+    Each file is given as a path, as a dict of keyword arguments (with ``when`` it
+    is a TimedRotatingFileHandler, with ``maxBytes``, ``backupCount`` or a retention
+    limit a RotatingFileHandler, otherwise a FileHandler), or as one of those
+    handlers. The
+    bands rotate independently; the formatter, filters and flush level of this
+    handler apply to all of them.
     """
 
-    def __init__(self):
+    def __init__(self, routes):
         super().__init__()
-        self._inner = logxide.MemoryHandler()
+        self.routes = {
+            logging._checkLevel(level): _routed_file(spec)
+            for level, spec in routes.items()
+        }
+        if len(self.routes) != len(routes):
+            raise ValueError("a level is routed to more than one file")
+        self._inner = _native(logxide, "LevelRoutedFileHandler", "file")(
+            {level: handler._inner for level, handler in self.routes.items()}
+        )
+        self._native = True
+        self._recompute_native()
+        _adopt_default_formatter(self, "file")
 
-    def setLevel(self, level):
-        super().setLevel(level)
-        self._inner.setLevel(level)
+    def _recompute_native(self):
+        native_fmt = isinstance(self.formatter, _native_formatter_types())
+        ok, fmt_str, datefmt, style, defaults = (
+            (True, None, None, "%", None)
+            if native_fmt
+            else _translatable(self.formatter)
+        )
+        if ok and self._inner.setFilters(self.filters):
+            if native_fmt:
+                self._inner.setFormatter(self.formatter)
+            else:
+                self._inner.setFormatterSpec(fmt_str, datefmt, style, defaults)
+            self._native = True
+        else:
+            self._inner.setFilters([])
+            self._inner.setPythonDispatch()
+            self._native = False
+
+    def format(self, record):
+        if isinstance(self.formatter, _native_formatter_types()):
+            return _format_natively(self.formatter, record)
+        return super().format(record)
+
+    def setLevel(self, level):
+        super().setLevel(level)
+        self._inner.setLevel(level)
+
+    def setFormatter(self, fmt):
+        super().setFormatter(fmt)
+        self._recompute_native()
+
+    def addFilter(self, filter):
+        super().addFilter(filter)
+        self._recompute_native()
+
+    def removeFilter(self, filter):
+        super().removeFilter(filter)
+        self._recompute_native()
+
+    def setSanitize(self, policy=None, newlines=True):
+        """
+        Escape or strip control characters in the formatted text before it is written.
+
+        Args:
+            policy: "escape" (``\\x1b``), "strip", or None to write text unchanged
+            newlines: False to treat newlines and tabs as control characters too,
+                keeping each record on one line
+        """
+        self._inner.setSanitize(policy, newlines)
+
+    def emit(self, record):
+        try:
+            if self._native:
+                self._inner.emit(_prepare_record_for_rust(record, native=True))
+            else:
+                if self.formatter:
+                    record.msg = self.format(record)
+                    record.args = None
+                self._inner.emit(_prepare_record_for_rust(record))
+        except Exception:
+            self.handleError(record)
+
+    def setFlushLevel(self, level):
+        """
+        Set the flush level of every file. Records at or above this level trigger
+        immediate flush. Default is ERROR (40).
+        """
+        self._inner.setFlushLevel(level)
+
+    def getFlushLevel(self):
+        """
+        Get the current flush level.
+        """
+        return self._inner.getFlushLevel()
+
+    def flush(self):
+        """Flush every file."""
+        self._inner.flush()
+
+    def close(self):
+        self._inner.flush()
+        for handler in self.routes.values():
+            handler.close()
+        super().close()
+
+
+class HTTPHandler(logging.Handler):
+    """
+    High-performance HTTP handler with batching and background transmission.
+
+    Args:
+        url: HTTP endpoint URL
+        headers: HTTP headers dict (e.g., {"Authorization": "Bearer token"})
+        capacity: Max buffer capacity (default: None, from logxide.preset();
+            10000)
+        batch_size: Records per batch (default: None, from logxide.preset(); 1000)
+        flush_interval: Seconds between auto-flush (default: None, from
+            logxide.preset(); 30)
+        global_context: Dict of fields added to every record
+        transform_callback: Callable(records) -> transformed_records for custom JSON
+        context_provider: Callable() -> dict for dynamic context per batch
+        context_ttl: Seconds a context_provider result is reused before the
+            provider is called again (default: None, called for every batch)
+        error_callback: Callable(error_msg) for HTTP failure handling
+        overflow: Policy when the queue is full: "block", "drop_oldest" or
+            "drop_newest" (default: None, the logxide.preset() overflow, else
+            "block")
+        block_timeout: Seconds "block" waits for room before dropping the
+            record (default: None, no limit)
+        shutdown_timeout: Seconds close() waits for pending batches (None = forever)
+        spill_path: File that receives records still pending after the timeout,
+            as JSON lines (default: they are dropped and counted)
+        daemon: True = never delay interpreter exit, False = drain at exit
+            (bounded by shutdown_timeout). None uses logxide.configure_workers().
+        sync_level: Records at or above this level (e.g. logging.CRITICAL) are
+            sent by the caller before the log call returns instead of queued
+            (default: None, the logxide.preset() sync level; always queued
+            unless a preset sets one)
+        method: None (default) posts JSON batches. "GET" or "POST" switches to
+            the stdlib ``logging.handlers.HTTPHandler`` wire format: one request
+            per record, its attributes URL-encoded into the query string (GET)
+            or a form body (POST), for receivers written against the stdlib
+        credentials: (user, password) tuple sent as HTTP basic auth
+        compression: "gzip" or "zstd" compresses JSON batch bodies and sets
+            Content-Encoding (default: None, uncompressed)
+        compression_min_bytes: Bodies smaller than this are sent uncompressed
+            (default: 1024)
+        max_batch_bytes: Split batches so no request body exceeds this many
+            bytes (before compression). A single record over the limit is not
+            sent and counts as delivery_failed (default: None, no limit)
+        idempotency_keys: Give every request a batch UUID (in the
+            Idempotency-Key header and on each record) and every record a
+            sequence id, so a deduplicating collector can drop retried batches
+            (default: False)
+        send_time: Add each record's send time as sent_at (default: False)
+        max_backdate: Seconds. Records older than this when sent go out with
+            created moved up to the limit, plus original_created and
+            timestamp_capped fields (default: None, never moved)
+        dry_run: Validate payloads without sending them. A path appends each
+            request to that file as a JSON line. A callable receives each request
+            as a dict and returns the simulated response: None or a 2xx status
+            succeeds, 429/503 (optionally as (status, retry_after_seconds))
+            throttle and are retried, other statuses fail (default: None, send)
+        record_transform: Reshape each JSON record in Rust, without calling into
+            Python: a dict (or YAML text) with "levels" (levelname remap),
+            "add" (fields; strings are "{field}" templates), "rename" and
+            "drop". Runs before transform_callback (default: None)
+
+    Slowly-changing context can also be pushed with update_context() instead of
+    pulled through context_provider.
+    """
+
+    def __init__(
+        self,
+        url,
+        headers=None,
+        capacity=None,
+        batch_size=None,
+        flush_interval=None,
+        global_context=None,
+        transform_callback=None,
+        context_provider=None,
+        error_callback=None,
+        overflow=None,
+        block_timeout=None,
+        shutdown_timeout=30.0,
+        spill_path=None,
+        daemon=None,
+        sync_level=None,
+        method=None,
+        credentials=None,
+        compression=None,
+        compression_min_bytes=1024,
+        max_batch_bytes=None,
+        idempotency_keys=False,
+        send_time=False,
+        max_backdate=None,
+        dry_run=None,
+        record_transform=None,
+        context_ttl=None,
+    ):
+        super().__init__()
+        self._inner = _native(logxide, "HTTPHandler", "network")(
+            url,
+            headers=headers,
+            capacity=capacity,
+            batch_size=batch_size,
+            flush_interval=flush_interval,
+            global_context=global_context,
+            transform_callback=transform_callback,
+            context_provider=context_provider,
+            error_callback=error_callback,
+            overflow=overflow,
+            block_timeout=block_timeout,
+            shutdown_timeout=shutdown_timeout,
+            spill_path=spill_path,
+            daemon=daemon,
+            sync_level=sync_level,
+            method=method,
+            credentials=credentials,
+            compression=compression,
+            compression_min_bytes=compression_min_bytes,
+            max_batch_bytes=max_batch_bytes,
+            idempotency_keys=idempotency_keys,
+            send_time=send_time,
+            max_backdate=max_backdate,
+            dry_run=dry_run,
+            record_transform=_record_transform_spec(record_transform),
+            context_ttl=context_ttl,
+        )
+
+    def _recompute_native(self):
+        self._inner.setPythonDispatch(not self._inner.setFilters(self.filters))
+
+    def setLevel(self, level):
+        super().setLevel(level)
+        self._inner.setLevel(level)
+
+    def addFilter(self, filter):
+        super().addFilter(filter)
+        self._recompute_native()
+
+    def removeFilter(self, filter):
+        super().removeFilter(filter)
+        self._recompute_native()
+
+    def emit(self, record):
+        try:
+            if self.formatter:
+                record.msg = self.format(record)
+                record.args = None
+            rust_record = _prepare_record_for_rust(record)
+            self._inner.emit(rust_record)
+        except Exception:
+            self.handleError(record)
+
+    def flush(self):
+        self._inner.flush()
+
+    def close(self):
+        self._inner.shutdown()
+        super().close()
+
+    @property
+    def daemon(self):
+        """Whether the background worker is abandoned (not drained) at exit."""
+        return self._inner.daemon
+
+    def get_metrics(self):
+        """
+        Return delivery accounting for this handler.
+
+        Keys: emitted, sink_acknowledged, queue_dropped, delivery_failed,
+        shutdown_dropped, shutdown_spilled, in_flight, sync_emitted, throttled
+        (429/503 responses received), throttle_remaining_ms (current backoff
+        pause, 0 when not throttled), payload_bytes and wire_bytes (request
+        bodies before and after compression), oversized_dropped (records over
+        max_batch_bytes on their own), timestamps_capped (records shipped with
+        their time moved up to max_backdate), context_provider_calls, plus the
+        queue diagnostics enqueued, dequeued, queue_depth, max_queue_depth,
+        queue_capacity, deadline_records, late and flush_latency_ms (histogram
+        of flush() round trips).
+        """
+        return self._inner.get_metrics()
+
+    def update_context(self, context, replace=False):
+        """
+        Add fields to every record sent from the next batch on, without a
+        context_provider call per batch. Pushed fields override global_context;
+        context_provider results override them.
+
+        Args:
+            context: Dict of fields to merge into the pushed context
+            replace: Drop previously pushed fields first (default: False)
+        """
+        self._inner.update_context(context, replace=replace)
+
+    def setSyncLevel(self, level=None):
+        """
+        Write records at or above this level synchronously, bypassing the queue,
+        and flush before the log call returns. None (the default) turns this off.
+
+        Args:
+            level: Log level (e.g., logging.CRITICAL) or None
+        """
+        self._inner.setSyncLevel(level)
+
+    def getSyncLevel(self):
+        """
+        Get the current sync level.
+
+        Returns:
+            int | None: Current sync level, or None when records are always queued
+        """
+        return self._inner.getSyncLevel()
+
+    def setFlushLevel(self, level):
+        """
+        Set the flush level. Records at or above this level trigger immediate flush.
+        Default is ERROR (40).
+
+        Args:
+            level: Log level (e.g., logging.ERROR, logging.CRITICAL)
+        """
+        self._inner.setFlushLevel(level)
+
+    def getFlushLevel(self):
+        """
+        Get the current flush level.
+
+        Returns:
+            int: Current flush level (e.g., 40 for ERROR)
+        """
+        return self._inner.getFlushLevel()
+
+
+class OTLPHandler(logging.Handler):
+    """
+    High-performance OTLP (OpenTelemetry) handler for log export.
+
+    Args:
+        url: OTLP endpoint URL (e.g., http://localhost:4318/v1/logs)
+        service_name: Service name for OTLP logs
+        headers: Optional HTTP headers dict
+        overflow: Policy when the queue is full: "block", "drop_oldest" or
+            "drop_newest" (default: None, the logxide.preset() overflow, else
+            "block")
+        block_timeout: Seconds "block" waits for room before dropping the
+            record (default: None, no limit)
+        shutdown_timeout: Seconds close() waits for pending batches (None = forever)
+        spill_path: File that receives records still pending after the timeout,
+            as JSON lines (default: they are dropped and counted)
+        daemon: True = never delay interpreter exit, False = drain at exit
+            (bounded by shutdown_timeout). None uses logxide.configure_workers().
+        sync_level: Records at or above this level (e.g. logging.CRITICAL) are
+            sent by the caller before the log call returns instead of queued
+            (default: None, the logxide.preset() sync level; always queued
+            unless a preset sets one)
+        compression: "gzip" or "zstd" compresses request bodies and sets
+            Content-Encoding (default: None, uncompressed)
+        compression_min_bytes: Bodies smaller than this are sent uncompressed
+            (default: 1024)
+        max_batch_bytes: Split batches so no request body exceeds this many
+            bytes (before compression). A single record over the limit is not
+            sent and counts as delivery_failed (default: None, no limit)
+        idempotency_keys: Give every request a batch UUID (in the
+            Idempotency-Key header and on each record) and every record a
+            sequence id, so a deduplicating collector can drop retried batches
+            (default: False)
+        send_time: Use the send time as each record's observed time
+            (default: False, the created time)
+        max_backdate: Seconds. Records older than this when sent go out with
+            their time moved up to the limit, plus the
+            logxide.original_time_unix_nano and logxide.timestamp_capped
+            attributes (default: None, never moved)
+        dry_run: Validate payloads without sending them. A path appends each
+            request to that file as a JSON line. A callable receives each request
+            as a dict and returns the simulated response: None or a 2xx status
+            succeeds, 429/503 (optionally as (status, retry_after_seconds))
+            throttle and are retried, other statuses fail (default: None, send)
+    """
+
+    def __init__(
+        self,
+        url,
+        service_name,
+        headers=None,
+        overflow=None,
+        block_timeout=None,
+        shutdown_timeout=30.0,
+        spill_path=None,
+        daemon=None,
+        sync_level=None,
+        compression=None,
+        compression_min_bytes=1024,
+        max_batch_bytes=None,
+        idempotency_keys=False,
+        send_time=False,
+        max_backdate=None,
+        dry_run=None,
+    ):
+        super().__init__()
+        self._inner = _native(logxide, "OTLPHandler", "otlp")(
+            url=url,
+            service_name=service_name,
+            headers=headers,
+            overflow=overflow,
+            block_timeout=block_timeout,
+            shutdown_timeout=shutdown_timeout,
+            spill_path=spill_path,
+            daemon=daemon,
+            sync_level=sync_level,
+            compression=compression,
+            compression_min_bytes=compression_min_bytes,
+            max_batch_bytes=max_batch_bytes,
+            idempotency_keys=idempotency_keys,
+            send_time=send_time,
+            max_backdate=max_backdate,
+            dry_run=dry_run,
+        )
+
+    def _recompute_native(self):
+        self._inner.setPythonDispatch(not self._inner.setFilters(self.filters))
+
+    def setLevel(self, level):
+        super().setLevel(level)
+        self._inner.setLevel(level)
+
+    def addFilter(self, filter):
+        super().addFilter(filter)
+        self._recompute_native()
+
+    def removeFilter(self, filter):
+        super().removeFilter(filter)
+        self._recompute_native()
+
+    def emit(self, record):
+        try:
+            if self.formatter:
+                record.msg = self.format(record)
+                record.args = None
+            rust_record = _prepare_record_for_rust(record)
+            self._inner.emit(rust_record)
+        except Exception:
+            self.handleError(record)
+
+    def flush(self):
+        self._inner.flush()
+
+    def close(self):
+        self._inner.shutdown()
+        super().close()
+
+    @property
+    def daemon(self):
+        """Whether the background worker is abandoned (not drained) at exit."""
+        return self._inner.daemon
+
+    def get_metrics(self):
+        """
+        Return delivery accounting for this handler.
+
+        Keys: emitted, sink_acknowledged, queue_dropped, delivery_failed,
+        shutdown_dropped, shutdown_spilled, in_flight, sync_emitted, throttled
+        (429/503 responses received), throttle_remaining_ms (current backoff
+        pause, 0 when not throttled), payload_bytes and wire_bytes (request
+        bodies before and after compression), oversized_dropped (records over
+        max_batch_bytes on their own), timestamps_capped (records shipped with
+        their time moved up to max_backdate), plus the queue diagnostics enqueued,
+        dequeued, queue_depth, max_queue_depth, queue_capacity, deadline_records,
+        late and flush_latency_ms (histogram of flush() round trips).
+        """
+        return self._inner.get_metrics()
+
+    def setSyncLevel(self, level=None):
+        """
+        Write records at or above this level synchronously, bypassing the queue,
+        and flush before the log call returns. None (the default) turns this off.
+
+        Args:
+            level: Log level (e.g., logging.CRITICAL) or None
+        """
+        self._inner.setSyncLevel(level)
+
+    def getSyncLevel(self):
+        """
+        Get the current sync level.
+
+        Returns:
+            int | None: Current sync level, or None when records are always queued
+        """
+        return self._inner.getSyncLevel()
+
+
+class DatagramHandler(logging.Handler):
+    """
+    Send each record as one UDP or Unix datagram, with no connection to manage.
+
+    The default payload matches ``logging.handlers.DatagramHandler`` (a
+    length-prefixed pickled attribute dict), so existing receivers that call
+    ``logging.makeLogRecord(pickle.loads(data[4:]))`` keep working. With
+    ``format="json"`` each datagram is one JSON record object instead. As in the
+    stdlib, those records are sent unformatted; the receiver formats them. With
+    ``format="text"`` each datagram is the record rendered by this handler's
+    formatter.
+
+    A payload larger than ``max_packet_size`` is dropped and reported through
+    ``error_callback``. With ``truncate=True`` the message is shortened so the
+    payload fits instead (text payloads are cut at the limit).
+
+    Args:
+        host: Host name or address, or the path of a Unix datagram socket when
+            port is None
+        port: UDP port, or None for a Unix datagram socket
+        format: "pickle" (default, stdlib-compatible), "json" or "text"
+        max_packet_size: Largest payload to send, in bytes (default: 65507, the
+            most one UDP datagram over IPv4 carries)
+        truncate: Shorten oversized records instead of dropping them
+        error_callback: Callable(error_msg) for failed sends (e.g. a record too
+            large for one datagram, or nothing bound to the Unix socket path)
+    """
+
+    def __init__(
+        self,
+        host,
+        port=None,
+        format="pickle",
+        max_packet_size=65507,
+        truncate=False,
+        error_callback=None,
+    ):
+        super().__init__()
+        self.host = host
+        self.port = port
+        self._text = format == "text"
+        self._native = True
+        self._inner = _native(logxide, "DatagramHandler", "network")(
+            host,
+            port,
+            format=format,
+            max_packet_size=max_packet_size,
+            truncate=truncate,
+            error_callback=error_callback,
+        )
+        self._recompute_native()
+
+    def _recompute_native(self):
+        if not self._text:
+            return
+        ok, fmt_str, datefmt, style, defaults = _translatable(self.formatter)
+        if ok and self._inner.setFilters(self.filters):
+            self._inner.setFormatterSpec(fmt_str, datefmt, style, defaults)
+            self._native = True
+        else:
+            self._inner.setFilters([])
+            self._inner.setPythonDispatch()
+            self._native = False
+
+    def setLevel(self, level):
+        super().setLevel(level)
+        self._inner.setLevel(level)
+
+    def setFormatter(self, fmt):
+        super().setFormatter(fmt)
+        self._recompute_native()
+
+    def addFilter(self, filter):
+        super().addFilter(filter)
+        self._recompute_native()
+
+    def removeFilter(self, filter):
+        super().removeFilter(filter)
+        self._recompute_native()
+
+    def setSanitize(self, policy=None, newlines=True):
+        """
+        Escape or strip control characters in the formatted text before it is written.
+
+        Args:
+            policy: "escape" (``\\x1b``), "strip", or None to write text unchanged
+            newlines: False to treat newlines and tabs as control characters too,
+                keeping each record on one line
+        """
+        self._inner.setSanitize(policy, newlines)
+
+    def emit(self, record):
+        try:
+            if self._native:
+                self._inner.emit(_prepare_record_for_rust(record, native=True))
+            else:
+                record.msg = self.format(record)
+                record.args = None
+                self._inner.emit(_prepare_record_for_rust(record))
+        except Exception:
+            self.handleError(record)
+
+    def get_metrics(self):
+        """
+        Return delivery accounting for this handler.
+
+        Keys: emitted, sink_acknowledged (handed to the socket),
+        delivery_failed (including oversized records) and truncated.
+        """
+        return self._inner.get_metrics()
+
+
+class SocketHandler(logging.Handler):
+    """
+    Send each record over a TCP or Unix stream socket, as a length-prefixed pickle.
+
+    The wire format matches ``logging.handlers.SocketHandler``, so existing
+    receivers (such as the logging cookbook's socket server) keep working. The
+    connection is opened by the first record. A broken connection is reopened
+    and the record sent again. While the receiver is unreachable, connection
+    attempts back off exponentially and records logged in between are dropped
+    and counted, as in the stdlib. Records are sent unformatted.
+
+    Args:
+        host: Host name or address, or the path of a Unix stream socket when
+            port is None
+        port: TCP port, or None for a Unix stream socket
+        retry_start: Seconds to wait after the first failed connection attempt
+        retry_factor: Multiplier for the wait after each further failure
+        retry_max: Longest wait between connection attempts, in seconds
+        error_callback: Callable(error_msg) for failed connections and sends
+    """
+
+    def __init__(
+        self,
+        host,
+        port=None,
+        retry_start=1.0,
+        retry_factor=2.0,
+        retry_max=30.0,
+        error_callback=None,
+    ):
+        # Built first: a rejected argument must not leave a half-made handler in
+        # logging's shutdown list.
+        self._inner = _native(logxide, "SocketHandler", "network")(
+            host,
+            port,
+            retry_start=retry_start,
+            retry_factor=retry_factor,
+            retry_max=retry_max,
+            error_callback=error_callback,
+        )
+        super().__init__()
+        self.host = host
+        self.port = port
+        self.address = host if port is None else (host, port)
+        self.retryStart = retry_start
+        self.retryFactor = retry_factor
+        self.retryMax = retry_max
+
+    def _recompute_native(self):
+        self._inner.setPythonDispatch(not self._inner.setFilters(self.filters))
+
+    def setLevel(self, level):
+        super().setLevel(level)
+        self._inner.setLevel(level)
+
+    def addFilter(self, filter):
+        super().addFilter(filter)
+        self._recompute_native()
+
+    def removeFilter(self, filter):
+        super().removeFilter(filter)
+        self._recompute_native()
+
+    def emit(self, record):
+        try:
+            rust_record = _prepare_record_for_rust(record, native=True)
+            self._inner.emit(rust_record)
+        except Exception:
+            self.handleError(record)
+
+    def close(self):
+        self._inner.close()
+        super().close()
+
+    def get_metrics(self):
+        """
+        Return delivery accounting for this handler.
+
+        Keys: emitted, sink_acknowledged (written to the socket), delivery_failed
+        (including records dropped while waiting to reconnect) and reconnects.
+        """
+        return self._inner.get_metrics()
+
+
+class SyslogHandler(logging.handlers.SysLogHandler):
+    """
+    Send records to a syslog daemon (rsyslog, syslog-ng, journald's syslog
+    socket) as RFC 3164 or RFC 5424 messages, built and sent in Rust.
+
+    A drop-in for ``logging.handlers.SysLogHandler``: the same ``address``,
+    ``facility`` and ``socktype`` arguments, facility names and constants.
+    Level names map to syslog severities the same way.
+
+    Args:
+        address: (host, port) for UDP or TCP, or the path of a local syslog
+            socket such as "/dev/log" (default: ("localhost", 514))
+        facility: Facility code or name, e.g. LOG_LOCAL0 or "local0"
+            (default: LOG_USER)
+        socktype: socket.SOCK_STREAM for TCP (octet-counted framing) or a
+            stream local socket; None or socket.SOCK_DGRAM for datagrams
+        protocol: "rfc3164" (default, BSD syslog) or "rfc5424"
+        app_name: TAG / APP-NAME field (default: the script name)
+        hostname: HOSTNAME field (default: socket.gethostname())
+        error_callback: Callable(error_msg) for failed sends
+    """
+
+    def __init__(
+        self,
+        address=("localhost", logging.handlers.SYSLOG_UDP_PORT),
+        facility=logging.handlers.SysLogHandler.LOG_USER,
+        socktype=None,
+        protocol="rfc3164",
+        app_name=None,
+        hostname=None,
+        error_callback=None,
+    ):
+        if isinstance(facility, str):
+            facility = self.facility_names[facility]
+        if isinstance(address, (list, tuple)):
+            host, port = address
+        else:
+            host, port = address, None
+        kinds = {None: None, socket.SOCK_DGRAM: "dgram", socket.SOCK_STREAM: "stream"}
+        if socktype not in kinds:
+            raise ValueError(f"unsupported socktype: {socktype!r}")
+        self._inner = _native(logxide, "SyslogHandler", "network")(
+            host,
+            port,
+            socktype=kinds[socktype],
+            protocol=protocol,
+            facility=facility,
+            app_name=app_name,
+            hostname=hostname,
+            error_callback=error_callback,
+        )
+        self._native = True
+        # Skip SysLogHandler.__init__: the Rust handler owns the socket.
+        logging.Handler.__init__(self)
+        self.address = address
+        self.facility = facility
+        self.socktype = socktype
+        self.unixsocket = port is None
+        self.socket = None
+        self._recompute_native()
+
+    def _recompute_native(self):
+        ok, fmt_str, datefmt, style, defaults = _translatable(self.formatter)
+        if ok and self._inner.setFilters(self.filters):
+            self._inner.setFormatterSpec(fmt_str, datefmt, style, defaults)
+            self._native = True
+        else:
+            self._inner.setFilters([])
+            self._inner.setPythonDispatch()
+            self._native = False
+
+    def setLevel(self, level):
+        super().setLevel(level)
+        self._inner.setLevel(level)
+
+    def setFormatter(self, fmt):
+        super().setFormatter(fmt)
+        self._recompute_native()
+
+    def addFilter(self, filter):
+        super().addFilter(filter)
+        self._recompute_native()
+
+    def removeFilter(self, filter):
+        super().removeFilter(filter)
+        self._recompute_native()
+
+    def setSanitize(self, policy=None, newlines=True):
+        """
+        Escape or strip control characters in the formatted text before it is written.
+
+        Args:
+            policy: "escape" (``\\x1b``), "strip", or None to write text unchanged
+            newlines: False to treat newlines and tabs as control characters too,
+                keeping each record on one line
+        """
+        self._inner.setSanitize(policy, newlines)
+
+    def emit(self, record):
+        try:
+            if self._native:
+                self._inner.emit(_prepare_record_for_rust(record, native=True))
+            else:
+                record.msg = self.format(record)
+                record.args = None
+                self._inner.emit(_prepare_record_for_rust(record))
+        except Exception:
+            self.handleError(record)
+
+    def get_metrics(self):
+        """
+        Return delivery accounting for this handler.
+
+        Keys: emitted, sink_acknowledged (handed to the socket) and
+        delivery_failed.
+        """
+        return self._inner.get_metrics()
+
+
+class SMTPHandler(logging.handlers.SMTPHandler):
+    """
+    Email each record, with the SMTP session run in Rust.
+
+    A drop-in for ``logging.handlers.SMTPHandler``: the same ``mailhost``,
+    ``fromaddr``, ``toaddrs``, ``subject``, ``credentials``, ``secure`` and
+    ``timeout`` arguments. As in the stdlib, every record opens its own session
+    on the logging thread, so give the handler a high level such as ERROR or
+    CRITICAL. ``subject`` may use ``%(levelname)s``-style fields of the record.
+
+    Args:
+        mailhost: Host name, or (host, port)
+        fromaddr: Envelope sender and From header
+        toaddrs: Recipient address, or a list of them
+        subject: Subject line template, e.g. "[%(levelname)s] %(name)s"
+        credentials: (username, password) for AUTH PLAIN / LOGIN
+        secure: Any tuple to upgrade with STARTTLS, as in the stdlib. Client
+            certificates (a non-empty tuple) are not supported.
+        timeout: Seconds allowed for the connection and each SMTP command
+        security: "none", "starttls" or "tls" (implicit TLS, port 465 by
+            default); overrides ``secure``
+        error_callback: Callable(error_msg) for failed deliveries
+    """
+
+    def __init__(
+        self,
+        mailhost,
+        fromaddr,
+        toaddrs,
+        subject,
+        credentials=None,
+        secure=None,
+        timeout=5.0,
+        security=None,
+        error_callback=None,
+    ):
+        if isinstance(mailhost, (list, tuple)):
+            host, port = mailhost
+        else:
+            host, port = mailhost, None
+        if isinstance(toaddrs, str):
+            toaddrs = [toaddrs]
+        if security is None:
+            if secure is None:
+                security = "none"
+            elif len(secure) == 0:
+                security = "starttls"
+            else:
+                raise ValueError(
+                    "SMTPHandler does not support client certificates in secure"
+                )
+        if isinstance(credentials, (list, tuple)):
+            credentials = tuple(credentials)
+        self._inner = _native(logxide, "SMTPHandler", "smtp")(
+            host,
+            fromaddr,
+            list(toaddrs),
+            subject,
+            port=port,
+            security=security,
+            credentials=credentials,
+            timeout=timeout,
+            error_callback=error_callback,
+        )
+        self._native = True
+        # Skip SMTPHandler.__init__: the Rust handler owns the session.
+        logging.Handler.__init__(self)
+        self.mailhost, self.mailport = host, port
+        self.fromaddr = fromaddr
+        self.toaddrs = list(toaddrs)
+        self.subject = subject
+        self.secure = secure
+        self.timeout = timeout
+        self.username, self.password = credentials or (None, None)
+        self._recompute_native()
+
+    def _recompute_native(self):
+        ok, fmt_str, datefmt, style, defaults = _translatable(self.formatter)
+        custom_subject = type(self).getSubject is not SMTPHandler.getSubject
+        if ok and self._inner.setFilters(self.filters) and not custom_subject:
+            self._inner.setFormatterSpec(fmt_str, datefmt, style, defaults)
+            self._native = True
+        else:
+            self._inner.setFilters([])
+            self._inner.setPythonDispatch()
+            self._native = False
+
+    def setLevel(self, level):
+        super().setLevel(level)
+        self._inner.setLevel(level)
+
+    def setFormatter(self, fmt):
+        super().setFormatter(fmt)
+        self._recompute_native()
+
+    def addFilter(self, filter):
+        super().addFilter(filter)
+        self._recompute_native()
+
+    def removeFilter(self, filter):
+        super().removeFilter(filter)
+        self._recompute_native()
+
+    def setSanitize(self, policy=None, newlines=True):
+        """
+        Escape or strip control characters in the formatted text before it is written.
+
+        Args:
+            policy: "escape" (``\\x1b``), "strip", or None to write text unchanged
+            newlines: False to treat newlines and tabs as control characters too,
+                keeping each record on one line
+        """
+        self._inner.setSanitize(policy, newlines)
+
+    def getSubject(self, record):
+        """The subject template with the record's fields filled in."""
+        return self._inner.getSubject(_prepare_record_for_rust(record, native=True))
+
+    def emit(self, record):
+        try:
+            if self._native:
+                self._inner.emit(_prepare_record_for_rust(record, native=True))
+            else:
+                subject = self.getSubject(record)
+                record.msg = self.format(record)
+                record.args = None
+                self._inner.emit(_prepare_record_for_rust(record), subject)
+        except Exception:
+            self.handleError(record)
+
+    def get_metrics(self):
+        """
+        Return delivery accounting for this handler.
+
+        Keys: emitted, sink_acknowledged (accepted by the SMTP server) and
+        delivery_failed.
+        """
+        return self._inner.get_metrics()
+
+
+class PipeHandler(logging.Handler):
+    """
+    Stream records as NDJSON to the stdin of a subprocess (e.g. ``vector`` or
+    ``jq``), or to an existing named pipe.
+
+    Args:
+        command: Command to spawn, as a list of arguments or a string split with
+            shlex. Its stdout and stderr are inherited.
+        path: Named pipe (FIFO) or file to append to instead of spawning a command
+        capacity: Max queued records per lane (default: None, from
+            logxide.preset(); 10000)
+        overflow: Policy when the pipe can't keep up: "block", "drop_oldest" or
+            "drop_newest" (default: None, the logxide.preset() overflow, else
+            "block")
+        block_timeout: Seconds "block" waits for room before dropping the
+            record (default: None, no limit)
+        restart: Respawn the command (reopen the path) when it exits or the pipe
+            breaks (default: True)
+        restart_delay: Minimum seconds between two connection attempts (default: 1.0)
+        error_callback: Callable(error_msg) for spawn/write failures
+        shutdown_timeout: Seconds close() waits for pending records (None = forever)
+        spill_path: File that receives records still pending after the timeout,
+            as JSON lines (default: they are dropped and counted)
+        daemon: True = never delay interpreter exit, False = drain at exit
+            (bounded by shutdown_timeout). None uses logxide.configure_workers().
+    """
+
+    def __init__(
+        self,
+        command=None,
+        path=None,
+        capacity=None,
+        overflow=None,
+        block_timeout=None,
+        restart=True,
+        restart_delay=1.0,
+        error_callback=None,
+        shutdown_timeout=30.0,
+        spill_path=None,
+        daemon=None,
+    ):
+        super().__init__()
+        if isinstance(command, str):
+            command = shlex.split(command)
+        self._inner = _native(logxide, "PipeHandler", "pipe")(
+            command=command,
+            path=path,
+            capacity=capacity,
+            overflow=overflow,
+            block_timeout=block_timeout,
+            restart=restart,
+            restart_delay=restart_delay,
+            error_callback=error_callback,
+            shutdown_timeout=shutdown_timeout,
+            spill_path=spill_path,
+            daemon=daemon,
+        )
+
+    def _recompute_native(self):
+        self._inner.setPythonDispatch(not self._inner.setFilters(self.filters))
+
+    def setLevel(self, level):
+        super().setLevel(level)
+        self._inner.setLevel(level)
+
+    def addFilter(self, filter):
+        super().addFilter(filter)
+        self._recompute_native()
+
+    def removeFilter(self, filter):
+        super().removeFilter(filter)
+        self._recompute_native()
+
+    def emit(self, record):
+        try:
+            if self.formatter:
+                record.msg = self.format(record)
+                record.args = None
+            rust_record = _prepare_record_for_rust(record)
+            self._inner.emit(rust_record)
+        except Exception:
+            self.handleError(record)
+
+    def flush(self):
+        self._inner.flush()
+
+    def close(self):
+        self._inner.shutdown()
+        super().close()
+
+    @property
+    def daemon(self):
+        """Whether the background worker is abandoned (not drained) at exit."""
+        return self._inner.daemon
+
+    def get_metrics(self):
+        """
+        Return delivery accounting for this handler.
+
+        Keys: emitted, sink_acknowledged (written to the pipe), queue_dropped,
+        delivery_failed, shutdown_dropped, shutdown_spilled, in_flight, restarts,
+        plus the queue diagnostics enqueued, dequeued, queue_depth,
+        max_queue_depth, queue_capacity, deadline_records, late and
+        flush_latency_ms (histogram of flush() round trips).
+        """
+        return self._inner.get_metrics()
+
+
+class QueueHandler(logging.handlers.QueueHandler):
+    """
+    Put records on a bounded Rust queue for a QueueListener to deliver.
+
+    The counterpart of ``logging.handlers.QueueHandler``, except that the queue is
+    built in: the handler owns it, and a :class:`QueueListener` created with the
+    handler drains it on its own thread. Records are queued unformatted, so the
+    listener's handlers format them. As with the other queued handlers, records
+    at ERROR and above take a priority lane and can overtake lower ones.
+
+    Args:
+        capacity: Queue size, per lane (ERROR and above, everything else;
+            default: None, from logxide.preset(); 10000)
+        overflow: "block", "drop_newest" or "drop_oldest" when the queue is full
+            (default: None, the logxide.preset() overflow, else "block")
+        block_timeout: Seconds "block" waits for room before dropping the
+            record (default: None, no limit)
+    """
+
+    def __init__(self, capacity=None, overflow=None, block_timeout=None):
+        self._inner = logxide.QueueHandler(capacity, overflow, block_timeout)
+        # Skip QueueHandler.__init__: the Rust handler owns the queue.
+        logging.Handler.__init__(self)
+        self.queue = self._inner
+        self.listener = None
+
+    def _recompute_native(self):
+        self._inner.setPythonDispatch(not self._inner.setFilters(self.filters))
+
+    def setLevel(self, level):
+        super().setLevel(level)
+        self._inner.setLevel(level)
+
+    def addFilter(self, filter):
+        super().addFilter(filter)
+        self._recompute_native()
+
+    def removeFilter(self, filter):
+        super().removeFilter(filter)
+        self._recompute_native()
+
+    def emit(self, record):
+        try:
+            self._inner.emit(_prepare_record_for_rust(record, native=True))
+        except Exception:
+            self.handleError(record)
+
+    def flush(self):
+        """Wait until a running listener has delivered what is queued."""
+        self._inner.flush()
+
+    def get_metrics(self):
+        """
+        Return queue accounting for this handler.
+
+        Keys: emitted, plus the queue counters reported by ``queue_stats()``
+        (enqueued, dequeued, queue_dropped, queue_depth, ...).
+        """
+        return self._inner.get_metrics()
+
+
+class QueueListener(logging.handlers.QueueListener):
+    """
+    Deliver the records of a :class:`QueueHandler` to handlers on a Rust thread.
+
+    Mirrors ``logging.handlers.QueueListener``: ``start()`` launches the thread and
+    ``stop()`` delivers what is still queued, then joins it. Rust-backed handlers
+    are called without the GIL; other handlers get a stdlib ``LogRecord`` through
+    ``handle()``.
+
+    Args:
+        queue: The QueueHandler whose records to deliver
+        *handlers: Handlers to receive every record
+        respect_handler_level: Only pass a record to a handler at or above the
+            handler's level. Rust-backed handlers always apply their level.
+    """
+
+    def __init__(self, queue, *handlers, respect_handler_level=False):
+        self._inner = logxide.QueueListener(
+            queue, *handlers, respect_handler_level=respect_handler_level
+        )
+        self.queue = queue
+        self.handlers = handlers
+        self.respect_handler_level = respect_handler_level
+        self._thread = None
+
+    def start(self):
+        self._inner.start()
+
+    def stop(self):
+        self._inner.stop()
+
+    @property
+    def running(self):
+        """Whether the listener thread is running."""
+        return self._inner.running
+
+    def __enter__(self):
+        self.start()
+        return self
+
+    def __exit__(self, *exc_info):
+        self.stop()
+
+
+class AggregatingHandler(logging.Handler):
+    """
+    Count records per (logger, level, message template) over a tumbling window and
+    pass one record per combination to ``handlers`` when the window closes.
+
+    The message template is the unformatted ``msg``, so ``log.info("GET %s", path)``
+    calls are counted together whatever ``path`` is. With
+    ``logxide.configure_message_templates()`` on, the ``message_template`` extra is
+    used instead, which also groups f-string messages. A combination seen once is
+    passed on as logged; repeats become one record with the template and the count
+    in its message, e.g. ``GET %s (logged 1200 times in 60s)``. Every record passed
+    on carries ``aggregate_count``, ``aggregate_template``, ``aggregate_first`` and
+    ``aggregate_last`` (creation times) extras, for turning logs into metrics.
+
+    Rust-backed handlers get the records without the GIL; other handlers get a
+    stdlib ``LogRecord`` through ``handle()`` when at or above their level.
+    ``close()`` emits the open window early.
+
+    Args:
+        *handlers: Handlers that receive the aggregated records
+        window: Window length in seconds (default: 60)
+    """
+
+    def __init__(self, *handlers, window=60.0):
+        super().__init__()
+        self._inner = logxide.AggregatingHandler(*handlers, window=window)
+
+    @property
+    def handlers(self):
+        """The handlers that receive the aggregated records."""
+        return self._inner.handlers
+
+    @property
+    def window(self):
+        """Window length in seconds."""
+        return self._inner.window
+
+    def _recompute_native(self):
+        self._inner.setPythonDispatch(not self._inner.setFilters(self.filters))
+
+    def setLevel(self, level):
+        super().setLevel(level)
+        self._inner.setLevel(level)
+
+    def addFilter(self, filter):
+        super().addFilter(filter)
+        self._recompute_native()
+
+    def removeFilter(self, filter):
+        super().removeFilter(filter)
+        self._recompute_native()
+
+    def emit(self, record):
+        try:
+            self._inner.emit(_prepare_record_for_rust(record, native=True))
+        except Exception:
+            self.handleError(record)
+
+    def flush(self):
+        """Flush the target handlers; the open window keeps counting."""
+        self._inner.flush()
+
+    def close(self):
+        self._inner.shutdown()
+        super().close()
+
+
+class MemoryHandler(logging.Handler):
+    """
+    High-performance memory handler for testing and log capture.
+    Stores records in Rust native memory for maximum performance.
+
+    Provides pytest caplog-compatible properties:
+    - `.records`: List of LogRecord objects
+    - `.text`: All messages joined with newlines
+    - `.record_tuples`: List of (logger_name, level, message) tuples
+    """
+
+    def __init__(self):
+        super().__init__()
+        self._inner = logxide.MemoryHandler()
+
+    def _recompute_native(self):
+        self._inner.setPythonDispatch(not self._inner.setFilters(self.filters))
+
+    def setLevel(self, level):
+        super().setLevel(level)
+        self._inner.setLevel(level)
+
+    def addFilter(self, filter):
+        super().addFilter(filter)
+        self._recompute_native()
+
+    def removeFilter(self, filter):
+        super().removeFilter(filter)
+        self._recompute_native()
 
     def emit(self, record):
         try:
@@ -25,102 +25,17 @@ except ImportError:
 # Track existing Python loggers that need to be migrated to LogXide
 _existing_logger_registry = {}
 
-# Track the current LogXide configuration to apply to new loggers
-_current_config = {"level": None, "format": None, "datefmt": None}
-
-# Track whether basicConfig has been called to prevent duplicate handlers
-_basic_config_called = False
-
 
 def basicConfig(**kwargs):
     """
-    Basic configuration for the logging system.
-
-    Supported parameters:
-    - level: Set the effective level for the root logger
-    - format: Format string for log messages (not implemented in Rust handlers)
-    - datefmt: Date format string (not implemented in Rust handlers)
-    - stream: Stream to write log output to (sys.stdout or sys.stderr supported)
-    - filename: Log to a file instead of a stream
-    - force: If True, remove any existing handlers and reconfigure (default: False)
-
-    Note: LogXide uses Rust native handlers for performance. All handler
-    configuration is done through this function. Direct handler registration
-    via addHandler() is not supported.
-
-    Like Python's standard logging, basicConfig() will do nothing if the root
-    logger already has handlers configured, unless force=True is specified.
+    Configure the root logger, like logging.basicConfig().
+
+    Takes the stdlib arguments: filename, filemode, stream, handlers, format,
+    datefmt, style, level, force, encoding and errors. Like the stdlib, it does
+    nothing if the root logger already has handlers, unless force=True is
+    specified. The handler for a file, sys.stdout or sys.stderr writes from Rust.
     """
-    import sys
-
-    # Import logxide at the top of the function
-    from . import logxide as logxide_module
-
-    global _basic_config_called
-
-    # Check if already configured (unless force=True)
-    force = kwargs.get("force", False)
-    if _basic_config_called and not force:
-        return
-
-    # If force=True, clear existing handlers
-    if force and _basic_config_called:
-        with contextlib.suppress(ImportError, AttributeError):
-            logxide_module.logging.clear_handlers()
-
-    _basic_config_called = True
-
-    # Store configuration for applying to new loggers
-    _current_config["level"] = kwargs.get("level")
-    _current_config["format"] = kwargs.get("format")
-    _current_config["datefmt"] = kwargs.get("datefmt")
-
-    # Get configuration parameters
-    level = kwargs.get("level", 10)  # Default to DEBUG (10)
-    stream = kwargs.get("stream")
-    filename = kwargs.get("filename")
-
-    # Get format parameters
-    fmt = kwargs.get("format")
-    datefmt = kwargs.get("datefmt")
-
-    # Register appropriate Rust native handler
-    if filename:
-        # File handler
-        logxide_module.logging.register_file_handler(filename, level, fmt, datefmt)
-    else:
-        # Stream handler (stdout, stderr, or Python object like StringIO)
-        if stream is None:
-            # Default to stderr
-            logxide_module.logging.register_stream_handler(
-                "stderr",
-                level,
-                fmt,
-                datefmt,
-            )
-        elif stream is sys.stdout:
-            logxide_module.logging.register_stream_handler(
-                "stdout",
-                level,
-                fmt,
-                datefmt,
-            )
-        elif stream is sys.stderr:
-            logxide_module.logging.register_stream_handler(
-                "stderr",
-                level,
-                fmt,
-                datefmt,
-            )
-        else:
-            # Python file-like object (StringIO, file, etc.)
-            # Pass the Python object directly to Rust
-            logxide_module.logging.register_stream_handler(stream, level, fmt, datefmt)
-
-    # Set root logger level
-    root_logger = getLogger()
-    if hasattr(root_logger, "setLevel"):
-        root_logger.setLevel(level)
+    _rust_basicConfig(**kwargs)
 
     # Now handle existing Python loggers that were created before LogXide
     _migrate_existing_loggers()
@@ -179,11 +94,6 @@ def getLogger(name=None):
     # logger.handlers.clear() # Handlers are managed by the Rust side now
     # logger.propagate = True # Propagate is handled by Rust side now
 
-    # Apply the current configuration level if available
-    if _current_config["level"] is not None:
-        with contextlib.suppress(AttributeError):
-            logger.setLevel(_current_config["level"])
-
     # Set parent for non-root loggers
     if name and "." in name:
         parent_name = name.rsplit(".", 1)[0]
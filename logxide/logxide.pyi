@@ -0,0 +1,2156 @@
+# Generated by `cargo xtask stubs` from the #[pyclass], #[pymethods] and
+# #[pyfunction] items in src/. Do not edit: rerun it when they change.
+# Describes a build with every cargo feature enabled; classes and functions
+# of a disabled feature are missing at runtime.
+
+import os
+from pathlib import Path
+from typing import Any, Literal, TypeAlias
+
+# A level number, or one of the standard level names.
+Level: TypeAlias = int | Literal[
+    "CRITICAL",
+    "FATAL",
+    "ERROR",
+    "WARN",
+    "WARNING",
+    "INFO",
+    "DEBUG",
+    "NOTSET",
+]
+
+class PyLogger:
+    def emit_record(self, record: LogRecord, exc_info_py: Any) -> None: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def level(self) -> int: ...
+    @property
+    def handlers(self) -> Any: ...
+    @handlers.setter
+    def handlers(self, _handlers: Any) -> None: ...
+    @property
+    def filters(self) -> list[Any]:
+        """The filters added with `addFilter()`, in order. A copy: changing it doesn't
+        change the logger.
+        """
+    @property
+    def disabled(self) -> bool: ...
+    @disabled.setter
+    def disabled(self, value: bool) -> None: ...
+    @property
+    def propagate(self) -> bool: ...
+    @propagate.setter
+    def propagate(self, value: bool) -> None: ...
+    @property
+    def parent(self) -> Any: ...
+    @parent.setter
+    def parent(self, value: Any) -> None: ...
+    @property
+    def manager(self) -> Any: ...
+    @manager.setter
+    def manager(self, value: Any) -> None: ...
+    @property
+    def root(self) -> PyLogger: ...
+    def filter(self, record: LogRecord) -> bool:
+        """Whether `record` passes this logger's filters. A filter may change `record`."""
+    def setLevel(self, level: Level) -> None: ...
+    def getEffectiveLevel(self) -> int: ...
+    def describe(self) -> dict[str, Any]:
+        """Explain how this logger is configured: its own and effective level, handlers,
+        filters and propagate flag, plus the ancestors a record is handed to after it
+        (each with its level, propagate flag and handlers), ending at the root when
+        propagation reaches it.
+        """
+    def addHandler(self, handler: Any) -> None: ...
+    def removeHandler(self, handler: Any) -> None: ...
+    def addFilter(self, filter_obj: Any) -> None:
+        """Add a filter to this logger: a `logging.Filter`, any object with a
+        `filter(record)` method, or a callable taking the record. A false or None result
+        drops the record. Filters get the `LogRecord` and may change it (`record.msg`,
+        extras), or return a `LogRecord` to log instead. Adding the same filter twice
+        has no effect.
+        """
+    def removeFilter(self, filter_obj: Any) -> None:
+        """Remove a filter from this logger."""
+    def debug(self, msg: Any, *args: Any, **kwargs: Any) -> None: ...
+    def info(self, msg: Any, *args: Any, **kwargs: Any) -> None: ...
+    def warning(self, msg: Any, *args: Any, **kwargs: Any) -> None: ...
+    def error(self, msg: Any, *args: Any, **kwargs: Any) -> None: ...
+    def critical(self, msg: Any, *args: Any, **kwargs: Any) -> None: ...
+    def exception(self, msg: Any, *args: Any, **kwargs: Any) -> None: ...
+    def log(self, level: int, msg: Any, *args: Any, **kwargs: Any) -> None: ...
+    def makeRecord(
+        self,
+        name: str,
+        level: int,
+        fn_: str,
+        lno: int,
+        msg: Any,
+        args: Any,
+        exc_info: Any = None,
+    ) -> Any: ...
+    def handle(self, record: Any) -> None: ...
+    def callHandlers(self, record: Any) -> None: ...
+    def getChild(self, suffix: str) -> PyLogger: ...
+    def getChildren(self) -> Any:
+        """The existing loggers one level below this one, as a set, like
+        `logging.Logger.getChildren`: `app.db` and `app.api` for `app`, but not
+        `app.db.pool`, even while `app.db` doesn't exist.
+        """
+    def log_batch(
+        self,
+        level: Level,
+        messages: Any,
+        extra: dict[str, Any] | None = None,
+    ) -> None:
+        """Log many pre-formed messages at one level: every record is built under this one
+        GIL hold (sharing caller info and `extra`), then dispatched together so each
+        queued handler takes the run as a single queue push.
+        """
+    def timer(
+        self,
+        name: str,
+        level: Level | None = None,
+        extra: dict[str, Any] | None = None,
+        log_start: bool = True,
+    ) -> Timer:
+        """Time a block or function: logs "<name> started" and "<name> finished in Xms"
+        with the elapsed milliseconds in the `elapsed_ms` extra.
+        """
+    def progress(
+        self,
+        iterable: Any,
+        name: str | None = None,
+        total: int | None = None,
+        every: int | None = None,
+        seconds: float | None = None,
+        level: Level | None = None,
+    ) -> Progress:
+        """Wrap `iterable`, logging throttled progress (count, rate, ETA) every `every`
+        items and/or `seconds` of wall time, plus a summary when it is exhausted.
+        """
+    def bind(self, **context: Any) -> PyLogger:
+        """A view of this logger whose records all carry `context` as extras, on top of
+        any context bound already; a call's own `extra` wins over both. The view
+        shares the logger's level, handlers and filters, and binding is cheap: the
+        context is converted once and kept on the Rust side. `getChild()` on a view
+        returns the plain child logger.
+        """
+    @property
+    def context(self) -> dict[str, Any]:
+        """The extras bound with `bind()`, as a dict (empty for the logger itself)."""
+    def isEnabledFor(self, level: int) -> bool: ...
+
+class LogRecord:
+    """Complete log record structure for compatibility with Python logging."""
+    name: str
+    levelno: int
+    levelname: str
+    pathname: str
+    filename: str
+    module: str
+    lineno: int
+    func_name: str
+    created: float
+    msecs: float
+    relative_created: float
+    thread: int
+    thread_name: str
+    process_name: str
+    process: int
+    ppid: int
+    """Id of the parent process; 0 where the platform has none."""
+    thread_native_id: int
+    """OS id of the logging thread, as `threading.get_native_id()` reports it; 0
+    where the platform has none.
+    """
+    seq: int
+    """Position of the record in this process's sequence, from 1; 0 for a record
+    built from Python rather than logged.
+    """
+    msg: str
+    exc_info: str | None
+    exc_text: str | None
+    stack_info: str | None
+    task_name: str | None
+    def __init__(
+        self,
+        name: str,
+        levelno: int,
+        pathname: str,
+        lineno: int,
+        msg: str,
+        args: Any = None,
+        exc_info: str | None = None,
+        func_name: str = "",
+        stack_info: str | None = None,
+    ) -> None: ...
+    @property
+    def args(self) -> Any: ...
+    @args.setter
+    def args(self, value: Any) -> None: ...
+    def getMessage(self) -> str: ...
+    @property
+    def message(self) -> str: ...
+    def __repr__(self) -> str:
+        """`<LogRecord: name, levelno, pathname, lineno, "msg">`, as in the stdlib."""
+    def __str__(self) -> str: ...
+    def __eq__(self, other: object) -> bool:
+        """Records are equal when they carry the same event: logger, level, call site,
+        merged message, exception and stack text, and extras. When and where it was
+        logged (`created`, thread and process fields, `seq`, the `run_id` extra) is ignored, so
+        a captured record can be compared against one built in a test.
+        """
+    def __getattr__(self, name: str) -> Any: ...
+    @property
+    def funcName(self) -> str: ...
+    @property
+    def relativeCreated(self) -> float: ...
+    @property
+    def threadName(self) -> str: ...
+    @property
+    def processName(self) -> str: ...
+    def __setattr__(self, name: str, value: Any) -> None: ...
+    def __getstate__(self) -> dict[str, Any]:
+        """Every field under its Rust name, `args` as Python values and the extras as
+        one `extra` dict. `__setstate__` takes the same dict back.
+        """
+    def __setstate__(self, state: dict[str, Any]) -> None: ...
+    def __reduce__(self) -> tuple[Any, tuple[Any, ...], dict[str, Any]]:
+        """Pickle support, for `QueueHandler` and multiprocessing: rebuilt from the
+        constructor arguments, then every field and extra from `__getstate__`.
+        """
+    @property
+    def __dict__(self) -> Any: ...
+
+class Filter:
+    """Passes records from the logger `name` and its children, like `logging.Filter`:
+    `Filter("app.db")` passes `app.db` and `app.db.pool` but not `app.dbx`. An empty
+    name passes everything.
+
+    A logger whose filters are all Rust filters (`logxide.Filter`, `logxide.RegexFilter`)
+    runs them in Rust, so its records keep the native dispatch path; other filters are
+    called with a Python record.
+
+    Args:
+        name: Logger name whose records (and its children's) pass.
+    """
+    def __init__(self, name: str = "") -> None: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def nlen(self) -> int: ...
+    def filter(self, record: Any) -> bool:
+        """Whether `record` (any object with a `name` attribute) passes."""
+    def __repr__(self) -> str: ...
+
+class RegexFilter:
+    """Passes or drops records by matching a regex against one of their fields.
+
+    `field` is `"msg"` (the message before `%` formatting), `"name"` (the logger name)
+    or the name of an `extra=` field; extras that are not strings are matched as JSON
+    text. The regex is searched for anywhere in the value, like `re.search`. With
+    `mode="include"` only matching records pass; with `mode="exclude"` matching records
+    are dropped. A record without the field does not match.
+
+    Runs in Rust like `logxide.Filter`, so loggers whose filters are all Rust filters
+    keep the native dispatch path.
+
+    Args:
+        pattern: Regex in the syntax of the Rust `regex` crate.
+        field: `"msg"`, `"name"` or an extra field name.
+        mode: `"include"` or `"exclude"`.
+    """
+    def __init__(self, pattern: str, field: str = "msg", mode: str = "include") -> None: ...
+    @property
+    def pattern(self) -> str: ...
+    @property
+    def field(self) -> str: ...
+    @property
+    def mode(self) -> str: ...
+    def filter(self, record: Any) -> bool:
+        """Whether `record` passes. Records that are not logxide `LogRecord`s are matched
+        on `str()` of the attribute named by `field`.
+        """
+    def __repr__(self) -> str: ...
+
+class LevelRangeFilter:
+    """Passes records whose level is between `min_level` and `max_level`, both included,
+    so handlers can split a stream by level: INFO..WARNING to stdout and ERROR and
+    above to stderr. `max_level=None` has no upper bound.
+
+    Runs in Rust on loggers and on logxide handlers, which keep their native dispatch
+    path when all their filters are Rust filters.
+
+    Args:
+        min_level: Lowest level passed, as a number or name.
+        max_level: Highest level passed, as a number or name, or None.
+    """
+    def __init__(
+        self,
+        min_level: Level | None = None,
+        max_level: Level | None = None,
+    ) -> None: ...
+    @property
+    def min_level(self) -> int: ...
+    @property
+    def max_level(self) -> int | None: ...
+    def filter(self, record: Any) -> bool:
+        """Whether `record` (any object with a `levelno` attribute) passes."""
+    def __repr__(self) -> str: ...
+
+class DedupFilter:
+    """Collapses consecutive identical messages, journald-style: the first record of a run
+    passes, identical records (same logger, level and formatted message) within
+    `window_seconds` of it are dropped, and when the window closes or a different
+    message arrives one summary record is logged, `"<message> (repeated N more
+    times)"` with `dedup_count` (N), `dedup_first` and `dedup_last` extras.
+
+    The summary is logged through the logger of the run's records, so it reaches the
+    same handlers; a summary that closes a run arrives before the record that ended
+    it. A background thread closes windows when no further record arrives; wasm32
+    builds have none, so there a run ends only with the next record.
+
+    Args:
+        window_seconds: How long after the first record of a run repeats are folded
+            into its summary.
+    """
+    def __init__(self, window_seconds: float) -> None: ...
+    @property
+    def window_seconds(self) -> float: ...
+    def filter(self, record: Any) -> bool:
+        """Whether `record` passes: False for a repeat inside the window. A record that
+        ends a run first logs the run's summary.
+        """
+    def __repr__(self) -> str: ...
+
+class Redactor:
+    """Replaces personal data and secrets in records with a placeholder.
+
+    The built-in `detectors` are `"email"`, `"credit_card"` (Luhn-checked), `"ssn"` and
+    `"bearer"` (the token after `Bearer `); `rules` adds regexes of your own. When a
+    pattern has a capture group, only the first group is replaced. The message, its
+    string arguments and string extras are rewritten, in Rust.
+
+    Pass one to `configure_redaction()` to redact every record logged through logxide
+    loggers, or add it to a handler with `addFilter()` to redact only what that
+    handler writes.
+
+    Args:
+        detectors: Built-in detectors to run; None runs all of them.
+        rules: Extra regexes, in the syntax of the Rust `regex` crate.
+        replacement: Text each match is replaced with.
+    """
+    def __init__(
+        self,
+        detectors: list[str] | None = None,
+        rules: list[str] = [],
+        replacement: str = ...,
+    ) -> None: ...
+    @property
+    def detectors(self) -> list[str]: ...
+    @property
+    def rules(self) -> list[str]: ...
+    @property
+    def replacement(self) -> str: ...
+    def redact(self, text: str) -> str:
+        """`text` with every match replaced."""
+    def filter(self, record: Any) -> bool:
+        """Redact `record` in place and pass it, for loggers, Python handlers and handlers
+        that fall back to Python dispatch.
+        """
+    def __repr__(self) -> str: ...
+
+class FieldProjection:
+    """Keep the extras and optional fields listed in `include` (all of them when None),
+    minus those in `exclude`, on each record a handler writes.
+
+    Fields every record has always stay. Add it to a handler with `addFilter()`; a
+    logxide handler applies it in Rust to its own copy of the record.
+
+    Args:
+        include: Extras and optional fields to keep; None keeps all of them.
+        exclude: Extras and optional fields to remove.
+    """
+    def __init__(
+        self,
+        include: list[str] | None = None,
+        exclude: list[str] = [],
+    ) -> None: ...
+    @property
+    def include(self) -> list[str] | None: ...
+    @property
+    def exclude(self) -> list[str]: ...
+    def filter(self, record: Any) -> bool:
+        """Project `record` in place and pass it, for loggers, Python handlers and
+        handlers that fall back to Python dispatch.
+        """
+    def __repr__(self) -> str: ...
+
+class Formatter:
+    """Python binding for PythonFormatter.
+    Standard Python logging-compatible formatter.
+    """
+    def __init__(
+        self,
+        fmt: str | None = None,
+        datefmt: str | None = None,
+        timestamps: str = "absolute",
+        style: str = "%",
+        defaults: dict[str, Any] | None = None,
+    ) -> None:
+        """Create a new Formatter with the specified format string.
+
+        Args:
+            fmt: Python-style format string with %(field)s placeholders, or in
+                 `style`. Defaults to the message alone.
+            datefmt: Optional strftime format for %(asctime)s
+            timestamps: What %(asctime)s shows: "absolute" (wall-clock time),
+                 "relative" (+12.345s since logging started) or "delta"
+                 (+350ms since the previous record)
+            style: "%" for %(field)s, "{" for {field} (str.format) or "$" for
+                 $field / ${field} (string.Template). `{` fields may have an
+                 alignment, width and `s` or `d` type such as {levelname:<8}; other
+                 format specs raise ValueError.
+            defaults: {field: value} used for fields a record doesn't have, such as
+                 {"app": "svc-a"} for %(app)s. The record's extras take precedence.
+        """
+    def format(self, record: LogRecord) -> str:
+        """Format a log record."""
+
+class ColorFormatter:
+    """Python binding for ColorFormatter.
+    Supports ANSI color codes for terminal output.
+
+    Additional format placeholders:
+    - %(ansi_level_color)s: ANSI color code for the log level
+    - %(ansi_reset_color)s: ANSI reset code
+
+    Example:
+        formatter = ColorFormatter(
+            "%(ansi_level_color)s%(levelname)s%(ansi_reset_color)s - %(message)s",
+            level_badges=True,
+        )
+    """
+    def __init__(
+        self,
+        fmt: str = "%(ansi_level_color)s%(levelname)s%(ansi_reset_color)s - %(message)s",
+        datefmt: str | None = None,
+        level_badges: Any = None,
+        timestamps: str = "absolute",
+    ) -> None:
+        """Create a new ColorFormatter with ANSI color support.
+
+        Args:
+            fmt: Format string with %(field)s placeholders.
+                 Use %(ansi_level_color)s and %(ansi_reset_color)s for colors.
+            datefmt: Optional strftime format for %(asctime)s
+            level_badges: Prefix each line with a badge for its level. True uses
+                 the defaults, or pass a dict of level name to badge. Left off when
+                 sys.stdout or sys.stderr can't encode the badges.
+            timestamps: What %(asctime)s shows: "absolute", "relative" or "delta"
+        """
+    def format(self, record: LogRecord) -> str:
+        """Format a log record with ANSI colors."""
+    @property
+    def level_badges(self) -> dict[str, str]:
+        """The level badges in use, as {level name: badge}. Empty when off."""
+
+class ColumnFormatter:
+    """Python binding for ColumnFormatter.
+    Keeps the level and logger name columns at fixed widths, so interleaved output
+    from many loggers lines up. Longer values are truncated and end in "…".
+
+    Example:
+        handler.setFormatter(ColumnFormatter(level_width=8, name_width=16))
+    """
+    def __init__(
+        self,
+        fmt: str | None = None,
+        datefmt: str | None = None,
+        level_width: int = 8,
+        name_width: int = 20,
+        timestamps: str = "absolute",
+    ) -> None:
+        """Create a new ColumnFormatter.
+
+        Args:
+            fmt: Format string with %(field)s placeholders
+                 (default: "%(asctime)s %(levelname)s %(name)s %(message)s")
+            datefmt: Optional strftime format for %(asctime)s
+            level_width: Width of the %(levelname)s column (0 = not a column)
+            name_width: Width of the %(name)s column (0 = not a column)
+            timestamps: What %(asctime)s shows: "absolute", "relative" or "delta"
+        """
+    def format(self, record: LogRecord) -> str:
+        """Format a log record into fixed-width columns."""
+
+class JsonFormatter:
+    """Python binding for JsonFormatter.
+    Writes each record as one JSON object: the standard LogRecord attributes, then
+    the `extra` fields with their JSON types.
+
+    Example:
+        handler.setFormatter(JsonFormatter(
+            fields=["created", "levelname", "message", "request_id"],
+            rename={"created": "ts", "levelname": "level"},
+        ))
+    """
+    def __init__(
+        self,
+        fields: list[str] | None = None,
+        rename: dict[str, str] | None = None,
+        datefmt: str | None = None,
+        canonical: bool = False,
+        schema_version: int | None = None,
+    ) -> None:
+        """Create a new JsonFormatter.
+
+        Args:
+            fields: Keys to write, in order; standard attributes and extras alike.
+                 None writes every standard attribute, then all extras.
+            rename: Dict of field name to the key it is written under
+            datefmt: strftime format for an "asctime" field, which is added to
+                 the default fields when given
+            canonical: Sort keys, write floats with six decimals, pin the
+                 timestamps to the epoch and seq to 0, so reruns give identical output
+            schema_version: Integer written first in every object as
+                 "schema_version", so consumers can detect layout changes
+        """
+    @property
+    def fields(self) -> list[str]:
+        """The fields written, in order, before any extras."""
+    @property
+    def canonical(self) -> bool:
+        """Whether the formatter writes canonical JSON."""
+    @property
+    def schema_version(self) -> int | None:
+        """The `schema_version` written in every object, or None."""
+    def format(self, record: LogRecord) -> str:
+        """Format a log record as a JSON object."""
+
+class FileHandler:
+    def __init__(
+        self,
+        filename: str | bytes | os.PathLike[str] | os.PathLike[bytes],
+        watch: bool = False,
+        checksum_every: int | None = None,
+        index_every: int | None = None,
+    ) -> None:
+        """`watch=True` checks before each write that `filename` still names the open file
+        and reopens it if it was moved or deleted, as `WatchedFileHandler` does.
+        `checksum_every=N` writes a Blake3 checksum trailer after every N records and on
+        flush, for `verify_log_file()`. `index_every=N` adds every Nth record's time and
+        offset to a sidecar index, `<filename>.idx`, for reading time ranges.
+        """
+    def setLevel(self, level: Level) -> None: ...
+    def setFlushLevel(self, level: int) -> None:
+        """Set the flush level. Records at or above this level trigger immediate flush.
+        Default is ERROR (40).
+        """
+    def getFlushLevel(self) -> int:
+        """Get the current flush level."""
+    def setErrorCallback(self, callback: Any) -> None:
+        """Set an error callback function."""
+    def flush(self) -> None: ...
+    def emit(self, record: Any) -> None: ...
+    def setFormatterSpec(
+        self,
+        fmt: str | None = None,
+        datefmt: str | None = None,
+        style: str = "%",
+        defaults: dict[str, Any] | None = None,
+    ) -> None: ...
+    def setFormatter(self, formatter: Any) -> None:
+        """Format records with a native `Formatter`, `ColorFormatter`, `ColumnFormatter`
+        or `JsonFormatter` object.
+        """
+    def setSanitize(self, policy: str | None = None, newlines: bool = True) -> None:
+        """Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+        text; None turns sanitization off. `newlines=False` also treats newlines and
+        tabs as control characters, keeping each record on one line.
+        """
+    def setFilters(self, filters: list[Any]) -> bool:
+        """Run `filters` in Rust and return True when they are all Rust filters
+        (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+        otherwise return False and leave them to the wrapper's `handle()`.
+        """
+    def setPythonDispatch(self) -> None: ...
+    def isNative(self) -> bool: ...
+
+class StreamHandler:
+    def __init__(
+        self,
+        stream: str | None = None,
+        overflow: str | None = None,
+        block_timeout: float | None = None,
+    ) -> None:
+        """`overflow` is "drop_newest" (the default), "drop_oldest" or "block" for a
+        full queue; `block_timeout` is how many seconds "block" waits for room.
+        """
+    def setLevel(self, level: Level) -> None: ...
+    def setErrorCallback(self, callback: Any) -> None:
+        """Set an error callback function."""
+    def emit(self, record: Any) -> None: ...
+    def setFormatterSpec(
+        self,
+        fmt: str | None = None,
+        datefmt: str | None = None,
+        style: str = "%",
+        defaults: dict[str, Any] | None = None,
+    ) -> None: ...
+    def setTtyFormatterSpec(
+        self,
+        fmt: str | None = None,
+        datefmt: str | None = None,
+        style: str = "%",
+        defaults: dict[str, Any] | None = None,
+    ) -> None:
+        """Format records with `fmt` while the destination is a terminal."""
+    def setTtyFormatter(self, formatter: Any = None) -> None:
+        """Use a native formatter object while the destination is a terminal, or None
+        to always use the regular formatter.
+        """
+    def setFormatter(self, formatter: Any) -> None:
+        """Format records with a native `Formatter`, `ColorFormatter`, `ColumnFormatter`
+        or `JsonFormatter` object.
+        """
+    def setSanitize(self, policy: str | None = None, newlines: bool = True) -> None:
+        """Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+        text; None turns sanitization off. `newlines=False` also treats newlines and
+        tabs as control characters, keeping each record on one line.
+        """
+    def setFilters(self, filters: list[Any]) -> bool:
+        """Run `filters` in Rust and return True when they are all Rust filters
+        (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+        otherwise return False and leave them to the wrapper's `handle()`.
+        """
+    def setPythonDispatch(self) -> None: ...
+    def isNative(self) -> bool: ...
+    def isTerminal(self) -> bool:
+        """Whether the destination stream is currently a terminal."""
+    def get_metrics(self) -> dict[str, Any]: ...
+    def setSyncLevel(self, level: Level | None = None) -> None:
+        """Records at or above `level` bypass the queue and are written and flushed
+        before `emit()` returns. None (the default) turns this off.
+        """
+    def getSyncLevel(self) -> int | None:
+        """Get the current sync level, or None when records are always queued."""
+
+class RotatingFileHandler:
+    def __init__(
+        self,
+        filename: str | bytes | os.PathLike[str] | os.PathLike[bytes],
+        max_bytes: int = 10485760,
+        backup_count: int = 5,
+        max_age_days: float | None = None,
+        max_total_bytes: int | None = None,
+        checksum_every: int | None = None,
+        index_every: int | None = None,
+    ) -> None:
+        """`max_age_days` and `max_total_bytes` delete backups that are too old, or the
+        oldest ones while the file and its backups exceed the budget, after each
+        rotation. `checksum_every` and `index_every` work as for `FileHandler`; each
+        backup ends with a trailer and keeps its index.
+        """
+    def setLevel(self, level: Level) -> None: ...
+    def setFlushLevel(self, level: int) -> None:
+        """Set the flush level. Records at or above this level trigger immediate flush."""
+    def getFlushLevel(self) -> int:
+        """Get the current flush level."""
+    def setErrorCallback(self, callback: Any) -> None:
+        """Set an error callback function."""
+    def flush(self) -> None: ...
+    def emit(self, record: Any) -> None: ...
+    def setFormatterSpec(
+        self,
+        fmt: str | None = None,
+        datefmt: str | None = None,
+        style: str = "%",
+        defaults: dict[str, Any] | None = None,
+    ) -> None: ...
+    def setFormatter(self, formatter: Any) -> None:
+        """Format records with a native `Formatter`, `ColorFormatter`, `ColumnFormatter`
+        or `JsonFormatter` object.
+        """
+    def setSanitize(self, policy: str | None = None, newlines: bool = True) -> None:
+        """Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+        text; None turns sanitization off. `newlines=False` also treats newlines and
+        tabs as control characters, keeping each record on one line.
+        """
+    def setFilters(self, filters: list[Any]) -> bool:
+        """Run `filters` in Rust and return True when they are all Rust filters
+        (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+        otherwise return False and leave them to the wrapper's `handle()`.
+        """
+    def setPythonDispatch(self) -> None: ...
+    def isNative(self) -> bool: ...
+
+class TimedRotatingFileHandler:
+    def __init__(
+        self,
+        filename: str | bytes | os.PathLike[str] | os.PathLike[bytes],
+        when: str = "h",
+        interval: int = 1,
+        backup_count: int = 0,
+        utc: bool = False,
+        at_time: int | None = None,
+        compress: bool = False,
+        max_age_days: float | None = None,
+        max_total_bytes: int | None = None,
+        checksum_every: int | None = None,
+        index_every: int | None = None,
+    ) -> None:
+        """`when`, `interval`, `utc` and `at_time` (seconds after midnight) follow
+        `logging.handlers.TimedRotatingFileHandler`. `compress` gzips each backup on a
+        background thread. `max_age_days`, `max_total_bytes`, `checksum_every` and
+        `index_every` work as for `RotatingFileHandler`; gzipped backups keep no index.
+        """
+    @property
+    def rolloverAt(self) -> int:
+        """Unix time (seconds) of the next rollover."""
+    def setLevel(self, level: Level) -> None: ...
+    def setFlushLevel(self, level: int) -> None:
+        """Set the flush level. Records at or above this level trigger immediate flush."""
+    def getFlushLevel(self) -> int:
+        """Get the current flush level."""
+    def setErrorCallback(self, callback: Any) -> None:
+        """Set an error callback function."""
+    def flush(self) -> None: ...
+    def emit(self, record: Any) -> None: ...
+    def setFormatterSpec(
+        self,
+        fmt: str | None = None,
+        datefmt: str | None = None,
+        style: str = "%",
+        defaults: dict[str, Any] | None = None,
+    ) -> None: ...
+    def setFormatter(self, formatter: Any) -> None:
+        """Format records with a native `Formatter`, `ColorFormatter`, `ColumnFormatter`
+        or `JsonFormatter` object.
+        """
+    def setSanitize(self, policy: str | None = None, newlines: bool = True) -> None:
+        """Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+        text; None turns sanitization off. `newlines=False` also treats newlines and
+        tabs as control characters, keeping each record on one line.
+        """
+    def setFilters(self, filters: list[Any]) -> bool:
+        """Run `filters` in Rust and return True when they are all Rust filters
+        (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+        otherwise return False and leave them to the wrapper's `handle()`.
+        """
+    def setPythonDispatch(self) -> None: ...
+    def isNative(self) -> bool: ...
+
+class LevelRoutedFileHandler:
+    def __init__(self, routes: dict[str, Any]) -> None:
+        """`routes` maps the lowest level of each band (int or level name) to the native
+        `FileHandler`, `RotatingFileHandler` or `TimedRotatingFileHandler` that writes it.
+        A record goes to the band with the highest level at or below its own.
+        """
+    def setLevel(self, level: Level) -> None: ...
+    def setFlushLevel(self, level: int) -> None:
+        """Set the flush level of every file. Records at or above this level trigger
+        immediate flush. Default is ERROR (40).
+        """
+    def getFlushLevel(self) -> int:
+        """Get the current flush level."""
+    def flush(self) -> None: ...
+    def emit(self, record: Any) -> None: ...
+    def setFormatterSpec(
+        self,
+        fmt: str | None = None,
+        datefmt: str | None = None,
+        style: str = "%",
+        defaults: dict[str, Any] | None = None,
+    ) -> None: ...
+    def setFormatter(self, formatter: Any) -> None:
+        """Format records with a native `Formatter`, `ColorFormatter`, `ColumnFormatter`
+        or `JsonFormatter` object.
+        """
+    def setSanitize(self, policy: str | None = None, newlines: bool = True) -> None:
+        """Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+        text; None turns sanitization off. `newlines=False` also treats newlines and
+        tabs as control characters, keeping each record on one line.
+        """
+    def setFilters(self, filters: list[Any]) -> bool:
+        """Run `filters` in Rust and return True when they are all Rust filters
+        (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+        otherwise return False and leave them to the wrapper's `handle()`.
+        """
+    def setPythonDispatch(self) -> None: ...
+    def isNative(self) -> bool: ...
+
+class HTTPHandler:
+    def __init__(
+        self,
+        url: str,
+        headers: dict[str, str] | None = None,
+        capacity: int | None = None,
+        batch_size: int | None = None,
+        flush_interval: int | None = None,
+        global_context: dict[str, Any] | None = None,
+        transform_callback: Any = None,
+        context_provider: Any = None,
+        error_callback: Any = None,
+        overflow: str | None = None,
+        block_timeout: float | None = None,
+        shutdown_timeout: float | None = ...,
+        spill_path: str | os.PathLike[str] | None = None,
+        daemon: bool | None = None,
+        sync_level: Level | None = None,
+        method: str | None = None,
+        credentials: tuple[str, str] | None = None,
+        compression: str | None = None,
+        compression_min_bytes: int = 1024,
+        max_batch_bytes: int | None = None,
+        idempotency_keys: bool = False,
+        send_time: bool = False,
+        max_backdate: float | None = None,
+        dry_run: Any = None,
+        record_transform: Any = None,
+        context_ttl: float | None = None,
+    ) -> None: ...
+    def setLevel(self, level: Level) -> None: ...
+    def flush(self) -> None: ...
+    def shutdown(self) -> None: ...
+    @property
+    def daemon(self) -> bool:
+        """Whether the worker is a daemon (abandoned at interpreter exit instead of drained)."""
+    def get_metrics(self) -> dict[str, Any]: ...
+    def update_context(self, context: dict[str, Any], replace: bool = False) -> None:
+        """Merge `context` into the fields added to every record from the next batch on,
+        or replace the fields pushed so far when `replace` is true.
+        """
+    def setSyncLevel(self, level: Level | None = None) -> None:
+        """Records at or above `level` bypass the queue and are written and flushed
+        before `emit()` returns. None (the default) turns this off.
+        """
+    def getSyncLevel(self) -> int | None:
+        """Get the current sync level, or None when records are always queued."""
+    def setFlushLevel(self, level: int) -> None:
+        """Set the flush level. Records at or above this level trigger immediate flush.
+        Default is ERROR (40). Use logging.CRITICAL (50) to flush only on critical.
+        Use logging.DEBUG (10) to flush on every record.
+        """
+    def getFlushLevel(self) -> int:
+        """Get the current flush level."""
+    def emit(self, record: Any) -> None: ...
+    def setFilters(self, filters: list[Any]) -> bool:
+        """Run `filters` in Rust and return True when they are all Rust filters
+        (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+        otherwise return False and leave them to the wrapper's `handle()`.
+        """
+    def setPythonDispatch(self, enabled: bool = True) -> None:
+        """Route records through the wrapper's `handle()` (it has filters) or not."""
+    def isNative(self) -> bool: ...
+
+class DatagramHandler:
+    def __init__(
+        self,
+        host: str,
+        port: int | None = None,
+        format: str = "pickle",
+        max_packet_size: int = ...,
+        truncate: bool = False,
+        error_callback: Any = None,
+    ) -> None:
+        """`port=None` makes `host` the path of a Unix datagram socket, as in the stdlib.
+        A payload over `max_packet_size` bytes is dropped, or with `truncate` sent
+        with its message shortened to fit.
+        """
+    def setLevel(self, level: Level) -> None: ...
+    def flush(self) -> None: ...
+    def shutdown(self) -> None: ...
+    def get_metrics(self) -> dict[str, Any]: ...
+    def emit(self, record: Any) -> None: ...
+    def setFormatterSpec(
+        self,
+        fmt: str | None = None,
+        datefmt: str | None = None,
+        style: str = "%",
+        defaults: dict[str, Any] | None = None,
+    ) -> None:
+        """Formatter for `format="text"` payloads; the other formats ignore it."""
+    def setSanitize(self, policy: str | None = None, newlines: bool = True) -> None:
+        """Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+        text; None turns sanitization off. `newlines=False` also treats newlines and
+        tabs as control characters, keeping each record on one line.
+        """
+    def setFilters(self, filters: list[Any]) -> bool:
+        """Run `filters` in Rust and return True when they are all Rust filters
+        (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+        otherwise return False and leave them to the wrapper's `handle()`.
+        """
+    def setPythonDispatch(self) -> None: ...
+
+class SyslogHandler:
+    def __init__(
+        self,
+        host: str = "/dev/log",
+        port: int | None = None,
+        socktype: str | None = None,
+        protocol: str = "rfc3164",
+        facility: int = 1,
+        app_name: str | None = None,
+        hostname: str | None = None,
+        error_callback: Any = None,
+    ) -> None:
+        """`port=None` makes `host` the path of a local syslog socket such as `/dev/log`.
+        `socktype` is "dgram" (UDP) or "stream" (TCP); for a local socket None tries a
+        datagram socket, then a stream one. `app_name` defaults to the script name and
+        `hostname` to `socket.gethostname()`.
+        """
+    def setLevel(self, level: Level) -> None: ...
+    def flush(self) -> None: ...
+    def shutdown(self) -> None: ...
+    def get_metrics(self) -> dict[str, Any]: ...
+    def emit(self, record: Any) -> None: ...
+    def setFormatterSpec(
+        self,
+        fmt: str | None = None,
+        datefmt: str | None = None,
+        style: str = "%",
+        defaults: dict[str, Any] | None = None,
+    ) -> None: ...
+    def setSanitize(self, policy: str | None = None, newlines: bool = True) -> None:
+        """Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+        text; None turns sanitization off. `newlines=False` also treats newlines and
+        tabs as control characters, keeping each record on one line.
+        """
+    def setFilters(self, filters: list[Any]) -> bool:
+        """Run `filters` in Rust and return True when they are all Rust filters
+        (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+        otherwise return False and leave them to the wrapper's `handle()`.
+        """
+    def setPythonDispatch(self) -> None: ...
+    def isNative(self) -> bool: ...
+    def buildMessage(self, record: LogRecord, body: str) -> str:
+        """The message that would be sent for `record` with `body` as its text."""
+
+class SocketHandler:
+    def __init__(
+        self,
+        host: str,
+        port: int | None = None,
+        retry_start: float = 1.0,
+        retry_factor: float = 2.0,
+        retry_max: float = 30.0,
+        error_callback: Any = None,
+    ) -> None:
+        """`port=None` makes `host` the path of a Unix stream socket, as in the stdlib. The
+        connection is opened by the first record, so the receiver need not be up yet.
+        """
+    def setLevel(self, level: Level) -> None: ...
+    def flush(self) -> None: ...
+    def close(self) -> None:
+        """Close the connection. A later record opens a new one."""
+    def shutdown(self) -> None: ...
+    def get_metrics(self) -> dict[str, Any]: ...
+    def emit(self, record: Any) -> None: ...
+    def setFilters(self, filters: list[Any]) -> bool:
+        """Run `filters` in Rust and return True when they are all Rust filters
+        (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+        otherwise return False and leave them to the wrapper's `handle()`.
+        """
+    def setPythonDispatch(self, enabled: bool = True) -> None:
+        """Route records through the wrapper's `handle()` (it has filters) or not."""
+    def isNative(self) -> bool: ...
+
+class SMTPHandler:
+    def __init__(
+        self,
+        host: str,
+        fromaddr: str,
+        toaddrs: list[str],
+        subject: str,
+        port: int | None = None,
+        security: str = "none",
+        credentials: tuple[str, str] | None = None,
+        timeout: float = 5.0,
+        local_hostname: str | None = None,
+        error_callback: Any = None,
+    ) -> None:
+        """`security` is "none", "starttls" or "tls" (implicit TLS). `port` defaults to
+        465 for "tls" and 25 otherwise; `local_hostname` (the EHLO name) to
+        `socket.getfqdn()`. `credentials` is a (username, password) pair.
+        """
+    def setLevel(self, level: Level) -> None: ...
+    def flush(self) -> None: ...
+    def shutdown(self) -> None: ...
+    def get_metrics(self) -> dict[str, Any]: ...
+    def getSubject(self, record: Any) -> str:
+        """The subject template rendered for `record`."""
+    def emit(self, record: Any, subject: str | None = None) -> None:
+        """Mail `record`. `subject` overrides the rendered subject template."""
+    def setFormatterSpec(
+        self,
+        fmt: str | None = None,
+        datefmt: str | None = None,
+        style: str = "%",
+        defaults: dict[str, Any] | None = None,
+    ) -> None: ...
+    def setSanitize(self, policy: str | None = None, newlines: bool = True) -> None:
+        """Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+        text; None turns sanitization off. `newlines=False` also treats newlines and
+        tabs as control characters, keeping each record on one line.
+        """
+    def setFilters(self, filters: list[Any]) -> bool:
+        """Run `filters` in Rust and return True when they are all Rust filters
+        (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+        otherwise return False and leave them to the wrapper's `handle()`.
+        """
+    def setPythonDispatch(self) -> None: ...
+
+class OTLPHandler:
+    def __init__(
+        self,
+        url: str,
+        headers: dict[str, str] | None = None,
+        service_name: str = "unknown_service",
+        capacity: int | None = None,
+        batch_size: int | None = None,
+        flush_interval: int | None = None,
+        error_callback: Any = None,
+        overflow: str | None = None,
+        block_timeout: float | None = None,
+        shutdown_timeout: float | None = ...,
+        spill_path: str | os.PathLike[str] | None = None,
+        daemon: bool | None = None,
+        sync_level: Level | None = None,
+        compression: str | None = None,
+        compression_min_bytes: int = 1024,
+        max_batch_bytes: int | None = None,
+        idempotency_keys: bool = False,
+        send_time: bool = False,
+        max_backdate: float | None = None,
+        dry_run: Any = None,
+    ) -> None: ...
+    def setLevel(self, level: Level) -> None: ...
+    def flush(self) -> None: ...
+    def shutdown(self) -> None: ...
+    @property
+    def daemon(self) -> bool:
+        """Whether the worker is a daemon (abandoned at interpreter exit instead of drained)."""
+    def get_metrics(self) -> dict[str, Any]: ...
+    def setSyncLevel(self, level: Level | None = None) -> None:
+        """Records at or above `level` bypass the queue and are written and flushed
+        before `emit()` returns. None (the default) turns this off.
+        """
+    def getSyncLevel(self) -> int | None:
+        """Get the current sync level, or None when records are always queued."""
+    def emit(self, record: Any) -> None: ...
+    def setFilters(self, filters: list[Any]) -> bool:
+        """Run `filters` in Rust and return True when they are all Rust filters
+        (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+        otherwise return False and leave them to the wrapper's `handle()`.
+        """
+    def setPythonDispatch(self, enabled: bool = True) -> None:
+        """Route records through the wrapper's `handle()` (it has filters) or not."""
+    def isNative(self) -> bool: ...
+
+class PipeHandler:
+    def __init__(
+        self,
+        command: list[str] | None = None,
+        path: str | os.PathLike[str] | None = None,
+        capacity: int | None = None,
+        overflow: str | None = None,
+        block_timeout: float | None = None,
+        restart: bool = True,
+        restart_delay: float = 1.0,
+        error_callback: Any = None,
+        shutdown_timeout: float | None = ...,
+        spill_path: str | os.PathLike[str] | None = None,
+        daemon: bool | None = None,
+    ) -> None: ...
+    def setLevel(self, level: Level) -> None: ...
+    def flush(self) -> None: ...
+    def shutdown(self) -> None: ...
+    @property
+    def daemon(self) -> bool:
+        """Whether the worker is a daemon (abandoned at interpreter exit instead of drained)."""
+    def get_metrics(self) -> dict[str, Any]: ...
+    def emit(self, record: Any) -> None: ...
+    def setFilters(self, filters: list[Any]) -> bool:
+        """Run `filters` in Rust and return True when they are all Rust filters
+        (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+        otherwise return False and leave them to the wrapper's `handle()`.
+        """
+    def setPythonDispatch(self, enabled: bool = True) -> None:
+        """Route records through the wrapper's `handle()` (it has filters) or not."""
+    def isNative(self) -> bool: ...
+
+class MemoryHandler:
+    def __init__(self) -> None: ...
+    def getRecords(self) -> list[LogRecord]:
+        """Returns all captured log records."""
+    @property
+    def records(self) -> list[LogRecord]:
+        """Alias for getRecords() - Python naming convention."""
+    @property
+    def text(self) -> str:
+        """Returns all captured messages as a single newline-separated string.
+        Compatible with pytest caplog.text
+        """
+    @property
+    def record_tuples(self) -> list[tuple[str, int, str]]:
+        """Returns record tuples in pytest caplog format: (logger_name, level_num, message).
+        Compatible with pytest caplog.record_tuples
+        """
+    def clear(self) -> None:
+        """Clear all captured records."""
+    def setLevel(self, level: Level) -> None: ...
+    def flush(self) -> None: ...
+    def shutdown(self) -> None: ...
+    def emit(self, record: Any) -> None: ...
+    def setFilters(self, filters: list[Any]) -> bool:
+        """Run `filters` in Rust and return True when they are all Rust filters
+        (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+        otherwise return False and leave them to the wrapper's `handle()`.
+        """
+    def setPythonDispatch(self, enabled: bool = True) -> None:
+        """Route records through the wrapper's `handle()` (it has filters) or not."""
+    def isNative(self) -> bool: ...
+
+class QueueHandler:
+    def __init__(
+        self,
+        capacity: int | None = None,
+        overflow: str | None = None,
+        block_timeout: float | None = None,
+    ) -> None:
+        """`capacity` bounds each of the queue's two lanes (ERROR and above, the rest).
+        `overflow` is "block", "drop_newest" or "drop_oldest"; `block_timeout` is how
+        many seconds "block" waits for room before dropping (None = no limit).
+        """
+    def setLevel(self, level: Level) -> None: ...
+    def flush(self) -> None:
+        """Wait until a running listener has delivered the queued records and flushed
+        its handlers.
+        """
+    def shutdown(self) -> None: ...
+    def emit(self, record: Any) -> None: ...
+    def setFilters(self, filters: list[Any]) -> bool:
+        """Run `filters` in Rust and return True when they are all Rust filters
+        (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+        otherwise return False and leave them to the wrapper's `handle()`.
+        """
+    def setPythonDispatch(self, enabled: bool = True) -> None:
+        """Route records through the wrapper's `handle()` (it has filters) or not."""
+    def isNative(self) -> bool: ...
+    def get_metrics(self) -> dict[str, Any]: ...
+
+class QueueListener:
+    def __init__(
+        self,
+        queue: Any,
+        *handlers: Any,
+        respect_handler_level: bool = False,
+    ) -> None:
+        """Deliver the records of `queue` (a QueueHandler) to `handlers` on a background
+        thread. With `respect_handler_level`, a Python handler only gets records at or
+        above its level; Rust handlers always apply their own level.
+        """
+    @property
+    def handlers(self) -> tuple[Any, ...]: ...
+    @property
+    def respect_handler_level(self) -> bool: ...
+    def start(self) -> None:
+        """Start the background thread."""
+    def stop(self) -> None:
+        """Deliver the records still queued, then stop the thread and wait for it."""
+    @property
+    def running(self) -> bool: ...
+
+class AggregatingHandler:
+    def __init__(self, *handlers: Any, window: float = 60.0) -> None:
+        """Count records per (logger, level, message template) and hand `handlers` one
+        record per combination every `window` seconds. A Python handler only gets
+        records at or above its level; Rust handlers apply their own level.
+        """
+    @property
+    def handlers(self) -> tuple[Any, ...]: ...
+    @property
+    def window(self) -> float:
+        """Window length in seconds."""
+    def setLevel(self, level: Level) -> None: ...
+    def flush(self) -> None:
+        """Flush the target handlers. The open window keeps counting."""
+    def shutdown(self) -> None:
+        """Stop the window timer and emit the open window now."""
+    def emit(self, record: Any) -> None: ...
+    def setFilters(self, filters: list[Any]) -> bool:
+        """Run `filters` in Rust and return True when they are all Rust filters
+        (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+        otherwise return False and leave them to the wrapper's `handle()`.
+        """
+    def setPythonDispatch(self, enabled: bool = True) -> None:
+        """Route records through the wrapper's `handle()` (it has filters) or not."""
+    def isNative(self) -> bool: ...
+
+class Timer:
+    @property
+    def name(self) -> str: ...
+    @property
+    def elapsed_ms(self) -> float | None:
+        """Elapsed milliseconds of the most recently finished measurement (None before the
+        first one completes).
+        """
+    def __enter__(self) -> Timer: ...
+    def __exit__(
+        self,
+        exc_type: Any = None,
+        _exc_value: Any = None,
+        _traceback: Any = None,
+    ) -> bool: ...
+    def __call__(self, func: Any) -> TimedFunction:
+        """Decorator form: every call of `func` is timed separately."""
+
+class Progress:
+    @property
+    def count(self) -> int:
+        """Items consumed so far."""
+    def __iter__(self) -> Progress: ...
+    def __next__(self) -> Any: ...
+
+class LogTail:
+    """Lines of a followed log file, as returned by `tail(..., follow=True)`.
+
+    Iterating yields the last lines of the file and then blocks, checking for new lines
+    a few times a second, until `close()` is called. After a rotation or truncation the
+    rest of the old file comes first, then the new file from its start. The GIL is
+    released while waiting, and Ctrl-C interrupts the wait.
+    """
+    @property
+    def path(self) -> Path:
+        """The followed file."""
+    def close(self) -> None:
+        """Stop following: iteration ends after the lines already read."""
+    def __iter__(self) -> LogTail: ...
+    def __next__(self) -> str | None: ...
+    def __enter__(self) -> LogTail: ...
+    def __exit__(self, *args: Any) -> None: ...
+    def __repr__(self) -> str: ...
+
+class ConfigWatcher:
+    """Handle returned by `watch_config()`. Reloads continue until `stop()` is called,
+    the `with` block exits or the interpreter shuts down; dropping the handle does not
+    stop them.
+    """
+    @property
+    def path(self) -> Path:
+        """The watched config file."""
+    @property
+    def reloads(self) -> int:
+        """Number of times a changed file has been applied (the initial apply not included)."""
+    @property
+    def last_error(self) -> str | None:
+        """Error of the last reload attempt, None once a reload succeeds."""
+    @property
+    def running(self) -> bool:
+        """Whether the file is still being watched."""
+    def reload(self) -> None:
+        """Apply the file now, as if it had changed. Raises on an invalid config, leaving
+        the previous configuration in place.
+        """
+    def stop(self) -> None:
+        """Stop watching. Safe to call more than once."""
+    def __enter__(self) -> ConfigWatcher: ...
+    def __exit__(self, *args: Any) -> None: ...
+    def __repr__(self) -> str: ...
+
+class TimedFunction:
+    """Function wrapped by a `Timer` used as a decorator."""
+    def __call__(self, *args: Any, **kwargs: Any) -> Any: ...
+    def __get__(self, obj: Any, _objtype: Any) -> Any:
+        """Bind as a method when the decorated function is a class attribute."""
+
+class _LoggingModule:
+    """The `logxide.logxide.logging` submodule."""
+
+    PyLogger = PyLogger
+    LogRecord = LogRecord
+    Filter = Filter
+    RegexFilter = RegexFilter
+    LevelRangeFilter = LevelRangeFilter
+    DedupFilter = DedupFilter
+    Redactor = Redactor
+    FieldProjection = FieldProjection
+    Formatter = Formatter
+    ColorFormatter = ColorFormatter
+    ColumnFormatter = ColumnFormatter
+    JsonFormatter = JsonFormatter
+    FileHandler = FileHandler
+    StreamHandler = StreamHandler
+    RotatingFileHandler = RotatingFileHandler
+    TimedRotatingFileHandler = TimedRotatingFileHandler
+    LevelRoutedFileHandler = LevelRoutedFileHandler
+    HTTPHandler = HTTPHandler
+    DatagramHandler = DatagramHandler
+    SyslogHandler = SyslogHandler
+    SocketHandler = SocketHandler
+    SMTPHandler = SMTPHandler
+    OTLPHandler = OTLPHandler
+    PipeHandler = PipeHandler
+    MemoryHandler = MemoryHandler
+    QueueHandler = QueueHandler
+    QueueListener = QueueListener
+    AggregatingHandler = AggregatingHandler
+    Timer = Timer
+    Progress = Progress
+    LogTail = LogTail
+    ConfigWatcher = ConfigWatcher
+    @staticmethod
+    def getLogger(name: str | None = None, manager: Any = None) -> PyLogger:
+        """The logger called `name` (root for None), created on first use. Every call for a
+        name returns the same object, whether through `getLogger`, `getChild` or `.root`.
+        """
+    @staticmethod
+    def basicConfig(**kwargs: Any) -> None:
+        """Configure the root logger, like `logging.basicConfig`.
+
+        Does nothing when root already has handlers, unless `force=True`.
+
+        Args:
+            filename: Log to this file with a FileHandler.
+            filemode: Mode the file is opened with (default "a").
+            stream: Log to this stream (default sys.stderr); not with `filename`.
+            handlers: Handlers to add instead; not with `filename` or `stream`. Those
+                without a formatter get the one built from `format`.
+            format: Format string (default "%(levelname)s:%(name)s:%(message)s").
+            datefmt: Date format for `%(asctime)s`.
+            style: "%", "{" or "$", the syntax of `format`.
+            level: Level of the root logger.
+            force: Remove and close root's handlers first.
+            encoding: Encoding of the file.
+            errors: How encoding errors are handled in the file.
+        """
+    @staticmethod
+    def dictConfig(config: dict[str, Any]) -> None:
+        """Configure logging from a dictionary, like `logging.config.dictConfig`.
+
+        The dictionary itself is not modified.
+        """
+    @staticmethod
+    def flush() -> None: ...
+    @staticmethod
+    def set_thread_name(name: str) -> None: ...
+    @staticmethod
+    def register_http_handler(
+        url: str,
+        headers: dict[str, str] | None = None,
+        capacity: int | None = None,
+        batch_size: int | None = None,
+        flush_interval: int | None = None,
+        level: int | None = None,
+    ) -> None: ...
+    @staticmethod
+    def clear_handlers() -> None: ...
+    @staticmethod
+    def reinitialize() -> int:
+        """Tear down the process-wide logging state: stop config watchers, shut down every
+        handler, remove namespace routes, lift `disable()`, and return each logger to its freshly created
+        state (root at WARNING).
+        Loggers stay registered, so references held by application code keep working.
+        Returns the new generation.
+        """
+    @staticmethod
+    def disable(level: int = ...) -> None:
+        """Drop records at or below `level` on every logger, whatever its own level, as
+        `logging.disable()` does; `disable(NOTSET)` turns this off.
+        """
+    @staticmethod
+    def process_state() -> dict[str, Any]:
+        """The process-wide dict the Python package keeps its module state in."""
+    @staticmethod
+    def generation() -> int:
+        """Current state generation; see `reinitialize`."""
+    @staticmethod
+    def register_file_handler(
+        filename: str | bytes | os.PathLike[str] | os.PathLike[bytes],
+        level: int | None = None,
+        format: str | None = None,
+        datefmt: str | None = None,
+    ) -> None: ...
+    @staticmethod
+    def register_rotating_file_handler(
+        filename: str | bytes | os.PathLike[str] | os.PathLike[bytes],
+        max_bytes: int | None = None,
+        backup_count: int | None = None,
+        level: int | None = None,
+    ) -> None: ...
+    @staticmethod
+    def register_stream_handler(
+        stream: Any = None,
+        level: int | None = None,
+        format: str | None = None,
+        datefmt: str | None = None,
+    ) -> None: ...
+    @staticmethod
+    def activate_caller_info(format_str: str) -> None:
+        """Expose caller-info activation to Python compatibility layer"""
+    @staticmethod
+    def configure_tracebacks(
+        show_locals: bool = False,
+        max_frames: int | None = None,
+        most_recent_first: bool = False,
+    ) -> None:
+        """Configure how exception tracebacks are rendered for `exc_info` records.
+
+        Args:
+            show_locals: Include `name = repr(value)` for each local of every frame.
+            max_frames: Keep only the innermost N frames (None = unlimited).
+            most_recent_first: Print the failing frame first instead of last.
+        """
+    @staticmethod
+    def format_exception(exc_info: tuple[Any, ...]) -> str | None:
+        """Render an `exc_info` tuple with the configured traceback options, so the
+        Python-side record conversion matches the Rust logger path.
+        """
+    @staticmethod
+    def configure_exception_grouping(
+        enabled: bool = True,
+        frames: int = 5,
+        window: float | None = None,
+    ) -> None:
+        """Configure exception fingerprinting and in-process grouping.
+
+        Args:
+            enabled: Attach an `exc_fingerprint` extra to every record carrying exc_info.
+            frames: Number of innermost frames that contribute to the fingerprint.
+            window: Aggregation window in seconds. Within a window only the first
+                occurrence of a fingerprint keeps its traceback; repeats log a
+                "seen N times" summary instead. None disables aggregation.
+        """
+    @staticmethod
+    def configure_message_templates(enabled: bool = True) -> None:
+        """Attach a `message_template` extra to every record logged through logxide loggers.
+
+        The template is the formatted message with numbers, UUIDs and hex ids replaced by
+        `<num>`, `<uuid>` and `<hex>`, so records that differ only in those values share
+        it. `AggregatingHandler` groups by it when present.
+
+        Args:
+            enabled: Turn template extraction on or off.
+        """
+    @staticmethod
+    def message_template(message: str) -> str:
+        """Return the template of `message`, as stored in the `message_template` extra."""
+    @staticmethod
+    def configure_extra_limits(
+        max_keys: int | None = None,
+        max_value_bytes: int | None = None,
+    ) -> None:
+        """Limit the `extra=` fields of records logged through logxide loggers.
+
+        A record keeps its first `max_keys` extras and gets an `extra_keys_dropped` count
+        when it had more. A value whose serialized size is over `max_value_bytes` keeps
+        that many bytes followed by `...[truncated N bytes]`. `None` removes a limit.
+
+        Args:
+            max_keys: Most extra fields kept per record.
+            max_value_bytes: Largest serialized size of one extra value, in bytes.
+        """
+    @staticmethod
+    def extra_limit_stats() -> dict[str, Any]:
+        """Counts of extras cut by `configure_extra_limits()` since the process started.
+
+        Returns:
+            dict: `records_limited`, `keys_dropped` and `values_truncated`.
+        """
+    @staticmethod
+    def configure_redaction(redactor: Redactor | None = None) -> None:
+        """Redact every record logged through logxide loggers with `redactor`, before logger
+        filters and handlers see it.
+
+        Args:
+            redactor: A `Redactor`, or None to stop redacting.
+        """
+    @staticmethod
+    def configure_secret_keys(
+        enabled: bool = True,
+        keys: list[str] | None = None,
+        replacement: str = ...,
+    ) -> None:
+        """Replace the values of `extra` fields with secret names, at any depth, before any
+        formatter or handler sees them.
+
+        Names are compared ignoring case, with `-` read as `_`. The whole value is
+        replaced, whatever its type.
+
+        Args:
+            enabled: Scrub records logged from now on; False turns scrubbing off.
+            keys: Field names to scrub; None means `password`, `authorization` and
+                `api_key`.
+            replacement: Text the values are replaced with.
+        """
+    @staticmethod
+    def configure_run_id(enabled: bool = True, run_id: str | None = None) -> None:
+        """Attach this process's run id to every record as the `run_id` extra.
+
+        Args:
+            enabled: Attach it to records logged from now on; False turns it off.
+            run_id: Use this id for the process instead of the generated one; None keeps
+                the current id.
+        """
+    @staticmethod
+    def run_id() -> str:
+        """Return the run id of this process, as attached to its records."""
+    @staticmethod
+    def addLevelName(level: int, levelName: str) -> None:
+        """Name `level`, like `logging.addLevelName`. Records logged at it from now on carry
+        the name as `levelname`, and `setLevel()` accepts it.
+        """
+    @staticmethod
+    def getLevelName(level: Level) -> Any:
+        """The name of a level number, or the number of a level name, like
+        `logging.getLevelName`; `Level <level>` when there is none.
+        """
+    @staticmethod
+    def getLevelNamesMapping() -> dict[str, int]:
+        """A copy of the name to level mapping, like `logging.getLevelNamesMapping`."""
+    @staticmethod
+    def route(namespaces: Any, handlers: list[Any] = [], terminal: bool = True) -> None:
+        """Send the records of one or more logger namespaces to their own handlers.
+
+        Records from each namespace (the logger of that name and every logger below it)
+        are handed to `handlers` after the namespace's own logger, if it exists. With
+        `terminal=True` they go no further, so they never reach the root's handlers; no
+        handlers and `terminal=True` drops them from every sink above the namespace. A
+        namespace that already has a route gets the new one, and the old route's handlers
+        are closed.
+
+        Args:
+            namespaces: A logger name, or a list of them.
+            handlers: Handlers for the namespaces' records.
+            terminal: Stop propagation after these handlers.
+        """
+    @staticmethod
+    def unroute(namespaces: Any) -> None:
+        """Remove the routes of `namespaces` and close their handlers; their records
+        propagate as usual again. Namespaces without a route are ignored.
+
+        Args:
+            namespaces: A logger name, or a list of them.
+        """
+    @staticmethod
+    def routes() -> dict[str, Any]:
+        """The routes set with `route()` or a `dictConfig` `routes` section.
+
+        Returns:
+            dict: namespace -> `{"handlers": [...], "terminal": bool}`.
+        """
+    @staticmethod
+    def configure_threads(nice: int | None = None) -> None:
+        """Set the nice value of logxide's background threads (Linux only).
+
+        Applies to threads started afterwards, so call it before creating handlers.
+        Higher values yield the CPU to the application's threads; values below the
+        process's own usually need `CAP_SYS_NICE`.
+
+        Args:
+            nice: Nice value from -20 to 19, or None to inherit the process's.
+        """
+    @staticmethod
+    def tune(
+        io_threads: int | None = None,
+        pin_to_cores: list[int] | None = None,
+    ) -> dict[str, Any]:
+        """Dedicate CPU cores to logxide's background threads (Linux only).
+
+        Threads started afterwards are pinned to `pin_to_cores`. logxide runs one thread
+        per handler rather than a shared pool, so `io_threads` splits the cores into that
+        many groups and each new thread takes the next group in turn; without it every
+        thread may run on any of the cores. On platforms without CPU affinity the
+        arguments are checked and nothing is pinned. `tune()` unpins.
+
+        Args:
+            io_threads: Number of core groups, at most `len(pin_to_cores)`.
+            pin_to_cores: CPU indices to run the threads on.
+
+        Returns:
+            dict: `io_threads`, `pin_to_cores` and `pinned` (False when nothing is pinned).
+        """
+    @staticmethod
+    def preset(name: str = "default") -> dict[str, Any]:
+        """Pick the defaults of handlers created afterwards.
+
+        `"low_latency"` never blocks a logging call on a full queue and sends HTTP batches
+        every second; `"max_throughput"` uses deep queues and large batches;
+        `"durable"` blocks on a full queue, flushes every file record and writes ERROR and
+        above before the logging call returns. `"default"` restores each handler's own
+        defaults. Arguments passed to a handler override the preset.
+
+        Args:
+            name: `"default"`, `"low_latency"`, `"max_throughput"` or `"durable"`.
+
+        Returns:
+            dict: The settings of the preset.
+        """
+    @staticmethod
+    def queue_stats() -> list[Any]:
+        """Snapshot the queue counters of every live queued handler.
+
+        Returns a list of dicts with `kind`, `enqueued`, `dequeued`, `queue_dropped`,
+        `queue_depth`, `max_queue_depth`, `queue_capacity`, `deadline_records`, `late` and
+        `flush_latency_ms`.
+        """
+    @staticmethod
+    def configure_console_dedup(enabled: bool = True) -> None:
+        """Write each record at most once to stdout and to stderr.
+
+        With a StreamHandler on a logger and another on root, both on stderr, every
+        propagated record shows up twice. When enabled, the first StreamHandler along the
+        propagation path to accept a record writes it, and the others skip that console.
+        Applies to records dispatched by logxide loggers; off by default, as in the stdlib.
+        """
+    @staticmethod
+    def configure_caller_info(enabled: bool | None = None) -> None:
+        """Choose when records get caller info (`pathname`, `filename`, `module`, `lineno`,
+        `funcName`).
+
+        Looking up the caller's frame costs every log call, so by default (`None`) it is
+        done only while a handler's format string uses one of those fields. `True` collects
+        it for every record, for handlers or filters that read the fields without naming
+        them in a format; `False` never does, leaving them empty.
+        """
+    @staticmethod
+    def configure_crash_dump(target: Any = None) -> None:
+        """Write a CRITICAL line and the native and Python backtraces to a log file when the
+        process dies of SIGSEGV, SIGBUS, SIGFPE, SIGILL or SIGABRT.
+
+        `target` is a path, or a handler with a `baseFilename` (FileHandler and the rotating
+        handlers) whose file gets the dump; `None` removes the handlers and disables
+        `faulthandler` again. The file is opened for appending when this is called, so
+        after a rotation the dump goes to the file that was current then. The Python
+        traceback comes from `faulthandler`, which this enables on the same file in place
+        of any earlier `faulthandler.enable()`.
+        """
+    @staticmethod
+    def tail(target: Any, n: int = 100, follow: bool = False) -> Any:
+        """Return the last `n` lines of a log file.
+
+        `target` is a path, or a handler with a `baseFilename` (FileHandler and the rotating
+        handlers). A last line without its newline yet is left out. When the file has fewer
+        than `n` lines, the older ones come from its rotated backups (`app.log.1`, ... or
+        dated suffixes, gzipped or not), oldest first. Each line is one list item, so a
+        record with a traceback takes several. With `follow=True`, returns a `LogTail`
+        that yields these lines and then waits for new ones.
+        """
+    @staticmethod
+    def verify_log_file(
+        path: str | bytes | os.PathLike[str] | os.PathLike[bytes],
+    ) -> dict[str, Any]:
+        """Check the checksum trailers a file handler wrote with `checksum_every`.
+
+        Each trailer's segment is hashed again. Returns a dict with `ok` (no segment
+        failed), `segments` and `records` (how many verified), `corrupt` (a list of
+        `{"first_line", "trailer_line", "reason"}` for the segments that failed, reason one
+        of "checksum mismatch", "content missing" or "malformed trailer"), `unverified`
+        (`(first_line, last_line)` of each stretch no trailer covers) and
+        `unverified_bytes`. A file cut short usually ends in unverified lines; so does one
+        still being written. Gzipped backups (`.gz`) are read decompressed.
+
+        Args:
+            path: The log file.
+        """
+    @staticmethod
+    def convert_log_file(
+        src: str | bytes | os.PathLike[str] | os.PathLike[bytes],
+        dst: str | bytes | os.PathLike[str] | os.PathLike[bytes] | None = None,
+        to: str = "json",
+        format: str | None = None,
+        datefmt: str | None = None,
+        pattern: str | None = None,
+        schema_version: int | None = None,
+        since: Any = None,
+        until: Any = None,
+    ) -> str | None:
+        """Convert a log file between text, JSON lines and parquet.
+
+        `src` is read as parquet when its name ends in `.parquet`, as JSON lines when its
+        first line is a JSON object, and otherwise as text in `format` (default
+        `"%(levelname)s:%(name)s:%(message)s"`); `.gz` files are decompressed first. Lines
+        that are not records of their own (tracebacks) go with the record before them.
+        `pattern`, a regex whose named groups are the fields (`(?P<levelname>\\w+)`), reads
+        text that no format string describes; it must match the whole line. With
+        `schema_version`, every record is brought to that version through the migrations
+        registered with `register_schema_migration()` before it is written. `to` is
+        `"text"`, `"json"` or `"parquet"`; `%(asctime)s` is read and written in `datefmt`.
+        Writes to `dst`, or returns the text when `dst` is None. `since` and `until` (a
+        datetime or seconds since the epoch) keep only the records created in that range,
+        using the file's `index_every=` index when it has one. Parquet needs the `parquet`
+        cargo feature.
+        """
+    @staticmethod
+    def merge_log_files(
+        paths: list[str | bytes | os.PathLike[str] | os.PathLike[bytes]],
+        dst: str | bytes | os.PathLike[str] | os.PathLike[bytes] | None = None,
+        format: str | None = None,
+        datefmt: str | None = None,
+        pattern: str | None = None,
+        since: Any = None,
+        until: Any = None,
+    ) -> str | None:
+        """Merge log files into one, ordered by when each record was created.
+
+        Each file is read as in `convert_log_file`, and its records are written back as the
+        lines they were read from, so text and JSON files can be merged as they are.
+        Records without a time of their own sort with the record before them; equal times
+        keep the order of `paths`. `since` and `until` keep only the records created in
+        that range. Writes to `dst`, or returns the text when `dst` is None.
+        """
+    @staticmethod
+    def log_file_stats(
+        paths: list[str | bytes | os.PathLike[str] | os.PathLike[bytes]],
+        format: str | None = None,
+        datefmt: str | None = None,
+        pattern: str | None = None,
+        since: Any = None,
+        until: Any = None,
+    ) -> dict[str, Any]:
+        """Count the records in log files by logger and level.
+
+        Each file is read as in `convert_log_file`. Returns a dict with `records` (the
+        total), `first` and `last` (the earliest and latest creation times, None when no
+        record has one), `levels` ({levelname: count}, by level) and `loggers` ({name:
+        {levelname: count}}, by name). `since` and `until` count only the records created in
+        that range.
+        """
+    @staticmethod
+    def read_log_file(
+        src: str | bytes | os.PathLike[str] | os.PathLike[bytes],
+        format: str | None = None,
+        datefmt: str | None = None,
+        pattern: str | None = None,
+        since: Any = None,
+        until: Any = None,
+    ) -> list[LogRecord]:
+        """Read a log file back into `LogRecord`s.
+
+        The file is read as in `convert_log_file`: text in `format` or matched by `pattern`,
+        JSON lines or parquet. Fields the format doesn't know become extras, and a traceback
+        under a record is its `exc_text`. `created` is 0 for records the file gives no time.
+        `since` and `until` (a datetime or seconds since the epoch) keep only the records
+        created in that range; with the sidecar index of a file written with
+        `index_every=`, only that part of the file is read.
+        """
+    @staticmethod
+    def replay_log_file(
+        src: str | bytes | os.PathLike[str] | os.PathLike[bytes],
+        logger: str | None = None,
+        format: str | None = None,
+        datefmt: str | None = None,
+        pattern: str | None = None,
+        since: Any = None,
+        until: Any = None,
+    ) -> int:
+        """Log the records of a log file again, through the handlers configured now.
+
+        The file is read as in `read_log_file`. Each record goes to the logger it names, or
+        to `logger` when given, and from there to that logger's handlers and its ancestors'
+        as if it had just been logged: level checks, filters and redaction apply. Records
+        keep their own name, level, message and time; those without a time get the current
+        one. `since` and `until` replay only the records created in that range. Returns how
+        many records passed the level checks.
+        """
+    @staticmethod
+    def register_schema_migration(from_version: int, migrate: Any) -> None:
+        """Register the migration from schema version `from_version` to the next.
+
+        `migrate` is called with a record as a dict (the keys `JsonFormatter` writes) and
+        returns the record in the layout of `from_version + 1`, or None after changing the
+        dict in place. `schema_version` is updated afterwards. Registering a second
+        migration for the same version replaces the first.
+
+        Args:
+            from_version: The version the migration reads.
+            migrate: Callable taking and returning a dict.
+        """
+    @staticmethod
+    def schema_migrations() -> dict[str, Any]:
+        """The registered migrations, as {from_version: migrate}."""
+    @staticmethod
+    def migrate_record(record: dict[str, Any], to_version: int) -> Any:
+        """Bring a record to schema version `to_version` through the registered migrations.
+
+        `record` is a dict such as a parsed `JsonFormatter` line; one without a
+        `schema_version` is version 0. Returns a new dict with `schema_version` set to
+        `to_version`. Raises ValueError when a migration along the way is missing or the
+        record is newer than `to_version`.
+        """
+    @staticmethod
+    def debug_dispatch(enabled: bool = True, sample_rate: float = 1.0) -> None:
+        """Trace how records are dispatched, for debugging logging configuration.
+
+        For a sampled fraction of records, writes a block to stderr naming the loggers the
+        record went through, and for each handler whether it accepted the record or why it
+        was dropped: logger level, a logger filter, the handler's level, a full queue or
+        console de-duplication.
+
+        Args:
+            enabled: Turn tracing on or off.
+            sample_rate: Fraction of records traced, evenly spread (1.0 = every record).
+        """
+    @staticmethod
+    def configure_workers(daemon: bool = False) -> None:
+        """Set the global default for whether network and pipe handler workers run as daemons.
+
+        Args:
+            daemon: True = never block interpreter exit (pending records are spilled or
+                dropped at exit); False = drain at exit, bounded by each handler's
+                shutdown_timeout. Applies to handlers created afterwards without an
+                explicit `daemon=` argument.
+        """
+    @staticmethod
+    def shutdown_workers() -> None:
+        """Stop every live network and pipe handler worker according to its daemon policy.
+        Registered with `atexit`; safe to call more than once.
+        """
+    @staticmethod
+    def before_fork() -> None:
+        """Run the before-fork hooks. Registered with `os.register_at_fork(before=...)`."""
+    @staticmethod
+    def after_fork_in_child() -> None:
+        """Run the in-child hooks. Registered with `os.register_at_fork(after_in_child=...)`."""
+    @staticmethod
+    def watch_config(
+        path: str | bytes | os.PathLike[str] | os.PathLike[bytes],
+        debounce: float = 0.2,
+    ) -> ConfigWatcher:
+        """Apply a YAML or JSON logging config file and re-apply it whenever it changes.
+
+        Args:
+            path: Config file in the `logging.config.dictConfig` schema. `.json` files are
+                read as JSON, anything else as YAML.
+            debounce: Seconds the file must stay unchanged before a reload (default 0.2).
+
+        Returns a `ConfigWatcher`. The file is applied once before this returns, and an
+        invalid file raises. Later changes that fail to apply keep the previous
+        configuration and are reported on stderr and in `ConfigWatcher.last_error`.
+        """
+    @staticmethod
+    def stop_config_watchers() -> None:
+        """Stop every live config watcher. Registered with `atexit`, so no reload starts while
+        the interpreter is shutting down; safe to call more than once.
+        """
+    @staticmethod
+    def runtime_info() -> dict[str, Any]:
+        """Describe what this build of logxide can do.
+
+        Returns a dict with `platform` ("native" or "wasm32"), `threads` and `network`
+        (bools), `console` ("stdio", or "js" when StreamHandler writes to the browser
+        console) and `features` (the optional cargo features compiled in).
+        """
+    @staticmethod
+    def check_interpreter() -> None:
+        """Raise ImportError unless called from the interpreter that owns the extension."""
+
+logging: _LoggingModule
+
+def getLogger(name: str | None = None, manager: Any = None) -> PyLogger:
+    """The logger called `name` (root for None), created on first use. Every call for a
+    name returns the same object, whether through `getLogger`, `getChild` or `.root`.
+    """
+
+def basicConfig(**kwargs: Any) -> None:
+    """Configure the root logger, like `logging.basicConfig`.
+
+    Does nothing when root already has handlers, unless `force=True`.
+
+    Args:
+        filename: Log to this file with a FileHandler.
+        filemode: Mode the file is opened with (default "a").
+        stream: Log to this stream (default sys.stderr); not with `filename`.
+        handlers: Handlers to add instead; not with `filename` or `stream`. Those
+            without a formatter get the one built from `format`.
+        format: Format string (default "%(levelname)s:%(name)s:%(message)s").
+        datefmt: Date format for `%(asctime)s`.
+        style: "%", "{" or "$", the syntax of `format`.
+        level: Level of the root logger.
+        force: Remove and close root's handlers first.
+        encoding: Encoding of the file.
+        errors: How encoding errors are handled in the file.
+    """
+
+def dictConfig(config: dict[str, Any]) -> None:
+    """Configure logging from a dictionary, like `logging.config.dictConfig`.
+
+    The dictionary itself is not modified.
+    """
+
+def flush() -> None: ...
+
+def set_thread_name(name: str) -> None: ...
+
+def register_http_handler(
+    url: str,
+    headers: dict[str, str] | None = None,
+    capacity: int | None = None,
+    batch_size: int | None = None,
+    flush_interval: int | None = None,
+    level: int | None = None,
+) -> None: ...
+
+def clear_handlers() -> None: ...
+
+def reinitialize() -> int:
+    """Tear down the process-wide logging state: stop config watchers, shut down every
+    handler, remove namespace routes, lift `disable()`, and return each logger to its freshly created
+    state (root at WARNING).
+    Loggers stay registered, so references held by application code keep working.
+    Returns the new generation.
+    """
+
+def disable(level: int = ...) -> None:
+    """Drop records at or below `level` on every logger, whatever its own level, as
+    `logging.disable()` does; `disable(NOTSET)` turns this off.
+    """
+
+def register_file_handler(
+    filename: str | bytes | os.PathLike[str] | os.PathLike[bytes],
+    level: int | None = None,
+    format: str | None = None,
+    datefmt: str | None = None,
+) -> None: ...
+
+def register_rotating_file_handler(
+    filename: str | bytes | os.PathLike[str] | os.PathLike[bytes],
+    max_bytes: int | None = None,
+    backup_count: int | None = None,
+    level: int | None = None,
+) -> None: ...
+
+def register_stream_handler(
+    stream: Any = None,
+    level: int | None = None,
+    format: str | None = None,
+    datefmt: str | None = None,
+) -> None: ...
+
+def activate_caller_info(format_str: str) -> None:
+    """Expose caller-info activation to Python compatibility layer"""
+
+def configure_tracebacks(
+    show_locals: bool = False,
+    max_frames: int | None = None,
+    most_recent_first: bool = False,
+) -> None:
+    """Configure how exception tracebacks are rendered for `exc_info` records.
+
+    Args:
+        show_locals: Include `name = repr(value)` for each local of every frame.
+        max_frames: Keep only the innermost N frames (None = unlimited).
+        most_recent_first: Print the failing frame first instead of last.
+    """
+
+def format_exception(exc_info: tuple[Any, ...]) -> str | None:
+    """Render an `exc_info` tuple with the configured traceback options, so the
+    Python-side record conversion matches the Rust logger path.
+    """
+
+def configure_exception_grouping(
+    enabled: bool = True,
+    frames: int = 5,
+    window: float | None = None,
+) -> None:
+    """Configure exception fingerprinting and in-process grouping.
+
+    Args:
+        enabled: Attach an `exc_fingerprint` extra to every record carrying exc_info.
+        frames: Number of innermost frames that contribute to the fingerprint.
+        window: Aggregation window in seconds. Within a window only the first
+            occurrence of a fingerprint keeps its traceback; repeats log a
+            "seen N times" summary instead. None disables aggregation.
+    """
+
+def configure_message_templates(enabled: bool = True) -> None:
+    """Attach a `message_template` extra to every record logged through logxide loggers.
+
+    The template is the formatted message with numbers, UUIDs and hex ids replaced by
+    `<num>`, `<uuid>` and `<hex>`, so records that differ only in those values share
+    it. `AggregatingHandler` groups by it when present.
+
+    Args:
+        enabled: Turn template extraction on or off.
+    """
+
+def message_template(message: str) -> str:
+    """Return the template of `message`, as stored in the `message_template` extra."""
+
+def configure_extra_limits(
+    max_keys: int | None = None,
+    max_value_bytes: int | None = None,
+) -> None:
+    """Limit the `extra=` fields of records logged through logxide loggers.
+
+    A record keeps its first `max_keys` extras and gets an `extra_keys_dropped` count
+    when it had more. A value whose serialized size is over `max_value_bytes` keeps
+    that many bytes followed by `...[truncated N bytes]`. `None` removes a limit.
+
+    Args:
+        max_keys: Most extra fields kept per record.
+        max_value_bytes: Largest serialized size of one extra value, in bytes.
+    """
+
+def extra_limit_stats() -> dict[str, Any]:
+    """Counts of extras cut by `configure_extra_limits()` since the process started.
+
+    Returns:
+        dict: `records_limited`, `keys_dropped` and `values_truncated`.
+    """
+
+def configure_redaction(redactor: Redactor | None = None) -> None:
+    """Redact every record logged through logxide loggers with `redactor`, before logger
+    filters and handlers see it.
+
+    Args:
+        redactor: A `Redactor`, or None to stop redacting.
+    """
+
+def configure_secret_keys(
+    enabled: bool = True,
+    keys: list[str] | None = None,
+    replacement: str = ...,
+) -> None:
+    """Replace the values of `extra` fields with secret names, at any depth, before any
+    formatter or handler sees them.
+
+    Names are compared ignoring case, with `-` read as `_`. The whole value is
+    replaced, whatever its type.
+
+    Args:
+        enabled: Scrub records logged from now on; False turns scrubbing off.
+        keys: Field names to scrub; None means `password`, `authorization` and
+            `api_key`.
+        replacement: Text the values are replaced with.
+    """
+
+def configure_run_id(enabled: bool = True, run_id: str | None = None) -> None:
+    """Attach this process's run id to every record as the `run_id` extra.
+
+    Args:
+        enabled: Attach it to records logged from now on; False turns it off.
+        run_id: Use this id for the process instead of the generated one; None keeps
+            the current id.
+    """
+
+def run_id() -> str:
+    """Return the run id of this process, as attached to its records."""
+
+def addLevelName(level: int, levelName: str) -> None:
+    """Name `level`, like `logging.addLevelName`. Records logged at it from now on carry
+    the name as `levelname`, and `setLevel()` accepts it.
+    """
+
+def getLevelName(level: Level) -> Any:
+    """The name of a level number, or the number of a level name, like
+    `logging.getLevelName`; `Level <level>` when there is none.
+    """
+
+def getLevelNamesMapping() -> dict[str, int]:
+    """A copy of the name to level mapping, like `logging.getLevelNamesMapping`."""
+
+def route(namespaces: Any, handlers: list[Any] = [], terminal: bool = True) -> None:
+    """Send the records of one or more logger namespaces to their own handlers.
+
+    Records from each namespace (the logger of that name and every logger below it)
+    are handed to `handlers` after the namespace's own logger, if it exists. With
+    `terminal=True` they go no further, so they never reach the root's handlers; no
+    handlers and `terminal=True` drops them from every sink above the namespace. A
+    namespace that already has a route gets the new one, and the old route's handlers
+    are closed.
+
+    Args:
+        namespaces: A logger name, or a list of them.
+        handlers: Handlers for the namespaces' records.
+        terminal: Stop propagation after these handlers.
+    """
+
+def unroute(namespaces: Any) -> None:
+    """Remove the routes of `namespaces` and close their handlers; their records
+    propagate as usual again. Namespaces without a route are ignored.
+
+    Args:
+        namespaces: A logger name, or a list of them.
+    """
+
+def routes() -> dict[str, Any]:
+    """The routes set with `route()` or a `dictConfig` `routes` section.
+
+    Returns:
+        dict: namespace -> `{"handlers": [...], "terminal": bool}`.
+    """
+
+def configure_threads(nice: int | None = None) -> None:
+    """Set the nice value of logxide's background threads (Linux only).
+
+    Applies to threads started afterwards, so call it before creating handlers.
+    Higher values yield the CPU to the application's threads; values below the
+    process's own usually need `CAP_SYS_NICE`.
+
+    Args:
+        nice: Nice value from -20 to 19, or None to inherit the process's.
+    """
+
+def tune(
+    io_threads: int | None = None,
+    pin_to_cores: list[int] | None = None,
+) -> dict[str, Any]:
+    """Dedicate CPU cores to logxide's background threads (Linux only).
+
+    Threads started afterwards are pinned to `pin_to_cores`. logxide runs one thread
+    per handler rather than a shared pool, so `io_threads` splits the cores into that
+    many groups and each new thread takes the next group in turn; without it every
+    thread may run on any of the cores. On platforms without CPU affinity the
+    arguments are checked and nothing is pinned. `tune()` unpins.
+
+    Args:
+        io_threads: Number of core groups, at most `len(pin_to_cores)`.
+        pin_to_cores: CPU indices to run the threads on.
+
+    Returns:
+        dict: `io_threads`, `pin_to_cores` and `pinned` (False when nothing is pinned).
+    """
+
+def preset(name: str = "default") -> dict[str, Any]:
+    """Pick the defaults of handlers created afterwards.
+
+    `"low_latency"` never blocks a logging call on a full queue and sends HTTP batches
+    every second; `"max_throughput"` uses deep queues and large batches;
+    `"durable"` blocks on a full queue, flushes every file record and writes ERROR and
+    above before the logging call returns. `"default"` restores each handler's own
+    defaults. Arguments passed to a handler override the preset.
+
+    Args:
+        name: `"default"`, `"low_latency"`, `"max_throughput"` or `"durable"`.
+
+    Returns:
+        dict: The settings of the preset.
+    """
+
+def runtime_info() -> dict[str, Any]:
+    """Describe what this build of logxide can do.
+
+    Returns a dict with `platform` ("native" or "wasm32"), `threads` and `network`
+    (bools), `console` ("stdio", or "js" when StreamHandler writes to the browser
+    console) and `features` (the optional cargo features compiled in).
+    """
+
+def watch_config(
+    path: str | bytes | os.PathLike[str] | os.PathLike[bytes],
+    debounce: float = 0.2,
+) -> ConfigWatcher:
+    """Apply a YAML or JSON logging config file and re-apply it whenever it changes.
+
+    Args:
+        path: Config file in the `logging.config.dictConfig` schema. `.json` files are
+            read as JSON, anything else as YAML.
+        debounce: Seconds the file must stay unchanged before a reload (default 0.2).
+
+    Returns a `ConfigWatcher`. The file is applied once before this returns, and an
+    invalid file raises. Later changes that fail to apply keep the previous
+    configuration and are reported on stderr and in `ConfigWatcher.last_error`.
+    """
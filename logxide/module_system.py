@@ -10,6 +10,10 @@ import threading
 import types
 import weakref
 
+# A re-imported package finds the previous import's facade installed as
+# sys.modules["logging"]; patch and read the real module underneath it.
+_std_logging = getattr(_std_logging, "_std_logging", _std_logging)
+
 try:
     from . import logxide
 except ImportError:
@@ -156,13 +160,21 @@ class _LoggingModule(types.ModuleType):
             [],
         )
         self.root = _std_logging.root
+        self._std_logging = _std_logging
 
         from . import logxide as _ext
 
-        self.FileHandler = _ext.FileHandler
-        self.RotatingFileHandler = _ext.RotatingFileHandler
-        self.HTTPHandler = _ext.HTTPHandler
-        self.OTLPHandler = _ext.OTLPHandler
+        self.FileHandler = getattr(_ext, "FileHandler", None)
+        self.RotatingFileHandler = getattr(_ext, "RotatingFileHandler", None)
+        self.TimedRotatingFileHandler = getattr(_ext, "TimedRotatingFileHandler", None)
+        self.LevelRoutedFileHandler = getattr(_ext, "LevelRoutedFileHandler", None)
+        self.HTTPHandler = getattr(_ext, "HTTPHandler", None)
+        self.OTLPHandler = getattr(_ext, "OTLPHandler", None)
+        self.DatagramHandler = getattr(_ext, "DatagramHandler", None)
+        self.SyslogHandler = getattr(_ext, "SyslogHandler", None)
+        self.SocketHandler = getattr(_ext, "SocketHandler", None)
+        self.SMTPHandler = getattr(_ext, "SMTPHandler", None)
+        self.PipeHandler = getattr(_ext, "PipeHandler", None)
         self.lastResort, self.raiseExceptions = _std_logging.lastResort, True
 
         import logging.config
@@ -237,6 +249,41 @@ class _LoggingModule(types.ModuleType):
 logging = _LoggingModule()
 
 
+def _get_shell_disabled(self):
+    pylogger = self.__dict__.get("_logxide_pylogger")
+    if pylogger is not None:
+        return pylogger.disabled
+    return self.__dict__.get("disabled", False)
+
+
+def _set_shell_disabled(self, value):
+    self.__dict__["disabled"] = value
+    pylogger = self.__dict__.get("_logxide_pylogger")
+    if pylogger is not None:
+        pylogger.disabled = value
+
+
+# Installed on the stdlib Logger class: a stdlib logger's `disabled` is that of the
+# logxide logger it forwards to, which is the one that drops the records.
+_shell_disabled = property(_get_shell_disabled, _set_shell_disabled)
+
+
+def _shell_getChildren(self):
+    """
+    The existing loggers one level below this one, as a set, like
+    logging.Logger.getChildren (Python 3.12+), which is installed on older versions.
+    """
+    prefix = "" if self is self.manager.root else self.name + "."
+    return {
+        logger
+        for name, logger in list(self.manager.loggerDict.items())
+        if isinstance(logger, _std_logging.Logger)
+        and name.startswith(prefix)
+        and name != prefix
+        and "." not in name[len(prefix) :]
+    }
+
+
 def _install(sentry=None):
     """
     Install LogXide patches into the standard logging module.
@@ -263,6 +310,8 @@ def _install(sentry=None):
             return std_logger
 
         logxide_logger = getLogger(name)
+        if std_logger.__dict__.get("disabled"):
+            logxide_logger.disabled = True
         std_logger._logxide_pylogger = logxide_logger
 
         methods = [
@@ -275,6 +324,11 @@ def _install(sentry=None):
             "log",
             "fatal",
             "warn",
+            "timer",
+            "progress",
+            "log_batch",
+            "describe",
+            "bind",
         ]
         for m in methods:
             if hasattr(logxide_logger, m):
@@ -333,12 +387,31 @@ def _install(sentry=None):
     if not hasattr(std_logging, "_original_basicConfig"):
         std_logging._original_basicConfig = std_logging.basicConfig
 
-    def logxide_basicConfig(**kwargs):
-        with contextlib.suppress(Exception):
-            std_logging._original_basicConfig(**kwargs)
-        return basicConfig(**kwargs)
+    std_logging.basicConfig = basicConfig
+
+    if not hasattr(std_logging, "_original_disable"):
+        std_logging._original_disable = std_logging.disable
+    std_logging.disable = disable
+    std_logging.Logger.disabled = _shell_disabled
+    if not hasattr(_std_logging.Logger, "getChildren"):
+        _std_logging.Logger.getChildren = _shell_getChildren
+
+    # Levels named before the install, e.g. by a library imported earlier.
+    for level, name in list(_std_logging._levelToName.items()):
+        if logxide.logging.getLevelName(level) != name:
+            logxide.logging.addLevelName(level, name)
+    if not hasattr(_std_logging, "_original_addLevelName"):
+        _std_logging._original_addLevelName = _std_logging.addLevelName
+    _std_logging.addLevelName = addLevelName
+
+    # Frameworks (Django's LOGGING, ...) call logging.config.dictConfig directly.
+    import logging.config as std_config
+
+    from .config import dictConfig
 
-    std_logging.basicConfig = logxide_basicConfig
+    if not hasattr(std_config, "_original_dictConfig"):
+        std_config._original_dictConfig = std_config.dictConfig
+    std_config.dictConfig = dictConfig
 
     if not hasattr(std_logging, "flush"):
         std_logging.flush = flush_fn
@@ -358,5 +431,20 @@ def uninstall():
     if hasattr(std_logging, "_original_basicConfig"):
         std_logging.basicConfig = std_logging._original_basicConfig
         delattr(std_logging, "_original_basicConfig")
+    if hasattr(std_logging, "_original_disable"):
+        std_logging.disable = std_logging._original_disable
+        delattr(std_logging, "_original_disable")
+    if std_logging.Logger.__dict__.get("disabled") is _shell_disabled:
+        del std_logging.Logger.disabled
+    if _std_logging.Logger.__dict__.get("getChildren") is _shell_getChildren:
+        del _std_logging.Logger.getChildren
+    if hasattr(_std_logging, "_original_addLevelName"):
+        _std_logging.addLevelName = _std_logging._original_addLevelName
+        delattr(_std_logging, "_original_addLevelName")
+    import logging.config as std_config
+
+    if hasattr(std_config, "_original_dictConfig"):
+        std_config.dictConfig = std_config._original_dictConfig
+        delattr(std_config, "_original_dictConfig")
     if hasattr(std_logging, "_logxide_installed"):
         delattr(std_logging, "_logxide_installed")
@@ -0,0 +1,127 @@
+"""
+LogXide Standard Stream Redirection
+
+Replaces ``sys.stdout`` and ``sys.stderr`` with text streams that log each complete
+line written to them, so bare ``print()`` calls in legacy code reach the log pipeline.
+logxide's own StreamHandler writes to the process's file descriptors, not through
+``sys.stdout``/``sys.stderr``, so its console output is never captured again.
+"""
+
+import io
+import sys
+import threading
+
+from . import logger_wrapper, logxide
+
+# The replaced streams outlive a re-import of the package, so they are tracked in the
+# extension's process-wide state rather than in this module.
+_state = logxide.logging.process_state().setdefault(
+    "stdio", {"lock": threading.Lock(), "streams": None}
+)
+
+# Set while a thread is logging a captured line: whatever a handler writes to the
+# redirected streams meanwhile goes to the original stream instead of looping back.
+_forwarding = threading.local()
+
+
+class LoggingStream(io.TextIOBase):
+    """A text stream that logs each line written to it on `logger` at `level`."""
+
+    def __init__(self, original, logger, level, name):
+        self.original = original
+        self._logger = logger_wrapper.getLogger(logger)
+        self._level = level
+        self._name = name
+        # print() writes its arguments, separators and end separately: lines are
+        # assembled per thread so concurrent prints don't interleave.
+        self._pending = threading.local()
+        self.enabled = True
+
+    @property
+    def encoding(self):
+        return getattr(self.original, "encoding", "utf-8")
+
+    @property
+    def errors(self):
+        return getattr(self.original, "errors", "strict")
+
+    def writable(self):
+        return True
+
+    def isatty(self):
+        return self.original.isatty()
+
+    def fileno(self):
+        return self.original.fileno()
+
+    def write(self, s):
+        if not self.enabled or getattr(_forwarding, "active", False):
+            return self.original.write(s)
+        if not isinstance(s, str):
+            raise TypeError(f"write() argument must be str, not {type(s).__name__}")
+        *lines, rest = (getattr(self._pending, "text", "") + s).split("\n")
+        self._pending.text = rest
+        for line in lines:
+            self._log(line)
+        return len(s)
+
+    def flush(self):
+        if getattr(self._pending, "text", ""):
+            line, self._pending.text = self._pending.text, ""
+            self._log(line)
+        self.original.flush()
+
+    def _log(self, line):
+        line = line.rstrip("\r")
+        if not line.strip():
+            return
+        _forwarding.active = True
+        try:
+            self._logger.log(self._level, line, extra={"stdio": self._name})
+        finally:
+            _forwarding.active = False
+
+
+def redirect_stdio(
+    level=20,
+    logger="stdout",
+    stderr_level=40,
+    stderr_logger="stderr",
+    enabled=True,
+):
+    """
+    Log lines written to sys.stdout and sys.stderr instead of printing them.
+
+    Each complete line becomes a record on `logger` at `level` (stdout) or on
+    `stderr_logger` at `stderr_level` (stderr), with a ``stdio`` extra naming the
+    stream; blank lines are dropped and a partial line is logged on ``flush()``.
+    Output that handlers write to the redirected streams while a line is being logged
+    goes to the original stream. Calling it again replaces the settings.
+
+    Args:
+        level: Level of the lines printed to stdout (default INFO).
+        logger: Logger name for stdout lines (default "stdout").
+        stderr_level: Level of the lines printed to stderr (default ERROR).
+        stderr_logger: Logger name for stderr lines (default "stderr").
+        enabled: False puts the original streams back.
+    """
+    with _state["lock"]:
+        if _state["streams"] is not None:
+            for attr, stream in _state["streams"].items():
+                stream.flush()
+                stream.enabled = False
+                # Streams replaced since are left alone; ours then only passes the
+                # writes on.
+                if getattr(sys, attr) is stream:
+                    setattr(sys, attr, stream.original)
+            _state["streams"] = None
+
+        if not enabled:
+            return
+
+        _state["streams"] = {
+            "stdout": LoggingStream(sys.stdout, logger, level, "stdout"),
+            "stderr": LoggingStream(sys.stderr, stderr_logger, stderr_level, "stderr"),
+        }
+        sys.stdout = _state["streams"]["stdout"]
+        sys.stderr = _state["streams"]["stderr"]
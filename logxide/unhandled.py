@@ -0,0 +1,122 @@
+"""
+LogXide Unhandled Exception Capture
+
+Logs exceptions nothing caught at CRITICAL on the ``logxide.unhandled`` logger, with
+the traceback attached as ``exc_info``: the ones that end the program
+(``sys.excepthook``), end a thread (``threading.excepthook``) or reach an asyncio
+event loop's default exception handler. The ``unhandled`` extra says which.
+"""
+
+import asyncio
+import sys
+import threading
+
+from . import logger_wrapper, logxide
+
+UNHANDLED_LOGGER = "logxide.unhandled"
+
+# The installed hooks outlive a re-import of the package, so they are tracked in the
+# extension's process-wide state rather than in this module.
+_state = logxide.logging.process_state().setdefault(
+    "unhandled", {"lock": threading.Lock(), "install": None}
+)
+
+
+def _log(msg, *args, exc_info, extra):
+    logger_wrapper.getLogger(UNHANDLED_LOGGER).critical(
+        msg, *args, exc_info=exc_info, extra=extra
+    )
+
+
+def _hooks(active):
+    """The three hooks of one install; they stop logging once `active` is cleared."""
+    previous = {
+        "sys": sys.excepthook,
+        "threading": threading.excepthook,
+        "asyncio": asyncio.BaseEventLoop.default_exception_handler,
+    }
+
+    def excepthook(exc_type, exc_value, exc_traceback):
+        # Ctrl-C is how a program is meant to stop, not a crash.
+        if active.is_set() and not issubclass(exc_type, KeyboardInterrupt):
+            _log(
+                "Uncaught exception",
+                exc_info=(exc_type, exc_value, exc_traceback),
+                extra={"unhandled": "sys"},
+            )
+            # The interpreter is about to exit: drain queued handlers first.
+            logxide.logging.flush()
+        previous["sys"](exc_type, exc_value, exc_traceback)
+
+    def threading_excepthook(args):
+        # threading's own hook stays silent on SystemExit too.
+        if active.is_set() and not issubclass(args.exc_type, SystemExit):
+            name = args.thread.name if args.thread is not None else "<unknown>"
+            _log(
+                "Uncaught exception in thread %s",
+                name,
+                exc_info=(args.exc_type, args.exc_value, args.exc_traceback),
+                extra={"unhandled": "thread", "unhandled_thread": name},
+            )
+        previous["threading"](args)
+
+    def asyncio_exception_handler(loop, context):
+        if not active.is_set():
+            return previous["asyncio"](loop, context)
+        exc = context.get("exception")
+        exc_info = (type(exc), exc, exc.__traceback__) if exc is not None else None
+        extra = {
+            "unhandled": "asyncio",
+            "asyncio_context": {
+                key: repr(value)
+                for key, value in context.items()
+                if key not in ("message", "exception")
+            },
+        }
+        msg = context.get("message", "Unhandled exception in event loop")
+        _log(msg, exc_info=exc_info, extra=extra)
+
+    installed = {
+        "sys": excepthook,
+        "threading": threading_excepthook,
+        "asyncio": asyncio_exception_handler,
+    }
+    return previous, installed
+
+
+def capture_unhandled(enabled=True):
+    """
+    Log uncaught exceptions at CRITICAL before they end the program or a thread.
+
+    Installs ``sys.excepthook`` and ``threading.excepthook`` wrappers, which log the
+    exception and then call the hooks they replaced (so the traceback still reaches
+    stderr), and replaces asyncio's default event loop exception handler, which would
+    otherwise log the same exception on the ``asyncio`` logger. Loops given their own
+    handler with ``loop.set_exception_handler()`` keep it. KeyboardInterrupt and a
+    thread's SystemExit are not logged.
+
+    Args:
+        enabled: True installs the hooks, False restores the ones they replaced.
+    """
+    with _state["lock"]:
+        if enabled and _state["install"] is None:
+            active = threading.Event()
+            active.set()
+            previous, installed = _hooks(active)
+            sys.excepthook = installed["sys"]
+            threading.excepthook = installed["threading"]
+            asyncio.BaseEventLoop.default_exception_handler = installed["asyncio"]
+            _state["install"] = (active, previous, installed)
+        elif not enabled and _state["install"] is not None:
+            active, previous, installed = _state["install"]
+            active.clear()
+            # A hook installed over ours since keeps calling it; ours then only
+            # passes the call on.
+            if sys.excepthook is installed["sys"]:
+                sys.excepthook = previous["sys"]
+            if threading.excepthook is installed["threading"]:
+                threading.excepthook = previous["threading"]
+            loop = asyncio.BaseEventLoop
+            if loop.default_exception_handler is installed["asyncio"]:
+                loop.default_exception_handler = previous["asyncio"]
+            _state["install"] = None
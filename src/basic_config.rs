@@ -0,0 +1,200 @@
+//! # basicConfig
+//!
+//! `logging.basicConfig` implemented on the Rust side. As in the stdlib, it configures
+//! the root logger only while root has no handlers, unless `force=True` removes and
+//! closes them first. Like [`dictConfig`](crate::config), root is configured twice:
+//! on the stdlib root logger and on the native one that dispatches records.
+//!
+//! The handler it adds for `filename` (opened with `filemode`), `sys.stdout` or
+//! `sys.stderr` (the default) is logxide's `FileHandler` / `StreamHandler`: the Rust
+//! handler writes each record with a [`PythonFormatter`](crate::formatter::PythonFormatter)
+//! built from `format`, `datefmt` and `style`. A format the Rust formatter can't
+//! parse makes that handler format in Python, as any handler with such a Formatter
+//! does. Any other `stream` object gets the stdlib `StreamHandler`, which root
+//! dispatches in Python.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyString};
+
+use crate::globals;
+use crate::py_logger::check_level;
+
+/// Configure the root logger, like `logging.basicConfig`.
+///
+/// Does nothing when root already has handlers, unless `force=True`.
+///
+/// Args:
+///     filename: Log to this file with a FileHandler.
+///     filemode: Mode the file is opened with (default "a").
+///     stream: Log to this stream (default sys.stderr); not with `filename`.
+///     handlers: Handlers to add instead; not with `filename` or `stream`. Those
+///         without a formatter get the one built from `format`.
+///     format: Format string (default "%(levelname)s:%(name)s:%(message)s").
+///     datefmt: Date format for `%(asctime)s`.
+///     style: "%", "{" or "$", the syntax of `format`.
+///     level: Level of the root logger.
+///     force: Remove and close root's handlers first.
+///     encoding: Encoding of the file.
+///     errors: How encoding errors are handled in the file.
+#[pyfunction(name = "basicConfig")]
+#[pyo3(signature = (**kwargs))]
+pub fn basic_config(py: Python, kwargs: Option<&Bound<PyDict>>) -> PyResult<()> {
+    // `logging` may be logxide's module replacement; the root logger, `Formatter` and
+    // `StreamHandler` are the stdlib ones.
+    let logging = py
+        .import("logxide.module_system")?
+        .getattr("_std_logging")?;
+    let kwargs = match kwargs {
+        Some(kwargs) => kwargs.copy()?,
+        None => PyDict::new(py),
+    };
+    let lock = logging.getattr("_lock")?;
+    lock.call_method0("acquire")?;
+    let result = configure(py, &logging, &kwargs);
+    lock.call_method0("release")?;
+    result
+}
+
+fn configure<'py>(
+    py: Python<'py>,
+    logging: &Bound<'py, PyAny>,
+    kwargs: &Bound<'py, PyDict>,
+) -> PyResult<()> {
+    let pop = |key: &str| -> PyResult<Option<Bound<'py, PyAny>>> {
+        let value = kwargs.get_item(key)?;
+        kwargs.del_item(key).ok();
+        Ok(value.filter(|value| !value.is_none()))
+    };
+    let shell = logging.call_method0("getLogger")?;
+    let native = globals::get_logger(py, None, None)?.into_bound(py);
+    let logger_class = logging.getattr("Logger")?;
+    // Class-level calls: an installed logxide patches these on the instance to
+    // forward to the native logger, which is configured explicitly here.
+    let on_shell = |method: &str, arg: &Bound<'py, PyAny>| -> PyResult<()> {
+        logger_class.getattr(method)?.call1((&shell, arg))?;
+        Ok(())
+    };
+
+    if let Some(force) = pop("force")? {
+        if force.is_truthy()? {
+            for handler in shell
+                .getattr("handlers")?
+                .call_method0("copy")?
+                .try_iter()?
+            {
+                let handler = handler?;
+                on_shell("removeHandler", &handler)?;
+                handler.call_method0("close")?;
+            }
+            native.borrow().clear_handlers(py);
+        }
+    }
+    if !shell.getattr("handlers")?.is_empty()? || !native.getattr("handlers")?.is_empty()? {
+        return Ok(());
+    }
+
+    let handlers = pop("handlers")?;
+    let stream = pop("stream")?;
+    let filename = pop("filename")?;
+    let filemode = pop("filemode")?;
+    let encoding = pop("encoding")?;
+    let errors = pop("errors")?;
+    let format = pop("format")?;
+    let datefmt = pop("datefmt")?;
+    let style = pop("style")?;
+    let level = pop("level")?;
+    if !kwargs.is_empty() {
+        let keys: Vec<String> = kwargs
+            .keys()
+            .iter()
+            .map(|key| key.str().map(|key| key.to_string()))
+            .collect::<PyResult<_>>()?;
+        return Err(PyValueError::new_err(format!(
+            "Unrecognised argument(s): {}",
+            keys.join(", ")
+        )));
+    }
+    if handlers.is_some() && (stream.is_some() || filename.is_some()) {
+        return Err(PyValueError::new_err(
+            "'stream' or 'filename' should not be specified together with 'handlers'",
+        ));
+    }
+    if stream.is_some() && filename.is_some() {
+        return Err(PyValueError::new_err(
+            "'stream' and 'filename' should not be specified together",
+        ));
+    }
+
+    let formatter = logging.getattr("Formatter")?.call1((
+        match &format {
+            Some(format) => format.clone(),
+            None => logging.getattr("BASIC_FORMAT")?,
+        },
+        datefmt,
+        style.unwrap_or_else(|| PyString::new(py, "%").into_any()),
+    ))?;
+    let level = level.map(|level| check_level(py, &level)).transpose()?;
+
+    let handlers: Vec<Bound<'py, PyAny>> = match handlers {
+        Some(handlers) => handlers.try_iter()?.collect::<PyResult<_>>()?,
+        None => {
+            let handler = new_handler(py, logging, stream, filename, filemode, encoding, errors)?;
+            // Keep a `set_default_formatter()` formatter unless `format` was given.
+            if format.is_some() {
+                handler.call_method1("setFormatter", (&formatter,))?;
+            }
+            vec![handler]
+        }
+    };
+    for handler in &handlers {
+        if handler.getattr("formatter")?.is_none() {
+            handler.call_method1("setFormatter", (&formatter,))?;
+        }
+        on_shell("addHandler", handler)?;
+        native.call_method1("addHandler", (handler,))?;
+    }
+    if let Some(level) = level {
+        on_shell("setLevel", &level.into_pyobject(py)?.into_any())?;
+        native.call_method1("setLevel", (level,))?;
+    }
+    Ok(())
+}
+
+/// The handler for `filename` or `stream`: logxide's for a file and the standard
+/// streams, the stdlib `StreamHandler` for any other stream object.
+fn new_handler<'py>(
+    py: Python<'py>,
+    logging: &Bound<'py, PyAny>,
+    stream: Option<Bound<'py, PyAny>>,
+    filename: Option<Bound<'py, PyAny>>,
+    filemode: Option<Bound<'py, PyAny>>,
+    encoding: Option<Bound<'py, PyAny>>,
+    errors: Option<Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let handlers = py.import("logxide.handlers")?;
+    if let Some(filename) = filename {
+        let filemode = match filemode {
+            Some(filemode) => filemode.extract::<String>()?,
+            None => "a".to_string(),
+        };
+        // As in the stdlib: undecodable text is escaped rather than raising.
+        let errors = match errors {
+            _ if filemode.contains('b') => None,
+            Some(errors) => Some(errors),
+            None => Some(PyString::new(py, "backslashreplace").into_any()),
+        };
+        return handlers
+            .getattr("FileHandler")?
+            .call1((filename, filemode, encoding, false, errors));
+    }
+    let sys = py.import("sys")?;
+    match stream {
+        Some(stream)
+            if !stream.is(&sys.getattr("stdout")?) && !stream.is(&sys.getattr("stderr")?) =>
+        {
+            logging.getattr("StreamHandler")?.call1((stream,))
+        }
+        stream => handlers.getattr("StreamHandler")?.call1((stream,)),
+    }
+}
@@ -0,0 +1,265 @@
+//! # Checksum Trailers
+//!
+//! Archived log files sit untouched for years, and a flipped bit or a file cut short
+//! by a failed copy goes unnoticed until someone needs the logs. With
+//! `checksum_every=N`, a file handler follows every N records with a trailer line
+//!
+//! ```text
+//! #logxide-checksum records=1000 bytes=84512 blake3=<64 hex digits>
+//! ```
+//!
+//! holding the Blake3 hash of the `bytes` bytes just before it (the trailer itself is
+//! not hashed). Flushing, closing and rotating the file end the current segment early,
+//! so a file that was flushed ends with a trailer. `verify_log_file()` (`python -m
+//! logxide verify`) hashes each segment again and reports those that changed.
+//!
+//! A segment is found from its trailer by its byte count, not from where the previous
+//! trailer ends. Lines left without a trailer by a crash, or written before checksums
+//! were turned on, are reported as unverified instead of spoiling the next segment.
+//! Only one process may write to a file with checksums: records from another writer
+//! land inside a segment and make it fail.
+//!
+//! The readers (`tail()`, `convert_log_file()` and friends) skip trailer lines.
+
+#[cfg(feature = "file")]
+use pyo3::prelude::*;
+#[cfg(feature = "file")]
+use pyo3::types::PyDict;
+#[cfg(feature = "file")]
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
+#[cfg(feature = "file")]
+use std::path::Path;
+
+/// What every trailer line starts with.
+pub const PREFIX: &str = "#logxide-checksum ";
+
+/// Whether `line` is a checksum trailer rather than log output.
+pub fn is_trailer(line: &[u8]) -> bool {
+    line.starts_with(PREFIX.as_bytes())
+}
+
+/// The records a file handler wrote since its last trailer. Only used with the
+/// handler's writer locked.
+#[cfg(feature = "file")]
+pub struct Segment {
+    every: u32,
+    hasher: blake3::Hasher,
+    records: u32,
+    bytes: u64,
+}
+
+#[cfg(feature = "file")]
+impl Segment {
+    pub fn new(every: u32) -> Self {
+        Self {
+            every: every.max(1),
+            hasher: blake3::Hasher::new(),
+            records: 0,
+            bytes: 0,
+        }
+    }
+
+    /// Count a record just written; returns the trailer to write after it when the
+    /// record completes the segment.
+    pub fn add(&mut self, record: &[u8]) -> Option<String> {
+        self.hasher.update(record);
+        self.records += 1;
+        self.bytes += record.len() as u64;
+        if self.records >= self.every {
+            self.close()
+        } else {
+            None
+        }
+    }
+
+    /// The trailer ending the segment now, or None when nothing was written since the
+    /// last one.
+    pub fn close(&mut self) -> Option<String> {
+        if self.records == 0 {
+            return None;
+        }
+        let trailer = format!(
+            "{PREFIX}records={} bytes={} blake3={}\n",
+            self.records,
+            self.bytes,
+            self.hasher.finalize().to_hex()
+        );
+        self.hasher.reset();
+        self.records = 0;
+        self.bytes = 0;
+        Some(trailer)
+    }
+}
+
+/// A segment that failed verification.
+#[cfg(feature = "file")]
+struct Corrupt {
+    /// Line numbers (1-based) of the segment's first line and of its trailer.
+    first_line: u64,
+    trailer_line: u64,
+    reason: &'static str,
+}
+
+#[cfg(feature = "file")]
+#[derive(Default)]
+struct Report {
+    segments: u64,
+    records: u64,
+    corrupt: Vec<Corrupt>,
+    /// First and last line of each stretch not covered by a trailer.
+    unverified: Vec<(u64, u64)>,
+    unverified_bytes: u64,
+}
+
+/// The `records`, `bytes` and `blake3` of a trailer line.
+#[cfg(feature = "file")]
+fn parse_trailer(line: &[u8]) -> Option<(u64, u64, blake3::Hash)> {
+    let text = std::str::from_utf8(line.strip_prefix(PREFIX.as_bytes())?).ok()?;
+    let (mut records, mut bytes, mut hash) = (None, None, None);
+    for part in text.split_whitespace() {
+        match part.split_once('=')? {
+            ("records", value) => records = value.parse().ok(),
+            ("bytes", value) => bytes = value.parse().ok(),
+            ("blake3", value) => hash = blake3::Hash::from_hex(value).ok(),
+            _ => {}
+        }
+    }
+    Some((records?, bytes?, hash?))
+}
+
+/// Check every segment of `source`.
+#[cfg(feature = "file")]
+fn verify<R: BufRead + Seek>(source: &mut R) -> io::Result<Report> {
+    let mut report = Report::default();
+    // Offset and line number where the bytes after the last trailer start.
+    let (mut start, mut start_line) = (0u64, 1u64);
+    // Offsets of the lines since then, to number the lines of a segment that starts
+    // after unverified ones.
+    let mut lines: Vec<u64> = Vec::new();
+    let mut hasher = blake3::Hasher::new();
+    let mut offset = 0u64;
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let read = source.read_until(b'\n', &mut line)? as u64;
+        if read == 0 {
+            break;
+        }
+        let line_no = start_line + lines.len() as u64;
+        if !is_trailer(&line) {
+            hasher.update(&line);
+            lines.push(offset);
+            offset += read;
+            continue;
+        }
+        let gap = offset - start;
+        let body = line.strip_suffix(b"\n").unwrap_or(&line);
+        let body = body.strip_suffix(b"\r").unwrap_or(body);
+        let first_line = |from: u64| start_line + lines.partition_point(|&at| at < from) as u64;
+        match parse_trailer(body) {
+            None => report.corrupt.push(Corrupt {
+                first_line: start_line,
+                trailer_line: line_no,
+                reason: "malformed trailer",
+            }),
+            Some((_, bytes, _)) if bytes > gap => report.corrupt.push(Corrupt {
+                first_line: start_line,
+                trailer_line: line_no,
+                reason: "content missing",
+            }),
+            Some((records, bytes, expected)) => {
+                let from = offset - bytes;
+                let actual = if from == start {
+                    hasher.finalize()
+                } else {
+                    // Lines before the segment were left without a trailer.
+                    report.unverified.push((start_line, first_line(from) - 1));
+                    report.unverified_bytes += from - start;
+                    let mut segment = blake3::Hasher::new();
+                    source.seek(SeekFrom::Start(from))?;
+                    io::copy(&mut source.by_ref().take(bytes), &mut segment)?;
+                    source.seek(SeekFrom::Start(offset + read))?;
+                    segment.finalize()
+                };
+                if actual == expected {
+                    report.segments += 1;
+                    report.records += records;
+                } else {
+                    report.corrupt.push(Corrupt {
+                        first_line: first_line(from),
+                        trailer_line: line_no,
+                        reason: "checksum mismatch",
+                    });
+                }
+            }
+        }
+        offset += read;
+        start = offset;
+        start_line = line_no + 1;
+        lines.clear();
+        hasher.reset();
+    }
+    if offset > start {
+        report
+            .unverified
+            .push((start_line, start_line + lines.len() as u64 - 1));
+        report.unverified_bytes += offset - start;
+    }
+    Ok(report)
+}
+
+/// Check the checksum trailers a file handler wrote with `checksum_every`.
+///
+/// Each trailer's segment is hashed again. Returns a dict with `ok` (no segment
+/// failed), `segments` and `records` (how many verified), `corrupt` (a list of
+/// `{"first_line", "trailer_line", "reason"}` for the segments that failed, reason one
+/// of "checksum mismatch", "content missing" or "malformed trailer"), `unverified`
+/// (`(first_line, last_line)` of each stretch no trailer covers) and
+/// `unverified_bytes`. A file cut short usually ends in unverified lines; so does one
+/// still being written. Gzipped backups (`.gz`) are read decompressed.
+///
+/// Args:
+///     path: The log file.
+#[cfg(feature = "file")]
+#[pyfunction]
+pub fn verify_log_file(
+    py: Python<'_>,
+    path: crate::platform::FsPath,
+) -> PyResult<Bound<'_, PyDict>> {
+    let path: &Path = &path.0;
+    let file_error =
+        |e: io::Error| io::Error::new(e.kind(), format!("{}: {e}", path.display())).into();
+    let report = py.detach(|| -> PyResult<Report> {
+        if path.extension().is_some_and(|ext| ext == "gz") {
+            let data = crate::platform::gunzip_file(path).map_err(|e| match e.kind() {
+                io::ErrorKind::Unsupported => pyo3::exceptions::PyNotImplementedError::new_err(
+                    "gzip is not available: this build has no 'compression' feature",
+                ),
+                _ => file_error(e),
+            })?;
+            verify(&mut io::Cursor::new(data)).map_err(file_error)
+        } else {
+            let file = std::fs::File::open(path).map_err(file_error)?;
+            verify(&mut io::BufReader::new(file)).map_err(file_error)
+        }
+    })?;
+    let result = PyDict::new(py);
+    result.set_item("ok", report.corrupt.is_empty())?;
+    result.set_item("segments", report.segments)?;
+    result.set_item("records", report.records)?;
+    let corrupt = report
+        .corrupt
+        .iter()
+        .map(|c| {
+            let item = PyDict::new(py);
+            item.set_item("first_line", c.first_line)?;
+            item.set_item("trailer_line", c.trailer_line)?;
+            item.set_item("reason", c.reason)?;
+            Ok(item)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    result.set_item("corrupt", corrupt)?;
+    result.set_item("unverified", report.unverified)?;
+    result.set_item("unverified_bytes", report.unverified_bytes)?;
+    Ok(result)
+}
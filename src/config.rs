@@ -1,158 +1,837 @@
-//! # Configuration Management
+//! # dictConfig
 //!
-//! This module provides configuration management for the LogXide logging system.
-//! It supports multiple configuration sources including YAML, JSON, and Python
-//! dictConfig for compatibility with existing Python logging configurations.
+//! `logging.config.dictConfig` implemented on the Rust side, so a framework's
+//! `LOGGING` dict configures logxide's dispatch directly. Every logger the dict names
+//! is configured twice: on the stdlib `logging.Logger` that `logging.getLogger`
+//! returns (what `logger.handlers` / `logger.propagate` report) and on the native
+//! [`PyLogger`](crate::py_logger::PyLogger) that routes records when logxide is installed.
 //!
-//! ## Planned Features
+//! The algorithm follows CPython's `DictConfigurator`:
 //!
-//! - **YAML Configuration**: Load logging configuration from YAML files
-//! - **JSON Configuration**: Load logging configuration from JSON files
-//! - **dictConfig Support**: Full compatibility with Python logging.config.dictConfig
-//! - **Programmatic Configuration**: Runtime configuration of loggers, handlers, and formatters
+//! - `version` must be 1; `incremental` only updates levels and `propagate`.
+//! - Existing handlers are closed, then formatters, filters and handlers (sorted by
+//!   name, a `MemoryHandler` whose `target` isn't built yet deferred to a second pass)
+//!   are built, with `()` factories, `.` properties and `ext://` / `cfg://` values.
+//! - Configured loggers get their level, handlers, filters and `propagate`; existing
+//!   children of a configured logger are reset to NOTSET / no handlers / propagate,
+//!   and every other existing logger is disabled unless `disable_existing_loggers`
+//!   is false.
+//! - `root` is configured last.
+//! - A logxide-only `routes` section, `{namespace: {"handlers": [...], "terminal":
+//!   bool}}`, replaces every [namespace route](crate::routing) (`terminal` defaults
+//!   to true).
 //!
-//! ## Current Status
+//! Errors are raised as `ValueError("Unable to configure handler 'x'")` and friends,
+//! chained to the underlying exception, like the stdlib.
 //!
-//! This module is currently a placeholder for future configuration functionality.
-//! The basic structure is in place but implementation is pending.
-//!
-//! ## Future Structure
-//!
-//! The configuration system will support:
-//! - Logger configuration (levels, handlers, propagation)
-//! - Handler configuration (types, formatters, filters)
-//! - Formatter configuration (format strings, date formats)
-//! - Filter configuration (custom filter rules)
+//! A call is atomic for dispatch: while it runs, other threads wait before reading
+//! their handler lists (see [`consistent`]), so a record goes to the old handlers or
+//! the new ones, never to a logger caught between the two.
 
-/// Configuration structure for the LogXide logging framework.
-///
-/// This struct will eventually support comprehensive configuration of the
-/// logging system from various sources including YAML files, JSON files,
-/// and Python dictConfig objects.
-///
-/// # Design Goals
-///
-/// - **Compatibility**: Full support for Python logging configuration formats
-/// - **Flexibility**: Support multiple configuration sources and formats
-/// - **Validation**: Comprehensive validation of configuration settings
-/// - **Hot Reload**: Runtime reconfiguration without restart (planned)
-///
-/// # Current Status
+use once_cell::sync::Lazy;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyString, PyTuple, PyType};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Condvar, Mutex};
+
+use crate::globals::{self, PY_LOGGER_KEEP_ALIVE};
+use crate::py_logger::check_level;
+
+/// Configure logging from a dictionary, like `logging.config.dictConfig`.
 ///
-/// This is currently a placeholder implementation. The structure is defined
-/// but most functionality is not yet implemented.
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
-pub struct Config {
-    // Placeholder for logger configurations (name, level, handlers, etc.)
-    // pub loggers: HashMap<String, LoggerConfig>,
+/// The dictionary itself is not modified.
+#[pyfunction(name = "dictConfig")]
+pub fn dict_config(py: Python, config: &Bound<PyDict>) -> PyResult<()> {
+    // `logging` may be logxide's module replacement; the handler registry, `Logger`,
+    // `Formatter` and `Filter` defaults are the stdlib ones.
+    let logging = py
+        .import("logxide.module_system")?
+        .getattr("_std_logging")?;
+
+    let config = config.copy()?;
+    match config.get_item("version")? {
+        None => {
+            return Err(PyValueError::new_err(
+                "dictionary doesn't specify a version",
+            ))
+        }
+        Some(version) if !version.eq(1)? => {
+            return Err(PyValueError::new_err(format!(
+                "Unsupported version: {}",
+                version.str()?
+            )))
+        }
+        Some(_) => {}
+    }
+    // Built objects replace their entries; copy the sections so the caller's dict
+    // keeps its own.
+    for section in ["formatters", "filters", "handlers"] {
+        if let Some(entries) = config.get_item(section)? {
+            config.set_item(section, entries.cast::<PyDict>()?.copy()?)?;
+        }
+    }
+    let incremental = match pop(&config, "incremental")? {
+        Some(value) => value.is_truthy()?,
+        None => false,
+    };
+
+    let configurator = Configurator {
+        py,
+        logging: logging.clone(),
+        config,
+    };
+    let lock = logging.getattr("_lock")?;
+    lock.call_method0("acquire")?;
+    let result = {
+        let _reconfiguring = Reconfiguring::begin();
+        if incremental {
+            configurator.configure_incremental()
+        } else {
+            configurator.configure()
+        }
+    };
+    lock.call_method0("release")?;
+    result
+}
+
+/// Odd while a `dictConfig` call is rewiring loggers, bumped again when it finishes.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Where dispatching threads wait for a reconfiguration to finish.
+static SETTLED: Lazy<(Mutex<()>, Condvar)> = Lazy::new(|| (Mutex::new(()), Condvar::new()));
+
+thread_local! {
+    /// Set on the thread running `dictConfig`, whose own records must not wait for it.
+    static CONFIGURING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Marks a `dictConfig` call in progress for as long as it lives.
+struct Reconfiguring;
+
+impl Reconfiguring {
+    fn begin() -> Self {
+        GENERATION.fetch_add(1, Ordering::AcqRel);
+        CONFIGURING.with(|c| c.set(true));
+        Reconfiguring
+    }
+}
+
+impl Drop for Reconfiguring {
+    fn drop(&mut self) {
+        CONFIGURING.with(|c| c.set(false));
+        GENERATION.fetch_add(1, Ordering::AcqRel);
+        let (lock, settled) = &*SETTLED;
+        let _guard = lock.lock().unwrap();
+        settled.notify_all();
+    }
+}
+
+/// Run `snapshot` (reading handler lists for dispatch) outside any `dictConfig` call:
+/// wait, with the GIL released, while one is in progress, and retry if one started
+/// meanwhile. Two atomic loads when nothing is being reconfigured.
+pub(crate) fn consistent<T>(py: Python, mut snapshot: impl FnMut() -> T) -> T {
+    loop {
+        let generation = GENERATION.load(Ordering::Acquire);
+        if generation % 2 == 1 && !CONFIGURING.with(|c| c.get()) {
+            py.detach(|| {
+                let (lock, settled) = &*SETTLED;
+                let mut guard = lock.lock().unwrap();
+                while GENERATION.load(Ordering::Acquire) == generation {
+                    guard = settled.wait(guard).unwrap();
+                }
+            });
+            continue;
+        }
+        let value = snapshot();
+        if GENERATION.load(Ordering::Acquire) == generation {
+            return value;
+        }
+    }
+}
+
+struct Configurator<'py> {
+    py: Python<'py>,
+    logging: Bound<'py, PyAny>,
+    config: Bound<'py, PyDict>,
+}
+
+impl<'py> Configurator<'py> {
+    fn configure(&self) -> PyResult<()> {
+        let disable_existing = match pop(&self.config, "disable_existing_loggers")? {
+            Some(value) => value.is_truthy()?,
+            None => true,
+        };
+        self.clear_existing_handlers()?;
+        crate::routing::clear_all(self.py);
+
+        if let Some(formatters) = self.section("formatters")? {
+            for name in formatters.keys() {
+                let built = self
+                    .configure_formatter(&formatters.as_any().get_item(&name)?)
+                    .map_err(|e| unable(self.py, "formatter", &name, e))?;
+                formatters.set_item(&name, built)?;
+            }
+        }
+        if let Some(filters) = self.section("filters")? {
+            for name in filters.keys() {
+                let built = self
+                    .configure_filter(&filters.as_any().get_item(&name)?)
+                    .map_err(|e| unable(self.py, "filter", &name, e))?;
+                filters.set_item(&name, built)?;
+            }
+        }
+        if let Some(handlers) = self.section("handlers")? {
+            let names = handlers.keys();
+            names.sort()?;
+            let mut deferred = Vec::new();
+            for name in names.iter() {
+                match self.build_handler(&handlers, &name, false)? {
+                    Some(_) => {}
+                    None => deferred.push(name),
+                }
+            }
+            for name in deferred {
+                self.build_handler(&handlers, &name, true)?;
+            }
+        }
+        if let Some(routes) = self.section("routes")? {
+            for (namespace, route_config) in routes.iter() {
+                self.configure_route(&namespace, &route_config)
+                    .map_err(|e| unable(self.py, "route", &namespace, e))?;
+            }
+        }
+
+        let logger_dict = self
+            .logging
+            .getattr("root")?
+            .getattr("manager")?
+            .getattr("loggerDict")?
+            .cast_into::<PyDict>()?;
+        let mut existing: Vec<String> = logger_dict
+            .keys()
+            .iter()
+            .filter_map(|name| name.extract().ok())
+            .collect();
+        existing.extend(
+            PY_LOGGER_KEEP_ALIVE
+                .lock()
+                .unwrap()
+                .keys()
+                .filter(|name| name.as_str() != "root")
+                .cloned(),
+        );
+        existing.sort();
+        existing.dedup();
+
+        let mut children = Vec::new();
+        if let Some(loggers) = self.config.get_item("loggers")? {
+            let loggers = loggers.cast_into::<PyDict>()?;
+            for (name, logger_config) in loggers.iter() {
+                let name_str: String = name.extract()?;
+                if let Ok(index) = existing.binary_search(&name_str) {
+                    let prefix = format!("{name_str}.");
+                    children.extend(
+                        existing[index + 1..]
+                            .iter()
+                            .filter(|child| child.starts_with(&prefix))
+                            .cloned(),
+                    );
+                    existing.remove(index);
+                }
+                self.configure_logger(Some(&name_str), &logger_config, false)
+                    .map_err(|e| unable(self.py, "logger", &name, e))?;
+            }
+        }
+        self.handle_existing_loggers(&logger_dict, &existing, &children, disable_existing)?;
+
+        if let Some(root) = self.config.get_item("root")? {
+            if root.is_truthy()? {
+                self.configure_logger(None, &root, false)
+                    .map_err(|e| chained(self.py, "Unable to configure root logger".into(), e))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn configure_incremental(&self) -> PyResult<()> {
+        if let Some(handlers) = self.config.get_item("handlers")? {
+            let registry = self.logging.getattr("_handlers")?;
+            for (name, handler_config) in handlers.cast::<PyDict>()?.iter() {
+                let handler = registry.get_item(&name).map_err(|_| {
+                    PyValueError::new_err(format!("No handler found with name {}", repr(&name)))
+                })?;
+                let apply = || -> PyResult<()> {
+                    if let Some(level) = handler_config.cast::<PyDict>()?.get_item("level")? {
+                        if level.is_truthy()? {
+                            handler.call_method1("setLevel", (check_level(self.py, &level)?,))?;
+                        }
+                    }
+                    Ok(())
+                };
+                apply().map_err(|e| unable(self.py, "handler", &name, e))?;
+            }
+        }
+        if let Some(loggers) = self.config.get_item("loggers")? {
+            for (name, logger_config) in loggers.cast::<PyDict>()?.iter() {
+                let name_str: String = name.extract()?;
+                self.configure_logger(Some(&name_str), &logger_config, true)
+                    .map_err(|e| unable(self.py, "logger", &name, e))?;
+            }
+        }
+        if let Some(root) = self.config.get_item("root")? {
+            if root.is_truthy()? {
+                self.configure_logger(None, &root, true)
+                    .map_err(|e| chained(self.py, "Unable to configure root logger".into(), e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Close every handler the stdlib knows about, like `_clearExistingHandlers`.
+    fn clear_existing_handlers(&self) -> PyResult<()> {
+        self.logging.getattr("_handlers")?.call_method0("clear")?;
+        let handler_list = self.logging.getattr("_handlerList")?;
+        self.logging
+            .call_method1("shutdown", (handler_list.call_method0("copy")?,))?;
+        handler_list.call_method0("clear")?;
+        Ok(())
+    }
+
+    fn section(&self, name: &str) -> PyResult<Option<Bound<'py, PyDict>>> {
+        match self.config.get_item(name)? {
+            Some(entries) => Ok(Some(entries.cast_into::<PyDict>()?)),
+            None => Ok(None),
+        }
+    }
+
+    /// `config[section][key]`: a built object once that section has been configured.
+    fn lookup(&self, section: &str, key: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        self.config.as_any().get_item(section)?.get_item(key)
+    }
+
+    fn configure_formatter(&self, config: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        let config = config.cast::<PyDict>()?.copy()?;
+        if config.contains("()")? {
+            return match self.configure_custom(&config.copy()?) {
+                // A factory taking `fmt` rather than `format`.
+                Err(e)
+                    if e.is_instance_of::<PyTypeError>(self.py)
+                        && e.value(self.py).to_string().contains("'format'") =>
+                {
+                    if let Some(format) = pop(&config, "format")? {
+                        config.set_item("fmt", format)?;
+                    }
+                    self.configure_custom(&config)
+                }
+                result => result,
+            };
+        }
+        let class = match config.get_item("class")? {
+            Some(class) if class.is_truthy()? => self.callable(&class)?,
+            _ => self.logging.getattr("Formatter")?,
+        };
+        let get = |key: &str| -> PyResult<Bound<'py, PyAny>> {
+            Ok(config
+                .get_item(key)?
+                .unwrap_or_else(|| self.py.None().into_bound(self.py)))
+        };
+        let style = match config.get_item("style")? {
+            Some(style) => style,
+            None => PyString::new(self.py, "%").into_any(),
+        };
+        let kwargs = PyDict::new(self.py);
+        let defaults = get("defaults")?;
+        if !defaults.is_none() {
+            kwargs.set_item("defaults", defaults)?;
+        }
+        match config.get_item("validate")? {
+            Some(validate) => class.call(
+                (get("format")?, get("datefmt")?, style, validate),
+                Some(&kwargs),
+            ),
+            None => class.call((get("format")?, get("datefmt")?, style), Some(&kwargs)),
+        }
+    }
+
+    fn configure_filter(&self, config: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        let config = config.cast::<PyDict>()?.copy()?;
+        if config.contains("()")? {
+            return self.configure_custom(&config);
+        }
+        let name = match config.get_item("name")? {
+            Some(name) => name,
+            None => PyString::new(self.py, "").into_any(),
+        };
+        self.logging.getattr("Filter")?.call1((name,))
+    }
+
+    /// Build `handlers[name]` and store it under that name. Returns None when it is a
+    /// `MemoryHandler` whose target isn't built yet and `last_pass` is false.
+    fn build_handler(
+        &self,
+        handlers: &Bound<'py, PyDict>,
+        name: &Bound<'py, PyAny>,
+        last_pass: bool,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let built = handlers
+            .as_any()
+            .get_item(name)
+            .and_then(|config| self.configure_handler(&config))
+            .and_then(|built| match built {
+                Some(handler) => Ok(Some(handler)),
+                None if last_pass => Err(PyTypeError::new_err("target not configured yet")),
+                None => Ok(None),
+            })
+            .map_err(|e| unable(self.py, "handler", name, e))?;
+        if let Some(handler) = built.as_ref() {
+            handler
+                .setattr("name", name)
+                .map_err(|e| unable(self.py, "handler", name, e))?;
+            handlers.set_item(name, handler)?;
+        }
+        Ok(built)
+    }
+
+    fn configure_handler(&self, config: &Bound<'py, PyAny>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let config = config.cast::<PyDict>()?.copy()?;
+        let formatter = match pop(&config, "formatter")? {
+            Some(name) if name.is_truthy()? => {
+                Some(self.lookup("formatters", &name).map_err(|e| {
+                    chained(
+                        self.py,
+                        format!("Unable to set formatter {}", repr(&name)),
+                        e,
+                    )
+                })?)
+            }
+            _ => None,
+        };
+        let level = pop(&config, "level")?;
+        let filters = pop(&config, "filters")?;
+
+        let factory = match pop(&config, "()")? {
+            Some(factory) => self.callable(&factory)?,
+            None => {
+                let class = pop(&config, "class")?
+                    .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("class"))?;
+                let class = self.callable(&class)?;
+                let handlers_module = self.py.import("logging.handlers")?;
+                let is_subclass = |base: &str| -> PyResult<bool> {
+                    match class.cast::<PyType>() {
+                        Ok(class) => class.is_subclass(&handlers_module.getattr(base)?),
+                        Err(_) => Ok(false),
+                    }
+                };
+                if is_subclass("MemoryHandler")? && config.contains("target")? {
+                    let target = config.as_any().get_item("target")?;
+                    let handler = self.lookup("handlers", &target).map_err(|e| {
+                        chained(
+                            self.py,
+                            format!("Unable to set target handler {}", repr(&target)),
+                            e,
+                        )
+                    })?;
+                    if !handler.is_instance(&self.logging.getattr("Handler")?)? {
+                        return Ok(None);
+                    }
+                    config.set_item("target", handler)?;
+                } else if is_subclass("SMTPHandler")? && config.contains("mailhost")? {
+                    let mailhost = as_tuple(&config.as_any().get_item("mailhost")?)?;
+                    config.set_item("mailhost", mailhost)?;
+                } else if is_subclass("SysLogHandler")? && config.contains("address")? {
+                    let address = as_tuple(&config.as_any().get_item("address")?)?;
+                    config.set_item("address", address)?;
+                }
+                class
+            }
+        };
+        let props = pop(&config, ".")?;
+        let handler = factory.call((), Some(&self.kwargs(&config)?))?;
+
+        if let Some(formatter) = formatter {
+            handler.call_method1("setFormatter", (formatter,))?;
+        }
+        if let Some(level) = level {
+            if !level.is_none() {
+                handler.call_method1("setLevel", (check_level(self.py, &level)?,))?;
+            }
+        }
+        if let Some(filters) = filters {
+            for filter in self.filters(&filters)? {
+                handler.call_method1("addFilter", (filter,))?;
+            }
+        }
+        self.set_props(&handler, props)?;
+        Ok(Some(handler))
+    }
+
+    /// Configure one logger (`None` is root) on both the stdlib logger and the native one.
+    fn configure_logger(
+        &self,
+        name: Option<&str>,
+        config: &Bound<'py, PyAny>,
+        incremental: bool,
+    ) -> PyResult<()> {
+        let config = config.cast::<PyDict>()?;
+        let shell = self.logging.call_method1("getLogger", (name,))?;
+        let native = globals::get_logger(self.py, name, None)?.into_bound(self.py);
+        let logger_class = self.logging.getattr("Logger")?;
+        // Class-level calls: an installed logxide patches these on the instance to
+        // forward to the native logger, which is configured explicitly here.
+        let on_shell = |method: &str, arg: &Bound<'py, PyAny>| -> PyResult<()> {
+            logger_class.getattr(method)?.call1((&shell, arg))?;
+            Ok(())
+        };
+
+        if let Some(level) = config.get_item("level")? {
+            if !level.is_none() {
+                on_shell("setLevel", &level)?;
+                native.call_method1("setLevel", (&level,))?;
+            }
+        }
+        if !incremental {
+            for handler in shell
+                .getattr("handlers")?
+                .call_method0("copy")?
+                .try_iter()?
+            {
+                on_shell("removeHandler", &handler?)?;
+            }
+            native.borrow().clear_handlers(self.py);
+            if let Some(handlers) = config.get_item("handlers")? {
+                for name in handlers.try_iter()? {
+                    let name = name?;
+                    self.lookup("handlers", &name)
+                        .and_then(|handler| {
+                            on_shell("addHandler", &handler)?;
+                            native.call_method1("addHandler", (handler,))?;
+                            Ok(())
+                        })
+                        .map_err(|e| {
+                            chained(self.py, format!("Unable to add handler {}", repr(&name)), e)
+                        })?;
+                }
+            }
+            if let Some(filters) = config.get_item("filters")? {
+                for filter in self.filters(&filters)? {
+                    on_shell("addFilter", &filter)?;
+                    native.call_method1("addFilter", (filter,))?;
+                }
+            }
+        }
+        if name.is_some() {
+            shell.setattr("disabled", false)?;
+            native.setattr("disabled", false)?;
+            if let Some(propagate) = config.get_item("propagate")? {
+                if !propagate.is_none() {
+                    shell.setattr("propagate", &propagate)?;
+                    native.setattr("propagate", propagate.is_truthy()?)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Route one namespace to its own handlers (see [`crate::routing::route`]).
+    fn configure_route(
+        &self,
+        namespace: &Bound<'py, PyAny>,
+        config: &Bound<'py, PyAny>,
+    ) -> PyResult<()> {
+        let config = config.cast::<PyDict>()?;
+        let mut handlers = Vec::new();
+        if let Some(names) = config.get_item("handlers")? {
+            for name in names.try_iter()? {
+                let name = name?;
+                handlers.push(self.lookup("handlers", &name).map_err(|e| {
+                    chained(self.py, format!("Unable to add handler {}", repr(&name)), e)
+                })?);
+            }
+        }
+        let terminal = match config.get_item("terminal")? {
+            Some(terminal) => terminal.is_truthy()?,
+            None => true,
+        };
+        crate::routing::route(self.py, namespace, handlers, terminal)
+    }
+
+    /// Loggers that existed before this call and weren't configured: children of a
+    /// configured logger are reset so they defer to it, the rest are disabled (or
+    /// re-enabled when `disable_existing_loggers` is false).
+    fn handle_existing_loggers(
+        &self,
+        logger_dict: &Bound<'py, PyDict>,
+        existing: &[String],
+        children: &[String],
+        disable_existing: bool,
+    ) -> PyResult<()> {
+        let logger_class = self.logging.getattr("Logger")?;
+        for name in existing {
+            let shell = match logger_dict.get_item(name)? {
+                Some(logger) if logger.is_instance(&logger_class)? => Some(logger),
+                _ => None,
+            };
+            let native = PY_LOGGER_KEEP_ALIVE
+                .lock()
+                .unwrap()
+                .get(name)
+                .map(|logger| logger.clone_ref(self.py).into_bound(self.py));
+            if children.contains(name) {
+                if let Some(shell) = shell {
+                    logger_class.getattr("setLevel")?.call1((&shell, 0))?;
+                    shell.setattr("handlers", PyList::empty(self.py))?;
+                    shell.setattr("propagate", true)?;
+                }
+                if let Some(native) = native {
+                    native.call_method1("setLevel", (0,))?;
+                    native.borrow().clear_handlers(self.py);
+                    native.setattr("propagate", true)?;
+                }
+            } else {
+                if let Some(shell) = shell {
+                    shell.setattr("disabled", disable_existing)?;
+                }
+                if let Some(native) = native {
+                    native.setattr("disabled", disable_existing)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Filter objects for a list of filter names (or filter objects / callables).
+    fn filters(&self, names: &Bound<'py, PyAny>) -> PyResult<Vec<Bound<'py, PyAny>>> {
+        let mut filters = Vec::new();
+        for name in names.try_iter()? {
+            let name = name?;
+            let is_filter = name.is_callable()
+                || name
+                    .getattr("filter")
+                    .map(|filter| filter.is_callable())
+                    .unwrap_or(false);
+            let filter = if is_filter {
+                Ok(name.clone())
+            } else {
+                self.lookup("filters", &name)
+            };
+            filters.push(filter.map_err(|e| {
+                chained(self.py, format!("Unable to add filter {}", repr(&name)), e)
+            })?);
+        }
+        Ok(filters)
+    }
+
+    /// Call a `()` factory with the remaining keys as keyword arguments.
+    fn configure_custom(&self, config: &Bound<'py, PyDict>) -> PyResult<Bound<'py, PyAny>> {
+        let factory = match pop(config, "()")? {
+            Some(factory) => self.callable(&factory)?,
+            None => return Err(pyo3::exceptions::PyKeyError::new_err("()")),
+        };
+        let props = pop(config, ".")?;
+        let result = factory.call((), Some(&self.kwargs(config)?))?;
+        self.set_props(&result, props)?;
+        Ok(result)
+    }
+
+    fn kwargs(&self, config: &Bound<'py, PyDict>) -> PyResult<Bound<'py, PyDict>> {
+        let kwargs = PyDict::new(self.py);
+        for (key, value) in config.iter() {
+            let valid = key
+                .extract::<String>()
+                .map(|key| is_identifier(&key))
+                .unwrap_or(false);
+            if !valid {
+                return Err(PyValueError::new_err(format!(
+                    "Not a valid Python identifier: {}",
+                    repr(&key)
+                )));
+            }
+            kwargs.set_item(key, self.convert(&value)?)?;
+        }
+        Ok(kwargs)
+    }
+
+    fn set_props(
+        &self,
+        target: &Bound<'py, PyAny>,
+        props: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<()> {
+        if let Some(props) = props {
+            if props.is_truthy()? {
+                for (name, value) in props.cast::<PyDict>()?.iter() {
+                    target.setattr(name.cast::<PyString>()?, self.convert(&value)?)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// A callable as-is, or a dotted name resolved to one.
+    fn callable(&self, value: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        if value.is_callable() {
+            return Ok(value.clone());
+        }
+        self.resolve(value.cast::<PyString>()?.to_str()?)
+    }
+
+    /// Resolve `ext://` and `cfg://` strings, and build nested `()` dicts.
+    fn convert(&self, value: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        if let Ok(text) = value.cast::<PyString>() {
+            let text = text.to_str()?;
+            if let Some(name) = text.strip_prefix("ext://") {
+                return self.resolve(name);
+            }
+            if let Some(path) = text.strip_prefix("cfg://") {
+                return self.cfg_lookup(path);
+            }
+        } else if let Ok(dict) = value.cast_exact::<PyDict>() {
+            let dict = dict.copy()?;
+            if dict.contains("()")? {
+                return self.configure_custom(&dict);
+            }
+            for (key, item) in dict.copy()?.iter() {
+                dict.set_item(key, self.convert(&item)?)?;
+            }
+            return Ok(dict.into_any());
+        } else if let Ok(list) = value.cast_exact::<PyList>() {
+            let items = list
+                .iter()
+                .map(|item| self.convert(&item))
+                .collect::<PyResult<Vec<_>>>()?;
+            return Ok(PyList::new(self.py, items)?.into_any());
+        } else if let Ok(tuple) = value.cast_exact::<PyTuple>() {
+            let items = tuple
+                .iter()
+                .map(|item| self.convert(&item))
+                .collect::<PyResult<Vec<_>>>()?;
+            return Ok(PyTuple::new(self.py, items)?.into_any());
+        }
+        Ok(value.clone())
+    }
 
-    // Placeholder for handler configurations
-    // pub handlers: HashMap<String, HandlerConfig>,
+    /// Import a dotted name: `logging.handlers.SysLogHandler`, `sys.stderr`.
+    fn resolve(&self, name: &str) -> PyResult<Bound<'py, PyAny>> {
+        let resolve = || -> PyResult<Bound<'py, PyAny>> {
+            let mut parts = name.split('.');
+            let mut used = parts.next().unwrap_or_default().to_string();
+            let mut found = self.py.import(used.as_str())?.into_any();
+            for part in parts {
+                used = format!("{used}.{part}");
+                found = match found.getattr(part) {
+                    Ok(attr) => attr,
+                    Err(_) => {
+                        self.py.import(used.as_str())?;
+                        found.getattr(part)?
+                    }
+                };
+            }
+            Ok(found)
+        };
+        resolve().map_err(|e| {
+            if e.is_instance_of::<pyo3::exceptions::PyImportError>(self.py) {
+                chained(
+                    self.py,
+                    format!("Cannot resolve '{name}': {}", e.value(self.py)),
+                    e,
+                )
+            } else {
+                e
+            }
+        })
+    }
 
-    // Placeholder for formatter configurations
-    // pub formatters: HashMap<String, FormatterConfig>,
+    /// `cfg://handlers.email[toaddrs][0]`: a path into the configuration itself.
+    fn cfg_lookup(&self, path: &str) -> PyResult<Bound<'py, PyAny>> {
+        let cannot = |at: &str| {
+            PyValueError::new_err(if at.is_empty() {
+                format!("Unable to convert '{path}'")
+            } else {
+                format!("Unable to convert '{path}' at '{at}'")
+            })
+        };
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
 
-    // Placeholder for filter configurations
-    // pub filters: HashMap<String, FilterConfig>,
+        let rest = path.trim_start();
+        let end = rest.find(|c| !is_word(c)).unwrap_or(rest.len());
+        if end == 0 {
+            return Err(cannot(""));
+        }
+        let mut found = self.config.as_any().get_item(&rest[..end])?;
+        let mut rest = rest[end..].trim_start();
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix('.') {
+                let after = after.trim_start();
+                let end = after.find(|c| !is_word(c)).unwrap_or(after.len());
+                if end == 0 {
+                    return Err(cannot(rest));
+                }
+                found = found.get_item(&after[..end])?;
+                rest = after[end..].trim_start();
+            } else if let Some(after) = rest.strip_prefix('[') {
+                let end = after.find([']', '[']).ok_or_else(|| cannot(rest))?;
+                if !after[end..].starts_with(']') {
+                    return Err(cannot(rest));
+                }
+                let index = &after[..end];
+                found = match index.parse::<usize>() {
+                    Ok(n) if index.bytes().all(|b| b.is_ascii_digit()) => match found.get_item(n) {
+                        Err(e) if e.is_instance_of::<PyTypeError>(self.py) => {
+                            found.get_item(index)?
+                        }
+                        result => result?,
+                    },
+                    _ => found.get_item(index)?,
+                };
+                rest = after[end + 1..].trim_start();
+            } else {
+                return Err(cannot(rest));
+            }
+        }
+        self.convert(&found)
+    }
+}
 
-    // Add more fields as needed for configuration
+fn pop<'py>(dict: &Bound<'py, PyDict>, key: &str) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let value = dict.get_item(key)?;
+    if value.is_some() {
+        dict.del_item(key)?;
+    }
+    Ok(value)
 }
 
-impl Config {
-    /// Create a new, empty configuration.
-    ///
-    /// Returns a default configuration with no loggers, handlers, formatters,
-    /// or filters configured. This can be used as a starting point for
-    /// programmatic configuration.
-    ///
-    /// # Returns
-    ///
-    /// A new, empty Config instance
-    #[allow(dead_code)]
-    pub fn new() -> Self {
-        Config {
-            // Initialize fields as needed
-        }
-    }
-
-    /// Load configuration from a YAML string.
-    ///
-    /// Parses a YAML configuration string and creates a Config instance.
-    /// The YAML format will follow Python logging configuration conventions
-    /// for maximum compatibility.
-    ///
-    /// # Arguments
-    ///
-    /// * `_yaml` - YAML configuration string
-    ///
-    /// # Returns
-    ///
-    /// Result containing the parsed Config or an error message
-    ///
-    /// # Note
-    ///
-    /// This method is not yet implemented and will return an error.
-    #[allow(dead_code)]
-    pub fn from_yaml(_yaml: &str) -> Result<Self, String> {
-        // TODO: Parse YAML and populate Config
-        Err("YAML parsing not yet implemented".to_string())
-    }
-
-    /// Load configuration from a JSON string.
-    ///
-    /// Parses a JSON configuration string and creates a Config instance.
-    /// The JSON format will follow Python logging configuration conventions
-    /// for maximum compatibility.
-    ///
-    /// # Arguments
-    ///
-    /// * `_json` - JSON configuration string
-    ///
-    /// # Returns
-    ///
-    /// Result containing the parsed Config or an error message
-    ///
-    /// # Note
-    ///
-    /// This method is not yet implemented and will return an error.
-    #[allow(dead_code)]
-    pub fn from_json(_json: &str) -> Result<Self, String> {
-        // TODO: Parse JSON and populate Config
-        Err("JSON parsing not yet implemented".to_string())
-    }
-
-    /// Load configuration from a Python dictionary (dictConfig compatibility).
-    ///
-    /// Parses a Python dictionary configuration object and creates a Config instance.
-    /// This method provides full compatibility with Python's logging.config.dictConfig
-    /// functionality, allowing existing Python logging configurations to work
-    /// seamlessly with LogXide.
-    ///
-    /// # Arguments
-    ///
-    /// * `_dict` - Python dictionary containing logging configuration
-    ///
-    /// # Returns
-    ///
-    /// Result containing the parsed Config or an error message
-    ///
-    /// # Note
-    ///
-    /// This method is not yet implemented and will return an error.
-    ///
-    /// # Planned Compatibility
-    ///
-    /// Will support all standard dictConfig keys:
-    /// - `version` - Configuration format version
-    /// - `loggers` - Logger configurations
-    /// - `handlers` - Handler configurations
-    /// - `formatters` - Formatter configurations
-    /// - `filters` - Filter configurations
-    /// - `root` - Root logger configuration
-    #[allow(dead_code)]
-    pub fn from_dict(_dict: &pyo3::types::PyDict) -> Result<Self, String> {
-        // TODO: Parse Python dict and populate Config
-        Err("dictConfig parsing not yet implemented".to_string())
+fn as_tuple<'py>(value: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    match value.cast::<PyList>() {
+        Ok(list) => Ok(list.to_tuple().into_any()),
+        Err(_) => Ok(value.clone()),
     }
 }
+
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn repr(value: &Bound<PyAny>) -> String {
+    value
+        .repr()
+        .map(|r| r.to_string())
+        .unwrap_or_else(|_| "<unprintable>".into())
+}
+
+/// A `ValueError(message)` raised from `cause`.
+fn chained(py: Python, message: String, cause: PyErr) -> PyErr {
+    let err = PyValueError::new_err(message);
+    err.set_cause(py, Some(cause));
+    err
+}
+
+fn unable(py: Python, what: &str, name: &Bound<PyAny>, cause: PyErr) -> PyErr {
+    chained(
+        py,
+        format!("Unable to configure {what} {}", repr(name)),
+        cause,
+    )
+}
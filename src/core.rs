@@ -6,48 +6,51 @@
 
 #![allow(dead_code)]
 
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
 
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyTuple};
 use pyo3::IntoPyObjectExt;
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-/// Log levels, matching Python's logging levels.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-pub enum LogLevel {
-    NotSet = 0,
-    Debug = 10,
-    Info = 20,
-    Warning = 30,
-    Error = 40,
-    Critical = 50,
-}
+use crate::fork::ForkHook;
+
+/// A log level: one of Python's standard levels or any other number, such as a custom
+/// level named with `addLevelName()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct LogLevel(u32);
 
+#[allow(non_upper_case_globals)]
 impl LogLevel {
+    pub const NotSet: LogLevel = LogLevel(0);
+    pub const Debug: LogLevel = LogLevel(10);
+    pub const Info: LogLevel = LogLevel(20);
+    pub const Warning: LogLevel = LogLevel(30);
+    pub const Error: LogLevel = LogLevel(40);
+    pub const Critical: LogLevel = LogLevel(50);
+
+    pub const fn new(level: u32) -> LogLevel {
+        LogLevel(level)
+    }
+
     pub fn from_usize(level: usize) -> LogLevel {
-        match level {
-            10 => LogLevel::Debug,
-            20 => LogLevel::Info,
-            30 => LogLevel::Warning,
-            40 => LogLevel::Error,
-            50 => LogLevel::Critical,
-            _ => LogLevel::NotSet,
-        }
+        LogLevel(u32::try_from(level).unwrap_or(u32::MAX))
     }
 
-    pub const fn as_str(self) -> &'static str {
-        match self {
-            LogLevel::NotSet => "NOTSET",
-            LogLevel::Debug => "DEBUG",
-            LogLevel::Info => "INFO",
-            LogLevel::Warning => "WARNING",
-            LogLevel::Error => "ERROR",
-            LogLevel::Critical => "CRITICAL",
-        }
+    pub const fn value(self) -> u32 {
+        self.0
+    }
+
+    /// The level's name: a registered one (`addLevelName()`), else `Level <n>`.
+    pub fn name(self) -> String {
+        crate::levels::name(i64::from(self.0))
     }
 }
 
@@ -110,8 +113,10 @@ pub fn json_value_to_py_as_list(py: Python, value: &Value) -> PyResult<Py<PyAny>
 }
 
 /// Complete log record structure for compatibility with Python logging.
-#[pyclass(from_py_object)]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// `module = "logxide"` lets pickle find the class again as `logxide.LogRecord`.
+#[pyclass(from_py_object, module = "logxide")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct LogRecord {
     #[pyo3(get, set)]
     pub name: String,
@@ -143,6 +148,20 @@ pub struct LogRecord {
     pub process_name: String,
     #[pyo3(get, set)]
     pub process: u32,
+    /// Id of the parent process; 0 where the platform has none.
+    #[pyo3(get, set)]
+    #[cfg_attr(feature = "json", serde(default))]
+    pub ppid: u32,
+    /// OS id of the logging thread, as `threading.get_native_id()` reports it; 0
+    /// where the platform has none.
+    #[pyo3(get, set)]
+    #[cfg_attr(feature = "json", serde(default))]
+    pub thread_native_id: u64,
+    /// Position of the record in this process's sequence, from 1; 0 for a record
+    /// built from Python rather than logged.
+    #[pyo3(get, set)]
+    #[cfg_attr(feature = "json", serde(default))]
+    pub seq: u64,
     #[pyo3(get, set)]
     pub msg: String,
     pub args: Option<Arc<Value>>,
@@ -154,8 +173,61 @@ pub struct LogRecord {
     pub stack_info: Option<String>,
     #[pyo3(get, set)]
     pub task_name: Option<String>,
-    #[serde(default)]
+    #[cfg_attr(feature = "json", serde(default))]
     pub extra: Option<HashMap<String, Value>>,
+    /// JSON encodings of this record, shared by its clones.
+    #[cfg_attr(feature = "json", serde(skip))]
+    pub json_cache: JsonCache,
+}
+
+/// JSON encodings of one record, keyed by schema, so handlers that write the same
+/// layout encode a record once between them.
+///
+/// Clones share the cache, so copies of a record (for a Python handler, a listener's
+/// sinks) reuse its encodings. Code that changes a clone's fields must start it on a
+/// fresh cache (see [`LogRecord::detached`]) so the original's encodings stay correct.
+#[derive(Default)]
+pub struct JsonCache(OnceLock<JsonEntries>);
+
+/// `(schema, encoding)` pairs; a record rarely has more than one or two.
+type JsonEntries = Arc<parking_lot::Mutex<Vec<(u64, Arc<str>)>>>;
+
+impl JsonCache {
+    fn entries(&self) -> &JsonEntries {
+        self.0.get_or_init(Default::default)
+    }
+
+    /// The encoding for `schema`, made with `encode` the first time it's asked for.
+    pub fn get_or_encode(&self, schema: u64, encode: impl FnOnce() -> String) -> Arc<str> {
+        let entries = self.entries();
+        if let Some((_, json)) = entries.lock().iter().find(|(s, _)| *s == schema) {
+            return json.clone();
+        }
+        // Encoded outside the lock; a handler racing on the same schema keeps the
+        // first result.
+        let json: Arc<str> = encode().into();
+        let mut entries = entries.lock();
+        if let Some((_, first)) = entries.iter().find(|(s, _)| *s == schema) {
+            return first.clone();
+        }
+        entries.push((schema, json.clone()));
+        json
+    }
+}
+
+impl Clone for JsonCache {
+    fn clone(&self) -> Self {
+        Self(OnceLock::from(self.entries().clone()))
+    }
+}
+
+impl std::fmt::Debug for JsonCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let schemas = self.0.get().map_or(0, |entries| entries.lock().len());
+        f.debug_struct("JsonCache")
+            .field("schemas", &schemas)
+            .finish()
+    }
 }
 
 #[pymethods]
@@ -168,7 +240,7 @@ impl LogRecord {
         levelno: i32,
         pathname: String,
         lineno: u32,
-        msg: String,
+        msg: &Bound<pyo3::types::PyString>,
         args: Option<Py<PyAny>>,
         exc_info: Option<String>,
         func_name: String,
@@ -188,7 +260,7 @@ impl LogRecord {
         LogRecord {
             name,
             levelno,
-            levelname: "".into(),
+            levelname: crate::levels::name(i64::from(levelno)),
             pathname,
             filename: "".into(),
             module: "".into(),
@@ -201,13 +273,17 @@ impl LogRecord {
             thread_name: "".into(),
             process_name: "".into(),
             process: 0,
-            msg,
+            ppid: 0,
+            thread_native_id: 0,
+            seq: 0,
+            msg: crate::sanitize::py_text(msg),
             args: args_value,
             exc_info,
             exc_text: None,
             stack_info,
             task_name: None,
             extra: None,
+            json_cache: JsonCache::default(),
         }
     }
 
@@ -222,6 +298,7 @@ impl LogRecord {
     #[setter]
     fn set_args(&mut self, py: Python, value: Py<PyAny>) -> PyResult<()> {
         let bound = value.bind(py);
+        self.json_cache = JsonCache::default();
         if bound.is_none() {
             self.args = None;
         } else {
@@ -248,6 +325,43 @@ impl LogRecord {
         self.getMessage(py)
     }
 
+    /// `<LogRecord: name, levelno, pathname, lineno, "msg">`, as in the stdlib.
+    fn __repr__(&self) -> String {
+        format!(
+            "<LogRecord: {}, {}, {}, {}, \"{}\">",
+            self.name, self.levelno, self.pathname, self.lineno, self.msg
+        )
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
+
+    /// Records are equal when they carry the same event: logger, level, call site,
+    /// merged message, exception and stack text, and extras. When and where it was
+    /// logged (`created`, thread and process fields, `seq`, the `run_id` extra) is ignored, so
+    /// a captured record can be compared against one built in a test.
+    fn __eq__(&self, py: Python, other: PyRef<Self>) -> PyResult<bool> {
+        fn extras(record: &LogRecord) -> HashMap<&String, &Value> {
+            record
+                .extra
+                .iter()
+                .flatten()
+                .filter(|(key, _)| key.as_str() != "run_id")
+                .collect()
+        }
+        Ok(self.name == other.name
+            && self.levelno == other.levelno
+            && self.pathname == other.pathname
+            && self.lineno == other.lineno
+            && self.func_name == other.func_name
+            && self.exc_info == other.exc_info
+            && self.exc_text == other.exc_text
+            && self.stack_info == other.stack_info
+            && extras(self) == extras(&other)
+            && self.getMessage(py)? == other.getMessage(py)?)
+    }
+
     fn __getattr__(&self, py: Python, name: &str) -> PyResult<Py<PyAny>> {
         if let Some(ref extra) = self.extra {
             if let Some(value) = extra.get(name) {
@@ -281,6 +395,7 @@ impl LogRecord {
 
     fn __setattr__(&mut self, py: Python, name: &str, value: Py<PyAny>) -> PyResult<()> {
         let bound = value.bind(py);
+        self.json_cache = JsonCache::default();
         match name {
             "name" => self.name = bound.extract()?,
             "levelno" => self.levelno = bound.extract()?,
@@ -297,6 +412,9 @@ impl LogRecord {
             "thread_name" | "threadName" => self.thread_name = bound.extract()?,
             "process_name" | "processName" => self.process_name = bound.extract()?,
             "process" => self.process = bound.extract()?,
+            "ppid" => self.ppid = bound.extract()?,
+            "thread_native_id" => self.thread_native_id = bound.extract()?,
+            "seq" => self.seq = bound.extract()?,
             "msg" => self.msg = bound.extract()?,
             "args" => {
                 if bound.is_none() {
@@ -319,6 +437,86 @@ impl LogRecord {
         Ok(())
     }
 
+    /// Every field under its Rust name, `args` as Python values and the extras as
+    /// one `extra` dict. `__setstate__` takes the same dict back.
+    fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let state = PyDict::new(py);
+        state.set_item("name", &self.name)?;
+        state.set_item("levelno", self.levelno)?;
+        state.set_item("levelname", &self.levelname)?;
+        state.set_item("pathname", &self.pathname)?;
+        state.set_item("filename", &self.filename)?;
+        state.set_item("module", &self.module)?;
+        state.set_item("lineno", self.lineno)?;
+        state.set_item("func_name", &self.func_name)?;
+        state.set_item("created", self.created)?;
+        state.set_item("msecs", self.msecs)?;
+        state.set_item("relative_created", self.relative_created)?;
+        state.set_item("thread", self.thread)?;
+        state.set_item("thread_name", &self.thread_name)?;
+        state.set_item("process_name", &self.process_name)?;
+        state.set_item("process", self.process)?;
+        state.set_item("ppid", self.ppid)?;
+        state.set_item("thread_native_id", self.thread_native_id)?;
+        state.set_item("seq", self.seq)?;
+        state.set_item("msg", &self.msg)?;
+        state.set_item("args", self.args(py)?)?;
+        state.set_item("exc_info", &self.exc_info)?;
+        state.set_item("exc_text", &self.exc_text)?;
+        state.set_item("stack_info", &self.stack_info)?;
+        state.set_item("task_name", &self.task_name)?;
+        let extra = PyDict::new(py);
+        if let Some(ref fields) = self.extra {
+            for (key, value) in fields {
+                extra.set_item(key, json_value_to_py_as_list(py, value)?)?;
+            }
+        }
+        state.set_item("extra", extra)?;
+        Ok(state)
+    }
+
+    fn __setstate__(&mut self, py: Python, state: &Bound<PyDict>) -> PyResult<()> {
+        self.json_cache = JsonCache::default();
+        for (key, value) in state.iter() {
+            let key: String = key.extract()?;
+            if key == "extra" {
+                let extra = value.cast::<PyDict>()?;
+                self.extra = if extra.is_empty() {
+                    None
+                } else {
+                    let mut fields = HashMap::with_capacity(extra.len());
+                    for (k, v) in extra.iter() {
+                        fields.insert(k.extract()?, crate::py_logger::py_to_json_value(&v));
+                    }
+                    Some(fields)
+                };
+            } else {
+                self.__setattr__(py, &key, value.unbind())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Pickle support, for `QueueHandler` and multiprocessing: rebuilt from the
+    /// constructor arguments, then every field and extra from `__getstate__`.
+    fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyTuple>, Bound<'py, PyDict>)> {
+        let py = slf.py();
+        let record = slf.borrow();
+        let args = PyTuple::new(
+            py,
+            [
+                record.name.as_str().into_pyobject(py)?.into_any(),
+                record.levelno.into_pyobject(py)?.into_any(),
+                record.pathname.as_str().into_pyobject(py)?.into_any(),
+                record.lineno.into_pyobject(py)?.into_any(),
+                record.msg.as_str().into_pyobject(py)?.into_any(),
+            ],
+        )?;
+        Ok((slf.get_type().into_any(), args, record.__getstate__(py)?))
+    }
+
     #[getter(__dict__)]
     fn get_dict(&self, py: Python) -> PyResult<Py<PyAny>> {
         let dict = PyDict::new(py);
@@ -341,6 +539,9 @@ impl LogRecord {
         dict.set_item("process_name", &self.process_name)?;
         dict.set_item("processName", &self.process_name)?;
         dict.set_item("process", self.process)?;
+        dict.set_item("ppid", self.ppid)?;
+        dict.set_item("thread_native_id", self.thread_native_id)?;
+        dict.set_item("seq", self.seq)?;
         dict.set_item("msg", &self.msg)?;
         dict.set_item("message", &self.msg)?;
         match &self.args {
@@ -363,6 +564,40 @@ impl LogRecord {
 }
 
 impl LogRecord {
+    /// Attach a Rust call site: `pathname` is the `file!()` path, `filename` its last
+    /// component, and `module` the `module_path!()` of the caller. Used by the logging
+    /// macros (`logxide::info!` etc.).
+    pub fn with_location(mut self, file: &str, line: u32, module_path: &str) -> Self {
+        self.filename = std::path::Path::new(file)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(file)
+            .to_string();
+        self.pathname = file.to_string();
+        self.lineno = line;
+        self.module = module_path.to_string();
+        self
+    }
+
+    /// Schema of [`LogRecord::to_json`]: every field under its Rust name.
+    pub const RECORD_JSON: u64 = 0;
+
+    /// A copy for changing: unlike a plain clone, it doesn't share the JSON cache.
+    pub fn detached(&self) -> LogRecord {
+        LogRecord {
+            json_cache: JsonCache::default(),
+            ..self.clone()
+        }
+    }
+
+    /// The whole record as JSON, as pipes, datagrams and spill files write it.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Arc<str> {
+        self.json_cache.get_or_encode(Self::RECORD_JSON, || {
+            serde_json::to_string(self).unwrap_or_default()
+        })
+    }
+
     pub fn get_message(&self) -> String {
         match &self.args {
             None => self.msg.clone(),
@@ -377,10 +612,9 @@ impl LogRecord {
                 let formatted = py_msg
                     .call_method1("__mod__", (py_args,))
                     .expect("String formatting (msg % args) failed");
-                formatted
-                    .str()
-                    .expect("Formatted result is not a string")
-                    .to_string()
+                crate::sanitize::py_text(
+                    &formatted.str().expect("Formatted result is not a string"),
+                )
             }),
         }
     }
@@ -420,8 +654,8 @@ pub fn create_log_record_with_extra(
 
     LogRecord {
         name,
-        levelno: level as i32,
-        levelname: level.as_str().to_string(),
+        levelno: level.value() as i32,
+        levelname: level.name(),
         pathname: String::new(),
         filename: String::new(),
         module: String::new(),
@@ -429,11 +663,14 @@ pub fn create_log_record_with_extra(
         func_name: String::new(),
         created,
         msecs,
-        relative_created: 0.0,
+        relative_created: (created - start_time()) * 1000.0,
         thread: cached_thread_id(),
         thread_name,
         process_name: String::new(),
         process: cached_process_id(),
+        ppid: cached_parent_process_id(),
+        thread_native_id: cached_thread_native_id(),
+        seq: next_seq(),
         msg,
         args: None,
         exc_info: None,
@@ -441,6 +678,7 @@ pub fn create_log_record_with_extra(
         stack_info: None,
         task_name: None,
         extra,
+        json_cache: JsonCache::default(),
     }
 }
 
@@ -459,11 +697,87 @@ fn cached_thread_id() -> u64 {
     THREAD_ID_CACHE.with(|id| *id)
 }
 
-static PROCESS_ID: OnceLock<u32> = OnceLock::new();
+static START_TIME: OnceLock<f64> = OnceLock::new();
+
+/// Seconds since the epoch that `relativeCreated` counts from. The module init sets it
+/// to the stdlib `logging._startTime`, so native and Python records agree.
+pub fn start_time() -> f64 {
+    *START_TIME.get_or_init(|| {
+        let now = chrono::Utc::now();
+        now.timestamp() as f64 + now.timestamp_subsec_nanos() as f64 / 1_000_000_000.0
+    })
+}
+
+/// Set the `relativeCreated` origin; ignored once a record has been created.
+pub fn set_start_time(start: f64) {
+    let _ = START_TIME.set(start);
+}
+
+thread_local! {
+    /// The OS id of this thread; 0 until a record reads it.
+    static THREAD_NATIVE_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+#[inline]
+fn cached_thread_native_id() -> u64 {
+    THREAD_NATIVE_ID.with(|id| {
+        if id.get() == 0 {
+            id.set(crate::platform::thread_native_id());
+        }
+        id.get()
+    })
+}
+
+/// The process and parent process ids, read again in a child created by `os.fork()`.
+struct ProcessIds {
+    pid: AtomicU32,
+    ppid: AtomicU32,
+    _fork_hook: Arc<ForkHook>,
+}
+
+static PROCESS_IDS: Lazy<ProcessIds> = Lazy::new(|| ProcessIds {
+    pid: AtomicU32::new(std::process::id()),
+    ppid: AtomicU32::new(crate::platform::parent_process_id()),
+    _fork_hook: ForkHook::default()
+        .with_in_child(|| {
+            let ids = &*PROCESS_IDS;
+            ids.pid.store(std::process::id(), Ordering::Relaxed);
+            ids.ppid
+                .store(crate::platform::parent_process_id(), Ordering::Relaxed);
+            // The hook runs on the forking thread, the only one the child has.
+            THREAD_NATIVE_ID.with(|id| id.set(0));
+        })
+        .register(),
+});
 
 #[inline]
 fn cached_process_id() -> u32 {
-    *PROCESS_ID.get_or_init(std::process::id)
+    PROCESS_IDS.pid.load(Ordering::Relaxed)
+}
+
+#[inline]
+fn cached_parent_process_id() -> u32 {
+    PROCESS_IDS.ppid.load(Ordering::Relaxed)
+}
+
+struct Sequence {
+    next: AtomicU64,
+    _fork_hook: Arc<ForkHook>,
+}
+
+static SEQUENCE: Lazy<Sequence> = Lazy::new(|| Sequence {
+    next: AtomicU64::new(1),
+    _fork_hook: ForkHook::default()
+        .with_in_child(|| SEQUENCE.next.store(1, Ordering::Relaxed))
+        .register(),
+});
+
+/// The next record's `seq`: records are numbered from 1 in the order they are
+/// created, so a consumer can spot gaps and restore the order of records shipped in
+/// parallel. A child created by `os.fork()` starts again from 1, as it gets its own
+/// `run_id`.
+pub fn next_seq() -> u64 {
+    SEQUENCE.next.fetch_add(1, Ordering::Relaxed)
 }
 
 impl Logger {
@@ -503,7 +817,7 @@ impl Logger {
     pub fn log(&self, level: LogLevel, msg: &str) {
         if self.is_enabled_for(level) {
             let record = create_log_record(self.name.clone(), level, msg.to_string());
-            self.handle(record);
+            self.handle(Arc::new(record));
         }
     }
 
@@ -523,15 +837,13 @@ impl Logger {
         self.log(LogLevel::Critical, msg);
     }
 
-    pub fn handle(&self, record: LogRecord) {
+    pub fn handle(&self, record: Arc<LogRecord>) {
         for filter in &self.filters {
             if !filter.filter(&record) {
                 return;
             }
         }
         for handler in &self.handlers {
-            let handler = handler.clone();
-            let record = record.clone();
             handler.emit(&record);
         }
         if self.propagate {
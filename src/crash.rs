@@ -0,0 +1,327 @@
+//! # Crash Dumps
+//!
+//! A segfault or abort in a native extension kills the process without running any
+//! Python, so its log file just stops. `configure_crash_dump(path)` opens `path` for
+//! appending ahead of time and installs handlers for SIGSEGV, SIGBUS, SIGFPE, SIGILL
+//! and SIGABRT that write a pre-formatted CRITICAL line and the native backtrace to it
+//! with async-signal-safe calls only. The handler then passes the signal on to the one
+//! it replaced: Python's `faulthandler`, enabled on the same file first, which adds the
+//! Python traceback of every thread before the process dies.
+//!
+//! Unix only; other platforms raise NotImplementedError.
+
+use pyo3::prelude::*;
+
+/// Write a CRITICAL line and the native and Python backtraces to a log file when the
+/// process dies of SIGSEGV, SIGBUS, SIGFPE, SIGILL or SIGABRT.
+///
+/// `target` is a path, or a handler with a `baseFilename` (FileHandler and the rotating
+/// handlers) whose file gets the dump; `None` removes the handlers and disables
+/// `faulthandler` again. The file is opened for appending when this is called, so
+/// after a rotation the dump goes to the file that was current then. The Python
+/// traceback comes from `faulthandler`, which this enables on the same file in place
+/// of any earlier `faulthandler.enable()`.
+#[pyfunction]
+#[pyo3(signature = (target=None))]
+pub fn configure_crash_dump(py: Python, target: Option<&Bound<PyAny>>) -> PyResult<()> {
+    imp::configure(py, target)
+}
+
+#[cfg(all(unix, not(target_family = "wasm")))]
+mod imp {
+    use pyo3::prelude::*;
+    use pyo3::types::PyDict;
+    use std::fs::{File, OpenOptions};
+    use std::os::fd::AsRawFd;
+    use std::ptr;
+    use std::sync::atomic::{AtomicI32, AtomicPtr, Ordering};
+    use std::sync::Mutex;
+
+    const SIGNALS: [(libc::c_int, &str); 5] = [
+        (libc::SIGSEGV, "SIGSEGV"),
+        (libc::SIGBUS, "SIGBUS"),
+        (libc::SIGFPE, "SIGFPE"),
+        (libc::SIGILL, "SIGILL"),
+        (libc::SIGABRT, "SIGABRT"),
+    ];
+
+    /// The dump file, -1 while no handler is installed. Read by the signal handler.
+    static CRASH_FD: AtomicI32 = AtomicI32::new(-1);
+
+    /// The action each of SIGNALS had before ours, leaked so the signal handler can
+    /// read it without taking a lock.
+    static PREVIOUS: [AtomicPtr<libc::sigaction>; 5] = [
+        AtomicPtr::new(ptr::null_mut()),
+        AtomicPtr::new(ptr::null_mut()),
+        AtomicPtr::new(ptr::null_mut()),
+        AtomicPtr::new(ptr::null_mut()),
+        AtomicPtr::new(ptr::null_mut()),
+    ];
+
+    /// The open dump file, owned here while the handlers are installed.
+    static FILE: Mutex<Option<File>> = Mutex::new(None);
+
+    pub fn configure(py: Python, target: Option<&Bound<PyAny>>) -> PyResult<()> {
+        let path = match target {
+            None => None,
+            Some(target) => {
+                let path = match target.getattr("baseFilename") {
+                    Ok(name) => name,
+                    Err(_) => target.clone(),
+                };
+                let path = py
+                    .import("os")?
+                    .call_method1("fsdecode", (path,))?
+                    .extract::<std::path::PathBuf>()?;
+                Some(OpenOptions::new().create(true).append(true).open(path)?)
+            }
+        };
+
+        let faulthandler = py.import("faulthandler")?;
+        let mut current = FILE.lock().unwrap();
+        if current.is_some() {
+            uninstall();
+            faulthandler.call_method0("disable")?;
+        }
+        *current = None;
+        let Some(file) = path else {
+            return Ok(());
+        };
+
+        // faulthandler first, so ours runs before it and hands the signal on to it.
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("file", file.as_raw_fd())?;
+        kwargs.set_item("all_threads", true)?;
+        faulthandler.call_method("enable", (), Some(&kwargs))?;
+        // backtrace() loads its unwinder on first use; do that now, not in the handler.
+        capture_backtrace(&mut [ptr::null_mut(); 64]);
+        CRASH_FD.store(file.as_raw_fd(), Ordering::SeqCst);
+        install();
+        *current = Some(file);
+        Ok(())
+    }
+
+    fn install() {
+        for (slot, &(signal, _)) in PREVIOUS.iter().zip(SIGNALS.iter()) {
+            // SAFETY: sigaction is plain data; zeroed is a valid empty action.
+            let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+            action.sa_sigaction =
+                on_fatal_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            // SA_NODEFER lets the re-raised signal reach the previous handler at once;
+            // SA_ONSTACK uses faulthandler's alternate stack after a stack overflow.
+            action.sa_flags = libc::SA_NODEFER | libc::SA_ONSTACK;
+            // SAFETY: as above.
+            let previous =
+                Box::into_raw(Box::new(unsafe { std::mem::zeroed::<libc::sigaction>() }));
+            // SAFETY: both pointers are valid for the call; the handler only makes
+            // async-signal-safe calls.
+            unsafe {
+                libc::sigemptyset(&mut action.sa_mask);
+                if libc::sigaction(signal, &action, previous) != 0 {
+                    drop(Box::from_raw(previous));
+                    continue;
+                }
+            }
+            slot.store(previous, Ordering::SeqCst);
+        }
+    }
+
+    fn uninstall() {
+        CRASH_FD.store(-1, Ordering::SeqCst);
+        for (slot, &(signal, _)) in PREVIOUS.iter().zip(SIGNALS.iter()) {
+            let previous = slot.swap(ptr::null_mut(), Ordering::SeqCst);
+            if previous.is_null() {
+                continue;
+            }
+            // SAFETY: `previous` came from Box::into_raw in install() and was taken out
+            // of its slot above, so nothing else reads it any more.
+            unsafe {
+                libc::sigaction(signal, previous, ptr::null_mut());
+                drop(Box::from_raw(previous));
+            }
+        }
+    }
+
+    extern "C" fn on_fatal_signal(signal: libc::c_int) {
+        let fd = CRASH_FD.load(Ordering::SeqCst);
+        if fd >= 0 {
+            write_dump(fd, signal);
+        }
+        let Some(index) = SIGNALS.iter().position(|&(s, _)| s == signal) else {
+            return;
+        };
+        let previous = PREVIOUS[index].load(Ordering::SeqCst);
+        // SAFETY: sigaction and raise are async-signal-safe; `previous` stays valid
+        // until uninstall() has restored it.
+        unsafe {
+            if previous.is_null() {
+                libc::signal(signal, libc::SIG_DFL);
+            } else {
+                libc::sigaction(signal, previous, ptr::null_mut());
+            }
+            libc::raise(signal);
+        }
+    }
+
+    /// `<UTC time> CRITICAL logxide.crash: Fatal signal <n> (<name>) in process <pid>,
+    /// thread <tid>` and the native backtrace, built on the stack.
+    fn write_dump(fd: libc::c_int, signal: libc::c_int) {
+        let name = SIGNALS
+            .iter()
+            .find(|&&(s, _)| s == signal)
+            .map_or("?", |&(_, name)| name);
+        let mut line = Line::default();
+        line.push_time();
+        line.push(" CRITICAL logxide.crash: Fatal signal ");
+        line.push_num(signal as u64, 1);
+        line.push(" (");
+        line.push(name);
+        line.push(") in process ");
+        // SAFETY: getpid is async-signal-safe and cannot fail.
+        line.push_num(unsafe { libc::getpid() } as u64, 1);
+        line.push(", thread ");
+        line.push_num(crate::platform::thread_native_id(), 1);
+        line.push("\nNative backtrace (most recent call first):\n");
+        line.write(fd);
+
+        let mut frames = [ptr::null_mut(); 64];
+        let depth = capture_backtrace(&mut frames);
+        if depth > 0 {
+            symbolize(&frames[..depth], fd);
+        } else {
+            let mut line = Line::default();
+            line.push("  <unavailable>\n");
+            line.write(fd);
+        }
+    }
+
+    #[cfg(any(all(target_os = "linux", target_env = "gnu"), target_os = "macos"))]
+    fn capture_backtrace(frames: &mut [*mut libc::c_void; 64]) -> usize {
+        // SAFETY: `frames` holds 64 entries.
+        let depth = unsafe { libc::backtrace(frames.as_mut_ptr(), 64) };
+        depth.max(0) as usize
+    }
+
+    #[cfg(not(any(all(target_os = "linux", target_env = "gnu"), target_os = "macos")))]
+    fn capture_backtrace(_frames: &mut [*mut libc::c_void; 64]) -> usize {
+        0
+    }
+
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    fn symbolize(frames: &[*mut libc::c_void], fd: libc::c_int) {
+        extern "C" {
+            fn backtrace_symbols_fd(
+                buffer: *const *mut libc::c_void,
+                size: libc::c_int,
+                fd: libc::c_int,
+            );
+        }
+        // SAFETY: glibc writes one line per frame to `fd` without allocating.
+        unsafe { backtrace_symbols_fd(frames.as_ptr(), frames.len() as libc::c_int, fd) };
+    }
+
+    #[cfg(target_os = "macos")]
+    fn symbolize(frames: &[*mut libc::c_void], fd: libc::c_int) {
+        // SAFETY: writes one line per frame to `fd` without allocating.
+        unsafe { libc::backtrace_symbols_fd(frames.as_ptr(), frames.len() as libc::c_int, fd) };
+    }
+
+    #[cfg(not(any(all(target_os = "linux", target_env = "gnu"), target_os = "macos")))]
+    fn symbolize(_frames: &[*mut libc::c_void], _fd: libc::c_int) {}
+
+    /// A fixed buffer for text built inside the signal handler, which must not allocate.
+    struct Line {
+        buf: [u8; 256],
+        len: usize,
+    }
+
+    impl Default for Line {
+        fn default() -> Self {
+            Self {
+                buf: [0; 256],
+                len: 0,
+            }
+        }
+    }
+
+    impl Line {
+        fn push(&mut self, text: &str) {
+            let n = text.len().min(self.buf.len() - self.len);
+            self.buf[self.len..self.len + n].copy_from_slice(&text.as_bytes()[..n]);
+            self.len += n;
+        }
+
+        /// `value` in decimal, zero-padded to `width` digits.
+        fn push_num(&mut self, mut value: u64, width: usize) {
+            let mut digits = [b'0'; 20];
+            let mut i = digits.len();
+            while value > 0 || digits.len() - i < width {
+                i -= 1;
+                digits[i] = b'0' + (value % 10) as u8;
+                value /= 10;
+            }
+            // The digits are ASCII.
+            self.push(std::str::from_utf8(&digits[i..]).unwrap_or("?"));
+        }
+
+        /// The current UTC time as `YYYY-MM-DDTHH:MM:SS.mmmZ`.
+        fn push_time(&mut self) {
+            // SAFETY: clock_gettime is async-signal-safe; `now` outlives the call.
+            let mut now: libc::timespec = unsafe { std::mem::zeroed() };
+            unsafe { libc::clock_gettime(libc::CLOCK_REALTIME, &mut now) };
+            let secs = now.tv_sec.max(0) as u64;
+            let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+            let time = secs % 86_400;
+            self.push_num(year as u64, 4);
+            self.push("-");
+            self.push_num(month, 2);
+            self.push("-");
+            self.push_num(day, 2);
+            self.push("T");
+            self.push_num(time / 3600, 2);
+            self.push(":");
+            self.push_num(time / 60 % 60, 2);
+            self.push(":");
+            self.push_num(time % 60, 2);
+            self.push(".");
+            self.push_num(now.tv_nsec as u64 / 1_000_000, 3);
+            self.push("Z");
+        }
+
+        fn write(&self, fd: libc::c_int) {
+            // SAFETY: write is async-signal-safe; the buffer outlives the call. A
+            // short or failed write loses part of the dump, which nothing can report.
+            unsafe { libc::write(fd, self.buf.as_ptr().cast(), self.len) };
+        }
+    }
+
+    /// (year, month, day) of a count of days since 1970-01-01 (Howard Hinnant's
+    /// `civil_from_days`).
+    fn civil_from_days(days: i64) -> (i64, u64, u64) {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u64;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u64;
+        let year = yoe + era * 400 + i64::from(month <= 2);
+        (year, month, day)
+    }
+}
+
+#[cfg(not(all(unix, not(target_family = "wasm"))))]
+mod imp {
+    use pyo3::exceptions::PyNotImplementedError;
+    use pyo3::prelude::*;
+
+    pub fn configure(_py: Python, target: Option<&Bound<PyAny>>) -> PyResult<()> {
+        if target.is_none() {
+            return Ok(());
+        }
+        Err(PyNotImplementedError::new_err(
+            "configure_crash_dump() needs POSIX signals, which this platform lacks",
+        ))
+    }
+}
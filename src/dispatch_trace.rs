@@ -0,0 +1,244 @@
+//! # Dispatch Tracing
+//!
+//! `debug_dispatch()` explains, for a sampled fraction of records, what happened to
+//! each one: the logger level or filter that dropped it, the loggers it propagated
+//! through, and for every handler along the way whether it accepted the record or why
+//! not (handler level, full queue, console already written). Each sampled record
+//! produces one block of lines on stderr, written in one go so blocks from different
+//! threads do not interleave.
+//!
+//! Sampled records always take the attached dispatch path, where every decision is
+//! visible; handlers report the drops they make inside `emit` through [`note`].
+
+use pyo3::prelude::*;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use crate::core::{LogLevel, LogRecord};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// `sample_rate` as f64 bits.
+static SAMPLE_RATE: AtomicU64 = AtomicU64::new(0);
+
+/// Records seen since tracing was configured, for deterministic sampling.
+static SEEN: AtomicU64 = AtomicU64::new(0);
+
+/// Longest message excerpt shown in a block header.
+const MAX_EXCERPT: usize = 80;
+
+/// Why a handler that passed its level check still did not keep the record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The queue was full and the record was dropped.
+    QueueFull,
+    /// The queue was full and an older record was evicted to make room.
+    EvictedOlder,
+    /// Console de-duplication: another handler already wrote the record there.
+    ConsoleClaimed,
+}
+
+thread_local! {
+    /// Outcome slot of the handler call being observed. The outer None means no
+    /// observation is running on this thread.
+    static OBSERVED: Cell<Option<Option<Outcome>>> = const { Cell::new(None) };
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Record `outcome` for the handler call being traced, if any.
+pub fn note(outcome: Outcome) {
+    OBSERVED.with(|slot| {
+        if slot.get().is_some() {
+            slot.set(Some(Some(outcome)));
+        }
+    });
+}
+
+/// Run one handler call, returning what it reported through [`note`].
+fn observe<R>(call: impl FnOnce() -> R) -> (R, Option<Outcome>) {
+    let previous = OBSERVED.with(|slot| slot.replace(Some(None)));
+    let result = call();
+    let outcome = OBSERVED.with(|slot| slot.replace(previous)).flatten();
+    (result, outcome)
+}
+
+/// Whether the next record is traced: every `1 / sample_rate`-th one, evenly spread.
+fn sample() -> bool {
+    if !enabled() {
+        return false;
+    }
+    let rate = f64::from_bits(SAMPLE_RATE.load(Ordering::Relaxed));
+    let n = SEEN.fetch_add(1, Ordering::Relaxed) as f64;
+    ((n + 1.0) * rate).floor() > (n * rate).floor()
+}
+
+/// Describe a handler or filter object: its type (or function) name, plus its `name`
+/// when it has a non-empty one.
+pub fn object_label(obj: &Bound<PyAny>) -> String {
+    let kind = obj
+        .getattr("__qualname__")
+        .and_then(|n| n.extract::<String>())
+        .or_else(|_| obj.get_type().name().map(|n| n.to_string()))
+        .unwrap_or_else(|_| "<unknown>".to_string());
+    match obj
+        .getattr("name")
+        .and_then(|n| n.extract::<String>())
+        .ok()
+        .filter(|n| !n.is_empty())
+    {
+        Some(name) => format!("{kind} {name:?}"),
+        None => kind,
+    }
+}
+
+/// Report a record a logger dropped before building it (level or `disabled`).
+pub fn logger_rejected(logger: &str, level: LogLevel, effective: u32, disabled: bool) {
+    if !sample() {
+        return;
+    }
+    let reason = if disabled {
+        "the logger is disabled".to_string()
+    } else if level.value() <= crate::fast_logger::disable_level() {
+        format!(
+            "logging.disable() is set to {}",
+            crate::levels::name(crate::fast_logger::disable_level() as i64)
+        )
+    } else {
+        format!(
+            "{} is below the effective level {}",
+            level.name(),
+            crate::levels::name(effective as i64)
+        )
+    };
+    write_block(format!(
+        "logxide dispatch: {logger} {}\n  logger {logger:?}: dropped, {reason}\n",
+        level.name()
+    ));
+}
+
+/// The trace of one sampled record, written to stderr when dropped.
+pub struct Trace {
+    text: String,
+    /// Handler identity (arc or Python object pointer) -> "'logger' HandlerType".
+    labels: HashMap<usize, String>,
+}
+
+impl Trace {
+    /// Start tracing `record` if it is sampled.
+    pub fn start(record: &LogRecord) -> Option<Trace> {
+        if !sample() {
+            return None;
+        }
+        let message = record.get_message();
+        let mut excerpt: String = message.chars().take(MAX_EXCERPT).collect();
+        if excerpt.len() < message.len() {
+            excerpt.push_str("...");
+        }
+        let mut text = String::new();
+        let _ = writeln!(
+            text,
+            "logxide dispatch: {} {} {excerpt:?}",
+            record.name,
+            crate::levels::name(record.levelno as i64)
+        );
+        Some(Trace {
+            text,
+            labels: HashMap::new(),
+        })
+    }
+
+    pub fn line(&mut self, line: impl std::fmt::Display) {
+        let _ = writeln!(self.text, "  {line}");
+    }
+
+    /// Name the handler with identity `id` in the lines that follow.
+    pub fn label(&mut self, id: usize, label: String) {
+        self.labels.insert(id, label);
+    }
+
+    fn handler_line(&mut self, id: usize, result: String) {
+        let label = self.labels.get(&id).map_or("handler", String::as_str);
+        let _ = writeln!(self.text, "  {label}: {result}");
+    }
+
+    /// Emit to a Rust handler whose minimum level is `level`, recording the result.
+    pub fn native(&mut self, id: usize, level: u32, record: &LogRecord, emit: impl FnOnce()) {
+        let ((), outcome) = observe(emit);
+        let result = if record.levelno < level as i32 {
+            format!(
+                "skipped, {} is below the handler level {}",
+                crate::levels::name(record.levelno as i64),
+                crate::levels::name(level as i64)
+            )
+        } else {
+            describe(outcome, "accepted")
+        };
+        self.handler_line(id, result);
+    }
+
+    /// Call a Python handler's `handle()`, recording whether it kept the record.
+    pub fn python(&mut self, id: usize, handle: impl FnOnce() -> bool) {
+        let (handled, outcome) = observe(handle);
+        let result = if handled {
+            describe(outcome, "handled")
+        } else {
+            "filtered out by the handler".to_string()
+        };
+        self.handler_line(id, result);
+    }
+}
+
+fn describe(outcome: Option<Outcome>, kept: &str) -> String {
+    match outcome {
+        Some(Outcome::QueueFull) => "dropped, queue full".to_string(),
+        Some(Outcome::EvictedOlder) => {
+            format!("{kept}, queue full so an older record was dropped")
+        }
+        Some(Outcome::ConsoleClaimed) => {
+            "skipped, an earlier handler already wrote it to this console".to_string()
+        }
+        None => kept.to_string(),
+    }
+}
+
+impl Drop for Trace {
+    fn drop(&mut self) {
+        write_block(std::mem::take(&mut self.text));
+    }
+}
+
+fn write_block(text: String) {
+    let mut stderr = std::io::stderr().lock();
+    let _ = stderr.write_all(text.as_bytes());
+    let _ = stderr.flush();
+}
+
+/// Trace how records are dispatched, for debugging logging configuration.
+///
+/// For a sampled fraction of records, writes a block to stderr naming the loggers the
+/// record went through, and for each handler whether it accepted the record or why it
+/// was dropped: logger level, a logger filter, the handler's level, a full queue or
+/// console de-duplication.
+///
+/// Args:
+///     enabled: Turn tracing on or off.
+///     sample_rate: Fraction of records traced, evenly spread (1.0 = every record).
+#[pyfunction]
+#[pyo3(signature = (enabled=true, sample_rate=1.0))]
+pub fn debug_dispatch(enabled: bool, sample_rate: f64) -> PyResult<()> {
+    if !(sample_rate > 0.0 && sample_rate <= 1.0) {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "sample_rate must be in (0, 1]",
+        ));
+    }
+    SAMPLE_RATE.store(sample_rate.to_bits(), Ordering::Relaxed);
+    SEEN.store(0, Ordering::Relaxed);
+    ENABLED.store(enabled, Ordering::Relaxed);
+    Ok(())
+}
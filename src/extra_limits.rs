@@ -0,0 +1,137 @@
+//! # Extra Limits
+//!
+//! Guards against huge `extra=` payloads: a record keeps at most `max_keys` extra
+//! fields (the first ones, in the order of the `extra` dict), and a value whose
+//! serialized size is over `max_value_bytes` keeps only that many bytes followed by a
+//! `...[truncated N bytes]` marker. Values that are not strings are truncated as JSON
+//! text. A record that lost keys gets an `extra_keys_dropped` extra with the count.
+//!
+//! The limits apply to `extra=` and structured keyword arguments on records logged
+//! through logxide loggers; both limits are off by default. Every truncation is
+//! counted, and the counts are reported by `extra_limit_stats()` and the queue
+//! diagnostics reporter.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde_json::Value;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// 0 means no limit.
+static MAX_KEYS: AtomicUsize = AtomicUsize::new(0);
+static MAX_VALUE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+static RECORDS_LIMITED: AtomicU64 = AtomicU64::new(0);
+static KEYS_DROPPED: AtomicU64 = AtomicU64::new(0);
+static VALUES_TRUNCATED: AtomicU64 = AtomicU64::new(0);
+
+/// Extra added to a record that lost keys to `max_keys`.
+pub const KEYS_DROPPED_EXTRA: &str = "extra_keys_dropped";
+
+/// Limits in effect for one record's extras.
+#[derive(Debug, Clone, Copy)]
+pub struct ExtraLimits {
+    max_keys: Option<usize>,
+    max_value_bytes: Option<usize>,
+    keys_dropped: u64,
+    values_truncated: u64,
+}
+
+impl ExtraLimits {
+    pub fn current() -> Self {
+        let limit = |value: usize| (value > 0).then_some(value);
+        Self {
+            max_keys: limit(MAX_KEYS.load(Ordering::Relaxed)),
+            max_value_bytes: limit(MAX_VALUE_BYTES.load(Ordering::Relaxed)),
+            keys_dropped: 0,
+            values_truncated: 0,
+        }
+    }
+
+    /// Whether a record that already has `kept` extras may take another one. A refused
+    /// key is counted; its value need not be converted at all.
+    pub fn admit(&mut self, kept: usize) -> bool {
+        match self.max_keys {
+            Some(max) if kept >= max => {
+                self.keys_dropped += 1;
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// `value`, cut down to `max_value_bytes` when it serializes to more.
+    pub fn value(&mut self, value: Value) -> Value {
+        let Some(max) = self.max_value_bytes else {
+            return value;
+        };
+        let text = match value {
+            Value::String(s) if s.len() <= max => return Value::String(s),
+            Value::String(s) => s,
+            other => match serde_json::to_string(&other) {
+                Ok(json) if json.len() > max => json,
+                _ => return other,
+            },
+        };
+        self.values_truncated += 1;
+        let mut end = max;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        Value::String(format!(
+            "{}...[truncated {} bytes]",
+            &text[..end],
+            text.len() - end
+        ))
+    }
+
+    /// Record the outcome for one record: count it, and mark dropped keys in `extra`.
+    pub fn finish<E: Extend<(String, Value)>>(self, extra: &mut E) {
+        if self.keys_dropped == 0 && self.values_truncated == 0 {
+            return;
+        }
+        RECORDS_LIMITED.fetch_add(1, Ordering::Relaxed);
+        KEYS_DROPPED.fetch_add(self.keys_dropped, Ordering::Relaxed);
+        VALUES_TRUNCATED.fetch_add(self.values_truncated, Ordering::Relaxed);
+        if self.keys_dropped > 0 {
+            extra.extend([(KEYS_DROPPED_EXTRA.to_string(), self.keys_dropped.into())]);
+        }
+    }
+}
+
+/// Limit the `extra=` fields of records logged through logxide loggers.
+///
+/// A record keeps its first `max_keys` extras and gets an `extra_keys_dropped` count
+/// when it had more. A value whose serialized size is over `max_value_bytes` keeps
+/// that many bytes followed by `...[truncated N bytes]`. `None` removes a limit.
+///
+/// Args:
+///     max_keys: Most extra fields kept per record.
+///     max_value_bytes: Largest serialized size of one extra value, in bytes.
+#[pyfunction]
+#[pyo3(signature = (max_keys=None, max_value_bytes=None))]
+pub fn configure_extra_limits(
+    max_keys: Option<usize>,
+    max_value_bytes: Option<usize>,
+) -> PyResult<()> {
+    if max_keys == Some(0) || max_value_bytes == Some(0) {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "extra limits must be positive or None",
+        ));
+    }
+    MAX_KEYS.store(max_keys.unwrap_or(0), Ordering::Relaxed);
+    MAX_VALUE_BYTES.store(max_value_bytes.unwrap_or(0), Ordering::Relaxed);
+    Ok(())
+}
+
+/// Counts of extras cut by `configure_extra_limits()` since the process started.
+///
+/// Returns:
+///     dict: `records_limited`, `keys_dropped` and `values_truncated`.
+#[pyfunction]
+pub fn extra_limit_stats(py: Python) -> PyResult<Bound<PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("records_limited", RECORDS_LIMITED.load(Ordering::Relaxed))?;
+    dict.set_item("keys_dropped", KEYS_DROPPED.load(Ordering::Relaxed))?;
+    dict.set_item("values_truncated", VALUES_TRUNCATED.load(Ordering::Relaxed))?;
+    Ok(dict)
+}
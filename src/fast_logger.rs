@@ -8,6 +8,18 @@ use crate::core::LogLevel;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
 
+/// Records at or below this level are dropped by every logger (`logging.disable()`);
+/// 0 when off.
+static DISABLE_LEVEL: AtomicU32 = AtomicU32::new(0);
+
+pub fn set_disable_level(level: u32) {
+    DISABLE_LEVEL.store(level, Ordering::Relaxed);
+}
+
+pub fn disable_level() -> u32 {
+    DISABLE_LEVEL.load(Ordering::Relaxed)
+}
+
 /// Fast logger using atomic operations for lock-free level checking
 #[derive(Debug)]
 pub struct FastLogger {
@@ -23,8 +35,8 @@ impl FastLogger {
     pub fn new(name: &str) -> Self {
         Self {
             name: Arc::from(name),
-            level: AtomicU32::new(LogLevel::NotSet as u32),
-            effective_level: AtomicU32::new(LogLevel::Warning as u32), // Default
+            level: AtomicU32::new(LogLevel::NotSet.value()),
+            effective_level: AtomicU32::new(LogLevel::Warning.value()), // Default
             disabled: AtomicBool::new(false),
             propagate: AtomicBool::new(true),
         }
@@ -33,11 +45,12 @@ impl FastLogger {
     #[inline(always)]
     pub fn is_enabled_for(&self, level: LogLevel) -> bool {
         !self.disabled.load(Ordering::Relaxed)
-            && level as u32 >= self.effective_level.load(Ordering::Relaxed)
+            && level.value() > DISABLE_LEVEL.load(Ordering::Relaxed)
+            && level.value() >= self.effective_level.load(Ordering::Relaxed)
     }
 
     pub fn set_level(&self, level: LogLevel) {
-        self.level.store(level as u32, Ordering::Relaxed);
+        self.level.store(level.value(), Ordering::Relaxed);
         self.update_effective_level();
     }
 
@@ -49,12 +62,10 @@ impl FastLogger {
         self.effective_level.load(Ordering::Relaxed)
     }
 
-    #[allow(dead_code)]
     pub fn set_disabled(&self, disabled: bool) {
         self.disabled.store(disabled, Ordering::Relaxed);
     }
 
-    #[allow(dead_code)]
     pub fn is_disabled(&self) -> bool {
         self.disabled.load(Ordering::Relaxed)
     }
@@ -64,7 +75,7 @@ impl FastLogger {
         // Only update effective_level when an explicit level is set.
         // For NOTSET, leave effective_level unchanged — it will be
         // resolved correctly by propagate_effective_levels().
-        if level != LogLevel::NotSet as u32 {
+        if level != LogLevel::NotSet.value() {
             self.effective_level.store(level, Ordering::Relaxed);
         }
     }
@@ -131,8 +142,8 @@ impl FastLoggerManager {
     pub fn propagate_effective_levels(&self) {
         // Update root's effective level first
         let root_level = self.root_logger.level.load(Ordering::Relaxed);
-        let root_effective = if root_level == LogLevel::NotSet as u32 {
-            LogLevel::Warning as u32
+        let root_effective = if root_level == LogLevel::NotSet.value() {
+            LogLevel::Warning.value()
         } else {
             root_level
         };
@@ -144,7 +155,7 @@ impl FastLoggerManager {
         for entry in self.loggers.iter() {
             let logger = entry.value();
             let own_level = logger.level.load(Ordering::Relaxed);
-            let effective = if own_level != LogLevel::NotSet as u32 {
+            let effective = if own_level != LogLevel::NotSet.value() {
                 own_level
             } else {
                 self.resolve_parent_effective_level(entry.key())
@@ -160,7 +171,7 @@ impl FastLoggerManager {
             current = &current[..dot_idx];
             if let Some(parent) = self.loggers.get(current) {
                 let parent_level = parent.level.load(Ordering::Relaxed);
-                if parent_level != LogLevel::NotSet as u32 {
+                if parent_level != LogLevel::NotSet.value() {
                     return parent_level;
                 }
                 // Parent is also NOTSET, keep walking up
@@ -168,10 +179,10 @@ impl FastLoggerManager {
         }
         // Reached root
         let root_level = self.root_logger.level.load(Ordering::Relaxed);
-        if root_level != LogLevel::NotSet as u32 {
+        if root_level != LogLevel::NotSet.value() {
             root_level
         } else {
-            LogLevel::Warning as u32
+            LogLevel::Warning.value()
         }
     }
 }
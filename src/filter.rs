@@ -7,6 +7,19 @@
 //! ## Filter Types
 //!
 //! - **AllowAllFilter**: Pass-through filter that accepts all records
+//! - **NameFilter**: `logging.Filter` semantics, exposed to Python as `logxide.Filter`
+//! - **RegexFilter**: a regex over `msg`, `name` or an extra field, exposed to Python
+//!   as `logxide.RegexFilter` (`regex-filters` feature)
+//! - **LevelRangeFilter**: a band of levels, exposed to Python as
+//!   `logxide.LevelRangeFilter`
+//! - **DedupFilter**: collapses runs of identical messages into one summary record,
+//!   exposed to Python as `logxide.DedupFilter`
+//!
+//! Loggers and handlers whose filters are all these Rust filters (except the stateful
+//! DedupFilter) run them without building a Python record; handlers keep them in a
+//! `FilterSlot`, which also runs `logxide.Redactor` (see `redact.rs`) and
+//! `logxide.FieldProjection` (see `projection.rs`) on the handler's own copy of each
+//! record.
 //!
 //! ## Custom Filters
 //!
@@ -24,6 +37,19 @@
 //! so implementations should be efficient. Complex filtering logic
 //! should be optimized for the common case.
 
+use arc_swap::ArcSwap;
+use pyo3::prelude::*;
+#[cfg(feature = "regex-filters")]
+use regex::Regex;
+#[cfg(feature = "regex-filters")]
+use serde_json::Value;
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::core::LogRecord;
+use crate::fork::ForkHook;
+
 /// Trait for filtering log records based on custom criteria.
 ///
 /// Filters provide a way to selectively process log records beyond
@@ -52,6 +78,13 @@ pub trait Filter: Send + Sync {
     ///
     /// * `true` if the record should be processed, `false` otherwise.
     fn filter(&self, record: &crate::core::LogRecord) -> bool;
+
+    /// A changed copy of a record that passed, for the handler running this filter
+    /// alone; None keeps the record as it is. `Redactor` and `FieldProjection` rewrite
+    /// records.
+    fn rewrite(&self, _record: &crate::core::LogRecord) -> Option<crate::core::LogRecord> {
+        None
+    }
 }
 
 /// Simple filter that allows all log records to pass through.
@@ -84,3 +117,594 @@ impl Filter for AllowAllFilter {
         true
     }
 }
+
+/// Passes records from the logger `name` and its children, like `logging.Filter`:
+/// `Filter("app.db")` passes `app.db` and `app.db.pool` but not `app.dbx`. An empty
+/// name passes everything.
+///
+/// A logger whose filters are all Rust filters (`logxide.Filter`, `logxide.RegexFilter`)
+/// runs them in Rust, so its records keep the native dispatch path; other filters are
+/// called with a Python record.
+///
+/// Args:
+///     name: Logger name whose records (and its children's) pass.
+#[pyclass(
+    name = "Filter",
+    module = "logxide",
+    frozen,
+    subclass,
+    skip_from_py_object
+)]
+#[derive(Debug, Clone)]
+pub struct NameFilter {
+    name: String,
+}
+
+impl NameFilter {
+    pub fn passes(&self, logger: &str) -> bool {
+        match logger.strip_prefix(self.name.as_str()) {
+            Some(rest) => self.name.is_empty() || rest.is_empty() || rest.starts_with('.'),
+            None => false,
+        }
+    }
+}
+
+impl Filter for NameFilter {
+    fn filter(&self, record: &crate::core::LogRecord) -> bool {
+        self.passes(&record.name)
+    }
+}
+
+#[pymethods]
+impl NameFilter {
+    #[new]
+    #[pyo3(signature = (name=String::new()))]
+    fn new(name: String) -> Self {
+        Self { name }
+    }
+
+    #[getter]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[getter]
+    fn nlen(&self) -> usize {
+        self.name.len()
+    }
+
+    /// Whether `record` (any object with a `name` attribute) passes.
+    fn filter(&self, record: &Bound<PyAny>) -> PyResult<bool> {
+        let name = record.getattr(pyo3::intern!(record.py(), "name"))?;
+        Ok(self.passes(&name.extract::<std::borrow::Cow<str>>()?))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Filter {:?}>", self.name)
+    }
+}
+
+/// Passes or drops records by matching a regex against one of their fields.
+///
+/// `field` is `"msg"` (the message before `%` formatting), `"name"` (the logger name)
+/// or the name of an `extra=` field; extras that are not strings are matched as JSON
+/// text. The regex is searched for anywhere in the value, like `re.search`. With
+/// `mode="include"` only matching records pass; with `mode="exclude"` matching records
+/// are dropped. A record without the field does not match.
+///
+/// Runs in Rust like `logxide.Filter`, so loggers whose filters are all Rust filters
+/// keep the native dispatch path.
+///
+/// Args:
+///     pattern: Regex in the syntax of the Rust `regex` crate.
+///     field: `"msg"`, `"name"` or an extra field name.
+///     mode: `"include"` or `"exclude"`.
+#[cfg(feature = "regex-filters")]
+#[pyclass(
+    name = "RegexFilter",
+    module = "logxide",
+    frozen,
+    subclass,
+    skip_from_py_object
+)]
+#[derive(Debug, Clone)]
+pub struct RegexFilter {
+    regex: Regex,
+    field: String,
+    include: bool,
+}
+
+#[cfg(feature = "regex-filters")]
+impl RegexFilter {
+    /// Whether the regex matches the record's field.
+    fn matches(&self, record: &LogRecord) -> bool {
+        match self.field.as_str() {
+            "msg" => self.regex.is_match(&record.msg),
+            "name" => self.regex.is_match(&record.name),
+            key => match record.extra.as_ref().and_then(|extra| extra.get(key)) {
+                Some(Value::String(text)) => self.regex.is_match(text),
+                Some(value) => self.regex.is_match(&value.to_string()),
+                None => false,
+            },
+        }
+    }
+
+    pub fn passes(&self, record: &LogRecord) -> bool {
+        self.matches(record) == self.include
+    }
+}
+
+#[cfg(feature = "regex-filters")]
+impl Filter for RegexFilter {
+    fn filter(&self, record: &LogRecord) -> bool {
+        self.passes(record)
+    }
+}
+
+#[cfg(feature = "regex-filters")]
+#[pymethods]
+impl RegexFilter {
+    #[new]
+    #[pyo3(signature = (pattern, field="msg", mode="include"))]
+    fn new(pattern: &str, field: &str, mode: &str) -> PyResult<Self> {
+        let include = match mode {
+            "include" => true,
+            "exclude" => false,
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "mode must be 'include' or 'exclude', not {mode:?}"
+                )))
+            }
+        };
+        let regex = Regex::new(pattern)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(Self {
+            regex,
+            field: field.to_string(),
+            include,
+        })
+    }
+
+    #[getter]
+    fn pattern(&self) -> &str {
+        self.regex.as_str()
+    }
+
+    #[getter]
+    fn field(&self) -> &str {
+        &self.field
+    }
+
+    #[getter]
+    fn mode(&self) -> &str {
+        if self.include {
+            "include"
+        } else {
+            "exclude"
+        }
+    }
+
+    /// Whether `record` passes. Records that are not logxide `LogRecord`s are matched
+    /// on `str()` of the attribute named by `field`.
+    fn filter(&self, record: &Bound<PyAny>) -> PyResult<bool> {
+        if let Ok(record) = record.cast::<LogRecord>() {
+            return Ok(self.passes(&record.borrow()));
+        }
+        let matches = match record.getattr(self.field.as_str()) {
+            Ok(value) if value.is_none() => false,
+            Ok(value) => self.regex.is_match(&value.str()?.to_cow()?),
+            Err(_) => false,
+        };
+        Ok(matches == self.include)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<RegexFilter {:?} on {}, {}>",
+            self.regex.as_str(),
+            self.field,
+            self.mode()
+        )
+    }
+}
+
+/// Passes records whose level is between `min_level` and `max_level`, both included,
+/// so handlers can split a stream by level: INFO..WARNING to stdout and ERROR and
+/// above to stderr. `max_level=None` has no upper bound.
+///
+/// Runs in Rust on loggers and on logxide handlers, which keep their native dispatch
+/// path when all their filters are Rust filters.
+///
+/// Args:
+///     min_level: Lowest level passed, as a number or name.
+///     max_level: Highest level passed, as a number or name, or None.
+#[pyclass(
+    name = "LevelRangeFilter",
+    module = "logxide",
+    frozen,
+    subclass,
+    skip_from_py_object
+)]
+#[derive(Debug, Clone)]
+pub struct LevelRangeFilter {
+    min_level: i32,
+    max_level: Option<i32>,
+}
+
+impl LevelRangeFilter {
+    pub fn passes(&self, levelno: i32) -> bool {
+        levelno >= self.min_level && self.max_level.is_none_or(|max| levelno <= max)
+    }
+}
+
+impl Filter for LevelRangeFilter {
+    fn filter(&self, record: &LogRecord) -> bool {
+        self.passes(record.levelno)
+    }
+}
+
+#[pymethods]
+impl LevelRangeFilter {
+    #[new]
+    #[pyo3(signature = (min_level=None, max_level=None))]
+    fn new(
+        py: Python,
+        min_level: Option<&Bound<PyAny>>,
+        max_level: Option<&Bound<PyAny>>,
+    ) -> PyResult<Self> {
+        let level = |level: &Bound<PyAny>| crate::py_logger::check_level(py, level);
+        let min_level = min_level.map(level).transpose()?.unwrap_or(0) as i32;
+        let max_level = max_level.map(level).transpose()?.map(|max| max as i32);
+        if max_level.is_some_and(|max| max < min_level) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "max_level must not be below min_level",
+            ));
+        }
+        Ok(Self {
+            min_level,
+            max_level,
+        })
+    }
+
+    #[getter]
+    fn min_level(&self) -> i32 {
+        self.min_level
+    }
+
+    #[getter]
+    fn max_level(&self) -> Option<i32> {
+        self.max_level
+    }
+
+    /// Whether `record` (any object with a `levelno` attribute) passes.
+    fn filter(&self, record: &Bound<PyAny>) -> PyResult<bool> {
+        let levelno = record.getattr(pyo3::intern!(record.py(), "levelno"))?;
+        Ok(self.passes(levelno.extract()?))
+    }
+
+    fn __repr__(&self) -> String {
+        match self.max_level {
+            Some(max) => format!("<LevelRangeFilter {}..{max}>", self.min_level),
+            None => format!("<LevelRangeFilter {}..>", self.min_level),
+        }
+    }
+}
+
+/// A run of identical records seen by a `DedupFilter`: the first passed, the repeats
+/// were dropped.
+struct Burst {
+    /// Logger name, level and formatted message.
+    key: (String, i32, String),
+    first: LogRecord,
+    started: Instant,
+    repeats: u64,
+    last_created: f64,
+}
+
+impl Burst {
+    /// The record logged when the run ends: the first record with the repeat count in
+    /// the message and as `dedup_*` extras. None when nothing was dropped.
+    fn summary(self) -> Option<LogRecord> {
+        let Burst {
+            key: (_, _, message),
+            first: mut record,
+            repeats,
+            last_created,
+            ..
+        } = self;
+        if repeats == 0 {
+            return None;
+        }
+        let plural = if repeats == 1 { "" } else { "s" };
+        record.msg = format!("{message} (repeated {repeats} more time{plural})");
+        record.args = None;
+        record.exc_info = None;
+        record.exc_text = None;
+        record.stack_info = None;
+        let first_created = record.created;
+        let extra = record.extra.get_or_insert_with(Default::default);
+        extra.insert(DEDUP_COUNT.to_string(), repeats.into());
+        extra.insert("dedup_first".to_string(), first_created.into());
+        extra.insert("dedup_last".to_string(), last_created.into());
+        Some(record)
+    }
+}
+
+/// Marks summary records, which every `DedupFilter` passes.
+const DEDUP_COUNT: &str = "dedup_count";
+
+/// What the window timer shares with the filter.
+struct DedupState {
+    window: Duration,
+    burst: parking_lot::Mutex<Option<Burst>>,
+}
+
+impl DedupState {
+    /// End the current run if its window has closed.
+    fn close_expired(&self, py: Python) {
+        let expired = {
+            let mut burst = self.burst.lock();
+            match burst.as_ref() {
+                Some(b) if b.started.elapsed() >= self.window => burst.take(),
+                _ => None,
+            }
+        };
+        if let Some(summary) = expired.and_then(Burst::summary) {
+            log_summary(py, summary);
+        }
+    }
+}
+
+/// `record` as a logxide `LogRecord`, or None for a summary, which passes every
+/// `DedupFilter`. Other records (stdlib ones, or those Python handlers receive) keep
+/// their logger name, level, formatted message and time.
+fn dedup_record(record: &Bound<PyAny>) -> PyResult<Option<LogRecord>> {
+    if let Ok(record) = record.cast::<LogRecord>() {
+        let record = record.borrow();
+        let summary = record
+            .extra
+            .as_ref()
+            .is_some_and(|extra| extra.contains_key(DEDUP_COUNT));
+        return Ok((!summary).then(|| record.clone()));
+    }
+    let py = record.py();
+    if record.hasattr(DEDUP_COUNT)? {
+        return Ok(None);
+    }
+    let levelno: i32 = record.getattr(pyo3::intern!(py, "levelno"))?.extract()?;
+    let message = record.call_method0(pyo3::intern!(py, "getMessage"))?;
+    let mut rust_record = crate::core::create_log_record(
+        record
+            .getattr(pyo3::intern!(py, "name"))?
+            .str()?
+            .to_string(),
+        crate::core::LogLevel::from_usize(levelno.max(0) as usize),
+        message.str()?.to_string(),
+    );
+    rust_record.levelno = levelno;
+    if let Ok(created) = record.getattr(pyo3::intern!(py, "created"))?.extract() {
+        rust_record.created = created;
+    }
+    Ok(Some(rust_record))
+}
+
+/// Hand a summary record to the logger that logged the run, as if logged there.
+fn log_summary(py: Python, record: LogRecord) {
+    match crate::globals::get_logger(py, Some(&record.name), None) {
+        Ok(logger) => logger.borrow(py).dispatch(py, record, None),
+        Err(e) => eprintln!("[LogXide Error] DedupFilter summary not logged: {e}"),
+    }
+}
+
+type DedupTimer = (crossbeam_channel::Sender<()>, std::thread::JoinHandle<()>);
+
+/// Collapses consecutive identical messages, journald-style: the first record of a run
+/// passes, identical records (same logger, level and formatted message) within
+/// `window_seconds` of it are dropped, and when the window closes or a different
+/// message arrives one summary record is logged, `"<message> (repeated N more
+/// times)"` with `dedup_count` (N), `dedup_first` and `dedup_last` extras.
+///
+/// The summary is logged through the logger of the run's records, so it reaches the
+/// same handlers; a summary that closes a run arrives before the record that ended
+/// it. A background thread closes windows when no further record arrives; wasm32
+/// builds have none, so there a run ends only with the next record.
+///
+/// Args:
+///     window_seconds: How long after the first record of a run repeats are folded
+///         into its summary.
+#[pyclass(name = "DedupFilter", module = "logxide", frozen, subclass)]
+pub struct DedupFilter {
+    state: Arc<DedupState>,
+    /// Started on the first dropped repeat. Dropping the sender stops it.
+    timer: Arc<parking_lot::Mutex<Option<DedupTimer>>>,
+    /// Forgets the run and the timer in a forked child.
+    _fork_hook: Arc<ForkHook>,
+}
+
+impl DedupFilter {
+    fn ensure_timer(&self) {
+        if !crate::platform::THREADS {
+            // No timer: the run's summary is logged when the next record arrives.
+            return;
+        }
+        let mut timer = self.timer.lock();
+        if timer.is_some() {
+            return;
+        }
+        let (stop_tx, stop_rx) = crossbeam_channel::bounded::<()>(0);
+        let state = self.state.clone();
+        let spawned = crate::threads::spawn("dedup", move || loop {
+            let wait = state.burst.lock().as_ref().map_or(state.window, |b| {
+                state.window.saturating_sub(b.started.elapsed())
+            });
+            match stop_rx.recv_timeout(wait) {
+                Err(crossbeam_channel::RecvTimeoutError::Timeout) => {
+                    Python::attach(|py| state.close_expired(py));
+                }
+                _ => return,
+            }
+        });
+        match spawned {
+            Ok(handle) => *timer = Some((stop_tx, handle)),
+            Err(e) => eprintln!("[LogXide Error] DedupFilter timer not started: {e}"),
+        }
+    }
+}
+
+#[pymethods]
+impl DedupFilter {
+    #[new]
+    fn new(window_seconds: f64) -> PyResult<Self> {
+        if !window_seconds.is_finite() || window_seconds <= 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "window_seconds must be a positive number",
+            ));
+        }
+        let state = Arc::new(DedupState {
+            window: Duration::from_secs_f64(window_seconds),
+            burst: parking_lot::Mutex::new(None),
+        });
+        let timer = Arc::new(parking_lot::Mutex::new(None));
+        let fork_hook = {
+            let state = state.clone();
+            let timer = timer.clone();
+            ForkHook::default()
+                .with_in_child(move || {
+                    // The parent reports the run that was open at the fork.
+                    crate::fork::unlock_in_child(&state.burst);
+                    *state.burst.lock() = None;
+                    crate::fork::unlock_in_child(&timer);
+                    // The parent's handle names a thread that does not exist here.
+                    if let Some(parent) = timer.lock().take() {
+                        std::mem::forget(parent);
+                    }
+                })
+                .register()
+        };
+        Ok(Self {
+            state,
+            timer,
+            _fork_hook: fork_hook,
+        })
+    }
+
+    #[getter]
+    fn window_seconds(&self) -> f64 {
+        self.state.window.as_secs_f64()
+    }
+
+    /// Whether `record` passes: False for a repeat inside the window. A record that
+    /// ends a run first logs the run's summary.
+    fn filter(&self, py: Python, record: &Bound<PyAny>) -> PyResult<bool> {
+        let Some(record) = dedup_record(record)? else {
+            return Ok(true);
+        };
+        let key = (record.name.clone(), record.levelno, record.get_message());
+        let ended = {
+            let mut burst = self.state.burst.lock();
+            match burst.as_mut() {
+                Some(b) if b.key == key && b.started.elapsed() < self.state.window => {
+                    b.repeats += 1;
+                    b.last_created = record.created;
+                    None
+                }
+                _ => Some(burst.replace(Burst {
+                    key,
+                    last_created: record.created,
+                    first: record,
+                    started: Instant::now(),
+                    repeats: 0,
+                })),
+            }
+        };
+        let Some(ended) = ended else {
+            self.ensure_timer();
+            return Ok(false);
+        };
+        if let Some(summary) = ended.and_then(Burst::summary) {
+            log_summary(py, summary);
+        }
+        Ok(true)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<DedupFilter {}s>", self.state.window.as_secs_f64())
+    }
+}
+
+/// Run `filter` in Rust when it is exactly one of the Rust filters; None for any other
+/// filter, including subclasses.
+pub fn native_filter(filter: &Bound<PyAny>, record: &LogRecord) -> Option<bool> {
+    if let Ok(filter) = filter.cast_exact::<NameFilter>() {
+        return Some(filter.get().passes(&record.name));
+    }
+    if let Ok(filter) = filter.cast_exact::<LevelRangeFilter>() {
+        return Some(filter.get().passes(record.levelno));
+    }
+    #[cfg(feature = "regex-filters")]
+    if let Ok(filter) = filter.cast_exact::<RegexFilter>() {
+        return Some(filter.get().passes(record));
+    }
+    None
+}
+
+/// `filter` as a Rust filter a handler can keep, or None for a Python filter.
+fn to_native(filter: &Bound<PyAny>) -> Option<Arc<dyn Filter>> {
+    if let Ok(filter) = filter.cast_exact::<NameFilter>() {
+        return Some(Arc::new(filter.get().clone()));
+    }
+    if let Ok(filter) = filter.cast_exact::<LevelRangeFilter>() {
+        return Some(Arc::new(filter.get().clone()));
+    }
+    #[cfg(feature = "regex-filters")]
+    if let Ok(filter) = filter.cast_exact::<crate::redact::Redactor>() {
+        return Some(Arc::new(filter.get().clone()));
+    }
+    if let Ok(filter) = filter.cast_exact::<crate::projection::FieldProjection>() {
+        return Some(Arc::new(filter.get().clone()));
+    }
+    #[cfg(feature = "regex-filters")]
+    if let Ok(filter) = filter.cast_exact::<RegexFilter>() {
+        return Some(Arc::new(filter.get().clone()));
+    }
+    None
+}
+
+/// A handler's Rust filters, changeable while the handler is in use. Empty passes
+/// every record.
+#[derive(Default)]
+pub struct FilterSlot(ArcSwap<Vec<Arc<dyn Filter>>>);
+
+impl FilterSlot {
+    /// `record` when every filter passes it, or the copy a rewriting filter made;
+    /// None when a filter drops it.
+    pub fn apply<'a>(&self, record: &'a LogRecord) -> Option<Cow<'a, LogRecord>> {
+        let mut record = Cow::Borrowed(record);
+        for filter in self.0.load().iter() {
+            if !filter.filter(&record) {
+                return None;
+            }
+            if let Some(rewritten) = filter.rewrite(&record) {
+                record = Cow::Owned(rewritten);
+            }
+        }
+        Some(record)
+    }
+
+    /// [`FilterSlot::apply`] for a record handlers share.
+    pub fn apply_shared<'a>(&self, record: &'a Arc<LogRecord>) -> Option<Cow<'a, Arc<LogRecord>>> {
+        Some(match self.apply(record)? {
+            Cow::Borrowed(_) => Cow::Borrowed(record),
+            Cow::Owned(rewritten) => Cow::Owned(Arc::new(rewritten)),
+        })
+    }
+
+    /// Take a handler's `filters` when they are all Rust filters and return true;
+    /// otherwise empty the slot and return false, leaving them to the Python wrapper.
+    pub fn set(&self, filters: &[Bound<PyAny>]) -> bool {
+        let native: Option<Vec<_>> = filters.iter().map(to_native).collect();
+        let kept = native.is_some();
+        self.0.store(Arc::new(native.unwrap_or_default()));
+        kept
+    }
+}
@@ -0,0 +1,174 @@
+//! # Exception Fingerprinting and Grouping
+//!
+//! Computes a stable fingerprint for records carrying an exception: the exception's
+//! qualified type name plus the innermost frames normalized to `module:function`
+//! (line numbers are deliberately left out so a fingerprint survives unrelated edits).
+//! The fingerprint is stored in the record's extras as `exc_fingerprint`.
+//!
+//! With an aggregation window configured, only the first occurrence of a fingerprint
+//! inside the window keeps its full traceback; repeats carry a one-line
+//! "seen N times" summary in `exc_text` and the running count in `exc_seen_count`.
+
+use arc_swap::ArcSwap;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::core::LogRecord;
+
+/// Upper bound on tracked fingerprints before expired windows are pruned.
+const MAX_TRACKED: usize = 1024;
+
+#[derive(Debug, Clone)]
+struct GroupingOptions {
+    enabled: bool,
+    frames: usize,
+    window: Option<Duration>,
+}
+
+static GROUPING: Lazy<ArcSwap<GroupingOptions>> = Lazy::new(|| {
+    ArcSwap::from_pointee(GroupingOptions {
+        enabled: false,
+        frames: 5,
+        window: None,
+    })
+});
+
+/// fingerprint -> (window start, occurrences inside the window)
+static SEEN: Lazy<DashMap<String, (Instant, u64)>> = Lazy::new(DashMap::new);
+
+/// 64-bit FNV-1a; stable across processes and releases, unlike `DefaultHasher`.
+fn fnv1a(parts: &[String]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in parts {
+        for byte in part.bytes().chain(std::iter::once(0u8)) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+/// Module-like name for a code object's file: basename without the extension.
+fn module_of(filename: &str) -> &str {
+    let base = filename.rsplit(['/', '\\']).next().unwrap_or(filename);
+    base.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(base)
+}
+
+/// Compute the fingerprint for an exception value, using at most `frames` innermost
+/// frames of its traceback.
+fn compute(exc_value: &Bound<PyAny>, frames: usize) -> Option<(String, String)> {
+    let exc_type = exc_value.get_type();
+    let type_name = format!("{}.{}", exc_type.module().ok()?, exc_type.qualname().ok()?);
+
+    let mut locations: Vec<String> = Vec::new();
+    let mut tb = exc_value.getattr("__traceback__").ok()?;
+    while !tb.is_none() {
+        let code = tb.getattr("tb_frame").ok()?.getattr("f_code").ok()?;
+        let filename: String = code.getattr("co_filename").ok()?.extract().ok()?;
+        let func: String = code.getattr("co_name").ok()?.extract().ok()?;
+        locations.push(format!("{}:{func}", module_of(&filename)));
+        tb = tb.getattr("tb_next").ok()?;
+    }
+    let keep = locations.len().saturating_sub(frames);
+
+    let mut parts = vec![type_name.clone()];
+    parts.extend(locations.drain(keep..));
+    Some((format!("{:016x}", fnv1a(&parts)), type_name))
+}
+
+/// Count an occurrence of `fingerprint`; returns the number of occurrences inside the
+/// current window (1 = first, keep the full traceback).
+fn record_occurrence(fingerprint: &str, window: Duration) -> u64 {
+    let now = Instant::now();
+    if SEEN.len() >= MAX_TRACKED {
+        SEEN.retain(|_, (start, _)| now.duration_since(*start) < window);
+    }
+    let mut entry = SEEN.entry(fingerprint.to_string()).or_insert((now, 0));
+    let (start, count) = entry.value_mut();
+    if now.duration_since(*start) >= window {
+        *start = now;
+        *count = 0;
+    }
+    *count += 1;
+    *count
+}
+
+/// Fingerprint (and optionally aggregate) the exception attached to `record`.
+/// `exc_info` is the raw `(type, value, tb)` tuple extracted from the log call.
+pub fn apply(record: &mut LogRecord, exc_info: Option<&Bound<PyAny>>) {
+    let opts = GROUPING.load();
+    if !opts.enabled {
+        return;
+    }
+    let Some(exc_value) = exc_info
+        .and_then(|info| info.cast::<PyTuple>().ok())
+        .and_then(|tuple| tuple.get_item(1).ok())
+        .filter(|v| !v.is_none())
+    else {
+        return;
+    };
+    let Some((fingerprint, type_name)) = compute(&exc_value, opts.frames) else {
+        return;
+    };
+
+    let extra = record.extra.get_or_insert_with(Default::default);
+    extra.insert(
+        "exc_fingerprint".to_string(),
+        Value::String(fingerprint.clone()),
+    );
+
+    if let Some(window) = opts.window {
+        let count = record_occurrence(&fingerprint, window);
+        extra.insert("exc_seen_count".to_string(), Value::from(count));
+        if count > 1 {
+            record.exc_text = Some(format!(
+                "{type_name} [{fingerprint}] seen {count} times in the last {}s \
+                 (traceback suppressed)",
+                window.as_secs_f64()
+            ));
+        }
+    }
+}
+
+/// Configure exception fingerprinting and in-process grouping.
+///
+/// Args:
+///     enabled: Attach an `exc_fingerprint` extra to every record carrying exc_info.
+///     frames: Number of innermost frames that contribute to the fingerprint.
+///     window: Aggregation window in seconds. Within a window only the first
+///         occurrence of a fingerprint keeps its traceback; repeats log a
+///         "seen N times" summary instead. None disables aggregation.
+#[pyfunction]
+#[pyo3(signature = (enabled=true, frames=5, window=None))]
+pub fn configure_exception_grouping(
+    enabled: bool,
+    frames: usize,
+    window: Option<f64>,
+) -> PyResult<()> {
+    if frames == 0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "frames must be a positive integer",
+        ));
+    }
+    let window = match window {
+        Some(secs) if secs.is_finite() && secs > 0.0 => Some(Duration::from_secs_f64(secs)),
+        Some(_) => {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "window must be a positive number of seconds or None",
+            ))
+        }
+        None => None,
+    };
+    GROUPING.store(Arc::new(GroupingOptions {
+        enabled,
+        frames,
+        window,
+    }));
+    SEEN.clear();
+    Ok(())
+}
@@ -0,0 +1,117 @@
+//! # Fork Safety
+//!
+//! A child created by `os.fork()` inherits every handler's memory but only the thread
+//! that forked: the writer and sender threads behind StreamHandler, the network and
+//! pipe handlers, QueueListener and AggregatingHandler are gone, so records queued in
+//! the child would never be written and `flush()` would wait for a thread that does
+//! not exist. Prefork servers (gunicorn, uWSGI, multiprocessing's `fork` start
+//! method) hit this in every worker.
+//!
+//! Components that own such state register a [`ForkHook`]. The Python package installs
+//! `os.register_at_fork` callbacks that run them:
+//!
+//! - before the fork, in the parent: file buffers are flushed so the child does not
+//!   write the parent's bytes a second time;
+//! - after the fork, in the child: queued records are dropped (the parent still
+//!   delivers them), background threads are started again, and connections shared
+//!   with the parent are closed so the child opens its own.
+//!
+//! Hooks are held weakly, so a handler that is dropped unregisters with it.
+#![cfg_attr(not(all(feature = "file", feature = "network")), allow(dead_code))]
+
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+use std::sync::{Arc, Weak};
+
+type Action = Box<dyn Fn() + Send + Sync>;
+
+/// What a component does around `os.fork()`.
+#[derive(Default)]
+pub struct ForkHook {
+    before: Option<Action>,
+    in_child: Option<Action>,
+}
+
+static HOOKS: Lazy<parking_lot::Mutex<Vec<Weak<ForkHook>>>> =
+    Lazy::new(|| parking_lot::Mutex::new(Vec::new()));
+
+impl ForkHook {
+    /// Run `action` in the parent just before a fork.
+    pub fn with_before(mut self, action: impl Fn() + Send + Sync + 'static) -> Self {
+        self.before = Some(Box::new(action));
+        self
+    }
+
+    /// Run `action` in the child after a fork.
+    pub fn with_in_child(mut self, action: impl Fn() + Send + Sync + 'static) -> Self {
+        self.in_child = Some(Box::new(action));
+        self
+    }
+
+    /// Add the registry entry. The caller keeps the returned Arc for as long as the
+    /// hook should run.
+    pub fn register(self) -> Arc<ForkHook> {
+        let hook = Arc::new(self);
+        let mut hooks = HOOKS.lock();
+        hooks.retain(|h| h.strong_count() > 0);
+        hooks.push(Arc::downgrade(&hook));
+        hook
+    }
+}
+
+/// In a forked child, release `mutex` if it is held. Only the forking thread survives
+/// a fork, so a held lock belongs to a thread that no longer exists and would never be
+/// released otherwise.
+pub fn unlock_in_child<T>(mutex: &parking_lot::Mutex<T>) {
+    if mutex.is_locked() {
+        // SAFETY: called from an after-fork hook, while the child runs a single thread;
+        // the owner of the lock did not survive the fork.
+        unsafe { mutex.force_unlock() };
+    }
+}
+
+/// Register a hook that applies `reset` to the value behind `mutex` in a forked child,
+/// typically to drop a connection shared with the parent.
+pub fn reset_in_child<T: Send + 'static>(
+    mutex: &Arc<parking_lot::Mutex<T>>,
+    reset: impl Fn(&mut T) + Send + Sync + 'static,
+) -> Arc<ForkHook> {
+    let mutex = mutex.clone();
+    ForkHook::default()
+        .with_in_child(move || {
+            unlock_in_child(&mutex);
+            reset(&mut mutex.lock());
+        })
+        .register()
+}
+
+fn live_hooks() -> Vec<Arc<ForkHook>> {
+    HOOKS.lock().iter().filter_map(Weak::upgrade).collect()
+}
+
+/// Run the before-fork hooks. Registered with `os.register_at_fork(before=...)`.
+#[pyfunction]
+pub fn before_fork(py: Python) {
+    let hooks = live_hooks();
+    py.detach(|| {
+        for hook in &hooks {
+            if let Some(before) = &hook.before {
+                before();
+            }
+        }
+    });
+}
+
+/// Run the in-child hooks. Registered with `os.register_at_fork(after_in_child=...)`.
+#[pyfunction]
+pub fn after_fork_in_child(py: Python) {
+    unlock_in_child(&HOOKS);
+    let hooks = live_hooks();
+    py.detach(|| {
+        for hook in &hooks {
+            if let Some(in_child) = &hook.in_child {
+                in_child();
+            }
+        }
+    });
+}
@@ -8,23 +8,30 @@
 //!
 //! - **DefaultFormatter**: Simple formatter with basic log information
 //! - **PythonFormatter**: Python-compatible formatter supporting format strings
+//! - **ColumnFormatter**: PythonFormatter with fixed-width, truncated columns
 //!
 //! ## Python Compatibility
 //!
 //! The PythonFormatter supports Python logging format strings including:
 //! - Field substitution: `%(levelname)s`, `%(message)s`, etc.
 //! - Padding and alignment: `%(levelname)-8s`, `%(name)15s`
-//! - Date/time formatting with custom date formats
+//! - Date/time formatting with custom date formats, rendered like `time.strftime`
+//!   (see [`DateFormat`])
 //! - Numeric formatting: `%(msecs)03d`
+//! - `%(asctime)s` as wall-clock time, or as time since start / since the previous
+//!   record for dev consoles (see [`Timestamps`])
 //!
 //! ## Performance
 //!
 //! Formatters use regex for complex pattern matching and replacement,
 //! providing both flexibility and reasonable performance for log formatting.
 
-use chrono::TimeZone;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Datelike, Offset, TimeZone, Timelike};
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 thread_local! {
     /// Re-used scratch buffer for format() — keeps capacity across calls so
@@ -99,19 +106,25 @@ impl Formatter for DefaultFormatter {
             result.push('\n');
             result.push_str(exc_text);
         }
+        if let Some(ref stack_info) = record.stack_info {
+            result.push('\n');
+            result.push_str(stack_info);
+        }
         result
     }
 }
 
 /// A single parsed element of a format string. Built once at formatter construction so
 /// `format()` walks the plan instead of re-parsing the format string per record.
-enum Token {
+pub(crate) enum Token {
     Literal(String),
     Field {
         name: String,
         left_align: bool,
         zero_pad: bool,
         width: usize,
+        /// Longer values are cut to this many chars, ending in `…` (0 = no limit).
+        max_width: usize,
     },
 }
 
@@ -120,7 +133,7 @@ enum Token {
 /// (left align), `0` (zero pad) and width digits, an unconditionally-consumed trailing
 /// conversion char (`s`/`d`/`f`/…), and the fallbacks for a bare `%`, a `%(` with no
 /// closing `)`, and `%(name)` with no trailing conversion char.
-fn parse_plan(format_str: &str) -> Vec<Token> {
+pub(crate) fn parse_plan(format_str: &str) -> Vec<Token> {
     let mut plan: Vec<Token> = Vec::new();
     let mut literal = String::new();
 
@@ -185,6 +198,7 @@ fn parse_plan(format_str: &str) -> Vec<Token> {
                         left_align,
                         zero_pad,
                         width,
+                        max_width: 0,
                     });
                 } else {
                     // `%(` with no closing `)`: emit only `%` (the scanned chars are dropped,
@@ -206,6 +220,186 @@ fn parse_plan(format_str: &str) -> Vec<Token> {
     plan
 }
 
+/// Which placeholder syntax a format string uses, as `logging.Formatter(style=...)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatStyle {
+    /// `%(name)s`
+    Percent,
+    /// `{name}`, as `str.format`
+    Brace,
+    /// `$name` / `${name}`, as `string.Template`
+    Dollar,
+}
+
+impl FormatStyle {
+    /// Parse the Python-side name: "%", "{" or "$".
+    pub fn parse(style: &str) -> Option<Self> {
+        match style {
+            "%" => Some(Self::Percent),
+            "{" => Some(Self::Brace),
+            "$" => Some(Self::Dollar),
+            _ => None,
+        }
+    }
+}
+
+/// Record fields holding numbers, which `str.format` right-aligns by default.
+const NUMERIC_FIELDS: [&str; 10] = [
+    "levelno",
+    "lineno",
+    "thread",
+    "process",
+    "msecs",
+    "created",
+    "relativeCreated",
+    "ppid",
+    "thread_native_id",
+    "seq",
+];
+
+/// Parse a format string in `style` into a token plan. `%` never fails, as
+/// [`parse_plan`]. `{` fields take the part of the `str.format` spec a plan can render:
+/// an optional `<` or `>`, `0`, a width and an `s` or `d` type. Other specs, `!r`
+/// conversions, attribute or index lookups and broken placeholders are errors, so the
+/// caller can format in Python instead.
+pub(crate) fn parse_styled_plan(
+    format_str: &str,
+    style: FormatStyle,
+) -> Result<Vec<Token>, String> {
+    match style {
+        FormatStyle::Percent => Ok(parse_plan(format_str)),
+        FormatStyle::Brace => parse_brace_plan(format_str),
+        FormatStyle::Dollar => parse_dollar_plan(format_str),
+    }
+}
+
+fn parse_brace_plan(format_str: &str) -> Result<Vec<Token>, String> {
+    let mut plan = Vec::new();
+    let mut literal = String::new();
+    let mut chars = format_str.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '}' => return Err("single '}' encountered in format string".to_string()),
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some('{') => return Err(format!("nested field in {{{placeholder}...}}")),
+                        Some(ch) => placeholder.push(ch),
+                        None => return Err("expected '}' before end of string".to_string()),
+                    }
+                }
+                if !literal.is_empty() {
+                    plan.push(Token::Literal(std::mem::take(&mut literal)));
+                }
+                plan.push(brace_field(&placeholder)?);
+            }
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        plan.push(Token::Literal(literal));
+    }
+    Ok(plan)
+}
+
+/// The field of a `{name!s:<8}` placeholder, given what is between the braces.
+fn brace_field(placeholder: &str) -> Result<Token, String> {
+    let (name, spec) = placeholder.split_once(':').unwrap_or((placeholder, ""));
+    let (name, conversion) = name.split_once('!').unwrap_or((name, "s"));
+    if !is_identifier(name) {
+        return Err(format!("{{{placeholder}}} must name a record attribute"));
+    }
+    if conversion != "s" {
+        return Err(format!(
+            "conversion !{conversion} in {{{placeholder}}} is not supported"
+        ));
+    }
+    let (align, rest) = match spec.strip_prefix('<') {
+        Some(rest) => (Some(true), rest),
+        None => match spec.strip_prefix('>') {
+            Some(rest) => (Some(false), rest),
+            None => (None, spec),
+        },
+    };
+    let (zero_pad, rest) = match rest.strip_prefix('0') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let width = rest[..digits].parse().unwrap_or(0);
+    if !matches!(&rest[digits..], "" | "s" | "d") {
+        return Err(format!("format spec in {{{placeholder}}} is not supported"));
+    }
+    Ok(Token::Field {
+        name: name.to_string(),
+        left_align: align.unwrap_or(!zero_pad && !NUMERIC_FIELDS.contains(&name)),
+        zero_pad,
+        width,
+        max_width: 0,
+    })
+}
+
+fn parse_dollar_plan(format_str: &str) -> Result<Vec<Token>, String> {
+    let mut plan = Vec::new();
+    let mut literal = String::new();
+    let mut rest = format_str;
+    while let Some(at) = rest.find('$') {
+        literal.push_str(&rest[..at]);
+        let after = &rest[at + 1..];
+        let (name, next) = if let Some(after) = after.strip_prefix('$') {
+            literal.push('$');
+            rest = after;
+            continue;
+        } else if let Some(braced) = after.strip_prefix('{') {
+            let end = braced.find('}').unwrap_or(braced.len());
+            (&braced[..end], braced.get(end + 1..))
+        } else {
+            let end = after
+                .find(|c: char| !(c == '_' || c.is_ascii_alphanumeric()))
+                .unwrap_or(after.len());
+            (&after[..end], Some(&after[end..]))
+        };
+        let Some(next) = next.filter(|_| is_identifier(name)) else {
+            return Err(format!(
+                "invalid placeholder at character {}",
+                format_str.len() - rest.len() + at
+            ));
+        };
+        if !literal.is_empty() {
+            plan.push(Token::Literal(std::mem::take(&mut literal)));
+        }
+        plan.push(Token::Field {
+            name: name.to_string(),
+            left_align: false,
+            zero_pad: false,
+            width: 0,
+            max_width: 0,
+        });
+        rest = next;
+    }
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        plan.push(Token::Literal(literal));
+    }
+    Ok(plan)
+}
+
+/// An ASCII identifier, as record attribute names are.
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c == '_' || c.is_ascii_alphabetic())
+        && name.chars().all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
 /// Python-compatible formatter supporting Python logging format strings.
 ///
 /// This formatter provides full compatibility with Python's logging module
@@ -243,8 +437,56 @@ pub struct PythonFormatter {
     pub format_string: String,
     /// Optional custom date format (strftime format)
     pub date_format: Option<String>,
+    /// What %(asctime)s shows; `date_format` only applies to `Timestamps::Absolute`.
+    pub timestamps: Timestamps,
     /// Format string parsed once into a token plan (see `parse_plan`).
     plan: Vec<Token>,
+    /// `date_format` compiled once for chrono.
+    date_plan: Option<DateFormat>,
+    /// `created` of the previous record (f64 bits), for `Timestamps::Delta`.
+    last_created: AtomicU64,
+    /// Text for fields a record doesn't have, as `logging.Formatter(defaults=...)`.
+    defaults: HashMap<String, String>,
+}
+
+/// What `%(asctime)s` renders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timestamps {
+    /// Wall-clock time in `datefmt` (the default).
+    Absolute,
+    /// Time since logging started, from `relativeCreated`: `+12.345s`.
+    Relative,
+    /// Time since the previous record this formatter wrote, humanized: `+350ms`,
+    /// `+2.4s`, `+3m05s`, `+1h02m`. The first record counts from logging start.
+    Delta,
+}
+
+impl Timestamps {
+    /// Parse the Python-side name: "absolute", "relative" or "delta".
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "absolute" => Some(Self::Absolute),
+            "relative" => Some(Self::Relative),
+            "delta" => Some(Self::Delta),
+            _ => None,
+        }
+    }
+
+    /// Write `seconds` (clamped at 0) the way `Delta` shows it.
+    pub fn humanize(seconds: f64, out: &mut String) {
+        let seconds = seconds.max(0.0);
+        let _ = if seconds < 1.0 {
+            write!(out, "+{}ms", (seconds * 1000.0).round() as u64)
+        } else if seconds < 60.0 {
+            write!(out, "+{seconds:.1}s")
+        } else if seconds < 3600.0 {
+            let total = seconds.round() as u64;
+            write!(out, "+{}m{:02}s", total / 60, total % 60)
+        } else {
+            let total = (seconds / 60.0).round() as u64;
+            write!(out, "+{}h{:02}m", total / 60, total % 60)
+        };
+    }
 }
 
 impl PythonFormatter {
@@ -269,7 +511,11 @@ impl PythonFormatter {
         Self {
             format_string,
             date_format: None,
+            timestamps: Timestamps::Absolute,
             plan,
+            date_plan: None,
+            last_created: AtomicU64::new(crate::core::start_time().to_bits()),
+            defaults: HashMap::new(),
         }
     }
 
@@ -293,12 +539,37 @@ impl PythonFormatter {
     /// ```
     pub fn with_date_format(format_string: String, date_format: String) -> Self {
         let plan = parse_plan(&format_string);
+        let date_plan = Some(DateFormat::new(&date_format));
         Self {
             format_string,
             date_format: Some(date_format),
+            timestamps: Timestamps::Absolute,
             plan,
+            date_plan,
+            last_created: AtomicU64::new(crate::core::start_time().to_bits()),
+            defaults: HashMap::new(),
         }
     }
+
+    /// Render %(asctime)s as `timestamps` instead of wall-clock time.
+    pub fn with_timestamps(mut self, timestamps: Timestamps) -> Self {
+        self.timestamps = timestamps;
+        self
+    }
+
+    /// Render a field the record doesn't have as its text in `defaults`. The record's
+    /// own fields and extras take precedence.
+    pub fn with_defaults(mut self, defaults: HashMap<String, String>) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
+    /// Read the format string as `style` (`{name}` or `$name` placeholders) instead
+    /// of `%(name)s`. Errors when it has placeholders the formatter can't render.
+    pub fn with_style(mut self, style: FormatStyle) -> Result<Self, String> {
+        self.plan = parse_styled_plan(&self.format_string, style)?;
+        Ok(self)
+    }
 }
 
 /// Implementation of Formatter trait for PythonFormatter.
@@ -326,19 +597,32 @@ impl Formatter for PythonFormatter {
 }
 
 impl PythonFormatter {
+    /// %(asctime)s for `Timestamps::Relative` / `Timestamps::Delta`.
+    fn elapsed(&self, record: &crate::core::LogRecord) -> String {
+        let mut out = String::new();
+        if self.timestamps == Timestamps::Relative {
+            let _ = write!(out, "+{:.3}s", record.relative_created.max(0.0) / 1000.0);
+        } else {
+            let previous = self
+                .last_created
+                .swap(record.created.to_bits(), Ordering::Relaxed);
+            Timestamps::humanize(record.created - f64::from_bits(previous), &mut out);
+        }
+        out
+    }
+
     fn format_into(&self, record: &crate::core::LogRecord, result: &mut String) {
         if result.capacity() < self.format_string.len() + 128 {
             result.reserve(self.format_string.len() + 128 - result.capacity());
         }
 
-        let date_format = self.date_format.as_deref();
         // Per-call cache: dedupes repeated %(asctime)s within one format string. Not shared
         // across calls (a shared cache would reintroduce cross-thread contention on the §4
         // detached path, and a custom datefmt could carry sub-second fields).
         let mut asctime_cache: Option<String> = None;
 
         for token in &self.plan {
-            let (name, left_align, zero_pad, width) = match token {
+            let (name, left_align, zero_pad, width, max_width) = match token {
                 Token::Literal(s) => {
                     result.push_str(s);
                     continue;
@@ -348,7 +632,8 @@ impl PythonFormatter {
                     left_align,
                     zero_pad,
                     width,
-                } => (name.as_str(), *left_align, *zero_pad, *width),
+                    max_width,
+                } => (name.as_str(), *left_align, *zero_pad, *width, *max_width),
             };
 
             let mut int_buf = itoa::Buffer::new();
@@ -370,6 +655,9 @@ impl PythonFormatter {
                 "thread" => int_buf.format(record.thread),
                 "processName" => &record.process_name,
                 "process" => int_buf.format(record.process),
+                "ppid" => int_buf.format(record.ppid),
+                "thread_native_id" => int_buf.format(record.thread_native_id),
+                "seq" => int_buf.format(record.seq),
                 "message" => {
                     owned = record.get_message();
                     &owned
@@ -384,15 +672,12 @@ impl PythonFormatter {
                 }
                 "asctime" => {
                     let s = asctime_cache.get_or_insert_with(|| {
-                        if let Some(date_fmt) = date_format {
-                            let datetime = chrono::Local
-                                .timestamp_opt(
-                                    record.created as i64,
-                                    (record.msecs * 1_000_000.0) as u32,
-                                )
-                                .single()
-                                .unwrap_or_else(chrono::Local::now);
-                            datetime.format(date_fmt).to_string()
+                        if self.timestamps != Timestamps::Absolute {
+                            self.elapsed(record)
+                        } else if let Some(date_plan) = &self.date_plan {
+                            let mut out = String::new();
+                            date_plan.render(record.created, &mut out);
+                            out
                         } else {
                             let sec = record.created as i64;
                             ASCTIME_SECOND_CACHE.with(|cell| {
@@ -412,23 +697,30 @@ impl PythonFormatter {
                     s.as_str()
                 }
                 other => {
-                    owned = if let Some(ref extra_fields) = record.extra {
-                        if let Some(value) = extra_fields.get(other) {
-                            match value {
-                                serde_json::Value::String(s) => s.clone(),
-                                serde_json::Value::Null => "null".to_string(),
-                                other_val => other_val.to_string(),
-                            }
-                        } else {
-                            format!("%({other})")
-                        }
-                    } else {
-                        format!("%({other})")
+                    let extra = record.extra.as_ref().and_then(|extra| extra.get(other));
+                    owned = match (extra, self.defaults.get(other)) {
+                        (Some(serde_json::Value::String(s)), _) => s.clone(),
+                        (Some(serde_json::Value::Null), _) => "null".to_string(),
+                        (Some(value), _) => value.to_string(),
+                        (None, Some(default)) => default.clone(),
+                        (None, None) => format!("%({other})"),
                     };
                     &owned
                 }
             };
 
+            let truncated: String;
+            let val_str = if max_width > 0 && val_str.chars().count() > max_width {
+                let cut = val_str
+                    .char_indices()
+                    .nth(max_width - 1)
+                    .map_or(val_str.len(), |(idx, _)| idx);
+                truncated = format!("{}…", &val_str[..cut]);
+                &truncated
+            } else {
+                val_str
+            };
+
             if width == 0 {
                 result.push_str(val_str);
             } else if left_align {
@@ -444,6 +736,320 @@ impl PythonFormatter {
             result.push('\n');
             result.push_str(exc_text);
         }
+        if let Some(ref stack_info) = record.stack_info {
+            result.push('\n');
+            result.push_str(stack_info);
+        }
+    }
+}
+
+/// Names C `strftime` prints for `%a %A %b %B %p` in the current `LC_TIME` locale,
+/// and the locale's own `%c %x %X` layouts when the platform reports them.
+pub struct LocaleTime {
+    /// Weekday names, Monday first.
+    pub weekdays: [String; 7],
+    pub weekdays_abbr: [String; 7],
+    pub months: [String; 12],
+    pub months_abbr: [String; 12],
+    /// AM and PM.
+    pub am_pm: [String; 2],
+    pub date_time_format: Option<String>,
+    pub date_format: Option<String>,
+    pub time_format: Option<String>,
+}
+
+/// What `%Z` prints: `time.tzname` with the UTC offsets (seconds east) they apply at.
+pub struct ZoneNames {
+    pub standard: (String, i32),
+    pub daylight: Option<(String, i32)>,
+}
+
+enum DateItem {
+    /// Literal text and specifiers chrono renders like C `strftime`, as a chrono
+    /// format string.
+    Chrono(String),
+    /// `%f`: microseconds, zero-padded to 6 digits as in `datetime.strftime`.
+    Micros,
+    /// `%Z`
+    ZoneName,
+    /// `%a` / `%A`
+    Weekday { abbr: bool },
+    /// `%b` / `%h` / `%B`
+    Month { abbr: bool },
+    /// `%p`
+    AmPm,
+}
+
+/// A `datefmt` compiled once so `%(asctime)s` renders as `time.strftime` does.
+///
+/// chrono covers most of C `strftime`, with a few differences this smooths over:
+///
+/// - `%f` is microseconds (6 digits) as in `datetime.strftime`, not chrono's
+///   nanoseconds. chrono's own `%3f`, `%.6f` and friends still work.
+/// - `%Z` is the zone name from `time.tzname` ("UTC", "CET") instead of an offset.
+/// - `%a %A %b %B %p` and `%c %x %X` follow the `LC_TIME` locale in effect when the
+///   formatter is created, as `time.strftime` would; the C locale keeps chrono's
+///   English names.
+/// - Specifiers chrono doesn't know are written out unchanged, as glibc does,
+///   rather than failing the whole line.
+pub struct DateFormat {
+    items: Vec<DateItem>,
+    locale: Option<LocaleTime>,
+    zone: Option<ZoneNames>,
+}
+
+impl DateFormat {
+    /// Compile `datefmt`, reading the locale and zone names from Python when it uses
+    /// them.
+    pub fn new(datefmt: &str) -> Self {
+        let specs = specifiers(datefmt);
+        let wants_names = specs.iter().any(|spec| {
+            matches!(
+                *spec,
+                "%a" | "%A" | "%b" | "%h" | "%B" | "%p" | "%c" | "%x" | "%X"
+            )
+        });
+        // The locale's %c layout may carry a %Z of its own.
+        let wants_zone = specs.iter().any(|spec| matches!(*spec, "%Z" | "%c"));
+        let (locale, zone) = pyo3::Python::try_attach(|py| {
+            (
+                wants_names
+                    .then(|| crate::platform::locale_time(py))
+                    .flatten(),
+                wants_zone
+                    .then(|| crate::platform::zone_names(py))
+                    .flatten(),
+            )
+        })
+        .unwrap_or((None, None));
+        Self::with_names(datefmt, locale, zone)
+    }
+
+    /// Compile `datefmt` with the given names; `None` keeps chrono's.
+    pub fn with_names(datefmt: &str, locale: Option<LocaleTime>, zone: Option<ZoneNames>) -> Self {
+        let mut items = Vec::new();
+        compile_into(datefmt, locale.as_ref(), true, &mut items);
+        Self {
+            items,
+            locale,
+            zone,
+        }
+    }
+
+    /// Append `created` (seconds since the epoch) in local time.
+    pub fn render(&self, created: f64, out: &mut String) {
+        let mut secs = created.floor();
+        // Rounded to the microsecond like datetime.fromtimestamp, carrying into
+        // the second.
+        let mut micros = ((created - secs) * 1_000_000.0).round() as u32;
+        if micros >= 1_000_000 {
+            secs += 1.0;
+            micros -= 1_000_000;
+        }
+        let datetime = chrono::Local
+            .timestamp_opt(secs as i64, micros * 1_000)
+            .single()
+            .unwrap_or_else(chrono::Local::now);
+        for item in &self.items {
+            match item {
+                DateItem::Chrono(spec) => {
+                    let _ = write!(out, "{}", datetime.format(spec));
+                }
+                DateItem::Micros => {
+                    let _ = write!(out, "{:06}", datetime.nanosecond() / 1_000);
+                }
+                DateItem::ZoneName => {
+                    let offset = datetime.offset().fix().local_minus_utc();
+                    match self.zone_name(offset) {
+                        Some(name) => out.push_str(name),
+                        None if offset == 0 => out.push_str("UTC"),
+                        None => {
+                            let _ = write!(out, "{}", datetime.format("%:z"));
+                        }
+                    }
+                }
+                DateItem::Weekday { abbr } => {
+                    let names = self.locale.as_ref().expect("compiled with a locale");
+                    let day = datetime.weekday().num_days_from_monday() as usize;
+                    out.push_str(if *abbr {
+                        &names.weekdays_abbr[day]
+                    } else {
+                        &names.weekdays[day]
+                    });
+                }
+                DateItem::Month { abbr } => {
+                    let names = self.locale.as_ref().expect("compiled with a locale");
+                    let month = datetime.month0() as usize;
+                    out.push_str(if *abbr {
+                        &names.months_abbr[month]
+                    } else {
+                        &names.months[month]
+                    });
+                }
+                DateItem::AmPm => {
+                    let names = self.locale.as_ref().expect("compiled with a locale");
+                    out.push_str(&names.am_pm[usize::from(datetime.hour() >= 12)]);
+                }
+            }
+        }
+    }
+
+    fn zone_name(&self, offset: i32) -> Option<&str> {
+        let zone = self.zone.as_ref()?;
+        match &zone.daylight {
+            Some((name, daylight)) if *daylight == offset && zone.standard.1 != offset => {
+                Some(name)
+            }
+            _ => (zone.standard.1 == offset).then_some(zone.standard.0.as_str()),
+        }
+    }
+}
+
+/// Split `datefmt` into `%` specifiers, each with its flags and width (`%-d`, `%3f`,
+/// `%:z`), skipping the text between them.
+fn specifiers(datefmt: &str) -> Vec<&str> {
+    let mut specs = Vec::new();
+    let mut rest = datefmt;
+    while let Some(start) = rest.find('%') {
+        let after = &rest[start + 1..];
+        let flags = after
+            .find(|c: char| !matches!(c, '-' | '_' | '0'..='9' | '.' | ':' | '#'))
+            .unwrap_or(after.len());
+        let end = after[flags..]
+            .chars()
+            .next()
+            .map_or(after.len(), |c| flags + c.len_utf8());
+        specs.push(&rest[start..start + 1 + end]);
+        rest = &after[end..];
+    }
+    specs
+}
+
+fn push_chrono(items: &mut Vec<DateItem>, spec: &str) {
+    if spec.is_empty() {
+        return;
+    }
+    if let Some(DateItem::Chrono(last)) = items.last_mut() {
+        last.push_str(spec);
+    } else {
+        items.push(DateItem::Chrono(spec.to_string()));
+    }
+}
+
+fn compile_into(
+    datefmt: &str,
+    locale: Option<&LocaleTime>,
+    expand_layouts: bool,
+    items: &mut Vec<DateItem>,
+) {
+    let mut rest = datefmt;
+    for spec in specifiers(datefmt) {
+        let start = rest.find(spec).expect("specifiers come from datefmt");
+        push_chrono(items, &rest[..start]);
+        rest = &rest[start + spec.len()..];
+
+        let layout = |layout: Option<&String>| layout.filter(|_| expand_layouts).cloned();
+        match (spec, locale) {
+            ("%f", _) => items.push(DateItem::Micros),
+            ("%Z", _) => items.push(DateItem::ZoneName),
+            ("%%", _) => push_chrono(items, "%%"),
+            ("%a" | "%A", Some(_)) => items.push(DateItem::Weekday { abbr: spec == "%a" }),
+            ("%b" | "%h" | "%B", Some(_)) => items.push(DateItem::Month { abbr: spec != "%B" }),
+            ("%p", Some(_)) => items.push(DateItem::AmPm),
+            ("%c" | "%x" | "%X", Some(names)) => {
+                let format = match spec {
+                    "%c" => layout(names.date_time_format.as_ref()),
+                    "%x" => layout(names.date_format.as_ref()),
+                    _ => layout(names.time_format.as_ref()),
+                };
+                match format {
+                    Some(format) => compile_into(&format, locale, false, items),
+                    None => push_chrono(items, spec),
+                }
+            }
+            _ if StrftimeItems::new(spec).any(|item| matches!(item, Item::Error)) => {
+                push_chrono(items, &spec.replace('%', "%%"));
+            }
+            _ => push_chrono(items, spec),
+        }
+    }
+    push_chrono(items, rest);
+}
+
+/// Fixed-width console columns for interleaved output from many loggers.
+///
+/// Wraps a PythonFormatter and pins the given fields to a width: shorter values are
+/// left-aligned and padded, longer ones are cut and end in `…`, so every line's
+/// message starts at the same column.
+///
+/// # Examples
+///
+/// ```text
+/// // Format: "%(asctime)s %(levelname)s %(name)s %(message)s", levelname 8, name 12
+/// // Output: "2023-01-01 12:00:00 INFO     myapp.db     Connected"
+/// // Output: "2023-01-01 12:00:00 WARNING  myapp.paymen… Card declined"
+/// ```
+pub struct ColumnFormatter {
+    /// The format string the columns are applied to
+    pub format_string: String,
+    /// Optional custom date format (strftime format)
+    pub date_format: Option<String>,
+    inner: PythonFormatter,
+}
+
+impl ColumnFormatter {
+    /// Default layout: time, level, logger name, message.
+    pub const DEFAULT_FORMAT: &'static str = "%(asctime)s %(levelname)s %(name)s %(message)s";
+
+    /// Create a ColumnFormatter. `columns` maps field names to widths; every
+    /// `%(field)s` with a width there is padded and truncated to it, and a width of 0
+    /// leaves the field as the format string has it.
+    pub fn new(
+        format_string: String,
+        date_format: Option<String>,
+        columns: &[(&str, usize)],
+    ) -> Self {
+        let mut inner = match date_format.clone() {
+            Some(df) => PythonFormatter::with_date_format(format_string.clone(), df),
+            None => PythonFormatter::new(format_string.clone()),
+        };
+        for token in &mut inner.plan {
+            let Token::Field {
+                name,
+                left_align,
+                zero_pad,
+                width,
+                max_width,
+            } = token
+            else {
+                continue;
+            };
+            if let Some(&(_, column)) = columns.iter().find(|(field, w)| field == name && *w > 0) {
+                *left_align = true;
+                *zero_pad = false;
+                *width = column;
+                *max_width = column;
+            }
+        }
+        Self {
+            format_string,
+            date_format,
+            inner,
+        }
+    }
+}
+
+impl ColumnFormatter {
+    /// Render %(asctime)s as `timestamps` instead of wall-clock time.
+    pub fn with_timestamps(mut self, timestamps: Timestamps) -> Self {
+        self.inner = self.inner.with_timestamps(timestamps);
+        self
+    }
+}
+
+impl Formatter for ColumnFormatter {
+    fn format(&self, record: &crate::core::LogRecord) -> String {
+        self.inner.format(record)
     }
 }
 
@@ -497,6 +1103,10 @@ pub mod ansi_colors {
 /// | WARNING  | Yellow  | \x1b[33m |
 /// | ERROR    | Red     | \x1b[31m |
 /// | CRITICAL | Magenta | \x1b[35m |
+///
+/// Optional level badges (see `with_level_badges`) prefix each line with a marker
+/// such as `✅` or `❌` for the record's level.
+#[cfg(feature = "colors")]
 pub struct ColorFormatter {
     /// The underlying format string with %(field)s placeholders
     pub format_string: String,
@@ -504,8 +1114,11 @@ pub struct ColorFormatter {
     pub date_format: Option<String>,
     /// Pre-built inner formatter (with its parsed plan) reused across format() calls.
     inner: PythonFormatter,
+    /// (levelname, badge) pairs; a record whose level has a badge starts with it.
+    badges: Vec<(String, String)>,
 }
 
+#[cfg(feature = "colors")]
 impl ColorFormatter {
     /// Create a new ColorFormatter with the specified format string.
     ///
@@ -529,6 +1142,7 @@ impl ColorFormatter {
             inner: PythonFormatter::new(format_string.clone()),
             format_string,
             date_format: None,
+            badges: Vec::new(),
         }
     }
 
@@ -543,16 +1157,382 @@ impl ColorFormatter {
             inner: PythonFormatter::with_date_format(format_string.clone(), date_format.clone()),
             format_string,
             date_format: Some(date_format),
+            badges: Vec::new(),
         }
     }
+
+    /// Default badges for the standard levels.
+    pub const DEFAULT_LEVEL_BADGES: &'static [(&'static str, &'static str)] = &[
+        ("DEBUG", "🔍"),
+        ("INFO", "✅"),
+        ("WARNING", "⚠️"),
+        ("ERROR", "❌"),
+        ("CRITICAL", "🔥"),
+    ];
+
+    /// Prefix each line with the badge for its level name, followed by a space.
+    /// Levels without a badge are left unprefixed.
+    pub fn with_level_badges(mut self, badges: Vec<(String, String)>) -> Self {
+        self.badges = badges;
+        self
+    }
+
+    /// The active (levelname, badge) pairs.
+    pub fn level_badges(&self) -> &[(String, String)] {
+        &self.badges
+    }
+
+    /// Render %(asctime)s as `timestamps` instead of wall-clock time.
+    pub fn with_timestamps(mut self, timestamps: Timestamps) -> Self {
+        self.inner = self.inner.with_timestamps(timestamps);
+        self
+    }
 }
 
+#[cfg(feature = "colors")]
 impl Formatter for ColorFormatter {
     /// Format a log record with ANSI color support.
     ///
     /// Delegates to the pre-built inner PythonFormatter, whose token plan handles the
-    /// %(ansi_level_color)s / %(ansi_reset_color)s fields alongside the standard ones.
+    /// %(ansi_level_color)s / %(ansi_reset_color)s fields alongside the standard ones,
+    /// then prepends the level badge, if any.
     fn format(&self, record: &crate::core::LogRecord) -> String {
-        self.inner.format(record)
+        let line = self.inner.format(record);
+        match self
+            .badges
+            .iter()
+            .find(|(level, _)| *level == record.levelname)
+        {
+            Some((_, badge)) => format!("{badge} {line}"),
+            None => line,
+        }
+    }
+}
+
+/// One JSON object per record, for log shippers and ingestion pipelines.
+///
+/// Writes the standard LogRecord attributes under their Python names (`message` is
+/// the merged message, `exc_info` the traceback text), then each `extra` field with
+/// the JSON type it was logged with. `fields` picks and orders the keys, standard
+/// and extra alike; `rename` changes the key a field is written under.
+///
+/// # Examples
+///
+/// ```text
+/// // fields: created, levelname, message, user_id; rename: levelname -> level
+/// // Output: {"created":1720000000.12,"level":"INFO","message":"login","user_id":42}
+/// ```
+///
+/// In canonical mode (see [`JsonFormatter::canonical`]) the output is the same for
+/// the same events on every run, for golden-file tests and diffing logs. With a
+/// schema version (see [`JsonFormatter::schema_version`]) every object starts with
+/// `"schema_version"`, so consumers can tell which layout a line was written in.
+#[cfg(feature = "json")]
+pub struct JsonFormatter {
+    /// Keys written, in order, before any extras.
+    fields: Vec<String>,
+    /// No fields were chosen, so every extra follows them.
+    all_extras: bool,
+    rename: std::collections::HashMap<String, String>,
+    /// Renders `asctime`, when it is written.
+    date_format: DateFormat,
+    /// Sorted keys, fixed float formatting and timestamps pinned to the epoch.
+    canonical: bool,
+    /// Written first as `schema_version`, in place of any extra of that name.
+    schema_version: Option<u32>,
+    /// Hash of the settings other than `canonical`; with it, identifies the output
+    /// for the record's JSON cache, so formatters with the same settings share
+    /// encodings.
+    layout: u64,
+}
+
+#[cfg(feature = "json")]
+impl JsonFormatter {
+    /// Fields written when none are chosen, before the extras. `asctime` leads them
+    /// when there's a date format.
+    pub const DEFAULT_FIELDS: &'static [&'static str] = &[
+        "name",
+        "levelname",
+        "levelno",
+        "pathname",
+        "filename",
+        "module",
+        "lineno",
+        "funcName",
+        "created",
+        "msecs",
+        "relativeCreated",
+        "thread",
+        "thread_native_id",
+        "threadName",
+        "processName",
+        "process",
+        "ppid",
+        "seq",
+        "taskName",
+        "message",
+        "exc_info",
+        "stack_info",
+    ];
+
+    /// Create a JsonFormatter. `fields` of `None` writes every standard field and
+    /// then the extras, sorted by key; `date_format` renders `asctime`.
+    pub fn new(
+        fields: Option<Vec<String>>,
+        rename: std::collections::HashMap<String, String>,
+        date_format: Option<String>,
+    ) -> Self {
+        let all_extras = fields.is_none();
+        let fields = fields.unwrap_or_else(|| {
+            date_format
+                .is_some()
+                .then_some("asctime")
+                .into_iter()
+                .chain(Self::DEFAULT_FIELDS.iter().copied())
+                .map(str::to_string)
+                .collect()
+        });
+        let date_format = date_format.as_deref().unwrap_or("%Y-%m-%d %H:%M:%S");
+        let layout = Self::layout_of(&fields, all_extras, &rename, date_format);
+        Self {
+            fields,
+            all_extras,
+            rename,
+            date_format: DateFormat::new(date_format),
+            canonical: false,
+            schema_version: None,
+            layout,
+        }
+    }
+
+    fn layout_of(
+        fields: &[String],
+        all_extras: bool,
+        rename: &std::collections::HashMap<String, String>,
+        date_format: &str,
+    ) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut renames: Vec<_> = rename.iter().collect();
+        renames.sort();
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        (fields, all_extras, renames, date_format).hash(&mut hasher);
+        // Never 0, which is LogRecord::RECORD_JSON.
+        hasher.finish() | 1
+    }
+
+    /// Cache key of this formatter's output: the layout, with the top bit for
+    /// canonical mode.
+    fn schema(&self) -> u64 {
+        self.layout ^ (u64::from(self.canonical) << 63)
+    }
+
+    /// `asctime` in canonical mode, whatever the date format.
+    pub const CANONICAL_ASCTIME: &'static str = "1970-01-01 00:00:00";
+
+    /// Write canonical JSON: keys sorted at every level (after renaming), floats
+    /// with six decimals, `created`, `msecs`, `relativeCreated` and `asctime` pinned
+    /// to the epoch, and `seq` to 0.
+    pub fn canonical(mut self, canonical: bool) -> Self {
+        self.canonical = canonical;
+        self
+    }
+
+    pub fn is_canonical(&self) -> bool {
+        self.canonical
+    }
+
+    /// Start every object with `"schema_version": version`; `None` leaves it out.
+    pub fn schema_version(mut self, version: Option<u32>) -> Self {
+        use std::hash::{Hash, Hasher};
+        if version != self.schema_version {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            (self.layout, version).hash(&mut hasher);
+            self.layout = hasher.finish() | 1;
+        }
+        self.schema_version = version;
+        self
+    }
+
+    pub fn version(&self) -> Option<u32> {
+        self.schema_version
+    }
+
+    /// The fields this formatter writes, before any extras.
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    fn is_standard(field: &str) -> bool {
+        field == "asctime" || Self::DEFAULT_FIELDS.contains(&field)
+    }
+
+    fn standard_field(
+        &self,
+        record: &crate::core::LogRecord,
+        field: &str,
+    ) -> Option<serde_json::Value> {
+        use serde_json::Value;
+        let text = |s: Option<&String>| s.map_or(Value::Null, |s| Value::from(s.as_str()));
+        if self.canonical {
+            match field {
+                "created" | "msecs" | "relativeCreated" => return Some(0.0.into()),
+                "seq" => return Some(0.into()),
+                "asctime" => return Some(Self::CANONICAL_ASCTIME.into()),
+                _ => {}
+            }
+        }
+        Some(match field {
+            "name" => record.name.as_str().into(),
+            "levelname" => record.levelname.as_str().into(),
+            "levelno" => record.levelno.into(),
+            "pathname" => record.pathname.as_str().into(),
+            "filename" => record.filename.as_str().into(),
+            "module" => record.module.as_str().into(),
+            "lineno" => record.lineno.into(),
+            "funcName" => record.func_name.as_str().into(),
+            "created" => record.created.into(),
+            "msecs" => record.msecs.into(),
+            "relativeCreated" => record.relative_created.into(),
+            "thread" => record.thread.into(),
+            "threadName" => record.thread_name.as_str().into(),
+            "processName" => record.process_name.as_str().into(),
+            "process" => record.process.into(),
+            "ppid" => record.ppid.into(),
+            "thread_native_id" => record.thread_native_id.into(),
+            "seq" => record.seq.into(),
+            "taskName" => text(record.task_name.as_ref()),
+            "message" => record.get_message().into(),
+            "exc_info" => record
+                .exc_text
+                .as_ref()
+                .or(record.exc_info.as_ref())
+                .map_or(Value::Null, |tb| tb.trim_end_matches('\n').into()),
+            "stack_info" => text(record.stack_info.as_ref()),
+            "asctime" => {
+                let mut asctime = String::new();
+                self.date_format.render(record.created, &mut asctime);
+                asctime.into()
+            }
+            _ => return None,
+        })
+    }
+}
+
+#[cfg(feature = "json")]
+impl Formatter for JsonFormatter {
+    fn format(&self, record: &crate::core::LogRecord) -> String {
+        record
+            .json_cache
+            .get_or_encode(self.schema(), || self.encode(record))
+            .to_string()
+    }
+}
+
+#[cfg(feature = "json")]
+impl JsonFormatter {
+    /// The keys and values of `record` as this formatter writes them, in order.
+    pub(crate) fn entries<'a>(
+        &'a self,
+        record: &'a crate::core::LogRecord,
+    ) -> Vec<(&'a str, serde_json::Value)> {
+        let mut entries: Vec<(&str, serde_json::Value)> = Vec::with_capacity(self.fields.len() + 1);
+        if let Some(version) = self.schema_version {
+            entries.push(("schema_version", version.into()));
+        }
+        for field in &self.fields {
+            if self.schema_version.is_some() && field == "schema_version" {
+                continue;
+            }
+            let value = self.standard_field(record, field).or_else(|| {
+                record
+                    .extra
+                    .as_ref()
+                    .and_then(|extra| extra.get(field).cloned())
+            });
+            if let Some(value) = value {
+                entries.push((field, value));
+            }
+        }
+        if self.all_extras {
+            if let Some(extra) = &record.extra {
+                let mut extras: Vec<_> = extra
+                    .iter()
+                    .filter(|(key, _)| {
+                        !Self::is_standard(key)
+                            && (self.schema_version.is_none() || *key != "schema_version")
+                    })
+                    .collect();
+                extras.sort_by(|a, b| a.0.cmp(b.0));
+                entries.extend(extras.into_iter().map(|(k, v)| (k.as_str(), v.clone())));
+            }
+        }
+
+        for entry in entries.iter_mut() {
+            entry.0 = self.rename.get(entry.0).map_or(entry.0, String::as_str);
+        }
+        if self.canonical {
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+        }
+        entries
+    }
+
+    fn encode(&self, record: &crate::core::LogRecord) -> String {
+        let entries = self.entries(record);
+        let mut out = Vec::with_capacity(256);
+        out.push(b'{');
+        for (i, (key, value)) in entries.iter().enumerate() {
+            if i > 0 {
+                out.push(b',');
+            }
+            let _ = serde_json::to_writer(&mut out, key);
+            out.push(b':');
+            if self.canonical {
+                write_canonical(value, &mut out);
+            } else {
+                let _ = serde_json::to_writer(&mut out, value);
+            }
+        }
+        out.push(b'}');
+        String::from_utf8(out).unwrap_or_default()
+    }
+}
+
+/// Write `value` as JSON with object keys sorted and floats as `{:.6}`. Integers
+/// stay integers.
+#[cfg(feature = "json")]
+fn write_canonical(value: &serde_json::Value, out: &mut Vec<u8>) {
+    use serde_json::Value;
+    match value {
+        Value::Number(n) if n.is_f64() => {
+            let _ =
+                std::io::Write::write_fmt(out, format_args!("{:.6}", n.as_f64().unwrap_or(0.0)));
+        }
+        Value::Array(items) => {
+            out.push(b'[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(b']');
+        }
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push(b'{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                let _ = serde_json::to_writer(&mut *out, key);
+                out.push(b':');
+                write_canonical(&map[key], out);
+            }
+            out.push(b'}');
+        }
+        _ => {
+            let _ = serde_json::to_writer(out, value);
+        }
     }
 }
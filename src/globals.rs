@@ -8,18 +8,32 @@ use pyo3::prelude::*;
 use pyo3::types::{PyAny, PyDict};
 use std::cell::RefCell;
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use once_cell::sync::Lazy;
 
 use crate::core::{get_logger as core_get_logger, get_root_logger, LogLevel};
 use crate::fast_logger;
 use crate::formatter::PythonFormatter;
-use crate::handler::{FileHandler, HTTPHandler, Handler, OverflowStrategy, RotatingFileHandler};
+use crate::handler::Handler;
+use crate::handler::ListenerSink;
+#[cfg(feature = "file")]
+use crate::handler::{FileHandler, RotatingFileHandler};
+#[cfg(feature = "network")]
+use crate::handler::{HTTPHandler, OverflowStrategy};
+#[cfg(feature = "otlp")]
+use crate::py_handlers::PyOTLPHandler;
+#[cfg(feature = "pipe")]
+use crate::py_handlers::PyPipeHandler;
+#[cfg(feature = "smtp")]
+use crate::py_handlers::PySMTPHandler;
+use crate::py_handlers::{PyAggregatingHandler, PyMemoryHandler, PyQueueHandler, PyStreamHandler};
+#[cfg(feature = "network")]
+use crate::py_handlers::{PyDatagramHandler, PyHTTPHandler, PySocketHandler, PySyslogHandler};
+#[cfg(feature = "file")]
 use crate::py_handlers::{
-    PyFileHandler, PyHTTPHandler, PyMemoryHandler, PyOTLPHandler, PyRotatingFileHandler,
-    PyStreamHandler,
+    PyFileHandler, PyLevelRoutedFileHandler, PyRotatingFileHandler, PyTimedRotatingFileHandler,
 };
 use crate::py_logger::PyLogger;
 
@@ -35,6 +49,12 @@ pub static GLOBAL_PY_HANDLERS: Lazy<Mutex<Vec<PyEntry>>> = Lazy::new(|| Mutex::n
 pub static GLOBAL_LIFECYCLE: Lazy<Mutex<Vec<Arc<dyn Handler + Send + Sync>>>> =
     Lazy::new(|| Mutex::new(Vec::new()));
 
+/// The Python object each attached rust-backed handler was added as, keyed by arc
+/// identity. Dispatch never reads it: root keeps bare arcs in HANDLERS and direct pyclass
+/// entries carry no wrapper, so this is what lets `Logger.describe()` name them.
+pub static HANDLER_OBJECTS: Lazy<Mutex<HashMap<HandlerId, Py<PyAny>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
 /// Number of currently-attached handlers that require caller-frame introspection.
 /// Lets removeHandler recompute CALLER_INFO_REQUIRED back to false.
 pub static CALLER_INFO_COUNT: AtomicUsize = AtomicUsize::new(0);
@@ -62,9 +82,75 @@ pub fn arc_id(arc: &Arc<dyn Handler + Send + Sync>) -> HandlerId {
     Arc::as_ptr(arc) as *const () as usize
 }
 
+/// ID of the interpreter that initialized the extension. Every registry above, the
+/// worker threads and the cached Python objects belong to it, so the extension is
+/// single-interpreter: a subinterpreter that reaches it (via the module copy CPython
+/// hands out for single-phase-init extensions) is rejected by `check_interpreter`.
+static OWNER_INTERPRETER: once_cell::sync::OnceCell<i64> = once_cell::sync::OnceCell::new();
+
+fn current_interpreter_id() -> i64 {
+    // SAFETY: called with the GIL held (from module init / a pyfunction), so the
+    // current thread has an attached thread state and interpreter.
+    unsafe { pyo3::ffi::PyInterpreterState_GetID(pyo3::ffi::PyInterpreterState_Get()) }
+}
+
+/// Record the initializing interpreter. Called once from module init.
+pub fn claim_interpreter(_py: Python) {
+    let _ = OWNER_INTERPRETER.set(current_interpreter_id());
+}
+
+/// Raise ImportError unless called from the interpreter that owns the extension.
+#[pyfunction]
+pub fn check_interpreter(_py: Python) -> PyResult<()> {
+    let current = current_interpreter_id();
+    match OWNER_INTERPRETER.get() {
+        Some(&owner) if owner != current => Err(pyo3::exceptions::PyImportError::new_err(format!(
+            "logxide supports a single interpreter per process: it was initialized by \
+                 interpreter {owner} and cannot be used from interpreter {current}. Its \
+                 handler registry and worker threads are process-global; use logxide only \
+                 from the interpreter that imported it first."
+        ))),
+        _ => Ok(()),
+    }
+}
+
 /// Global flag indicating if caller frame introspection is required by any formatter/handler
 pub static CALLER_INFO_REQUIRED: AtomicBool = AtomicBool::new(false);
 
+/// `configure_caller_info()` override of CALLER_INFO_REQUIRED: automatic, always or never.
+static CALLER_INFO_MODE: AtomicU8 = AtomicU8::new(CALLER_INFO_AUTO);
+const CALLER_INFO_AUTO: u8 = 0;
+const CALLER_INFO_ALWAYS: u8 = 1;
+const CALLER_INFO_NEVER: u8 = 2;
+
+/// Whether log calls look up their caller frame: CALLER_INFO_REQUIRED unless
+/// `configure_caller_info()` forced it on or off.
+pub fn caller_info_wanted() -> bool {
+    match CALLER_INFO_MODE.load(Ordering::Relaxed) {
+        CALLER_INFO_ALWAYS => true,
+        CALLER_INFO_NEVER => false,
+        _ => CALLER_INFO_REQUIRED.load(Ordering::Relaxed),
+    }
+}
+
+/// Choose when records get caller info (`pathname`, `filename`, `module`, `lineno`,
+/// `funcName`).
+///
+/// Looking up the caller's frame costs every log call, so by default (`None`) it is
+/// done only while a handler's format string uses one of those fields. `True` collects
+/// it for every record, for handlers or filters that read the fields without naming
+/// them in a format; `False` never does, leaving them empty.
+#[pyfunction]
+#[pyo3(signature = (enabled=None))]
+pub fn configure_caller_info(enabled: Option<bool>) {
+    let mode = match enabled {
+        None => CALLER_INFO_AUTO,
+        Some(true) => CALLER_INFO_ALWAYS,
+        Some(false) => CALLER_INFO_NEVER,
+    };
+    CALLER_INFO_MODE.store(mode, Ordering::Relaxed);
+}
+
 /// Check if a format string contains caller-related placeholders and activate introspection if so
 pub fn check_caller_info_needed(format_str: &str) {
     if format_string_needs_caller(format_str) {
@@ -72,14 +158,23 @@ pub fn check_caller_info_needed(format_str: &str) {
     }
 }
 
-/// Whether a format string references caller-frame fields.
+/// Whether a format string references caller-frame fields, as `%(lineno)d`,
+/// `{lineno}` or `$lineno` / `${lineno}`.
 pub fn format_string_needs_caller(format_str: &str) -> bool {
-    format_str.contains("%(pathname)")
-        || format_str.contains("%(filename)")
-        || format_str.contains("%(module)")
-        || format_str.contains("%(lineno)")
-        || format_str.contains("%(funcName)")
-        || format_str.contains("%(func_name)")
+    const CALLER_FIELDS: [&str; 6] = [
+        "pathname",
+        "filename",
+        "module",
+        "lineno",
+        "funcName",
+        "func_name",
+    ];
+    CALLER_FIELDS.iter().any(|field| {
+        format_str.match_indices(field).any(|(at, _)| {
+            let before = &format_str[..at];
+            before.ends_with("%(") || before.ends_with('{') || before.ends_with('$')
+        })
+    })
 }
 
 /// Expose caller-info activation to Python compatibility layer
@@ -120,18 +215,19 @@ thread_local! {
     pub static THREAD_NAME: RefCell<Option<String>> = const { RefCell::new(None) };
 }
 
+/// The logger called `name` (root for None), created on first use. Every call for a
+/// name returns the same object, whether through `getLogger`, `getChild` or `.root`.
 #[pyfunction(name = "getLogger")]
 #[pyo3(signature = (name=None, manager=None))]
 pub fn get_logger(
     py: Python,
     name: Option<&str>,
     manager: Option<Py<PyAny>>,
-) -> PyResult<PyLogger> {
+) -> PyResult<Py<PyLogger>> {
     let logger_name = name.unwrap_or("root");
 
-    let mut alive = PY_LOGGER_KEEP_ALIVE.lock().unwrap();
-    if let Some(p) = alive.get(logger_name) {
-        return Ok(p.bind(py).borrow().clone());
+    if let Some(p) = PY_LOGGER_KEEP_ALIVE.lock().unwrap().get(logger_name) {
+        return Ok(p.clone_ref(py));
     }
 
     let inner = if name.is_some() {
@@ -145,16 +241,89 @@ pub fn get_logger(
         manager.map(|m| m.clone_ref(py)),
     );
 
-    let p = Py::new(py, pylogger)?;
-    alive.insert(logger_name.to_string(), p.clone_ref(py));
+    // Allocating can run Python code (and switch threads), so the registry lock is not
+    // held across it: dispatch takes that lock with the GIL held to walk ancestors.
+    let created = Py::new(py, pylogger)?;
+    let p = PY_LOGGER_KEEP_ALIVE
+        .lock()
+        .unwrap()
+        .entry(logger_name.to_string())
+        .or_insert(created)
+        .clone_ref(py);
+
+    Ok(p)
+}
+
+/// Module-level state of the Python package (`basicConfig` having run, the diagnostics
+/// reporter, the `atexit` registrations). It lives in the extension, which the
+/// interpreter loads once, so a re-imported package (pytest-xdist, hot reloaders) finds
+/// the state of the previous import instead of starting over next to it.
+static PROCESS_STATE: OnceLock<Py<PyDict>> = OnceLock::new();
 
-    Ok(p.bind(py).borrow().clone())
+/// Bumped by `reinitialize()`. Background threads started from Python remember the
+/// generation they were started in and exit once it has moved on.
+static STATE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// The process-wide dict the Python package keeps its module state in.
+#[pyfunction]
+pub fn process_state(py: Python) -> Py<PyDict> {
+    PROCESS_STATE
+        .get_or_init(|| PyDict::new(py).unbind())
+        .clone_ref(py)
 }
 
+/// Current state generation; see `reinitialize`.
 #[pyfunction]
-#[pyo3(signature = (**_kwargs))]
-pub fn basicConfig(_py: Python, _kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<()> {
-    Ok(())
+pub fn generation() -> u64 {
+    STATE_GENERATION.load(Ordering::Acquire)
+}
+
+/// Tear down the process-wide logging state: stop config watchers, shut down every
+/// handler, remove namespace routes, lift `disable()`, and return each logger to its freshly created
+/// state (root at WARNING).
+/// Loggers stay registered, so references held by application code keep working.
+/// Returns the new generation.
+#[pyfunction]
+pub fn reinitialize(py: Python) -> PyResult<u64> {
+    #[cfg(feature = "watch")]
+    crate::watch::stop_config_watchers(py);
+    get_logger(py, None, None)?;
+    let loggers: Vec<Py<PyLogger>> = PY_LOGGER_KEEP_ALIVE
+        .lock()
+        .unwrap()
+        .values()
+        .map(|logger| logger.clone_ref(py))
+        .collect();
+    for logger in loggers {
+        logger.bind(py).borrow().reset(py);
+    }
+    crate::routing::clear_all(py);
+    fast_logger::set_disable_level(0);
+    fast_logger::propagate_all_effective_levels();
+    CALLER_INFO_COUNT.store(0, Ordering::Relaxed);
+    CALLER_INFO_REQUIRED.store(false, Ordering::Relaxed);
+    CALLER_INFO_MODE.store(CALLER_INFO_AUTO, Ordering::Relaxed);
+    Ok(STATE_GENERATION.fetch_add(1, Ordering::AcqRel) + 1)
+}
+
+/// Drop records at or below `level` on every logger, whatever its own level, as
+/// `logging.disable()` does; `disable(NOTSET)` turns this off.
+#[pyfunction]
+#[pyo3(signature = (level=LogLevel::Critical.value()))]
+pub fn disable(level: u32) {
+    fast_logger::set_disable_level(level);
+}
+
+/// Write each record at most once to stdout and to stderr.
+///
+/// With a StreamHandler on a logger and another on root, both on stderr, every
+/// propagated record shows up twice. When enabled, the first StreamHandler along the
+/// propagation path to accept a record writes it, and the others skip that console.
+/// Applies to records dispatched by logxide loggers; off by default, as in the stdlib.
+#[pyfunction]
+#[pyo3(signature = (enabled=true))]
+pub fn configure_console_dedup(enabled: bool) {
+    crate::handler::set_console_dedup(enabled);
 }
 
 #[pyfunction]
@@ -186,6 +355,7 @@ pub fn set_thread_name(_py: Python, name: String) -> PyResult<()> {
     Ok(())
 }
 
+#[cfg(feature = "network")]
 #[pyfunction]
 #[pyo3(signature = (url, headers=None, capacity=None, batch_size=None, flush_interval=None, level=None))]
 pub fn register_http_handler(
@@ -197,6 +367,7 @@ pub fn register_http_handler(
     flush_interval: Option<u64>,
     level: Option<u32>,
 ) -> PyResult<()> {
+    crate::platform::require_network("HTTPHandler")?;
     let h = Arc::new(HTTPHandler::new(
         url,
         headers.unwrap_or_default(),
@@ -220,16 +391,18 @@ pub fn clear_handlers(py: Python) -> PyResult<()> {
             arc.shutdown();
         }
     });
+    forget_handler_objects(&arcs);
     HANDLERS.store(Arc::new(Vec::new()));
     GLOBAL_PY_HANDLERS.lock().unwrap().clear();
     Ok(())
 }
 
+#[cfg(feature = "file")]
 #[pyfunction(name = "register_file_handler")]
 #[pyo3(signature = (filename, level=None, format=None, datefmt=None))]
 pub fn register_file_handler(
     _py: Python,
-    filename: String,
+    filename: crate::platform::FsPath,
     level: Option<u32>,
     format: Option<String>,
     datefmt: Option<String>,
@@ -238,7 +411,7 @@ pub fn register_file_handler(
 
     let log_level = LogLevel::from_usize(level.unwrap_or(10) as usize);
 
-    let handler = FileHandler::new(filename)
+    let handler = FileHandler::new(filename.0)
         .map_err(|e| PyValueError::new_err(format!("Failed to create file handler: {e}")))?;
 
     handler.set_level(log_level);
@@ -257,11 +430,12 @@ pub fn register_file_handler(
     Ok(())
 }
 
+#[cfg(feature = "file")]
 #[pyfunction(name = "register_rotating_file_handler")]
 #[pyo3(signature = (filename, max_bytes=None, backup_count=None, level=None))]
 pub fn register_rotating_file_handler(
     _py: Python,
-    filename: String,
+    filename: crate::platform::FsPath,
     max_bytes: Option<u64>,
     backup_count: Option<u32>,
     level: Option<u32>,
@@ -269,7 +443,7 @@ pub fn register_rotating_file_handler(
     let log_level = LogLevel::from_usize(level.unwrap_or(10) as usize);
 
     let handler = RotatingFileHandler::new(
-        filename,
+        filename.0,
         max_bytes.unwrap_or(10 * 1024 * 1024),
         backup_count.unwrap_or(5),
     )
@@ -344,26 +518,88 @@ pub fn register_stream_handler(
     Ok(())
 }
 
-/// Extract the Rust `Arc<dyn Handler>` from a handler pyclass (HTTP/OTLP/Memory/File/
-/// Stream/Rotating). Used on both the object itself (DIRECT pyclass) and its `_inner`
+/// Extract the Rust `Arc<dyn Handler>` from a handler pyclass (HTTP/OTLP/Datagram/Socket/SMTP/Pipe/
+/// Memory/File/Stream/Queue/Rotating). Used on both the object itself (DIRECT pyclass) and its `_inner`
 /// (public wrapper). All text-sink kinds route through rust_dispatch; the per-record
 /// Native/Python decision lives on the arc's dispatch_mode flag.
 fn extract_rust_arc(obj: &Bound<PyAny>) -> Option<Arc<dyn Handler + Send + Sync>> {
+    #[cfg(feature = "network")]
     if let Ok(h) = obj.extract::<PyRef<PyHTTPHandler>>() {
-        Some(h.inner.clone())
-    } else if let Ok(h) = obj.extract::<PyRef<PyOTLPHandler>>() {
-        Some(h.inner.clone())
-    } else if let Ok(h) = obj.extract::<PyRef<PyMemoryHandler>>() {
-        Some(h.inner.clone())
-    } else if let Ok(h) = obj.extract::<PyRef<PyFileHandler>>() {
-        Some(h.inner.clone())
-    } else if let Ok(h) = obj.extract::<PyRef<PyStreamHandler>>() {
-        Some(h.inner.clone())
-    } else if let Ok(h) = obj.extract::<PyRef<PyRotatingFileHandler>>() {
-        Some(h.inner.clone())
-    } else {
-        None
+        return Some(h.inner.clone());
+    }
+    #[cfg(feature = "otlp")]
+    if let Ok(h) = obj.extract::<PyRef<PyOTLPHandler>>() {
+        return Some(h.inner.clone());
+    }
+    #[cfg(feature = "network")]
+    if let Ok(h) = obj.extract::<PyRef<PyDatagramHandler>>() {
+        return Some(h.inner.clone());
+    }
+    #[cfg(feature = "network")]
+    if let Ok(h) = obj.extract::<PyRef<PySyslogHandler>>() {
+        return Some(h.inner.clone());
+    }
+    #[cfg(feature = "network")]
+    if let Ok(h) = obj.extract::<PyRef<PySocketHandler>>() {
+        return Some(h.inner.clone());
+    }
+    #[cfg(feature = "smtp")]
+    if let Ok(h) = obj.extract::<PyRef<PySMTPHandler>>() {
+        return Some(h.inner.clone());
     }
+    #[cfg(feature = "pipe")]
+    if let Ok(h) = obj.extract::<PyRef<PyPipeHandler>>() {
+        return Some(h.inner.clone());
+    }
+    if let Ok(h) = obj.extract::<PyRef<PyMemoryHandler>>() {
+        return Some(h.inner.clone());
+    }
+    #[cfg(feature = "file")]
+    if let Ok(h) = obj.extract::<PyRef<PyFileHandler>>() {
+        return Some(h.inner.clone());
+    }
+    if let Ok(h) = obj.extract::<PyRef<PyStreamHandler>>() {
+        return Some(h.inner.clone());
+    }
+    if let Ok(h) = obj.extract::<PyRef<PyQueueHandler>>() {
+        return Some(h.inner.clone());
+    }
+    if let Ok(h) = obj.extract::<PyRef<PyAggregatingHandler>>() {
+        return Some(h.inner.clone());
+    }
+    #[cfg(feature = "file")]
+    if let Ok(h) = obj.extract::<PyRef<PyRotatingFileHandler>>() {
+        return Some(h.inner.clone());
+    }
+    #[cfg(feature = "file")]
+    if let Ok(h) = obj.extract::<PyRef<PyTimedRotatingFileHandler>>() {
+        return Some(h.inner.clone());
+    }
+    #[cfg(feature = "file")]
+    if let Ok(h) = obj.extract::<PyRef<PyLevelRoutedFileHandler>>() {
+        return Some(h.inner.clone());
+    }
+    None
+}
+
+/// Classify a QueueListener sink the way `add_handler_to_registry` classifies a
+/// logger's handler: a Rust pyclass, a wrapper around one, or a foreign handler.
+pub fn listener_sink(handler: &Bound<PyAny>) -> ListenerSink {
+    if let Some(arc) = extract_rust_arc(handler) {
+        return ListenerSink::Rust { arc, wrapper: None };
+    }
+    if let Some(arc) = handler
+        .getattr("_inner")
+        .ok()
+        .as_ref()
+        .and_then(extract_rust_arc)
+    {
+        return ListenerSink::Rust {
+            arc,
+            wrapper: Some(handler.clone().unbind()),
+        };
+    }
+    ListenerSink::Python(handler.clone().unbind())
 }
 
 fn decrement_caller_info() {
@@ -377,14 +613,27 @@ fn decrement_caller_info() {
     }
 }
 
+/// Drop the `HANDLER_OBJECTS` entries of handlers that were just detached.
+fn forget_handler_objects(arcs: &[Arc<dyn Handler + Send + Sync>]) {
+    let mut objects = HANDLER_OBJECTS.lock().unwrap();
+    for arc in arcs {
+        objects.remove(&arc_id(arc));
+    }
+}
+
 fn register_rust_entry(
     is_root: bool,
+    handler: &Bound<PyAny>,
     arc: Arc<dyn Handler + Send + Sync>,
     wrapper: Option<Py<PyAny>>,
     rust_dispatch: &Mutex<Vec<RustEntry>>,
     lifecycle: &Mutex<Vec<Arc<dyn Handler + Send + Sync>>>,
 ) {
     let id = arc_id(&arc);
+    HANDLER_OBJECTS
+        .lock()
+        .unwrap()
+        .insert(id, handler.clone().unbind());
     if is_root {
         // Root handlers live in the global HANDLERS list (Arc only). Text-sink wrappers
         // attached to root therefore dispatch natively (no per-entry wrapper is kept).
@@ -403,7 +652,7 @@ fn register_rust_entry(
 /// Route a handler into the correct dispatch list by backend kind (PHASE 6).
 /// A DIRECT rust pyclass (RustFileHandler etc.) -> rust_dispatch{wrapper:None}. A public
 /// wrapper whose `_inner` is a rust pyclass (File/Stream/Rotating/Memory/HTTP/OTLP) ->
-/// rust_dispatch{wrapper:Some(handler)} so emit_record can read the arc's dispatch_mode and
+/// rust_dispatch{wrapper:Some(handler)} so deliver_record can read the arc's dispatch_mode and
 /// fall back to `wrapper.handle()` in Python mode. FOREIGN Python handlers -> py_dispatch.
 /// `name == "root"` targets the global lists; otherwise the per-logger lists.
 pub fn add_handler_to_registry(
@@ -418,7 +667,7 @@ pub fn add_handler_to_registry(
 
     // DIRECT rust pyclass: the object itself is a handler.
     if let Some(arc) = extract_rust_arc(handler) {
-        register_rust_entry(is_root, arc, None, rust_dispatch, lifecycle);
+        register_rust_entry(is_root, handler, arc, None, rust_dispatch, lifecycle);
         return Ok(true);
     }
 
@@ -426,6 +675,7 @@ pub fn add_handler_to_registry(
     if let Some(arc) = inner.as_ref().and_then(extract_rust_arc) {
         register_rust_entry(
             is_root,
+            handler,
             arc,
             Some(handler.clone().unbind()),
             rust_dispatch,
@@ -453,6 +703,36 @@ pub fn add_handler_to_registry(
     Ok(true)
 }
 
+/// Detach every handler from one logger, shutting the Rust ones down (`dictConfig`
+/// reconfiguring it). `name == "root"` empties the global lists.
+pub fn clear_logger_handlers(
+    py: Python,
+    logger_name: &str,
+    rust_dispatch: &Mutex<Vec<RustEntry>>,
+    py_dispatch: &Mutex<Vec<PyEntry>>,
+    lifecycle: &Mutex<Vec<Arc<dyn Handler + Send + Sync>>>,
+) {
+    let (arcs, entries): (Vec<Arc<dyn Handler + Send + Sync>>, Vec<PyEntry>) =
+        if logger_name == "root" {
+            let mut arcs: Vec<_> = GLOBAL_LIFECYCLE.lock().unwrap().drain(..).collect();
+            arcs.extend(HANDLERS.swap(Arc::new(Vec::new())).iter().cloned());
+            (arcs, GLOBAL_PY_HANDLERS.lock().unwrap().drain(..).collect())
+        } else {
+            rust_dispatch.lock().unwrap().clear();
+            let arcs = lifecycle.lock().unwrap().drain(..).collect();
+            (arcs, py_dispatch.lock().unwrap().drain(..).collect())
+        };
+    for _ in entries.iter().filter(|e| e.needs_caller) {
+        decrement_caller_info();
+    }
+    forget_handler_objects(&arcs);
+    py.detach(|| {
+        for arc in arcs.iter() {
+            arc.shutdown();
+        }
+    });
+}
+
 /// Remove a handler by identity. Rust entries match by `_inner` Arc pointer OR by stored
 /// wrapper identity. Structured/async entries have their worker shut down. Foreign Python
 /// handler removal recomputes CALLER_INFO_REQUIRED.
@@ -485,6 +765,7 @@ pub fn remove_handler_from_registry(
                 .lock()
                 .unwrap()
                 .retain(|h| arc_id(h) != aid);
+            HANDLER_OBJECTS.lock().unwrap().remove(&aid);
         }
         GLOBAL_PY_HANDLERS.lock().unwrap().retain(|e| {
             if e.id == py_id {
@@ -516,6 +797,10 @@ pub fn remove_handler_from_registry(
                 .lock()
                 .unwrap()
                 .retain(|h| !removed_ids.contains(&arc_id(h)));
+            let mut objects = HANDLER_OBJECTS.lock().unwrap();
+            for id in removed_ids.iter() {
+                objects.remove(id);
+            }
         }
         py_dispatch.lock().unwrap().retain(|e| {
             if e.id == py_id {
@@ -1,23 +1,52 @@
 //! # Log Handlers
 //!
-//! StreamHandler, HTTPHandler, OTLPHandler use crossbeam channels + background threads
-//! for non-blocking emit(). FileHandler and RotatingFileHandler use synchronous direct writes.
+//! StreamHandler, HTTPHandler, OTLPHandler and PipeHandler use crossbeam channels +
+//! background threads for non-blocking emit(); QueueHandler does the same with the
+//! consumer thread (QueueListener) under the caller's control. On wasm32 (no threads) StreamHandler
+//! writes synchronously. FileHandler, RotatingFileHandler and TimedRotatingFileHandler
+//! use synchronous direct writes, and DatagramHandler, SyslogHandler, SocketHandler and
+//! SMTPHandler send from the logging thread.
 
 use pyo3::prelude::*;
+#[cfg(feature = "network")]
 use pyo3::types::PyDict;
+#[cfg(feature = "network")]
 use serde_json::Value;
+use std::borrow::Cow;
+#[cfg(feature = "network")]
 use std::collections::HashMap;
-use std::fs::{File, OpenOptions};
-use std::io::{BufWriter, Write};
-use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
-use std::sync::{Arc, Mutex};
-use std::thread::JoinHandle;
-use std::time::Duration;
+#[cfg(feature = "file")]
+use std::fs::File;
+#[cfg(any(feature = "file", feature = "pipe"))]
+use std::fs::OpenOptions;
+#[cfg(feature = "file")]
+use std::io::BufWriter;
+#[cfg(any(feature = "file", feature = "network", feature = "pipe"))]
+use std::io::Write;
+#[cfg(feature = "network")]
+use std::ops::Range;
+#[cfg(feature = "file")]
+use std::path::Path;
+#[cfg(any(feature = "file", feature = "network", feature = "pipe"))]
+use std::path::PathBuf;
+#[cfg(any(feature = "network", feature = "pipe"))]
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crate::core::{LogLevel, LogRecord};
-use crate::filter::Filter;
+use crate::filter::{Filter, FilterSlot};
+use crate::fork::ForkHook;
 use crate::formatter::{Formatter, NoOpFormatter};
+use crate::queue::{LaneReceiver, LaneSender, QueueStats, Queued};
+#[cfg(feature = "file")]
+use crate::retention::Retention;
+use crate::sanitize::{SanitizePolicy, SanitizeSlot};
+#[cfg(feature = "network")]
+use crate::transform::RecordTransform;
+#[cfg(any(feature = "network", feature = "pipe"))]
+use crate::worker::{ShutdownSpill, WorkerConfig, WorkerHandle};
 
 fn default_formatter() -> Arc<dyn Formatter + Send + Sync> {
     Arc::new(NoOpFormatter)
@@ -29,6 +58,62 @@ thread_local! {
     /// a same-GIL-sink deadlock, so it uses a true blocking send() instead of the
     /// Phase-2 bounded send_timeout.
     static BLOCK_CAN_WAIT: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+
+    /// Consoles already written to by the record(s) this thread is dispatching, as a
+    /// bitmask of `StreamDestination`s. None outside a `ConsoleDedupScope`.
+    static CONSOLE_CLAIMS: std::cell::Cell<Option<u8>> = const { std::cell::Cell::new(None) };
+}
+
+/// Whether a record dispatched to several StreamHandlers on the same console is
+/// written there only once (`configure_console_dedup`).
+static CONSOLE_DEDUP: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+pub fn set_console_dedup(enabled: bool) {
+    CONSOLE_DEDUP.store(enabled, Ordering::Relaxed);
+}
+
+pub fn console_dedup() -> bool {
+    CONSOLE_DEDUP.load(Ordering::Relaxed)
+}
+
+/// RAII scope around the dispatch of one record (or one `log_batch()` run) to its
+/// handlers. With console de-duplication on, the first StreamHandler that writes the
+/// record to stdout or stderr claims that console, and the others skip it. Nested
+/// dispatch (a handler that logs) gets a scope of its own.
+pub struct ConsoleDedupScope {
+    previous: Option<u8>,
+}
+
+impl ConsoleDedupScope {
+    pub fn enter() -> Self {
+        let claims = console_dedup().then_some(0);
+        Self {
+            previous: CONSOLE_CLAIMS.with(|c| c.replace(claims)),
+        }
+    }
+}
+
+impl Drop for ConsoleDedupScope {
+    fn drop(&mut self) {
+        CONSOLE_CLAIMS.with(|c| c.set(self.previous));
+    }
+}
+
+/// Claim `dest` for the record being dispatched. False when another handler already
+/// wrote it there; always true outside a de-duplicating scope.
+fn claim_console(dest: StreamDestination) -> bool {
+    let bit = 1u8 << dest as u8;
+    CONSOLE_CLAIMS.with(|c| match c.get() {
+        Some(claims) if claims & bit != 0 => {
+            crate::dispatch_trace::note(crate::dispatch_trace::Outcome::ConsoleClaimed);
+            false
+        }
+        Some(claims) => {
+            c.set(Some(claims | bit));
+            true
+        }
+        None => true,
+    })
 }
 
 /// RAII scope marking the current thread as running detached (GIL-released) dispatch.
@@ -47,7 +132,7 @@ impl Drop for BlockWaitGuard {
     }
 }
 
-fn block_can_wait() -> bool {
+pub(crate) fn block_can_wait() -> bool {
     BLOCK_CAN_WAIT.with(|c| c.get())
 }
 
@@ -73,18 +158,36 @@ impl DispatchMode {
 }
 
 pub trait Handler: Send + Sync {
-    fn emit(&self, record: &LogRecord);
+    /// Handle one record. Every handler a record reaches gets the same `Arc`: a handler
+    /// that keeps the record (a queue, a batch buffer) clones the `Arc`, not the record.
+    fn emit(&self, record: &Arc<LogRecord>);
+    /// Emit records that share one level and set of extras (`logger.log_batch()`).
+    /// Queued handlers override this to push the whole run as a single queue item.
+    fn emit_batch(&self, records: &[Arc<LogRecord>]) {
+        for record in records {
+            self.emit(record);
+        }
+    }
     fn flush(&self);
+    /// Minimum `levelno` the handler accepts (`debug_dispatch()` reports records below it).
+    fn level(&self) -> u32 {
+        LogLevel::NotSet.value()
+    }
     /// Stop the handler's background worker (if any), draining/joining as appropriate.
-    /// Default no-op for synchronous handlers (File/Stream/Rotating/Memory).
+    /// Default no-op for synchronous handlers (File/Rotating/Memory).
     fn shutdown(&self) {}
     /// Current dispatch mode. Defaults to Native; text-sink handlers override with an
     /// AtomicU8-backed flag so the wrapper can flip them to Python for fallback formatting.
     fn dispatch_mode(&self) -> DispatchMode {
         DispatchMode::Native
     }
-    /// Set the dispatch mode. Default no-op (HTTP/OTLP/Memory never fall back).
+    /// Set the dispatch mode. Default no-op (handlers without a Python fallback).
     fn set_dispatch_mode(&self, _mode: DispatchMode) {}
+    /// The Rust filters the handler runs itself, for handlers with a dispatch mode: the
+    /// wrapper's filters, when they are all Rust filters, keep it native.
+    fn filters(&self) -> Option<&FilterSlot> {
+        None
+    }
     #[allow(dead_code)]
     fn set_formatter(&mut self, formatter: Arc<dyn Formatter + Send + Sync>);
     #[allow(dead_code)]
@@ -102,72 +205,122 @@ pub enum StreamDestination {
 }
 
 pub struct StreamHandler {
-    sender: crossbeam_channel::Sender<String>,
-    drop_rx: crossbeam_channel::Receiver<String>,
+    dest: StreamDestination,
+    lanes: LaneSender<String>,
     flush_signal: crossbeam_channel::Sender<()>,
     flush_done: crossbeam_channel::Receiver<()>,
-    level: AtomicU8,
+    level: AtomicU32,
     dispatch_mode: AtomicU8,
+    filters: FilterSlot,
     overflow: OverflowStrategy,
     flush_timeout: Duration,
     emitted: AtomicU64,
-    queue_dropped: AtomicU64,
+    queue: Arc<QueueStats>,
+    sync: SyncLevel,
     formatter: parking_lot::Mutex<Arc<dyn Formatter + Send + Sync>>,
+    sanitize: SanitizeSlot,
+    /// Used instead of `formatter` while the destination is a terminal.
+    tty_formatter: parking_lot::Mutex<Option<Arc<dyn Formatter + Send + Sync>>>,
+    /// Restarts the writer thread in a forked child.
+    _fork_hook: Arc<ForkHook>,
 }
 
 impl StreamHandler {
     fn new_with_dest(dest: StreamDestination) -> Self {
-        let (tx, rx) = crossbeam_channel::bounded::<String>(8192);
-        let drop_rx = rx.clone();
+        let (tx, rx) = crate::queue::lanes::<String>(8192);
         let (flush_tx, flush_rx) = crossbeam_channel::bounded::<()>(1);
         let (done_tx, done_rx) = crossbeam_channel::bounded::<()>(1);
-
-        std::thread::Builder::new()
-            .name("logxide-stream".into())
-            .spawn(move || {
-                loop {
-                    // Check for flush signal
-                    if flush_rx.try_recv().is_ok() {
-                        // Drain all pending messages
-                        while let Ok(msg) = rx.try_recv() {
-                            Self::write_to_dest(dest, &msg);
-                        }
-                        let _ = done_tx.try_send(());
-                    }
-
-                    match rx.recv_timeout(Duration::from_millis(50)) {
-                        Ok(msg) => {
-                            Self::write_to_dest(dest, &msg);
-                        }
-                        Err(crossbeam_channel::RecvTimeoutError::Timeout) => {}
-                        Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
-                            // Drain remaining
-                            while let Ok(msg) = rx.try_recv() {
-                                Self::write_to_dest(dest, &msg);
-                            }
-                            let _ = done_tx.try_send(());
-                            break;
-                        }
+        let queue = QueueStats::register("stream", rx.clone());
+        let worker_queue = queue.clone();
+
+        if crate::platform::THREADS {
+            Self::spawn_worker(
+                dest,
+                rx.clone(),
+                flush_rx.clone(),
+                done_tx.clone(),
+                worker_queue.clone(),
+            );
+        }
+        let fork_hook = {
+            let done_rx = done_rx.clone();
+            ForkHook::default()
+                .with_in_child(move || {
+                    worker_queue.reset_after_fork(&rx);
+                    while flush_rx.try_recv().is_ok() || done_rx.try_recv().is_ok() {}
+                    if crate::platform::THREADS {
+                        Self::spawn_worker(
+                            dest,
+                            rx.clone(),
+                            flush_rx.clone(),
+                            done_tx.clone(),
+                            worker_queue.clone(),
+                        );
                     }
-                }
-            })
-            .expect("Failed to spawn stream handler thread");
+                })
+                .register()
+        };
 
         Self {
-            sender: tx,
-            drop_rx,
+            dest,
+            lanes: tx,
             flush_signal: flush_tx,
             flush_done: done_rx,
-            level: AtomicU8::new(LogLevel::Debug as u8),
+            level: AtomicU32::new(LogLevel::NotSet.value()),
             dispatch_mode: AtomicU8::new(DispatchMode::Native as u8),
+            filters: FilterSlot::default(),
             overflow: OverflowStrategy::DropNewest,
             flush_timeout: DEFAULT_FLUSH_TIMEOUT,
             emitted: AtomicU64::new(0),
-            queue_dropped: AtomicU64::new(0),
+            queue,
+            sync: SyncLevel::new(None),
             formatter: parking_lot::Mutex::new(default_formatter()),
+            sanitize: SanitizeSlot::default(),
+            tty_formatter: parking_lot::Mutex::new(None),
+            _fork_hook: fork_hook,
         }
     }
 
+    fn spawn_worker(
+        dest: StreamDestination,
+        rx: LaneReceiver<String>,
+        flush_rx: crossbeam_channel::Receiver<()>,
+        done_tx: crossbeam_channel::Sender<()>,
+        worker_queue: Arc<QueueStats>,
+    ) {
+        crate::threads::spawn("stream", move || {
+            loop {
+                // Check for flush signal
+                if flush_rx.try_recv().is_ok() {
+                    // Drain all pending messages
+                    while let Ok(msg) = rx.try_recv() {
+                        let (msg, _) = worker_queue.dequeue(msg);
+                        Self::write_to_dest(dest, &msg);
+                    }
+                    let _ = done_tx.try_send(());
+                }
+
+                match rx.recv_timeout(Duration::from_millis(50)) {
+                    Ok(msg) => {
+                        let (msg, _) = worker_queue.dequeue(msg);
+                        Self::write_to_dest(dest, &msg);
+                    }
+                    Err(crossbeam_channel::RecvTimeoutError::Timeout) => {}
+                    Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
+                        // Drain remaining
+                        while let Ok(msg) = rx.try_recv() {
+                            let (msg, _) = worker_queue.dequeue(msg);
+                            Self::write_to_dest(dest, &msg);
+                        }
+                        let _ = done_tx.try_send(());
+                        break;
+                    }
+                }
+            }
+        })
+        .expect("Failed to spawn stream handler thread");
+    }
+
     pub fn stdout() -> Self {
         Self::new_with_dest(StreamDestination::Stdout)
     }
@@ -176,21 +329,18 @@ impl StreamHandler {
         Self::new_with_dest(StreamDestination::Stderr)
     }
 
+    /// Handle a full queue with `overflow` instead of dropping the new record.
+    pub fn with_overflow(mut self, overflow: OverflowStrategy) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
     fn write_to_dest(dest: StreamDestination, msg: &str) {
-        match dest {
-            StreamDestination::Stdout => {
-                let stdout = std::io::stdout();
-                let _ = writeln!(stdout.lock(), "{msg}");
-            }
-            StreamDestination::Stderr => {
-                let stderr = std::io::stderr();
-                let _ = writeln!(stderr.lock(), "{msg}");
-            }
-        }
+        crate::platform::write_console(dest, msg);
     }
 
     pub fn set_level(&self, level: LogLevel) {
-        self.level.store(level as u8, Ordering::Relaxed);
+        self.level.store(level.value(), Ordering::Relaxed);
     }
 
     /// Set an error callback for this handler.
@@ -204,75 +354,150 @@ impl StreamHandler {
         *self.formatter.lock() = formatter;
     }
 
+    /// Escape or strip control characters in the formatted text.
+    pub fn set_sanitize(&self, policy: SanitizePolicy) {
+        self.sanitize.set(policy);
+    }
+
+    /// Set the formatter used while the destination is a terminal (None = always use
+    /// the regular formatter).
+    pub fn set_tty_formatter(&self, formatter: Option<Arc<dyn Formatter + Send + Sync>>) {
+        *self.tty_formatter.lock() = formatter;
+    }
+
+    /// Whether the destination is currently a terminal.
+    pub fn is_terminal(&self) -> bool {
+        crate::platform::console_is_terminal(self.dest)
+    }
+
     /// Format a record using the configured formatter, or return the raw message.
+    /// The terminal check runs per record, so a redirected stream switches formats.
     fn format_record(&self, record: &LogRecord) -> String {
-        self.formatter.lock().format(record)
+        if let Some(formatter) = &*self.tty_formatter.lock() {
+            if self.is_terminal() {
+                return self.sanitize.apply(formatter.format(record));
+            }
+        }
+        self.sanitize.apply(self.formatter.lock().format(record))
     }
 
-    fn enqueue(&self, output: String) {
-        match self.overflow {
-            OverflowStrategy::DropNewest => {
-                if self.sender.try_send(output).is_err() {
-                    self.queue_dropped.fetch_add(1, Ordering::Relaxed);
-                }
-            }
-            OverflowStrategy::DropOldest => {
-                let mut output = output;
-                loop {
-                    match self.sender.try_send(output) {
-                        Ok(()) => break,
-                        Err(crossbeam_channel::TrySendError::Full(returned)) => {
-                            if self.drop_rx.try_recv().is_ok() {
-                                self.queue_dropped.fetch_add(1, Ordering::Relaxed);
-                            }
-                            output = returned;
-                        }
-                        Err(crossbeam_channel::TrySendError::Disconnected(_)) => {
-                            self.queue_dropped.fetch_add(1, Ordering::Relaxed);
-                            break;
-                        }
-                    }
-                }
-            }
-            OverflowStrategy::Block => {
-                // True blocking send on the detached path (§4); bounded on the attached path.
-                if block_can_wait() {
-                    if self.sender.send(output).is_err() {
-                        self.queue_dropped.fetch_add(1, Ordering::Relaxed);
-                    }
-                } else if self
-                    .sender
-                    .send_timeout(output, self.flush_timeout)
-                    .is_err()
-                {
-                    self.queue_dropped.fetch_add(1, Ordering::Relaxed);
-                }
-            }
+    fn enqueue(&self, record: &LogRecord, output: String) {
+        if !crate::platform::THREADS {
+            // No worker thread to drain the queue: write in the caller.
+            Self::write_to_dest(self.dest, &output);
+            return;
         }
+        crate::queue::enqueue(
+            &self.lanes,
+            self.overflow,
+            self.flush_timeout,
+            &self.queue,
+            Queued::new(output, record),
+        );
     }
 
     pub fn metrics_snapshot(&self) -> (u64, u64) {
-        (
-            self.emitted.load(Ordering::Relaxed),
-            self.queue_dropped.load(Ordering::Relaxed),
-        )
+        (self.emitted.load(Ordering::Relaxed), self.queue.dropped())
+    }
+
+    pub fn queue_stats(&self) -> &QueueStats {
+        &self.queue
+    }
+
+    pub fn sync_level(&self) -> &SyncLevel {
+        &self.sync
     }
 }
 
 impl Handler for StreamHandler {
-    fn emit(&self, record: &LogRecord) {
+    fn emit(&self, record: &Arc<LogRecord>) {
         let level = self.level.load(Ordering::Relaxed);
         if record.levelno < level as i32 {
             return;
         }
+        let Some(record) = self.filters.apply(record) else {
+            return;
+        };
+        if !claim_console(self.dest) {
+            return;
+        }
+        let record = &*record;
         self.emitted.fetch_add(1, Ordering::Relaxed);
         let output = self.format_record(record);
-        self.enqueue(output);
+        if self.sync.applies(record) {
+            // Written ahead of anything still queued, and flushed before returning.
+            Self::write_to_dest(self.dest, &output);
+            crate::platform::flush_console(self.dest);
+            return;
+        }
+        self.enqueue(record, output);
+    }
+
+    fn emit_batch(&self, records: &[Arc<LogRecord>]) {
+        let level = self.level.load(Ordering::Relaxed);
+        // The records share one level, so the run is written here in full or not at all.
+        let Some(first) = records.first() else {
+            return;
+        };
+        if first.levelno < level as i32 || !claim_console(self.dest) {
+            return;
+        }
+        let mut queued = Vec::with_capacity(records.len());
+        for record in records.iter().filter(|r| r.levelno >= level as i32) {
+            let Some(record) = self.filters.apply(record) else {
+                continue;
+            };
+            self.emitted.fetch_add(1, Ordering::Relaxed);
+            let output = self.format_record(&record);
+            if self.sync.applies(&record) {
+                Self::write_to_dest(self.dest, &output);
+                crate::platform::flush_console(self.dest);
+                continue;
+            }
+            queued.push((record, output));
+        }
+        let Some((first, _)) = queued.first() else {
+            return;
+        };
+        let first = first.clone();
+        // The worker writes one item per line, so the run goes out as one joined item.
+        let count = queued.len();
+        let output = queued
+            .into_iter()
+            .map(|(_, output)| output)
+            .collect::<Vec<_>>()
+            .join("\n");
+        if !crate::platform::THREADS {
+            Self::write_to_dest(self.dest, &output);
+            return;
+        }
+        crate::queue::enqueue(
+            &self.lanes,
+            self.overflow,
+            self.flush_timeout,
+            &self.queue,
+            Queued::batch(output, &first, count),
+        );
+    }
+
+    fn level(&self) -> u32 {
+        self.level.load(Ordering::Relaxed)
     }
 
     fn flush(&self) {
+        if !crate::platform::THREADS {
+            return;
+        }
+        let started = Instant::now();
         let _ = self.flush_signal.try_send(());
         let _ = self.flush_done.recv_timeout(Duration::from_secs(5));
+        self.queue.observe_flush(started);
+    }
+
+    /// Write out what is still queued, so a record logged before the handler was
+    /// detached (`reinitialize()`, a `dictConfig` reload) is not written after it.
+    fn shutdown(&self) {
+        self.flush();
     }
 
     fn dispatch_mode(&self) -> DispatchMode {
@@ -283,6 +508,10 @@ impl Handler for StreamHandler {
         self.dispatch_mode.store(mode as u8, Ordering::Relaxed);
     }
 
+    fn filters(&self) -> Option<&FilterSlot> {
+        Some(&self.filters)
+    }
+
     fn set_formatter(&mut self, formatter: Arc<dyn Formatter + Send + Sync>) {
         *self.formatter.lock() = formatter;
     }
@@ -294,38 +523,268 @@ impl Handler for StreamHandler {
 // FileHandler — synchronous direct file write
 // ============================================================================
 
+#[cfg(feature = "file")]
 pub struct FileHandler {
-    writer: parking_lot::Mutex<BufWriter<File>>,
-    level: AtomicU8,
-    flush_level: AtomicU8,
+    writer: Arc<parking_lot::Mutex<BufWriter<File>>>,
+    /// Flushes the buffer before a fork.
+    _fork_hook: Arc<ForkHook>,
+    level: AtomicU32,
+    flush_level: AtomicU32,
     dispatch_mode: AtomicU8,
+    filters: FilterSlot,
     formatter: parking_lot::Mutex<Arc<dyn Formatter + Send + Sync>>,
+    sanitize: SanitizeSlot,
+    watch: Option<WatchedPath>,
+    checksums: Checksums,
+    index: SidecarIndex,
+    path: PathBuf,
+}
+
+/// What a watching `FileHandler` compares before each write: the path it logs to and
+/// the (device, inode) of the file it has open. Only read or replaced with the writer
+/// lock held.
+#[cfg(feature = "file")]
+struct WatchedPath {
+    path: PathBuf,
+    opened: parking_lot::Mutex<Option<(u64, u64)>>,
+}
+
+/// (device, inode) of a file; None where the platform has no such identity.
+#[cfg(all(feature = "file", unix))]
+fn file_identity(meta: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((meta.dev(), meta.ino()))
+}
+
+#[cfg(all(feature = "file", not(unix)))]
+fn file_identity(_meta: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
 }
 
+/// Where a rotating handler moves its file first, before the backups make room for it:
+/// a move that fails then leaves every backup as it was.
+#[cfg(feature = "file")]
+fn pending_backup(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".rotating");
+    PathBuf::from(name)
+}
+
+/// Move a log file to `backup` for rotation. Renaming fails on Windows while another
+/// process has the file open without delete sharing (a log shipper, `Get-Content
+/// -Wait`, a virus scanner); the content is then copied to `backup` instead, and the
+/// caller's truncating reopen completes a copy-truncate rotation. On error nothing was
+/// moved and the file must be kept as it is.
+#[cfg(feature = "file")]
+fn move_aside(path: &Path, backup: &Path) -> std::io::Result<()> {
+    let renamed = match std::fs::rename(path, backup) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && !path.exists() => return Ok(()),
+        renamed => renamed,
+    };
+    renamed.or_else(|rename_error| match std::fs::copy(path, backup) {
+        Ok(_) => Ok(()),
+        Err(copy_error) => Err(std::io::Error::new(
+            rename_error.kind(),
+            format!("rename failed ({rename_error}), copy failed ({copy_error})"),
+        )),
+    })
+}
+
+/// A hook that flushes `writer` before a fork and, in the child, drops whatever the
+/// parent buffered after that, so no bytes are written by both processes.
+#[cfg(feature = "file")]
+fn flush_before_fork(writer: &Arc<parking_lot::Mutex<BufWriter<File>>>) -> Arc<ForkHook> {
+    let parent = writer.clone();
+    let child = writer.clone();
+    ForkHook::default()
+        .with_before(move || {
+            let _ = parent.lock().flush();
+        })
+        .with_in_child(move || {
+            crate::fork::unlock_in_child(&child);
+            let mut writer = child.lock();
+            if writer.buffer().is_empty() {
+                return;
+            }
+            if let Ok(file) = writer.get_ref().try_clone() {
+                let (_, _discarded) =
+                    std::mem::replace(&mut *writer, BufWriter::new(file)).into_parts();
+            }
+        })
+        .register()
+}
+
+#[cfg(feature = "file")]
+fn open_append(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// The open checksum segment of a file handler with `checksum_every`, locked after the
+/// writer.
+#[cfg(feature = "file")]
+type Checksums = Option<parking_lot::Mutex<crate::checksum::Segment>>;
+
+/// The sidecar index of a file handler with `index_every`, locked after the writer.
+#[cfg(feature = "file")]
+type SidecarIndex = Option<parking_lot::Mutex<crate::index::Index>>;
+
+/// Add the record about to be written to the sidecar index, if there is one.
+#[cfg(feature = "file")]
+fn index_record(writer: &BufWriter<File>, record: &LogRecord, index: &SidecarIndex) {
+    if let Some(index) = index {
+        index.lock().add(record.created, writer);
+    }
+}
+
+/// Start the sidecar index over after the handler opened a new file.
+#[cfg(feature = "file")]
+fn restart_index(index: &SidecarIndex) {
+    if let Some(index) = index {
+        index.lock().restart();
+    }
+}
+
+/// Write a record's text and, when it completes a checksum segment, the trailer.
+/// Returns the bytes written.
+#[cfg(feature = "file")]
+fn write_record(
+    writer: &mut BufWriter<File>,
+    text: &[u8],
+    checksums: &Checksums,
+) -> std::io::Result<u64> {
+    writer.write_all(text)?;
+    let trailer = checksums
+        .as_ref()
+        .and_then(|segment| segment.lock().add(text));
+    match trailer {
+        Some(trailer) => {
+            writer.write_all(trailer.as_bytes())?;
+            Ok((text.len() + trailer.len()) as u64)
+        }
+        None => Ok(text.len() as u64),
+    }
+}
+
+/// End the open checksum segment with its trailer, so everything written so far is
+/// covered before a flush or a rotation. Returns the bytes written.
+#[cfg(feature = "file")]
+fn end_segment(writer: &mut BufWriter<File>, checksums: &Checksums) -> u64 {
+    let Some(trailer) = checksums
+        .as_ref()
+        .and_then(|segment| segment.lock().close())
+    else {
+        return 0;
+    };
+    match writer.write_all(trailer.as_bytes()) {
+        Ok(()) => trailer.len() as u64,
+        Err(e) => {
+            eprintln!("[LogXide Error] cannot write checksum trailer: {e}");
+            0
+        }
+    }
+}
+
+#[cfg(feature = "file")]
 impl FileHandler {
     pub fn new<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
-        let f = OpenOptions::new().create(true).append(true).open(path)?;
+        let path = path.as_ref().to_path_buf();
+        let f = open_append(&path)?;
+        let writer = Arc::new(parking_lot::Mutex::new(BufWriter::new(f)));
         Ok(Self {
-            writer: parking_lot::Mutex::new(BufWriter::new(f)),
-            level: AtomicU8::new(LogLevel::Debug as u8),
-            flush_level: AtomicU8::new(LogLevel::Error as u8),
+            _fork_hook: flush_before_fork(&writer),
+            writer,
+            level: AtomicU32::new(LogLevel::NotSet.value()),
+            flush_level: AtomicU32::new(crate::preset::current().flush_level.value()),
             dispatch_mode: AtomicU8::new(DispatchMode::Native as u8),
+            filters: FilterSlot::default(),
             formatter: parking_lot::Mutex::new(default_formatter()),
+            sanitize: SanitizeSlot::default(),
+            watch: None,
+            checksums: None,
+            index: None,
+            path,
         })
     }
 
+    /// Follow every `every` records with a checksum trailer (see [`crate::checksum`]).
+    pub fn with_checksums(mut self, every: u32) -> Self {
+        self.checksums = Some(parking_lot::Mutex::new(crate::checksum::Segment::new(
+            every,
+        )));
+        self
+    }
+
+    /// Add every `every`th record to a sidecar index (see [`crate::index`]).
+    pub fn with_index(mut self, every: u32) -> Self {
+        self.index = Some(parking_lot::Mutex::new(crate::index::Index::new(
+            &self.path, every,
+        )));
+        self
+    }
+
+    /// Like [`FileHandler::new`], but before each write the handler checks whether the
+    /// file at `path` is still the one it has open, and reopens `path` if it was moved
+    /// or deleted (by logrotate, say), like `logging.handlers.WatchedFileHandler`.
+    /// Without the check the handler would keep appending to the rotated file.
+    ///
+    /// On platforms without device/inode numbers this behaves like `new`.
+    pub fn watched<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut handler = Self::new(&path)?;
+        let opened = file_identity(&handler.writer.lock().get_ref().metadata()?);
+        handler.watch = Some(WatchedPath {
+            path,
+            opened: parking_lot::Mutex::new(opened),
+        });
+        Ok(handler)
+    }
+
+    /// Reopen the watched path if the file there is no longer the open one. Called with
+    /// the writer locked, so concurrent writers reopen it once.
+    fn reopen_if_moved(&self, writer: &mut BufWriter<File>) {
+        let Some(watch) = &self.watch else {
+            return;
+        };
+        let mut opened = watch.opened.lock();
+        let Some(identity) = *opened else {
+            return;
+        };
+        let current = std::fs::metadata(&watch.path)
+            .ok()
+            .and_then(|meta| file_identity(&meta));
+        if current == Some(identity) {
+            return;
+        }
+        // Whatever is still buffered belongs to the old file.
+        end_segment(writer, &self.checksums);
+        let _ = writer.flush();
+        match open_append(&watch.path) {
+            Ok(file) => {
+                if let Some(reopened) = file.metadata().ok().and_then(|m| file_identity(&m)) {
+                    *opened = Some(reopened);
+                }
+                *writer = BufWriter::new(file);
+                restart_index(&self.index);
+            }
+            Err(e) => eprintln!(
+                "[LogXide Error] FileHandler could not reopen {}: {e}",
+                watch.path.display()
+            ),
+        }
+    }
+
     pub fn set_level(&self, level: LogLevel) {
-        self.level.store(level as u8, Ordering::Relaxed);
+        self.level.store(level.value(), Ordering::Relaxed);
     }
 
     /// Set the flush level. Records at or above this level trigger immediate flush.
     /// Default is ERROR (40).
     pub fn set_flush_level(&self, level: LogLevel) {
-        self.flush_level.store(level as u8, Ordering::Relaxed);
+        self.flush_level.store(level.value(), Ordering::Relaxed);
     }
 
     /// Get the current flush level.
-    pub fn get_flush_level(&self) -> u8 {
+    pub fn get_flush_level(&self) -> u32 {
         self.flush_level.load(Ordering::Relaxed)
     }
 
@@ -337,21 +796,37 @@ impl FileHandler {
         *self.formatter.lock() = formatter;
     }
 
+    /// Escape or strip control characters in the formatted text.
+    pub fn set_sanitize(&self, policy: SanitizePolicy) {
+        self.sanitize.set(policy);
+    }
+
     /// Format a record using the configured formatter, or return the raw message.
     fn format_record(&self, record: &LogRecord) -> String {
-        self.formatter.lock().format(record)
+        self.sanitize.apply(self.formatter.lock().format(record))
     }
 }
 
+#[cfg(feature = "file")]
 impl Handler for FileHandler {
-    fn emit(&self, record: &LogRecord) {
+    fn emit(&self, record: &Arc<LogRecord>) {
         let level = self.level.load(Ordering::Relaxed);
         if record.levelno < level as i32 {
             return;
         }
-        let output = self.format_record(record);
+        let Some(record) = self.filters.apply(record) else {
+            return;
+        };
+        let record = &*record;
+        // One write per record: BufWriter only flushes between writes, so a line is never
+        // split by another writer appending to the same file (such as the handler a
+        // config reload is replacing).
+        let mut line = self.format_record(record);
+        line.push('\n');
         let mut w = self.writer.lock();
-        if let Err(e) = writeln!(w, "{output}") {
+        self.reopen_if_moved(&mut w);
+        index_record(&w, record, &self.index);
+        if let Err(e) = write_record(&mut w, line.as_bytes(), &self.checksums) {
             eprintln!("[LogXide Error] FileHandler write failed: {e}");
         }
         // Level-based flush: flush if record level >= flush_level
@@ -361,8 +836,14 @@ impl Handler for FileHandler {
         }
     }
 
+    fn level(&self) -> u32 {
+        self.level.load(Ordering::Relaxed)
+    }
+
     fn flush(&self) {
-        let _ = self.writer.lock().flush();
+        let mut w = self.writer.lock();
+        end_segment(&mut w, &self.checksums);
+        let _ = w.flush();
     }
 
     fn dispatch_mode(&self) -> DispatchMode {
@@ -373,6 +854,10 @@ impl Handler for FileHandler {
         self.dispatch_mode.store(mode as u8, Ordering::Relaxed);
     }
 
+    fn filters(&self) -> Option<&FilterSlot> {
+        Some(&self.filters)
+    }
+
     fn set_formatter(&mut self, formatter: Arc<dyn Formatter + Send + Sync>) {
         *self.formatter.lock() = formatter;
     }
@@ -384,50 +869,95 @@ impl Handler for FileHandler {
 // RotatingFileHandler — synchronous direct file write with rotation
 // ============================================================================
 
+#[cfg(feature = "file")]
 pub struct RotatingFileHandler {
-    writer: parking_lot::Mutex<BufWriter<File>>,
+    writer: Arc<parking_lot::Mutex<BufWriter<File>>>,
+    /// Flushes the buffer before a fork.
+    _fork_hook: Arc<ForkHook>,
     filename: PathBuf,
     max_bytes: u64,
     backup_count: u32,
+    retention: Retention,
+    checksums: Checksums,
+    index: SidecarIndex,
     current_size: std::sync::atomic::AtomicU64,
-    level: AtomicU8,
-    flush_level: AtomicU8,
+    level: AtomicU32,
+    flush_level: AtomicU32,
     dispatch_mode: AtomicU8,
+    filters: FilterSlot,
     formatter: parking_lot::Mutex<Arc<dyn Formatter + Send + Sync>>,
+    sanitize: SanitizeSlot,
 }
 
+#[cfg(feature = "file")]
 impl RotatingFileHandler {
-    pub fn new(filename: String, max_bytes: u64, backup_count: u32) -> std::io::Result<Self> {
-        let path = PathBuf::from(&filename);
+    pub fn new<P: AsRef<Path>>(
+        filename: P,
+        max_bytes: u64,
+        backup_count: u32,
+    ) -> std::io::Result<Self> {
+        let path = filename.as_ref().to_path_buf();
 
         let initial_size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
 
         let file = OpenOptions::new().create(true).append(true).open(&path)?;
 
+        let writer = Arc::new(parking_lot::Mutex::new(BufWriter::new(file)));
         Ok(Self {
-            writer: parking_lot::Mutex::new(BufWriter::new(file)),
+            _fork_hook: flush_before_fork(&writer),
+            writer,
             filename: path,
             max_bytes,
             backup_count,
+            retention: Retention::default(),
+            checksums: None,
+            index: None,
             current_size: std::sync::atomic::AtomicU64::new(initial_size),
-            level: AtomicU8::new(LogLevel::Debug as u8),
-            flush_level: AtomicU8::new(LogLevel::Error as u8),
+            level: AtomicU32::new(LogLevel::NotSet.value()),
+            flush_level: AtomicU32::new(crate::preset::current().flush_level.value()),
             dispatch_mode: AtomicU8::new(DispatchMode::Native as u8),
+            filters: FilterSlot::default(),
             formatter: parking_lot::Mutex::new(default_formatter()),
+            sanitize: SanitizeSlot::default(),
         })
     }
 
+    /// Also delete backups past the `retention` limits after each rotation.
+    pub fn with_retention(mut self, retention: Retention) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Follow every `every` records with a checksum trailer (see [`crate::checksum`]).
+    /// Each backup ends with one.
+    pub fn with_checksums(mut self, every: u32) -> Self {
+        self.checksums = Some(parking_lot::Mutex::new(crate::checksum::Segment::new(
+            every,
+        )));
+        self
+    }
+
+    /// Add every `every`th record to a sidecar index (see [`crate::index`]), which
+    /// moves with the file to its backup.
+    pub fn with_index(mut self, every: u32) -> Self {
+        self.index = Some(parking_lot::Mutex::new(crate::index::Index::new(
+            &self.filename,
+            every,
+        )));
+        self
+    }
+
     pub fn set_level(&self, level: LogLevel) {
-        self.level.store(level as u8, Ordering::Relaxed);
+        self.level.store(level.value(), Ordering::Relaxed);
     }
 
     /// Set the flush level. Records at or above this level trigger immediate flush.
     pub fn set_flush_level(&self, level: LogLevel) {
-        self.flush_level.store(level as u8, Ordering::Relaxed);
+        self.flush_level.store(level.value(), Ordering::Relaxed);
     }
 
     /// Get the current flush level.
-    pub fn get_flush_level(&self) -> u8 {
+    pub fn get_flush_level(&self) -> u32 {
         self.flush_level.load(Ordering::Relaxed)
     }
 
@@ -436,23 +966,27 @@ impl RotatingFileHandler {
         *self.formatter.lock() = formatter;
     }
 
+    /// Escape or strip control characters in the formatted text.
+    pub fn set_sanitize(&self, policy: SanitizePolicy) {
+        self.sanitize.set(policy);
+    }
+
     /// Set an error callback for this handler.
     pub fn set_error_callback(&self, _callback: Option<Arc<dyn Fn(String) + Send + Sync>>) {}
 
     /// Format a record using the configured formatter, or return the raw message.
     fn format_record(&self, record: &LogRecord) -> String {
-        self.formatter.lock().format(record)
+        self.sanitize.apply(self.formatter.lock().format(record))
     }
 
     /// Generate backup filename for given index (e.g., app.log.1, app.log.2)
     fn backup_filename(path: &Path, index: u32) -> PathBuf {
-        let mut backup = path.to_path_buf();
-        let filename = backup
+        let mut filename = path
             .file_name()
-            .and_then(|s| s.to_str())
-            .unwrap_or("app.log");
-        backup.set_file_name(format!("{filename}.{index}"));
-        backup
+            .unwrap_or("app.log".as_ref())
+            .to_os_string();
+        filename.push(format!(".{index}"));
+        path.with_file_name(filename)
     }
 
     /// Perform rotation.
@@ -468,28 +1002,53 @@ impl RotatingFileHandler {
             if let Ok(f) = OpenOptions::new().write(true).truncate(true).open(path) {
                 *writer = BufWriter::new(f);
                 current_size.store(0, Ordering::Relaxed);
+                crate::index::remove(path);
             }
             return;
         }
 
+        let pending = pending_backup(path);
+        if let Err(e) = move_aside(path, &pending) {
+            eprintln!(
+                "[LogXide Error] RotatingFileHandler: cannot rotate {}: {e}",
+                path.display()
+            );
+            let _ = std::fs::remove_file(&pending);
+            // Keep appending, and try again after another max_bytes.
+            current_size.store(0, Ordering::Relaxed);
+            return;
+        }
+        crate::index::rename(path, &pending);
+
         for i in backup_count..backup_count + 10 {
             let bp = Self::backup_filename(path, i);
             if bp.exists() {
                 let _ = std::fs::remove_file(&bp);
+                crate::index::remove(&bp);
             } else {
                 break;
             }
         }
 
+        // Sidecar indexes move with their files.
         for i in (1..backup_count).rev() {
             let src = Self::backup_filename(path, i);
             let dst = Self::backup_filename(path, i + 1);
             if src.exists() {
                 let _ = std::fs::rename(&src, &dst);
+                crate::index::rename(&src, &dst);
             }
         }
 
-        let _ = std::fs::rename(path, Self::backup_filename(path, 1));
+        let backup = Self::backup_filename(path, 1);
+        if let Err(e) = std::fs::rename(&pending, &backup) {
+            eprintln!(
+                "[LogXide Error] RotatingFileHandler: cannot rename {} to {}: {e}",
+                pending.display(),
+                backup.display()
+            );
+        }
+        crate::index::rename(&pending, &backup);
 
         match OpenOptions::new()
             .create(true)
@@ -508,34 +1067,48 @@ impl RotatingFileHandler {
     }
 }
 
+#[cfg(feature = "file")]
 impl Handler for RotatingFileHandler {
-    fn emit(&self, record: &LogRecord) {
+    fn emit(&self, record: &Arc<LogRecord>) {
         let level = self.level.load(Ordering::Relaxed);
         if record.levelno < level as i32 {
             return;
         }
+        let Some(record) = self.filters.apply(record) else {
+            return;
+        };
+        let record = &*record;
 
-        let output = self.format_record(record);
-        let message_bytes = output.len() as u64 + 1;
+        let mut line = self.format_record(record);
+        line.push('\n');
+        let message_bytes = line.len() as u64;
 
         let mut w = self.writer.lock();
 
         // Check rotation
         let cur = self.current_size.load(Ordering::Relaxed);
         if self.max_bytes > 0 && cur + message_bytes > self.max_bytes {
+            end_segment(&mut w, &self.checksums);
             Self::do_rotation(
                 &self.filename,
                 self.backup_count,
                 &mut w,
                 &self.current_size,
             );
+            restart_index(&self.index);
+            let backups: Vec<PathBuf> = (1..=self.backup_count)
+                .rev()
+                .map(|i| Self::backup_filename(&self.filename, i))
+                .collect();
+            self.retention.apply(&self.filename, &backups);
         }
 
-        if let Err(e) = writeln!(w, "{output}") {
-            eprintln!("[LogXide Error] RotatingFileHandler write failed: {e}");
-        } else {
-            self.current_size
-                .fetch_add(message_bytes, Ordering::Relaxed);
+        index_record(&w, record, &self.index);
+        match write_record(&mut w, line.as_bytes(), &self.checksums) {
+            Ok(written) => {
+                self.current_size.fetch_add(written, Ordering::Relaxed);
+            }
+            Err(e) => eprintln!("[LogXide Error] RotatingFileHandler write failed: {e}"),
         }
 
         // Level-based flush
@@ -545,8 +1118,14 @@ impl Handler for RotatingFileHandler {
         }
     }
 
+    fn level(&self) -> u32 {
+        self.level.load(Ordering::Relaxed)
+    }
+
     fn flush(&self) {
-        let _ = self.writer.lock().flush();
+        let mut w = self.writer.lock();
+        end_segment(&mut w, &self.checksums);
+        let _ = w.flush();
     }
 
     fn dispatch_mode(&self) -> DispatchMode {
@@ -557,6 +1136,10 @@ impl Handler for RotatingFileHandler {
         self.dispatch_mode.store(mode as u8, Ordering::Relaxed);
     }
 
+    fn filters(&self) -> Option<&FilterSlot> {
+        Some(&self.filters)
+    }
+
     fn set_formatter(&mut self, formatter: Arc<dyn Formatter + Send + Sync>) {
         *self.formatter.lock() = formatter;
     }
@@ -565,835 +1148,4396 @@ impl Handler for RotatingFileHandler {
 }
 
 // ============================================================================
-// HTTPHandler — batch JSON to remote endpoint (already uses channel pattern)
+// TimedRotatingFileHandler — synchronous direct file write, rotated on a schedule
 // ============================================================================
 
-pub struct HTTPHandler {
-    sender: crossbeam_channel::Sender<LogRecord>,
-    drop_rx: crossbeam_channel::Receiver<LogRecord>,
-    flush_signal: crossbeam_channel::Sender<()>,
-    flush_done: crossbeam_channel::Receiver<()>,
-    level: AtomicU8,
-    flush_level: AtomicU8,
-    shutdown: Arc<AtomicBool>,
-    stopped: AtomicBool,
-    overflow: OverflowStrategy,
-    flush_timeout: Duration,
-    join_handle: Mutex<Option<JoinHandle<()>>>,
-    emitted: AtomicU64,
-    queue_dropped: AtomicU64,
-    sink_acknowledged: Arc<AtomicU64>,
-    delivery_failed: Arc<AtomicU64>,
+/// What a `TimedRotatingFileHandler` interval is counted in (stdlib `when`).
+#[cfg(feature = "file")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotateWhen {
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    Midnight,
+    /// Midnight before the given weekday (0 = Monday).
+    Weekday(u32),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum OverflowStrategy {
-    DropOldest,
-    DropNewest,
-    Block,
+/// When a `TimedRotatingFileHandler` rolls over and how it names backups, following
+/// `logging.handlers.TimedRotatingFileHandler`.
+#[cfg(feature = "file")]
+#[derive(Debug, Clone, Copy)]
+pub struct RotationSchedule {
+    when: RotateWhen,
+    interval: u32,
+    utc: bool,
+    /// Seconds after midnight that `midnight` / `W0`-`W6` rotate at.
+    at_time: Option<u32>,
 }
 
-/// Default bound for the flush/shutdown handshake so callers never hang unboundedly.
-const DEFAULT_FLUSH_TIMEOUT: Duration = Duration::from_secs(30);
+#[cfg(feature = "file")]
+impl RotationSchedule {
+    /// Parse stdlib `when` ("S", "M", "H", "D", "midnight", "W0"-"W6", any case).
+    pub fn new(when: &str, interval: u32, utc: bool, at_time: Option<u32>) -> Result<Self, String> {
+        let when = match when.to_ascii_uppercase().as_str() {
+            "S" => RotateWhen::Seconds,
+            "M" => RotateWhen::Minutes,
+            "H" => RotateWhen::Hours,
+            "D" => RotateWhen::Days,
+            "MIDNIGHT" => RotateWhen::Midnight,
+            w => match w.strip_prefix('W').and_then(|d| d.parse::<u32>().ok()) {
+                Some(day) if day < 7 && w.len() == 2 => RotateWhen::Weekday(day),
+                _ => {
+                    return Err(format!(
+                        "invalid rollover interval specified: {when} \
+                         (expected S, M, H, D, midnight or W0-W6)"
+                    ))
+                }
+            },
+        };
+        if interval == 0 {
+            return Err("interval must be at least 1".to_string());
+        }
+        if at_time.is_some_and(|secs| secs >= 86_400) {
+            return Err("atTime must be within one day".to_string());
+        }
+        Ok(Self {
+            when,
+            interval,
+            utc,
+            at_time,
+        })
+    }
 
-impl OverflowStrategy {
-    pub fn from_overflow_str(s: &str) -> Self {
-        match s.to_ascii_lowercase().replace(['-', '_'], "").as_str() {
-            "dropoldest" => OverflowStrategy::DropOldest,
-            "dropnewest" => OverflowStrategy::DropNewest,
-            _ => OverflowStrategy::Block,
+    /// strftime suffix of backup names, as the stdlib uses it.
+    fn suffix(&self) -> &'static str {
+        match self.when {
+            RotateWhen::Seconds => "%Y-%m-%d_%H-%M-%S",
+            RotateWhen::Minutes => "%Y-%m-%d_%H-%M",
+            RotateWhen::Hours => "%Y-%m-%d_%H",
+            RotateWhen::Days | RotateWhen::Midnight | RotateWhen::Weekday(_) => "%Y-%m-%d",
         }
     }
-}
 
-pub struct HTTPHandlerConfig {
-    pub url: String,
-    pub headers: HashMap<String, String>,
-    pub global_context: HashMap<String, Value>,
-    pub transform_callback: Option<Py<PyAny>>,
-    pub context_provider: Option<Py<PyAny>>,
-    pub error_callback: Option<Py<PyAny>>,
-    pub overflow: OverflowStrategy,
-}
+    /// Length of one rollover period in seconds.
+    fn period(&self) -> i64 {
+        let unit = match self.when {
+            RotateWhen::Seconds => 1,
+            RotateWhen::Minutes => 60,
+            RotateWhen::Hours => 3_600,
+            RotateWhen::Days | RotateWhen::Midnight => 86_400,
+            RotateWhen::Weekday(_) => 7 * 86_400,
+        };
+        unit * i64::from(self.interval)
+    }
 
-impl HTTPHandler {
-    pub fn new(
-        url: String,
-        headers: HashMap<String, String>,
-        capacity: usize,
-        batch_size: usize,
-        flush_interval: u64,
-        overflow: OverflowStrategy,
+    /// First rollover after `current` (unix seconds).
+    fn next_rollover(&self, current: i64) -> i64 {
+        let day = match self.when {
+            RotateWhen::Midnight => None,
+            RotateWhen::Weekday(day) => Some(day),
+            _ => return current + self.period(),
+        };
+        let next = if self.utc {
+            self.next_boundary(&chrono::Utc, current, day)
+        } else {
+            self.next_boundary(&chrono::Local, current, day)
+        };
+        next + self.period() - 86_400 * if day.is_some() { 7 } else { 1 }
+    }
+
+    /// The next `at_time` (default midnight) after `current`, on `weekday` if given.
+    fn next_boundary<Tz: chrono::TimeZone>(
+        &self,
+        tz: &Tz,
+        current: i64,
+        weekday: Option<u32>,
+    ) -> i64 {
+        use chrono::Datelike;
+        let now = tz
+            .timestamp_opt(current, 0)
+            .earliest()
+            .unwrap_or_else(|| tz.timestamp_opt(0, 0).unwrap());
+        let at = self.at_time.unwrap_or(0);
+        let at = chrono::NaiveTime::from_num_seconds_from_midnight_opt(at, 0).unwrap_or_default();
+        let mut date = now.date_naive();
+        loop {
+            let candidate = date.and_time(at);
+            let ts = tz
+                .from_local_datetime(&candidate)
+                .earliest()
+                .map(|dt| dt.timestamp())
+                // A skipped local time (DST gap): rotate an hour later.
+                .unwrap_or_else(|| candidate.and_utc().timestamp() + 3_600);
+            let on_day = weekday.is_none_or(|d| date.weekday().num_days_from_monday() == d);
+            if ts > current && on_day {
+                return ts;
+            }
+            date = date.succ_opt().unwrap_or(date);
+        }
+    }
+
+    /// Backup name suffix for the period that started at `start`.
+    fn backup_suffix(&self, start: i64) -> String {
+        let start = chrono::DateTime::from_timestamp(start, 0).unwrap_or_default();
+        if self.utc {
+            start.format(self.suffix()).to_string()
+        } else {
+            start
+                .with_timezone(&chrono::Local)
+                .format(self.suffix())
+                .to_string()
+        }
+    }
+
+    /// Whether `suffix` (the part after `<filename>.`) names one of our backups,
+    /// compressed or not.
+    fn is_backup_suffix(&self, suffix: &str) -> bool {
+        let suffix = suffix.strip_suffix(".gz").unwrap_or(suffix);
+        let pattern = self.suffix();
+        let mut chars = suffix.chars();
+        let mut spec = pattern.chars();
+        while let Some(p) = spec.next() {
+            let digits = match p {
+                '%' => match spec.next() {
+                    Some('Y') => 4,
+                    _ => 2,
+                },
+                literal => {
+                    if chars.next() != Some(literal) {
+                        return false;
+                    }
+                    continue;
+                }
+            };
+            for _ in 0..digits {
+                if !chars.next().is_some_and(|c| c.is_ascii_digit()) {
+                    return false;
+                }
+            }
+        }
+        chars.next().is_none()
+    }
+}
+
+#[cfg(feature = "file")]
+pub struct TimedRotatingFileHandler {
+    writer: Arc<parking_lot::Mutex<BufWriter<File>>>,
+    /// Flushes the buffer before a fork.
+    _fork_hook: Arc<ForkHook>,
+    filename: PathBuf,
+    schedule: RotationSchedule,
+    backup_count: u32,
+    retention: Retention,
+    compress: bool,
+    checksums: Checksums,
+    index: SidecarIndex,
+    /// Unix second at which the next record rolls the file over.
+    rollover_at: std::sync::atomic::AtomicI64,
+    level: AtomicU32,
+    flush_level: AtomicU32,
+    dispatch_mode: AtomicU8,
+    filters: FilterSlot,
+    formatter: parking_lot::Mutex<Arc<dyn Formatter + Send + Sync>>,
+    sanitize: SanitizeSlot,
+}
+
+#[cfg(feature = "file")]
+impl TimedRotatingFileHandler {
+    /// Open `filename` for append. The first rollover is one period after the file
+    /// was last modified, so a restarted process still rotates a stale file.
+    pub fn new<P: AsRef<Path>>(
+        filename: P,
+        schedule: RotationSchedule,
+        backup_count: u32,
+        compress: bool,
+    ) -> std::io::Result<Self> {
+        let path = filename.as_ref().to_path_buf();
+        let now = chrono::Utc::now().timestamp();
+        let modified = std::fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(now, |d| d.as_secs() as i64);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        let writer = Arc::new(parking_lot::Mutex::new(BufWriter::new(file)));
+        Ok(Self {
+            _fork_hook: flush_before_fork(&writer),
+            writer,
+            filename: path,
+            schedule,
+            backup_count,
+            retention: Retention::default(),
+            compress,
+            checksums: None,
+            index: None,
+            rollover_at: std::sync::atomic::AtomicI64::new(schedule.next_rollover(modified)),
+            level: AtomicU32::new(LogLevel::NotSet.value()),
+            flush_level: AtomicU32::new(crate::preset::current().flush_level.value()),
+            dispatch_mode: AtomicU8::new(DispatchMode::Native as u8),
+            filters: FilterSlot::default(),
+            formatter: parking_lot::Mutex::new(default_formatter()),
+            sanitize: SanitizeSlot::default(),
+        })
+    }
+
+    /// Also delete backups past the `retention` limits after each rollover.
+    pub fn with_retention(mut self, retention: Retention) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Follow every `every` records with a checksum trailer (see [`crate::checksum`]).
+    /// Each backup ends with one.
+    pub fn with_checksums(mut self, every: u32) -> Self {
+        self.checksums = Some(parking_lot::Mutex::new(crate::checksum::Segment::new(
+            every,
+        )));
+        self
+    }
+
+    /// Add every `every`th record to a sidecar index (see [`crate::index`]), which
+    /// moves with the file to its backup unless the backup is gzipped.
+    pub fn with_index(mut self, every: u32) -> Self {
+        self.index = Some(parking_lot::Mutex::new(crate::index::Index::new(
+            &self.filename,
+            every,
+        )));
+        self
+    }
+
+    pub fn set_level(&self, level: LogLevel) {
+        self.level.store(level.value(), Ordering::Relaxed);
+    }
+
+    /// Set the flush level. Records at or above this level trigger immediate flush.
+    pub fn set_flush_level(&self, level: LogLevel) {
+        self.flush_level.store(level.value(), Ordering::Relaxed);
+    }
+
+    /// Get the current flush level.
+    pub fn get_flush_level(&self) -> u32 {
+        self.flush_level.load(Ordering::Relaxed)
+    }
+
+    /// Set a formatter for this handler.
+    pub fn set_formatter_instance(&self, formatter: Arc<dyn Formatter + Send + Sync>) {
+        *self.formatter.lock() = formatter;
+    }
+
+    /// Escape or strip control characters in the formatted text.
+    pub fn set_sanitize(&self, policy: SanitizePolicy) {
+        self.sanitize.set(policy);
+    }
+
+    /// Set an error callback for this handler.
+    pub fn set_error_callback(&self, _callback: Option<Arc<dyn Fn(String) + Send + Sync>>) {}
+
+    /// Unix second of the next rollover.
+    pub fn rollover_at(&self) -> i64 {
+        self.rollover_at.load(Ordering::Relaxed)
+    }
+
+    /// Format a record using the configured formatter, or return the raw message.
+    fn format_record(&self, record: &LogRecord) -> String {
+        self.sanitize.apply(self.formatter.lock().format(record))
+    }
+
+    /// Rename the file to `<filename>.<suffix of the period that just ended>`, start
+    /// a new one, then compress and prune backups.
+    fn do_rollover(&self, writer: &mut BufWriter<File>, now: i64) {
+        let _ = writer.flush();
+        let rollover_at = self.rollover_at.load(Ordering::Relaxed);
+        let mut backup = self.filename.clone().into_os_string();
+        backup.push(".");
+        backup.push(
+            self.schedule
+                .backup_suffix(rollover_at - self.schedule.period()),
+        );
+        let backup = PathBuf::from(backup);
+        let pending = pending_backup(&self.filename);
+        let rotated = match move_aside(&self.filename, &pending) {
+            Ok(()) => true,
+            Err(e) => {
+                // Keep appending to the current file until the next rollover.
+                eprintln!(
+                    "[LogXide Error] TimedRotatingFileHandler: cannot rotate {}: {e}",
+                    self.filename.display()
+                );
+                let _ = std::fs::remove_file(&pending);
+                false
+            }
+        };
+
+        if rotated {
+            let _ = std::fs::remove_file(&backup);
+            crate::index::remove(&backup);
+            if let Err(e) = std::fs::rename(&pending, &backup) {
+                eprintln!(
+                    "[LogXide Error] TimedRotatingFileHandler: cannot rename {} to {}: {e}",
+                    pending.display(),
+                    backup.display()
+                );
+            }
+            // A gzipped backup can't be seeked, so it keeps no index.
+            if self.compress {
+                crate::index::remove(&self.filename);
+            } else {
+                crate::index::rename(&self.filename, &backup);
+            }
+            match OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(&self.filename)
+            {
+                Ok(f) => *writer = BufWriter::new(f),
+                Err(e) => {
+                    eprintln!(
+                        "[LogXide Error] TimedRotatingFileHandler: failed to create new file: {e}"
+                    );
+                }
+            }
+        }
+
+        let mut next = self.schedule.next_rollover(now);
+        while next <= now {
+            next += self.schedule.period();
+        }
+        self.rollover_at.store(next, Ordering::Relaxed);
+
+        let filename = self.filename.clone();
+        let schedule = self.schedule;
+        let backup_count = self.backup_count;
+        let retention = self.retention;
+        if rotated && self.compress {
+            // Compressing a large file would stall every logging thread on the lock.
+            let spawned = crate::threads::spawn("gzip", move || {
+                if let Err(e) = crate::platform::gzip_file(&backup) {
+                    eprintln!(
+                        "[LogXide Error] TimedRotatingFileHandler: failed to compress {}: {e}",
+                        backup.display()
+                    );
+                }
+                Self::prune(&filename, &schedule, backup_count, retention);
+            });
+            if let Err(e) = spawned {
+                eprintln!("[LogXide Error] TimedRotatingFileHandler: {e}");
+            }
+        } else {
+            Self::prune(&filename, &schedule, backup_count, retention);
+        }
+    }
+
+    /// Delete the oldest backups beyond `backup_count` (0 keeps them all), then those
+    /// past the `retention` limits.
+    fn prune(path: &Path, schedule: &RotationSchedule, backup_count: u32, retention: Retention) {
+        let Some(name) = path.file_name() else {
+            return;
+        };
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        // Compared as bytes: the name need not be UTF-8, the date suffix always is.
+        let mut prefix = name.as_encoded_bytes().to_vec();
+        prefix.push(b'.');
+        let mut backups: Vec<std::ffi::OsString> = entries
+            .filter_map(|entry| Some(entry.ok()?.file_name()))
+            .filter(|file| {
+                file.as_encoded_bytes()
+                    .strip_prefix(prefix.as_slice())
+                    .and_then(|suffix| std::str::from_utf8(suffix).ok())
+                    .is_some_and(|suffix| schedule.is_backup_suffix(suffix))
+            })
+            .collect();
+        // The date suffixes sort chronologically.
+        backups.sort();
+        let excess = match backup_count {
+            0 => 0,
+            n => backups.len().saturating_sub(n as usize),
+        };
+        for old in &backups[..excess] {
+            let _ = std::fs::remove_file(dir.join(old));
+            crate::index::remove(&dir.join(old));
+        }
+        let kept: Vec<PathBuf> = backups[excess..].iter().map(|b| dir.join(b)).collect();
+        retention.apply(path, &kept);
+    }
+}
+
+#[cfg(feature = "file")]
+impl Handler for TimedRotatingFileHandler {
+    fn emit(&self, record: &Arc<LogRecord>) {
+        let level = self.level.load(Ordering::Relaxed);
+        if record.levelno < level as i32 {
+            return;
+        }
+        let Some(record) = self.filters.apply(record) else {
+            return;
+        };
+        let record = &*record;
+
+        let mut line = self.format_record(record);
+        line.push('\n');
+        let mut w = self.writer.lock();
+
+        let now = chrono::Utc::now().timestamp();
+        if now >= self.rollover_at.load(Ordering::Relaxed) {
+            end_segment(&mut w, &self.checksums);
+            self.do_rollover(&mut w, now);
+            restart_index(&self.index);
+        }
+
+        index_record(&w, record, &self.index);
+        if let Err(e) = write_record(&mut w, line.as_bytes(), &self.checksums) {
+            eprintln!("[LogXide Error] TimedRotatingFileHandler write failed: {e}");
+        }
+
+        // Level-based flush
+        let flush_level = self.flush_level.load(Ordering::Relaxed);
+        if record.levelno >= flush_level as i32 {
+            let _ = w.flush();
+        }
+    }
+
+    fn level(&self) -> u32 {
+        self.level.load(Ordering::Relaxed)
+    }
+
+    fn flush(&self) {
+        let mut w = self.writer.lock();
+        end_segment(&mut w, &self.checksums);
+        let _ = w.flush();
+    }
+
+    fn dispatch_mode(&self) -> DispatchMode {
+        DispatchMode::from_u8(self.dispatch_mode.load(Ordering::Relaxed))
+    }
+
+    fn set_dispatch_mode(&self, mode: DispatchMode) {
+        self.dispatch_mode.store(mode as u8, Ordering::Relaxed);
+    }
+
+    fn filters(&self) -> Option<&FilterSlot> {
+        Some(&self.filters)
+    }
+
+    fn set_formatter(&mut self, formatter: Arc<dyn Formatter + Send + Sync>) {
+        *self.formatter.lock() = formatter;
+    }
+
+    fn add_filter(&mut self, _: Arc<dyn Filter + Send + Sync>) {}
+}
+
+// ============================================================================
+// LevelRoutedFileHandler — one file per level band
+// ============================================================================
+
+/// The file a `LevelRoutedFileHandler` writes one level band to.
+#[cfg(feature = "file")]
+pub enum RoutedFile {
+    Plain(Arc<FileHandler>),
+    Rotating(Arc<RotatingFileHandler>),
+    Timed(Arc<TimedRotatingFileHandler>),
+}
+
+#[cfg(feature = "file")]
+impl RoutedFile {
+    fn handler(&self) -> &dyn Handler {
+        match self {
+            Self::Plain(h) => h.as_ref(),
+            Self::Rotating(h) => h.as_ref(),
+            Self::Timed(h) => h.as_ref(),
+        }
+    }
+
+    fn set_level(&self, level: LogLevel) {
+        match self {
+            Self::Plain(h) => h.set_level(level),
+            Self::Rotating(h) => h.set_level(level),
+            Self::Timed(h) => h.set_level(level),
+        }
+    }
+
+    fn set_flush_level(&self, level: LogLevel) {
+        match self {
+            Self::Plain(h) => h.set_flush_level(level),
+            Self::Rotating(h) => h.set_flush_level(level),
+            Self::Timed(h) => h.set_flush_level(level),
+        }
+    }
+
+    fn set_formatter_instance(&self, formatter: Arc<dyn Formatter + Send + Sync>) {
+        match self {
+            Self::Plain(h) => h.set_formatter_instance(formatter),
+            Self::Rotating(h) => h.set_formatter_instance(formatter),
+            Self::Timed(h) => h.set_formatter_instance(formatter),
+        }
+    }
+
+    fn set_sanitize(&self, policy: SanitizePolicy) {
+        match self {
+            Self::Plain(h) => h.set_sanitize(policy),
+            Self::Rotating(h) => h.set_sanitize(policy),
+            Self::Timed(h) => h.set_sanitize(policy),
+        }
+    }
+}
+
+/// Writes each record to the file of its level band: the file registered for the
+/// highest level at or below the record's. Records below every band are dropped. Each
+/// band keeps its own rotation; the formatter and flush level are shared.
+#[cfg(feature = "file")]
+pub struct LevelRoutedFileHandler {
+    /// (lowest levelno of the band, its file), highest band first.
+    routes: Vec<(u32, RoutedFile)>,
+    level: AtomicU32,
+    flush_level: AtomicU32,
+    dispatch_mode: AtomicU8,
+    filters: FilterSlot,
+}
+
+#[cfg(feature = "file")]
+impl LevelRoutedFileHandler {
+    pub fn new(mut routes: Vec<(u32, RoutedFile)>) -> Self {
+        routes.sort_by(|a, b| b.0.cmp(&a.0));
+        let handler = Self {
+            routes,
+            level: AtomicU32::new(LogLevel::NotSet.value()),
+            flush_level: AtomicU32::new(crate::preset::current().flush_level.value()),
+            dispatch_mode: AtomicU8::new(DispatchMode::Native as u8),
+            filters: FilterSlot::default(),
+        };
+        // The band decides which file gets a record; the files themselves take all.
+        for (_, file) in &handler.routes {
+            file.set_level(LogLevel::NotSet);
+            file.set_flush_level(crate::preset::current().flush_level);
+        }
+        handler
+    }
+
+    pub fn set_level(&self, level: LogLevel) {
+        self.level.store(level.value(), Ordering::Relaxed);
+    }
+
+    /// Set the flush level of every file. Default is ERROR (40).
+    pub fn set_flush_level(&self, level: LogLevel) {
+        self.flush_level.store(level.value(), Ordering::Relaxed);
+        for (_, file) in &self.routes {
+            file.set_flush_level(level);
+        }
+    }
+
+    pub fn get_flush_level(&self) -> u32 {
+        self.flush_level.load(Ordering::Relaxed)
+    }
+
+    /// Set the formatter of every file.
+    pub fn set_formatter_instance(&self, formatter: Arc<dyn Formatter + Send + Sync>) {
+        for (_, file) in &self.routes {
+            file.set_formatter_instance(formatter.clone());
+        }
+    }
+
+    /// Set the sanitization policy of every file.
+    pub fn set_sanitize(&self, policy: SanitizePolicy) {
+        for (_, file) in &self.routes {
+            file.set_sanitize(policy);
+        }
+    }
+
+    fn route(&self, levelno: i32) -> Option<&RoutedFile> {
+        self.routes
+            .iter()
+            .find(|(band, _)| levelno >= *band as i32)
+            .map(|(_, file)| file)
+    }
+}
+
+#[cfg(feature = "file")]
+impl Handler for LevelRoutedFileHandler {
+    fn emit(&self, record: &Arc<LogRecord>) {
+        if record.levelno < self.level.load(Ordering::Relaxed) as i32 {
+            return;
+        }
+        let Some(record) = self.filters.apply_shared(record) else {
+            return;
+        };
+        if let Some(file) = self.route(record.levelno) {
+            file.handler().emit(&record);
+        }
+    }
+
+    fn level(&self) -> u32 {
+        let own = self.level.load(Ordering::Relaxed);
+        let lowest_band = self.routes.last().map_or(0, |(band, _)| *band);
+        own.max(lowest_band)
+    }
+
+    fn flush(&self) {
+        for (_, file) in &self.routes {
+            file.handler().flush();
+        }
+    }
+
+    fn dispatch_mode(&self) -> DispatchMode {
+        DispatchMode::from_u8(self.dispatch_mode.load(Ordering::Relaxed))
+    }
+
+    fn set_dispatch_mode(&self, mode: DispatchMode) {
+        self.dispatch_mode.store(mode as u8, Ordering::Relaxed);
+    }
+
+    fn filters(&self) -> Option<&FilterSlot> {
+        Some(&self.filters)
+    }
+
+    fn set_formatter(&mut self, formatter: Arc<dyn Formatter + Send + Sync>) {
+        self.set_formatter_instance(formatter);
+    }
+
+    fn add_filter(&mut self, _: Arc<dyn Filter + Send + Sync>) {}
+}
+
+/// A hook that starts a queue worker again in a forked child. What the parent had
+/// queued is dropped (the parent still delivers it) and pending flush handshakes are
+/// cleared before `spawn` runs.
+#[cfg(any(feature = "network", feature = "pipe"))]
+fn restart_worker_after_fork(
+    worker: &Arc<WorkerHandle>,
+    queue: &Arc<QueueStats>,
+    rx: LaneReceiver<Vec<Arc<LogRecord>>>,
+    flush_rx: crossbeam_channel::Receiver<()>,
+    done_rx: crossbeam_channel::Receiver<()>,
+    spawn: impl Fn() -> std::thread::JoinHandle<()> + Send + Sync + 'static,
+) -> Arc<ForkHook> {
+    let worker = worker.clone();
+    let queue = queue.clone();
+    ForkHook::default()
+        .with_in_child(move || {
+            queue.reset_after_fork(&rx);
+            while flush_rx.try_recv().is_ok() || done_rx.try_recv().is_ok() {}
+            worker.restart_after_fork(&spawn);
+        })
+        .register()
+}
+
+// ============================================================================
+// HTTPHandler — batch JSON to remote endpoint (already uses channel pattern)
+// ============================================================================
+
+#[cfg(feature = "network")]
+pub struct HTTPHandler {
+    lanes: LaneSender<Vec<Arc<LogRecord>>>,
+    flush_signal: crossbeam_channel::Sender<()>,
+    flush_done: crossbeam_channel::Receiver<()>,
+    level: AtomicU32,
+    flush_level: AtomicU32,
+    overflow: OverflowStrategy,
+    flush_timeout: Duration,
+    worker: Arc<WorkerHandle>,
+    /// Restarts the worker thread in a forked child.
+    _fork_hook: Arc<ForkHook>,
+    emitted: AtomicU64,
+    queue: Arc<QueueStats>,
+    sink_acknowledged: Arc<AtomicU64>,
+    delivery_failed: Arc<AtomicU64>,
+    sync: SyncLevel,
+    send_batch: BatchSend,
+    backoff: Arc<Backoff>,
+    encoding: Arc<BodyEncoding>,
+    context: Arc<RecordContext>,
+    dispatch_mode: AtomicU8,
+    filters: FilterSlot,
+}
+
+/// What a queued handler does with a record when its queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowStrategy {
+    /// Evict the oldest queued record to make room.
+    DropOldest,
+    /// Drop the new record.
+    DropNewest,
+    /// Wait for room. With a timeout, the record is dropped once it expires; without
+    /// one, the wait is only bounded on the GIL-holding path (see `queue::enqueue`).
+    Block(Option<Duration>),
+}
+
+/// Default bound for the flush/shutdown handshake so callers never hang unboundedly.
+const DEFAULT_FLUSH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// `SyncLevel` value meaning no record is written synchronously.
+const SYNC_OFF: u32 = u32::MAX;
+
+/// Per-handler threshold at or above which records skip the queue: they are written
+/// and flushed by the caller before `emit()` returns. Off by default.
+pub struct SyncLevel {
+    level: AtomicU32,
+    emitted: AtomicU64,
+}
+
+impl SyncLevel {
+    fn new(level: Option<LogLevel>) -> Self {
+        Self {
+            level: AtomicU32::new(level.map_or(SYNC_OFF, |l| l.value())),
+            emitted: AtomicU64::new(0),
+        }
+    }
+
+    pub fn set(&self, level: Option<LogLevel>) {
+        self.level
+            .store(level.map_or(SYNC_OFF, |l| l.value()), Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> Option<u32> {
+        let level = self.level.load(Ordering::Relaxed);
+        (level != SYNC_OFF).then_some(level)
+    }
+
+    /// Records written synchronously so far.
+    pub fn emitted(&self) -> u64 {
+        self.emitted.load(Ordering::Relaxed)
+    }
+
+    /// Whether `record` must bypass the queue; counts it if so.
+    fn applies(&self, record: &LogRecord) -> bool {
+        let level = self.level.load(Ordering::Relaxed);
+        if level == SYNC_OFF || record.levelno < level as i32 {
+            return false;
+        }
+        self.emitted.fetch_add(1, Ordering::Relaxed);
+        true
+    }
+}
+
+/// Sends one batch to a network sink; shared by the worker and synchronous emits.
+#[cfg(feature = "network")]
+type BatchSend = Arc<dyn Fn(&mut Vec<Arc<LogRecord>>) + Send + Sync>;
+
+/// Request-body codec, sent as `Content-Encoding`. Needs the `compression` feature.
+#[cfg(feature = "network")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+}
+
+#[cfg(feature = "network")]
+impl Compression {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "gzip" => Some(Compression::Gzip),
+            "zstd" => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+
+    pub const fn content_encoding(self) -> &'static str {
+        match self {
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+        }
+    }
+}
+
+/// Stands in for a batch id while a batch is measured; every UUID is this long.
+#[cfg(feature = "network")]
+const UUID_PLACEHOLDER: &str = "00000000-0000-0000-0000-000000000000";
+
+/// A random (version 4) UUID. The bits come from std's randomly keyed hasher,
+/// which is plenty for idempotency keys and run ids and saves an RNG dependency.
+pub(crate) fn random_uuid() -> String {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let word = || {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+        hasher.finish()
+    };
+    let mut bytes = (u128::from(word()) << 64 | u128::from(word())).to_be_bytes();
+    bytes[6] = bytes[6] & 0x0f | 0x40;
+    bytes[8] = bytes[8] & 0x3f | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// Add a JSON record's idempotency fields: its request's `batch_id` and, the first
+/// time, its `sequence`.
+#[cfg(feature = "network")]
+fn stamp_json(record: &mut Value, batch_id: &str, sequence: Option<u64>) {
+    if let Value::Object(obj) = record {
+        obj.insert("batch_id".to_string(), batch_id.into());
+        if let Some(sequence) = sequence {
+            obj.insert("sequence".to_string(), sequence.into());
+        }
+    }
+}
+
+/// What a network handler does to record timestamps when a batch is built.
+#[cfg(feature = "network")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimestampPolicy {
+    /// Ship the send time alongside each record's `created` time.
+    pub send_time: bool,
+    /// Records older than this at send time go out with their timestamp moved up to
+    /// the limit and the original kept alongside, for backends with an ingestion window.
+    pub max_backdate: Option<Duration>,
+}
+
+/// Current time in Unix seconds, as in `LogRecord::created`.
+#[cfg(feature = "network")]
+fn unix_now() -> f64 {
+    let now = chrono::Utc::now();
+    now.timestamp() as f64 + now.timestamp_subsec_nanos() as f64 / 1_000_000_000.0
+}
+
+/// One request body of a batch, with the number of records it carries.
+#[cfg(feature = "network")]
+enum BatchBody {
+    Ready {
+        body: Vec<u8>,
+        records: usize,
+        /// Sent as `Idempotency-Key`; the same on every retry of this body.
+        key: Option<String>,
+    },
+    /// Over `max_bytes` even as small as it can be cut; never sent.
+    Oversized { bytes: usize, records: usize },
+}
+
+/// How one network handler builds request bodies: the byte limit batches are split
+/// to, idempotency keys, compression, and byte accounting. Bodies under `min_bytes`
+/// are sent uncompressed, and so is any body the codec fails on.
+#[cfg(feature = "network")]
+pub struct BodyEncoding {
+    codec: Option<Compression>,
+    min_bytes: usize,
+    max_bytes: Option<usize>,
+    idempotent: bool,
+    /// Last sequence id handed to a record.
+    sequence: AtomicU64,
+    timestamps: TimestampPolicy,
+    capped: AtomicU64,
+    payload_bytes: AtomicU64,
+    wire_bytes: AtomicU64,
+    oversized: AtomicU64,
+}
+
+#[cfg(feature = "network")]
+impl BodyEncoding {
+    pub fn new(
+        codec: Option<Compression>,
+        min_bytes: usize,
+        max_bytes: Option<usize>,
+        idempotent: bool,
+        timestamps: TimestampPolicy,
+    ) -> Self {
+        Self {
+            codec,
+            min_bytes,
+            max_bytes,
+            idempotent,
+            sequence: AtomicU64::new(0),
+            timestamps,
+            capped: AtomicU64::new(0),
+            payload_bytes: AtomicU64::new(0),
+            wire_bytes: AtomicU64::new(0),
+            oversized: AtomicU64::new(0),
+        }
+    }
+
+    /// Cut a batch into runs of records whose body stays within `max_bytes`.
+    /// `costs` are the encoded size of each record and `overhead` the bytes the body
+    /// adds around them. A record that can't fit even alone gets a run of its own.
+    fn chunk_ranges(&self, costs: &[usize], overhead: usize) -> Vec<Range<usize>> {
+        let max = self.max_bytes.unwrap_or(usize::MAX);
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        let mut size = overhead;
+        for (i, &cost) in costs.iter().enumerate() {
+            if i > start && size.saturating_add(cost) > max {
+                ranges.push(start..i);
+                start = i;
+                size = overhead;
+            }
+            size = size.saturating_add(cost);
+        }
+        if start < costs.len() {
+            ranges.push(start..costs.len());
+        }
+        ranges
+    }
+
+    /// `body` as a request, unless it is over `max_bytes`.
+    fn checked(&self, body: Vec<u8>, records: usize, key: Option<String>) -> BatchBody {
+        match self.max_bytes {
+            Some(max) if body.len() > max => BatchBody::Oversized {
+                bytes: body.len(),
+                records,
+            },
+            _ => BatchBody::Ready { body, records, key },
+        }
+    }
+
+    /// A fresh batch id for the next request body, when idempotency keys are on.
+    fn batch_key(&self) -> Option<String> {
+        self.idempotent.then(random_uuid)
+    }
+
+    /// Sequence id for the next record; unique and increasing per handler.
+    fn next_sequence(&self) -> u64 {
+        self.sequence.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Whether records carry their send time.
+    pub fn send_time(&self) -> bool {
+        self.timestamps.send_time
+    }
+
+    /// The timestamp to ship for a record created at `created` and sent at `now`,
+    /// when it is older than `max_backdate` allows.
+    fn cap_backdate(&self, created: f64, now: f64) -> Option<f64> {
+        let oldest = now - self.timestamps.max_backdate?.as_secs_f64();
+        if created >= oldest {
+            return None;
+        }
+        self.capped.fetch_add(1, Ordering::Relaxed);
+        Some(oldest)
+    }
+
+    /// Apply the timestamp policy to a JSON record sent at `now`.
+    fn stamp_times(&self, record: &mut Value, now: f64) {
+        let Value::Object(obj) = record else {
+            return;
+        };
+        if self.timestamps.send_time {
+            obj.insert("sent_at".to_string(), now.into());
+        }
+        let Some(created) = obj.get("created").and_then(Value::as_f64) else {
+            return;
+        };
+        if let Some(capped) = self.cap_backdate(created, now) {
+            obj.insert("original_created".to_string(), created.into());
+            obj.insert("created".to_string(), capped.into());
+            obj.insert("timestamp_capped".to_string(), true.into());
+        }
+    }
+
+    /// Records shipped with their timestamp moved up to `max_backdate`.
+    pub fn capped(&self) -> u64 {
+        self.capped.load(Ordering::Relaxed)
+    }
+
+    /// Whether requests carry idempotency keys.
+    pub fn is_idempotent(&self) -> bool {
+        self.idempotent
+    }
+
+    /// Request bodies for a JSON batch, split between array elements. A payload that
+    /// isn't one element per record (reshaped by a transform_callback) goes out whole
+    /// and only carries its key in the header.
+    fn json_bodies(&self, mut payload: Value, records: usize) -> Vec<BatchBody> {
+        let items = match &mut payload {
+            Value::Array(items)
+                if items.len() == records && (self.max_bytes.is_some() || self.idempotent) =>
+            {
+                items
+            }
+            _ => {
+                let body = serde_json::to_vec(&payload).unwrap_or_default();
+                return vec![self.checked(body, records, self.batch_key())];
+            }
+        };
+        if self.idempotent {
+            for item in items.iter_mut() {
+                stamp_json(item, UUID_PLACEHOLDER, Some(self.next_sequence()));
+            }
+        }
+        let mut encoded: Vec<Vec<u8>> = items
+            .iter()
+            .map(|item| serde_json::to_vec(item).unwrap_or_default())
+            .collect();
+        // "[" then each element followed by "," or "]".
+        let costs: Vec<usize> = encoded.iter().map(|item| item.len() + 1).collect();
+        let mut bodies = Vec::new();
+        for range in self.chunk_ranges(&costs, 1) {
+            let key = self.batch_key();
+            if let Some(key) = &key {
+                // Same length as the placeholder, so the body keeps the size it was cut to.
+                for i in range.clone() {
+                    stamp_json(&mut items[i], key, None);
+                    encoded[i] = serde_json::to_vec(&items[i]).unwrap_or_default();
+                }
+            }
+            let mut body = Vec::with_capacity(1 + costs[range.clone()].iter().sum::<usize>());
+            body.push(b'[');
+            for (i, item) in encoded[range.clone()].iter().enumerate() {
+                if i > 0 {
+                    body.push(b',');
+                }
+                body.extend_from_slice(item);
+            }
+            body.push(b']');
+            bodies.push(self.checked(body, range.len(), key));
+        }
+        bodies
+    }
+
+    /// POST each body, counting its records as acknowledged or failed.
+    fn post(
+        &self,
+        bodies: Vec<BatchBody>,
+        url: &str,
+        headers: &HashMap<String, String>,
+        content_type: &str,
+        transport: &Transport,
+        backoff: &Backoff,
+        error_callback: &Option<Py<PyAny>>,
+        sink_acknowledged: &AtomicU64,
+        delivery_failed: &AtomicU64,
+    ) {
+        for batch_body in bodies {
+            let (records, result) = match batch_body {
+                BatchBody::Ready { body, records, key } => {
+                    let headers = match key {
+                        Some(key) => {
+                            let mut headers = headers.clone();
+                            headers.insert("Idempotency-Key".to_string(), key);
+                            Cow::Owned(headers)
+                        }
+                        None => Cow::Borrowed(headers),
+                    };
+                    let (body, content_encoding) = self.encode(&body);
+                    let result = backoff.deliver(|| {
+                        transport.send(&Request {
+                            method: "POST",
+                            url,
+                            headers: &headers,
+                            content_type: Some(content_type),
+                            content_encoding,
+                            body: &body,
+                        })
+                    });
+                    (records, result)
+                }
+                BatchBody::Oversized { bytes, records } => {
+                    self.oversized.fetch_add(records as u64, Ordering::Relaxed);
+                    let max = self.max_bytes.unwrap_or_default();
+                    let error = format!(
+                        "{url}: {records} record(s) not sent: {bytes}-byte request exceeds max_batch_bytes ({max})"
+                    );
+                    (records, Err(error))
+                }
+            };
+            match result {
+                Ok(()) => {
+                    sink_acknowledged.fetch_add(records as u64, Ordering::Relaxed);
+                }
+                Err(e) => {
+                    delivery_failed.fetch_add(records as u64, Ordering::Relaxed);
+                    if let Some(ref cb) = error_callback {
+                        Python::attach(|py| {
+                            let _ = cb.call1(py, (e,));
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// The body to send and its `Content-Encoding` (None when sent uncompressed).
+    fn encode<'a>(&self, body: &'a [u8]) -> (Cow<'a, [u8]>, Option<&'static str>) {
+        let compressed = self
+            .codec
+            .filter(|_| body.len() >= self.min_bytes)
+            .and_then(|codec| {
+                let bytes = crate::platform::compress(codec, body).ok()?;
+                Some((Cow::Owned(bytes), Some(codec.content_encoding())))
+            });
+        let (wire, encoding) = compressed.unwrap_or((Cow::Borrowed(body), None));
+        self.payload_bytes
+            .fetch_add(body.len() as u64, Ordering::Relaxed);
+        self.wire_bytes
+            .fetch_add(wire.len() as u64, Ordering::Relaxed);
+        (wire, encoding)
+    }
+
+    /// Serialized batch bytes before compression.
+    pub fn payload_bytes(&self) -> u64 {
+        self.payload_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Request-body bytes put on the wire.
+    pub fn wire_bytes(&self) -> u64 {
+        self.wire_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Records not sent because they were over `max_bytes` on their own.
+    pub fn oversized(&self) -> u64 {
+        self.oversized.load(Ordering::Relaxed)
+    }
+}
+
+/// Throttled attempts retried before a batch counts as failed.
+#[cfg(feature = "network")]
+const THROTTLE_RETRIES: u32 = 3;
+
+/// Pause after a throttling response without a usable `Retry-After`; doubles with
+/// each retry of the same batch.
+#[cfg(feature = "network")]
+const THROTTLE_BASE: Duration = Duration::from_secs(1);
+
+/// Longest single pause, whatever the collector asks for.
+#[cfg(feature = "network")]
+const THROTTLE_MAX: Duration = Duration::from_secs(60);
+
+/// Collector-driven backoff shared by every sender of one network handler (the worker
+/// and `sync_level` callers). A 429/503 response pauses all of them for the
+/// `Retry-After` delay instead of letting the next batch hit the collector at once.
+#[cfg(feature = "network")]
+pub struct Backoff {
+    paused_until: parking_lot::Mutex<Option<Instant>>,
+    throttled: AtomicU64,
+}
+
+#[cfg(feature = "network")]
+impl Backoff {
+    fn new() -> Self {
+        Self {
+            paused_until: parking_lot::Mutex::new(None),
+            throttled: AtomicU64::new(0),
+        }
+    }
+
+    /// Run `attempt` once the current pause (if any) is over, retrying it after each
+    /// throttling response up to `THROTTLE_RETRIES` times. Other errors are returned
+    /// as they are.
+    fn deliver(
+        &self,
+        mut attempt: impl FnMut() -> Result<(), crate::platform::HttpError>,
+    ) -> Result<(), String> {
+        let mut retries = 0;
+        loop {
+            let until = *self.paused_until.lock();
+            if let Some(until) = until {
+                std::thread::sleep(until.saturating_duration_since(Instant::now()));
+            }
+            match attempt() {
+                Ok(()) => return Ok(()),
+                Err(e) if e.throttled => {
+                    self.throttled.fetch_add(1, Ordering::Relaxed);
+                    let pause = e
+                        .retry_after
+                        .unwrap_or(THROTTLE_BASE * 2u32.pow(retries))
+                        .min(THROTTLE_MAX);
+                    let until = Instant::now() + pause;
+                    let mut paused = self.paused_until.lock();
+                    *paused = Some(paused.map_or(until, |p| p.max(until)));
+                    if retries == THROTTLE_RETRIES {
+                        return Err(e.message);
+                    }
+                    retries += 1;
+                }
+                Err(e) => return Err(e.message),
+            }
+        }
+    }
+
+    /// Throttling responses received so far.
+    pub fn throttled(&self) -> u64 {
+        self.throttled.load(Ordering::Relaxed)
+    }
+
+    /// Time left before the next delivery attempt; zero when not paused.
+    pub fn remaining(&self) -> Duration {
+        self.paused_until.lock().map_or(Duration::ZERO, |until| {
+            until.saturating_duration_since(Instant::now())
+        })
+    }
+}
+
+/// Standard base64 (with padding).
+#[cfg(feature = "network")]
+pub fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// One HTTP request from a network handler.
+#[cfg(feature = "network")]
+struct Request<'a> {
+    method: &'static str,
+    url: &'a str,
+    headers: &'a HashMap<String, String>,
+    content_type: Option<&'a str>,
+    content_encoding: Option<&'a str>,
+    body: &'a [u8],
+}
+
+/// Where a network handler's requests go: the collector, or a dry run that records
+/// each request without sending it.
+#[cfg(feature = "network")]
+pub enum Transport {
+    Http,
+    /// Append each request to a file as one JSON line.
+    DryRunFile(parking_lot::Mutex<std::fs::File>),
+    /// Pass each request to a Python callable, whose return value stands in for the
+    /// response: None or a 2xx status succeeds, 429/503 throttle, anything else fails.
+    DryRunCallback(Py<PyAny>),
+}
+
+#[cfg(feature = "network")]
+impl Transport {
+    pub fn dry_run_file(path: &std::path::Path) -> std::io::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        Ok(Transport::DryRunFile(parking_lot::Mutex::new(file)))
+    }
+
+    fn send(&self, request: &Request) -> Result<(), crate::platform::HttpError> {
+        match self {
+            Transport::Http if request.method == "GET" => {
+                crate::platform::http_get(request.url, request.headers)
+            }
+            Transport::Http => crate::platform::http_post(
+                request.url,
+                request.headers,
+                request.content_type.unwrap_or_default(),
+                request.content_encoding,
+                request.body,
+            ),
+            Transport::DryRunFile(file) => {
+                use std::io::Write;
+                let line = dry_run_line(request).to_string();
+                writeln!(file.lock(), "{line}").map_err(|e| dry_run_error(e.to_string()))
+            }
+            Transport::DryRunCallback(cb) => Python::attach(|py| {
+                let dict = PyDict::new(py);
+                let headers = PyDict::new(py);
+                for (key, value) in request.headers {
+                    headers.set_item(key, value)?;
+                }
+                dict.set_item("method", request.method)?;
+                dict.set_item("url", request.url)?;
+                dict.set_item("headers", headers)?;
+                dict.set_item("content_type", request.content_type)?;
+                dict.set_item("content_encoding", request.content_encoding)?;
+                dict.set_item("body", pyo3::types::PyBytes::new(py, request.body))?;
+                let response = cb.call1(py, (dict,))?;
+                let (status, retry_after) = if response.is_none(py) {
+                    (200, None)
+                } else if let Ok(status) = response.extract::<u16>(py) {
+                    (status, None)
+                } else {
+                    response.extract::<(u16, Option<f64>)>(py)?
+                };
+                Ok(simulated_response(status, retry_after))
+            })
+            .unwrap_or_else(|e: PyErr| Err(dry_run_error(format!("dry_run callback failed: {e}")))),
+        }
+    }
+}
+
+/// A dry-run request as a JSON line. JSON bodies are embedded, other text bodies are
+/// strings, and compressed or binary bodies are base64. `Authorization` is redacted.
+#[cfg(feature = "network")]
+fn dry_run_line(request: &Request) -> Value {
+    let headers: serde_json::Map<String, Value> = request
+        .headers
+        .iter()
+        .map(|(key, value)| {
+            let value = if key.eq_ignore_ascii_case("authorization") {
+                "<redacted>"
+            } else {
+                value
+            };
+            (key.clone(), value.into())
+        })
+        .collect();
+    let mut line = serde_json::json!({
+        "method": request.method,
+        "url": request.url,
+        "headers": headers,
+        "content_type": request.content_type,
+        "content_encoding": request.content_encoding,
+    });
+    let text = match request.content_encoding {
+        None => std::str::from_utf8(request.body).ok(),
+        Some(_) => None,
+    };
+    line["body"] = match text {
+        Some(text) if request.content_type == Some("application/json") => {
+            serde_json::from_str(text).unwrap_or_else(|_| text.into())
+        }
+        Some(text) => text.into(),
+        None => {
+            line["body_base64"] = base64_encode(request.body).into();
+            Value::Null
+        }
+    };
+    line
+}
+
+/// The outcome a dry run reports for a simulated response status.
+#[cfg(feature = "network")]
+fn simulated_response(
+    status: u16,
+    retry_after: Option<f64>,
+) -> Result<(), crate::platform::HttpError> {
+    if (200..300).contains(&status) {
+        return Ok(());
+    }
+    Err(crate::platform::HttpError {
+        message: format!("dry run: simulated HTTP {status}"),
+        throttled: matches!(status, 429 | 503),
+        retry_after: retry_after
+            .filter(|secs| secs.is_finite() && *secs >= 0.0)
+            .map(Duration::from_secs_f64),
+    })
+}
+
+#[cfg(feature = "network")]
+fn dry_run_error(message: String) -> crate::platform::HttpError {
+    crate::platform::HttpError {
+        message,
+        throttled: false,
+        retry_after: None,
+    }
+}
+
+impl OverflowStrategy {
+    /// Parse the Python-side name ("block", "drop_newest" or "drop_oldest"; case,
+    /// `-` and `_` are ignored). `block_timeout` bounds the wait of "block".
+    pub fn parse(name: &str, block_timeout: Option<Duration>) -> Option<Self> {
+        match name.to_ascii_lowercase().replace(['-', '_'], "").as_str() {
+            "dropoldest" => Some(OverflowStrategy::DropOldest),
+            "dropnewest" => Some(OverflowStrategy::DropNewest),
+            "block" => Some(OverflowStrategy::Block(block_timeout)),
+            _ => None,
+        }
+    }
+}
+
+/// How HTTPHandler puts records on the wire.
+#[cfg(feature = "network")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpPayload {
+    /// One POST per batch with a JSON array body.
+    Json,
+    /// One GET per record, the record's attributes URL-encoded into the query string
+    /// (stdlib `HTTPHandler` with `method="GET"`).
+    FormGet,
+    /// One POST per record with a URL-encoded form body (stdlib `method="POST"`).
+    FormPost,
+}
+
+/// Fields HTTPHandler merges into every record: `global_context`, then fields pushed
+/// with `update_context()`, then the `context_provider` result. With a TTL the
+/// provider's result is reused until it expires, so batches in between never take
+/// the GIL for it.
+#[cfg(feature = "network")]
+pub struct RecordContext {
+    global: HashMap<String, Value>,
+    pushed: parking_lot::RwLock<HashMap<String, Value>>,
+    provider: Option<Py<PyAny>>,
+    ttl: Option<Duration>,
+    cached: parking_lot::Mutex<Option<(Instant, HashMap<String, Value>)>>,
+    provider_calls: AtomicU64,
+}
+
+#[cfg(feature = "network")]
+impl RecordContext {
+    fn new(
+        global: HashMap<String, Value>,
+        provider: Option<Py<PyAny>>,
+        ttl: Option<Duration>,
+    ) -> Self {
+        Self {
+            global,
+            pushed: parking_lot::RwLock::new(HashMap::new()),
+            provider,
+            ttl,
+            cached: parking_lot::Mutex::new(None),
+            provider_calls: AtomicU64::new(0),
+        }
+    }
+
+    /// Merge `fields` into the pushed context, or replace it with them. Takes effect
+    /// from the next batch.
+    pub fn update(&self, fields: HashMap<String, Value>, replace: bool) {
+        let mut pushed = self.pushed.write();
+        if replace {
+            *pushed = fields;
+        } else {
+            pushed.extend(fields);
+        }
+    }
+
+    /// How many times `context_provider` has been called.
+    pub fn provider_calls(&self) -> u64 {
+        self.provider_calls.load(Ordering::Relaxed)
+    }
+
+    /// The fields for the next batch. Calls `context_provider` (under the GIL) only
+    /// when there is no cached result younger than the TTL. With a TTL, a provider
+    /// that raises or returns a non-dict leaves the last good result in place.
+    fn resolve(&self) -> HashMap<String, Value> {
+        let mut fields = self.global.clone();
+        fields.extend(
+            self.pushed
+                .read()
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
+        let Some(provider) = &self.provider else {
+            return fields;
+        };
+        let mut cached = self.cached.lock();
+        let fresh = match (&*cached, self.ttl) {
+            (Some((fetched, _)), Some(ttl)) => fetched.elapsed() < ttl,
+            _ => false,
+        };
+        if !fresh {
+            self.provider_calls.fetch_add(1, Ordering::Relaxed);
+            let result = Python::attach(|py| {
+                let result = provider.call0(py).ok()?;
+                let dict = result.cast_bound::<PyDict>(py).ok()?;
+                let mut map = HashMap::new();
+                for (k, v) in dict.iter() {
+                    if let Ok(key) = k.extract::<String>() {
+                        map.insert(key, crate::py_logger::py_to_json_value(&v));
+                    }
+                }
+                Some(map)
+            });
+            match result {
+                Some(map) => *cached = Some((Instant::now(), map)),
+                None if self.ttl.is_none() => *cached = None,
+                None => {}
+            }
+        }
+        if let Some((_, dynamic)) = &*cached {
+            fields.extend(dynamic.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+        fields
+    }
+}
+
+#[cfg(feature = "network")]
+pub struct HTTPHandlerConfig {
+    pub url: String,
+    pub payload: HttpPayload,
+    pub headers: HashMap<String, String>,
+    pub global_context: HashMap<String, Value>,
+    /// Declarative reshaping applied in Rust, before `transform_callback`.
+    pub record_transform: Option<RecordTransform>,
+    pub transform_callback: Option<Py<PyAny>>,
+    pub context_provider: Option<Py<PyAny>>,
+    /// Reuse a `context_provider` result for this long (None = call it every batch).
+    pub context_ttl: Option<Duration>,
+    pub error_callback: Option<Py<PyAny>>,
+    pub overflow: OverflowStrategy,
+    /// How long `shutdown()` waits for the worker to drain (None = indefinitely).
+    pub shutdown_timeout: Option<Duration>,
+    /// File receiving records still pending when the shutdown timeout expires.
+    pub spill_path: Option<PathBuf>,
+    /// Daemon workers never delay interpreter exit; non-daemon workers drain first.
+    pub daemon: bool,
+    /// Records at or above this level are sent by the caller instead of queued.
+    pub sync_level: Option<LogLevel>,
+    /// Compress request bodies of at least `compression_min_bytes`.
+    pub compression: Option<Compression>,
+    pub compression_min_bytes: usize,
+    /// Split batches so no request body is larger than this.
+    pub max_batch_bytes: Option<usize>,
+    /// Give each request body a batch UUID and each record a sequence id.
+    pub idempotency_keys: bool,
+    pub timestamps: TimestampPolicy,
+    /// `Transport::Http`, or a dry run that records requests instead of sending them.
+    pub transport: Transport,
+}
+
+#[cfg(feature = "network")]
+impl HTTPHandler {
+    pub fn new(
+        url: String,
+        headers: HashMap<String, String>,
+        capacity: usize,
+        batch_size: usize,
+        flush_interval: u64,
+        overflow: OverflowStrategy,
+    ) -> Self {
+        Self::with_config(
+            HTTPHandlerConfig {
+                url,
+                payload: HttpPayload::Json,
+                headers,
+                global_context: HashMap::new(),
+                record_transform: None,
+                transform_callback: None,
+                context_provider: None,
+                context_ttl: None,
+                error_callback: None,
+                overflow,
+                shutdown_timeout: Some(DEFAULT_FLUSH_TIMEOUT),
+                spill_path: None,
+                daemon: crate::worker::resolve_daemon(None),
+                sync_level: None,
+                compression: None,
+                compression_min_bytes: 0,
+                max_batch_bytes: None,
+                idempotency_keys: false,
+                timestamps: TimestampPolicy::default(),
+                transport: Transport::Http,
+            },
+            capacity,
+            batch_size,
+            flush_interval,
+        )
+    }
+
+    pub fn with_config(
+        config: HTTPHandlerConfig,
+        capacity: usize,
+        batch_size: usize,
+        flush_interval: u64,
+    ) -> Self {
+        let (s, r) = crate::queue::lanes(capacity);
+        let pending = r.clone();
+        let (flush_tx, flush_rx) = crossbeam_channel::bounded::<()>(1);
+        let (done_tx, done_rx) = crossbeam_channel::bounded::<()>(1);
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let shutdown_clone = shutdown.clone();
+
+        let url = config.url;
+        let payload = config.payload;
+        let headers = config.headers;
+        let record_transform = config.record_transform;
+        let transform_callback = config.transform_callback;
+        let error_callback = config.error_callback;
+        let transport = config.transport;
+
+        let sink_acknowledged = Arc::new(AtomicU64::new(0));
+        let delivery_failed = Arc::new(AtomicU64::new(0));
+        let sink_ack_worker = sink_acknowledged.clone();
+        let delivery_failed_worker = delivery_failed.clone();
+
+        let queue = QueueStats::register("http", r.clone());
+        let worker_queue = queue.clone();
+        let spill = Arc::new(ShutdownSpill::new(config.spill_path));
+        let worker_spill = spill.clone();
+        let backoff = Arc::new(Backoff::new());
+        let worker_backoff = backoff.clone();
+        let encoding = Arc::new(BodyEncoding::new(
+            config.compression,
+            config.compression_min_bytes,
+            config.max_batch_bytes,
+            config.idempotency_keys,
+            config.timestamps,
+        ));
+        let worker_encoding = encoding.clone();
+        let context = Arc::new(RecordContext::new(
+            config.global_context,
+            config.context_provider,
+            config.context_ttl,
+        ));
+        let worker_context = context.clone();
+
+        let send_batch: BatchSend = Arc::new(move |buffer: &mut Vec<Arc<LogRecord>>| {
+            if worker_spill.is_abandoned() {
+                worker_spill.discard(std::mem::take(buffer));
+                return;
+            }
+            if payload != HttpPayload::Json {
+                Self::send_form_records(
+                    &url,
+                    payload,
+                    &headers,
+                    &worker_context,
+                    &error_callback,
+                    &transport,
+                    &worker_backoff,
+                    buffer,
+                    &sink_ack_worker,
+                    &delivery_failed_worker,
+                );
+                return;
+            }
+            Self::send_batch_with_callbacks(
+                &url,
+                &headers,
+                &worker_context,
+                &record_transform,
+                &transform_callback,
+                &error_callback,
+                &transport,
+                &worker_backoff,
+                &worker_encoding,
+                buffer,
+                &sink_ack_worker,
+                &delivery_failed_worker,
+            );
+        });
+        let worker_send = send_batch.clone();
+
+        let fork_rx = r.clone();
+        let fork_flush_rx = flush_rx.clone();
+        let run = Arc::new(move || {
+            let mut buffer = Vec::with_capacity(batch_size);
+            let mut last_flush = std::time::Instant::now();
+
+            let send = |buffer: &mut Vec<Arc<LogRecord>>| worker_send(buffer);
+            // A queue item holds one record, or every record of a log_batch() call.
+            // Returns whether a full batch was sent along the way.
+            let push = |buffer: &mut Vec<Arc<LogRecord>>, records: Vec<Arc<LogRecord>>| {
+                let mut sent = false;
+                for rec in records {
+                    buffer.push(rec);
+                    if buffer.len() >= batch_size {
+                        send(buffer);
+                        sent = true;
+                    }
+                }
+                sent
+            };
+
+            loop {
+                if matches!(flush_rx.try_recv(), Ok(())) {
+                    // Drain the queue to empty (batching) before signalling done, so a
+                    // returning flush() has attempted every record enqueued at signal time.
+                    while let Ok(rec) = r.try_recv() {
+                        let (records, _) = worker_queue.dequeue(rec);
+                        push(&mut buffer, records);
+                    }
+                    send(&mut buffer);
+                    last_flush = std::time::Instant::now();
+                    let _ = done_tx.try_send(());
+                }
+
+                if shutdown_clone.load(Ordering::Relaxed) {
+                    while let Ok(rec) = r.try_recv() {
+                        let (records, _) = worker_queue.dequeue(rec);
+                        push(&mut buffer, records);
+                    }
+                    send(&mut buffer);
+                    let _ = done_tx.try_send(());
+                    break;
+                }
+
+                match r.recv_timeout(Duration::from_millis(100)) {
+                    Ok(rec) => {
+                        // A record already past its deadline ships now rather than
+                        // waiting for the batch to fill or the flush interval.
+                        let (records, late) = worker_queue.dequeue(rec);
+                        if push(&mut buffer, records) {
+                            last_flush = std::time::Instant::now();
+                        }
+                        if late {
+                            send(&mut buffer);
+                            last_flush = std::time::Instant::now();
+                        }
+                    }
+                    Err(crossbeam_channel::RecvTimeoutError::Timeout) => {
+                        if !buffer.is_empty() && last_flush.elapsed().as_secs() >= flush_interval {
+                            send(&mut buffer);
+                            last_flush = std::time::Instant::now();
+                        }
+                    }
+                    Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
+                        while let Ok(rec) = r.try_recv() {
+                            let (records, _) = worker_queue.dequeue(rec);
+                            push(&mut buffer, records);
+                        }
+                        send(&mut buffer);
+                        let _ = done_tx.try_send(());
+                        break;
+                    }
+                }
+            }
+        });
+        let spawn = move || {
+            let run = run.clone();
+            crate::threads::spawn("http", move || run())
+                .expect("Failed to spawn HTTP handler thread")
+        };
+        let handle = spawn();
+
+        let worker = WorkerHandle::register(
+            WorkerConfig {
+                kind: "HTTPHandler",
+                stop: shutdown,
+                wake: flush_tx.clone(),
+                pending,
+                spill,
+                shutdown_timeout: config.shutdown_timeout,
+                daemon: config.daemon,
+            },
+            handle,
+        );
+        let fork_hook = restart_worker_after_fork(
+            &worker,
+            &queue,
+            fork_rx,
+            fork_flush_rx,
+            done_rx.clone(),
+            spawn,
+        );
+
+        Self {
+            lanes: s,
+            flush_signal: flush_tx,
+            flush_done: done_rx,
+            level: AtomicU32::new(LogLevel::NotSet.value()),
+            flush_level: AtomicU32::new(crate::preset::current().flush_level.value()),
+            overflow: config.overflow,
+            flush_timeout: DEFAULT_FLUSH_TIMEOUT,
+            worker,
+            _fork_hook: fork_hook,
+            emitted: AtomicU64::new(0),
+            queue,
+            sink_acknowledged,
+            delivery_failed,
+            sync: SyncLevel::new(config.sync_level),
+            send_batch,
+            backoff,
+            encoding,
+            context,
+            dispatch_mode: AtomicU8::new(DispatchMode::Native as u8),
+            filters: FilterSlot::default(),
+        }
+    }
+
+    /// Enqueue records as one queue item honoring the configured overflow strategy,
+    /// counting drops.
+    fn enqueue(&self, records: Vec<Arc<LogRecord>>) {
+        let queued = Queued::records(records);
+        crate::queue::enqueue(
+            &self.lanes,
+            self.overflow,
+            self.flush_timeout,
+            &self.queue,
+            queued,
+        );
+    }
+
+    /// Stdlib form mode: one request per record, each acknowledged or failed on its
+    /// own.
+    fn send_form_records(
+        url: &str,
+        payload: HttpPayload,
+        headers: &HashMap<String, String>,
+        context: &RecordContext,
+        error_callback: &Option<Py<PyAny>>,
+        transport: &Transport,
+        backoff: &Backoff,
+        buffer: &mut Vec<Arc<LogRecord>>,
+        sink_acknowledged: &AtomicU64,
+        delivery_failed: &AtomicU64,
+    ) {
+        let context = context.resolve();
+        for rec in std::mem::take(buffer) {
+            let form = form_encode_record(&rec, &context);
+            let result = backoff.deliver(|| {
+                if payload == HttpPayload::FormGet {
+                    let sep = if url.contains('?') { '&' } else { '?' };
+                    transport.send(&Request {
+                        method: "GET",
+                        url: &format!("{url}{sep}{form}"),
+                        headers,
+                        content_type: None,
+                        content_encoding: None,
+                        body: &[],
+                    })
+                } else {
+                    transport.send(&Request {
+                        method: "POST",
+                        url,
+                        headers,
+                        content_type: Some("application/x-www-form-urlencoded"),
+                        content_encoding: None,
+                        body: form.as_bytes(),
+                    })
+                }
+            });
+            match result {
+                Ok(()) => {
+                    sink_acknowledged.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(e) => {
+                    delivery_failed.fetch_add(1, Ordering::Relaxed);
+                    if let Some(ref cb) = error_callback {
+                        Python::attach(|py| {
+                            let _ = cb.call1(py, (e,));
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn send_batch_with_callbacks(
+        url: &str,
+        headers: &HashMap<String, String>,
+        context: &RecordContext,
+        record_transform: &Option<RecordTransform>,
+        transform_callback: &Option<Py<PyAny>>,
+        error_callback: &Option<Py<PyAny>>,
+        transport: &Transport,
+        backoff: &Backoff,
+        encoding: &BodyEncoding,
+        buffer: &mut Vec<Arc<LogRecord>>,
+        sink_acknowledged: &AtomicU64,
+        delivery_failed: &AtomicU64,
+    ) {
+        if buffer.is_empty() {
+            return;
+        }
+
+        let batch = std::mem::take(buffer);
+        let now = unix_now();
+        // Takes the GIL only when a context_provider result is due.
+        let context = context.resolve();
+        let records: Vec<Value> = batch
+            .iter()
+            .map(|rec| {
+                let mut rec_map = serde_json::to_value(rec).unwrap_or(Value::Null);
+                if let Value::Object(ref mut obj) = rec_map {
+                    for (k, v) in &context {
+                        obj.insert(k.clone(), v.clone());
+                    }
+                }
+                encoding.stamp_times(&mut rec_map, now);
+                if let Some(transform) = record_transform {
+                    transform.apply(&mut rec_map);
+                }
+                rec_map
+            })
+            .collect();
+
+        let json_payload: Value = match transform_callback {
+            // FAST PATH (§3): no transform_callback => the payload is built in pure
+            // Rust with NO Python::attach.
+            None => Value::Array(records),
+            Some(cb) => Python::attach(|py| {
+                let transformed = serde_json::to_string(&records).ok().and_then(|s| {
+                    let json_mod = py.import("json").ok()?;
+                    let py_recs = json_mod.call_method1("loads", (s,)).ok()?;
+                    let result = cb.call1(py, (py_recs,)).ok()?;
+                    let json_str = json_mod.call_method1("dumps", (result,)).ok()?;
+                    serde_json::from_str(&json_str.extract::<String>().ok()?).ok()
+                });
+                transformed.unwrap_or(Value::Array(records))
+            }),
+        };
+
+        let bodies = encoding.json_bodies(json_payload, batch.len());
+        encoding.post(
+            bodies,
+            url,
+            headers,
+            "application/json",
+            transport,
+            backoff,
+            error_callback,
+            sink_acknowledged,
+            delivery_failed,
+        );
+    }
+
+    pub fn flush(&self) {
+        let started = Instant::now();
+        let _ = self.flush_signal.try_send(());
+        let _ = self.flush_done.recv_timeout(self.flush_timeout);
+        self.queue.observe_flush(started);
+    }
+
+    pub fn shutdown(&self) {
+        self.worker.shutdown();
+    }
+
+    /// Records dropped / spilled to disk because the worker was abandoned.
+    pub fn shutdown_snapshot(&self) -> (u64, u64) {
+        self.worker.shutdown_snapshot()
+    }
+
+    pub fn is_daemon(&self) -> bool {
+        self.worker.is_daemon()
+    }
+
+    pub fn metrics_snapshot(&self) -> (u64, u64, u64, u64) {
+        (
+            self.emitted.load(Ordering::Relaxed),
+            self.sink_acknowledged.load(Ordering::Relaxed),
+            self.queue.dropped(),
+            self.delivery_failed.load(Ordering::Relaxed),
+        )
+    }
+
+    pub fn queue_stats(&self) -> &QueueStats {
+        &self.queue
+    }
+
+    pub fn sync_level(&self) -> &SyncLevel {
+        &self.sync
+    }
+
+    pub fn backoff(&self) -> &Backoff {
+        &self.backoff
+    }
+
+    pub fn body_encoding(&self) -> &BodyEncoding {
+        &self.encoding
+    }
+
+    pub fn record_context(&self) -> &RecordContext {
+        &self.context
+    }
+
+    /// Level-filter a `log_batch()` run and send its sync_level records right away.
+    /// Returns the records left to queue, if any.
+    fn split_batch(&self, records: &[Arc<LogRecord>]) -> Option<Vec<Arc<LogRecord>>> {
+        let level = self.level.load(Ordering::Relaxed);
+        let (mut sync, queued): (Vec<Arc<LogRecord>>, Vec<Arc<LogRecord>>) = records
+            .iter()
+            .filter(|r| r.levelno >= level as i32)
+            .filter_map(|r| self.filters.apply_shared(r))
+            .map(Cow::into_owned)
+            .inspect(|_| {
+                self.emitted.fetch_add(1, Ordering::Relaxed);
+            })
+            .partition(|r| self.sync.applies(r));
+        if !sync.is_empty() {
+            (self.send_batch)(&mut sync);
+        }
+        (!queued.is_empty()).then_some(queued)
+    }
+
+    pub fn set_level(&self, level: LogLevel) {
+        self.level.store(level.value(), Ordering::Relaxed);
+    }
+
+    /// Set the flush level. Records at or above this level trigger immediate flush.
+    /// Default is ERROR (40).
+    pub fn set_flush_level(&self, level: LogLevel) {
+        self.flush_level.store(level.value(), Ordering::Relaxed);
+    }
+
+    /// Get the current flush level.
+    pub fn get_flush_level(&self) -> u32 {
+        self.flush_level.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(feature = "network")]
+impl Handler for HTTPHandler {
+    fn emit(&self, record: &Arc<LogRecord>) {
+        let level = self.level.load(Ordering::Relaxed);
+        if record.levelno < level as i32 {
+            return;
+        }
+        let Some(record) = self.filters.apply_shared(record) else {
+            return;
+        };
+        let record = &*record;
+        self.emitted.fetch_add(1, Ordering::Relaxed);
+        if self.sync.applies(record) {
+            // Delivered by the caller, ahead of anything still queued.
+            (self.send_batch)(&mut vec![record.clone()]);
+            return;
+        }
+        self.enqueue(vec![record.clone()]);
+
+        // Level-based flush: immediately flush if record level >= flush_level
+        let flush_level = self.flush_level.load(Ordering::Relaxed);
+        if record.levelno >= flush_level as i32 {
+            let _ = self.flush_signal.try_send(());
+        }
+    }
+
+    fn emit_batch(&self, records: &[Arc<LogRecord>]) {
+        let Some(batch) = self.split_batch(records) else {
+            return;
+        };
+        let flush_level = self.flush_level.load(Ordering::Relaxed);
+        let flush = batch.iter().any(|r| r.levelno >= flush_level as i32);
+        self.enqueue(batch);
+        if flush {
+            let _ = self.flush_signal.try_send(());
+        }
+    }
+
+    fn level(&self) -> u32 {
+        self.level.load(Ordering::Relaxed)
+    }
+
+    fn flush(&self) {
+        HTTPHandler::flush(self);
+    }
+
+    fn shutdown(&self) {
+        HTTPHandler::shutdown(self);
+    }
+
+    fn dispatch_mode(&self) -> DispatchMode {
+        DispatchMode::from_u8(self.dispatch_mode.load(Ordering::Relaxed))
+    }
+
+    fn set_dispatch_mode(&self, mode: DispatchMode) {
+        self.dispatch_mode.store(mode as u8, Ordering::Relaxed);
+    }
+
+    fn filters(&self) -> Option<&FilterSlot> {
+        Some(&self.filters)
+    }
+
+    fn set_formatter(&mut self, _: Arc<dyn Formatter + Send + Sync>) {}
+    fn add_filter(&mut self, _: Arc<dyn Filter + Send + Sync>) {}
+}
+
+#[cfg(feature = "network")]
+impl Drop for HTTPHandler {
+    fn drop(&mut self) {
+        // Do NOT join here: Drop may run under the GIL (e.g. gc.collect) while the worker
+        // needs the GIL for a callback/error path — joining would deadlock. Signalling
+        // shutdown and dropping `lanes` (which disconnects the channel) still terminates
+        // the worker; explicit shutdown()/close() via py.detach performs the join.
+        self.worker.signal_stop();
+    }
+}
+
+// ============================================================================
+// OTLPHandler — batch protobuf to OTLP endpoint
+// ============================================================================
+
+#[cfg(feature = "otlp")]
+pub struct OTLPHandler {
+    lanes: LaneSender<Vec<Arc<LogRecord>>>,
+    flush_signal: crossbeam_channel::Sender<()>,
+    flush_done: crossbeam_channel::Receiver<()>,
+    level: AtomicU32,
+    overflow: OverflowStrategy,
+    flush_timeout: Duration,
+    worker: Arc<WorkerHandle>,
+    /// Restarts the worker thread in a forked child.
+    _fork_hook: Arc<ForkHook>,
+    emitted: AtomicU64,
+    queue: Arc<QueueStats>,
+    sink_acknowledged: Arc<AtomicU64>,
+    delivery_failed: Arc<AtomicU64>,
+    sync: SyncLevel,
+    send_batch: BatchSend,
+    backoff: Arc<Backoff>,
+    encoding: Arc<BodyEncoding>,
+    dispatch_mode: AtomicU8,
+    filters: FilterSlot,
+}
+
+#[cfg(feature = "otlp")]
+pub struct OTLPHandlerConfig {
+    pub url: String,
+    pub headers: HashMap<String, String>,
+    pub service_name: String,
+    pub error_callback: Option<Py<PyAny>>,
+    pub overflow: OverflowStrategy,
+    /// How long `shutdown()` waits for the worker to drain (None = indefinitely).
+    pub shutdown_timeout: Option<Duration>,
+    /// File receiving records still pending when the shutdown timeout expires.
+    pub spill_path: Option<PathBuf>,
+    /// Daemon workers never delay interpreter exit; non-daemon workers drain first.
+    pub daemon: bool,
+    /// Records at or above this level are sent by the caller instead of queued.
+    pub sync_level: Option<LogLevel>,
+    /// Compress request bodies of at least `compression_min_bytes`.
+    pub compression: Option<Compression>,
+    pub compression_min_bytes: usize,
+    /// Split batches so no request body is larger than this.
+    pub max_batch_bytes: Option<usize>,
+    /// Give each request body a batch UUID and each record a sequence id.
+    pub idempotency_keys: bool,
+    pub timestamps: TimestampPolicy,
+    /// `Transport::Http`, or a dry run that records requests instead of sending them.
+    pub transport: Transport,
+}
+
+#[cfg(feature = "otlp")]
+impl OTLPHandler {
+    pub fn new(
+        url: String,
+        headers: HashMap<String, String>,
+        service_name: String,
+        capacity: usize,
+        batch_size: usize,
+        flush_interval: u64,
+        overflow: OverflowStrategy,
     ) -> Self {
         Self::with_config(
-            HTTPHandlerConfig {
+            OTLPHandlerConfig {
                 url,
                 headers,
-                global_context: HashMap::new(),
-                transform_callback: None,
-                context_provider: None,
+                service_name,
                 error_callback: None,
                 overflow,
+                shutdown_timeout: Some(DEFAULT_FLUSH_TIMEOUT),
+                spill_path: None,
+                daemon: crate::worker::resolve_daemon(None),
+                sync_level: None,
+                compression: None,
+                compression_min_bytes: 0,
+                max_batch_bytes: None,
+                idempotency_keys: false,
+                timestamps: TimestampPolicy::default(),
+                transport: Transport::Http,
+            },
+            capacity,
+            batch_size,
+            flush_interval,
+        )
+    }
+
+    pub fn with_config(
+        config: OTLPHandlerConfig,
+        capacity: usize,
+        batch_size: usize,
+        flush_interval: u64,
+    ) -> Self {
+        let (s, r) = crate::queue::lanes(capacity);
+        let pending = r.clone();
+        let (flush_tx, flush_rx) = crossbeam_channel::bounded::<()>(1);
+        let (done_tx, done_rx) = crossbeam_channel::bounded::<()>(1);
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let shutdown_clone = shutdown.clone();
+
+        let url = config.url;
+        let headers = config.headers;
+        let service_name = config.service_name;
+        let error_callback = config.error_callback;
+        let transport = config.transport;
+
+        let sink_acknowledged = Arc::new(AtomicU64::new(0));
+        let delivery_failed = Arc::new(AtomicU64::new(0));
+        let sink_ack_worker = sink_acknowledged.clone();
+        let delivery_failed_worker = delivery_failed.clone();
+
+        let queue = QueueStats::register("otlp", r.clone());
+        let worker_queue = queue.clone();
+        let spill = Arc::new(ShutdownSpill::new(config.spill_path));
+        let worker_spill = spill.clone();
+        let backoff = Arc::new(Backoff::new());
+        let worker_backoff = backoff.clone();
+        let encoding = Arc::new(BodyEncoding::new(
+            config.compression,
+            config.compression_min_bytes,
+            config.max_batch_bytes,
+            config.idempotency_keys,
+            config.timestamps,
+        ));
+        let worker_encoding = encoding.clone();
+
+        let send_batch: BatchSend = Arc::new(move |buffer: &mut Vec<Arc<LogRecord>>| {
+            if worker_spill.is_abandoned() {
+                worker_spill.discard(std::mem::take(buffer));
+                return;
+            }
+            Self::send_otlp_batch(
+                &url,
+                &headers,
+                &service_name,
+                &error_callback,
+                &transport,
+                &worker_backoff,
+                &worker_encoding,
+                buffer,
+                &sink_ack_worker,
+                &delivery_failed_worker,
+            );
+        });
+        let worker_send = send_batch.clone();
+
+        let fork_rx = r.clone();
+        let fork_flush_rx = flush_rx.clone();
+        let run = Arc::new(move || {
+            let mut buffer = Vec::with_capacity(batch_size);
+            let mut last_flush = std::time::Instant::now();
+
+            let send = |buffer: &mut Vec<Arc<LogRecord>>| worker_send(buffer);
+            // A queue item holds one record, or every record of a log_batch() call.
+            // Returns whether a full batch was sent along the way.
+            let push = |buffer: &mut Vec<Arc<LogRecord>>, records: Vec<Arc<LogRecord>>| {
+                let mut sent = false;
+                for rec in records {
+                    buffer.push(rec);
+                    if buffer.len() >= batch_size {
+                        send(buffer);
+                        sent = true;
+                    }
+                }
+                sent
+            };
+
+            loop {
+                if matches!(flush_rx.try_recv(), Ok(())) {
+                    while let Ok(rec) = r.try_recv() {
+                        let (records, _) = worker_queue.dequeue(rec);
+                        push(&mut buffer, records);
+                    }
+                    send(&mut buffer);
+                    last_flush = std::time::Instant::now();
+                    let _ = done_tx.try_send(());
+                }
+
+                if shutdown_clone.load(Ordering::Relaxed) {
+                    while let Ok(rec) = r.try_recv() {
+                        let (records, _) = worker_queue.dequeue(rec);
+                        push(&mut buffer, records);
+                    }
+                    send(&mut buffer);
+                    let _ = done_tx.try_send(());
+                    break;
+                }
+
+                match r.recv_timeout(Duration::from_millis(100)) {
+                    Ok(rec) => {
+                        // A record already past its deadline ships now rather than
+                        // waiting for the batch to fill or the flush interval.
+                        let (records, late) = worker_queue.dequeue(rec);
+                        if push(&mut buffer, records) {
+                            last_flush = std::time::Instant::now();
+                        }
+                        if late {
+                            send(&mut buffer);
+                            last_flush = std::time::Instant::now();
+                        }
+                    }
+                    Err(crossbeam_channel::RecvTimeoutError::Timeout) => {
+                        if !buffer.is_empty() && last_flush.elapsed().as_secs() >= flush_interval {
+                            send(&mut buffer);
+                            last_flush = std::time::Instant::now();
+                        }
+                    }
+                    Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
+                        while let Ok(rec) = r.try_recv() {
+                            let (records, _) = worker_queue.dequeue(rec);
+                            push(&mut buffer, records);
+                        }
+                        send(&mut buffer);
+                        let _ = done_tx.try_send(());
+                        break;
+                    }
+                }
+            }
+        });
+        let spawn = move || {
+            let run = run.clone();
+            crate::threads::spawn("otlp", move || run())
+                .expect("Failed to spawn OTLP handler thread")
+        };
+        let handle = spawn();
+
+        let worker = WorkerHandle::register(
+            WorkerConfig {
+                kind: "OTLPHandler",
+                stop: shutdown,
+                wake: flush_tx.clone(),
+                pending,
+                spill,
+                shutdown_timeout: config.shutdown_timeout,
+                daemon: config.daemon,
+            },
+            handle,
+        );
+        let fork_hook = restart_worker_after_fork(
+            &worker,
+            &queue,
+            fork_rx,
+            fork_flush_rx,
+            done_rx.clone(),
+            spawn,
+        );
+
+        Self {
+            lanes: s,
+            flush_signal: flush_tx,
+            flush_done: done_rx,
+            level: AtomicU32::new(LogLevel::NotSet.value()),
+            overflow: config.overflow,
+            flush_timeout: DEFAULT_FLUSH_TIMEOUT,
+            worker,
+            _fork_hook: fork_hook,
+            emitted: AtomicU64::new(0),
+            queue,
+            sink_acknowledged,
+            delivery_failed,
+            sync: SyncLevel::new(config.sync_level),
+            send_batch,
+            backoff,
+            encoding,
+            dispatch_mode: AtomicU8::new(DispatchMode::Native as u8),
+            filters: FilterSlot::default(),
+        }
+    }
+
+    /// Enqueue records as one queue item honoring the configured overflow strategy,
+    /// counting drops.
+    fn enqueue(&self, records: Vec<Arc<LogRecord>>) {
+        let queued = Queued::records(records);
+        crate::queue::enqueue(
+            &self.lanes,
+            self.overflow,
+            self.flush_timeout,
+            &self.queue,
+            queued,
+        );
+    }
+
+    fn send_otlp_batch(
+        url: &str,
+        headers: &HashMap<String, String>,
+        service_name: &str,
+        error_callback: &Option<Py<PyAny>>,
+        transport: &Transport,
+        backoff: &Backoff,
+        encoding: &BodyEncoding,
+        buffer: &mut Vec<Arc<LogRecord>>,
+        sink_acknowledged: &AtomicU64,
+        delivery_failed: &AtomicU64,
+    ) {
+        use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, KeyValue};
+        use opentelemetry_proto::tonic::logs::v1::{
+            LogRecord as OtlpLogRecord, ResourceLogs, ScopeLogs,
+        };
+        use opentelemetry_proto::tonic::resource::v1::Resource;
+        use prost::Message;
+
+        if buffer.is_empty() {
+            return;
+        }
+
+        let batch = std::mem::take(buffer);
+        let now = unix_now();
+
+        let mut log_records: Vec<OtlpLogRecord> = batch
+            .iter()
+            .map(|rec| {
+                let capped = encoding.cap_backdate(rec.created, now);
+                let observed = if encoding.send_time() {
+                    now
+                } else {
+                    rec.created
+                };
+                let mut log_record = OtlpLogRecord {
+                    time_unix_nano: (capped.unwrap_or(rec.created) * 1_000_000_000.0) as u64,
+                    observed_time_unix_nano: (observed * 1_000_000_000.0) as u64,
+                    severity_number: match rec.levelno {
+                        10 => 5,  // DEBUG
+                        20 => 9,  // INFO
+                        30 => 13, // WARN
+                        40 => 17, // ERROR
+                        50 => 21, // FATAL
+                        _ => 0,
+                    },
+                    severity_text: rec.levelname.clone(),
+                    body: Some(AnyValue {
+                        value: Some(any_value::Value::StringValue(rec.msg.clone())),
+                    }),
+                    attributes: vec![
+                        KeyValue {
+                            key: "logger.name".to_string(),
+                            value: Some(AnyValue {
+                                value: Some(any_value::Value::StringValue(rec.name.clone())),
+                            }),
+                        },
+                        KeyValue {
+                            key: "code.filepath".to_string(),
+                            value: Some(AnyValue {
+                                value: Some(any_value::Value::StringValue(rec.pathname.clone())),
+                            }),
+                        },
+                        KeyValue {
+                            key: "code.lineno".to_string(),
+                            value: Some(AnyValue {
+                                value: Some(any_value::Value::IntValue(rec.lineno as i64)),
+                            }),
+                        },
+                        KeyValue {
+                            key: "code.function".to_string(),
+                            value: Some(AnyValue {
+                                value: Some(any_value::Value::StringValue(rec.func_name.clone())),
+                            }),
+                        },
+                    ],
+                    ..Default::default()
+                };
+                if capped.is_some() {
+                    log_record.attributes.push(KeyValue {
+                        key: "logxide.original_time_unix_nano".to_string(),
+                        value: Some(AnyValue {
+                            value: Some(any_value::Value::IntValue(
+                                (rec.created * 1_000_000_000.0) as i64,
+                            )),
+                        }),
+                    });
+                    log_record.attributes.push(KeyValue {
+                        key: "logxide.timestamp_capped".to_string(),
+                        value: Some(AnyValue {
+                            value: Some(any_value::Value::BoolValue(true)),
+                        }),
+                    });
+                }
+                if let Some(extra) = &rec.extra {
+                    // Sorted, so the same record always encodes the same way.
+                    let mut fields: Vec<_> = extra.iter().collect();
+                    fields.sort_unstable_by(|a, b| a.0.cmp(b.0));
+                    log_record
+                        .attributes
+                        .extend(fields.into_iter().map(|(key, value)| KeyValue {
+                            key: key.clone(),
+                            value: Some(otlp_value(value)),
+                        }));
+                }
+                log_record
+            })
+            .collect();
+
+        let mut resource_logs = ResourceLogs {
+            resource: Some(Resource {
+                attributes: vec![KeyValue {
+                    key: "service.name".to_string(),
+                    value: Some(AnyValue {
+                        value: Some(any_value::Value::StringValue(service_name.to_string())),
+                    }),
+                }],
+                ..Default::default()
+            }),
+            scope_logs: vec![ScopeLogs::default()],
+            ..Default::default()
+        };
+
+        if encoding.is_idempotent() {
+            for rec in &mut log_records {
+                rec.attributes.push(KeyValue {
+                    key: "logxide.sequence".to_string(),
+                    value: Some(AnyValue {
+                        value: Some(any_value::Value::IntValue(encoding.next_sequence() as i64)),
+                    }),
+                });
+                // Last, so each body's real batch id can be filled in below.
+                rec.attributes.push(KeyValue {
+                    key: "logxide.batch_id".to_string(),
+                    value: Some(AnyValue {
+                        value: Some(any_value::Value::StringValue(UUID_PLACEHOLDER.to_string())),
+                    }),
+                });
+            }
+        }
+
+        // Each record is a length-delimited field of ScopeLogs, whose own length
+        // prefix grows by at most 4 bytes as records are added.
+        let costs: Vec<usize> = log_records
+            .iter()
+            .map(|rec| {
+                let len = rec.encoded_len();
+                1 + prost::length_delimiter_len(len) + len
+            })
+            .collect();
+        let overhead = resource_logs.encoded_len() + 4;
+        let mut log_records = log_records.into_iter();
+        let bodies = encoding
+            .chunk_ranges(&costs, overhead)
+            .into_iter()
+            .map(|range| {
+                let key = encoding.batch_key();
+                let mut chunk: Vec<OtlpLogRecord> =
+                    log_records.by_ref().take(range.len()).collect();
+                if let Some(key) = &key {
+                    for rec in &mut chunk {
+                        if let Some(batch_id) = rec.attributes.last_mut() {
+                            batch_id.value = Some(AnyValue {
+                                value: Some(any_value::Value::StringValue(key.clone())),
+                            });
+                        }
+                    }
+                }
+                resource_logs.scope_logs[0].log_records = chunk;
+                encoding.checked(resource_logs.encode_to_vec(), range.len(), key)
+            })
+            .collect();
+
+        encoding.post(
+            bodies,
+            url,
+            headers,
+            "application/x-protobuf",
+            transport,
+            backoff,
+            error_callback,
+            sink_acknowledged,
+            delivery_failed,
+        );
+    }
+
+    pub fn flush(&self) {
+        let started = Instant::now();
+        let _ = self.flush_signal.try_send(());
+        let _ = self.flush_done.recv_timeout(self.flush_timeout);
+        self.queue.observe_flush(started);
+    }
+
+    pub fn shutdown(&self) {
+        self.worker.shutdown();
+    }
+
+    /// Records dropped / spilled to disk because the worker was abandoned.
+    pub fn shutdown_snapshot(&self) -> (u64, u64) {
+        self.worker.shutdown_snapshot()
+    }
+
+    pub fn is_daemon(&self) -> bool {
+        self.worker.is_daemon()
+    }
+
+    pub fn metrics_snapshot(&self) -> (u64, u64, u64, u64) {
+        (
+            self.emitted.load(Ordering::Relaxed),
+            self.sink_acknowledged.load(Ordering::Relaxed),
+            self.queue.dropped(),
+            self.delivery_failed.load(Ordering::Relaxed),
+        )
+    }
+
+    pub fn queue_stats(&self) -> &QueueStats {
+        &self.queue
+    }
+
+    pub fn sync_level(&self) -> &SyncLevel {
+        &self.sync
+    }
+
+    pub fn backoff(&self) -> &Backoff {
+        &self.backoff
+    }
+
+    pub fn body_encoding(&self) -> &BodyEncoding {
+        &self.encoding
+    }
+
+    /// Level-filter a `log_batch()` run and send its sync_level records right away.
+    /// Returns the records left to queue, if any.
+    fn split_batch(&self, records: &[Arc<LogRecord>]) -> Option<Vec<Arc<LogRecord>>> {
+        let level = self.level.load(Ordering::Relaxed);
+        let (mut sync, queued): (Vec<Arc<LogRecord>>, Vec<Arc<LogRecord>>) = records
+            .iter()
+            .filter(|r| r.levelno >= level as i32)
+            .filter_map(|r| self.filters.apply_shared(r))
+            .map(Cow::into_owned)
+            .inspect(|_| {
+                self.emitted.fetch_add(1, Ordering::Relaxed);
+            })
+            .partition(|r| self.sync.applies(r));
+        if !sync.is_empty() {
+            (self.send_batch)(&mut sync);
+        }
+        (!queued.is_empty()).then_some(queued)
+    }
+
+    pub fn set_level(&self, level: LogLevel) {
+        self.level.store(level.value(), Ordering::Relaxed);
+    }
+}
+
+/// An extra as an OTLP attribute value, keeping its JSON type: numbers, booleans,
+/// lists and dicts stay typed instead of turning into strings.
+#[cfg(feature = "otlp")]
+fn otlp_value(value: &Value) -> opentelemetry_proto::tonic::common::v1::AnyValue {
+    use opentelemetry_proto::tonic::common::v1::{
+        any_value, AnyValue, ArrayValue, KeyValue, KeyValueList,
+    };
+    let value = match value {
+        Value::Null => None,
+        Value::Bool(b) => Some(any_value::Value::BoolValue(*b)),
+        Value::Number(n) => Some(match n.as_i64() {
+            Some(i) => any_value::Value::IntValue(i),
+            None => any_value::Value::DoubleValue(n.as_f64().unwrap_or(f64::NAN)),
+        }),
+        Value::String(s) => Some(any_value::Value::StringValue(s.clone())),
+        Value::Array(items) => Some(any_value::Value::ArrayValue(ArrayValue {
+            values: items.iter().map(otlp_value).collect(),
+        })),
+        Value::Object(map) => Some(any_value::Value::KvlistValue(KeyValueList {
+            values: map
+                .iter()
+                .map(|(key, value)| KeyValue {
+                    key: key.clone(),
+                    value: Some(otlp_value(value)),
+                })
+                .collect(),
+        })),
+    };
+    AnyValue { value }
+}
+
+#[cfg(feature = "otlp")]
+impl Handler for OTLPHandler {
+    fn emit(&self, record: &Arc<LogRecord>) {
+        let level = self.level.load(Ordering::Relaxed);
+        if record.levelno < level as i32 {
+            return;
+        }
+        let Some(record) = self.filters.apply_shared(record) else {
+            return;
+        };
+        let record = &*record;
+        self.emitted.fetch_add(1, Ordering::Relaxed);
+        if self.sync.applies(record) {
+            // Delivered by the caller, ahead of anything still queued.
+            (self.send_batch)(&mut vec![record.clone()]);
+            return;
+        }
+        self.enqueue(vec![record.clone()]);
+    }
+
+    fn emit_batch(&self, records: &[Arc<LogRecord>]) {
+        if let Some(batch) = self.split_batch(records) {
+            self.enqueue(batch);
+        }
+    }
+
+    fn level(&self) -> u32 {
+        self.level.load(Ordering::Relaxed)
+    }
+
+    fn flush(&self) {
+        OTLPHandler::flush(self);
+    }
+
+    fn shutdown(&self) {
+        OTLPHandler::shutdown(self);
+    }
+
+    fn dispatch_mode(&self) -> DispatchMode {
+        DispatchMode::from_u8(self.dispatch_mode.load(Ordering::Relaxed))
+    }
+
+    fn set_dispatch_mode(&self, mode: DispatchMode) {
+        self.dispatch_mode.store(mode as u8, Ordering::Relaxed);
+    }
+
+    fn filters(&self) -> Option<&FilterSlot> {
+        Some(&self.filters)
+    }
+
+    fn set_formatter(&mut self, _: Arc<dyn Formatter + Send + Sync>) {}
+    fn add_filter(&mut self, _: Arc<dyn Filter + Send + Sync>) {}
+}
+
+#[cfg(feature = "otlp")]
+impl Drop for OTLPHandler {
+    fn drop(&mut self) {
+        // See HTTPHandler::drop — never join under the GIL; signal + channel disconnect
+        // terminate the worker, explicit shutdown() (via py.detach) joins.
+        self.worker.signal_stop();
+    }
+}
+
+// ============================================================================
+// DatagramHandler — one record per UDP / Unix datagram (stdlib DatagramHandler)
+// ============================================================================
+
+/// Where a DatagramHandler sends.
+#[cfg(feature = "network")]
+pub enum DatagramTarget {
+    Udp(std::net::SocketAddr),
+    /// Path of a Unix datagram socket (`AF_UNIX`, `SOCK_DGRAM`).
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+/// Payload of each datagram.
+#[cfg(feature = "network")]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DatagramFormat {
+    /// Length-prefixed pickled record dict, byte-compatible with the receivers of
+    /// `logging.handlers.DatagramHandler`.
+    Pickle,
+    /// One JSON record object per datagram, same shape as the HTTPHandler payload.
+    Json,
+    /// The record rendered by the handler's formatter, as UTF-8 text.
+    Text,
+}
+
+#[cfg(feature = "network")]
+impl DatagramFormat {
+    pub fn from_format_str(s: &str) -> Option<Self> {
+        match s {
+            "pickle" => Some(DatagramFormat::Pickle),
+            "json" => Some(DatagramFormat::Json),
+            "text" => Some(DatagramFormat::Text),
+            _ => None,
+        }
+    }
+}
+
+/// Largest payload of one UDP datagram over IPv4 (65535 minus the IP and UDP headers).
+#[cfg(feature = "network")]
+pub const DATAGRAM_MAX_PACKET_SIZE: usize = 65507;
+
+/// The longest prefix of `s` that is at most `max` bytes and ends on a char boundary.
+#[cfg(feature = "network")]
+fn truncate_at_char_boundary(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+#[cfg(feature = "network")]
+enum DatagramSocket {
+    Udp(std::net::UdpSocket, std::net::SocketAddr),
+    #[cfg(unix)]
+    Unix(std::os::unix::net::UnixDatagram, PathBuf),
+}
+
+/// Handler that sends every record as a single datagram from the logging thread.
+/// There is no connection to manage and no queue: a send either leaves the host's
+/// socket buffer or fails (oversized record, no listener on a Unix socket), and
+/// failures are counted and reported instead of retried.
+///
+/// A payload larger than `max_packet_size` is not sent. With `truncate` the message
+/// is shortened until the payload fits instead; a record that does not fit even
+/// with an empty message is still dropped.
+#[cfg(feature = "network")]
+pub struct DatagramHandler {
+    socket: DatagramSocket,
+    format: DatagramFormat,
+    max_packet_size: usize,
+    truncate: bool,
+    level: AtomicU32,
+    dispatch_mode: AtomicU8,
+    filters: FilterSlot,
+    formatter: parking_lot::Mutex<Arc<dyn Formatter + Send + Sync>>,
+    sanitize: SanitizeSlot,
+    error_callback: Option<Py<PyAny>>,
+    emitted: AtomicU64,
+    sink_acknowledged: AtomicU64,
+    delivery_failed: AtomicU64,
+    truncated: AtomicU64,
+}
+
+/// One value of a record's stdlib attribute dict (see [`stdlib_attributes`]).
+#[cfg(feature = "network")]
+enum RecordAttr<'a> {
+    Str(&'a str),
+    Int(i128),
+    Float(f64),
+    None,
+    Value(&'a Value),
+}
+
+#[cfg(feature = "network")]
+impl RecordAttr<'_> {
+    /// What Python's `str()` gives for the value, as stdlib `urlencode` sends it.
+    /// Lists and dicts from extras are rendered as JSON.
+    fn to_py_str(&self) -> String {
+        fn float(f: f64) -> String {
+            if f.is_finite() && f.fract() == 0.0 && f.abs() < 1e16 {
+                format!("{f:.1}")
+            } else {
+                f.to_string()
+            }
+        }
+        match *self {
+            RecordAttr::Str(s) => s.to_string(),
+            RecordAttr::Int(i) => i.to_string(),
+            RecordAttr::Float(f) => float(f),
+            RecordAttr::None | RecordAttr::Value(Value::Null) => "None".to_string(),
+            RecordAttr::Value(Value::Bool(b)) => if *b { "True" } else { "False" }.to_string(),
+            RecordAttr::Value(Value::String(s)) => s.clone(),
+            RecordAttr::Value(Value::Number(n)) => match n.as_f64() {
+                Some(f) if !n.is_i64() && !n.is_u64() => float(f),
+                _ => n.to_string(),
             },
-            capacity,
-            batch_size,
-            flush_interval,
+            RecordAttr::Value(v) => v.to_string(),
+        }
+    }
+}
+
+/// The attribute dict stdlib socket/HTTP handlers send for a record
+/// (`record.__dict__`, as prepared by `SocketHandler.makePickle`): the message
+/// pre-merged with `args` cleared, the traceback as `exc_text`, then the extras.
+#[cfg(feature = "network")]
+fn stdlib_attributes(record: &LogRecord) -> Vec<(&str, RecordAttr<'_>)> {
+    fn text(s: Option<&String>) -> RecordAttr<'_> {
+        s.map_or(RecordAttr::None, |s| RecordAttr::Str(s))
+    }
+    let mut attrs = vec![
+        ("name", RecordAttr::Str(&record.name)),
+        ("args", RecordAttr::None),
+        ("levelname", RecordAttr::Str(&record.levelname)),
+        ("levelno", RecordAttr::Int(record.levelno.into())),
+        ("pathname", RecordAttr::Str(&record.pathname)),
+        ("filename", RecordAttr::Str(&record.filename)),
+        ("module", RecordAttr::Str(&record.module)),
+        ("exc_info", RecordAttr::None),
+        (
+            "exc_text",
+            text(record.exc_text.as_ref().or(record.exc_info.as_ref())),
+        ),
+        ("stack_info", text(record.stack_info.as_ref())),
+        ("lineno", RecordAttr::Int(record.lineno.into())),
+        ("funcName", RecordAttr::Str(&record.func_name)),
+        ("created", RecordAttr::Float(record.created)),
+        ("msecs", RecordAttr::Float(record.msecs)),
+        (
+            "relativeCreated",
+            RecordAttr::Float(record.relative_created),
+        ),
+        ("thread", RecordAttr::Int(record.thread.into())),
+        ("threadName", RecordAttr::Str(&record.thread_name)),
+        ("processName", RecordAttr::Str(&record.process_name)),
+        ("process", RecordAttr::Int(record.process.into())),
+        ("ppid", RecordAttr::Int(record.ppid.into())),
+        (
+            "thread_native_id",
+            RecordAttr::Int(record.thread_native_id.into()),
+        ),
+        ("seq", RecordAttr::Int(record.seq.into())),
+        ("taskName", text(record.task_name.as_ref())),
+    ];
+    if let Some(ref extra) = record.extra {
+        attrs.extend(
+            extra
+                .iter()
+                .map(|(k, v)| (k.as_str(), RecordAttr::Value(v))),
+        );
+    }
+    attrs
+}
+
+/// Encode `record` the way `SocketHandler.makePickle` does: its stdlib attribute
+/// dict, pickled and prefixed with its big-endian u32 length.
+#[cfg(feature = "network")]
+fn pickle_record(record: &LogRecord) -> Vec<u8> {
+    let mut d = crate::pickle::DictWriter::new();
+    d.str("msg", &record.get_message());
+    for (key, attr) in stdlib_attributes(record) {
+        match attr {
+            RecordAttr::Str(s) => d.str(key, s),
+            RecordAttr::Int(i) => d.int(key, i),
+            RecordAttr::Float(f) => d.float(key, f),
+            RecordAttr::None => d.none(key),
+            RecordAttr::Value(v) => d.value(key, v),
+        }
+    }
+    let body = d.finish();
+    let mut payload = Vec::with_capacity(body.len() + 4);
+    payload.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    payload.extend_from_slice(&body);
+    payload
+}
+
+/// Encode `record` as stdlib `HTTPHandler` does: its stdlib attribute dict through
+/// `urllib.parse.urlencode` (values as `str()`, `quote_plus` escaping).
+#[cfg(feature = "network")]
+fn form_encode_record(record: &LogRecord, global_context: &HashMap<String, Value>) -> String {
+    fn quote_plus(out: &mut String, s: &str) {
+        for b in s.bytes() {
+            match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'_' | b'.' | b'-' | b'~' => {
+                    out.push(b as char)
+                }
+                b' ' => out.push('+'),
+                _ => out.push_str(&format!("%{b:02X}")),
+            }
+        }
+    }
+    let msg = record.get_message();
+    let mut attrs = vec![("msg", RecordAttr::Str(&msg))];
+    attrs.extend(stdlib_attributes(record));
+    attrs.extend(
+        global_context
+            .iter()
+            .map(|(k, v)| (k.as_str(), RecordAttr::Value(v))),
+    );
+
+    let mut out = String::new();
+    for (key, attr) in attrs {
+        if !out.is_empty() {
+            out.push('&');
+        }
+        quote_plus(&mut out, key);
+        out.push('=');
+        quote_plus(&mut out, &attr.to_py_str());
+    }
+    out
+}
+
+#[cfg(feature = "network")]
+impl DatagramHandler {
+    pub fn new(
+        target: DatagramTarget,
+        format: DatagramFormat,
+        max_packet_size: usize,
+        truncate: bool,
+        error_callback: Option<Py<PyAny>>,
+    ) -> std::io::Result<Self> {
+        let socket = match target {
+            DatagramTarget::Udp(addr) => {
+                let local = if addr.is_ipv4() {
+                    "0.0.0.0:0"
+                } else {
+                    "[::]:0"
+                };
+                DatagramSocket::Udp(std::net::UdpSocket::bind(local)?, addr)
+            }
+            #[cfg(unix)]
+            DatagramTarget::Unix(path) => {
+                DatagramSocket::Unix(std::os::unix::net::UnixDatagram::unbound()?, path)
+            }
+        };
+        Ok(Self {
+            socket,
+            format,
+            max_packet_size,
+            truncate,
+            level: AtomicU32::new(LogLevel::NotSet.value()),
+            dispatch_mode: AtomicU8::new(DispatchMode::Native as u8),
+            filters: FilterSlot::default(),
+            formatter: parking_lot::Mutex::new(default_formatter()),
+            sanitize: SanitizeSlot::default(),
+            error_callback,
+            emitted: AtomicU64::new(0),
+            sink_acknowledged: AtomicU64::new(0),
+            delivery_failed: AtomicU64::new(0),
+            truncated: AtomicU64::new(0),
+        })
+    }
+
+    fn encode(&self, record: &LogRecord) -> Vec<u8> {
+        match self.format {
+            DatagramFormat::Pickle => pickle_record(record),
+            DatagramFormat::Json => record.to_json().as_bytes().to_vec(),
+            DatagramFormat::Text => self
+                .sanitize
+                .apply(self.formatter.lock().format(record))
+                .into_bytes(),
+        }
+    }
+
+    /// The payload for `record`, shortened to `max_packet_size` when `truncate` is
+    /// set, or None when it does not fit. The bool tells whether it was shortened.
+    fn payload(&self, record: &LogRecord) -> Option<(Vec<u8>, bool)> {
+        let payload = self.encode(record);
+        if payload.len() <= self.max_packet_size {
+            return Some((payload, false));
+        }
+        if !self.truncate {
+            return None;
+        }
+        if self.format == DatagramFormat::Text {
+            let text = String::from_utf8(payload).unwrap_or_default();
+            let cut = truncate_at_char_boundary(&text, self.max_packet_size);
+            return Some((cut.as_bytes().to_vec(), true));
+        }
+        // Pickle and JSON cannot be cut anywhere: shorten the merged message by the
+        // overflow and encode again. Escaping can make the overflow larger than the
+        // bytes removed, so repeat until the payload fits or the message is empty.
+        let mut message = record.get_message();
+        let mut shortened = record.detached();
+        shortened.args = None;
+        let mut overflow = payload.len() - self.max_packet_size;
+        while !message.is_empty() {
+            let keep = message.len().saturating_sub(overflow);
+            message = truncate_at_char_boundary(&message, keep).to_string();
+            shortened.msg.clone_from(&message);
+            // Encoded afresh each round: the cached encoding has the previous message.
+            shortened.json_cache = Default::default();
+            let payload = self.encode(&shortened);
+            if payload.len() <= self.max_packet_size {
+                return Some((payload, true));
+            }
+            overflow = payload.len() - self.max_packet_size;
+        }
+        None
+    }
+
+    fn report(&self, msg: String) {
+        self.delivery_failed.fetch_add(1, Ordering::Relaxed);
+        if let Some(ref cb) = self.error_callback {
+            Python::attach(|py| {
+                let _ = cb.call1(py, (msg,));
+            });
+        }
+    }
+
+    fn send(&self, payload: &[u8]) -> std::io::Result<()> {
+        match self.socket {
+            DatagramSocket::Udp(ref socket, addr) => socket.send_to(payload, addr).map(|_| ()),
+            #[cfg(unix)]
+            DatagramSocket::Unix(ref socket, ref path) => socket.send_to(payload, path).map(|_| ()),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self.socket {
+            DatagramSocket::Udp(_, addr) => addr.to_string(),
+            #[cfg(unix)]
+            DatagramSocket::Unix(_, ref path) => path.display().to_string(),
+        }
+    }
+
+    /// (emitted, sink_acknowledged, delivery_failed, truncated).
+    pub fn metrics_snapshot(&self) -> (u64, u64, u64, u64) {
+        (
+            self.emitted.load(Ordering::Relaxed),
+            self.sink_acknowledged.load(Ordering::Relaxed),
+            self.delivery_failed.load(Ordering::Relaxed),
+            self.truncated.load(Ordering::Relaxed),
         )
     }
 
-    pub fn with_config(
-        config: HTTPHandlerConfig,
-        capacity: usize,
-        batch_size: usize,
-        flush_interval: u64,
-    ) -> Self {
-        let (s, r) = crossbeam_channel::bounded(capacity);
-        let drop_rx = r.clone();
-        let (flush_tx, flush_rx) = crossbeam_channel::bounded::<()>(1);
-        let (done_tx, done_rx) = crossbeam_channel::bounded::<()>(1);
-        let shutdown = Arc::new(AtomicBool::new(false));
-        let shutdown_clone = shutdown.clone();
+    pub fn set_level(&self, level: LogLevel) {
+        self.level.store(level.value(), Ordering::Relaxed);
+    }
+
+    /// Set the formatter that renders `format="text"` payloads.
+    pub fn set_formatter_instance(&self, formatter: Arc<dyn Formatter + Send + Sync>) {
+        *self.formatter.lock() = formatter;
+    }
+
+    /// Escape or strip control characters in the formatted text.
+    pub fn set_sanitize(&self, policy: SanitizePolicy) {
+        self.sanitize.set(policy);
+    }
+}
+
+#[cfg(feature = "network")]
+impl Handler for DatagramHandler {
+    fn emit(&self, record: &Arc<LogRecord>) {
+        let level = self.level.load(Ordering::Relaxed);
+        if record.levelno < level as i32 {
+            return;
+        }
+        let Some(record) = self.filters.apply(record) else {
+            return;
+        };
+        let record = &*record;
+        self.emitted.fetch_add(1, Ordering::Relaxed);
+        let Some((payload, truncated)) = self.payload(record) else {
+            self.report(format!(
+                "record for {} exceeds max_packet_size ({} bytes)",
+                self.describe(),
+                self.max_packet_size
+            ));
+            return;
+        };
+        if truncated {
+            self.truncated.fetch_add(1, Ordering::Relaxed);
+        }
+        match self.send(&payload) {
+            Ok(()) => {
+                self.sink_acknowledged.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => self.report(format!("send to {} failed: {e}", self.describe())),
+        }
+    }
+
+    fn level(&self) -> u32 {
+        self.level.load(Ordering::Relaxed)
+    }
+
+    fn flush(&self) {}
+
+    fn dispatch_mode(&self) -> DispatchMode {
+        DispatchMode::from_u8(self.dispatch_mode.load(Ordering::Relaxed))
+    }
+
+    fn set_dispatch_mode(&self, mode: DispatchMode) {
+        self.dispatch_mode.store(mode as u8, Ordering::Relaxed);
+    }
+
+    fn filters(&self) -> Option<&FilterSlot> {
+        Some(&self.filters)
+    }
+
+    fn set_formatter(&mut self, formatter: Arc<dyn Formatter + Send + Sync>) {
+        *self.formatter.lock() = formatter;
+    }
+
+    fn add_filter(&mut self, _: Arc<dyn Filter + Send + Sync>) {}
+}
+
+// ============================================================================
+// SyslogHandler — RFC 3164 / RFC 5424 messages to a syslog daemon
+// ============================================================================
+
+/// Where a SyslogHandler sends.
+#[cfg(feature = "network")]
+pub enum SyslogTarget {
+    Udp(std::net::SocketAddr),
+    /// TCP with octet-counting framing (RFC 6587), one connection kept open.
+    Tcp(std::net::SocketAddr),
+    /// A local syslog socket such as `/dev/log`. A datagram socket is tried first,
+    /// then a stream socket, as `logging.handlers.SysLogHandler` does.
+    #[cfg(unix)]
+    Unix {
+        path: PathBuf,
+        stream: bool,
+    },
+}
+
+/// Layout of each syslog message.
+#[cfg(feature = "network")]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SyslogProtocol {
+    /// BSD syslog: `<PRI>Mmm dd hh:mm:ss HOST TAG[PID]: MSG`.
+    Rfc3164,
+    /// `<PRI>1 TIMESTAMP HOST APP-NAME PROCID - - MSG`.
+    Rfc5424,
+}
+
+#[cfg(feature = "network")]
+impl SyslogProtocol {
+    pub fn from_protocol_str(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "rfc3164" | "3164" => Some(SyslogProtocol::Rfc3164),
+            "rfc5424" | "5424" => Some(SyslogProtocol::Rfc5424),
+            _ => None,
+        }
+    }
+}
+
+/// Syslog severity for a level number, as `SysLogHandler.mapPriority` maps the
+/// standard levels. Custom levels take the severity of the level below them.
+#[cfg(feature = "network")]
+pub fn syslog_severity(levelno: i32) -> u8 {
+    match levelno {
+        n if n >= LogLevel::Critical.value() as i32 => 2,
+        n if n >= LogLevel::Error.value() as i32 => 3,
+        n if n >= LogLevel::Warning.value() as i32 => 4,
+        n if n >= LogLevel::Info.value() as i32 => 6,
+        _ => 7,
+    }
+}
+
+#[cfg(feature = "network")]
+enum SyslogConnection {
+    Udp(std::net::UdpSocket),
+    Tcp(std::net::TcpStream),
+    #[cfg(unix)]
+    UnixDatagram(std::os::unix::net::UnixDatagram),
+    #[cfg(unix)]
+    UnixStream(std::os::unix::net::UnixStream),
+}
+
+/// How long a TCP connect or write may block the logging thread.
+#[cfg(feature = "network")]
+const SYSLOG_TCP_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[cfg(feature = "network")]
+impl SyslogConnection {
+    fn open(target: &SyslogTarget) -> std::io::Result<Self> {
+        match *target {
+            SyslogTarget::Udp(addr) => {
+                let local = if addr.is_ipv4() {
+                    "0.0.0.0:0"
+                } else {
+                    "[::]:0"
+                };
+                let socket = std::net::UdpSocket::bind(local)?;
+                socket.connect(addr)?;
+                Ok(SyslogConnection::Udp(socket))
+            }
+            SyslogTarget::Tcp(addr) => {
+                let stream = std::net::TcpStream::connect_timeout(&addr, SYSLOG_TCP_TIMEOUT)?;
+                stream.set_write_timeout(Some(SYSLOG_TCP_TIMEOUT))?;
+                Ok(SyslogConnection::Tcp(stream))
+            }
+            #[cfg(unix)]
+            SyslogTarget::Unix { ref path, stream } => {
+                if !stream {
+                    let socket = std::os::unix::net::UnixDatagram::unbound()?;
+                    if socket.connect(path).is_ok() {
+                        return Ok(SyslogConnection::UnixDatagram(socket));
+                    }
+                }
+                Ok(SyslogConnection::UnixStream(
+                    std::os::unix::net::UnixStream::connect(path)?,
+                ))
+            }
+        }
+    }
+
+    /// Send one message with the framing its transport needs.
+    fn send(&mut self, message: &[u8]) -> std::io::Result<()> {
+        match self {
+            SyslogConnection::Udp(socket) => socket.send(message).map(|_| ()),
+            SyslogConnection::Tcp(stream) => {
+                let mut framed = format!("{} ", message.len()).into_bytes();
+                framed.extend_from_slice(message);
+                stream.write_all(&framed)
+            }
+            #[cfg(unix)]
+            SyslogConnection::UnixDatagram(socket) => socket.send(message).map(|_| ()),
+            #[cfg(unix)]
+            SyslogConnection::UnixStream(stream) => {
+                let mut framed = message.to_vec();
+                framed.push(0);
+                stream.write_all(&framed)
+            }
+        }
+    }
+}
+
+/// Handler that writes each record to a syslog daemon from the logging thread, like
+/// DatagramHandler. Stream connections are reopened once when a send fails; records
+/// that still can't be sent are counted and reported instead of queued.
+#[cfg(feature = "network")]
+pub struct SyslogHandler {
+    target: SyslogTarget,
+    protocol: SyslogProtocol,
+    facility: u8,
+    hostname: String,
+    app_name: String,
+    connection: Arc<parking_lot::Mutex<Option<SyslogConnection>>>,
+    /// Closes the connection in a forked child, which opens its own.
+    _fork_hook: Arc<ForkHook>,
+    level: AtomicU32,
+    dispatch_mode: AtomicU8,
+    filters: FilterSlot,
+    formatter: parking_lot::Mutex<Arc<dyn Formatter + Send + Sync>>,
+    sanitize: SanitizeSlot,
+    error_callback: Option<Py<PyAny>>,
+    emitted: AtomicU64,
+    sink_acknowledged: AtomicU64,
+    delivery_failed: AtomicU64,
+}
+
+/// A syslog header field: printable ASCII without spaces, at most `max` chars, or
+/// `-` (the RFC 5424 NILVALUE) when nothing is left.
+#[cfg(feature = "network")]
+fn syslog_header_field(value: &str, max: usize) -> String {
+    let field: String = value
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max)
+        .collect();
+    if field.is_empty() {
+        "-".to_string()
+    } else {
+        field
+    }
+}
+
+#[cfg(feature = "network")]
+impl SyslogHandler {
+    /// Connect to `target`. `facility` is the syslog facility code (0-23).
+    pub fn new(
+        target: SyslogTarget,
+        protocol: SyslogProtocol,
+        facility: u8,
+        hostname: &str,
+        app_name: &str,
+        error_callback: Option<Py<PyAny>>,
+    ) -> std::io::Result<Self> {
+        let connection = Arc::new(parking_lot::Mutex::new(Some(SyslogConnection::open(
+            &target,
+        )?)));
+        Ok(Self {
+            target,
+            protocol,
+            facility,
+            hostname: syslog_header_field(hostname, 255),
+            app_name: syslog_header_field(
+                app_name,
+                if protocol == SyslogProtocol::Rfc3164 {
+                    32
+                } else {
+                    48
+                },
+            ),
+            _fork_hook: crate::fork::reset_in_child(&connection, |connection| *connection = None),
+            connection,
+            level: AtomicU32::new(LogLevel::NotSet.value()),
+            dispatch_mode: AtomicU8::new(DispatchMode::Native as u8),
+            filters: FilterSlot::default(),
+            formatter: parking_lot::Mutex::new(default_formatter()),
+            sanitize: SanitizeSlot::default(),
+            error_callback,
+            emitted: AtomicU64::new(0),
+            sink_acknowledged: AtomicU64::new(0),
+            delivery_failed: AtomicU64::new(0),
+        })
+    }
+
+    /// The full syslog message for `record`, with `body` as its MSG part.
+    pub fn build_message(&self, record: &LogRecord, body: &str) -> String {
+        let pri = u32::from(self.facility) * 8 + u32::from(syslog_severity(record.levelno));
+        let created = if record.created > 0.0 {
+            chrono::DateTime::from_timestamp(
+                record.created as i64,
+                (record.created.fract() * 1e9) as u32,
+            )
+            .unwrap_or_default()
+        } else {
+            chrono::Utc::now()
+        }
+        .with_timezone(&chrono::Local);
+        let pid = record.process;
+        match self.protocol {
+            SyslogProtocol::Rfc3164 => {
+                let timestamp = created.format("%b %e %H:%M:%S");
+                // The local daemon fills in the hostname, as with glibc's syslog().
+                #[cfg(unix)]
+                if matches!(self.target, SyslogTarget::Unix { .. }) {
+                    return format!("<{pri}>{timestamp} {}[{pid}]: {body}", self.app_name);
+                }
+                format!(
+                    "<{pri}>{timestamp} {} {}[{pid}]: {body}",
+                    self.hostname, self.app_name
+                )
+            }
+            SyslogProtocol::Rfc5424 => format!(
+                "<{pri}>1 {} {} {} {pid} - - {body}",
+                created.format("%Y-%m-%dT%H:%M:%S%.6f%:z"),
+                self.hostname,
+                self.app_name
+            ),
+        }
+    }
+
+    fn send(&self, message: &[u8]) -> std::io::Result<()> {
+        let mut connection = self.connection.lock();
+        if let Some(ref mut conn) = *connection {
+            if conn.send(message).is_ok() {
+                return Ok(());
+            }
+        }
+        // The daemon restarted or dropped the connection: reopen once and retry.
+        *connection = None;
+        let mut conn = SyslogConnection::open(&self.target)?;
+        let sent = conn.send(message);
+        *connection = Some(conn);
+        sent
+    }
+
+    fn describe(&self) -> String {
+        match self.target {
+            SyslogTarget::Udp(addr) => format!("udp://{addr}"),
+            SyslogTarget::Tcp(addr) => format!("tcp://{addr}"),
+            #[cfg(unix)]
+            SyslogTarget::Unix { ref path, .. } => path.display().to_string(),
+        }
+    }
+
+    pub fn metrics_snapshot(&self) -> (u64, u64, u64) {
+        (
+            self.emitted.load(Ordering::Relaxed),
+            self.sink_acknowledged.load(Ordering::Relaxed),
+            self.delivery_failed.load(Ordering::Relaxed),
+        )
+    }
+
+    pub fn set_level(&self, level: LogLevel) {
+        self.level.store(level.value(), Ordering::Relaxed);
+    }
+
+    /// Set a formatter for the MSG part of each message.
+    pub fn set_formatter_instance(&self, formatter: Arc<dyn Formatter + Send + Sync>) {
+        *self.formatter.lock() = formatter;
+    }
+
+    /// Escape or strip control characters in the formatted text.
+    pub fn set_sanitize(&self, policy: SanitizePolicy) {
+        self.sanitize.set(policy);
+    }
+}
+
+#[cfg(feature = "network")]
+impl Handler for SyslogHandler {
+    fn emit(&self, record: &Arc<LogRecord>) {
+        let level = self.level.load(Ordering::Relaxed);
+        if record.levelno < level as i32 {
+            return;
+        }
+        let Some(record) = self.filters.apply(record) else {
+            return;
+        };
+        let record = &*record;
+        self.emitted.fetch_add(1, Ordering::Relaxed);
+        let body = self.sanitize.apply(self.formatter.lock().format(record));
+        let message = self.build_message(record, &body);
+        match self.send(message.as_bytes()) {
+            Ok(()) => {
+                self.sink_acknowledged.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => {
+                self.delivery_failed.fetch_add(1, Ordering::Relaxed);
+                if let Some(ref cb) = self.error_callback {
+                    let msg = format!("send to {} failed: {e}", self.describe());
+                    Python::attach(|py| {
+                        let _ = cb.call1(py, (msg,));
+                    });
+                }
+            }
+        }
+    }
+
+    fn level(&self) -> u32 {
+        self.level.load(Ordering::Relaxed)
+    }
+
+    fn flush(&self) {}
+
+    fn dispatch_mode(&self) -> DispatchMode {
+        DispatchMode::from_u8(self.dispatch_mode.load(Ordering::Relaxed))
+    }
+
+    fn set_dispatch_mode(&self, mode: DispatchMode) {
+        self.dispatch_mode.store(mode as u8, Ordering::Relaxed);
+    }
+
+    fn filters(&self) -> Option<&FilterSlot> {
+        Some(&self.filters)
+    }
+
+    fn set_formatter(&mut self, formatter: Arc<dyn Formatter + Send + Sync>) {
+        *self.formatter.lock() = formatter;
+    }
+
+    fn add_filter(&mut self, _: Arc<dyn Filter + Send + Sync>) {}
+}
+
+// ============================================================================
+// SocketHandler — pickled records over TCP / a Unix stream socket (stdlib SocketHandler)
+// ============================================================================
+
+/// Where a SocketHandler connects.
+#[cfg(feature = "network")]
+pub enum SocketTarget {
+    Tcp(std::net::SocketAddr),
+    /// Path of a Unix stream socket (`AF_UNIX`, `SOCK_STREAM`).
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+/// Waits between failed connection attempts: `start` after the first failure, then
+/// multiplied by `factor` up to `max`. Same defaults as `logging.handlers.SocketHandler`.
+#[cfg(feature = "network")]
+#[derive(Clone, Copy)]
+pub struct SocketBackoff {
+    pub start: Duration,
+    pub factor: f64,
+    pub max: Duration,
+}
+
+#[cfg(feature = "network")]
+impl Default for SocketBackoff {
+    fn default() -> Self {
+        Self {
+            start: Duration::from_secs(1),
+            factor: 2.0,
+            max: Duration::from_secs(30),
+        }
+    }
+}
 
-        let url = config.url;
-        let headers = config.headers;
-        let global_context = config.global_context;
-        let transform_callback = config.transform_callback;
-        let context_provider = config.context_provider;
-        let error_callback = config.error_callback;
+#[cfg(feature = "network")]
+enum SocketConnection {
+    Tcp(std::net::TcpStream),
+    #[cfg(unix)]
+    Unix(std::os::unix::net::UnixStream),
+}
 
-        let sink_acknowledged = Arc::new(AtomicU64::new(0));
-        let delivery_failed = Arc::new(AtomicU64::new(0));
-        let sink_ack_worker = sink_acknowledged.clone();
-        let delivery_failed_worker = delivery_failed.clone();
+/// How long a connect or write may block the logging thread (`SocketHandler.makeSocket`
+/// uses one second as well).
+#[cfg(feature = "network")]
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[cfg(feature = "network")]
+impl SocketConnection {
+    fn open(target: &SocketTarget) -> std::io::Result<Self> {
+        match *target {
+            SocketTarget::Tcp(addr) => {
+                let stream = std::net::TcpStream::connect_timeout(&addr, SOCKET_TIMEOUT)?;
+                stream.set_write_timeout(Some(SOCKET_TIMEOUT))?;
+                Ok(SocketConnection::Tcp(stream))
+            }
+            #[cfg(unix)]
+            SocketTarget::Unix(ref path) => {
+                let stream = std::os::unix::net::UnixStream::connect(path)?;
+                stream.set_write_timeout(Some(SOCKET_TIMEOUT))?;
+                Ok(SocketConnection::Unix(stream))
+            }
+        }
+    }
 
-        let handle = std::thread::spawn(move || {
-            let mut buffer = Vec::with_capacity(batch_size);
-            let mut last_flush = std::time::Instant::now();
+    fn send(&mut self, payload: &[u8]) -> std::io::Result<()> {
+        match self {
+            SocketConnection::Tcp(stream) => stream.write_all(payload),
+            #[cfg(unix)]
+            SocketConnection::Unix(stream) => stream.write_all(payload),
+        }
+    }
 
-            let send = |buffer: &mut Vec<LogRecord>| {
-                Self::send_batch_with_callbacks(
-                    &url,
-                    &headers,
-                    &global_context,
-                    &transform_callback,
-                    &context_provider,
-                    &error_callback,
-                    buffer,
-                    &sink_ack_worker,
-                    &delivery_failed_worker,
-                );
+    /// Whether the receiver has closed its end. A write to a closed TCP connection
+    /// usually still succeeds once and the record is lost, so check before writing.
+    /// Receivers never send anything, so a readable socket means end-of-stream.
+    fn is_closed(&self) -> bool {
+        fn peek_eof(
+            set_nonblocking: impl Fn(bool) -> std::io::Result<()>,
+            peek: impl FnOnce() -> std::io::Result<usize>,
+        ) -> bool {
+            if set_nonblocking(true).is_err() {
+                return true;
+            }
+            let closed = match peek() {
+                Ok(n) => n == 0,
+                Err(e) => e.kind() != std::io::ErrorKind::WouldBlock,
             };
+            closed || set_nonblocking(false).is_err()
+        }
+        let mut byte = [0u8; 1];
+        match self {
+            SocketConnection::Tcp(stream) => {
+                peek_eof(|on| stream.set_nonblocking(on), || stream.peek(&mut byte))
+            }
+            #[cfg(unix)]
+            SocketConnection::Unix(stream) => {
+                use std::io::Read;
+                peek_eof(
+                    |on| stream.set_nonblocking(on),
+                    || (&*stream).read(&mut byte),
+                )
+            }
+        }
+    }
+}
 
-            loop {
-                if matches!(flush_rx.try_recv(), Ok(())) {
-                    // Drain the queue to empty (batching) before signalling done, so a
-                    // returning flush() has attempted every record enqueued at signal time.
-                    while let Ok(rec) = r.try_recv() {
-                        buffer.push(rec);
-                        if buffer.len() >= batch_size {
-                            send(&mut buffer);
-                        }
-                    }
-                    send(&mut buffer);
-                    last_flush = std::time::Instant::now();
-                    let _ = done_tx.try_send(());
-                }
-
-                if shutdown_clone.load(Ordering::Relaxed) {
-                    while let Ok(rec) = r.try_recv() {
-                        buffer.push(rec);
-                        if buffer.len() >= batch_size {
-                            send(&mut buffer);
-                        }
-                    }
-                    send(&mut buffer);
-                    let _ = done_tx.try_send(());
-                    break;
-                }
+/// Connection state of a SocketHandler.
+#[cfg(feature = "network")]
+#[derive(Default)]
+struct SocketState {
+    connection: Option<SocketConnection>,
+    /// No connection attempt before this instant; records until then are dropped.
+    retry_at: Option<Instant>,
+    /// Wait after the most recent failed attempt, grown by the backoff factor.
+    retry_delay: Option<Duration>,
+    /// Whether a connection has been opened before (to count reconnects).
+    connected_once: bool,
+}
 
-                match r.recv_timeout(Duration::from_millis(100)) {
-                    Ok(rec) => {
-                        buffer.push(rec);
-                        if buffer.len() >= batch_size {
-                            send(&mut buffer);
-                            last_flush = std::time::Instant::now();
-                        }
-                    }
-                    Err(crossbeam_channel::RecvTimeoutError::Timeout) => {
-                        if !buffer.is_empty() && last_flush.elapsed().as_secs() >= flush_interval {
-                            send(&mut buffer);
-                            last_flush = std::time::Instant::now();
-                        }
-                    }
-                    Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
-                        while let Ok(rec) = r.try_recv() {
-                            buffer.push(rec);
-                            if buffer.len() >= batch_size {
-                                send(&mut buffer);
-                            }
-                        }
-                        send(&mut buffer);
-                        let _ = done_tx.try_send(());
-                        break;
-                    }
-                }
-            }
-        });
+/// Handler that writes each record to a TCP or Unix stream socket from the logging
+/// thread, as a length-prefixed pickle that `logging.handlers.SocketHandler` receivers
+/// read. The connection is opened on the first record. When it breaks, the record is
+/// retried once on a new connection; when connecting fails, attempts back off
+/// exponentially and records logged in between are dropped and counted, as the stdlib
+/// does.
+#[cfg(feature = "network")]
+pub struct SocketHandler {
+    target: SocketTarget,
+    backoff: SocketBackoff,
+    state: Arc<parking_lot::Mutex<SocketState>>,
+    /// Closes the connection in a forked child, which opens its own.
+    _fork_hook: Arc<ForkHook>,
+    level: AtomicU32,
+    dispatch_mode: AtomicU8,
+    filters: FilterSlot,
+    error_callback: Option<Py<PyAny>>,
+    emitted: AtomicU64,
+    sink_acknowledged: AtomicU64,
+    delivery_failed: AtomicU64,
+    reconnects: AtomicU64,
+}
 
+#[cfg(feature = "network")]
+impl SocketHandler {
+    pub fn new(
+        target: SocketTarget,
+        backoff: SocketBackoff,
+        error_callback: Option<Py<PyAny>>,
+    ) -> Self {
+        let state = Arc::new(parking_lot::Mutex::new(SocketState::default()));
         Self {
-            sender: s,
-            drop_rx,
-            flush_signal: flush_tx,
-            flush_done: done_rx,
-            level: AtomicU8::new(LogLevel::Debug as u8),
-            flush_level: AtomicU8::new(LogLevel::Error as u8),
-            shutdown,
-            stopped: AtomicBool::new(false),
-            overflow: config.overflow,
-            flush_timeout: DEFAULT_FLUSH_TIMEOUT,
-            join_handle: Mutex::new(Some(handle)),
+            target,
+            backoff,
+            _fork_hook: crate::fork::reset_in_child(&state, |state| {
+                state.connection = None;
+                state.retry_at = None;
+                state.retry_delay = None;
+            }),
+            state,
+            level: AtomicU32::new(LogLevel::NotSet.value()),
+            dispatch_mode: AtomicU8::new(DispatchMode::Native as u8),
+            filters: FilterSlot::default(),
+            error_callback,
             emitted: AtomicU64::new(0),
-            queue_dropped: AtomicU64::new(0),
-            sink_acknowledged,
-            delivery_failed,
+            sink_acknowledged: AtomicU64::new(0),
+            delivery_failed: AtomicU64::new(0),
+            reconnects: AtomicU64::new(0),
         }
     }
 
-    /// Enqueue a record honoring the configured overflow strategy, counting drops.
-    fn enqueue(&self, record: LogRecord) {
-        match self.overflow {
-            OverflowStrategy::DropNewest => {
-                if self.sender.try_send(record).is_err() {
-                    self.queue_dropped.fetch_add(1, Ordering::Relaxed);
-                }
-            }
-            OverflowStrategy::DropOldest => {
-                let mut record = record;
-                loop {
-                    match self.sender.try_send(record) {
-                        Ok(()) => break,
-                        Err(crossbeam_channel::TrySendError::Full(returned)) => {
-                            if self.drop_rx.try_recv().is_ok() {
-                                self.queue_dropped.fetch_add(1, Ordering::Relaxed);
-                            }
-                            record = returned;
-                        }
-                        Err(crossbeam_channel::TrySendError::Disconnected(_)) => {
-                            self.queue_dropped.fetch_add(1, Ordering::Relaxed);
-                            break;
-                        }
-                    }
+    /// Open a connection unless the backoff window is still running. A failure starts
+    /// (or grows) the window; the error is returned for reporting.
+    fn connect(&self, state: &mut SocketState) -> Result<SocketConnection, Option<std::io::Error>> {
+        let now = Instant::now();
+        if state.retry_at.is_some_and(|at| now < at) {
+            return Err(None);
+        }
+        match SocketConnection::open(&self.target) {
+            Ok(connection) => {
+                if state.connected_once {
+                    self.reconnects.fetch_add(1, Ordering::Relaxed);
                 }
+                state.connected_once = true;
+                state.retry_at = None;
+                state.retry_delay = None;
+                Ok(connection)
             }
-            OverflowStrategy::Block => {
-                if block_can_wait() {
-                    // Detached producer path (§4): GIL is released, so a true blocking
-                    // send is safe (a same-GIL sink can still make progress) and never
-                    // drops — it only errors on channel disconnect.
-                    if self.sender.send(record).is_err() {
-                        self.queue_dropped.fetch_add(1, Ordering::Relaxed);
-                    }
-                } else if self
-                    .sender
-                    .send_timeout(record, self.flush_timeout)
-                    .is_err()
-                {
-                    // Attached path (GIL may be held): bound the wait by flush_timeout so a
-                    // stalled same-GIL sink degrades to a counted drop instead of a
-                    // deadlock. Fully GIL-safe blocking is the detached branch above.
-                    self.queue_dropped.fetch_add(1, Ordering::Relaxed);
-                }
+            Err(e) => {
+                let delay = match state.retry_delay {
+                    None => self.backoff.start,
+                    Some(delay) => delay.mul_f64(self.backoff.factor).min(self.backoff.max),
+                };
+                state.retry_delay = Some(delay);
+                state.retry_at = Some(now + delay);
+                Err(Some(e))
             }
         }
     }
 
-    fn send_batch_with_callbacks(
-        url: &str,
-        headers: &HashMap<String, String>,
-        global_context: &HashMap<String, Value>,
-        transform_callback: &Option<Py<PyAny>>,
-        context_provider: &Option<Py<PyAny>>,
-        error_callback: &Option<Py<PyAny>>,
-        buffer: &mut Vec<LogRecord>,
-        sink_acknowledged: &AtomicU64,
-        delivery_failed: &AtomicU64,
-    ) {
-        if buffer.is_empty() {
-            return;
+    /// Send one payload, reconnecting once if the connection broke. `Err(None)` means
+    /// the record was dropped inside the backoff window.
+    fn send(&self, payload: &[u8]) -> Result<(), Option<std::io::Error>> {
+        let mut state = self.state.lock();
+        if let Some(ref mut connection) = state.connection {
+            if !connection.is_closed() && connection.send(payload).is_ok() {
+                return Ok(());
+            }
+            // The receiver restarted or dropped the connection: reconnect and retry once.
+            state.connection = None;
         }
+        let mut connection = self.connect(&mut state)?;
+        connection.send(payload).map_err(Some)?;
+        state.connection = Some(connection);
+        Ok(())
+    }
 
-        let batch = std::mem::take(buffer);
-        let batch_len = batch.len() as u64;
-
-        let json_payload: Value = if transform_callback.is_none() && context_provider.is_none() {
-            // FAST PATH (§3): no callbacks => build the payload in pure Rust with NO
-            // Python::attach. Byte-identical to the previous no-callback default branch
-            // (dynamic_context is empty when context_provider is None).
-            let records_with_context: Vec<Value> = batch
-                .iter()
-                .map(|rec| {
-                    let mut rec_map = serde_json::to_value(rec).unwrap_or(Value::Null);
-                    if let Value::Object(ref mut obj) = rec_map {
-                        for (k, v) in global_context {
-                            obj.insert(k.clone(), v.clone());
-                        }
-                    }
-                    rec_map
-                })
-                .collect();
-            Value::Array(records_with_context)
-        } else {
-            Python::attach(|py| {
-                let dynamic_context: HashMap<String, Value> = context_provider
-                    .as_ref()
-                    .and_then(|cb| {
-                        cb.call0(py).ok().and_then(|result| {
-                            let dict = result.cast_bound::<PyDict>(py).ok()?;
-                            let mut map = HashMap::new();
-                            for (k, v) in dict.iter() {
-                                if let Ok(key) = k.extract::<String>() {
-                                    map.insert(key, crate::py_logger::py_to_json_value(&v));
-                                }
-                            }
-                            Some(map)
-                        })
-                    })
-                    .unwrap_or_default();
-
-                if let Some(ref cb) = transform_callback {
-                    let records_list: Vec<Value> = batch
-                        .iter()
-                        .map(|rec| {
-                            let mut rec_map = serde_json::to_value(rec).unwrap_or(Value::Null);
-                            if let Value::Object(ref mut obj) = rec_map {
-                                for (k, v) in global_context {
-                                    obj.insert(k.clone(), v.clone());
-                                }
-                                for (k, v) in &dynamic_context {
-                                    obj.insert(k.clone(), v.clone());
-                                }
-                            }
-                            rec_map
-                        })
-                        .collect();
-
-                    let py_records = serde_json::to_string(&records_list).ok().and_then(|s| {
-                        py.import("json")
-                            .ok()
-                            .and_then(|json_mod| json_mod.call_method1("loads", (s,)).ok())
-                    });
+    fn describe(&self) -> String {
+        match self.target {
+            SocketTarget::Tcp(addr) => format!("tcp://{addr}"),
+            #[cfg(unix)]
+            SocketTarget::Unix(ref path) => path.display().to_string(),
+        }
+    }
 
-                    if let Some(py_recs) = py_records {
-                        if let Ok(result) = cb.call1(py, (py_recs,)) {
-                            if let Ok(json_mod) = py.import("json") {
-                                if let Ok(json_str) = json_mod.call_method1("dumps", (result,)) {
-                                    if let Ok(s) = json_str.extract::<String>() {
-                                        if let Ok(v) = serde_json::from_str(&s) {
-                                            return v;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+    /// Close the connection; the next record opens a new one.
+    pub fn close(&self) {
+        self.state.lock().connection = None;
+    }
 
-                let records_with_context: Vec<Value> = batch
-                    .iter()
-                    .map(|rec| {
-                        let mut rec_map = serde_json::to_value(rec).unwrap_or(Value::Null);
-                        if let Value::Object(ref mut obj) = rec_map {
-                            for (k, v) in global_context {
-                                obj.insert(k.clone(), v.clone());
-                            }
-                            for (k, v) in &dynamic_context {
-                                obj.insert(k.clone(), v.clone());
-                            }
-                        }
-                        rec_map
-                    })
-                    .collect();
+    pub fn metrics_snapshot(&self) -> (u64, u64, u64, u64) {
+        (
+            self.emitted.load(Ordering::Relaxed),
+            self.sink_acknowledged.load(Ordering::Relaxed),
+            self.delivery_failed.load(Ordering::Relaxed),
+            self.reconnects.load(Ordering::Relaxed),
+        )
+    }
 
-                Value::Array(records_with_context)
-            })
-        };
+    pub fn set_level(&self, level: LogLevel) {
+        self.level.store(level.value(), Ordering::Relaxed);
+    }
+}
 
-        let mut request = ureq::post(url).set("Content-Type", "application/json");
-        for (key, value) in headers {
-            request = request.set(key, value);
+#[cfg(feature = "network")]
+impl Handler for SocketHandler {
+    fn emit(&self, record: &Arc<LogRecord>) {
+        let level = self.level.load(Ordering::Relaxed);
+        if record.levelno < level as i32 {
+            return;
         }
-
-        match request.send_json(&json_payload) {
-            Ok(_) => {
-                sink_acknowledged.fetch_add(batch_len, Ordering::Relaxed);
+        let Some(record) = self.filters.apply(record) else {
+            return;
+        };
+        self.emitted.fetch_add(1, Ordering::Relaxed);
+        match self.send(&pickle_record(&record)) {
+            Ok(()) => {
+                self.sink_acknowledged.fetch_add(1, Ordering::Relaxed);
             }
-            Err(e) => {
-                delivery_failed.fetch_add(batch_len, Ordering::Relaxed);
-                if let Some(ref cb) = error_callback {
+            Err(error) => {
+                self.delivery_failed.fetch_add(1, Ordering::Relaxed);
+                // Records dropped while waiting to reconnect are only counted.
+                if let (Some(e), Some(ref cb)) = (error, &self.error_callback) {
+                    let msg = format!("send to {} failed: {e}", self.describe());
                     Python::attach(|py| {
-                        let _ = cb.call1(py, (e.to_string(),));
+                        let _ = cb.call1(py, (msg,));
                     });
                 }
             }
         }
     }
 
-    pub fn flush(&self) {
-        let _ = self.flush_signal.try_send(());
-        let _ = self.flush_done.recv_timeout(self.flush_timeout);
+    fn level(&self) -> u32 {
+        self.level.load(Ordering::Relaxed)
     }
 
-    pub fn shutdown(&self) {
-        if self.stopped.swap(true, Ordering::SeqCst) {
+    fn flush(&self) {}
+
+    fn shutdown(&self) {
+        self.close();
+    }
+
+    fn dispatch_mode(&self) -> DispatchMode {
+        DispatchMode::from_u8(self.dispatch_mode.load(Ordering::Relaxed))
+    }
+
+    fn set_dispatch_mode(&self, mode: DispatchMode) {
+        self.dispatch_mode.store(mode as u8, Ordering::Relaxed);
+    }
+
+    fn filters(&self) -> Option<&FilterSlot> {
+        Some(&self.filters)
+    }
+
+    fn set_formatter(&mut self, _: Arc<dyn Formatter + Send + Sync>) {}
+    fn add_filter(&mut self, _: Arc<dyn Filter + Send + Sync>) {}
+}
+
+// ============================================================================
+// SMTPHandler — one email per record (stdlib SMTPHandler)
+// ============================================================================
+
+/// Handler that emails every record, in one SMTP session per record opened from the
+/// logging thread, as `logging.handlers.SMTPHandler` does. It is meant for rare,
+/// important records: set its level to ERROR or CRITICAL. The subject is a
+/// `%(field)s` template rendered against the record; the body is the formatted
+/// record. Failed deliveries are counted and reported, not retried.
+#[cfg(feature = "smtp")]
+pub struct SMTPHandler {
+    config: crate::smtp::SmtpConfig,
+    from: String,
+    to: Vec<String>,
+    subject: crate::formatter::PythonFormatter,
+    level: AtomicU32,
+    dispatch_mode: AtomicU8,
+    filters: FilterSlot,
+    formatter: parking_lot::Mutex<Arc<dyn Formatter + Send + Sync>>,
+    sanitize: SanitizeSlot,
+    error_callback: Option<Py<PyAny>>,
+    emitted: AtomicU64,
+    sink_acknowledged: AtomicU64,
+    delivery_failed: AtomicU64,
+}
+
+#[cfg(feature = "smtp")]
+impl SMTPHandler {
+    pub fn new(
+        config: crate::smtp::SmtpConfig,
+        from: String,
+        to: Vec<String>,
+        subject: String,
+        error_callback: Option<Py<PyAny>>,
+    ) -> Self {
+        Self {
+            config,
+            from,
+            to,
+            subject: crate::formatter::PythonFormatter::new(subject),
+            level: AtomicU32::new(LogLevel::NotSet.value()),
+            dispatch_mode: AtomicU8::new(DispatchMode::Native as u8),
+            filters: FilterSlot::default(),
+            formatter: parking_lot::Mutex::new(default_formatter()),
+            sanitize: SanitizeSlot::default(),
+            error_callback,
+            emitted: AtomicU64::new(0),
+            sink_acknowledged: AtomicU64::new(0),
+            delivery_failed: AtomicU64::new(0),
+        }
+    }
+
+    /// The subject template rendered for `record`, cut at the first line break.
+    pub fn subject(&self, record: &LogRecord) -> String {
+        let subject = self.sanitize.apply(self.subject.format(record));
+        subject.lines().next().unwrap_or_default().to_string()
+    }
+
+    /// Format `record` and mail it under `subject`.
+    pub fn deliver(&self, record: &LogRecord, subject: &str) {
+        let level = self.level.load(Ordering::Relaxed);
+        if record.levelno < level as i32 {
             return;
         }
-        self.shutdown.store(true, Ordering::Relaxed);
-        let _ = self.flush_signal.try_send(());
-        if let Some(handle) = self.join_handle.lock().unwrap().take() {
-            let _ = handle.join();
+        let Some(record) = self.filters.apply(record) else {
+            return;
+        };
+        let record = &*record;
+        self.emitted.fetch_add(1, Ordering::Relaxed);
+        let body = self.sanitize.apply(self.formatter.lock().format(record));
+        let date = chrono::Local::now().to_rfc2822();
+        let message = crate::smtp::build_message(&self.from, &self.to, subject, &date, &body);
+        match crate::smtp::send_mail(&self.config, &self.from, &self.to, &message) {
+            Ok(()) => {
+                self.sink_acknowledged.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => {
+                self.delivery_failed.fetch_add(1, Ordering::Relaxed);
+                if let Some(ref cb) = self.error_callback {
+                    let msg = format!(
+                        "mail to {}:{} failed: {e}",
+                        self.config.host, self.config.port
+                    );
+                    Python::attach(|py| {
+                        let _ = cb.call1(py, (msg,));
+                    });
+                }
+            }
         }
     }
 
-    pub fn metrics_snapshot(&self) -> (u64, u64, u64, u64) {
+    pub fn metrics_snapshot(&self) -> (u64, u64, u64) {
         (
             self.emitted.load(Ordering::Relaxed),
             self.sink_acknowledged.load(Ordering::Relaxed),
-            self.queue_dropped.load(Ordering::Relaxed),
             self.delivery_failed.load(Ordering::Relaxed),
         )
     }
 
     pub fn set_level(&self, level: LogLevel) {
-        self.level.store(level as u8, Ordering::Relaxed);
+        self.level.store(level.value(), Ordering::Relaxed);
     }
 
-    /// Set the flush level. Records at or above this level trigger immediate flush.
-    /// Default is ERROR (40).
-    pub fn set_flush_level(&self, level: LogLevel) {
-        self.flush_level.store(level as u8, Ordering::Relaxed);
+    /// Set the formatter for the message body.
+    pub fn set_formatter_instance(&self, formatter: Arc<dyn Formatter + Send + Sync>) {
+        *self.formatter.lock() = formatter;
     }
 
-    /// Get the current flush level.
-    pub fn get_flush_level(&self) -> u8 {
-        self.flush_level.load(Ordering::Relaxed)
+    /// Escape or strip control characters in the formatted text.
+    pub fn set_sanitize(&self, policy: SanitizePolicy) {
+        self.sanitize.set(policy);
     }
 }
 
-impl Handler for HTTPHandler {
-    fn emit(&self, record: &LogRecord) {
-        let level = self.level.load(Ordering::Relaxed);
-        if record.levelno < level as i32 {
-            return;
-        }
-        self.emitted.fetch_add(1, Ordering::Relaxed);
-        self.enqueue(record.clone());
+#[cfg(feature = "smtp")]
+impl Handler for SMTPHandler {
+    fn emit(&self, record: &Arc<LogRecord>) {
+        self.deliver(record, &self.subject(record));
+    }
 
-        // Level-based flush: immediately flush if record level >= flush_level
-        let flush_level = self.flush_level.load(Ordering::Relaxed);
-        if record.levelno >= flush_level as i32 {
-            let _ = self.flush_signal.try_send(());
-        }
+    fn level(&self) -> u32 {
+        self.level.load(Ordering::Relaxed)
     }
 
-    fn flush(&self) {
-        HTTPHandler::flush(self);
+    fn flush(&self) {}
+
+    fn dispatch_mode(&self) -> DispatchMode {
+        DispatchMode::from_u8(self.dispatch_mode.load(Ordering::Relaxed))
     }
 
-    fn shutdown(&self) {
-        HTTPHandler::shutdown(self);
+    fn set_dispatch_mode(&self, mode: DispatchMode) {
+        self.dispatch_mode.store(mode as u8, Ordering::Relaxed);
+    }
+
+    fn filters(&self) -> Option<&FilterSlot> {
+        Some(&self.filters)
+    }
+
+    fn set_formatter(&mut self, formatter: Arc<dyn Formatter + Send + Sync>) {
+        *self.formatter.lock() = formatter;
     }
 
-    fn set_formatter(&mut self, _: Arc<dyn Formatter + Send + Sync>) {}
     fn add_filter(&mut self, _: Arc<dyn Filter + Send + Sync>) {}
 }
 
-impl Drop for HTTPHandler {
-    fn drop(&mut self) {
-        // Do NOT join here: Drop may run under the GIL (e.g. gc.collect) while the worker
-        // needs the GIL for a callback/error path — joining would deadlock. Signalling
-        // shutdown and dropping `sender` (which disconnects the channel) still terminates
-        // the worker; explicit shutdown()/close() via py.detach performs the join.
-        self.shutdown.store(true, Ordering::Relaxed);
-        let _ = self.flush_signal.try_send(());
-    }
+// ============================================================================
+// PipeHandler — NDJSON to a subprocess's stdin (or a named pipe)
+// ============================================================================
+
+/// Where a PipeHandler writes.
+#[cfg(feature = "pipe")]
+pub enum PipeTarget {
+    /// Spawn `argv` and write to its stdin; its stdout and stderr are inherited.
+    Command(Vec<String>),
+    /// Open an existing named pipe (FIFO) or file for appending.
+    Path(PathBuf),
+}
+
+#[cfg(feature = "pipe")]
+pub struct PipeHandlerConfig {
+    pub target: PipeTarget,
+    pub overflow: OverflowStrategy,
+    /// Respawn the command (reopen the path) after it exits or the pipe breaks.
+    pub restart: bool,
+    /// Minimum wait between two connection attempts.
+    pub restart_delay: Duration,
+    pub error_callback: Option<Py<PyAny>>,
+    /// How long `shutdown()` waits for the worker to drain (None = indefinitely).
+    pub shutdown_timeout: Option<Duration>,
+    /// File receiving records still pending when the shutdown timeout expires.
+    pub spill_path: Option<PathBuf>,
+    /// Daemon workers never delay interpreter exit; non-daemon workers drain first.
+    pub daemon: bool,
 }
 
-// ============================================================================
-// OTLPHandler — batch protobuf to OTLP endpoint
-// ============================================================================
+/// Records serialized into one pipe write.
+#[cfg(feature = "pipe")]
+const PIPE_BATCH: usize = 512;
 
-pub struct OTLPHandler {
-    sender: crossbeam_channel::Sender<LogRecord>,
-    drop_rx: crossbeam_channel::Receiver<LogRecord>,
+/// How long a spawned command gets to exit after its stdin is closed at shutdown.
+#[cfg(feature = "pipe")]
+const PIPE_EXIT_GRACE: Duration = Duration::from_secs(5);
+
+#[cfg(feature = "pipe")]
+pub struct PipeHandler {
+    lanes: LaneSender<Vec<Arc<LogRecord>>>,
     flush_signal: crossbeam_channel::Sender<()>,
     flush_done: crossbeam_channel::Receiver<()>,
-    level: AtomicU8,
-    shutdown: Arc<AtomicBool>,
-    stopped: AtomicBool,
+    level: AtomicU32,
+    dispatch_mode: AtomicU8,
+    filters: FilterSlot,
     overflow: OverflowStrategy,
     flush_timeout: Duration,
-    join_handle: Mutex<Option<JoinHandle<()>>>,
+    worker: Arc<WorkerHandle>,
+    /// Restarts the worker thread in a forked child.
+    _fork_hook: Arc<ForkHook>,
     emitted: AtomicU64,
-    queue_dropped: AtomicU64,
+    queue: Arc<QueueStats>,
     sink_acknowledged: Arc<AtomicU64>,
     delivery_failed: Arc<AtomicU64>,
+    restarts: Arc<AtomicU64>,
 }
 
-pub struct OTLPHandlerConfig {
-    pub url: String,
-    pub headers: HashMap<String, String>,
-    pub service_name: String,
-    pub error_callback: Option<Py<PyAny>>,
-    pub overflow: OverflowStrategy,
+/// Worker-side connection to the pipe target. A write that fails because the target
+/// went away drops the connection; the next write reconnects (when `restart` is set),
+/// no sooner than `restart_delay` after the previous attempt.
+#[cfg(feature = "pipe")]
+struct PipeSink {
+    target: PipeTarget,
+    restart: bool,
+    restart_delay: Duration,
+    child: Option<std::process::Child>,
+    writer: Option<Box<dyn Write + Send>>,
+    last_attempt: Option<Instant>,
+    restarts: Arc<AtomicU64>,
 }
 
-impl OTLPHandler {
-    pub fn new(
-        url: String,
-        headers: HashMap<String, String>,
-        service_name: String,
-        capacity: usize,
-        batch_size: usize,
-        flush_interval: u64,
-        overflow: OverflowStrategy,
-    ) -> Self {
-        Self::with_config(
-            OTLPHandlerConfig {
-                url,
-                headers,
-                service_name,
-                error_callback: None,
-                overflow,
-            },
-            capacity,
-            batch_size,
-            flush_interval,
-        )
+#[cfg(feature = "pipe")]
+impl PipeSink {
+    fn describe(&self) -> String {
+        match self.target {
+            PipeTarget::Command(ref argv) => argv[0].clone(),
+            PipeTarget::Path(ref path) => path.display().to_string(),
+        }
     }
 
-    pub fn with_config(
-        config: OTLPHandlerConfig,
-        capacity: usize,
-        batch_size: usize,
-        flush_interval: u64,
-    ) -> Self {
-        let (s, r) = crossbeam_channel::bounded(capacity);
-        let drop_rx = r.clone();
+    fn connect(&mut self) -> Result<(), String> {
+        let exited = self
+            .child
+            .as_mut()
+            .is_some_and(|child| matches!(child.try_wait(), Ok(Some(_))));
+        if exited {
+            self.disconnect(Duration::ZERO);
+        }
+        if self.writer.is_some() {
+            return Ok(());
+        }
+        if let Some(last) = self.last_attempt {
+            if !self.restart {
+                return Err(format!(
+                    "{} is gone and restart is disabled",
+                    self.describe()
+                ));
+            }
+            std::thread::sleep(self.restart_delay.saturating_sub(last.elapsed()));
+            self.restarts.fetch_add(1, Ordering::Relaxed);
+        }
+        self.last_attempt = Some(Instant::now());
+
+        match self.target {
+            PipeTarget::Command(ref argv) => {
+                let mut child = std::process::Command::new(&argv[0])
+                    .args(&argv[1..])
+                    .stdin(std::process::Stdio::piped())
+                    .spawn()
+                    .map_err(|e| format!("failed to spawn {}: {e}", argv[0]))?;
+                self.writer = child
+                    .stdin
+                    .take()
+                    .map(|stdin| Box::new(stdin) as Box<dyn Write + Send>);
+                self.child = Some(child);
+            }
+            PipeTarget::Path(ref path) => {
+                // Opening a FIFO blocks until a reader is attached.
+                let file = OpenOptions::new()
+                    .append(true)
+                    .open(path)
+                    .map_err(|e| format!("failed to open {}: {e}", path.display()))?;
+                self.writer = Some(Box::new(file));
+            }
+        }
+        Ok(())
+    }
+
+    /// In a forked child, forget the parent's command and pipe without closing the
+    /// command down; the child's next write starts or opens its own.
+    fn leave_to_parent(&mut self) {
+        self.writer = None;
+        self.child = None;
+        self.last_attempt = None;
+    }
+
+    /// Close the pipe and reap the command, killing it if it is still running after
+    /// `grace`.
+    fn disconnect(&mut self, grace: Duration) {
+        self.writer = None;
+        let Some(mut child) = self.child.take() else {
+            return;
+        };
+        let deadline = Instant::now() + grace;
+        while matches!(child.try_wait(), Ok(None)) {
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let _ = child.wait();
+    }
+
+    fn write(&mut self, payload: &[u8]) -> Result<(), String> {
+        self.connect()?;
+        let Some(writer) = self.writer.as_mut() else {
+            return Err(format!("{} has no stdin", self.describe()));
+        };
+        if let Err(e) = writer.write_all(payload).and_then(|()| writer.flush()) {
+            self.disconnect(Duration::ZERO);
+            return Err(format!("write to {} failed: {e}", self.describe()));
+        }
+        Ok(())
+    }
+
+    /// Write `records` as NDJSON. A write that fails is retried once on a fresh
+    /// connection, so a restarted command receives the batch that found it gone.
+    fn deliver(&mut self, records: &[Arc<LogRecord>]) -> Result<(), String> {
+        let mut payload = Vec::new();
+        for rec in records {
+            let json = rec.to_json();
+            if !json.is_empty() {
+                payload.extend_from_slice(json.as_bytes());
+                payload.push(b'\n');
+            }
+        }
+        match self.write(&payload) {
+            Err(_) if self.restart => self.write(&payload),
+            result => result,
+        }
+    }
+}
+
+#[cfg(feature = "pipe")]
+impl PipeHandler {
+    pub fn with_config(config: PipeHandlerConfig, capacity: usize) -> Self {
+        let (s, r) = crate::queue::lanes::<Vec<Arc<LogRecord>>>(capacity);
+        let pending = r.clone();
         let (flush_tx, flush_rx) = crossbeam_channel::bounded::<()>(1);
         let (done_tx, done_rx) = crossbeam_channel::bounded::<()>(1);
         let shutdown = Arc::new(AtomicBool::new(false));
         let shutdown_clone = shutdown.clone();
 
-        let url = config.url;
-        let headers = config.headers;
-        let service_name = config.service_name;
-        let error_callback = config.error_callback;
-
         let sink_acknowledged = Arc::new(AtomicU64::new(0));
         let delivery_failed = Arc::new(AtomicU64::new(0));
+        let restarts = Arc::new(AtomicU64::new(0));
         let sink_ack_worker = sink_acknowledged.clone();
         let delivery_failed_worker = delivery_failed.clone();
 
-        let handle = std::thread::spawn(move || {
-            let mut buffer = Vec::with_capacity(batch_size);
-            let mut last_flush = std::time::Instant::now();
+        let queue = QueueStats::register("pipe", r.clone());
+        let worker_queue = queue.clone();
+        let spill = Arc::new(ShutdownSpill::new(config.spill_path));
+        let worker_spill = spill.clone();
+        let error_callback = config.error_callback;
 
-            let send = |buffer: &mut Vec<LogRecord>| {
-                Self::send_otlp_batch(
-                    &url,
-                    &headers,
-                    &service_name,
-                    &error_callback,
-                    buffer,
-                    &sink_ack_worker,
-                    &delivery_failed_worker,
-                );
+        let sink = Arc::new(parking_lot::Mutex::new(PipeSink {
+            target: config.target,
+            restart: config.restart,
+            restart_delay: config.restart_delay,
+            child: None,
+            writer: None,
+            last_attempt: None,
+            restarts: restarts.clone(),
+        }));
+        let worker_sink = sink.clone();
+
+        let fork_rx = r.clone();
+        let fork_flush_rx = flush_rx.clone();
+        let run = Arc::new(move || {
+            // Held for the life of the thread; a forked child releases it.
+            let mut sink = worker_sink.lock();
+            let mut buffer: Vec<Arc<LogRecord>> = Vec::with_capacity(PIPE_BATCH);
+
+            let send = |sink: &mut PipeSink, buffer: &mut Vec<Arc<LogRecord>>| {
+                if buffer.is_empty() {
+                    return;
+                }
+                let batch = std::mem::take(buffer);
+                if worker_spill.is_abandoned() {
+                    worker_spill.discard(batch);
+                    return;
+                }
+                let batch_len = batch.len() as u64;
+                match sink.deliver(&batch) {
+                    Ok(()) => {
+                        sink_ack_worker.fetch_add(batch_len, Ordering::Relaxed);
+                    }
+                    Err(e) => {
+                        delivery_failed_worker.fetch_add(batch_len, Ordering::Relaxed);
+                        if let Some(ref cb) = error_callback {
+                            Python::attach(|py| {
+                                let _ = cb.call1(py, (e,));
+                            });
+                        }
+                    }
+                }
             };
 
-            loop {
-                if matches!(flush_rx.try_recv(), Ok(())) {
-                    while let Ok(rec) = r.try_recv() {
-                        buffer.push(rec);
-                        if buffer.len() >= batch_size {
-                            send(&mut buffer);
-                        }
+            // Move everything already queued into `buffer`, writing every full batch.
+            let drain = |sink: &mut PipeSink, buffer: &mut Vec<Arc<LogRecord>>| {
+                while let Ok(item) = r.try_recv() {
+                    let (records, _) = worker_queue.dequeue(item);
+                    buffer.extend(records);
+                    if buffer.len() >= PIPE_BATCH {
+                        send(sink, buffer);
                     }
-                    send(&mut buffer);
-                    last_flush = std::time::Instant::now();
+                }
+                send(sink, buffer);
+            };
+
+            loop {
+                if flush_rx.try_recv().is_ok() {
+                    drain(&mut sink, &mut buffer);
                     let _ = done_tx.try_send(());
                 }
 
                 if shutdown_clone.load(Ordering::Relaxed) {
-                    while let Ok(rec) = r.try_recv() {
-                        buffer.push(rec);
-                        if buffer.len() >= batch_size {
-                            send(&mut buffer);
-                        }
-                    }
-                    send(&mut buffer);
-                    let _ = done_tx.try_send(());
                     break;
                 }
 
                 match r.recv_timeout(Duration::from_millis(100)) {
-                    Ok(rec) => {
-                        buffer.push(rec);
-                        if buffer.len() >= batch_size {
-                            send(&mut buffer);
-                            last_flush = std::time::Instant::now();
-                        }
-                    }
-                    Err(crossbeam_channel::RecvTimeoutError::Timeout) => {
-                        if !buffer.is_empty() && last_flush.elapsed().as_secs() >= flush_interval {
-                            send(&mut buffer);
-                            last_flush = std::time::Instant::now();
-                        }
-                    }
-                    Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
-                        while let Ok(rec) = r.try_recv() {
-                            buffer.push(rec);
-                            if buffer.len() >= batch_size {
-                                send(&mut buffer);
-                            }
-                        }
-                        send(&mut buffer);
-                        let _ = done_tx.try_send(());
-                        break;
+                    // Records are streamed as they arrive; whatever queued up while
+                    // the previous write blocked goes out together.
+                    Ok(item) => {
+                        let (records, _) = worker_queue.dequeue(item);
+                        buffer.extend(records);
+                        drain(&mut sink, &mut buffer);
                     }
+                    Err(crossbeam_channel::RecvTimeoutError::Timeout) => {}
+                    Err(crossbeam_channel::RecvTimeoutError::Disconnected) => break,
                 }
             }
+            drain(&mut sink, &mut buffer);
+            sink.disconnect(PIPE_EXIT_GRACE);
+            let _ = done_tx.try_send(());
         });
+        let spawn = move || {
+            let run = run.clone();
+            crate::threads::spawn("pipe", move || run())
+                .expect("Failed to spawn pipe handler thread")
+        };
+        let handle = spawn();
+
+        let worker = WorkerHandle::register(
+            WorkerConfig {
+                kind: "PipeHandler",
+                stop: shutdown,
+                wake: flush_tx.clone(),
+                pending,
+                spill,
+                shutdown_timeout: config.shutdown_timeout,
+                daemon: config.daemon,
+            },
+            handle,
+        );
+        let fork_hook = restart_worker_after_fork(
+            &worker,
+            &queue,
+            fork_rx,
+            fork_flush_rx,
+            done_rx.clone(),
+            move || {
+                crate::fork::unlock_in_child(&sink);
+                sink.lock().leave_to_parent();
+                spawn()
+            },
+        );
 
         Self {
-            sender: s,
-            drop_rx,
+            lanes: s,
             flush_signal: flush_tx,
             flush_done: done_rx,
-            level: AtomicU8::new(LogLevel::Debug as u8),
-            shutdown,
-            stopped: AtomicBool::new(false),
+            level: AtomicU32::new(LogLevel::NotSet.value()),
+            dispatch_mode: AtomicU8::new(DispatchMode::Native as u8),
+            filters: FilterSlot::default(),
             overflow: config.overflow,
             flush_timeout: DEFAULT_FLUSH_TIMEOUT,
-            join_handle: Mutex::new(Some(handle)),
+            worker,
+            _fork_hook: fork_hook,
             emitted: AtomicU64::new(0),
-            queue_dropped: AtomicU64::new(0),
+            queue,
             sink_acknowledged,
             delivery_failed,
+            restarts,
         }
     }
 
-    fn enqueue(&self, record: LogRecord) {
-        match self.overflow {
-            OverflowStrategy::DropNewest => {
-                if self.sender.try_send(record).is_err() {
-                    self.queue_dropped.fetch_add(1, Ordering::Relaxed);
-                }
-            }
-            OverflowStrategy::DropOldest => {
-                let mut record = record;
-                loop {
-                    match self.sender.try_send(record) {
-                        Ok(()) => break,
-                        Err(crossbeam_channel::TrySendError::Full(returned)) => {
-                            if self.drop_rx.try_recv().is_ok() {
-                                self.queue_dropped.fetch_add(1, Ordering::Relaxed);
-                            }
-                            record = returned;
-                        }
-                        Err(crossbeam_channel::TrySendError::Disconnected(_)) => {
-                            self.queue_dropped.fetch_add(1, Ordering::Relaxed);
-                            break;
-                        }
-                    }
-                }
-            }
-            OverflowStrategy::Block => {
-                // See HTTPHandler::enqueue: true blocking send on the detached path (§4),
-                // bounded send_timeout on the attached path to avoid same-GIL deadlock.
-                if block_can_wait() {
-                    if self.sender.send(record).is_err() {
-                        self.queue_dropped.fetch_add(1, Ordering::Relaxed);
-                    }
-                } else if self
-                    .sender
-                    .send_timeout(record, self.flush_timeout)
-                    .is_err()
-                {
-                    self.queue_dropped.fetch_add(1, Ordering::Relaxed);
-                }
-            }
-        }
-    }
-
-    fn send_otlp_batch(
-        url: &str,
-        headers: &HashMap<String, String>,
-        service_name: &str,
-        error_callback: &Option<Py<PyAny>>,
-        buffer: &mut Vec<LogRecord>,
-        sink_acknowledged: &AtomicU64,
-        delivery_failed: &AtomicU64,
-    ) {
-        use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, KeyValue};
-        use opentelemetry_proto::tonic::logs::v1::{
-            LogRecord as OtlpLogRecord, ResourceLogs, ScopeLogs,
-        };
-        use opentelemetry_proto::tonic::resource::v1::Resource;
-        use prost::Message;
-
-        if buffer.is_empty() {
-            return;
-        }
-
-        let batch = std::mem::take(buffer);
-        let batch_len = batch.len() as u64;
-
-        let log_records: Vec<OtlpLogRecord> = batch
-            .iter()
-            .map(|rec| {
-                OtlpLogRecord {
-                    time_unix_nano: (rec.created * 1_000_000_000.0) as u64,
-                    observed_time_unix_nano: (rec.created * 1_000_000_000.0) as u64,
-                    severity_number: match rec.levelno {
-                        10 => 5,  // DEBUG
-                        20 => 9,  // INFO
-                        30 => 13, // WARN
-                        40 => 17, // ERROR
-                        50 => 21, // FATAL
-                        _ => 0,
-                    },
-                    severity_text: rec.levelname.clone(),
-                    body: Some(AnyValue {
-                        value: Some(any_value::Value::StringValue(rec.msg.clone())),
-                    }),
-                    attributes: vec![
-                        KeyValue {
-                            key: "logger.name".to_string(),
-                            value: Some(AnyValue {
-                                value: Some(any_value::Value::StringValue(rec.name.clone())),
-                            }),
-                        },
-                        KeyValue {
-                            key: "code.filepath".to_string(),
-                            value: Some(AnyValue {
-                                value: Some(any_value::Value::StringValue(rec.pathname.clone())),
-                            }),
-                        },
-                        KeyValue {
-                            key: "code.lineno".to_string(),
-                            value: Some(AnyValue {
-                                value: Some(any_value::Value::IntValue(rec.lineno as i64)),
-                            }),
-                        },
-                        KeyValue {
-                            key: "code.function".to_string(),
-                            value: Some(AnyValue {
-                                value: Some(any_value::Value::StringValue(rec.func_name.clone())),
-                            }),
-                        },
-                    ],
-                    ..Default::default()
-                }
-            })
-            .collect();
-
-        let resource_logs = ResourceLogs {
-            resource: Some(Resource {
-                attributes: vec![KeyValue {
-                    key: "service.name".to_string(),
-                    value: Some(AnyValue {
-                        value: Some(any_value::Value::StringValue(service_name.to_string())),
-                    }),
-                }],
-                ..Default::default()
-            }),
-            scope_logs: vec![ScopeLogs {
-                log_records,
-                ..Default::default()
-            }],
-            ..Default::default()
-        };
-
-        let payload = resource_logs.encode_to_vec();
-
-        let mut request = ureq::post(url).set("Content-Type", "application/x-protobuf");
-        for (key, value) in headers {
-            request = request.set(key, value);
-        }
-
-        match request.send_bytes(&payload) {
-            Ok(_) => {
-                sink_acknowledged.fetch_add(batch_len, Ordering::Relaxed);
-            }
-            Err(e) => {
-                delivery_failed.fetch_add(batch_len, Ordering::Relaxed);
-                if let Some(ref cb) = error_callback {
-                    Python::attach(|py| {
-                        let _ = cb.call1(py, (e.to_string(),));
-                    });
-                }
-            }
-        }
+    /// Enqueue records as one queue item honoring the configured overflow strategy,
+    /// counting drops.
+    fn enqueue(&self, records: Vec<Arc<LogRecord>>) {
+        crate::queue::enqueue(
+            &self.lanes,
+            self.overflow,
+            self.flush_timeout,
+            &self.queue,
+            Queued::records(records),
+        );
     }
 
     pub fn flush(&self) {
+        let started = Instant::now();
         let _ = self.flush_signal.try_send(());
         let _ = self.flush_done.recv_timeout(self.flush_timeout);
+        self.queue.observe_flush(started);
     }
 
     pub fn shutdown(&self) {
-        if self.stopped.swap(true, Ordering::SeqCst) {
-            return;
-        }
-        self.shutdown.store(true, Ordering::Relaxed);
-        let _ = self.flush_signal.try_send(());
-        if let Some(handle) = self.join_handle.lock().unwrap().take() {
-            let _ = handle.join();
-        }
+        self.worker.shutdown();
+    }
+
+    /// Records dropped / spilled to disk because the worker was abandoned.
+    pub fn shutdown_snapshot(&self) -> (u64, u64) {
+        self.worker.shutdown_snapshot()
+    }
+
+    pub fn is_daemon(&self) -> bool {
+        self.worker.is_daemon()
     }
 
     pub fn metrics_snapshot(&self) -> (u64, u64, u64, u64) {
         (
             self.emitted.load(Ordering::Relaxed),
             self.sink_acknowledged.load(Ordering::Relaxed),
-            self.queue_dropped.load(Ordering::Relaxed),
+            self.queue.dropped(),
             self.delivery_failed.load(Ordering::Relaxed),
         )
     }
 
+    /// Times the command was respawned (or the path reopened) after the first attempt.
+    pub fn restarts(&self) -> u64 {
+        self.restarts.load(Ordering::Relaxed)
+    }
+
+    pub fn queue_stats(&self) -> &QueueStats {
+        &self.queue
+    }
+
     pub fn set_level(&self, level: LogLevel) {
-        self.level.store(level as u8, Ordering::Relaxed);
+        self.level.store(level.value(), Ordering::Relaxed);
     }
 }
 
-impl Handler for OTLPHandler {
-    fn emit(&self, record: &LogRecord) {
+#[cfg(feature = "pipe")]
+impl Handler for PipeHandler {
+    fn emit(&self, record: &Arc<LogRecord>) {
         let level = self.level.load(Ordering::Relaxed);
         if record.levelno < level as i32 {
             return;
         }
-        self.emitted.fetch_add(1, Ordering::Relaxed);
-        self.enqueue(record.clone());
+        let Some(record) = self.filters.apply_shared(record) else {
+            return;
+        };
+        self.emitted.fetch_add(1, Ordering::Relaxed);
+        self.enqueue(vec![record.into_owned()]);
+    }
+
+    fn emit_batch(&self, records: &[Arc<LogRecord>]) {
+        let level = self.level.load(Ordering::Relaxed);
+        let batch: Vec<Arc<LogRecord>> = records
+            .iter()
+            .filter(|r| r.levelno >= level as i32)
+            .filter_map(|r| self.filters.apply_shared(r))
+            .map(Cow::into_owned)
+            .collect();
+        if batch.is_empty() {
+            return;
+        }
+        self.emitted
+            .fetch_add(batch.len() as u64, Ordering::Relaxed);
+        self.enqueue(batch);
+    }
+
+    fn level(&self) -> u32 {
+        self.level.load(Ordering::Relaxed)
     }
 
     fn flush(&self) {
-        OTLPHandler::flush(self);
+        PipeHandler::flush(self);
     }
 
     fn shutdown(&self) {
-        OTLPHandler::shutdown(self);
+        PipeHandler::shutdown(self);
+    }
+
+    fn dispatch_mode(&self) -> DispatchMode {
+        DispatchMode::from_u8(self.dispatch_mode.load(Ordering::Relaxed))
+    }
+
+    fn set_dispatch_mode(&self, mode: DispatchMode) {
+        self.dispatch_mode.store(mode as u8, Ordering::Relaxed);
+    }
+
+    fn filters(&self) -> Option<&FilterSlot> {
+        Some(&self.filters)
     }
 
     fn set_formatter(&mut self, _: Arc<dyn Formatter + Send + Sync>) {}
     fn add_filter(&mut self, _: Arc<dyn Filter + Send + Sync>) {}
 }
 
-impl Drop for OTLPHandler {
+#[cfg(feature = "pipe")]
+impl Drop for PipeHandler {
     fn drop(&mut self) {
-        // See HTTPHandler::drop — never join under the GIL; signal + channel disconnect
-        // terminate the worker, explicit shutdown() (via py.detach) joins.
-        self.shutdown.store(true, Ordering::Relaxed);
-        let _ = self.flush_signal.try_send(());
+        // Same as HTTPHandler: never join under a possibly held GIL.
+        self.worker.signal_stop();
     }
 }
 
@@ -1409,8 +5553,10 @@ impl Drop for OTLPHandler {
 /// - `get_text()` - Returns all captured messages as a single string
 /// - `get_record_tuples()` - Returns (logger_name, level, message) tuples
 pub struct MemoryHandler {
-    records: Arc<parking_lot::Mutex<Vec<LogRecord>>>,
-    level: AtomicU8,
+    records: Arc<parking_lot::Mutex<Vec<Arc<LogRecord>>>>,
+    level: AtomicU32,
+    dispatch_mode: AtomicU8,
+    filters: FilterSlot,
     formatter: parking_lot::Mutex<Option<Arc<dyn Formatter + Send + Sync>>>,
 }
 
@@ -1418,13 +5564,15 @@ impl MemoryHandler {
     pub fn new() -> Self {
         Self {
             records: Arc::new(parking_lot::Mutex::new(Vec::new())),
-            level: AtomicU8::new(LogLevel::Debug as u8),
+            level: AtomicU32::new(LogLevel::NotSet.value()),
+            dispatch_mode: AtomicU8::new(DispatchMode::Native as u8),
+            filters: FilterSlot::default(),
             formatter: parking_lot::Mutex::new(None),
         }
     }
 
     /// Returns all captured log records.
-    pub fn get_records(&self) -> Vec<LogRecord> {
+    pub fn get_records(&self) -> Vec<Arc<LogRecord>> {
         self.records.lock().clone()
     }
 
@@ -1465,7 +5613,7 @@ impl MemoryHandler {
     }
 
     pub fn set_level(&self, level: LogLevel) {
-        self.level.store(level as u8, Ordering::Relaxed);
+        self.level.store(level.value(), Ordering::Relaxed);
     }
 
     /// Set a formatter for this handler.
@@ -1487,16 +5635,35 @@ impl MemoryHandler {
 }
 
 impl Handler for MemoryHandler {
-    fn emit(&self, record: &LogRecord) {
+    fn emit(&self, record: &Arc<LogRecord>) {
         let level = self.level.load(Ordering::Relaxed);
         if record.levelno < level as i32 {
             return;
         }
-        self.records.lock().push(record.clone());
+        let Some(record) = self.filters.apply_shared(record) else {
+            return;
+        };
+        self.records.lock().push(record.into_owned());
+    }
+
+    fn level(&self) -> u32 {
+        self.level.load(Ordering::Relaxed)
     }
 
     fn flush(&self) {}
 
+    fn dispatch_mode(&self) -> DispatchMode {
+        DispatchMode::from_u8(self.dispatch_mode.load(Ordering::Relaxed))
+    }
+
+    fn set_dispatch_mode(&self, mode: DispatchMode) {
+        self.dispatch_mode.store(mode as u8, Ordering::Relaxed);
+    }
+
+    fn filters(&self) -> Option<&FilterSlot> {
+        Some(&self.filters)
+    }
+
     fn set_formatter(&mut self, formatter: Arc<dyn Formatter + Send + Sync>) {
         *self.formatter.lock() = Some(formatter);
     }
@@ -1509,3 +5676,605 @@ impl Default for MemoryHandler {
         Self::new()
     }
 }
+
+// ============================================================================
+// QueueHandler / QueueListener — explicit producer/consumer decoupling
+// ============================================================================
+
+/// An item in a QueueHandler's queue.
+pub enum QueueItem {
+    Records(Vec<Arc<LogRecord>>),
+    /// Deliver everything queued before it, flush the sinks, then signal.
+    Flush(crossbeam_channel::Sender<()>),
+    /// Deliver everything queued before it, then stop the listener that takes it.
+    Stop,
+}
+
+/// The bounded queue a QueueHandler fills and QueueListeners drain.
+pub struct RecordQueue {
+    tx: LaneSender<QueueItem>,
+    rx: LaneReceiver<QueueItem>,
+    stats: Arc<QueueStats>,
+    /// Listeners currently running on this queue.
+    listeners: AtomicUsize,
+}
+
+/// Pushes records onto a bounded queue; a QueueListener hands them to its sinks on
+/// its own thread. Holds no formatter: sinks format. Python dispatch mode is only
+/// used so the wrapper can run its filters first.
+pub struct QueueHandler {
+    queue: Arc<RecordQueue>,
+    /// Empties the queue in a forked child.
+    _fork_hook: Arc<ForkHook>,
+    level: AtomicU32,
+    dispatch_mode: AtomicU8,
+    filters: FilterSlot,
+    overflow: OverflowStrategy,
+    flush_timeout: Duration,
+    emitted: AtomicU64,
+}
+
+impl QueueHandler {
+    pub fn new(capacity: usize, overflow: OverflowStrategy) -> Self {
+        let (tx, rx) = crate::queue::lanes::<QueueItem>(capacity);
+        let stats = QueueStats::register("queue", rx.clone());
+        let queue = Arc::new(RecordQueue {
+            tx,
+            rx,
+            stats,
+            listeners: AtomicUsize::new(0),
+        });
+        let fork_queue = queue.clone();
+        // The parent's listener delivers what it had queued, and its listener threads
+        // did not come along.
+        let fork_hook = ForkHook::default()
+            .with_in_child(move || {
+                fork_queue.stats.reset_after_fork(&fork_queue.rx);
+                fork_queue.listeners.store(0, Ordering::Release);
+            })
+            .register();
+        Self {
+            queue,
+            _fork_hook: fork_hook,
+            level: AtomicU32::new(LogLevel::NotSet.value()),
+            dispatch_mode: AtomicU8::new(DispatchMode::Native as u8),
+            filters: FilterSlot::default(),
+            overflow,
+            flush_timeout: DEFAULT_FLUSH_TIMEOUT,
+            emitted: AtomicU64::new(0),
+        }
+    }
+
+    pub fn queue(&self) -> Arc<RecordQueue> {
+        self.queue.clone()
+    }
+
+    pub fn set_level(&self, level: LogLevel) {
+        self.level.store(level.value(), Ordering::Relaxed);
+    }
+
+    pub fn emitted(&self) -> u64 {
+        self.emitted.load(Ordering::Relaxed)
+    }
+
+    pub fn queue_stats(&self) -> &QueueStats {
+        &self.queue.stats
+    }
+}
+
+impl Handler for QueueHandler {
+    fn emit(&self, record: &Arc<LogRecord>) {
+        let level = self.level.load(Ordering::Relaxed);
+        if record.levelno < level as i32 {
+            return;
+        }
+        let Some(record) = self.filters.apply_shared(record) else {
+            return;
+        };
+        let record = &*record;
+        self.emitted.fetch_add(1, Ordering::Relaxed);
+        crate::queue::enqueue(
+            &self.queue.tx,
+            self.overflow,
+            self.flush_timeout,
+            &self.queue.stats,
+            Queued::new(QueueItem::Records(vec![record.clone()]), record),
+        );
+    }
+
+    fn emit_batch(&self, records: &[Arc<LogRecord>]) {
+        let level = self.level.load(Ordering::Relaxed);
+        let Some(first) = records.first() else {
+            return;
+        };
+        if first.levelno < level as i32 {
+            return;
+        }
+        let records: Vec<Arc<LogRecord>> = records
+            .iter()
+            .filter_map(|r| self.filters.apply_shared(r))
+            .map(Cow::into_owned)
+            .collect();
+        let count = records.len();
+        if count == 0 {
+            return;
+        }
+        self.emitted.fetch_add(count as u64, Ordering::Relaxed);
+        crate::queue::enqueue(
+            &self.queue.tx,
+            self.overflow,
+            self.flush_timeout,
+            &self.queue.stats,
+            Queued::batch(QueueItem::Records(records), first, count),
+        );
+    }
+
+    fn level(&self) -> u32 {
+        self.level.load(Ordering::Relaxed)
+    }
+
+    /// Wait until a running listener has delivered everything queued so far and
+    /// flushed its sinks. Returns at once when no listener is running.
+    fn flush(&self) {
+        if self.queue.listeners.load(Ordering::Acquire) == 0 {
+            return;
+        }
+        let started = Instant::now();
+        let (done_tx, done_rx) = crossbeam_channel::bounded::<()>(1);
+        if self
+            .queue
+            .tx
+            .send_control(QueueItem::Flush(done_tx), self.flush_timeout)
+        {
+            let _ = done_rx.recv_timeout(self.flush_timeout);
+        }
+        self.queue.stats.observe_flush(started);
+    }
+
+    fn dispatch_mode(&self) -> DispatchMode {
+        DispatchMode::from_u8(self.dispatch_mode.load(Ordering::Relaxed))
+    }
+
+    fn set_dispatch_mode(&self, mode: DispatchMode) {
+        self.dispatch_mode.store(mode as u8, Ordering::Relaxed);
+    }
+
+    fn filters(&self) -> Option<&FilterSlot> {
+        Some(&self.filters)
+    }
+
+    fn set_formatter(&mut self, _: Arc<dyn Formatter + Send + Sync>) {}
+
+    fn add_filter(&mut self, _: Arc<dyn Filter + Send + Sync>) {}
+}
+
+/// Where a QueueListener delivers records.
+pub enum ListenerSink {
+    /// A Rust handler. `wrapper` is its Python wrapper, whose `handle()` runs instead
+    /// while the handler is in Python dispatch mode.
+    Rust {
+        arc: Arc<dyn Handler + Send + Sync>,
+        wrapper: Option<Py<PyAny>>,
+    },
+    /// Any other Python handler, called through `handle()`.
+    Python(Py<PyAny>),
+}
+
+/// Drains a RecordQueue on a background thread, handing each record to every sink.
+pub struct QueueListener {
+    queue: Arc<RecordQueue>,
+    sinks: Arc<Vec<ListenerSink>>,
+    respect_handler_level: bool,
+    worker: Arc<parking_lot::Mutex<Option<std::thread::JoinHandle<()>>>>,
+    /// Starts the thread again in a forked child when it was running.
+    _fork_hook: Arc<ForkHook>,
+}
+
+impl QueueListener {
+    pub fn new(
+        queue: Arc<RecordQueue>,
+        sinks: Vec<ListenerSink>,
+        respect_handler_level: bool,
+    ) -> Self {
+        let sinks = Arc::new(sinks);
+        let worker = Arc::new(parking_lot::Mutex::new(None));
+        let fork_hook = {
+            let queue = queue.clone();
+            let sinks = sinks.clone();
+            let worker = worker.clone();
+            ForkHook::default()
+                .with_in_child(move || {
+                    crate::fork::unlock_in_child(&worker);
+                    let mut worker = worker.lock();
+                    // The parent's handle names a thread that does not exist here.
+                    if let Some(parent) = worker.take() {
+                        std::mem::forget(parent);
+                        *worker = Self::spawn(&queue, &sinks, respect_handler_level);
+                    }
+                })
+                .register()
+        };
+        Self {
+            queue,
+            sinks,
+            respect_handler_level,
+            worker,
+            _fork_hook: fork_hook,
+        }
+    }
+
+    /// Start the listener thread. Returns false if it is already running.
+    pub fn start(&self) -> bool {
+        let mut worker = self.worker.lock();
+        if worker.is_some() {
+            return false;
+        }
+        *worker = Self::spawn(&self.queue, &self.sinks, self.respect_handler_level);
+        worker.is_some()
+    }
+
+    fn spawn(
+        queue: &Arc<RecordQueue>,
+        sinks: &Arc<Vec<ListenerSink>>,
+        respect_handler_level: bool,
+    ) -> Option<std::thread::JoinHandle<()>> {
+        let queue = queue.clone();
+        let sinks = sinks.clone();
+        queue.listeners.fetch_add(1, Ordering::AcqRel);
+        let spawned = crate::threads::spawn("listener", {
+            let queue = queue.clone();
+            move || {
+                Self::run(&queue, &sinks, respect_handler_level);
+                queue.listeners.fetch_sub(1, Ordering::AcqRel);
+            }
+        });
+        if spawned.is_err() {
+            queue.listeners.fetch_sub(1, Ordering::AcqRel);
+        }
+        spawned.ok()
+    }
+
+    /// Deliver everything queued so far, then stop the thread and wait for it.
+    /// No-op when the listener is not running.
+    pub fn stop(&self) {
+        let Some(handle) = self.worker.lock().take() else {
+            return;
+        };
+        // The listener keeps draining, so room for the marker turns up.
+        while !self
+            .queue
+            .tx
+            .send_control(QueueItem::Stop, Duration::from_millis(100))
+        {
+            if handle.is_finished() {
+                break;
+            }
+        }
+        let _ = handle.join();
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.worker.lock().is_some()
+    }
+
+    pub fn respect_handler_level(&self) -> bool {
+        self.respect_handler_level
+    }
+
+    fn run(queue: &RecordQueue, sinks: &[ListenerSink], respect_handler_level: bool) {
+        loop {
+            let item = match queue.rx.recv_timeout(Duration::from_millis(100)) {
+                Ok(item) => queue.stats.dequeue(item).0,
+                Err(crossbeam_channel::RecvTimeoutError::Timeout) => continue,
+                Err(crossbeam_channel::RecvTimeoutError::Disconnected) => return,
+            };
+            match item {
+                QueueItem::Records(records) => {
+                    Self::deliver(sinks, &records, respect_handler_level)
+                }
+                QueueItem::Flush(done) => {
+                    Self::flush_sinks(sinks);
+                    let _ = done.try_send(());
+                }
+                QueueItem::Stop => {
+                    Self::flush_sinks(sinks);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Native sinks emit without the GIL (and apply their own level, as always);
+    /// Python sinks get one stdlib record per record through `handle()`.
+    fn deliver(sinks: &[ListenerSink], records: &[Arc<LogRecord>], respect_handler_level: bool) {
+        let mut python: Vec<&Py<PyAny>> = Vec::new();
+        for sink in sinks {
+            match sink {
+                ListenerSink::Rust {
+                    wrapper: Some(w),
+                    arc,
+                } if arc.dispatch_mode() == DispatchMode::Python => python.push(w),
+                ListenerSink::Rust { arc, .. } => arc.emit_batch(records),
+                ListenerSink::Python(handler) => python.push(handler),
+            }
+        }
+        if python.is_empty() {
+            return;
+        }
+        Python::attach(|py| {
+            for record in records {
+                let Ok(py_record) = crate::py_logger::python_record(py, record, None) else {
+                    continue;
+                };
+                for handler in python.iter() {
+                    let handler = handler.bind(py);
+                    if respect_handler_level {
+                        let level = handler
+                            .getattr("level")
+                            .and_then(|l| l.extract::<i32>())
+                            .unwrap_or(0);
+                        if record.levelno < level {
+                            continue;
+                        }
+                    }
+                    let _ = handler.call_method1("handle", (&py_record,));
+                }
+            }
+        });
+    }
+
+    fn flush_sinks(sinks: &[ListenerSink]) {
+        let mut python: Vec<&Py<PyAny>> = Vec::new();
+        for sink in sinks {
+            match sink {
+                ListenerSink::Rust { arc, .. } => arc.flush(),
+                ListenerSink::Python(handler) => python.push(handler),
+            }
+        }
+        if python.is_empty() {
+            return;
+        }
+        Python::attach(|py| {
+            for handler in python {
+                let _ = handler.bind(py).call_method0("flush");
+            }
+        });
+    }
+}
+
+impl Drop for QueueListener {
+    /// Ask a thread that is still running to finish the queue and exit, without
+    /// waiting for it: the GIL may be held here, and Python sinks need it.
+    fn drop(&mut self) {
+        if self.worker.lock().take().is_some() {
+            let _ = self.queue.tx.send_control(QueueItem::Stop, Duration::ZERO);
+        }
+    }
+}
+
+// ============================================================================
+// AggregatingHandler — one summary record per message template and window
+// ============================================================================
+
+/// Records of one (logger, level, template) seen in the current window.
+struct AggregateBucket {
+    /// The first record, which the summary is built from.
+    record: LogRecord,
+    template: String,
+    count: u64,
+    last_created: f64,
+}
+
+#[derive(Default)]
+struct AggregateWindow {
+    index: std::collections::HashMap<(String, i32, String), usize>,
+    /// In order of first appearance.
+    buckets: Vec<AggregateBucket>,
+}
+
+/// What the window timer shares with the handler.
+struct AggregateState {
+    window: Duration,
+    current: parking_lot::Mutex<AggregateWindow>,
+    sinks: Vec<ListenerSink>,
+}
+
+impl AggregateState {
+    /// Close the current window: hand one record per bucket to the sinks.
+    fn emit_window(&self) {
+        let window = std::mem::take(&mut *self.current.lock());
+        if window.buckets.is_empty() {
+            return;
+        }
+        let records: Vec<Arc<LogRecord>> = window
+            .buckets
+            .into_iter()
+            .map(|bucket| Arc::new(self.summary(bucket)))
+            .collect();
+        QueueListener::deliver(&self.sinks, &records, true);
+    }
+
+    /// The bucket's first record, or for repeats a summary of them with the count in
+    /// the message. Both carry the counts as `aggregate_*` extras.
+    fn summary(&self, bucket: AggregateBucket) -> LogRecord {
+        let AggregateBucket {
+            mut record,
+            template,
+            count,
+            last_created,
+        } = bucket;
+        let first_created = record.created;
+        if count > 1 {
+            record.msg = format!(
+                "{template} (logged {count} times in {}s)",
+                self.window.as_secs_f64()
+            );
+            record.args = None;
+            record.exc_info = None;
+            record.exc_text = None;
+            record.stack_info = None;
+        }
+        let extra = record.extra.get_or_insert_with(Default::default);
+        extra.insert("aggregate_count".to_string(), count.into());
+        extra.insert("aggregate_template".to_string(), template.into());
+        extra.insert("aggregate_first".to_string(), first_created.into());
+        extra.insert("aggregate_last".to_string(), last_created.into());
+        record
+    }
+}
+
+/// Counts records per (logger, level, message template) over a tumbling window and
+/// hands its sinks one record per combination when the window closes, instead of
+/// every record. A background timer closes each window; `shutdown` closes the last
+/// one early.
+///
+/// The template is the record's `message_template` extra when message templates are
+/// enabled, so pre-formatted messages that differ only in numbers or ids share a
+/// bucket; otherwise it is the unformatted `msg`.
+pub struct AggregatingHandler {
+    state: Arc<AggregateState>,
+    level: AtomicU32,
+    dispatch_mode: AtomicU8,
+    filters: FilterSlot,
+    /// Dropping the sender stops the timer thread.
+    timer: Arc<parking_lot::Mutex<Option<AggregateTimer>>>,
+    /// Restarts the timer in a forked child.
+    _fork_hook: Arc<ForkHook>,
+}
+
+type AggregateTimer = (crossbeam_channel::Sender<()>, std::thread::JoinHandle<()>);
+
+impl AggregatingHandler {
+    pub fn new(window: Duration, sinks: Vec<ListenerSink>) -> std::io::Result<Self> {
+        let state = Arc::new(AggregateState {
+            window,
+            current: parking_lot::Mutex::new(AggregateWindow::default()),
+            sinks,
+        });
+        let timer = Arc::new(parking_lot::Mutex::new(Some(Self::spawn_timer(&state)?)));
+        let fork_hook = {
+            let state = state.clone();
+            let timer = timer.clone();
+            ForkHook::default()
+                .with_in_child(move || {
+                    // The parent reports the window that was open at the fork.
+                    crate::fork::unlock_in_child(&state.current);
+                    *state.current.lock() = AggregateWindow::default();
+                    crate::fork::unlock_in_child(&timer);
+                    let mut timer = timer.lock();
+                    // The parent's handle names a thread that does not exist here.
+                    if let Some(parent) = timer.take() {
+                        std::mem::forget(parent);
+                        *timer = Self::spawn_timer(&state).ok();
+                    }
+                })
+                .register()
+        };
+        Ok(Self {
+            state,
+            level: AtomicU32::new(LogLevel::NotSet.value()),
+            dispatch_mode: AtomicU8::new(DispatchMode::Native as u8),
+            filters: FilterSlot::default(),
+            timer,
+            _fork_hook: fork_hook,
+        })
+    }
+
+    fn spawn_timer(state: &Arc<AggregateState>) -> std::io::Result<AggregateTimer> {
+        let (stop_tx, stop_rx) = crossbeam_channel::bounded::<()>(0);
+        let timer_state = state.clone();
+        let timer = crate::threads::spawn("aggregate", move || {
+            while let Err(crossbeam_channel::RecvTimeoutError::Timeout) =
+                stop_rx.recv_timeout(timer_state.window)
+            {
+                timer_state.emit_window();
+            }
+        })?;
+        Ok((stop_tx, timer))
+    }
+
+    pub fn set_level(&self, level: LogLevel) {
+        self.level.store(level.value(), Ordering::Relaxed);
+    }
+
+    pub fn window(&self) -> Duration {
+        self.state.window
+    }
+}
+
+impl Handler for AggregatingHandler {
+    fn emit(&self, record: &Arc<LogRecord>) {
+        if record.levelno < self.level.load(Ordering::Relaxed) as i32 {
+            return;
+        }
+        let Some(record) = self.filters.apply(record) else {
+            return;
+        };
+        let record = &*record;
+        let mut current = self.state.current.lock();
+        let AggregateWindow { index, buckets } = &mut *current;
+        let template = match record
+            .extra
+            .as_ref()
+            .and_then(|extra| extra.get("message_template"))
+        {
+            Some(serde_json::Value::String(template)) => template.clone(),
+            _ => record.msg.clone(),
+        };
+        let key = (record.name.clone(), record.levelno, template);
+        match index.get(&key) {
+            Some(&i) => {
+                let bucket = &mut buckets[i];
+                bucket.count += 1;
+                bucket.last_created = record.created;
+            }
+            None => {
+                let template = key.2.clone();
+                index.insert(key, buckets.len());
+                buckets.push(AggregateBucket {
+                    // `summary` rewrites it, so it must not share the record's encodings.
+                    record: record.detached(),
+                    template,
+                    count: 1,
+                    last_created: record.created,
+                });
+            }
+        }
+    }
+
+    fn level(&self) -> u32 {
+        self.level.load(Ordering::Relaxed)
+    }
+
+    /// Flush the sinks; the open window keeps counting.
+    fn flush(&self) {
+        QueueListener::flush_sinks(&self.state.sinks);
+    }
+
+    /// Stop the timer, then emit the open window early and flush the sinks.
+    fn shutdown(&self) {
+        let Some((stop, timer)) = self.timer.lock().take() else {
+            return;
+        };
+        drop(stop);
+        let _ = timer.join();
+        self.state.emit_window();
+        QueueListener::flush_sinks(&self.state.sinks);
+    }
+
+    fn dispatch_mode(&self) -> DispatchMode {
+        DispatchMode::from_u8(self.dispatch_mode.load(Ordering::Relaxed))
+    }
+
+    fn set_dispatch_mode(&self, mode: DispatchMode) {
+        self.dispatch_mode.store(mode as u8, Ordering::Relaxed);
+    }
+
+    fn filters(&self) -> Option<&FilterSlot> {
+        Some(&self.filters)
+    }
+
+    fn set_formatter(&mut self, _: Arc<dyn Formatter + Send + Sync>) {}
+
+    fn add_filter(&mut self, _: Arc<dyn Filter + Send + Sync>) {}
+}
@@ -0,0 +1,191 @@
+//! # Index Sidecars
+//!
+//! Finding the records of the last hour in a log file of several gigabytes means
+//! parsing every line before them. With `index_every=N`, a file handler writes a
+//! sparse index next to the log, `app.log.idx`, with a line for every Nth record:
+//!
+//! ```text
+//! 1714557600.123456 8412
+//! ```
+//!
+//! the record's `created` time and the byte offset in the log where its line starts.
+//! Given `since` or `until`, the readers (`read_log_file()`, `replay_log_file()`,
+//! `convert_log_file()` and the `python -m logxide` commands) binary-search the index
+//! and read only the stretch of the file between the two entries around the range,
+//! then drop the records outside it.
+//!
+//! The index assumes records are written in time order, as they are by one process.
+//! It moves with its log file when the rotating handlers rotate it and is deleted with
+//! the backup, or when the backup is gzipped (a gzipped file can't be seeked). An index
+//! that doesn't match its file (written before the file was truncated, say) is
+//! detected on read and ignored, so the whole file is scanned.
+
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "file")]
+use std::fs::File;
+#[cfg(feature = "file")]
+use std::io::{BufWriter, Write};
+#[cfg(feature = "json")]
+use std::io::{Read, Seek, SeekFrom};
+
+/// The index of the log file at `log`: `app.log.idx`.
+pub fn sidecar(log: &Path) -> PathBuf {
+    let mut name = log.as_os_str().to_os_string();
+    name.push(".idx");
+    PathBuf::from(name)
+}
+
+/// The `(created, offset)` entries of the index of `log`, or None when there is no
+/// index or its offsets don't grow.
+#[cfg(feature = "json")]
+fn entries(log: &Path) -> Option<Vec<(f64, u64)>> {
+    let text = std::fs::read_to_string(sidecar(log)).ok()?;
+    let mut entries: Vec<(f64, u64)> = Vec::new();
+    for line in text.lines() {
+        let Some((created, offset)) = line.split_once(' ') else {
+            continue;
+        };
+        let (Ok(created), Ok(offset)) = (created.parse::<f64>(), offset.parse::<u64>()) else {
+            continue;
+        };
+        if entries.last().is_some_and(|&(_, last)| offset <= last) {
+            return None;
+        }
+        entries.push((created, offset));
+    }
+    Some(entries)
+}
+
+/// Move the index of `log` to that of `backup` when the rotating handlers move the file.
+#[cfg(feature = "file")]
+pub fn rename(log: &Path, backup: &Path) {
+    let _ = std::fs::rename(sidecar(log), sidecar(backup));
+}
+
+/// Delete the index of `log`, if it has one.
+#[cfg(feature = "file")]
+pub fn remove(log: &Path) {
+    let _ = std::fs::remove_file(sidecar(log));
+}
+
+/// The index a file handler with `index_every` keeps. Only used with the handler's
+/// writer locked.
+#[cfg(feature = "file")]
+pub struct Index {
+    every: u32,
+    path: PathBuf,
+    /// Records written since the last entry.
+    count: u32,
+    /// Whether the next entry is the first since the log file was opened.
+    fresh: bool,
+}
+
+#[cfg(feature = "file")]
+impl Index {
+    pub fn new(log: &Path, every: u32) -> Self {
+        Self {
+            every: every.max(1),
+            path: sidecar(log),
+            count: 0,
+            fresh: true,
+        }
+    }
+
+    /// Start over on a new log file (after a rotation), whose first record gets an
+    /// entry.
+    pub fn restart(&mut self) {
+        self.count = 0;
+        self.fresh = true;
+    }
+
+    /// Count a record about to be written to `writer`, and add an entry for it when it
+    /// starts a stride of `every` records.
+    pub fn add(&mut self, created: f64, writer: &BufWriter<File>) {
+        let count = self.count;
+        self.count = (count + 1) % self.every;
+        if count != 0 {
+            return;
+        }
+        let Ok(meta) = writer.get_ref().metadata() else {
+            return;
+        };
+        let offset = meta.len() + writer.buffer().len() as u64;
+        if std::mem::take(&mut self.fresh) {
+            // An index left from before the file was truncated or replaced.
+            let stale = offset == 0
+                || std::fs::read_to_string(&self.path).is_ok_and(|text| {
+                    text.lines()
+                        .filter_map(|line| line.split_once(' ')?.1.parse::<u64>().ok())
+                        .next_back()
+                        .is_some_and(|last| last >= offset)
+                });
+            if stale {
+                let _ = std::fs::remove_file(&self.path);
+            }
+        }
+        let entry = format!("{created:.6} {offset}\n");
+        let written = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(entry.as_bytes()));
+        if let Err(e) = written {
+            eprintln!(
+                "[LogXide Error] cannot write index {}: {e}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+/// The stretch of a log file that holds the records from `since` to `until`.
+#[cfg(feature = "json")]
+pub struct Span {
+    /// Offset of the first line to read, which starts a record.
+    pub start: u64,
+    /// `created` of the record at `start`, to check the index against the file.
+    pub start_time: Option<f64>,
+    /// Offset to stop reading at, or None to read to the end.
+    pub end: Option<u64>,
+}
+
+/// Where to read `log` for the records from `since` to `until`, from its index. None
+/// when it has no usable index: the whole file is read then.
+#[cfg(feature = "json")]
+pub fn span(log: &Path, since: Option<f64>, until: Option<f64>) -> Option<Span> {
+    let mut entries = entries(log)?;
+    let mut file = std::fs::File::open(log).ok()?;
+    let len = file.metadata().ok()?.len();
+    // Records the handler still buffers.
+    entries.retain(|&(_, offset)| offset < len);
+    // The last entry before `since`: its stride may hold records from `since` on.
+    let (start, start_time) = match since {
+        Some(since) => match entries.partition_point(|&(created, _)| created < since) {
+            0 => (0, None),
+            after => {
+                let (created, offset) = entries[after - 1];
+                (offset, Some(created))
+            }
+        },
+        None => (0, None),
+    };
+    let end = until.and_then(|until| {
+        let after = entries.partition_point(|&(created, _)| created <= until);
+        entries.get(after).map(|&(_, offset)| offset)
+    });
+    if start > 0 {
+        // A record starts a line.
+        let mut before = [0u8];
+        file.seek(SeekFrom::Start(start - 1)).ok()?;
+        file.read_exact(&mut before).ok()?;
+        if before[0] != b'\n' {
+            return None;
+        }
+    }
+    Some(Span {
+        start,
+        start_time,
+        end,
+    })
+}
@@ -0,0 +1,123 @@
+//! # Level Names
+//!
+//! The registry behind `addLevelName()`, `getLevelName()` and `getLevelNamesMapping()`.
+//! A custom level such as `TRACE = 5` or `AUDIT = 60` is just a number; naming it here
+//! makes records logged at it carry the name in `levelname`, which every formatter
+//! renders for `%(levelname)s`, and lets `setLevel("TRACE")` resolve it.
+//!
+//! Records take their name when they are created, so renaming a level does not change
+//! records already logged. Until a name is added, the standard ones are looked up
+//! without taking the lock.
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The standard names, with the aliases `getLevelNamesMapping()` includes.
+const STANDARD: [(&str, u32); 8] = [
+    ("CRITICAL", 50),
+    ("FATAL", 50),
+    ("ERROR", 40),
+    ("WARN", 30),
+    ("WARNING", 30),
+    ("INFO", 20),
+    ("DEBUG", 10),
+    ("NOTSET", 0),
+];
+
+struct Registry {
+    names: HashMap<i64, String>,
+    levels: HashMap<String, u32>,
+}
+
+static REGISTRY: Lazy<RwLock<Registry>> = Lazy::new(|| {
+    RwLock::new(Registry {
+        names: [(50, "CRITICAL"), (40, "ERROR"), (30, "WARNING")]
+            .into_iter()
+            .chain([(20, "INFO"), (10, "DEBUG"), (0, "NOTSET")])
+            .map(|(level, name)| (level, name.to_string()))
+            .collect(),
+        levels: STANDARD
+            .into_iter()
+            .map(|(name, level)| (name.to_string(), level))
+            .collect(),
+    })
+});
+
+/// Set once `addLevelName()` is called; until then the names are the standard ones.
+static CUSTOMIZED: AtomicBool = AtomicBool::new(false);
+
+fn standard_name(level: i64) -> Option<&'static str> {
+    Some(match level {
+        50 => "CRITICAL",
+        40 => "ERROR",
+        30 => "WARNING",
+        20 => "INFO",
+        10 => "DEBUG",
+        0 => "NOTSET",
+        _ => return None,
+    })
+}
+
+/// The name of `level`: the registered one, else `Level <n>` as in the stdlib.
+pub fn name(level: i64) -> String {
+    let registered = if CUSTOMIZED.load(Ordering::Acquire) {
+        REGISTRY.read().names.get(&level).cloned()
+    } else {
+        standard_name(level).map(str::to_string)
+    };
+    registered.unwrap_or_else(|| format!("Level {level}"))
+}
+
+/// The level registered under `name`, or under its upper-cased form, if any.
+pub fn level(name: &str) -> Option<u32> {
+    registered_level(name).or_else(|| registered_level(&name.to_uppercase()))
+}
+
+fn registered_level(name: &str) -> Option<u32> {
+    if !CUSTOMIZED.load(Ordering::Acquire) {
+        return STANDARD
+            .iter()
+            .find(|(standard, _)| *standard == name)
+            .map(|(_, level)| *level);
+    }
+    REGISTRY.read().levels.get(name).copied()
+}
+
+/// Name `level`, like `logging.addLevelName`. Records logged at it from now on carry
+/// the name as `levelname`, and `setLevel()` accepts it.
+#[pyfunction]
+#[pyo3(name = "addLevelName")]
+pub fn add_level_name(level: u32, #[allow(non_snake_case)] levelName: String) {
+    let mut registry = REGISTRY.write();
+    registry.names.insert(i64::from(level), levelName.clone());
+    registry.levels.insert(levelName, level);
+    CUSTOMIZED.store(true, Ordering::Release);
+}
+
+/// The name of a level number, or the number of a level name, like
+/// `logging.getLevelName`; `Level <level>` when there is none.
+#[pyfunction]
+#[pyo3(name = "getLevelName")]
+pub fn get_level_name(py: Python, level: &Bound<PyAny>) -> PyResult<Py<PyAny>> {
+    if let Ok(levelno) = level.extract::<i64>() {
+        return Ok(PyString::new(py, &name(levelno)).into_any().unbind());
+    }
+    let text = level.str()?.to_string();
+    Ok(match self::level(&text) {
+        Some(levelno) => levelno.into_pyobject(py)?.into_any().unbind(),
+        None => PyString::new(py, &format!("Level {text}"))
+            .into_any()
+            .unbind(),
+    })
+}
+
+/// A copy of the name to level mapping, like `logging.getLevelNamesMapping`.
+#[pyfunction]
+#[pyo3(name = "getLevelNamesMapping")]
+pub fn get_level_names_mapping() -> HashMap<String, u32> {
+    REGISTRY.read().levels.clone()
+}
@@ -2,51 +2,173 @@
 
 use pyo3::prelude::*;
 
+mod basic_config;
+mod checksum;
 mod config;
 pub mod core;
+mod crash;
+mod dispatch_trace;
+mod extra_limits;
 mod fast_logger;
 mod filter;
+mod fingerprint;
+mod fork;
 pub mod formatter;
 mod globals;
 pub mod handler;
+#[cfg(any(feature = "file", feature = "json"))]
+mod index;
+mod levels;
+#[cfg(feature = "log")]
+pub mod log_bridge;
+#[cfg(feature = "json")]
+mod logfile;
+mod macros;
+#[cfg(feature = "network")]
+mod pickle;
+mod platform;
+mod preset;
+mod progress;
+mod projection;
 mod py_handlers;
 mod py_logger;
+mod queue;
+#[cfg(feature = "regex-filters")]
+mod redact;
+#[cfg(feature = "file")]
+mod retention;
+mod routing;
+mod run_id;
+mod sanitize;
+#[cfg(feature = "json")]
+mod schema;
+mod secret_keys;
+#[cfg(feature = "smtp")]
+mod smtp;
+mod tail;
+mod template;
+mod threads;
+mod timer;
+mod traceback;
+#[cfg(feature = "network")]
+mod transform;
+#[cfg(feature = "watch")]
+mod watch;
+mod worker;
 
 pub use core::{create_log_record_with_extra, LogLevel, LogRecord};
-pub use formatter::{ColorFormatter, Formatter, PythonFormatter};
+#[cfg(feature = "colors")]
+pub use formatter::ColorFormatter;
+#[cfg(feature = "json")]
+pub use formatter::JsonFormatter;
+pub use formatter::{ColumnFormatter, Formatter, PythonFormatter};
 pub use globals::{HANDLERS, THREAD_NAME};
+#[cfg(feature = "colors")]
+pub use py_handlers::PyColorFormatter;
+#[cfg(feature = "json")]
+pub use py_handlers::PyJsonFormatter;
+#[cfg(feature = "otlp")]
+pub use py_handlers::PyOTLPHandler;
+#[cfg(feature = "pipe")]
+pub use py_handlers::PyPipeHandler;
+#[cfg(feature = "smtp")]
+pub use py_handlers::PySMTPHandler;
 pub use py_handlers::{
-    PyColorFormatter, PyFileHandler, PyFormatter, PyHTTPHandler, PyMemoryHandler, PyOTLPHandler,
-    PyRotatingFileHandler, PyStreamHandler,
+    PyAggregatingHandler, PyColumnFormatter, PyFormatter, PyMemoryHandler, PyQueueHandler,
+    PyQueueListener, PyStreamHandler,
+};
+#[cfg(feature = "network")]
+pub use py_handlers::{PyDatagramHandler, PyHTTPHandler, PySocketHandler, PySyslogHandler};
+#[cfg(feature = "file")]
+pub use py_handlers::{
+    PyFileHandler, PyLevelRoutedFileHandler, PyRotatingFileHandler, PyTimedRotatingFileHandler,
 };
 pub use py_logger::PyLogger;
 
 #[pymodule]
 fn logxide(_py: Python, m: &Bound<'_, pyo3::types::PyModule>) -> PyResult<()> {
+    globals::claim_interpreter(m.py());
+    if let Ok(start) = m
+        .py()
+        .import("logging")
+        .and_then(|logging| logging.getattr("_startTime"))
+        .and_then(|start| start.extract::<f64>())
+    {
+        core::set_start_time(start);
+    }
     let logging_module = PyModule::new(m.py(), "logging")?;
     logging_module.add_class::<PyLogger>()?;
     logging_module.add_class::<LogRecord>()?;
+    logging_module.add_class::<filter::NameFilter>()?;
+    #[cfg(feature = "regex-filters")]
+    logging_module.add_class::<filter::RegexFilter>()?;
+    logging_module.add_class::<filter::LevelRangeFilter>()?;
+    logging_module.add_class::<filter::DedupFilter>()?;
+    #[cfg(feature = "regex-filters")]
+    logging_module.add_class::<redact::Redactor>()?;
+    logging_module.add_class::<projection::FieldProjection>()?;
     logging_module.add_class::<PyFormatter>()?;
+    #[cfg(feature = "colors")]
     logging_module.add_class::<PyColorFormatter>()?;
+    logging_module.add_class::<PyColumnFormatter>()?;
+    #[cfg(feature = "json")]
+    logging_module.add_class::<PyJsonFormatter>()?;
+    #[cfg(feature = "file")]
     logging_module.add_class::<PyFileHandler>()?;
     logging_module.add_class::<PyStreamHandler>()?;
+    #[cfg(feature = "file")]
     logging_module.add_class::<PyRotatingFileHandler>()?;
+    #[cfg(feature = "file")]
+    logging_module.add_class::<PyTimedRotatingFileHandler>()?;
+    #[cfg(feature = "file")]
+    logging_module.add_class::<PyLevelRoutedFileHandler>()?;
+    #[cfg(feature = "network")]
     logging_module.add_class::<PyHTTPHandler>()?;
+    #[cfg(feature = "network")]
+    logging_module.add_class::<PyDatagramHandler>()?;
+    #[cfg(feature = "network")]
+    logging_module.add_class::<PySyslogHandler>()?;
+    #[cfg(feature = "network")]
+    logging_module.add_class::<PySocketHandler>()?;
+    #[cfg(feature = "smtp")]
+    logging_module.add_class::<PySMTPHandler>()?;
+    #[cfg(feature = "otlp")]
     logging_module.add_class::<PyOTLPHandler>()?;
+    #[cfg(feature = "pipe")]
+    logging_module.add_class::<PyPipeHandler>()?;
     logging_module.add_class::<PyMemoryHandler>()?;
+    logging_module.add_class::<PyQueueHandler>()?;
+    logging_module.add_class::<PyQueueListener>()?;
+    logging_module.add_class::<PyAggregatingHandler>()?;
+    logging_module.add_class::<timer::PyTimer>()?;
+    logging_module.add_class::<progress::PyProgress>()?;
+    logging_module.add_class::<tail::LogTail>()?;
+    #[cfg(feature = "watch")]
+    logging_module.add_class::<watch::PyConfigWatcher>()?;
     logging_module.add_function(wrap_pyfunction!(globals::get_logger, &logging_module)?)?;
-    logging_module.add_function(wrap_pyfunction!(globals::basicConfig, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        basic_config::basic_config,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(config::dict_config, &logging_module)?)?;
     logging_module.add_function(wrap_pyfunction!(globals::flush, &logging_module)?)?;
     logging_module.add_function(wrap_pyfunction!(globals::set_thread_name, &logging_module)?)?;
+    #[cfg(feature = "network")]
     logging_module.add_function(wrap_pyfunction!(
         globals::register_http_handler,
         &logging_module
     )?)?;
     logging_module.add_function(wrap_pyfunction!(globals::clear_handlers, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(globals::reinitialize, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(globals::disable, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(globals::process_state, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(globals::generation, &logging_module)?)?;
+    #[cfg(feature = "file")]
     logging_module.add_function(wrap_pyfunction!(
         globals::register_file_handler,
         &logging_module
     )?)?;
+    #[cfg(feature = "file")]
     logging_module.add_function(wrap_pyfunction!(
         globals::register_rotating_file_handler,
         &logging_module
@@ -59,30 +181,224 @@ fn logxide(_py: Python, m: &Bound<'_, pyo3::types::PyModule>) -> PyResult<()> {
         globals::activate_caller_info,
         &logging_module
     )?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        traceback::configure_tracebacks,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        traceback::format_exception,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        fingerprint::configure_exception_grouping,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        template::configure_message_templates,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        template::message_template,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        extra_limits::configure_extra_limits,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        extra_limits::extra_limit_stats,
+        &logging_module
+    )?)?;
+    #[cfg(feature = "regex-filters")]
+    logging_module.add_function(wrap_pyfunction!(
+        redact::configure_redaction,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        secret_keys::configure_secret_keys,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(run_id::configure_run_id, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(run_id::run_id, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(levels::add_level_name, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(levels::get_level_name, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        levels::get_level_names_mapping,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(routing::route, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(routing::unroute, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(routing::routes, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        threads::configure_threads,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(threads::tune, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(preset::preset, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(queue::queue_stats, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        globals::configure_console_dedup,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        globals::configure_caller_info,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        crash::configure_crash_dump,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(tail::tail, &logging_module)?)?;
+    #[cfg(feature = "file")]
+    logging_module.add_function(wrap_pyfunction!(
+        checksum::verify_log_file,
+        &logging_module
+    )?)?;
+    #[cfg(feature = "json")]
+    logging_module.add_function(wrap_pyfunction!(
+        logfile::convert_log_file,
+        &logging_module
+    )?)?;
+    #[cfg(feature = "json")]
+    logging_module.add_function(wrap_pyfunction!(logfile::merge_log_files, &logging_module)?)?;
+    #[cfg(feature = "json")]
+    logging_module.add_function(wrap_pyfunction!(logfile::log_file_stats, &logging_module)?)?;
+    #[cfg(feature = "json")]
+    logging_module.add_function(wrap_pyfunction!(logfile::read_log_file, &logging_module)?)?;
+    #[cfg(feature = "json")]
+    logging_module.add_function(wrap_pyfunction!(logfile::replay_log_file, &logging_module)?)?;
+    #[cfg(feature = "json")]
+    logging_module.add_function(wrap_pyfunction!(
+        schema::register_schema_migration,
+        &logging_module
+    )?)?;
+    #[cfg(feature = "json")]
+    logging_module.add_function(wrap_pyfunction!(
+        schema::schema_migrations,
+        &logging_module
+    )?)?;
+    #[cfg(feature = "json")]
+    logging_module.add_function(wrap_pyfunction!(schema::migrate_record, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        dispatch_trace::debug_dispatch,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        worker::configure_workers,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(worker::shutdown_workers, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(fork::before_fork, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        fork::after_fork_in_child,
+        &logging_module
+    )?)?;
+    #[cfg(feature = "watch")]
+    logging_module.add_function(wrap_pyfunction!(watch::watch_config, &logging_module)?)?;
+    #[cfg(feature = "watch")]
+    logging_module.add_function(wrap_pyfunction!(
+        watch::stop_config_watchers,
+        &logging_module
+    )?)?;
+    logging_module.add_function(wrap_pyfunction!(platform::runtime_info, &logging_module)?)?;
+    logging_module.add_function(wrap_pyfunction!(
+        globals::check_interpreter,
+        &logging_module
+    )?)?;
     m.add_submodule(&logging_module)?;
 
     m.add_class::<PyLogger>()?;
     m.add_class::<LogRecord>()?;
+    m.add_class::<filter::NameFilter>()?;
+    #[cfg(feature = "regex-filters")]
+    m.add_class::<filter::RegexFilter>()?;
+    m.add_class::<filter::LevelRangeFilter>()?;
+    m.add_class::<filter::DedupFilter>()?;
+    #[cfg(feature = "regex-filters")]
+    m.add_class::<redact::Redactor>()?;
+    m.add_class::<projection::FieldProjection>()?;
     m.add_class::<PyFormatter>()?;
+    #[cfg(feature = "colors")]
     m.add_class::<PyColorFormatter>()?;
+    m.add_class::<PyColumnFormatter>()?;
+    #[cfg(feature = "json")]
+    m.add_class::<PyJsonFormatter>()?;
+    #[cfg(feature = "file")]
     m.add_class::<PyFileHandler>()?;
     m.add_class::<PyStreamHandler>()?;
+    #[cfg(feature = "file")]
     m.add_class::<PyRotatingFileHandler>()?;
+    #[cfg(feature = "file")]
+    m.add_class::<PyTimedRotatingFileHandler>()?;
+    #[cfg(feature = "file")]
+    m.add_class::<PyLevelRoutedFileHandler>()?;
+    #[cfg(feature = "network")]
     m.add_class::<PyHTTPHandler>()?;
+    #[cfg(feature = "network")]
+    m.add_class::<PyDatagramHandler>()?;
+    #[cfg(feature = "network")]
+    m.add_class::<PySyslogHandler>()?;
+    #[cfg(feature = "network")]
+    m.add_class::<PySocketHandler>()?;
+    #[cfg(feature = "smtp")]
+    m.add_class::<PySMTPHandler>()?;
+    #[cfg(feature = "otlp")]
     m.add_class::<PyOTLPHandler>()?;
+    #[cfg(feature = "pipe")]
+    m.add_class::<PyPipeHandler>()?;
     m.add_class::<PyMemoryHandler>()?;
+    m.add_class::<PyQueueHandler>()?;
+    m.add_class::<PyQueueListener>()?;
+    m.add_class::<PyAggregatingHandler>()?;
+    m.add_class::<timer::PyTimer>()?;
+    m.add_class::<progress::PyProgress>()?;
+    #[cfg(feature = "watch")]
+    m.add_class::<watch::PyConfigWatcher>()?;
     m.add_function(wrap_pyfunction!(globals::get_logger, m)?)?;
-    m.add_function(wrap_pyfunction!(globals::basicConfig, m)?)?;
+    m.add_function(wrap_pyfunction!(basic_config::basic_config, m)?)?;
+    m.add_function(wrap_pyfunction!(config::dict_config, m)?)?;
     m.add_function(wrap_pyfunction!(globals::flush, m)?)?;
     m.add_function(wrap_pyfunction!(globals::set_thread_name, m)?)?;
+    #[cfg(feature = "network")]
     m.add_function(wrap_pyfunction!(globals::register_http_handler, m)?)?;
     m.add_function(wrap_pyfunction!(globals::clear_handlers, m)?)?;
+    m.add_function(wrap_pyfunction!(globals::reinitialize, m)?)?;
+    m.add_function(wrap_pyfunction!(globals::disable, m)?)?;
+    #[cfg(feature = "file")]
     m.add_function(wrap_pyfunction!(globals::register_file_handler, m)?)?;
+    #[cfg(feature = "file")]
     m.add_function(wrap_pyfunction!(
         globals::register_rotating_file_handler,
         m
     )?)?;
     m.add_function(wrap_pyfunction!(globals::register_stream_handler, m)?)?;
     m.add_function(wrap_pyfunction!(globals::activate_caller_info, m)?)?;
+    m.add_function(wrap_pyfunction!(traceback::configure_tracebacks, m)?)?;
+    m.add_function(wrap_pyfunction!(traceback::format_exception, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        fingerprint::configure_exception_grouping,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(template::configure_message_templates, m)?)?;
+    m.add_function(wrap_pyfunction!(template::message_template, m)?)?;
+    m.add_function(wrap_pyfunction!(extra_limits::configure_extra_limits, m)?)?;
+    m.add_function(wrap_pyfunction!(extra_limits::extra_limit_stats, m)?)?;
+    #[cfg(feature = "regex-filters")]
+    m.add_function(wrap_pyfunction!(redact::configure_redaction, m)?)?;
+    m.add_function(wrap_pyfunction!(secret_keys::configure_secret_keys, m)?)?;
+    m.add_function(wrap_pyfunction!(run_id::configure_run_id, m)?)?;
+    m.add_function(wrap_pyfunction!(run_id::run_id, m)?)?;
+    m.add_function(wrap_pyfunction!(levels::add_level_name, m)?)?;
+    m.add_function(wrap_pyfunction!(levels::get_level_name, m)?)?;
+    m.add_function(wrap_pyfunction!(levels::get_level_names_mapping, m)?)?;
+    m.add_function(wrap_pyfunction!(routing::route, m)?)?;
+    m.add_function(wrap_pyfunction!(routing::unroute, m)?)?;
+    m.add_function(wrap_pyfunction!(routing::routes, m)?)?;
+    m.add_function(wrap_pyfunction!(threads::configure_threads, m)?)?;
+    m.add_function(wrap_pyfunction!(threads::tune, m)?)?;
+    m.add_function(wrap_pyfunction!(preset::preset, m)?)?;
+    m.add_function(wrap_pyfunction!(platform::runtime_info, m)?)?;
+    #[cfg(feature = "watch")]
+    m.add_function(wrap_pyfunction!(watch::watch_config, m)?)?;
     Ok(())
 }
@@ -0,0 +1,133 @@
+//! # `log` Crate Bridge
+//!
+//! Routes records of the `log` facade to the Rust logger registry, so libraries that
+//! log through `log::info!` reach the same handlers as the logging macros. A record's
+//! target becomes its logger (`my_crate::net` logs to `my_crate.net`), and it keeps the
+//! `file!()` / `line!()` / `module_path!()` of the call. `Trace` maps to level 5, below
+//! DEBUG, which is filtered out unless a logger is set to it.
+//!
+//! ```
+//! logxide::log_bridge::init().unwrap();
+//! log::warn!(target: "app::net", "retrying");
+//! ```
+
+use std::sync::Arc;
+
+use crate::core::{create_log_record, get_logger, get_root_logger, LogLevel, LOGGER_MANAGER};
+
+/// `log::Level::Trace`, one step below DEBUG as in libraries that add a TRACE level.
+const TRACE: LogLevel = LogLevel::new(5);
+
+/// The `log::Log` that hands records to the logger registry. Installed by [`init`].
+pub struct LogBridge;
+
+fn level(level: log::Level) -> LogLevel {
+    match level {
+        log::Level::Error => LogLevel::Error,
+        log::Level::Warn => LogLevel::Warning,
+        log::Level::Info => LogLevel::Info,
+        log::Level::Debug => LogLevel::Debug,
+        log::Level::Trace => TRACE,
+    }
+}
+
+fn logger(target: &str) -> Arc<std::sync::Mutex<crate::core::Logger>> {
+    if target.is_empty() {
+        get_root_logger()
+    } else {
+        get_logger(&target.replace("::", "."))
+    }
+}
+
+impl log::Log for LogBridge {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        let logger = logger(metadata.target());
+        let enabled = logger
+            .lock()
+            .unwrap()
+            .is_enabled_for(level(metadata.level()));
+        enabled
+    }
+
+    fn log(&self, record: &log::Record) {
+        let logger = logger(record.target());
+        let logger = logger.lock().unwrap();
+        let level = level(record.level());
+        if !logger.is_enabled_for(level) {
+            return;
+        }
+        let mut log_record =
+            create_log_record(logger.name.clone(), level, record.args().to_string());
+        if let Some(file) = record.file() {
+            log_record = log_record.with_location(
+                file,
+                record.line().unwrap_or(0),
+                record.module_path().unwrap_or_default(),
+            );
+        }
+        logger.handle(Arc::new(log_record));
+    }
+
+    fn flush(&self) {
+        let mut loggers: Vec<_> = LOGGER_MANAGER
+            .loggers
+            .lock()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect();
+        loggers.push(get_root_logger());
+        for logger in loggers {
+            let handlers = logger.lock().unwrap().handlers.clone();
+            for handler in handlers {
+                handler.flush();
+            }
+        }
+    }
+}
+
+/// Install [`LogBridge`] as the `log` crate's logger, passing every level on: the
+/// loggers' own levels decide. Fails when another logger was installed first.
+pub fn init() -> Result<(), log::SetLoggerError> {
+    log::set_logger(&LogBridge)?;
+    log::set_max_level(log::LevelFilter::Trace);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::core::{get_logger, LogLevel};
+    use crate::macros::tests::Capture;
+
+    #[test]
+    fn log_records_reach_the_target_logger() {
+        let capture = Arc::new(Capture::default());
+        {
+            let logger = get_logger("test.bridge.net");
+            let mut logger = logger.lock().unwrap();
+            logger.set_level(LogLevel::Info);
+            logger.propagate = false;
+            logger.add_handler(capture.clone());
+        }
+        super::init().unwrap();
+
+        let line = line!();
+        log::warn!(target: "test::bridge::net", "retrying {}", 2);
+        log::debug!(target: "test::bridge::net", "filtered");
+        log::logger().flush();
+
+        let records = capture.0.lock().unwrap();
+        let [record] = records.as_slice() else {
+            panic!("expected one record, got {}", records.len());
+        };
+        assert_eq!(record.name, "test.bridge.net");
+        assert_eq!((record.levelno, record.msg.as_str()), (30, "retrying 2"));
+        assert_eq!(
+            (record.pathname.as_str(), record.lineno),
+            (file!(), line + 1)
+        );
+        assert_eq!(record.module, module_path!());
+    }
+}
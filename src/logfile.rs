@@ -0,0 +1,1061 @@
+//! # Log File Tools
+//!
+//! The file operations behind `python -m logxide`: reading log files back into
+//! `LogRecord`s, then converting, merging or counting them through the crate's own
+//! formatters, or replaying them through the handlers configured now. This is how
+//! logs written by the stdlib before switching to logxide are brought along.
+//!
+//! A file whose name ends in `.parquet` is read as parquet. Otherwise a file whose first
+//! line is a JSON object is read as JSON lines (JsonFormatter output), and anything else
+//! as text in a `%`-style format string. The format is parsed with the formatter's own
+//! plan and turned into a regex; a regex with named groups can be given instead for
+//! layouts no format string describes. A line that doesn't parse as a record (a traceback, the
+//! rest of a multi-line message) continues the record before it and ends up in its
+//! `exc_text`, which the formatters write back after the message. `.gz` files are
+//! decompressed first.
+//!
+//! With `since` or `until`, only the records created in that range are kept. A file
+//! written with `index_every=` is then read from the sidecar index's entry before
+//! `since` to the one after `until` instead of from the start (see [`crate::index`]).
+//!
+//! Parquet needs the `parquet` cargo feature, which is not in the defaults. Text logs
+//! need the default `regex-filters` feature; without it only JSON lines are read.
+
+use crate::checksum::is_trailer;
+use crate::core::{JsonCache, LogRecord};
+#[cfg(feature = "regex-filters")]
+use crate::formatter::{parse_plan, Token};
+use crate::formatter::{Formatter, JsonFormatter, PythonFormatter};
+use crate::platform::FsPath;
+use crate::py_logger::PyLogger;
+use chrono::TimeZone;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+#[cfg(feature = "regex-filters")]
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "regex-filters")]
+use std::fmt::Write as _;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// The format text logs are read and written in when none is given: what
+/// `basicConfig()` writes.
+const DEFAULT_FORMAT: &str = "%(levelname)s:%(name)s:%(message)s";
+
+/// The date format `%(asctime)s` is read and written in when none is given.
+const DEFAULT_DATEFMT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// How a log file is laid out.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    Text,
+    Json,
+    Parquet,
+}
+
+impl Layout {
+    fn parse(name: &str) -> PyResult<Self> {
+        match name {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "parquet" => Ok(Self::Parquet),
+            other => Err(PyValueError::new_err(format!(
+                "unknown log format {other:?}: expected 'text', 'json' or 'parquet'"
+            ))),
+        }
+    }
+}
+
+/// The records of one log file.
+struct LogFile {
+    layout: Layout,
+    records: Vec<LogRecord>,
+    /// When each record was created, where the file says.
+    times: Vec<Option<f64>>,
+    /// The lines each record was read from; empty for parquet.
+    lines: Vec<String>,
+    /// The fields the file has, in the order first seen.
+    fields: Vec<String>,
+}
+
+impl LogFile {
+    fn new(layout: Layout) -> Self {
+        Self {
+            layout,
+            records: Vec::new(),
+            times: Vec::new(),
+            lines: Vec::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, record: LogRecord, time: Option<f64>, line: &str) {
+        self.records.push(record);
+        self.times.push(time);
+        self.lines.push(line.to_string());
+    }
+
+    /// Leave out the records created before `since` or after `until`. A record without
+    /// a time of its own goes with the record before it, and is left out when there is
+    /// none.
+    fn keep_between(&mut self, since: Option<f64>, until: Option<f64>) {
+        let mut last = None;
+        let keep: Vec<bool> = self
+            .times
+            .iter()
+            .map(|time| {
+                last = time.or(last);
+                last.is_some_and(|t| since.is_none_or(|s| t >= s) && until.is_none_or(|u| t <= u))
+            })
+            .collect();
+        let mut kept = keep.iter().copied();
+        self.records.retain(|_| kept.next().unwrap_or(false));
+        let mut kept = keep.iter().copied();
+        self.times.retain(|_| kept.next().unwrap_or(false));
+        if !self.lines.is_empty() {
+            let mut kept = keep.iter().copied();
+            self.lines.retain(|_| kept.next().unwrap_or(false));
+        }
+    }
+
+    fn add_field(&mut self, name: &str) {
+        if !self.fields.iter().any(|field| field == name) {
+            self.fields.push(name.to_string());
+        }
+    }
+
+    /// Append a line that is not a record of its own to the last record.
+    fn continue_last(&mut self, line: &str) {
+        let Some(record) = self.records.last_mut() else {
+            // Lines before the first record (the file starts mid-record after a
+            // rotation) become a record of their own.
+            if !line.trim().is_empty() {
+                let record = LogRecord {
+                    msg: line.to_string(),
+                    ..blank_record()
+                };
+                self.add_field("message");
+                self.push(record, None, line);
+            }
+            return;
+        };
+        match &mut record.exc_text {
+            Some(text) => {
+                text.push('\n');
+                text.push_str(line);
+            }
+            None => record.exc_text = Some(line.to_string()),
+        }
+        if let Some(raw) = self.lines.last_mut() {
+            raw.push('\n');
+            raw.push_str(line);
+        }
+        self.add_field("exc_info");
+    }
+}
+
+/// A record with every field empty, to fill in from a file.
+fn blank_record() -> LogRecord {
+    LogRecord {
+        name: String::new(),
+        levelno: 0,
+        levelname: String::new(),
+        pathname: String::new(),
+        filename: String::new(),
+        module: String::new(),
+        lineno: 0,
+        func_name: String::new(),
+        created: 0.0,
+        msecs: 0.0,
+        relative_created: 0.0,
+        thread: 0,
+        thread_name: String::new(),
+        process_name: String::new(),
+        process: 0,
+        ppid: 0,
+        thread_native_id: 0,
+        seq: 0,
+        msg: String::new(),
+        args: None,
+        exc_info: None,
+        exc_text: None,
+        stack_info: None,
+        task_name: None,
+        extra: None,
+        json_cache: JsonCache::default(),
+    }
+}
+
+/// Parses text log lines written with a `%`-style format string, or matched by a regex
+/// with named groups.
+#[cfg(feature = "regex-filters")]
+struct TextParser {
+    regex: Regex,
+    /// The capture groups that hold fields, and the field each holds.
+    groups: Vec<(usize, String)>,
+}
+
+#[cfg(feature = "regex-filters")]
+impl TextParser {
+    /// The parser for `pattern` when one is given, else for `format`.
+    fn for_options(format: &str, pattern: Option<&str>) -> PyResult<Self> {
+        match pattern {
+            Some(pattern) => Self::from_pattern(pattern),
+            None => Self::new(format),
+        }
+    }
+
+    /// A parser for lines that `pattern` matches in full. Its named groups are the fields,
+    /// named as in `%(name)s`; groups with other names become extras.
+    fn from_pattern(pattern: &str) -> PyResult<Self> {
+        let regex = Regex::new(&format!("^(?:{pattern})$"))
+            .map_err(|e| PyValueError::new_err(format!("invalid log pattern {pattern:?}: {e}")))?;
+        let groups: Vec<(usize, String)> = regex
+            .capture_names()
+            .enumerate()
+            .filter_map(|(index, name)| Some((index, name?.to_string())))
+            .collect();
+        if groups.is_empty() {
+            return Err(PyValueError::new_err(format!(
+                "log pattern {pattern:?} has no named groups"
+            )));
+        }
+        Ok(Self { regex, groups })
+    }
+
+    fn new(format: &str) -> PyResult<Self> {
+        let plan = parse_plan(format);
+        let count = plan
+            .iter()
+            .filter(|token| matches!(token, Token::Field { .. }))
+            .count();
+        let mut pattern = String::from("^");
+        let mut groups = Vec::with_capacity(count);
+        for token in &plan {
+            match token {
+                Token::Literal(text) => pattern.push_str(&regex::escape(text)),
+                Token::Field { name, width, .. } => {
+                    groups.push((groups.len() + 1, name.clone()));
+                    let value = match name.as_str() {
+                        "levelname" => "[A-Z][A-Z0-9_]*",
+                        "levelno" | "lineno" | "thread" | "process" | "ppid"
+                        | "thread_native_id" | "seq" => r"-?\d+",
+                        "created" | "msecs" | "relativeCreated" => r"-?\d+(?:\.\d*)?",
+                        // The last field takes the rest of the line.
+                        _ if groups.len() == count => ".*",
+                        _ => ".*?",
+                    };
+                    if *width > 0 {
+                        let _ = write!(pattern, " *({value}) *");
+                    } else {
+                        let _ = write!(pattern, "({value})");
+                    }
+                }
+            }
+        }
+        pattern.push('$');
+        let regex = Regex::new(&pattern)
+            .map_err(|e| PyValueError::new_err(format!("invalid log format {format:?}: {e}")))?;
+        Ok(Self { regex, groups })
+    }
+
+    /// The fields of `line`, or None when it is not a record in this format.
+    fn parse<'a>(&'a self, line: &'a str) -> Option<Vec<(&'a str, Value)>> {
+        let captures = self.regex.captures(line)?;
+        Some(
+            self.groups
+                .iter()
+                .map(|(index, name)| {
+                    let value = captures.get(*index).map_or("", |m| m.as_str()).trim();
+                    (name.as_str(), Value::from(value))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// How log files are read: the parser for text logs, the date format of `asctime`, the
+/// schema version records are migrated to, if any, and the times records must be
+/// created between.
+struct Reader {
+    #[cfg(feature = "regex-filters")]
+    parser: TextParser,
+    datefmt: String,
+    schema_version: Option<u32>,
+    since: Option<f64>,
+    until: Option<f64>,
+}
+
+impl Reader {
+    fn new(format: &str, datefmt: &str, pattern: Option<&str>) -> PyResult<Self> {
+        // Without the `regex-filters` feature only JSON lines and parquet logs are read.
+        #[cfg(not(feature = "regex-filters"))]
+        let _ = (format, pattern);
+        Ok(Self {
+            #[cfg(feature = "regex-filters")]
+            parser: TextParser::for_options(format, pattern)?,
+            datefmt: datefmt.to_string(),
+            schema_version: None,
+            since: None,
+            until: None,
+        })
+    }
+
+    /// Keep only the records created from `since` to `until`, each a datetime or
+    /// seconds since the epoch.
+    fn between(
+        mut self,
+        since: Option<&Bound<PyAny>>,
+        until: Option<&Bound<PyAny>>,
+    ) -> PyResult<Self> {
+        self.since = since.map(unix_time).transpose()?;
+        self.until = until.map(unix_time).transpose()?;
+        Ok(self)
+    }
+
+    /// A record from its fields, as `record_from_fields` builds it. With a schema
+    /// version, the fields go through `schema::migrate` first, and the fields of the
+    /// migrated record are added to `file`'s.
+    fn record<'a>(
+        &self,
+        file: &mut LogFile,
+        fields: impl IntoIterator<Item = (&'a str, Value)>,
+    ) -> PyResult<(LogRecord, Option<f64>)> {
+        let Some(version) = self.schema_version else {
+            return Ok(record_from_fields(fields, &self.datefmt));
+        };
+        let fields: Map<String, Value> = fields
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect();
+        let fields = Python::attach(|py| crate::schema::migrate(py, fields, version))?;
+        for name in fields.keys() {
+            file.add_field(name);
+        }
+        Ok(record_from_fields(
+            fields
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.clone())),
+            &self.datefmt,
+        ))
+    }
+}
+
+/// Build a record from its fields (standard ones by their `%(name)s`, the rest become
+/// extras) and work out when it was created.
+fn record_from_fields<'a>(
+    fields: impl IntoIterator<Item = (&'a str, Value)>,
+    datefmt: &str,
+) -> (LogRecord, Option<f64>) {
+    let mut record = blank_record();
+    let mut created = None;
+    let mut asctime = None;
+    let mut msecs = None;
+    let number = |value: &Value| match value {
+        Value::String(s) => s.parse::<f64>().ok(),
+        other => other.as_f64(),
+    };
+    let text = |value: Value| match value {
+        Value::String(s) => s,
+        other => other.to_string(),
+    };
+    for (name, value) in fields {
+        if value.is_null() {
+            continue;
+        }
+        match name {
+            "name" => record.name = text(value),
+            "levelname" => record.levelname = text(value),
+            "levelno" => record.levelno = number(&value).unwrap_or(0.0) as i32,
+            "pathname" => record.pathname = text(value),
+            "filename" => record.filename = text(value),
+            "module" => record.module = text(value),
+            "lineno" => record.lineno = number(&value).unwrap_or(0.0) as u32,
+            "funcName" => record.func_name = text(value),
+            "created" => created = number(&value),
+            "msecs" => msecs = number(&value),
+            "relativeCreated" => record.relative_created = number(&value).unwrap_or(0.0),
+            "thread" => record.thread = number(&value).unwrap_or(0.0) as u64,
+            "threadName" => record.thread_name = text(value),
+            "processName" => record.process_name = text(value),
+            "process" => record.process = number(&value).unwrap_or(0.0) as u32,
+            "ppid" => record.ppid = number(&value).unwrap_or(0.0) as u32,
+            "thread_native_id" => record.thread_native_id = number(&value).unwrap_or(0.0) as u64,
+            "seq" => record.seq = number(&value).unwrap_or(0.0) as u64,
+            "taskName" => record.task_name = Some(text(value)),
+            "message" => record.msg = text(value),
+            "exc_info" => record.exc_text = Some(text(value)),
+            "stack_info" => record.stack_info = Some(text(value)),
+            "asctime" => asctime = Some(text(value)),
+            other => {
+                record
+                    .extra
+                    .get_or_insert_with(HashMap::new)
+                    .insert(other.to_string(), value);
+            }
+        }
+    }
+    if record.levelno == 0 && !record.levelname.is_empty() {
+        record.levelno = crate::levels::level(&record.levelname).unwrap_or(0) as i32;
+    } else if record.levelname.is_empty() && record.levelno != 0 {
+        record.levelname = crate::levels::name(record.levelno.into());
+    }
+    let time = created.or_else(|| {
+        let (secs, fraction) = parse_asctime(asctime.as_deref()?, datefmt)?;
+        // A format with `%(asctime)s,%(msecs)03d` keeps the milliseconds apart.
+        Some(secs + fraction.or(msecs.map(|ms| ms / 1000.0)).unwrap_or(0.0))
+    });
+    if let Some(time) = time {
+        record.created = time;
+        // Rounded to the microsecond first: `,123` is 0.12299... as a float.
+        record.msecs = msecs.unwrap_or_else(|| ((time.fract() * 1e6).round() / 1000.0).floor());
+    }
+    (record, time)
+}
+
+/// Seconds since the epoch of an `%(asctime)s` value, and its fraction of a second when
+/// it carries one after a `,` or `.` (the stdlib's default `2024-05-01 12:00:00,123`).
+/// Times without a UTC offset are local.
+fn parse_asctime(value: &str, datefmt: &str) -> Option<(f64, Option<f64>)> {
+    if let Ok(datetime) = chrono::DateTime::parse_from_str(value, datefmt) {
+        return Some((datetime.timestamp() as f64, None));
+    }
+    let local = |naive: chrono::NaiveDateTime| {
+        chrono::Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|datetime| datetime.timestamp() as f64)
+    };
+    if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(value, datefmt) {
+        return Some((local(naive)?, None));
+    }
+    let (head, digits) = value.rsplit_once([',', '.'])?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let naive = chrono::NaiveDateTime::parse_from_str(head, datefmt).ok()?;
+    let fraction = format!("0.{digits}").parse::<f64>().ok()?;
+    Some((local(naive)?, Some(fraction)))
+}
+
+/// Seconds since the epoch of a datetime (naive ones are local time) or a number.
+fn unix_time(value: &Bound<PyAny>) -> PyResult<f64> {
+    if let Ok(timestamp) = value.getattr("timestamp") {
+        return timestamp.call0()?.extract();
+    }
+    value.extract().map_err(|_| {
+        pyo3::exceptions::PyTypeError::new_err(
+            "since and until take a datetime or seconds since the epoch",
+        )
+    })
+}
+
+/// An I/O error on `path`, raised as the matching OSError subclass with the path in
+/// its message.
+fn file_error(path: &Path, e: std::io::Error) -> PyErr {
+    std::io::Error::new(e.kind(), format!("{}: {e}", path.display())).into()
+}
+
+/// Read a log file, picking the layout from its name and first line, and keep the
+/// records in the reader's time range.
+fn read_file(path: &Path, reader: &Reader) -> PyResult<LogFile> {
+    let mut file = read_records(path, reader)?;
+    if reader.since.is_some() || reader.until.is_some() {
+        file.keep_between(reader.since, reader.until);
+    }
+    Ok(file)
+}
+
+fn read_records(path: &Path, reader: &Reader) -> PyResult<LogFile> {
+    let name = path.to_string_lossy();
+    if name.ends_with(".parquet") {
+        return parquet::read(path, reader);
+    }
+    if name.ends_with(".gz") {
+        let data = crate::platform::gunzip_file(path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::Unsupported => pyo3::exceptions::PyNotImplementedError::new_err(
+                "gzip is not available: this build has no 'compression' feature",
+            ),
+            _ => file_error(path, e),
+        })?;
+        return read_content(&data, reader);
+    }
+    if reader.since.is_some() || reader.until.is_some() {
+        if let Some(span) = crate::index::span(path, reader.since, reader.until) {
+            let data = read_span(path, &span).map_err(|e| file_error(path, e))?;
+            let file = read_content(&data, reader)?;
+            // The index belongs to this file if its entry names the record found there.
+            let first = file.times.first().copied().flatten();
+            let matches = match (span.start_time, first) {
+                (None, _) => true,
+                (Some(expected), Some(found)) => (expected - found).abs() < 1.0,
+                (Some(_), None) => false,
+            };
+            if matches {
+                return Ok(file);
+            }
+        }
+    }
+    let data = std::fs::read(path).map_err(|e| file_error(path, e))?;
+    read_content(&data, reader)
+}
+
+/// The bytes of `path` that `span` covers.
+fn read_span(path: &Path, span: &crate::index::Span) -> std::io::Result<Vec<u8>> {
+    let mut file = std::fs::File::open(path)?;
+    file.seek(SeekFrom::Start(span.start))?;
+    let mut data = Vec::new();
+    match span.end {
+        Some(end) => file.take(end - span.start).read_to_end(&mut data)?,
+        None => file.read_to_end(&mut data)?,
+    };
+    Ok(data)
+}
+
+/// Read the records in `data`, as JSON lines when its first line is a JSON object and
+/// as text otherwise.
+fn read_content(data: &[u8], reader: &Reader) -> PyResult<LogFile> {
+    let content = String::from_utf8_lossy(data);
+    let json = content
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .is_some_and(|line| serde_json::from_str::<Map<String, Value>>(line).is_ok());
+    if json {
+        read_json(&content, reader)
+    } else {
+        read_text(&content, reader)
+    }
+}
+
+#[cfg(feature = "regex-filters")]
+fn read_text(content: &str, reader: &Reader) -> PyResult<LogFile> {
+    let mut file = LogFile::new(Layout::Text);
+    if reader.schema_version.is_none() {
+        for (_, name) in &reader.parser.groups {
+            file.add_field(name);
+        }
+    }
+    for line in content.lines().filter(|line| !is_trailer(line.as_bytes())) {
+        match reader.parser.parse(line) {
+            Some(fields) => {
+                let (record, time) = reader.record(&mut file, fields)?;
+                file.push(record, time, line);
+            }
+            None => file.continue_last(line),
+        }
+    }
+    // Written out again with the milliseconds that `asctime` may have dropped.
+    if file.times.iter().any(Option::is_some) {
+        file.add_field("created");
+    }
+    Ok(file)
+}
+
+#[cfg(not(feature = "regex-filters"))]
+fn read_text(_content: &str, _reader: &Reader) -> PyResult<LogFile> {
+    Err(pyo3::exceptions::PyNotImplementedError::new_err(
+        "text logs cannot be read: this build has no 'regex-filters' feature",
+    ))
+}
+
+fn read_json(content: &str, reader: &Reader) -> PyResult<LogFile> {
+    let mut file = LogFile::new(Layout::Json);
+    for line in content.lines().filter(|line| !is_trailer(line.as_bytes())) {
+        match serde_json::from_str::<Map<String, Value>>(line) {
+            Ok(object) => {
+                if reader.schema_version.is_none() {
+                    for key in object.keys() {
+                        file.add_field(key);
+                    }
+                }
+                let (record, time) = reader.record(
+                    &mut file,
+                    object.iter().map(|(k, v)| (k.as_str(), v.clone())),
+                )?;
+                file.push(record, time, line);
+            }
+            Err(_) => file.continue_last(line),
+        }
+    }
+    Ok(file)
+}
+
+/// Write `file`'s records as text in `format`, or as JSON lines with its fields.
+fn render(file: &LogFile, layout: Layout, format: &str, datefmt: &str) -> String {
+    let formatter: Box<dyn Formatter> = match layout {
+        Layout::Json => {
+            let mut fields = file.fields.clone();
+            if !fields.iter().any(|field| field == "message") {
+                fields.push("message".to_string());
+            }
+            Box::new(JsonFormatter::new(
+                Some(fields),
+                HashMap::new(),
+                Some(datefmt.to_string()),
+            ))
+        }
+        _ => Box::new(PythonFormatter::with_date_format(
+            format.to_string(),
+            datefmt.to_string(),
+        )),
+    };
+    let mut out = String::new();
+    for record in &file.records {
+        out.push_str(&formatter.format(record));
+        out.push('\n');
+    }
+    out
+}
+
+/// Write `text` to `dst`, or hand it back when there is no `dst`.
+fn output(text: String, dst: Option<FsPath>) -> PyResult<Option<String>> {
+    match dst {
+        Some(FsPath(path)) => {
+            std::fs::write(&path, text).map_err(|e| file_error(&path, e))?;
+            Ok(None)
+        }
+        None => Ok(Some(text)),
+    }
+}
+
+/// Convert a log file between text, JSON lines and parquet.
+///
+/// `src` is read as parquet when its name ends in `.parquet`, as JSON lines when its
+/// first line is a JSON object, and otherwise as text in `format` (default
+/// `"%(levelname)s:%(name)s:%(message)s"`); `.gz` files are decompressed first. Lines
+/// that are not records of their own (tracebacks) go with the record before them.
+/// `pattern`, a regex whose named groups are the fields (`(?P<levelname>\w+)`), reads
+/// text that no format string describes; it must match the whole line. With
+/// `schema_version`, every record is brought to that version through the migrations
+/// registered with `register_schema_migration()` before it is written. `to` is
+/// `"text"`, `"json"` or `"parquet"`; `%(asctime)s` is read and written in `datefmt`.
+/// Writes to `dst`, or returns the text when `dst` is None. `since` and `until` (a
+/// datetime or seconds since the epoch) keep only the records created in that range,
+/// using the file's `index_every=` index when it has one. Parquet needs the `parquet`
+/// cargo feature.
+#[pyfunction]
+#[pyo3(signature = (
+    src,
+    dst=None,
+    to="json",
+    format=None,
+    datefmt=None,
+    pattern=None,
+    schema_version=None,
+    since=None,
+    until=None
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn convert_log_file(
+    py: Python,
+    src: FsPath,
+    dst: Option<FsPath>,
+    to: &str,
+    format: Option<String>,
+    datefmt: Option<String>,
+    pattern: Option<String>,
+    schema_version: Option<u32>,
+    since: Option<Bound<PyAny>>,
+    until: Option<Bound<PyAny>>,
+) -> PyResult<Option<String>> {
+    let layout = Layout::parse(to)?;
+    let format = format.as_deref().unwrap_or(DEFAULT_FORMAT);
+    let datefmt = datefmt.as_deref().unwrap_or(DEFAULT_DATEFMT);
+    let mut reader = Reader::new(format, datefmt, pattern.as_deref())?
+        .between(since.as_ref(), until.as_ref())?;
+    reader.schema_version = schema_version;
+    let file = read_file(&src.0, &reader)?;
+    if layout == Layout::Parquet {
+        let Some(FsPath(dst)) = dst else {
+            return Err(PyValueError::new_err(
+                "parquet output needs a destination file",
+            ));
+        };
+        parquet::write(&dst, &file, datefmt)?;
+        return Ok(None);
+    }
+    let text = py.detach(|| render(&file, layout, format, datefmt));
+    output(text, dst)
+}
+
+/// Merge log files into one, ordered by when each record was created.
+///
+/// Each file is read as in `convert_log_file`, and its records are written back as the
+/// lines they were read from, so text and JSON files can be merged as they are.
+/// Records without a time of their own sort with the record before them; equal times
+/// keep the order of `paths`. `since` and `until` keep only the records created in
+/// that range. Writes to `dst`, or returns the text when `dst` is None.
+#[pyfunction]
+#[pyo3(signature = (paths, dst=None, format=None, datefmt=None, pattern=None, since=None, until=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn merge_log_files(
+    py: Python,
+    paths: Vec<FsPath>,
+    dst: Option<FsPath>,
+    format: Option<String>,
+    datefmt: Option<String>,
+    pattern: Option<String>,
+    since: Option<Bound<PyAny>>,
+    until: Option<Bound<PyAny>>,
+) -> PyResult<Option<String>> {
+    let format = format.as_deref().unwrap_or(DEFAULT_FORMAT);
+    let datefmt = datefmt.as_deref().unwrap_or(DEFAULT_DATEFMT);
+    let reader = Reader::new(format, datefmt, pattern.as_deref())?
+        .between(since.as_ref(), until.as_ref())?;
+    let files = paths
+        .iter()
+        .map(|FsPath(path)| {
+            let file = read_file(path, &reader)?;
+            if file.layout == Layout::Parquet {
+                return Err(PyValueError::new_err(format!(
+                    "{}: merge reads text and JSON logs; convert parquet files first",
+                    path.display()
+                )));
+            }
+            Ok(file)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let text = py.detach(|| {
+        let mut lines: Vec<(f64, &str)> = Vec::new();
+        for file in &files {
+            // Records before the first time in a file sort with that time.
+            let first = file.times.iter().flatten().next().copied();
+            let mut last = first.unwrap_or(f64::NEG_INFINITY);
+            for (time, line) in file.times.iter().zip(&file.lines) {
+                last = time.unwrap_or(last);
+                lines.push((last, line));
+            }
+        }
+        // Stable, so equal times keep the file order.
+        lines.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut out = String::new();
+        for (_, line) in lines {
+            out.push_str(line);
+            out.push('\n');
+        }
+        out
+    });
+    output(text, dst)
+}
+
+/// Count the records in log files by logger and level.
+///
+/// Each file is read as in `convert_log_file`. Returns a dict with `records` (the
+/// total), `first` and `last` (the earliest and latest creation times, None when no
+/// record has one), `levels` ({levelname: count}, by level) and `loggers` ({name:
+/// {levelname: count}}, by name). `since` and `until` count only the records created in
+/// that range.
+#[pyfunction]
+#[pyo3(signature = (paths, format=None, datefmt=None, pattern=None, since=None, until=None))]
+pub fn log_file_stats<'py>(
+    py: Python<'py>,
+    paths: Vec<FsPath>,
+    format: Option<String>,
+    datefmt: Option<String>,
+    pattern: Option<String>,
+    since: Option<Bound<'py, PyAny>>,
+    until: Option<Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let format = format.as_deref().unwrap_or(DEFAULT_FORMAT);
+    let datefmt = datefmt.as_deref().unwrap_or(DEFAULT_DATEFMT);
+    let reader = Reader::new(format, datefmt, pattern.as_deref())?
+        .between(since.as_ref(), until.as_ref())?;
+    let mut total = 0;
+    let mut first: Option<f64> = None;
+    let mut last: Option<f64> = None;
+    let mut levels: BTreeMap<(i32, String), u64> = BTreeMap::new();
+    let mut loggers: BTreeMap<String, BTreeMap<(i32, String), u64>> = BTreeMap::new();
+    for FsPath(path) in &paths {
+        let file = read_file(path, &reader)?;
+        total += file.records.len();
+        for time in file.times.iter().flatten() {
+            first = Some(first.map_or(*time, |t| t.min(*time)));
+            last = Some(last.map_or(*time, |t| t.max(*time)));
+        }
+        for record in &file.records {
+            let level = (record.levelno, record.levelname.clone());
+            *levels.entry(level.clone()).or_default() += 1;
+            *loggers
+                .entry(record.name.clone())
+                .or_default()
+                .entry(level)
+                .or_default() += 1;
+        }
+    }
+    let counts = |counts: &BTreeMap<(i32, String), u64>| -> PyResult<Bound<PyDict>> {
+        let dict = PyDict::new(py);
+        for ((_, name), count) in counts {
+            dict.set_item(name, count)?;
+        }
+        Ok(dict)
+    };
+    let stats = PyDict::new(py);
+    stats.set_item("records", total)?;
+    stats.set_item("first", first)?;
+    stats.set_item("last", last)?;
+    stats.set_item("levels", counts(&levels)?)?;
+    let by_logger = PyDict::new(py);
+    for (name, levels) in &loggers {
+        by_logger.set_item(name, counts(levels)?)?;
+    }
+    stats.set_item("loggers", by_logger)?;
+    Ok(stats)
+}
+
+/// Read a log file back into `LogRecord`s.
+///
+/// The file is read as in `convert_log_file`: text in `format` or matched by `pattern`,
+/// JSON lines or parquet. Fields the format doesn't know become extras, and a traceback
+/// under a record is its `exc_text`. `created` is 0 for records the file gives no time.
+/// `since` and `until` (a datetime or seconds since the epoch) keep only the records
+/// created in that range; with the sidecar index of a file written with
+/// `index_every=`, only that part of the file is read.
+#[pyfunction]
+#[pyo3(signature = (src, format=None, datefmt=None, pattern=None, since=None, until=None))]
+pub fn read_log_file(
+    src: FsPath,
+    format: Option<String>,
+    datefmt: Option<String>,
+    pattern: Option<String>,
+    since: Option<Bound<PyAny>>,
+    until: Option<Bound<PyAny>>,
+) -> PyResult<Vec<LogRecord>> {
+    let format = format.as_deref().unwrap_or(DEFAULT_FORMAT);
+    let datefmt = datefmt.as_deref().unwrap_or(DEFAULT_DATEFMT);
+    let reader = Reader::new(format, datefmt, pattern.as_deref())?
+        .between(since.as_ref(), until.as_ref())?;
+    Ok(read_file(&src.0, &reader)?.records)
+}
+
+/// Log the records of a log file again, through the handlers configured now.
+///
+/// The file is read as in `read_log_file`. Each record goes to the logger it names, or
+/// to `logger` when given, and from there to that logger's handlers and its ancestors'
+/// as if it had just been logged: level checks, filters and redaction apply. Records
+/// keep their own name, level, message and time; those without a time get the current
+/// one. `since` and `until` replay only the records created in that range. Returns how
+/// many records passed the level checks.
+#[pyfunction]
+#[pyo3(signature = (src, logger=None, format=None, datefmt=None, pattern=None, since=None, until=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn replay_log_file(
+    py: Python,
+    src: FsPath,
+    logger: Option<String>,
+    format: Option<String>,
+    datefmt: Option<String>,
+    pattern: Option<String>,
+    since: Option<Bound<PyAny>>,
+    until: Option<Bound<PyAny>>,
+) -> PyResult<usize> {
+    let format = format.as_deref().unwrap_or(DEFAULT_FORMAT);
+    let datefmt = datefmt.as_deref().unwrap_or(DEFAULT_DATEFMT);
+    let reader = Reader::new(format, datefmt, pattern.as_deref())?
+        .between(since.as_ref(), until.as_ref())?;
+    let file = read_file(&src.0, &reader)?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |elapsed| elapsed.as_secs_f64());
+    let mut loggers: HashMap<String, Py<PyLogger>> = HashMap::new();
+    let mut replayed = 0;
+    for (mut record, time) in file.records.into_iter().zip(file.times) {
+        if time.is_none() {
+            record.created = now;
+            record.msecs = (now.fract() * 1000.0).floor();
+        }
+        let name = match (&logger, record.name.as_str()) {
+            (Some(name), _) => name.clone(),
+            (None, "") => "root".to_string(),
+            (None, name) => name.to_string(),
+        };
+        let target = match loggers.entry(name) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let logger = crate::globals::get_logger(py, Some(entry.key()), None)?;
+                entry.insert(logger)
+            }
+        };
+        if target.borrow(py).replay(py, record) {
+            replayed += 1;
+        }
+    }
+    Ok(replayed)
+}
+
+#[cfg(all(feature = "parquet", not(target_family = "wasm")))]
+mod parquet {
+    //! Parquet files hold one column per field: integers, floats and booleans keep
+    //! their type, everything else is a string.
+
+    use super::{Layout, LogFile, Reader};
+    use crate::formatter::JsonFormatter;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Float64Type, Int32Type, Int64Type, UInt64Type};
+    use arrow_array::{
+        ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, RecordBatchReader,
+        StringArray, UInt64Array,
+    };
+    use arrow_schema::{DataType, Field, Schema};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use parquet::arrow::ArrowWriter;
+    use parquet::basic::Compression;
+    use parquet::file::properties::WriterProperties;
+    use pyo3::exceptions::PyValueError;
+    use pyo3::prelude::*;
+    use serde_json::Value;
+    use std::collections::HashMap;
+    use std::fs::File;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    fn error(path: &Path, e: impl std::fmt::Display) -> PyErr {
+        PyValueError::new_err(format!("{}: {e}", path.display()))
+    }
+
+    pub fn read(path: &Path, reader: &Reader) -> PyResult<LogFile> {
+        let batches = ParquetRecordBatchReaderBuilder::try_new(
+            File::open(path).map_err(|e| super::file_error(path, e))?,
+        )
+        .and_then(|builder| builder.build())
+        .map_err(|e| error(path, e))?;
+        let mut file = LogFile::new(Layout::Parquet);
+        let columns: Vec<String> = batches
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect();
+        if reader.schema_version.is_none() {
+            for name in &columns {
+                file.add_field(name);
+            }
+        }
+        for batch in batches {
+            let batch = batch.map_err(|e| error(path, e))?;
+            for row in 0..batch.num_rows() {
+                let mut fields = Vec::with_capacity(batch.num_columns());
+                for (name, column) in columns.iter().zip(batch.columns()) {
+                    if column.is_null(row) {
+                        continue;
+                    }
+                    let value = match column.data_type() {
+                        DataType::Int64 => column.as_primitive::<Int64Type>().value(row).into(),
+                        DataType::Int32 => column.as_primitive::<Int32Type>().value(row).into(),
+                        DataType::UInt64 => column.as_primitive::<UInt64Type>().value(row).into(),
+                        DataType::Float64 => column.as_primitive::<Float64Type>().value(row).into(),
+                        DataType::Boolean => column.as_boolean().value(row).into(),
+                        DataType::Utf8 => column.as_string::<i32>().value(row).into(),
+                        DataType::LargeUtf8 => column.as_string::<i64>().value(row).into(),
+                        other => {
+                            return Err(error(
+                                path,
+                                format!("column {name:?} has unsupported type {other}"),
+                            ))
+                        }
+                    };
+                    fields.push((name.as_str(), value));
+                }
+                let (record, time) = reader.record(&mut file, fields)?;
+                file.records.push(record);
+                file.times.push(time);
+            }
+        }
+        Ok(file)
+    }
+
+    pub fn write(path: &Path, file: &LogFile, datefmt: &str) -> PyResult<()> {
+        let mut fields = file.fields.clone();
+        if !fields.iter().any(|field| field == "message") {
+            fields.push("message".to_string());
+        }
+        let formatter =
+            JsonFormatter::new(Some(fields.clone()), HashMap::new(), Some(datefmt.into()));
+        let rows: Vec<_> = file
+            .records
+            .iter()
+            .map(|record| formatter.entries(record))
+            .collect();
+        let mut schema = Vec::with_capacity(fields.len());
+        let mut columns: Vec<ArrayRef> = Vec::with_capacity(fields.len());
+        for field in &fields {
+            let values: Vec<Option<&Value>> = rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .find(|(key, _)| key == field)
+                        .map(|(_, value)| value)
+                        .filter(|value| !value.is_null())
+                })
+                .collect();
+            let all = |test: fn(&Value) -> bool| values.iter().flatten().all(|v| test(v));
+            let (data_type, column): (DataType, ArrayRef) = if all(Value::is_i64) {
+                let column = values.iter().map(|v| v.and_then(Value::as_i64));
+                (DataType::Int64, Arc::new(column.collect::<Int64Array>()))
+            } else if all(Value::is_u64) {
+                let column = values.iter().map(|v| v.and_then(Value::as_u64));
+                (DataType::UInt64, Arc::new(column.collect::<UInt64Array>()))
+            } else if all(Value::is_number) {
+                let column = values.iter().map(|v| v.and_then(Value::as_f64));
+                (
+                    DataType::Float64,
+                    Arc::new(column.collect::<Float64Array>()),
+                )
+            } else if all(Value::is_boolean) {
+                let column = values.iter().map(|v| v.and_then(Value::as_bool));
+                (
+                    DataType::Boolean,
+                    Arc::new(column.collect::<BooleanArray>()),
+                )
+            } else {
+                let column = values.iter().map(|v| {
+                    v.map(|v| match v {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                });
+                (DataType::Utf8, Arc::new(column.collect::<StringArray>()))
+            };
+            schema.push(Field::new(field, data_type, true));
+            columns.push(column);
+        }
+        let schema = Arc::new(Schema::new(schema));
+        let batch = RecordBatch::try_new(schema.clone(), columns).map_err(|e| error(path, e))?;
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let mut writer = ArrowWriter::try_new(
+            File::create(path).map_err(|e| super::file_error(path, e))?,
+            schema,
+            Some(properties),
+        )
+        .map_err(|e| error(path, e))?;
+        writer.write(&batch).map_err(|e| error(path, e))?;
+        writer.close().map_err(|e| error(path, e))?;
+        Ok(())
+    }
+}
+
+#[cfg(not(all(feature = "parquet", not(target_family = "wasm"))))]
+mod parquet {
+    use super::{LogFile, Reader};
+    use pyo3::prelude::*;
+    use std::path::Path;
+
+    fn unavailable() -> PyErr {
+        pyo3::exceptions::PyNotImplementedError::new_err(
+            "parquet is not available: this build has no 'parquet' feature",
+        )
+    }
+
+    pub fn read(_path: &Path, _reader: &Reader) -> PyResult<LogFile> {
+        Err(unavailable())
+    }
+
+    pub fn write(_path: &Path, _file: &LogFile, _datefmt: &str) -> PyResult<()> {
+        Err(unavailable())
+    }
+}
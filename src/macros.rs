@@ -0,0 +1,136 @@
+//! # Logging Macros
+//!
+//! Call-site aware logging for Rust callers. Unlike `Logger::info(&str)`, the macros
+//! record the Rust `file!()` / `line!()` / `module_path!()` of the call as the
+//! record's `pathname` / `filename` / `lineno` / `module`, so `%(pathname)s:%(lineno)d`
+//! points at Rust source. The message is a `format!` string and is only built when
+//! the level is enabled.
+//!
+//! ```
+//! let logger = logxide::core::get_logger("app.net");
+//! let logger = logger.lock().unwrap();
+//! let (host, elapsed) = ("db-1", 12);
+//! logxide::info!(logger, "connected to {} in {}ms", host, elapsed);
+//! logxide::log!(logger, logxide::LogLevel::Warning, "retrying");
+//! ```
+
+/// Log a `format!` message at `level` with the caller's source location.
+#[macro_export]
+macro_rules! log {
+    ($logger:expr, $level:expr, $($arg:tt)+) => {{
+        let logger: &$crate::core::Logger = &$logger;
+        let level: $crate::LogLevel = $level;
+        if logger.is_enabled_for(level) {
+            logger.handle(::std::sync::Arc::new(
+                $crate::core::create_log_record(logger.name.clone(), level, format!($($arg)+))
+                    .with_location(file!(), line!(), module_path!()),
+            ));
+        }
+    }};
+}
+
+/// Log at DEBUG with the caller's source location.
+#[macro_export]
+macro_rules! debug {
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::log!($logger, $crate::LogLevel::Debug, $($arg)+)
+    };
+}
+
+/// Log at INFO with the caller's source location.
+#[macro_export]
+macro_rules! info {
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::log!($logger, $crate::LogLevel::Info, $($arg)+)
+    };
+}
+
+/// Log at WARNING with the caller's source location.
+#[macro_export]
+macro_rules! warning {
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::log!($logger, $crate::LogLevel::Warning, $($arg)+)
+    };
+}
+
+/// Log at ERROR with the caller's source location.
+#[macro_export]
+macro_rules! error {
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::log!($logger, $crate::LogLevel::Error, $($arg)+)
+    };
+}
+
+/// Log at CRITICAL with the caller's source location.
+#[macro_export]
+macro_rules! critical {
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::log!($logger, $crate::LogLevel::Critical, $($arg)+)
+    };
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::core::{get_logger, LogLevel, LogRecord};
+    use crate::filter::Filter;
+    use crate::formatter::Formatter;
+    use crate::handler::Handler;
+
+    /// Keeps the records it is given.
+    #[derive(Default)]
+    pub(crate) struct Capture(pub(crate) Mutex<Vec<Arc<LogRecord>>>);
+
+    impl Handler for Capture {
+        fn emit(&self, record: &Arc<LogRecord>) {
+            self.0.lock().unwrap().push(record.clone());
+        }
+        fn flush(&self) {}
+        fn set_formatter(&mut self, _formatter: Arc<dyn Formatter + Send + Sync>) {}
+        fn add_filter(&mut self, _filter: Arc<dyn Filter + Send + Sync>) {}
+    }
+
+    #[test]
+    fn level_macros_record_the_call_site() {
+        let capture = Arc::new(Capture::default());
+        let logger = get_logger("test.macros");
+        let mut logger = logger.lock().unwrap();
+        logger.set_level(LogLevel::Debug);
+        logger.propagate = false;
+        logger.add_handler(capture.clone());
+
+        let line = line!();
+        crate::debug!(logger, "debug {}", 1);
+        crate::info!(logger, "connected to {} in {}ms", "db", 12);
+        crate::warning!(logger, "warning");
+        crate::error!(logger, "error");
+        crate::critical!(logger, "critical");
+        crate::log!(logger, LogLevel::new(25), "custom");
+        logger.set_level(LogLevel::Error);
+        crate::info!(logger, "filtered");
+
+        let records = capture.0.lock().unwrap();
+        let seen: Vec<_> = records
+            .iter()
+            .map(|r| (r.levelno, r.msg.as_str(), r.lineno))
+            .collect();
+        assert_eq!(
+            seen,
+            [
+                (10, "debug 1", line + 1),
+                (20, "connected to db in 12ms", line + 2),
+                (30, "warning", line + 3),
+                (40, "error", line + 4),
+                (50, "critical", line + 5),
+                (25, "custom", line + 6),
+            ]
+        );
+        for record in records.iter() {
+            assert_eq!(record.name, "test.macros");
+            assert_eq!(record.pathname, file!());
+            assert_eq!(record.filename, "macros.rs");
+            assert_eq!(record.module, module_path!());
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! # Pickle Encoding
+//!
+//! Minimal pickle (protocol 2) writer for the payload stdlib's `DatagramHandler` and
+//! `SocketHandler` send: one dict of str keys to JSON-shaped values, which the
+//! receiving side turns back into a record with
+//! `logging.makeLogRecord(pickle.loads(data))`. Only the opcodes those values need
+//! are emitted, and there is no memo, so repeated values are written out in full.
+
+use serde_json::Value;
+
+const PROTO: u8 = 0x80;
+const STOP: u8 = b'.';
+const NONE: u8 = b'N';
+const NEWTRUE: u8 = 0x88;
+const NEWFALSE: u8 = 0x89;
+const BININT: u8 = b'J';
+const LONG1: u8 = 0x8a;
+const BINFLOAT: u8 = b'G';
+const BINUNICODE: u8 = b'X';
+const EMPTY_DICT: u8 = b'}';
+const EMPTY_LIST: u8 = b']';
+const MARK: u8 = b'(';
+const SETITEMS: u8 = b'u';
+const APPENDS: u8 = b'e';
+
+/// Builds a pickled `dict` one key at a time. Later keys overwrite earlier ones on
+/// load, as with repeated `d[key] = value`.
+pub struct DictWriter {
+    buf: Vec<u8>,
+}
+
+impl DictWriter {
+    pub fn new() -> Self {
+        Self {
+            buf: vec![PROTO, 2, EMPTY_DICT, MARK],
+        }
+    }
+
+    pub fn str(&mut self, key: &str, value: &str) {
+        self.key(key);
+        self.write_str(value);
+    }
+
+    pub fn int(&mut self, key: &str, value: i128) {
+        self.key(key);
+        self.write_int(value);
+    }
+
+    pub fn float(&mut self, key: &str, value: f64) {
+        self.key(key);
+        self.write_float(value);
+    }
+
+    pub fn none(&mut self, key: &str) {
+        self.key(key);
+        self.buf.push(NONE);
+    }
+
+    pub fn value(&mut self, key: &str, value: &Value) {
+        self.key(key);
+        self.write_value(value);
+    }
+
+    /// Close the dict and the pickle.
+    pub fn finish(mut self) -> Vec<u8> {
+        self.buf.push(SETITEMS);
+        self.buf.push(STOP);
+        self.buf
+    }
+
+    fn key(&mut self, key: &str) {
+        self.write_str(key);
+    }
+
+    fn write_str(&mut self, s: &str) {
+        self.buf.push(BINUNICODE);
+        self.buf.extend_from_slice(&(s.len() as u32).to_le_bytes());
+        self.buf.extend_from_slice(s.as_bytes());
+    }
+
+    fn write_int(&mut self, n: i128) {
+        if let Ok(small) = i32::try_from(n) {
+            self.buf.push(BININT);
+            self.buf.extend_from_slice(&small.to_le_bytes());
+            return;
+        }
+        // LONG1: shortest little-endian two's complement encoding.
+        let mut bytes = n.to_le_bytes().to_vec();
+        while bytes.len() > 1 {
+            let last = bytes[bytes.len() - 1];
+            let sign = bytes[bytes.len() - 2] & 0x80;
+            if (last == 0x00 && sign == 0) || (last == 0xff && sign != 0) {
+                bytes.pop();
+            } else {
+                break;
+            }
+        }
+        self.buf.push(LONG1);
+        self.buf.push(bytes.len() as u8);
+        self.buf.extend_from_slice(&bytes);
+    }
+
+    fn write_float(&mut self, f: f64) {
+        self.buf.push(BINFLOAT);
+        self.buf.extend_from_slice(&f.to_be_bytes());
+    }
+
+    fn write_value(&mut self, value: &Value) {
+        match value {
+            Value::Null => self.buf.push(NONE),
+            Value::Bool(true) => self.buf.push(NEWTRUE),
+            Value::Bool(false) => self.buf.push(NEWFALSE),
+            Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    self.write_int(i.into());
+                } else if let Some(u) = n.as_u64() {
+                    self.write_int(u.into());
+                } else {
+                    self.write_float(n.as_f64().unwrap_or(f64::NAN));
+                }
+            }
+            Value::String(s) => self.write_str(s),
+            Value::Array(items) => {
+                self.buf.push(EMPTY_LIST);
+                if !items.is_empty() {
+                    self.buf.push(MARK);
+                    for item in items {
+                        self.write_value(item);
+                    }
+                    self.buf.push(APPENDS);
+                }
+            }
+            Value::Object(map) => {
+                self.buf.push(EMPTY_DICT);
+                if !map.is_empty() {
+                    self.buf.push(MARK);
+                    for (k, v) in map {
+                        self.write_str(k);
+                        self.write_value(v);
+                    }
+                    self.buf.push(SETITEMS);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,585 @@
+//! # Platform Support
+//!
+//! Capabilities of the current build target. Native builds run the queued handlers on
+//! background threads and ship HTTP/OTLP batches over ureq. wasm32 builds (Pyodide /
+//! `wasm32-unknown-emscripten`) have neither threads nor sockets: StreamHandler writes
+//! synchronously, through the JS console under emscripten, and the network handlers
+//! refuse to construct with a clear error instead of failing on first emit. So does
+//! PipeHandler, since there are no subprocesses either.
+
+use pyo3::exceptions::PyNotImplementedError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+#[cfg(feature = "network")]
+use std::collections::HashMap;
+#[cfg(feature = "network")]
+use std::time::Duration;
+
+use crate::handler::StreamDestination;
+
+/// Background worker threads are available (queued StreamHandler, HTTP/OTLP senders).
+pub const THREADS: bool = cfg!(not(target_family = "wasm"));
+
+/// Threads have their own nice value (see `set_thread_nice`).
+pub const THREAD_NICE: bool = cfg!(target_os = "linux");
+
+/// Threads can be pinned to CPU cores (see `set_thread_affinity`).
+pub const THREAD_AFFINITY: bool = cfg!(target_os = "linux");
+
+/// Cores a thread can be pinned to: the size of glibc's `cpu_set_t`.
+pub const MAX_CORES: usize = 1024;
+
+/// Outbound sockets are available (HTTPHandler, OTLPHandler, DatagramHandler).
+pub const NETWORK: bool = cfg!(all(feature = "network", not(target_family = "wasm")));
+
+/// Optional cargo features compiled into this build.
+const FEATURES: &[(&str, bool)] = &[
+    ("file", cfg!(feature = "file")),
+    ("network", cfg!(feature = "network")),
+    ("otlp", cfg!(feature = "otlp")),
+    ("compression", cfg!(feature = "compression")),
+    ("pipe", cfg!(feature = "pipe")),
+    ("smtp", cfg!(feature = "smtp")),
+    ("colors", cfg!(feature = "colors")),
+    ("json", cfg!(feature = "json")),
+    ("watch", cfg!(feature = "watch")),
+    ("regex-filters", cfg!(feature = "regex-filters")),
+    (
+        "parquet",
+        cfg!(all(feature = "parquet", not(target_family = "wasm"))),
+    ),
+];
+
+/// Name of the platform reported by `runtime_info()`.
+const PLATFORM: &str = if cfg!(target_family = "wasm") {
+    "wasm32"
+} else {
+    "native"
+};
+
+/// Where StreamHandler output ends up.
+const CONSOLE: &str = if cfg!(target_os = "emscripten") {
+    "js"
+} else {
+    "stdio"
+};
+
+/// A filesystem path argument, taken like `open()` takes one: str, bytes or
+/// `os.PathLike`. Names that are not valid UTF-8 (undecodable bytes, str with surrogate
+/// escapes, unpaired surrogates on Windows) reach the OS unchanged.
+#[cfg(any(feature = "file", feature = "watch", feature = "json"))]
+pub struct FsPath(pub std::path::PathBuf);
+
+#[cfg(any(feature = "file", feature = "watch", feature = "json"))]
+impl FromPyObject<'_, '_> for FsPath {
+    type Error = PyErr;
+
+    fn extract(ob: Borrowed<'_, '_, PyAny>) -> PyResult<Self> {
+        // os.fsdecode turns bytes into str with surrogate escapes, which the PathBuf
+        // conversion encodes back to the original bytes.
+        let py = ob.py();
+        let decoded = py
+            .import(pyo3::intern!(py, "os"))?
+            .call_method1(pyo3::intern!(py, "fsdecode"), (ob,))?;
+        Ok(Self(decoded.extract()?))
+    }
+}
+
+/// Raise NotImplementedError when `kind` needs the network on a build without it.
+#[cfg(feature = "network")]
+pub fn require_network(kind: &str) -> PyResult<()> {
+    if NETWORK {
+        return Ok(());
+    }
+    Err(PyNotImplementedError::new_err(format!(
+        "{kind} is not available on {PLATFORM}: this build has no threads or network access"
+    )))
+}
+
+/// Raise NotImplementedError when request-body or rotated-file compression isn't
+/// compiled in.
+#[cfg(any(feature = "file", feature = "network"))]
+pub fn require_compression(kind: &str) -> PyResult<()> {
+    if cfg!(all(feature = "compression", not(target_family = "wasm"))) {
+        return Ok(());
+    }
+    Err(PyNotImplementedError::new_err(format!(
+        "{kind} compression is not available: this build has no 'compression' feature"
+    )))
+}
+
+/// Raise NotImplementedError when `kind` needs to spawn processes on a build that can't.
+#[cfg(feature = "pipe")]
+pub fn require_subprocess(kind: &str) -> PyResult<()> {
+    if THREADS {
+        return Ok(());
+    }
+    Err(PyNotImplementedError::new_err(format!(
+        "{kind} is not available on {PLATFORM}: this build has no threads or subprocesses"
+    )))
+}
+
+/// Raise NotImplementedError when `kind` needs a background thread on a build without.
+pub fn require_threads(kind: &str) -> PyResult<()> {
+    if THREADS {
+        return Ok(());
+    }
+    Err(PyNotImplementedError::new_err(format!(
+        "{kind} is not available on {PLATFORM}: this build has no threads"
+    )))
+}
+
+/// Write one line of StreamHandler output.
+#[cfg(not(target_os = "emscripten"))]
+pub fn write_console(dest: StreamDestination, msg: &str) {
+    use std::io::Write;
+    match dest {
+        StreamDestination::Stdout => {
+            let stdout = std::io::stdout();
+            let _ = writeln!(stdout.lock(), "{msg}");
+        }
+        StreamDestination::Stderr => {
+            let stderr = std::io::stderr();
+            let _ = writeln!(stderr.lock(), "{msg}");
+        }
+    }
+}
+
+/// Whether StreamHandler output currently goes to a terminal.
+#[cfg(not(target_os = "emscripten"))]
+pub fn console_is_terminal(dest: StreamDestination) -> bool {
+    use std::io::IsTerminal;
+    match dest {
+        StreamDestination::Stdout => std::io::stdout().is_terminal(),
+        StreamDestination::Stderr => std::io::stderr().is_terminal(),
+    }
+}
+
+/// The JS console is not a terminal and renders no ANSI escapes.
+#[cfg(target_os = "emscripten")]
+pub fn console_is_terminal(_dest: StreamDestination) -> bool {
+    false
+}
+
+/// Whether `sys.stdout` and `sys.stderr` can both encode `text`, e.g. so emoji
+/// aren't written to a latin-1 or ASCII console. A missing stream doesn't count.
+#[cfg(feature = "colors")]
+pub fn console_can_encode(py: Python, text: &str) -> bool {
+    let Ok(sys) = py.import("sys") else {
+        return true;
+    };
+    ["stdout", "stderr"].iter().all(|name| {
+        let encoding = sys
+            .getattr(*name)
+            .and_then(|stream| stream.getattr("encoding"))
+            .and_then(|encoding| encoding.extract::<String>());
+        match encoding {
+            Ok(encoding) => pyo3::types::PyString::new(py, text)
+                .call_method1("encode", (encoding,))
+                .is_ok(),
+            Err(_) => true,
+        }
+    })
+}
+
+/// The `LC_TIME` names `time.strftime` would print right now, or `None` in the C
+/// locale, where chrono's English names already match.
+pub fn locale_time(py: Python) -> Option<crate::formatter::LocaleTime> {
+    let locale = py.import("locale").ok()?;
+    let lc_time = locale.getattr("LC_TIME").ok()?;
+    let current: String = locale
+        .call_method1("setlocale", (lc_time,))
+        .and_then(|name| name.extract())
+        .ok()?;
+    if matches!(current.as_str(), "C" | "POSIX") {
+        return None;
+    }
+    let time = py.import("time").ok()?;
+    // 2024-01-01 was a Monday; the struct_time fields are (year, month, day, hour,
+    // minute, second, weekday, yearday, isdst).
+    let strftime = |format: &str, month: i32, day: i32, hour: i32| -> Option<String> {
+        let weekday = if month == 1 { day - 1 } else { 0 };
+        time.call_method1(
+            "strftime",
+            (format, (2024, month, day, hour, 0, 0, weekday, day, -1)),
+        )
+        .and_then(|name| name.extract())
+        .ok()
+    };
+    let weekdays = |format: &str| -> Option<[String; 7]> {
+        let names: Option<Vec<String>> = (1..=7).map(|day| strftime(format, 1, day, 0)).collect();
+        names?.try_into().ok()
+    };
+    let months = |format: &str| -> Option<[String; 12]> {
+        let names: Option<Vec<String>> = (1..=12)
+            .map(|month| strftime(format, month, 1, 0))
+            .collect();
+        names?.try_into().ok()
+    };
+    let layout = |item: &str| -> Option<String> {
+        let item = locale.getattr(item).ok()?;
+        locale
+            .call_method1("nl_langinfo", (item,))
+            .and_then(|layout| layout.extract())
+            .ok()
+            .filter(|layout: &String| !layout.is_empty())
+    };
+    Some(crate::formatter::LocaleTime {
+        weekdays: weekdays("%A")?,
+        weekdays_abbr: weekdays("%a")?,
+        months: months("%B")?,
+        months_abbr: months("%b")?,
+        am_pm: [strftime("%p", 1, 1, 0)?, strftime("%p", 1, 1, 12)?],
+        date_time_format: layout("D_T_FMT"),
+        date_format: layout("D_FMT"),
+        time_format: layout("T_FMT"),
+    })
+}
+
+/// `time.tzname` with the offsets from `time.timezone` and `time.altzone`.
+pub fn zone_names(py: Python) -> Option<crate::formatter::ZoneNames> {
+    let time = py.import("time").ok()?;
+    let (standard, daylight): (String, String) = time.getattr("tzname").ok()?.extract().ok()?;
+    let west = |name: &str| -> Option<i32> { time.getattr(name).ok()?.extract().ok() };
+    let has_daylight = west("daylight")? != 0;
+    Some(crate::formatter::ZoneNames {
+        standard: (standard, -west("timezone")?),
+        daylight: if has_daylight {
+            Some((daylight, -west("altzone")?))
+        } else {
+            None
+        },
+    })
+}
+
+/// The OS id of the calling thread, as `threading.get_native_id()`, debuggers and
+/// `top -H` report it; 0 on platforms without one.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn thread_native_id() -> u64 {
+    // SAFETY: gettid takes no arguments and cannot fail.
+    unsafe { libc::syscall(libc::SYS_gettid) as u64 }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+pub fn thread_native_id() -> u64 {
+    let mut id = 0;
+    // SAFETY: a null thread is the calling thread; `id` outlives the call.
+    unsafe { libc::pthread_threadid_np(0, &mut id) };
+    id
+}
+
+#[cfg(target_os = "freebsd")]
+pub fn thread_native_id() -> u64 {
+    // SAFETY: returns the calling thread's id and cannot fail.
+    unsafe { libc::pthread_getthreadid_np() as u64 }
+}
+
+#[cfg(windows)]
+pub fn thread_native_id() -> u64 {
+    extern "system" {
+        fn GetCurrentThreadId() -> u32;
+    }
+    // SAFETY: kernel32 call without arguments that cannot fail.
+    u64::from(unsafe { GetCurrentThreadId() })
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    windows
+)))]
+pub fn thread_native_id() -> u64 {
+    0
+}
+
+/// The id of the process that started this one; 0 on platforms without one.
+#[cfg(unix)]
+pub fn parent_process_id() -> u32 {
+    std::os::unix::process::parent_id()
+}
+
+#[cfg(not(unix))]
+pub fn parent_process_id() -> u32 {
+    0
+}
+
+/// Set the calling thread's nice value. Linux keeps one per thread; elsewhere it
+/// belongs to the whole process, so it is left alone.
+#[cfg(target_os = "linux")]
+pub fn set_thread_nice(nice: i32) -> std::io::Result<()> {
+    use std::ffi::{c_int, c_uint};
+
+    extern "C" {
+        fn setpriority(which: c_int, who: c_uint, prio: c_int) -> c_int;
+    }
+
+    const PRIO_PROCESS: c_int = 0;
+    // SAFETY: plain syscall wrapper; `who` 0 is the calling thread on Linux.
+    if unsafe { setpriority(PRIO_PROCESS, 0, nice) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_thread_nice(_nice: i32) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "per-thread nice values are only supported on Linux",
+    ))
+}
+
+/// Pin the calling thread to `cores` (each below `MAX_CORES`).
+#[cfg(target_os = "linux")]
+pub fn set_thread_affinity(cores: &[usize]) -> std::io::Result<()> {
+    use std::ffi::{c_int, c_ulong};
+
+    extern "C" {
+        fn sched_setaffinity(pid: c_int, cpusetsize: usize, mask: *const c_ulong) -> c_int;
+    }
+
+    const BITS: usize = c_ulong::BITS as usize;
+    let mut mask = [0 as c_ulong; MAX_CORES / BITS];
+    for &core in cores {
+        mask[core / BITS] |= 1 << (core % BITS);
+    }
+    // SAFETY: `mask` is a cpu_set_t of the size passed; pid 0 is the calling thread.
+    if unsafe { sched_setaffinity(0, std::mem::size_of_val(&mask), mask.as_ptr()) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_thread_affinity(_cores: &[usize]) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "CPU affinity is only supported on Linux",
+    ))
+}
+
+/// Flush StreamHandler output written so far.
+#[cfg(not(target_os = "emscripten"))]
+pub fn flush_console(dest: StreamDestination) {
+    use std::io::Write;
+    let _ = match dest {
+        StreamDestination::Stdout => std::io::stdout().flush(),
+        StreamDestination::Stderr => std::io::stderr().flush(),
+    };
+}
+
+/// The JS console has no buffer to flush.
+#[cfg(target_os = "emscripten")]
+pub fn flush_console(_dest: StreamDestination) {}
+
+/// Write one line of StreamHandler output to `console.log` / `console.error`.
+#[cfg(target_os = "emscripten")]
+pub fn write_console(dest: StreamDestination, msg: &str) {
+    use std::ffi::{c_char, CString};
+
+    extern "C" {
+        fn emscripten_console_log(utf8: *const c_char);
+        fn emscripten_console_error(utf8: *const c_char);
+    }
+
+    let Ok(line) = CString::new(msg.replace('\0', "\u{FFFD}")) else {
+        return;
+    };
+    // SAFETY: `line` is a valid NUL-terminated UTF-8 string for the duration of the call.
+    unsafe {
+        match dest {
+            StreamDestination::Stdout => emscripten_console_log(line.as_ptr()),
+            StreamDestination::Stderr => emscripten_console_error(line.as_ptr()),
+        }
+    }
+}
+
+/// A failed HTTP delivery. `throttled` is set when the collector asked the sender to
+/// back off (429 Too Many Requests or 503 Service Unavailable), with the delay from
+/// its `Retry-After` header when there was a usable one.
+#[cfg(feature = "network")]
+#[derive(Debug)]
+pub struct HttpError {
+    pub message: String,
+    pub throttled: bool,
+    pub retry_after: Option<Duration>,
+}
+
+#[cfg(feature = "network")]
+impl HttpError {
+    #[cfg(target_family = "wasm")]
+    fn unavailable() -> Self {
+        Self {
+            message: format!("HTTP delivery is not available on {PLATFORM}"),
+            throttled: false,
+            retry_after: None,
+        }
+    }
+}
+
+#[cfg(all(feature = "network", not(target_family = "wasm")))]
+impl From<ureq::Error> for HttpError {
+    fn from(e: ureq::Error) -> Self {
+        let (throttled, retry_after) = match e {
+            ureq::Error::Status(429 | 503, ref response) => (
+                true,
+                response.header("Retry-After").and_then(parse_retry_after),
+            ),
+            _ => (false, None),
+        };
+        Self {
+            message: e.to_string(),
+            throttled,
+            retry_after,
+        }
+    }
+}
+
+/// `Retry-After` is either delay-seconds or an HTTP-date; a date in the past means
+/// "now". Fractional seconds are accepted too.
+#[cfg(all(feature = "network", not(target_family = "wasm")))]
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<f64>() {
+        return (secs.is_finite() && secs >= 0.0).then(|| Duration::from_secs_f64(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (at.with_timezone(&chrono::Utc) - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+/// POST `body` to `url`, labelled with `content_encoding` when it is compressed.
+#[cfg(all(feature = "network", not(target_family = "wasm")))]
+pub fn http_post(
+    url: &str,
+    headers: &HashMap<String, String>,
+    content_type: &str,
+    content_encoding: Option<&str>,
+    body: &[u8],
+) -> Result<(), HttpError> {
+    let mut request = ureq::post(url).set("Content-Type", content_type);
+    if let Some(encoding) = content_encoding {
+        request = request.set("Content-Encoding", encoding);
+    }
+    for (key, value) in headers {
+        request = request.set(key, value);
+    }
+    request
+        .send_bytes(body)
+        .map(|_| ())
+        .map_err(HttpError::from)
+}
+
+#[cfg(all(feature = "network", target_family = "wasm"))]
+pub fn http_post(
+    _url: &str,
+    _headers: &HashMap<String, String>,
+    _content_type: &str,
+    _content_encoding: Option<&str>,
+    _body: &[u8],
+) -> Result<(), HttpError> {
+    Err(HttpError::unavailable())
+}
+
+/// Compress a request body with `codec`.
+#[cfg(all(feature = "compression", not(target_family = "wasm")))]
+pub fn compress(codec: crate::handler::Compression, body: &[u8]) -> std::io::Result<Vec<u8>> {
+    use crate::handler::Compression;
+    use std::io::Write;
+    match codec {
+        Compression::Gzip => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(body)?;
+            encoder.finish()
+        }
+        Compression::Zstd => zstd::encode_all(body, zstd::DEFAULT_COMPRESSION_LEVEL),
+    }
+}
+
+#[cfg(all(
+    feature = "network",
+    any(not(feature = "compression"), target_family = "wasm")
+))]
+pub fn compress(_codec: crate::handler::Compression, _body: &[u8]) -> std::io::Result<Vec<u8>> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+/// Gzip a rotated log file to `<path>.gz` and remove the original.
+#[cfg(all(feature = "file", feature = "compression", not(target_family = "wasm")))]
+pub fn gzip_file(path: &std::path::Path) -> std::io::Result<()> {
+    let mut target = path.as_os_str().to_owned();
+    target.push(".gz");
+    let mut input = std::fs::File::open(path)?;
+    let output = std::fs::File::create(&target)?;
+    let mut encoder = flate2::write::GzEncoder::new(output, flate2::Compression::default());
+    std::io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?.sync_all()?;
+    std::fs::remove_file(path)
+}
+
+#[cfg(all(
+    feature = "file",
+    any(not(feature = "compression"), target_family = "wasm")
+))]
+pub fn gzip_file(_path: &std::path::Path) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+/// Read a gzipped rotated log file back.
+#[cfg(all(feature = "compression", not(target_family = "wasm")))]
+pub fn gunzip_file(path: &std::path::Path) -> std::io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    let mut decoder = flate2::read::GzDecoder::new(std::fs::File::open(path)?);
+    std::io::Read::read_to_end(&mut decoder, &mut data)?;
+    Ok(data)
+}
+
+#[cfg(any(not(feature = "compression"), target_family = "wasm"))]
+pub fn gunzip_file(_path: &std::path::Path) -> std::io::Result<Vec<u8>> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+/// GET `url` (the payload is in its query string).
+#[cfg(all(feature = "network", not(target_family = "wasm")))]
+pub fn http_get(url: &str, headers: &HashMap<String, String>) -> Result<(), HttpError> {
+    let mut request = ureq::get(url);
+    for (key, value) in headers {
+        request = request.set(key, value);
+    }
+    request.call().map(|_| ()).map_err(HttpError::from)
+}
+
+#[cfg(all(feature = "network", target_family = "wasm"))]
+pub fn http_get(_url: &str, _headers: &HashMap<String, String>) -> Result<(), HttpError> {
+    Err(HttpError::unavailable())
+}
+
+/// Describe what this build of logxide can do.
+///
+/// Returns a dict with `platform` ("native" or "wasm32"), `threads` and `network`
+/// (bools), `console` ("stdio", or "js" when StreamHandler writes to the browser
+/// console) and `features` (the optional cargo features compiled in).
+#[pyfunction]
+pub fn runtime_info(py: Python) -> PyResult<Bound<PyDict>> {
+    let info = PyDict::new(py);
+    info.set_item("platform", PLATFORM)?;
+    info.set_item("threads", THREADS)?;
+    info.set_item("network", NETWORK)?;
+    info.set_item("console", CONSOLE)?;
+    let features: Vec<&str> = FEATURES
+        .iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| *name)
+        .collect();
+    info.set_item("features", features)?;
+    Ok(info)
+}
@@ -0,0 +1,126 @@
+//! # Presets
+//!
+//! Named sets of handler defaults that trade throughput against latency and
+//! durability, so one call picks sensible queue sizes, overflow behavior, batching,
+//! flush levels and sync levels for every handler created afterwards:
+//!
+//! - `default`: each handler's own defaults.
+//! - `low_latency`: logging calls never wait for a full queue, and HTTP batches go out
+//!   every second.
+//! - `max_throughput`: deep queues and large batches, flushing files only for
+//!   CRITICAL.
+//! - `durable`: callers wait for queue room, every file record is flushed, and ERROR
+//!   and above are written before the logging call returns.
+//!
+//! An argument passed to a handler always wins over the preset.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::core::LogLevel;
+
+/// Handler defaults picked by `preset()`.
+#[derive(Debug, Clone, Copy)]
+pub struct Preset {
+    pub name: &'static str,
+    /// Queue capacity of HTTPHandler, PipeHandler and QueueHandler.
+    pub capacity: usize,
+    /// Overflow of queued handlers; None keeps each handler's own default.
+    pub overflow: Option<&'static str>,
+    /// Records per HTTPHandler batch.
+    pub batch_size: usize,
+    /// Seconds between HTTPHandler batches.
+    pub flush_interval: u64,
+    /// Level at or above which file and HTTP handlers flush.
+    pub flush_level: LogLevel,
+    /// Level at or above which queued handlers write before returning.
+    pub sync_level: Option<LogLevel>,
+}
+
+const PRESETS: [Preset; 4] = [
+    Preset {
+        name: "default",
+        capacity: 10_000,
+        overflow: None,
+        batch_size: 1000,
+        flush_interval: 30,
+        flush_level: LogLevel::Error,
+        sync_level: None,
+    },
+    Preset {
+        name: "low_latency",
+        capacity: 10_000,
+        overflow: Some("drop_newest"),
+        batch_size: 100,
+        flush_interval: 1,
+        flush_level: LogLevel::Error,
+        sync_level: None,
+    },
+    Preset {
+        name: "max_throughput",
+        capacity: 100_000,
+        overflow: None,
+        batch_size: 5000,
+        flush_interval: 30,
+        flush_level: LogLevel::Critical,
+        sync_level: None,
+    },
+    Preset {
+        name: "durable",
+        capacity: 10_000,
+        overflow: Some("block"),
+        batch_size: 1000,
+        flush_interval: 5,
+        flush_level: LogLevel::NotSet,
+        sync_level: Some(LogLevel::Error),
+    },
+];
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+/// The preset handlers created now take their defaults from.
+pub fn current() -> &'static Preset {
+    &PRESETS[CURRENT.load(Ordering::Relaxed)]
+}
+
+/// `overflow` as given, or the preset's, or the handler's own `default`.
+pub fn overflow<'a>(overflow: Option<&'a str>, default: &'a str) -> &'a str {
+    overflow.or(current().overflow).unwrap_or(default)
+}
+
+/// Pick the defaults of handlers created afterwards.
+///
+/// `"low_latency"` never blocks a logging call on a full queue and sends HTTP batches
+/// every second; `"max_throughput"` uses deep queues and large batches;
+/// `"durable"` blocks on a full queue, flushes every file record and writes ERROR and
+/// above before the logging call returns. `"default"` restores each handler's own
+/// defaults. Arguments passed to a handler override the preset.
+///
+/// Args:
+///     name: `"default"`, `"low_latency"`, `"max_throughput"` or `"durable"`.
+///
+/// Returns:
+///     dict: The settings of the preset.
+#[pyfunction]
+#[pyo3(signature = (name="default"))]
+pub fn preset<'py>(py: Python<'py>, name: &str) -> PyResult<Bound<'py, PyDict>> {
+    let Some(index) = PRESETS.iter().position(|p| p.name == name) else {
+        let names: Vec<&str> = PRESETS.iter().map(|p| p.name).collect();
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "unknown preset {name:?}; expected one of {}",
+            names.join(", ")
+        )));
+    };
+    CURRENT.store(index, Ordering::Relaxed);
+    let preset = &PRESETS[index];
+    let settings = PyDict::new(py);
+    settings.set_item("name", preset.name)?;
+    settings.set_item("capacity", preset.capacity)?;
+    settings.set_item("overflow", preset.overflow)?;
+    settings.set_item("batch_size", preset.batch_size)?;
+    settings.set_item("flush_interval", preset.flush_interval)?;
+    settings.set_item("flush_level", preset.flush_level.value())?;
+    settings.set_item("sync_level", preset.sync_level.map(|l| l.value()))?;
+    Ok(settings)
+}
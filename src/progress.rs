@@ -0,0 +1,195 @@
+//! # Progress
+//!
+//! `logger.progress(iterable)` wraps an iterable and logs throttled progress records
+//! ("processed 10000/1000000 (1.0%) at 2534.1/s, ETA 394s") without flooding the log.
+//! A record is emitted every `every` items and/or every `seconds` of wall time,
+//! whichever comes first, plus one summary record when the iterable is exhausted.
+//!
+//! Records carry typed extras: `progress_event` ("update" / "done"), `progress_count`,
+//! `progress_total` (when known), `rate` (items per second), `eta_seconds` (when the
+//! total is known), `elapsed_seconds` and `progress` (the name, when given).
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyIterator;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::core::LogLevel;
+use crate::py_logger::PyLogger;
+
+/// Interval used when neither `every` nor `seconds` is given.
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(10);
+
+struct ProgressState {
+    count: u64,
+    last_emit: Instant,
+    done: bool,
+}
+
+#[pyclass(name = "Progress", frozen)]
+pub struct PyProgress {
+    logger: PyLogger,
+    iter: Py<PyIterator>,
+    name: Option<String>,
+    total: Option<u64>,
+    every: Option<u64>,
+    interval: Option<Duration>,
+    level: LogLevel,
+    started: Instant,
+    state: Mutex<ProgressState>,
+}
+
+impl PyProgress {
+    pub fn new(
+        logger: PyLogger,
+        iterable: &Bound<PyAny>,
+        name: Option<String>,
+        total: Option<u64>,
+        every: Option<u64>,
+        seconds: Option<f64>,
+        level: LogLevel,
+    ) -> PyResult<Self> {
+        if every == Some(0) {
+            return Err(PyValueError::new_err("every must be a positive integer"));
+        }
+        let interval = match seconds {
+            Some(secs) if !secs.is_finite() || secs <= 0.0 => {
+                return Err(PyValueError::new_err(
+                    "seconds must be a positive number of seconds",
+                ))
+            }
+            Some(secs) => Some(Duration::from_secs_f64(secs)),
+            None if every.is_none() => Some(DEFAULT_INTERVAL),
+            None => None,
+        };
+        let total = total.or_else(|| iterable.len().ok().map(|n| n as u64));
+        let now = Instant::now();
+        Ok(Self {
+            logger,
+            iter: iterable.try_iter()?.unbind(),
+            name,
+            total,
+            every,
+            interval,
+            level,
+            started: now,
+            state: Mutex::new(ProgressState {
+                count: 0,
+                last_emit: now,
+                done: false,
+            }),
+        })
+    }
+
+    fn emit(&self, py: Python, count: u64, done: bool) {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let rate = if elapsed > 0.0 {
+            count as f64 / elapsed
+        } else {
+            0.0
+        };
+        let eta = match self.total {
+            Some(total) if rate > 0.0 && !done => Some(total.saturating_sub(count) as f64 / rate),
+            _ => None,
+        };
+
+        let mut fields: HashMap<String, Value> = HashMap::new();
+        if let Some(ref name) = self.name {
+            fields.insert("progress".into(), Value::String(name.clone()));
+        }
+        let event = if done { "done" } else { "update" };
+        fields.insert("progress_event".into(), Value::String(event.into()));
+        fields.insert("progress_count".into(), Value::from(count));
+        if let Some(total) = self.total {
+            fields.insert("progress_total".into(), Value::from(total));
+        }
+        fields.insert("rate".into(), float_value(rate));
+        fields.insert("elapsed_seconds".into(), float_value(elapsed));
+        if let Some(eta) = eta {
+            fields.insert("eta_seconds".into(), float_value(eta));
+        }
+
+        let prefix = self
+            .name
+            .as_ref()
+            .map(|name| format!("{name}: "))
+            .unwrap_or_default();
+        let msg = if done {
+            format!("{prefix}finished {count} in {elapsed:.1}s ({rate:.1}/s)")
+        } else {
+            let done_part = match self.total {
+                Some(total) if total > 0 => format!(
+                    "{count}/{total} ({:.1}%)",
+                    count as f64 * 100.0 / total as f64
+                ),
+                _ => count.to_string(),
+            };
+            match eta {
+                Some(eta) => {
+                    format!("{prefix}processed {done_part} at {rate:.1}/s, ETA {eta:.0}s")
+                }
+                None => format!("{prefix}processed {done_part} at {rate:.1}/s"),
+            }
+        };
+        self.logger.log_with_extra(py, self.level, msg, fields);
+    }
+}
+
+fn float_value(v: f64) -> Value {
+    serde_json::Number::from_f64(v)
+        .map(Value::Number)
+        .unwrap_or(Value::Null)
+}
+
+#[pymethods]
+impl PyProgress {
+    /// Items consumed so far.
+    #[getter]
+    fn count(&self) -> u64 {
+        self.state.lock().unwrap().count
+    }
+
+    fn __iter__(slf: Bound<'_, Self>) -> Bound<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python) -> PyResult<Option<Py<PyAny>>> {
+        let mut iter = self.iter.bind(py).clone();
+        match iter.next() {
+            Some(item) => {
+                let item = item?;
+                let due = {
+                    let mut state = self.state.lock().unwrap();
+                    state.count += 1;
+                    let due = self.every.is_some_and(|every| state.count % every == 0)
+                        || self
+                            .interval
+                            .is_some_and(|interval| state.last_emit.elapsed() >= interval);
+                    if due {
+                        state.last_emit = Instant::now();
+                    }
+                    due.then_some(state.count)
+                };
+                if let Some(count) = due {
+                    self.emit(py, count, false);
+                }
+                Ok(Some(item.unbind()))
+            }
+            None => {
+                let finished = {
+                    let mut state = self.state.lock().unwrap();
+                    let first = !state.done;
+                    state.done = true;
+                    first.then_some(state.count)
+                };
+                if let Some(count) = finished {
+                    self.emit(py, count, true);
+                }
+                Ok(None)
+            }
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! # Field Projection
+//!
+//! A `FieldProjection` chooses the fields a handler's records carry, so each
+//! destination gets its own field set: a console handler can leave out extras and
+//! tracebacks while an HTTP handler keeps everything. Added to a handler with
+//! `addFilter()`, it runs in Rust on that handler's own copy of each record, before
+//! the handler formats or serializes it, and never drops a record.
+//!
+//! The fields it controls are the extras and the standard fields a record may go
+//! without: `exc_info` (the traceback; `exc_text` names the same field), `stack_info`
+//! and `taskName`. `include` keeps only the listed ones and `exclude` removes the
+//! listed ones. The fields every record has (`name`, `levelname`, `msg`, `created`, ...)
+//! always stay; formatters choose among those with their format string or `fields`.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::BTreeSet;
+
+use crate::core::{JsonCache, LogRecord};
+use crate::filter::Filter;
+
+/// The names each optional standard field goes by.
+const EXC: &[&str] = &["exc_info", "exc_text"];
+const STACK: &[&str] = &["stack_info"];
+const TASK: &[&str] = &["taskName", "task_name"];
+
+/// The optional standard fields of a `logging.LogRecord`, with their names.
+const OPTIONAL: [(&str, &[&str]); 4] = [
+    ("exc_info", EXC),
+    ("exc_text", EXC),
+    ("stack_info", STACK),
+    ("taskName", TASK),
+];
+
+/// Attributes every `logging.LogRecord` has, which are not extras.
+const RECORD_ATTRS: &[&str] = &[
+    "name",
+    "msg",
+    "args",
+    "levelname",
+    "levelno",
+    "pathname",
+    "filename",
+    "module",
+    "exc_info",
+    "exc_text",
+    "stack_info",
+    "lineno",
+    "funcName",
+    "created",
+    "msecs",
+    "relativeCreated",
+    "thread",
+    "threadName",
+    "processName",
+    "process",
+    "ppid",
+    "thread_native_id",
+    "seq",
+    "taskName",
+    "message",
+    "asctime",
+];
+
+/// Keep the extras and optional fields listed in `include` (all of them when None),
+/// minus those in `exclude`, on each record a handler writes.
+///
+/// Fields every record has always stay. Add it to a handler with `addFilter()`; a
+/// logxide handler applies it in Rust to its own copy of the record.
+///
+/// Args:
+///     include: Extras and optional fields to keep; None keeps all of them.
+///     exclude: Extras and optional fields to remove.
+#[pyclass(
+    name = "FieldProjection",
+    module = "logxide",
+    frozen,
+    skip_from_py_object
+)]
+#[derive(Debug, Clone)]
+pub struct FieldProjection {
+    include: Option<BTreeSet<String>>,
+    exclude: BTreeSet<String>,
+}
+
+impl FieldProjection {
+    /// Whether the field named any of `names` stays.
+    fn keeps(&self, names: &[&str]) -> bool {
+        let included = match &self.include {
+            Some(include) => names.iter().any(|name| include.contains(*name)),
+            None => true,
+        };
+        included && !names.iter().any(|name| self.exclude.contains(*name))
+    }
+
+    /// Whether projecting `record` would change it, so it is worth copying.
+    fn changes(&self, record: &LogRecord) -> bool {
+        ((record.exc_info.is_some() || record.exc_text.is_some()) && !self.keeps(EXC))
+            || (record.stack_info.is_some() && !self.keeps(STACK))
+            || (record.task_name.is_some() && !self.keeps(TASK))
+            || record
+                .extra
+                .iter()
+                .flat_map(|extra| extra.keys())
+                .any(|key| !self.keeps(&[key]))
+    }
+
+    /// Remove the fields that don't stay from `record`; true when any were removed.
+    fn apply(&self, record: &mut LogRecord) -> bool {
+        if !self.changes(record) {
+            return false;
+        }
+        if !self.keeps(EXC) {
+            record.exc_info = None;
+            record.exc_text = None;
+        }
+        if !self.keeps(STACK) {
+            record.stack_info = None;
+        }
+        if !self.keeps(TASK) {
+            record.task_name = None;
+        }
+        if let Some(extra) = record.extra.as_mut() {
+            extra.retain(|key, _| self.keeps(&[key]));
+        }
+        true
+    }
+
+    /// Project a record from Python that is not a logxide `LogRecord`: its extras are
+    /// the attributes beyond those of `logging.LogRecord`.
+    fn apply_foreign(&self, record: &Bound<PyAny>) -> PyResult<()> {
+        let py = record.py();
+        for (field, names) in OPTIONAL {
+            if !self.keeps(names) && record.hasattr(field)? {
+                record.setattr(field, py.None())?;
+            }
+        }
+        let Ok(attrs) = record.getattr(pyo3::intern!(py, "__dict__")) else {
+            return Ok(());
+        };
+        let attrs = attrs.cast_into::<PyDict>()?;
+        for key in attrs.copy()?.keys() {
+            let key = key.str()?;
+            let key = key.to_cow()?;
+            if !RECORD_ATTRS.contains(&&*key) && !key.starts_with('_') && !self.keeps(&[&key]) {
+                attrs.del_item(&*key)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Filter for FieldProjection {
+    fn filter(&self, _record: &LogRecord) -> bool {
+        true
+    }
+
+    fn rewrite(&self, record: &LogRecord) -> Option<LogRecord> {
+        if !self.changes(record) {
+            return None;
+        }
+        let mut copy = record.detached();
+        self.apply(&mut copy);
+        Some(copy)
+    }
+}
+
+#[pymethods]
+impl FieldProjection {
+    #[new]
+    #[pyo3(signature = (include=None, exclude=Vec::new()))]
+    fn new(include: Option<Vec<String>>, exclude: Vec<String>) -> Self {
+        Self {
+            include: include.map(|include| include.into_iter().collect()),
+            exclude: exclude.into_iter().collect(),
+        }
+    }
+
+    #[getter]
+    fn include(&self) -> Option<Vec<String>> {
+        self.include
+            .as_ref()
+            .map(|include| include.iter().cloned().collect())
+    }
+
+    #[getter]
+    fn exclude(&self) -> Vec<String> {
+        self.exclude.iter().cloned().collect()
+    }
+
+    /// Project `record` in place and pass it, for loggers, Python handlers and
+    /// handlers that fall back to Python dispatch.
+    fn filter(&self, record: &Bound<PyAny>) -> PyResult<bool> {
+        match record.cast::<LogRecord>() {
+            Ok(record) => {
+                let mut record = record.borrow_mut();
+                if self.apply(&mut record) {
+                    // Its encodings may be shared with the record it was copied from.
+                    record.json_cache = JsonCache::default();
+                }
+            }
+            Err(_) => self.apply_foreign(record)?,
+        }
+        Ok(true)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<FieldProjection include={:?} exclude={:?}>",
+            self.include(),
+            self.exclude()
+        )
+    }
+}
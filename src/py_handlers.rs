@@ -1,20 +1,60 @@
 //! Python wrapper types for Rust handlers and formatters
 
-use pyo3::exceptions::PyValueError;
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+#[cfg(feature = "colors")]
+use pyo3::types::PyBool;
+use pyo3::types::{PyDict, PyTuple};
+#[cfg(feature = "network")]
 use serde_json::Value;
 use std::collections::HashMap;
+#[cfg(any(feature = "network", feature = "pipe"))]
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::core::{LogLevel, LogRecord};
-use crate::formatter::{ColorFormatter, Formatter, NoOpFormatter, PythonFormatter};
+#[cfg(feature = "colors")]
+use crate::formatter::ColorFormatter;
+#[cfg(feature = "json")]
+use crate::formatter::JsonFormatter;
+use crate::formatter::{
+    ColumnFormatter, FormatStyle, Formatter, NoOpFormatter, PythonFormatter, Timestamps,
+};
 use crate::globals::check_caller_info_needed;
+#[cfg(feature = "smtp")]
+use crate::handler::SMTPHandler;
+use crate::handler::{
+    AggregatingHandler, DispatchMode, Handler, MemoryHandler, OverflowStrategy, QueueHandler,
+    QueueListener, StreamHandler,
+};
+#[cfg(feature = "network")]
+use crate::handler::{
+    Compression, DatagramFormat, DatagramHandler, DatagramTarget, SocketBackoff, SocketHandler,
+    SocketTarget, SyslogHandler, SyslogProtocol, SyslogTarget, TimestampPolicy, Transport,
+    DATAGRAM_MAX_PACKET_SIZE,
+};
+#[cfg(feature = "file")]
 use crate::handler::{
-    DispatchMode, FileHandler, HTTPHandler, HTTPHandlerConfig, Handler, MemoryHandler, OTLPHandler,
-    OTLPHandlerConfig, OverflowStrategy, RotatingFileHandler, StreamHandler,
+    FileHandler, LevelRoutedFileHandler, RotatingFileHandler, RotationSchedule, RoutedFile,
+    TimedRotatingFileHandler,
 };
+#[cfg(feature = "network")]
+use crate::handler::{HTTPHandler, HTTPHandlerConfig, HttpPayload};
+#[cfg(feature = "otlp")]
+use crate::handler::{OTLPHandler, OTLPHandlerConfig};
+#[cfg(feature = "pipe")]
+use crate::handler::{PipeHandler, PipeHandlerConfig, PipeTarget};
+#[cfg(feature = "file")]
+use crate::platform::FsPath;
 use crate::py_logger::check_level;
+#[cfg(feature = "file")]
+use crate::retention::Retention;
+use crate::sanitize::SanitizePolicy;
+#[cfg(feature = "smtp")]
+use crate::smtp::{SmtpConfig, SmtpSecurity};
+#[cfg(feature = "network")]
+use crate::transform::RecordTransform;
 
 // ============================================================================
 // Formatter Bindings
@@ -32,20 +72,39 @@ impl PyFormatter {
     /// Create a new Formatter with the specified format string.
     ///
     /// Args:
-    ///     fmt: Python-style format string with %(field)s placeholders
+    ///     fmt: Python-style format string with %(field)s placeholders, or in
+    ///          `style`. Defaults to the message alone.
     ///     datefmt: Optional strftime format for %(asctime)s
+    ///     timestamps: What %(asctime)s shows: "absolute" (wall-clock time),
+    ///          "relative" (+12.345s since logging started) or "delta"
+    ///          (+350ms since the previous record)
+    ///     style: "%" for %(field)s, "{" for {field} (str.format) or "$" for
+    ///          $field / ${field} (string.Template). `{` fields may have an
+    ///          alignment, width and `s` or `d` type such as {levelname:<8}; other
+    ///          format specs raise ValueError.
+    ///     defaults: {field: value} used for fields a record doesn't have, such as
+    ///          {"app": "svc-a"} for %(app)s. The record's extras take precedence.
     #[new]
-    #[pyo3(signature = (fmt="%(message)s".to_string(), datefmt=None))]
-    pub fn new(fmt: String, datefmt: Option<String>) -> Self {
-        check_caller_info_needed(&fmt);
-        let formatter = if let Some(df) = datefmt {
-            PythonFormatter::with_date_format(fmt, df)
-        } else {
-            PythonFormatter::new(fmt)
-        };
-        Self {
-            inner: Arc::new(formatter),
-        }
+    #[pyo3(signature = (fmt=None, datefmt=None, timestamps="absolute", style="%", defaults=None))]
+    pub fn new(
+        fmt: Option<String>,
+        datefmt: Option<String>,
+        timestamps: &str,
+        style: &str,
+        defaults: Option<&Bound<PyDict>>,
+    ) -> PyResult<Self> {
+        let fmt = fmt.unwrap_or_else(|| {
+            match style {
+                "{" => "{message}",
+                "$" => "${message}",
+                _ => "%(message)s",
+            }
+            .to_string()
+        });
+        let formatter = styled_formatter(fmt, datefmt, style, defaults)?;
+        Ok(Self {
+            inner: Arc::new(formatter.with_timestamps(parse_timestamps(timestamps)?)),
+        })
     }
 
     /// Format a log record.
@@ -54,6 +113,7 @@ impl PyFormatter {
     }
 }
 
+#[cfg(feature = "colors")]
 /// Python binding for ColorFormatter.
 /// Supports ANSI color codes for terminal output.
 ///
@@ -63,13 +123,15 @@ impl PyFormatter {
 ///
 /// Example:
 ///     formatter = ColorFormatter(
-///         "%(ansi_level_color)s%(levelname)s%(ansi_reset_color)s - %(message)s"
+///         "%(ansi_level_color)s%(levelname)s%(ansi_reset_color)s - %(message)s",
+///         level_badges=True,
 ///     )
 #[pyclass(name = "ColorFormatter")]
 pub struct PyColorFormatter {
     pub(crate) inner: Arc<ColorFormatter>,
 }
 
+#[cfg(feature = "colors")]
 #[pymethods]
 impl PyColorFormatter {
     /// Create a new ColorFormatter with ANSI color support.
@@ -78,21 +140,242 @@ impl PyColorFormatter {
     ///     fmt: Format string with %(field)s placeholders.
     ///          Use %(ansi_level_color)s and %(ansi_reset_color)s for colors.
     ///     datefmt: Optional strftime format for %(asctime)s
+    ///     level_badges: Prefix each line with a badge for its level. True uses
+    ///          the defaults, or pass a dict of level name to badge. Left off when
+    ///          sys.stdout or sys.stderr can't encode the badges.
+    ///     timestamps: What %(asctime)s shows: "absolute", "relative" or "delta"
     #[new]
-    #[pyo3(signature = (fmt="%(ansi_level_color)s%(levelname)s%(ansi_reset_color)s - %(message)s".to_string(), datefmt=None))]
-    pub fn new(fmt: String, datefmt: Option<String>) -> Self {
+    #[pyo3(signature = (fmt="%(ansi_level_color)s%(levelname)s%(ansi_reset_color)s - %(message)s".to_string(), datefmt=None, level_badges=None, timestamps="absolute"))]
+    pub fn new(
+        py: Python,
+        fmt: String,
+        datefmt: Option<String>,
+        level_badges: Option<&Bound<PyAny>>,
+        timestamps: &str,
+    ) -> PyResult<Self> {
         check_caller_info_needed(&fmt);
         let formatter = if let Some(df) = datefmt {
             ColorFormatter::with_date_format(fmt, df)
         } else {
             ColorFormatter::new(fmt)
         };
+        let badges = parse_level_badges(level_badges)?;
+        let badges = if badges
+            .iter()
+            .all(|(_, badge)| crate::platform::console_can_encode(py, badge))
+        {
+            badges
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            inner: Arc::new(
+                formatter
+                    .with_level_badges(badges)
+                    .with_timestamps(parse_timestamps(timestamps)?),
+            ),
+        })
+    }
+
+    /// Format a log record with ANSI colors.
+    pub fn format(&self, record: &LogRecord) -> String {
+        self.inner.format(record)
+    }
+
+    /// The level badges in use, as {level name: badge}. Empty when off.
+    #[getter]
+    fn level_badges(&self) -> HashMap<String, String> {
+        self.inner.level_badges().iter().cloned().collect()
+    }
+}
+
+/// Read `style`: "%", "{" or "$".
+fn parse_style(style: &str) -> PyResult<FormatStyle> {
+    FormatStyle::parse(style).ok_or_else(|| {
+        PyValueError::new_err(format!("style must be '%', '{{' or '$', not {style:?}"))
+    })
+}
+
+/// A `PythonFormatter` for `fmt` in `style`, with `datefmt` and `defaults` when given.
+/// Raises ValueError for placeholders it can't render.
+fn styled_formatter(
+    fmt: String,
+    datefmt: Option<String>,
+    style: &str,
+    defaults: Option<&Bound<PyDict>>,
+) -> PyResult<PythonFormatter> {
+    let style = parse_style(style)?;
+    check_caller_info_needed(&fmt);
+    let formatter = match datefmt {
+        Some(df) => PythonFormatter::with_date_format(fmt, df),
+        None => PythonFormatter::new(fmt),
+    };
+    let formatter = match defaults {
+        Some(defaults) => formatter.with_defaults(parse_defaults(defaults)?),
+        None => formatter,
+    };
+    formatter.with_style(style).map_err(PyValueError::new_err)
+}
+
+/// Read `defaults`: {field: value}, each value rendered with `str()` as `%s` would.
+fn parse_defaults(defaults: &Bound<PyDict>) -> PyResult<HashMap<String, String>> {
+    defaults
+        .iter()
+        .map(|(field, value)| Ok((field.extract::<String>()?, value.str()?.to_string())))
+        .collect()
+}
+
+/// Read `timestamps`: "absolute", "relative" or "delta".
+fn parse_timestamps(name: &str) -> PyResult<Timestamps> {
+    Timestamps::parse(name).ok_or_else(|| {
+        PyValueError::new_err(format!(
+            "timestamps must be 'absolute', 'relative' or 'delta', not {name:?}"
+        ))
+    })
+}
+
+/// Read `level_badges`: None/False (off), True (defaults) or {level name: badge}.
+#[cfg(feature = "colors")]
+fn parse_level_badges(value: Option<&Bound<PyAny>>) -> PyResult<Vec<(String, String)>> {
+    let Some(value) = value else {
+        return Ok(Vec::new());
+    };
+    if let Ok(enabled) = value.cast::<PyBool>() {
+        if !enabled.is_true() {
+            return Ok(Vec::new());
+        }
+        return Ok(ColorFormatter::DEFAULT_LEVEL_BADGES
+            .iter()
+            .map(|(level, badge)| (level.to_string(), badge.to_string()))
+            .collect());
+    }
+    let map = value
+        .cast::<PyDict>()
+        .map_err(|_| PyTypeError::new_err("level_badges must be True, False, None or a dict"))?;
+    map.iter()
+        .map(|(level, badge)| {
+            let level: String = level.extract()?;
+            let badge: String = badge.extract()?;
+            Ok((level.to_uppercase(), badge))
+        })
+        .collect()
+}
+
+/// Python binding for ColumnFormatter.
+/// Keeps the level and logger name columns at fixed widths, so interleaved output
+/// from many loggers lines up. Longer values are truncated and end in "…".
+///
+/// Example:
+///     handler.setFormatter(ColumnFormatter(level_width=8, name_width=16))
+#[pyclass(name = "ColumnFormatter")]
+pub struct PyColumnFormatter {
+    pub(crate) inner: Arc<ColumnFormatter>,
+}
+
+#[pymethods]
+impl PyColumnFormatter {
+    /// Create a new ColumnFormatter.
+    ///
+    /// Args:
+    ///     fmt: Format string with %(field)s placeholders
+    ///          (default: "%(asctime)s %(levelname)s %(name)s %(message)s")
+    ///     datefmt: Optional strftime format for %(asctime)s
+    ///     level_width: Width of the %(levelname)s column (0 = not a column)
+    ///     name_width: Width of the %(name)s column (0 = not a column)
+    ///     timestamps: What %(asctime)s shows: "absolute", "relative" or "delta"
+    #[new]
+    #[pyo3(signature = (fmt=None, datefmt=None, level_width=8, name_width=20, timestamps="absolute"))]
+    pub fn new(
+        fmt: Option<String>,
+        datefmt: Option<String>,
+        level_width: usize,
+        name_width: usize,
+        timestamps: &str,
+    ) -> PyResult<Self> {
+        let fmt = fmt.unwrap_or_else(|| ColumnFormatter::DEFAULT_FORMAT.to_string());
+        check_caller_info_needed(&fmt);
+        let columns = [("levelname", level_width), ("name", name_width)];
+        let formatter = ColumnFormatter::new(fmt, datefmt, &columns);
+        Ok(Self {
+            inner: Arc::new(formatter.with_timestamps(parse_timestamps(timestamps)?)),
+        })
+    }
+
+    /// Format a log record into fixed-width columns.
+    pub fn format(&self, record: &LogRecord) -> String {
+        self.inner.format(record)
+    }
+}
+
+#[cfg(feature = "json")]
+/// Python binding for JsonFormatter.
+/// Writes each record as one JSON object: the standard LogRecord attributes, then
+/// the `extra` fields with their JSON types.
+///
+/// Example:
+///     handler.setFormatter(JsonFormatter(
+///         fields=["created", "levelname", "message", "request_id"],
+///         rename={"created": "ts", "levelname": "level"},
+///     ))
+#[pyclass(name = "JsonFormatter")]
+pub struct PyJsonFormatter {
+    pub(crate) inner: Arc<JsonFormatter>,
+}
+
+#[cfg(feature = "json")]
+#[pymethods]
+impl PyJsonFormatter {
+    /// Create a new JsonFormatter.
+    ///
+    /// Args:
+    ///     fields: Keys to write, in order; standard attributes and extras alike.
+    ///          None writes every standard attribute, then all extras.
+    ///     rename: Dict of field name to the key it is written under
+    ///     datefmt: strftime format for an "asctime" field, which is added to
+    ///          the default fields when given
+    ///     canonical: Sort keys, write floats with six decimals, pin the
+    ///          timestamps to the epoch and seq to 0, so reruns give identical output
+    ///     schema_version: Integer written first in every object as
+    ///          "schema_version", so consumers can detect layout changes
+    #[new]
+    #[pyo3(signature = (fields=None, rename=None, datefmt=None, canonical=false, schema_version=None))]
+    pub fn new(
+        fields: Option<Vec<String>>,
+        rename: Option<HashMap<String, String>>,
+        datefmt: Option<String>,
+        canonical: bool,
+        schema_version: Option<u32>,
+    ) -> Self {
+        let inner = JsonFormatter::new(fields, rename.unwrap_or_default(), datefmt)
+            .canonical(canonical)
+            .schema_version(schema_version);
+        for field in inner.fields() {
+            check_caller_info_needed(&format!("%({field})"));
+        }
         Self {
-            inner: Arc::new(formatter),
+            inner: Arc::new(inner),
         }
     }
 
-    /// Format a log record with ANSI colors.
+    /// The fields written, in order, before any extras.
+    #[getter]
+    fn fields(&self) -> Vec<String> {
+        self.inner.fields().to_vec()
+    }
+
+    /// Whether the formatter writes canonical JSON.
+    #[getter]
+    fn canonical(&self) -> bool {
+        self.inner.is_canonical()
+    }
+
+    /// The `schema_version` written in every object, or None.
+    #[getter]
+    fn schema_version(&self) -> Option<u32> {
+        self.inner.version()
+    }
+
+    /// Format a log record as a JSON object.
     pub fn format(&self, record: &LogRecord) -> String {
         self.inner.format(record)
     }
@@ -101,16 +384,208 @@ impl PyColorFormatter {
 // ============================================================================
 // Handler Bindings
 // ============================================================================
+
+/// Convert the `overflow` / `block_timeout` arguments of a queued handler. Without
+/// `overflow`, a `block_timeout` means "block"; otherwise the current preset's
+/// overflow applies, then the handler's own `default`.
+fn parse_overflow(
+    overflow: Option<&str>,
+    default: &str,
+    block_timeout: Option<f64>,
+) -> PyResult<OverflowStrategy> {
+    let overflow = match (overflow, block_timeout) {
+        (None, Some(_)) => "block",
+        (overflow, _) => crate::preset::overflow(overflow, default),
+    };
+    let block_timeout = match block_timeout {
+        Some(secs) if !secs.is_finite() || secs < 0.0 => {
+            return Err(PyValueError::new_err(
+                "block_timeout must be a non-negative number of seconds or None",
+            ))
+        }
+        Some(secs) => Some(Duration::from_secs_f64(secs)),
+        None => None,
+    };
+    match OverflowStrategy::parse(overflow, block_timeout) {
+        Some(strategy @ OverflowStrategy::Block(_)) => Ok(strategy),
+        Some(_) if block_timeout.is_some() => Err(PyValueError::new_err(
+            "block_timeout only applies to overflow=\"block\"",
+        )),
+        Some(strategy) => Ok(strategy),
+        None => Err(PyValueError::new_err(format!(
+            "overflow must be \"block\", \"drop_newest\" or \"drop_oldest\", not {overflow:?}"
+        ))),
+    }
+}
+
+#[cfg(any(feature = "network", feature = "pipe"))]
+/// Convert a `shutdown_timeout` argument in seconds (None = wait indefinitely).
+fn parse_shutdown_timeout(seconds: Option<f64>) -> PyResult<Option<Duration>> {
+    match seconds {
+        Some(secs) if !secs.is_finite() || secs < 0.0 => Err(PyValueError::new_err(
+            "shutdown_timeout must be a non-negative number of seconds or None",
+        )),
+        Some(secs) => Ok(Some(Duration::from_secs_f64(secs))),
+        None => Ok(None),
+    }
+}
+
+/// Resolve a `sync_level` argument: a level number or name, or None to turn it off.
+fn parse_sync_level(py: Python, level: Option<&Bound<PyAny>>) -> PyResult<Option<LogLevel>> {
+    level
+        .map(|l| check_level(py, l).map(|n| LogLevel::from_usize(n as usize)))
+        .transpose()
+}
+
+/// Resolve a handler's `sync_level` constructor argument; None takes the current
+/// preset's.
+#[cfg(feature = "network")]
+fn default_sync_level(py: Python, level: Option<&Bound<PyAny>>) -> PyResult<Option<LogLevel>> {
+    match level {
+        Some(_) => parse_sync_level(py, level),
+        None => Ok(crate::preset::current().sync_level),
+    }
+}
+
+/// Resolve HTTPHandler's `method`: None keeps JSON batches, "GET"/"POST" select the
+/// stdlib form encoding.
+#[cfg(feature = "network")]
+fn parse_http_method(method: Option<&str>) -> PyResult<HttpPayload> {
+    match method.map(str::to_ascii_uppercase).as_deref() {
+        None => Ok(HttpPayload::Json),
+        Some("GET") => Ok(HttpPayload::FormGet),
+        Some("POST") => Ok(HttpPayload::FormPost),
+        Some(_) => Err(PyValueError::new_err(
+            "method must be 'GET', 'POST' or None",
+        )),
+    }
+}
+
+/// Resolve a network handler's `compression`: None sends bodies as they are,
+/// "gzip"/"zstd" pick the codec.
+#[cfg(feature = "network")]
+fn parse_compression(compression: Option<&str>) -> PyResult<Option<Compression>> {
+    let Some(name) = compression else {
+        return Ok(None);
+    };
+    let codec = Compression::from_name(name)
+        .ok_or_else(|| PyValueError::new_err("compression must be 'gzip', 'zstd' or None"))?;
+    crate::platform::require_compression(codec.content_encoding())?;
+    Ok(Some(codec))
+}
+
+/// Validate a network handler's `max_batch_bytes`.
+#[cfg(feature = "network")]
+fn parse_max_batch_bytes(max_batch_bytes: Option<usize>) -> PyResult<Option<usize>> {
+    if max_batch_bytes == Some(0) {
+        return Err(PyValueError::new_err(
+            "max_batch_bytes must be positive or None",
+        ));
+    }
+    Ok(max_batch_bytes)
+}
+
+/// Resolve a network handler's `send_time` and `max_backdate` (seconds).
+#[cfg(feature = "network")]
+fn parse_timestamp_policy(send_time: bool, max_backdate: Option<f64>) -> PyResult<TimestampPolicy> {
+    let max_backdate = match max_backdate {
+        None => None,
+        Some(secs) if secs.is_finite() && secs > 0.0 => Some(Duration::from_secs_f64(secs)),
+        Some(_) => {
+            return Err(PyValueError::new_err(
+                "max_backdate must be a positive number of seconds or None",
+            ))
+        }
+    };
+    Ok(TimestampPolicy {
+        send_time,
+        max_backdate,
+    })
+}
+
+/// `Authorization` header value for HTTP basic auth.
+#[cfg(feature = "network")]
+fn basic_auth(user: &str, password: &str) -> String {
+    let credentials = format!("{user}:{password}");
+    format!(
+        "Basic {}",
+        crate::handler::base64_encode(credentials.as_bytes())
+    )
+}
+
+/// Resolve a network handler's `dry_run`: None sends for real, a callable simulates
+/// the collector, anything else is a path that requests are appended to.
+#[cfg(feature = "network")]
+fn parse_dry_run(dry_run: Option<&Bound<PyAny>>) -> PyResult<Transport> {
+    match dry_run {
+        None => Ok(Transport::Http),
+        Some(target) if target.is_callable() => {
+            Ok(Transport::DryRunCallback(target.clone().unbind()))
+        }
+        Some(target) => {
+            let path: PathBuf = target.extract()?;
+            Transport::dry_run_file(&path).map_err(|e| PyValueError::new_err(e.to_string()))
+        }
+    }
+}
+
+#[cfg(feature = "network")]
+fn parse_record_transform(spec: Option<&Bound<PyAny>>) -> PyResult<Option<RecordTransform>> {
+    spec.map(|spec| {
+        RecordTransform::from_spec(&crate::py_logger::py_to_json_value(spec))
+            .map_err(PyValueError::new_err)
+    })
+    .transpose()
+}
+
+/// Reject a `checksum_every` or `index_every` of 0: they count records.
+#[cfg(feature = "file")]
+fn check_every(option: &str, every: Option<u32>) -> PyResult<()> {
+    match every {
+        Some(0) => Err(PyValueError::new_err(format!(
+            "{option} must be at least 1"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(feature = "file")]
 #[pyclass(name = "FileHandler", subclass)]
 pub struct PyFileHandler {
     pub(crate) inner: Arc<FileHandler>,
 }
 
+#[cfg(feature = "file")]
 #[pymethods]
 impl PyFileHandler {
+    /// `watch=True` checks before each write that `filename` still names the open file
+    /// and reopens it if it was moved or deleted, as `WatchedFileHandler` does.
+    /// `checksum_every=N` writes a Blake3 checksum trailer after every N records and on
+    /// flush, for `verify_log_file()`. `index_every=N` adds every Nth record's time and
+    /// offset to a sidecar index, `<filename>.idx`, for reading time ranges.
     #[new]
-    fn new(filename: String) -> PyResult<Self> {
-        let h = FileHandler::new(filename).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    #[pyo3(signature = (filename, watch=false, checksum_every=None, index_every=None))]
+    fn new(
+        filename: FsPath,
+        watch: bool,
+        checksum_every: Option<u32>,
+        index_every: Option<u32>,
+    ) -> PyResult<Self> {
+        check_every("checksum_every", checksum_every)?;
+        check_every("index_every", index_every)?;
+        let FsPath(filename) = filename;
+        let mut h = if watch {
+            FileHandler::watched(filename)
+        } else {
+            FileHandler::new(filename)
+        }
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        if let Some(every) = checksum_every {
+            h = h.with_checksums(every);
+        }
+        if let Some(every) = index_every {
+            h = h.with_index(every);
+        }
         Ok(Self { inner: Arc::new(h) })
     }
 
@@ -133,7 +608,7 @@ impl PyFileHandler {
     /// Get the current flush level.
     #[pyo3(name = "getFlushLevel")]
     fn get_flush_level(&self) -> PyResult<u32> {
-        Ok(self.inner.get_flush_level() as u32)
+        Ok(self.inner.get_flush_level())
     }
 
     /// Set an error callback function.
@@ -163,20 +638,22 @@ impl PyFileHandler {
 
     fn emit(&self, _py: Python, record: &Bound<PyAny>) -> PyResult<()> {
         let rust_record = record.extract::<LogRecord>()?;
-        self.inner.emit(&rust_record);
+        self.inner.emit(&Arc::new(rust_record));
         Ok(())
     }
 
-    #[pyo3(name = "setFormatterSpec", signature = (fmt=None, datefmt=None))]
-    fn set_formatter_spec(&self, fmt: Option<String>, datefmt: Option<String>) -> PyResult<()> {
+    #[pyo3(name = "setFormatterSpec", signature = (fmt=None, datefmt=None, style="%", defaults=None))]
+    fn set_formatter_spec(
+        &self,
+        fmt: Option<String>,
+        datefmt: Option<String>,
+        style: &str,
+        defaults: Option<&Bound<PyDict>>,
+    ) -> PyResult<()> {
         match fmt {
             Some(f) => {
-                check_caller_info_needed(&f);
-                let formatter: Arc<dyn Formatter + Send + Sync> = match datefmt {
-                    Some(df) => Arc::new(PythonFormatter::with_date_format(f, df)),
-                    None => Arc::new(PythonFormatter::new(f)),
-                };
-                self.inner.set_formatter_instance(formatter);
+                let formatter = styled_formatter(f, datefmt, style, defaults)?;
+                self.inner.set_formatter_instance(Arc::new(formatter));
             }
             None => self.inner.set_formatter_instance(Arc::new(NoOpFormatter)),
         }
@@ -184,6 +661,34 @@ impl PyFileHandler {
         Ok(())
     }
 
+    /// Format records with a native `Formatter`, `ColorFormatter`, `ColumnFormatter`
+    /// or `JsonFormatter` object.
+    #[pyo3(name = "setFormatter")]
+    fn set_formatter(&self, formatter: &Bound<PyAny>) -> PyResult<()> {
+        let formatter = native_formatter(formatter)
+            .ok_or_else(|| PyTypeError::new_err(NATIVE_FORMATTER_EXPECTED))?;
+        self.inner.set_formatter_instance(formatter);
+        self.inner.set_dispatch_mode(DispatchMode::Native);
+        Ok(())
+    }
+
+    /// Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+    /// text; None turns sanitization off. `newlines=False` also treats newlines and
+    /// tabs as control characters, keeping each record on one line.
+    #[pyo3(name = "setSanitize", signature = (policy=None, newlines=true))]
+    fn set_sanitize(&self, policy: Option<&str>, newlines: bool) -> PyResult<()> {
+        self.inner.set_sanitize(sanitize_policy(policy, newlines)?);
+        Ok(())
+    }
+
+    /// Run `filters` in Rust and return True when they are all Rust filters
+    /// (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+    /// otherwise return False and leave them to the wrapper's `handle()`.
+    #[pyo3(name = "setFilters")]
+    fn set_filters(&self, filters: Vec<Bound<PyAny>>) -> bool {
+        self.inner.filters().is_some_and(|slot| slot.set(&filters))
+    }
+
     #[pyo3(name = "setPythonDispatch")]
     fn set_python_dispatch(&self) -> PyResult<()> {
         self.inner.set_formatter_instance(Arc::new(NoOpFormatter));
@@ -197,6 +702,33 @@ impl PyFileHandler {
     }
 }
 
+/// The Rust formatter behind a native `Formatter`, `ColorFormatter`,
+/// `ColumnFormatter` or `JsonFormatter` object.
+fn native_formatter(obj: &Bound<PyAny>) -> Option<Arc<dyn Formatter + Send + Sync>> {
+    if let Ok(f) = obj.cast::<PyFormatter>() {
+        return Some(f.borrow().inner.clone());
+    }
+    if let Ok(f) = obj.cast::<PyColumnFormatter>() {
+        return Some(f.borrow().inner.clone());
+    }
+    #[cfg(feature = "colors")]
+    if let Ok(f) = obj.cast::<PyColorFormatter>() {
+        return Some(f.borrow().inner.clone());
+    }
+    #[cfg(feature = "json")]
+    if let Ok(f) = obj.cast::<PyJsonFormatter>() {
+        return Some(f.borrow().inner.clone());
+    }
+    None
+}
+
+fn sanitize_policy(policy: Option<&str>, newlines: bool) -> PyResult<SanitizePolicy> {
+    SanitizePolicy::new(policy, newlines).map_err(PyValueError::new_err)
+}
+
+const NATIVE_FORMATTER_EXPECTED: &str =
+    "expected a logxide Formatter, ColorFormatter, ColumnFormatter or JsonFormatter";
+
 #[pyclass(name = "StreamHandler", subclass)]
 pub struct PyStreamHandler {
     pub(crate) inner: Arc<StreamHandler>,
@@ -204,13 +736,21 @@ pub struct PyStreamHandler {
 
 #[pymethods]
 impl PyStreamHandler {
+    /// `overflow` is "drop_newest" (the default), "drop_oldest" or "block" for a
+    /// full queue; `block_timeout` is how many seconds "block" waits for room.
     #[new]
-    #[pyo3(signature = (stream=None))]
-    fn new(stream: Option<&str>) -> PyResult<Self> {
+    #[pyo3(signature = (stream=None, overflow=None, block_timeout=None))]
+    fn new(
+        stream: Option<&str>,
+        overflow: Option<&str>,
+        block_timeout: Option<f64>,
+    ) -> PyResult<Self> {
         let h = match stream {
             Some("stdout") => StreamHandler::stdout(),
             _ => StreamHandler::stderr(),
         };
+        let h = h.with_overflow(parse_overflow(overflow, "drop_newest", block_timeout)?);
+        h.sync_level().set(crate::preset::current().sync_level);
         Ok(Self { inner: Arc::new(h) })
     }
 
@@ -243,20 +783,22 @@ impl PyStreamHandler {
 
     fn emit(&self, _py: Python, record: &Bound<PyAny>) -> PyResult<()> {
         let rust_record = record.extract::<LogRecord>()?;
-        self.inner.emit(&rust_record);
+        self.inner.emit(&Arc::new(rust_record));
         Ok(())
     }
 
-    #[pyo3(name = "setFormatterSpec", signature = (fmt=None, datefmt=None))]
-    fn set_formatter_spec(&self, fmt: Option<String>, datefmt: Option<String>) -> PyResult<()> {
+    #[pyo3(name = "setFormatterSpec", signature = (fmt=None, datefmt=None, style="%", defaults=None))]
+    fn set_formatter_spec(
+        &self,
+        fmt: Option<String>,
+        datefmt: Option<String>,
+        style: &str,
+        defaults: Option<&Bound<PyDict>>,
+    ) -> PyResult<()> {
         match fmt {
             Some(f) => {
-                check_caller_info_needed(&f);
-                let formatter: Arc<dyn Formatter + Send + Sync> = match datefmt {
-                    Some(df) => Arc::new(PythonFormatter::with_date_format(f, df)),
-                    None => Arc::new(PythonFormatter::new(f)),
-                };
-                self.inner.set_formatter_instance(formatter);
+                let formatter = styled_formatter(f, datefmt, style, defaults)?;
+                self.inner.set_formatter_instance(Arc::new(formatter));
             }
             None => self.inner.set_formatter_instance(Arc::new(NoOpFormatter)),
         }
@@ -264,9 +806,73 @@ impl PyStreamHandler {
         Ok(())
     }
 
+    /// Format records with `fmt` while the destination is a terminal.
+    #[pyo3(
+        name = "setTtyFormatterSpec",
+        signature = (fmt=None, datefmt=None, style="%", defaults=None)
+    )]
+    fn set_tty_formatter_spec(
+        &self,
+        fmt: Option<String>,
+        datefmt: Option<String>,
+        style: &str,
+        defaults: Option<&Bound<PyDict>>,
+    ) -> PyResult<()> {
+        let formatter: Arc<dyn Formatter + Send + Sync> = match fmt {
+            Some(f) => Arc::new(styled_formatter(f, datefmt, style, defaults)?),
+            None => Arc::new(NoOpFormatter),
+        };
+        self.inner.set_tty_formatter(Some(formatter));
+        Ok(())
+    }
+
+    /// Use a native formatter object while the destination is a terminal, or None
+    /// to always use the regular formatter.
+    #[pyo3(name = "setTtyFormatter", signature = (formatter=None))]
+    fn set_tty_formatter(&self, formatter: Option<&Bound<PyAny>>) -> PyResult<()> {
+        let formatter = match formatter {
+            None => None,
+            Some(obj) => Some(
+                native_formatter(obj)
+                    .ok_or_else(|| PyTypeError::new_err(NATIVE_FORMATTER_EXPECTED))?,
+            ),
+        };
+        self.inner.set_tty_formatter(formatter);
+        Ok(())
+    }
+
+    /// Format records with a native `Formatter`, `ColorFormatter`, `ColumnFormatter`
+    /// or `JsonFormatter` object.
+    #[pyo3(name = "setFormatter")]
+    fn set_formatter(&self, formatter: &Bound<PyAny>) -> PyResult<()> {
+        let formatter = native_formatter(formatter)
+            .ok_or_else(|| PyTypeError::new_err(NATIVE_FORMATTER_EXPECTED))?;
+        self.inner.set_formatter_instance(formatter);
+        self.inner.set_dispatch_mode(DispatchMode::Native);
+        Ok(())
+    }
+
+    /// Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+    /// text; None turns sanitization off. `newlines=False` also treats newlines and
+    /// tabs as control characters, keeping each record on one line.
+    #[pyo3(name = "setSanitize", signature = (policy=None, newlines=true))]
+    fn set_sanitize(&self, policy: Option<&str>, newlines: bool) -> PyResult<()> {
+        self.inner.set_sanitize(sanitize_policy(policy, newlines)?);
+        Ok(())
+    }
+
+    /// Run `filters` in Rust and return True when they are all Rust filters
+    /// (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+    /// otherwise return False and leave them to the wrapper's `handle()`.
+    #[pyo3(name = "setFilters")]
+    fn set_filters(&self, filters: Vec<Bound<PyAny>>) -> bool {
+        self.inner.filters().is_some_and(|slot| slot.set(&filters))
+    }
+
     #[pyo3(name = "setPythonDispatch")]
     fn set_python_dispatch(&self) -> PyResult<()> {
         self.inner.set_formatter_instance(Arc::new(NoOpFormatter));
+        self.inner.set_tty_formatter(None);
         self.inner.set_dispatch_mode(DispatchMode::Python);
         Ok(())
     }
@@ -275,20 +881,83 @@ impl PyStreamHandler {
     fn is_native(&self) -> PyResult<bool> {
         Ok(self.inner.dispatch_mode() == DispatchMode::Native)
     }
+
+    /// Whether the destination stream is currently a terminal.
+    #[pyo3(name = "isTerminal")]
+    fn is_terminal(&self) -> bool {
+        self.inner.is_terminal()
+    }
+
+    #[pyo3(name = "get_metrics")]
+    fn get_metrics<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let (emitted, _) = self.inner.metrics_snapshot();
+        let dict = PyDict::new(py);
+        dict.set_item("emitted", emitted)?;
+        self.inner.queue_stats().fill_metrics(&dict)?;
+        dict.set_item("sync_emitted", self.inner.sync_level().emitted())?;
+        Ok(dict)
+    }
+
+    /// Records at or above `level` bypass the queue and are written and flushed
+    /// before `emit()` returns. None (the default) turns this off.
+    #[pyo3(name = "setSyncLevel", signature = (level=None))]
+    fn set_sync_level(&self, py: Python, level: Option<&Bound<PyAny>>) -> PyResult<()> {
+        self.inner.sync_level().set(parse_sync_level(py, level)?);
+        Ok(())
+    }
+
+    /// Get the current sync level, or None when records are always queued.
+    #[pyo3(name = "getSyncLevel")]
+    fn get_sync_level(&self) -> Option<u32> {
+        self.inner.sync_level().get().map(u32::from)
+    }
 }
 
+#[cfg(feature = "file")]
 #[pyclass(name = "RotatingFileHandler", subclass)]
 pub struct PyRotatingFileHandler {
     pub(crate) inner: Arc<RotatingFileHandler>,
 }
 
+#[cfg(feature = "file")]
 #[pymethods]
 impl PyRotatingFileHandler {
+    /// `max_age_days` and `max_total_bytes` delete backups that are too old, or the
+    /// oldest ones while the file and its backups exceed the budget, after each
+    /// rotation. `checksum_every` and `index_every` work as for `FileHandler`; each
+    /// backup ends with a trailer and keeps its index.
     #[new]
-    #[pyo3(signature = (filename, max_bytes=10485760, backup_count=5))]
-    fn new(filename: String, max_bytes: u64, backup_count: u32) -> PyResult<Self> {
-        let h = RotatingFileHandler::new(filename, max_bytes, backup_count)
-            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    #[pyo3(signature = (
+        filename,
+        max_bytes=10485760,
+        backup_count=5,
+        max_age_days=None,
+        max_total_bytes=None,
+        checksum_every=None,
+        index_every=None
+    ))]
+    fn new(
+        filename: FsPath,
+        max_bytes: u64,
+        backup_count: u32,
+        max_age_days: Option<f64>,
+        max_total_bytes: Option<u64>,
+        checksum_every: Option<u32>,
+        index_every: Option<u32>,
+    ) -> PyResult<Self> {
+        check_every("checksum_every", checksum_every)?;
+        check_every("index_every", index_every)?;
+        let retention =
+            Retention::new(max_age_days, max_total_bytes).map_err(PyValueError::new_err)?;
+        let mut h = RotatingFileHandler::new(filename.0, max_bytes, backup_count)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?
+            .with_retention(retention);
+        if let Some(every) = checksum_every {
+            h = h.with_checksums(every);
+        }
+        if let Some(every) = index_every {
+            h = h.with_index(every);
+        }
         Ok(Self { inner: Arc::new(h) })
     }
 
@@ -310,7 +979,7 @@ impl PyRotatingFileHandler {
     /// Get the current flush level.
     #[pyo3(name = "getFlushLevel")]
     fn get_flush_level(&self) -> PyResult<u32> {
-        Ok(self.inner.get_flush_level() as u32)
+        Ok(self.inner.get_flush_level())
     }
 
     /// Set an error callback function.
@@ -340,20 +1009,22 @@ impl PyRotatingFileHandler {
 
     fn emit(&self, _py: Python, record: &Bound<PyAny>) -> PyResult<()> {
         let rust_record = record.extract::<LogRecord>()?;
-        self.inner.emit(&rust_record);
+        self.inner.emit(&Arc::new(rust_record));
         Ok(())
     }
 
-    #[pyo3(name = "setFormatterSpec", signature = (fmt=None, datefmt=None))]
-    fn set_formatter_spec(&self, fmt: Option<String>, datefmt: Option<String>) -> PyResult<()> {
+    #[pyo3(name = "setFormatterSpec", signature = (fmt=None, datefmt=None, style="%", defaults=None))]
+    fn set_formatter_spec(
+        &self,
+        fmt: Option<String>,
+        datefmt: Option<String>,
+        style: &str,
+        defaults: Option<&Bound<PyDict>>,
+    ) -> PyResult<()> {
         match fmt {
             Some(f) => {
-                check_caller_info_needed(&f);
-                let formatter: Arc<dyn Formatter + Send + Sync> = match datefmt {
-                    Some(df) => Arc::new(PythonFormatter::with_date_format(f, df)),
-                    None => Arc::new(PythonFormatter::new(f)),
-                };
-                self.inner.set_formatter_instance(formatter);
+                let formatter = styled_formatter(f, datefmt, style, defaults)?;
+                self.inner.set_formatter_instance(Arc::new(formatter));
             }
             None => self.inner.set_formatter_instance(Arc::new(NoOpFormatter)),
         }
@@ -361,6 +1032,34 @@ impl PyRotatingFileHandler {
         Ok(())
     }
 
+    /// Format records with a native `Formatter`, `ColorFormatter`, `ColumnFormatter`
+    /// or `JsonFormatter` object.
+    #[pyo3(name = "setFormatter")]
+    fn set_formatter(&self, formatter: &Bound<PyAny>) -> PyResult<()> {
+        let formatter = native_formatter(formatter)
+            .ok_or_else(|| PyTypeError::new_err(NATIVE_FORMATTER_EXPECTED))?;
+        self.inner.set_formatter_instance(formatter);
+        self.inner.set_dispatch_mode(DispatchMode::Native);
+        Ok(())
+    }
+
+    /// Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+    /// text; None turns sanitization off. `newlines=False` also treats newlines and
+    /// tabs as control characters, keeping each record on one line.
+    #[pyo3(name = "setSanitize", signature = (policy=None, newlines=true))]
+    fn set_sanitize(&self, policy: Option<&str>, newlines: bool) -> PyResult<()> {
+        self.inner.set_sanitize(sanitize_policy(policy, newlines)?);
+        Ok(())
+    }
+
+    /// Run `filters` in Rust and return True when they are all Rust filters
+    /// (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+    /// otherwise return False and leave them to the wrapper's `handle()`.
+    #[pyo3(name = "setFilters")]
+    fn set_filters(&self, filters: Vec<Bound<PyAny>>) -> bool {
+        self.inner.filters().is_some_and(|slot| slot.set(&filters))
+    }
+
     #[pyo3(name = "setPythonDispatch")]
     fn set_python_dispatch(&self) -> PyResult<()> {
         self.inner.set_formatter_instance(Arc::new(NoOpFormatter));
@@ -374,72 +1073,74 @@ impl PyRotatingFileHandler {
     }
 }
 
-#[pyclass(name = "HTTPHandler", subclass)]
-pub struct PyHTTPHandler {
-    pub(crate) inner: Arc<HTTPHandler>,
-}
-
-impl Drop for PyHTTPHandler {
-    fn drop(&mut self) {}
+#[cfg(feature = "file")]
+#[pyclass(name = "TimedRotatingFileHandler", subclass)]
+pub struct PyTimedRotatingFileHandler {
+    pub(crate) inner: Arc<TimedRotatingFileHandler>,
 }
 
+#[cfg(feature = "file")]
 #[pymethods]
-impl PyHTTPHandler {
+impl PyTimedRotatingFileHandler {
+    /// `when`, `interval`, `utc` and `at_time` (seconds after midnight) follow
+    /// `logging.handlers.TimedRotatingFileHandler`. `compress` gzips each backup on a
+    /// background thread. `max_age_days`, `max_total_bytes`, `checksum_every` and
+    /// `index_every` work as for `RotatingFileHandler`; gzipped backups keep no index.
     #[new]
     #[pyo3(signature = (
-        url,
-        headers=None,
-        capacity=10000,
-        batch_size=1000,
-        flush_interval=30,
-        global_context=None,
-        transform_callback=None,
-        context_provider=None,
-        error_callback=None,
-        overflow="block"
+        filename,
+        when="h",
+        interval=1,
+        backup_count=0,
+        utc=false,
+        at_time=None,
+        compress=false,
+        max_age_days=None,
+        max_total_bytes=None,
+        checksum_every=None,
+        index_every=None
     ))]
     #[allow(clippy::too_many_arguments)]
     fn new(
-        py: Python,
-        url: String,
-        headers: Option<HashMap<String, String>>,
-        capacity: usize,
-        batch_size: usize,
-        flush_interval: u64,
-        global_context: Option<&Bound<PyDict>>,
-        transform_callback: Option<Py<PyAny>>,
-        context_provider: Option<Py<PyAny>>,
-        error_callback: Option<Py<PyAny>>,
-        overflow: &str,
+        filename: FsPath,
+        when: &str,
+        interval: u32,
+        backup_count: u32,
+        utc: bool,
+        at_time: Option<u32>,
+        compress: bool,
+        max_age_days: Option<f64>,
+        max_total_bytes: Option<u64>,
+        checksum_every: Option<u32>,
+        index_every: Option<u32>,
     ) -> PyResult<Self> {
-        let h_map = headers.unwrap_or_default();
-
-        let global_ctx: HashMap<String, Value> = global_context
-            .map(|dict| {
-                let mut map = HashMap::new();
-                for (k, v) in dict.iter() {
-                    if let Ok(key) = k.extract::<String>() {
-                        map.insert(key, crate::py_logger::py_to_json_value(&v));
-                    }
-                }
-                map
-            })
-            .unwrap_or_default();
-
-        let config = HTTPHandlerConfig {
-            url,
-            headers: h_map,
-            global_context: global_ctx,
-            transform_callback: transform_callback.map(|cb| cb.clone_ref(py)),
-            context_provider: context_provider.map(|cb| cb.clone_ref(py)),
-            error_callback: error_callback.map(|cb| cb.clone_ref(py)),
-            overflow: OverflowStrategy::from_overflow_str(overflow),
-        };
-
-        let h = HTTPHandler::with_config(config, capacity, batch_size, flush_interval);
+        check_every("checksum_every", checksum_every)?;
+        check_every("index_every", index_every)?;
+        if compress {
+            crate::platform::require_compression("gzip")?;
+        }
+        let schedule =
+            RotationSchedule::new(when, interval, utc, at_time).map_err(PyValueError::new_err)?;
+        let retention =
+            Retention::new(max_age_days, max_total_bytes).map_err(PyValueError::new_err)?;
+        let mut h = TimedRotatingFileHandler::new(filename.0, schedule, backup_count, compress)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?
+            .with_retention(retention);
+        if let Some(every) = checksum_every {
+            h = h.with_checksums(every);
+        }
+        if let Some(every) = index_every {
+            h = h.with_index(every);
+        }
         Ok(Self { inner: Arc::new(h) })
     }
 
+    /// Unix time (seconds) of the next rollover.
+    #[getter(rolloverAt)]
+    fn rollover_at(&self) -> i64 {
+        self.inner.rollover_at()
+    }
+
     fn setLevel(&self, py: Python, level: &Bound<PyAny>) -> PyResult<()> {
         let level_int = check_level(py, level)?;
         self.inner
@@ -447,102 +1148,1482 @@ impl PyHTTPHandler {
         Ok(())
     }
 
-    fn flush(&self, py: Python) -> PyResult<()> {
-        py.detach(|| self.inner.flush());
+    /// Set the flush level. Records at or above this level trigger immediate flush.
+    #[pyo3(name = "setFlushLevel")]
+    fn set_flush_level(&self, level: u32) -> PyResult<()> {
+        self.inner
+            .set_flush_level(LogLevel::from_usize(level as usize));
         Ok(())
     }
 
-    fn shutdown(&self, py: Python) -> PyResult<()> {
-        py.detach(|| self.inner.shutdown());
-        Ok(())
+    /// Get the current flush level.
+    #[pyo3(name = "getFlushLevel")]
+    fn get_flush_level(&self) -> PyResult<u32> {
+        Ok(self.inner.get_flush_level())
     }
 
-    #[pyo3(name = "get_metrics")]
-    fn get_metrics<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
-        let (emitted, sink_acknowledged, queue_dropped, delivery_failed) =
-            self.inner.metrics_snapshot();
-        let in_flight = emitted
-            .saturating_sub(sink_acknowledged)
-            .saturating_sub(queue_dropped)
-            .saturating_sub(delivery_failed);
-        let dict = PyDict::new(py);
-        dict.set_item("emitted", emitted)?;
+    /// Set an error callback function.
+    #[pyo3(name = "setErrorCallback")]
+    fn set_error_callback(&self, py: Python, callback: Option<Py<PyAny>>) -> PyResult<()> {
+        match callback {
+            Some(cb) => {
+                let cb = cb.clone_ref(py);
+                self.inner
+                    .set_error_callback(Some(Arc::new(move |msg: String| {
+                        Python::attach(|py| {
+                            let _ = cb.call1(py, (msg,));
+                        });
+                    })));
+            }
+            None => {
+                self.inner.set_error_callback(None);
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&self) -> PyResult<()> {
+        self.inner.flush();
+        Ok(())
+    }
+
+    fn emit(&self, _py: Python, record: &Bound<PyAny>) -> PyResult<()> {
+        let rust_record = record.extract::<LogRecord>()?;
+        self.inner.emit(&Arc::new(rust_record));
+        Ok(())
+    }
+
+    #[pyo3(name = "setFormatterSpec", signature = (fmt=None, datefmt=None, style="%", defaults=None))]
+    fn set_formatter_spec(
+        &self,
+        fmt: Option<String>,
+        datefmt: Option<String>,
+        style: &str,
+        defaults: Option<&Bound<PyDict>>,
+    ) -> PyResult<()> {
+        match fmt {
+            Some(f) => {
+                let formatter = styled_formatter(f, datefmt, style, defaults)?;
+                self.inner.set_formatter_instance(Arc::new(formatter));
+            }
+            None => self.inner.set_formatter_instance(Arc::new(NoOpFormatter)),
+        }
+        self.inner.set_dispatch_mode(DispatchMode::Native);
+        Ok(())
+    }
+
+    /// Format records with a native `Formatter`, `ColorFormatter`, `ColumnFormatter`
+    /// or `JsonFormatter` object.
+    #[pyo3(name = "setFormatter")]
+    fn set_formatter(&self, formatter: &Bound<PyAny>) -> PyResult<()> {
+        let formatter = native_formatter(formatter)
+            .ok_or_else(|| PyTypeError::new_err(NATIVE_FORMATTER_EXPECTED))?;
+        self.inner.set_formatter_instance(formatter);
+        self.inner.set_dispatch_mode(DispatchMode::Native);
+        Ok(())
+    }
+
+    /// Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+    /// text; None turns sanitization off. `newlines=False` also treats newlines and
+    /// tabs as control characters, keeping each record on one line.
+    #[pyo3(name = "setSanitize", signature = (policy=None, newlines=true))]
+    fn set_sanitize(&self, policy: Option<&str>, newlines: bool) -> PyResult<()> {
+        self.inner.set_sanitize(sanitize_policy(policy, newlines)?);
+        Ok(())
+    }
+
+    /// Run `filters` in Rust and return True when they are all Rust filters
+    /// (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+    /// otherwise return False and leave them to the wrapper's `handle()`.
+    #[pyo3(name = "setFilters")]
+    fn set_filters(&self, filters: Vec<Bound<PyAny>>) -> bool {
+        self.inner.filters().is_some_and(|slot| slot.set(&filters))
+    }
+
+    #[pyo3(name = "setPythonDispatch")]
+    fn set_python_dispatch(&self) -> PyResult<()> {
+        self.inner.set_formatter_instance(Arc::new(NoOpFormatter));
+        self.inner.set_dispatch_mode(DispatchMode::Python);
+        Ok(())
+    }
+
+    #[pyo3(name = "isNative")]
+    fn is_native(&self) -> PyResult<bool> {
+        Ok(self.inner.dispatch_mode() == DispatchMode::Native)
+    }
+}
+
+#[cfg(feature = "file")]
+#[pyclass(name = "LevelRoutedFileHandler", subclass)]
+pub struct PyLevelRoutedFileHandler {
+    pub(crate) inner: Arc<LevelRoutedFileHandler>,
+}
+
+#[cfg(feature = "file")]
+#[pymethods]
+impl PyLevelRoutedFileHandler {
+    /// `routes` maps the lowest level of each band (int or level name) to the native
+    /// `FileHandler`, `RotatingFileHandler` or `TimedRotatingFileHandler` that writes it.
+    /// A record goes to the band with the highest level at or below its own.
+    #[new]
+    fn new(py: Python, routes: &Bound<PyDict>) -> PyResult<Self> {
+        if routes.is_empty() {
+            return Err(PyValueError::new_err("routes must name at least one file"));
+        }
+        let mut bands = Vec::with_capacity(routes.len());
+        for (level, file) in routes.iter() {
+            let band = check_level(py, &level)?;
+            if bands.iter().any(|(b, _)| *b == band) {
+                return Err(PyValueError::new_err(format!(
+                    "level {band} is routed to more than one file"
+                )));
+            }
+            let file = if let Ok(h) = file.extract::<PyRef<PyFileHandler>>() {
+                RoutedFile::Plain(h.inner.clone())
+            } else if let Ok(h) = file.extract::<PyRef<PyRotatingFileHandler>>() {
+                RoutedFile::Rotating(h.inner.clone())
+            } else if let Ok(h) = file.extract::<PyRef<PyTimedRotatingFileHandler>>() {
+                RoutedFile::Timed(h.inner.clone())
+            } else {
+                return Err(PyTypeError::new_err(
+                    "routes values must be native FileHandler, RotatingFileHandler or \
+                     TimedRotatingFileHandler objects",
+                ));
+            };
+            bands.push((band, file));
+        }
+        Ok(Self {
+            inner: Arc::new(LevelRoutedFileHandler::new(bands)),
+        })
+    }
+
+    fn setLevel(&self, py: Python, level: &Bound<PyAny>) -> PyResult<()> {
+        let level_int = check_level(py, level)?;
+        self.inner
+            .set_level(LogLevel::from_usize(level_int as usize));
+        Ok(())
+    }
+
+    /// Set the flush level of every file. Records at or above this level trigger
+    /// immediate flush. Default is ERROR (40).
+    #[pyo3(name = "setFlushLevel")]
+    fn set_flush_level(&self, level: u32) -> PyResult<()> {
+        self.inner
+            .set_flush_level(LogLevel::from_usize(level as usize));
+        Ok(())
+    }
+
+    /// Get the current flush level.
+    #[pyo3(name = "getFlushLevel")]
+    fn get_flush_level(&self) -> PyResult<u32> {
+        Ok(self.inner.get_flush_level())
+    }
+
+    fn flush(&self) -> PyResult<()> {
+        self.inner.flush();
+        Ok(())
+    }
+
+    fn emit(&self, _py: Python, record: &Bound<PyAny>) -> PyResult<()> {
+        let rust_record = record.extract::<LogRecord>()?;
+        self.inner.emit(&Arc::new(rust_record));
+        Ok(())
+    }
+
+    #[pyo3(name = "setFormatterSpec", signature = (fmt=None, datefmt=None, style="%", defaults=None))]
+    fn set_formatter_spec(
+        &self,
+        fmt: Option<String>,
+        datefmt: Option<String>,
+        style: &str,
+        defaults: Option<&Bound<PyDict>>,
+    ) -> PyResult<()> {
+        match fmt {
+            Some(f) => {
+                let formatter = styled_formatter(f, datefmt, style, defaults)?;
+                self.inner.set_formatter_instance(Arc::new(formatter));
+            }
+            None => self.inner.set_formatter_instance(Arc::new(NoOpFormatter)),
+        }
+        self.inner.set_dispatch_mode(DispatchMode::Native);
+        Ok(())
+    }
+
+    /// Format records with a native `Formatter`, `ColorFormatter`, `ColumnFormatter`
+    /// or `JsonFormatter` object.
+    #[pyo3(name = "setFormatter")]
+    fn set_formatter(&self, formatter: &Bound<PyAny>) -> PyResult<()> {
+        let formatter = native_formatter(formatter)
+            .ok_or_else(|| PyTypeError::new_err(NATIVE_FORMATTER_EXPECTED))?;
+        self.inner.set_formatter_instance(formatter);
+        self.inner.set_dispatch_mode(DispatchMode::Native);
+        Ok(())
+    }
+
+    /// Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+    /// text; None turns sanitization off. `newlines=False` also treats newlines and
+    /// tabs as control characters, keeping each record on one line.
+    #[pyo3(name = "setSanitize", signature = (policy=None, newlines=true))]
+    fn set_sanitize(&self, policy: Option<&str>, newlines: bool) -> PyResult<()> {
+        self.inner.set_sanitize(sanitize_policy(policy, newlines)?);
+        Ok(())
+    }
+
+    /// Run `filters` in Rust and return True when they are all Rust filters
+    /// (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+    /// otherwise return False and leave them to the wrapper's `handle()`.
+    #[pyo3(name = "setFilters")]
+    fn set_filters(&self, filters: Vec<Bound<PyAny>>) -> bool {
+        self.inner.filters().is_some_and(|slot| slot.set(&filters))
+    }
+
+    #[pyo3(name = "setPythonDispatch")]
+    fn set_python_dispatch(&self) -> PyResult<()> {
+        self.inner.set_formatter_instance(Arc::new(NoOpFormatter));
+        self.inner.set_dispatch_mode(DispatchMode::Python);
+        Ok(())
+    }
+
+    #[pyo3(name = "isNative")]
+    fn is_native(&self) -> PyResult<bool> {
+        Ok(self.inner.dispatch_mode() == DispatchMode::Native)
+    }
+}
+
+#[cfg(feature = "network")]
+#[pyclass(name = "HTTPHandler", subclass)]
+pub struct PyHTTPHandler {
+    pub(crate) inner: Arc<HTTPHandler>,
+}
+
+#[cfg(feature = "network")]
+impl Drop for PyHTTPHandler {
+    fn drop(&mut self) {}
+}
+
+#[cfg(feature = "network")]
+#[pymethods]
+impl PyHTTPHandler {
+    #[new]
+    #[pyo3(signature = (
+        url,
+        headers=None,
+        capacity=None,
+        batch_size=None,
+        flush_interval=None,
+        global_context=None,
+        transform_callback=None,
+        context_provider=None,
+        error_callback=None,
+        overflow=None,
+        block_timeout=None,
+        shutdown_timeout=Some(30.0),
+        spill_path=None,
+        daemon=None,
+        sync_level=None,
+        method=None,
+        credentials=None,
+        compression=None,
+        compression_min_bytes=1024,
+        max_batch_bytes=None,
+        idempotency_keys=false,
+        send_time=false,
+        max_backdate=None,
+        dry_run=None,
+        record_transform=None,
+        context_ttl=None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        py: Python,
+        url: String,
+        headers: Option<HashMap<String, String>>,
+        capacity: Option<usize>,
+        batch_size: Option<usize>,
+        flush_interval: Option<u64>,
+        global_context: Option<&Bound<PyDict>>,
+        transform_callback: Option<Py<PyAny>>,
+        context_provider: Option<Py<PyAny>>,
+        error_callback: Option<Py<PyAny>>,
+        overflow: Option<&str>,
+        block_timeout: Option<f64>,
+        shutdown_timeout: Option<f64>,
+        spill_path: Option<PathBuf>,
+        daemon: Option<bool>,
+        sync_level: Option<&Bound<PyAny>>,
+        method: Option<&str>,
+        credentials: Option<(String, String)>,
+        compression: Option<&str>,
+        compression_min_bytes: usize,
+        max_batch_bytes: Option<usize>,
+        idempotency_keys: bool,
+        send_time: bool,
+        max_backdate: Option<f64>,
+        dry_run: Option<&Bound<PyAny>>,
+        record_transform: Option<&Bound<PyAny>>,
+        context_ttl: Option<f64>,
+    ) -> PyResult<Self> {
+        crate::platform::require_network("HTTPHandler")?;
+        let shutdown_timeout = parse_shutdown_timeout(shutdown_timeout)?;
+        let payload = parse_http_method(method)?;
+        if payload != HttpPayload::Json
+            && (transform_callback.is_some() || context_provider.is_some())
+        {
+            return Err(PyValueError::new_err(
+                "transform_callback and context_provider only apply to JSON batches (method=None)",
+            ));
+        }
+        let compression = parse_compression(compression)?;
+        if payload != HttpPayload::Json && compression.is_some() {
+            return Err(PyValueError::new_err(
+                "compression only applies to JSON batches (method=None)",
+            ));
+        }
+        let max_batch_bytes = parse_max_batch_bytes(max_batch_bytes)?;
+        if payload != HttpPayload::Json && max_batch_bytes.is_some() {
+            return Err(PyValueError::new_err(
+                "max_batch_bytes only applies to JSON batches (method=None)",
+            ));
+        }
+        if payload != HttpPayload::Json && idempotency_keys {
+            return Err(PyValueError::new_err(
+                "idempotency_keys only applies to JSON batches (method=None)",
+            ));
+        }
+        if payload != HttpPayload::Json && (send_time || max_backdate.is_some()) {
+            return Err(PyValueError::new_err(
+                "send_time and max_backdate only apply to JSON batches (method=None)",
+            ));
+        }
+        let context_ttl = match context_ttl {
+            None => None,
+            Some(_) if context_provider.is_none() => {
+                return Err(PyValueError::new_err(
+                    "context_ttl needs a context_provider",
+                ))
+            }
+            Some(secs) if secs.is_finite() && secs > 0.0 => Some(Duration::from_secs_f64(secs)),
+            Some(_) => {
+                return Err(PyValueError::new_err(
+                    "context_ttl must be a positive number of seconds or None",
+                ))
+            }
+        };
+        let record_transform = parse_record_transform(record_transform)?;
+        if payload != HttpPayload::Json && record_transform.is_some() {
+            return Err(PyValueError::new_err(
+                "record_transform only applies to JSON batches (method=None)",
+            ));
+        }
+        let mut h_map = headers.unwrap_or_default();
+        if let Some((user, password)) = credentials {
+            h_map.insert("Authorization".to_string(), basic_auth(&user, &password));
+        }
+
+        let global_ctx: HashMap<String, Value> = global_context
+            .map(|dict| {
+                let mut map = HashMap::new();
+                for (k, v) in dict.iter() {
+                    if let Ok(key) = k.extract::<String>() {
+                        map.insert(key, crate::py_logger::py_to_json_value(&v));
+                    }
+                }
+                map
+            })
+            .unwrap_or_default();
+
+        let config = HTTPHandlerConfig {
+            url,
+            payload,
+            headers: h_map,
+            global_context: global_ctx,
+            record_transform,
+            transform_callback: transform_callback.map(|cb| cb.clone_ref(py)),
+            context_provider: context_provider.map(|cb| cb.clone_ref(py)),
+            context_ttl,
+            error_callback: error_callback.map(|cb| cb.clone_ref(py)),
+            overflow: parse_overflow(overflow, "block", block_timeout)?,
+            shutdown_timeout,
+            spill_path,
+            daemon: crate::worker::resolve_daemon(daemon),
+            sync_level: default_sync_level(py, sync_level)?,
+            compression,
+            compression_min_bytes,
+            max_batch_bytes,
+            idempotency_keys,
+            timestamps: parse_timestamp_policy(send_time, max_backdate)?,
+            transport: parse_dry_run(dry_run)?,
+        };
+
+        let preset = crate::preset::current();
+        let h = HTTPHandler::with_config(
+            config,
+            capacity.unwrap_or(preset.capacity),
+            batch_size.unwrap_or(preset.batch_size),
+            flush_interval.unwrap_or(preset.flush_interval),
+        );
+        Ok(Self { inner: Arc::new(h) })
+    }
+
+    fn setLevel(&self, py: Python, level: &Bound<PyAny>) -> PyResult<()> {
+        let level_int = check_level(py, level)?;
+        self.inner
+            .set_level(LogLevel::from_usize(level_int as usize));
+        Ok(())
+    }
+
+    fn flush(&self, py: Python) -> PyResult<()> {
+        py.detach(|| self.inner.flush());
+        Ok(())
+    }
+
+    fn shutdown(&self, py: Python) -> PyResult<()> {
+        py.detach(|| self.inner.shutdown());
+        Ok(())
+    }
+
+    /// Whether the worker is a daemon (abandoned at interpreter exit instead of drained).
+    #[getter]
+    fn daemon(&self) -> bool {
+        self.inner.is_daemon()
+    }
+
+    #[pyo3(name = "get_metrics")]
+    fn get_metrics<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let (emitted, sink_acknowledged, queue_dropped, delivery_failed) =
+            self.inner.metrics_snapshot();
+        let (shutdown_dropped, shutdown_spilled) = self.inner.shutdown_snapshot();
+        let in_flight = emitted
+            .saturating_sub(sink_acknowledged)
+            .saturating_sub(queue_dropped)
+            .saturating_sub(delivery_failed)
+            .saturating_sub(shutdown_dropped)
+            .saturating_sub(shutdown_spilled);
+        let dict = PyDict::new(py);
+        dict.set_item("emitted", emitted)?;
+        dict.set_item("sink_acknowledged", sink_acknowledged)?;
+        dict.set_item("queue_dropped", queue_dropped)?;
+        dict.set_item("delivery_failed", delivery_failed)?;
+        dict.set_item("shutdown_dropped", shutdown_dropped)?;
+        dict.set_item("shutdown_spilled", shutdown_spilled)?;
+        dict.set_item("in_flight", in_flight)?;
+        self.inner.queue_stats().fill_metrics(&dict)?;
+        dict.set_item("sync_emitted", self.inner.sync_level().emitted())?;
+        dict.set_item("throttled", self.inner.backoff().throttled())?;
+        dict.set_item(
+            "throttle_remaining_ms",
+            self.inner.backoff().remaining().as_millis() as u64,
+        )?;
+        dict.set_item("payload_bytes", self.inner.body_encoding().payload_bytes())?;
+        dict.set_item("wire_bytes", self.inner.body_encoding().wire_bytes())?;
+        dict.set_item("oversized_dropped", self.inner.body_encoding().oversized())?;
+        dict.set_item("timestamps_capped", self.inner.body_encoding().capped())?;
+        dict.set_item(
+            "context_provider_calls",
+            self.inner.record_context().provider_calls(),
+        )?;
+        Ok(dict)
+    }
+
+    /// Merge `context` into the fields added to every record from the next batch on,
+    /// or replace the fields pushed so far when `replace` is true.
+    #[pyo3(signature = (context, replace=false))]
+    fn update_context(&self, context: &Bound<PyDict>, replace: bool) {
+        let fields = context
+            .iter()
+            .filter_map(|(k, v)| {
+                let key = k.extract::<String>().ok()?;
+                Some((key, crate::py_logger::py_to_json_value(&v)))
+            })
+            .collect();
+        self.inner.record_context().update(fields, replace);
+    }
+
+    /// Records at or above `level` bypass the queue and are written and flushed
+    /// before `emit()` returns. None (the default) turns this off.
+    #[pyo3(name = "setSyncLevel", signature = (level=None))]
+    fn set_sync_level(&self, py: Python, level: Option<&Bound<PyAny>>) -> PyResult<()> {
+        self.inner.sync_level().set(parse_sync_level(py, level)?);
+        Ok(())
+    }
+
+    /// Get the current sync level, or None when records are always queued.
+    #[pyo3(name = "getSyncLevel")]
+    fn get_sync_level(&self) -> Option<u32> {
+        self.inner.sync_level().get().map(u32::from)
+    }
+
+    /// Set the flush level. Records at or above this level trigger immediate flush.
+    /// Default is ERROR (40). Use logging.CRITICAL (50) to flush only on critical.
+    /// Use logging.DEBUG (10) to flush on every record.
+    #[pyo3(name = "setFlushLevel")]
+    fn set_flush_level(&self, level: u32) -> PyResult<()> {
+        self.inner
+            .set_flush_level(LogLevel::from_usize(level as usize));
+        Ok(())
+    }
+
+    /// Get the current flush level.
+    #[pyo3(name = "getFlushLevel")]
+    fn get_flush_level(&self) -> PyResult<u32> {
+        Ok(self.inner.get_flush_level())
+    }
+
+    fn emit(&self, py: Python, record: &Bound<PyAny>) -> PyResult<()> {
+        let rust_record = record.extract::<LogRecord>()?;
+        // A sync_level record is sent on this thread; don't hold the GIL over the request.
+        py.detach(|| self.inner.emit(&Arc::new(rust_record)));
+        Ok(())
+    }
+
+    /// Run `filters` in Rust and return True when they are all Rust filters
+    /// (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+    /// otherwise return False and leave them to the wrapper's `handle()`.
+    #[pyo3(name = "setFilters")]
+    fn set_filters(&self, filters: Vec<Bound<PyAny>>) -> bool {
+        self.inner.filters().is_some_and(|slot| slot.set(&filters))
+    }
+
+    /// Route records through the wrapper's `handle()` (it has filters) or not.
+    #[pyo3(name = "setPythonDispatch", signature = (enabled=true))]
+    fn set_python_dispatch(&self, enabled: bool) {
+        self.inner.set_dispatch_mode(if enabled {
+            DispatchMode::Python
+        } else {
+            DispatchMode::Native
+        });
+    }
+
+    #[pyo3(name = "isNative")]
+    fn is_native(&self) -> bool {
+        self.inner.dispatch_mode() == DispatchMode::Native
+    }
+}
+
+#[cfg(feature = "otlp")]
+#[pyclass(name = "OTLPHandler", subclass)]
+pub struct PyOTLPHandler {
+    pub(crate) inner: Arc<OTLPHandler>,
+}
+
+#[cfg(feature = "otlp")]
+impl Drop for PyOTLPHandler {
+    fn drop(&mut self) {}
+}
+
+#[cfg(feature = "otlp")]
+#[pymethods]
+impl PyOTLPHandler {
+    #[new]
+    #[pyo3(signature = (
+        url,
+        headers=None,
+        service_name="unknown_service".to_string(),
+        capacity=None,
+        batch_size=None,
+        flush_interval=None,
+        error_callback=None,
+        overflow=None,
+        block_timeout=None,
+        shutdown_timeout=Some(30.0),
+        spill_path=None,
+        daemon=None,
+        sync_level=None,
+        compression=None,
+        compression_min_bytes=1024,
+        max_batch_bytes=None,
+        idempotency_keys=false,
+        send_time=false,
+        max_backdate=None,
+        dry_run=None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        py: Python,
+        url: String,
+        headers: Option<HashMap<String, String>>,
+        service_name: String,
+        capacity: Option<usize>,
+        batch_size: Option<usize>,
+        flush_interval: Option<u64>,
+        error_callback: Option<Py<PyAny>>,
+        overflow: Option<&str>,
+        block_timeout: Option<f64>,
+        shutdown_timeout: Option<f64>,
+        spill_path: Option<PathBuf>,
+        daemon: Option<bool>,
+        sync_level: Option<&Bound<PyAny>>,
+        compression: Option<&str>,
+        compression_min_bytes: usize,
+        max_batch_bytes: Option<usize>,
+        idempotency_keys: bool,
+        send_time: bool,
+        max_backdate: Option<f64>,
+        dry_run: Option<&Bound<PyAny>>,
+    ) -> PyResult<Self> {
+        crate::platform::require_network("OTLPHandler")?;
+        let shutdown_timeout = parse_shutdown_timeout(shutdown_timeout)?;
+        let h_map = headers.unwrap_or_default();
+
+        let config = OTLPHandlerConfig {
+            url,
+            headers: h_map,
+            service_name,
+            error_callback: error_callback.map(|cb| cb.clone_ref(py)),
+            overflow: parse_overflow(overflow, "block", block_timeout)?,
+            shutdown_timeout,
+            spill_path,
+            daemon: crate::worker::resolve_daemon(daemon),
+            sync_level: default_sync_level(py, sync_level)?,
+            compression: parse_compression(compression)?,
+            compression_min_bytes,
+            max_batch_bytes: parse_max_batch_bytes(max_batch_bytes)?,
+            idempotency_keys,
+            timestamps: parse_timestamp_policy(send_time, max_backdate)?,
+            transport: parse_dry_run(dry_run)?,
+        };
+
+        let preset = crate::preset::current();
+        let h = OTLPHandler::with_config(
+            config,
+            capacity.unwrap_or(preset.capacity),
+            batch_size.unwrap_or(preset.batch_size),
+            flush_interval.unwrap_or(preset.flush_interval),
+        );
+        Ok(Self { inner: Arc::new(h) })
+    }
+
+    fn setLevel(&self, py: Python, level: &Bound<PyAny>) -> PyResult<()> {
+        let level_int = check_level(py, level)?;
+        self.inner
+            .set_level(LogLevel::from_usize(level_int as usize));
+        Ok(())
+    }
+
+    fn flush(&self, py: Python) -> PyResult<()> {
+        py.detach(|| self.inner.flush());
+        Ok(())
+    }
+
+    fn shutdown(&self, py: Python) -> PyResult<()> {
+        py.detach(|| self.inner.shutdown());
+        Ok(())
+    }
+
+    /// Whether the worker is a daemon (abandoned at interpreter exit instead of drained).
+    #[getter]
+    fn daemon(&self) -> bool {
+        self.inner.is_daemon()
+    }
+
+    #[pyo3(name = "get_metrics")]
+    fn get_metrics<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let (emitted, sink_acknowledged, queue_dropped, delivery_failed) =
+            self.inner.metrics_snapshot();
+        let (shutdown_dropped, shutdown_spilled) = self.inner.shutdown_snapshot();
+        let in_flight = emitted
+            .saturating_sub(sink_acknowledged)
+            .saturating_sub(queue_dropped)
+            .saturating_sub(delivery_failed)
+            .saturating_sub(shutdown_dropped)
+            .saturating_sub(shutdown_spilled);
+        let dict = PyDict::new(py);
+        dict.set_item("emitted", emitted)?;
+        dict.set_item("sink_acknowledged", sink_acknowledged)?;
+        dict.set_item("queue_dropped", queue_dropped)?;
+        dict.set_item("delivery_failed", delivery_failed)?;
+        dict.set_item("shutdown_dropped", shutdown_dropped)?;
+        dict.set_item("shutdown_spilled", shutdown_spilled)?;
+        dict.set_item("in_flight", in_flight)?;
+        self.inner.queue_stats().fill_metrics(&dict)?;
+        dict.set_item("sync_emitted", self.inner.sync_level().emitted())?;
+        dict.set_item("throttled", self.inner.backoff().throttled())?;
+        dict.set_item(
+            "throttle_remaining_ms",
+            self.inner.backoff().remaining().as_millis() as u64,
+        )?;
+        dict.set_item("payload_bytes", self.inner.body_encoding().payload_bytes())?;
+        dict.set_item("wire_bytes", self.inner.body_encoding().wire_bytes())?;
+        dict.set_item("oversized_dropped", self.inner.body_encoding().oversized())?;
+        dict.set_item("timestamps_capped", self.inner.body_encoding().capped())?;
+        Ok(dict)
+    }
+
+    /// Records at or above `level` bypass the queue and are written and flushed
+    /// before `emit()` returns. None (the default) turns this off.
+    #[pyo3(name = "setSyncLevel", signature = (level=None))]
+    fn set_sync_level(&self, py: Python, level: Option<&Bound<PyAny>>) -> PyResult<()> {
+        self.inner.sync_level().set(parse_sync_level(py, level)?);
+        Ok(())
+    }
+
+    /// Get the current sync level, or None when records are always queued.
+    #[pyo3(name = "getSyncLevel")]
+    fn get_sync_level(&self) -> Option<u32> {
+        self.inner.sync_level().get().map(u32::from)
+    }
+
+    fn emit(&self, py: Python, record: &Bound<PyAny>) -> PyResult<()> {
+        let rust_record = record.extract::<LogRecord>()?;
+        // A sync_level record is sent on this thread; don't hold the GIL over the request.
+        py.detach(|| self.inner.emit(&Arc::new(rust_record)));
+        Ok(())
+    }
+
+    /// Run `filters` in Rust and return True when they are all Rust filters
+    /// (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+    /// otherwise return False and leave them to the wrapper's `handle()`.
+    #[pyo3(name = "setFilters")]
+    fn set_filters(&self, filters: Vec<Bound<PyAny>>) -> bool {
+        self.inner.filters().is_some_and(|slot| slot.set(&filters))
+    }
+
+    /// Route records through the wrapper's `handle()` (it has filters) or not.
+    #[pyo3(name = "setPythonDispatch", signature = (enabled=true))]
+    fn set_python_dispatch(&self, enabled: bool) {
+        self.inner.set_dispatch_mode(if enabled {
+            DispatchMode::Python
+        } else {
+            DispatchMode::Native
+        });
+    }
+
+    #[pyo3(name = "isNative")]
+    fn is_native(&self) -> bool {
+        self.inner.dispatch_mode() == DispatchMode::Native
+    }
+}
+
+#[cfg(feature = "network")]
+#[pyclass(name = "DatagramHandler", subclass)]
+pub struct PyDatagramHandler {
+    pub(crate) inner: Arc<DatagramHandler>,
+}
+
+#[cfg(feature = "network")]
+#[pymethods]
+impl PyDatagramHandler {
+    /// `port=None` makes `host` the path of a Unix datagram socket, as in the stdlib.
+    /// A payload over `max_packet_size` bytes is dropped, or with `truncate` sent
+    /// with its message shortened to fit.
+    #[new]
+    #[pyo3(signature = (
+        host,
+        port=None,
+        format="pickle",
+        max_packet_size=DATAGRAM_MAX_PACKET_SIZE,
+        truncate=false,
+        error_callback=None
+    ))]
+    fn new(
+        host: String,
+        port: Option<u16>,
+        format: &str,
+        max_packet_size: usize,
+        truncate: bool,
+        error_callback: Option<Py<PyAny>>,
+    ) -> PyResult<Self> {
+        crate::platform::require_network("DatagramHandler")?;
+        let format = DatagramFormat::from_format_str(format).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "format must be 'pickle', 'json' or 'text', got {format:?}"
+            ))
+        })?;
+        if max_packet_size == 0 {
+            return Err(PyValueError::new_err("max_packet_size must be positive"));
+        }
+        let target = match port {
+            Some(port) => {
+                use std::net::ToSocketAddrs;
+                let addr = (host.as_str(), port)
+                    .to_socket_addrs()?
+                    .next()
+                    .ok_or_else(|| {
+                        PyValueError::new_err(format!("{host}:{port} did not resolve"))
+                    })?;
+                DatagramTarget::Udp(addr)
+            }
+            #[cfg(unix)]
+            None => DatagramTarget::Unix(PathBuf::from(host)),
+            #[cfg(not(unix))]
+            None => {
+                return Err(PyValueError::new_err(
+                    "Unix datagram sockets are not supported on this platform; pass a port",
+                ));
+            }
+        };
+        Ok(Self {
+            inner: Arc::new(DatagramHandler::new(
+                target,
+                format,
+                max_packet_size,
+                truncate,
+                error_callback,
+            )?),
+        })
+    }
+
+    fn setLevel(&self, py: Python, level: &Bound<PyAny>) -> PyResult<()> {
+        let level_int = check_level(py, level)?;
+        self.inner
+            .set_level(LogLevel::from_usize(level_int as usize));
+        Ok(())
+    }
+
+    fn flush(&self) -> PyResult<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> PyResult<()> {
+        Ok(())
+    }
+
+    #[pyo3(name = "get_metrics")]
+    fn get_metrics<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let (emitted, sink_acknowledged, delivery_failed, truncated) =
+            self.inner.metrics_snapshot();
+        let dict = PyDict::new(py);
+        dict.set_item("emitted", emitted)?;
+        dict.set_item("sink_acknowledged", sink_acknowledged)?;
+        dict.set_item("delivery_failed", delivery_failed)?;
+        dict.set_item("truncated", truncated)?;
+        Ok(dict)
+    }
+
+    fn emit(&self, py: Python, record: &Bound<PyAny>) -> PyResult<()> {
+        let rust_record = record.extract::<LogRecord>()?;
+        py.detach(|| self.inner.emit(&Arc::new(rust_record)));
+        Ok(())
+    }
+
+    /// Formatter for `format="text"` payloads; the other formats ignore it.
+    #[pyo3(name = "setFormatterSpec", signature = (fmt=None, datefmt=None, style="%", defaults=None))]
+    fn set_formatter_spec(
+        &self,
+        fmt: Option<String>,
+        datefmt: Option<String>,
+        style: &str,
+        defaults: Option<&Bound<PyDict>>,
+    ) -> PyResult<()> {
+        match fmt {
+            Some(f) => {
+                let formatter = styled_formatter(f, datefmt, style, defaults)?;
+                self.inner.set_formatter_instance(Arc::new(formatter));
+            }
+            None => self.inner.set_formatter_instance(Arc::new(NoOpFormatter)),
+        }
+        self.inner.set_dispatch_mode(DispatchMode::Native);
+        Ok(())
+    }
+
+    /// Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+    /// text; None turns sanitization off. `newlines=False` also treats newlines and
+    /// tabs as control characters, keeping each record on one line.
+    #[pyo3(name = "setSanitize", signature = (policy=None, newlines=true))]
+    fn set_sanitize(&self, policy: Option<&str>, newlines: bool) -> PyResult<()> {
+        self.inner.set_sanitize(sanitize_policy(policy, newlines)?);
+        Ok(())
+    }
+
+    /// Run `filters` in Rust and return True when they are all Rust filters
+    /// (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+    /// otherwise return False and leave them to the wrapper's `handle()`.
+    #[pyo3(name = "setFilters")]
+    fn set_filters(&self, filters: Vec<Bound<PyAny>>) -> bool {
+        self.inner.filters().is_some_and(|slot| slot.set(&filters))
+    }
+
+    #[pyo3(name = "setPythonDispatch")]
+    fn set_python_dispatch(&self) -> PyResult<()> {
+        self.inner.set_formatter_instance(Arc::new(NoOpFormatter));
+        self.inner.set_dispatch_mode(DispatchMode::Python);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "network")]
+#[pyclass(name = "SocketHandler", subclass)]
+pub struct PySocketHandler {
+    pub(crate) inner: Arc<SocketHandler>,
+}
+
+#[cfg(feature = "network")]
+#[pymethods]
+impl PySocketHandler {
+    /// `port=None` makes `host` the path of a Unix stream socket, as in the stdlib. The
+    /// connection is opened by the first record, so the receiver need not be up yet.
+    #[new]
+    #[pyo3(signature = (
+        host,
+        port=None,
+        retry_start=1.0,
+        retry_factor=2.0,
+        retry_max=30.0,
+        error_callback=None
+    ))]
+    fn new(
+        host: String,
+        port: Option<u16>,
+        retry_start: f64,
+        retry_factor: f64,
+        retry_max: f64,
+        error_callback: Option<Py<PyAny>>,
+    ) -> PyResult<Self> {
+        crate::platform::require_network("SocketHandler")?;
+        if !(retry_start.is_finite() && retry_start > 0.0) {
+            return Err(PyValueError::new_err(
+                "retry_start must be a positive number of seconds",
+            ));
+        }
+        if !(retry_factor.is_finite() && retry_factor >= 1.0) {
+            return Err(PyValueError::new_err("retry_factor must be at least 1"));
+        }
+        if !(retry_max.is_finite() && retry_max >= retry_start) {
+            return Err(PyValueError::new_err(
+                "retry_max must be a number of seconds no smaller than retry_start",
+            ));
+        }
+        let target = match port {
+            Some(port) => {
+                use std::net::ToSocketAddrs;
+                let addr = (host.as_str(), port)
+                    .to_socket_addrs()?
+                    .next()
+                    .ok_or_else(|| {
+                        PyValueError::new_err(format!("{host}:{port} did not resolve"))
+                    })?;
+                SocketTarget::Tcp(addr)
+            }
+            #[cfg(unix)]
+            None => SocketTarget::Unix(PathBuf::from(host)),
+            #[cfg(not(unix))]
+            None => {
+                return Err(PyValueError::new_err(
+                    "Unix stream sockets are not supported on this platform; pass a port",
+                ));
+            }
+        };
+        let backoff = SocketBackoff {
+            start: Duration::from_secs_f64(retry_start),
+            factor: retry_factor,
+            max: Duration::from_secs_f64(retry_max),
+        };
+        Ok(Self {
+            inner: Arc::new(SocketHandler::new(target, backoff, error_callback)),
+        })
+    }
+
+    fn setLevel(&self, py: Python, level: &Bound<PyAny>) -> PyResult<()> {
+        let level_int = check_level(py, level)?;
+        self.inner
+            .set_level(LogLevel::from_usize(level_int as usize));
+        Ok(())
+    }
+
+    fn flush(&self) -> PyResult<()> {
+        Ok(())
+    }
+
+    /// Close the connection. A later record opens a new one.
+    fn close(&self) -> PyResult<()> {
+        self.inner.close();
+        Ok(())
+    }
+
+    fn shutdown(&self) -> PyResult<()> {
+        self.inner.close();
+        Ok(())
+    }
+
+    #[pyo3(name = "get_metrics")]
+    fn get_metrics<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let (emitted, sink_acknowledged, delivery_failed, reconnects) =
+            self.inner.metrics_snapshot();
+        let dict = PyDict::new(py);
+        dict.set_item("emitted", emitted)?;
+        dict.set_item("sink_acknowledged", sink_acknowledged)?;
+        dict.set_item("delivery_failed", delivery_failed)?;
+        dict.set_item("reconnects", reconnects)?;
+        Ok(dict)
+    }
+
+    fn emit(&self, py: Python, record: &Bound<PyAny>) -> PyResult<()> {
+        let rust_record = record.extract::<LogRecord>()?;
+        py.detach(|| self.inner.emit(&Arc::new(rust_record)));
+        Ok(())
+    }
+
+    /// Run `filters` in Rust and return True when they are all Rust filters
+    /// (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+    /// otherwise return False and leave them to the wrapper's `handle()`.
+    #[pyo3(name = "setFilters")]
+    fn set_filters(&self, filters: Vec<Bound<PyAny>>) -> bool {
+        self.inner.filters().is_some_and(|slot| slot.set(&filters))
+    }
+
+    /// Route records through the wrapper's `handle()` (it has filters) or not.
+    #[pyo3(name = "setPythonDispatch", signature = (enabled=true))]
+    fn set_python_dispatch(&self, enabled: bool) {
+        self.inner.set_dispatch_mode(if enabled {
+            DispatchMode::Python
+        } else {
+            DispatchMode::Native
+        });
+    }
+
+    #[pyo3(name = "isNative")]
+    fn is_native(&self) -> bool {
+        self.inner.dispatch_mode() == DispatchMode::Native
+    }
+}
+
+#[cfg(feature = "network")]
+#[pyclass(name = "SyslogHandler", subclass)]
+pub struct PySyslogHandler {
+    pub(crate) inner: Arc<SyslogHandler>,
+}
+
+#[cfg(feature = "network")]
+#[pymethods]
+impl PySyslogHandler {
+    /// `port=None` makes `host` the path of a local syslog socket such as `/dev/log`.
+    /// `socktype` is "dgram" (UDP) or "stream" (TCP); for a local socket None tries a
+    /// datagram socket, then a stream one. `app_name` defaults to the script name and
+    /// `hostname` to `socket.gethostname()`.
+    #[new]
+    #[pyo3(signature = (
+        host="/dev/log".to_string(),
+        port=None,
+        socktype=None,
+        protocol="rfc3164",
+        facility=1,
+        app_name=None,
+        hostname=None,
+        error_callback=None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        py: Python,
+        host: String,
+        port: Option<u16>,
+        socktype: Option<&str>,
+        protocol: &str,
+        facility: u8,
+        app_name: Option<String>,
+        hostname: Option<String>,
+        error_callback: Option<Py<PyAny>>,
+    ) -> PyResult<Self> {
+        crate::platform::require_network("SyslogHandler")?;
+        let protocol = SyslogProtocol::from_protocol_str(protocol).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "protocol must be 'rfc3164' or 'rfc5424', got {protocol:?}"
+            ))
+        })?;
+        if facility > 23 {
+            return Err(PyValueError::new_err(format!(
+                "facility must be a syslog facility code from 0 to 23, got {facility}"
+            )));
+        }
+        let stream = match socktype {
+            None | Some("dgram") => false,
+            Some("stream") => true,
+            Some(other) => {
+                return Err(PyValueError::new_err(format!(
+                    "socktype must be 'dgram', 'stream' or None, got {other:?}"
+                )))
+            }
+        };
+        let target = match port {
+            Some(port) => {
+                use std::net::ToSocketAddrs;
+                let addr = (host.as_str(), port)
+                    .to_socket_addrs()?
+                    .next()
+                    .ok_or_else(|| {
+                        PyValueError::new_err(format!("{host}:{port} did not resolve"))
+                    })?;
+                if stream {
+                    SyslogTarget::Tcp(addr)
+                } else {
+                    SyslogTarget::Udp(addr)
+                }
+            }
+            #[cfg(unix)]
+            None => SyslogTarget::Unix {
+                path: PathBuf::from(host),
+                stream: socktype == Some("stream"),
+            },
+            #[cfg(not(unix))]
+            None => {
+                return Err(PyValueError::new_err(
+                    "local syslog sockets are not supported on this platform; pass a port",
+                ));
+            }
+        };
+        let hostname = match hostname {
+            Some(hostname) => hostname,
+            None => py
+                .import("socket")?
+                .call_method0("gethostname")?
+                .extract()?,
+        };
+        let app_name = match app_name {
+            Some(app_name) => app_name,
+            None => {
+                let argv0: String = py
+                    .import("sys")?
+                    .getattr("argv")?
+                    .get_item(0)
+                    .and_then(|arg| arg.extract())
+                    .unwrap_or_default();
+                match std::path::Path::new(&argv0)
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                {
+                    Some(name) if !name.is_empty() => name.to_string(),
+                    _ => "python".to_string(),
+                }
+            }
+        };
+        let handler = SyslogHandler::new(
+            target,
+            protocol,
+            facility,
+            &hostname,
+            &app_name,
+            error_callback,
+        )?;
+        Ok(Self {
+            inner: Arc::new(handler),
+        })
+    }
+
+    fn setLevel(&self, py: Python, level: &Bound<PyAny>) -> PyResult<()> {
+        let level_int = check_level(py, level)?;
+        self.inner
+            .set_level(LogLevel::from_usize(level_int as usize));
+        Ok(())
+    }
+
+    fn flush(&self) -> PyResult<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> PyResult<()> {
+        Ok(())
+    }
+
+    #[pyo3(name = "get_metrics")]
+    fn get_metrics<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let (emitted, sink_acknowledged, delivery_failed) = self.inner.metrics_snapshot();
+        let dict = PyDict::new(py);
+        dict.set_item("emitted", emitted)?;
+        dict.set_item("sink_acknowledged", sink_acknowledged)?;
+        dict.set_item("delivery_failed", delivery_failed)?;
+        Ok(dict)
+    }
+
+    fn emit(&self, py: Python, record: &Bound<PyAny>) -> PyResult<()> {
+        let rust_record = record.extract::<LogRecord>()?;
+        py.detach(|| self.inner.emit(&Arc::new(rust_record)));
+        Ok(())
+    }
+
+    #[pyo3(name = "setFormatterSpec", signature = (fmt=None, datefmt=None, style="%", defaults=None))]
+    fn set_formatter_spec(
+        &self,
+        fmt: Option<String>,
+        datefmt: Option<String>,
+        style: &str,
+        defaults: Option<&Bound<PyDict>>,
+    ) -> PyResult<()> {
+        match fmt {
+            Some(f) => {
+                let formatter = styled_formatter(f, datefmt, style, defaults)?;
+                self.inner.set_formatter_instance(Arc::new(formatter));
+            }
+            None => self.inner.set_formatter_instance(Arc::new(NoOpFormatter)),
+        }
+        self.inner.set_dispatch_mode(DispatchMode::Native);
+        Ok(())
+    }
+
+    /// Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+    /// text; None turns sanitization off. `newlines=False` also treats newlines and
+    /// tabs as control characters, keeping each record on one line.
+    #[pyo3(name = "setSanitize", signature = (policy=None, newlines=true))]
+    fn set_sanitize(&self, policy: Option<&str>, newlines: bool) -> PyResult<()> {
+        self.inner.set_sanitize(sanitize_policy(policy, newlines)?);
+        Ok(())
+    }
+
+    /// Run `filters` in Rust and return True when they are all Rust filters
+    /// (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+    /// otherwise return False and leave them to the wrapper's `handle()`.
+    #[pyo3(name = "setFilters")]
+    fn set_filters(&self, filters: Vec<Bound<PyAny>>) -> bool {
+        self.inner.filters().is_some_and(|slot| slot.set(&filters))
+    }
+
+    #[pyo3(name = "setPythonDispatch")]
+    fn set_python_dispatch(&self) -> PyResult<()> {
+        self.inner.set_formatter_instance(Arc::new(NoOpFormatter));
+        self.inner.set_dispatch_mode(DispatchMode::Python);
+        Ok(())
+    }
+
+    #[pyo3(name = "isNative")]
+    fn is_native(&self) -> PyResult<bool> {
+        Ok(self.inner.dispatch_mode() == DispatchMode::Native)
+    }
+
+    /// The message that would be sent for `record` with `body` as its text.
+    #[pyo3(name = "buildMessage")]
+    fn build_message(&self, record: &LogRecord, body: &str) -> String {
+        self.inner.build_message(record, body)
+    }
+}
+
+#[cfg(feature = "smtp")]
+#[pyclass(name = "SMTPHandler", subclass)]
+pub struct PySMTPHandler {
+    pub(crate) inner: Arc<SMTPHandler>,
+}
+
+#[cfg(feature = "smtp")]
+#[pymethods]
+impl PySMTPHandler {
+    /// `security` is "none", "starttls" or "tls" (implicit TLS). `port` defaults to
+    /// 465 for "tls" and 25 otherwise; `local_hostname` (the EHLO name) to
+    /// `socket.getfqdn()`. `credentials` is a (username, password) pair.
+    #[new]
+    #[pyo3(signature = (
+        host,
+        fromaddr,
+        toaddrs,
+        subject,
+        port=None,
+        security="none",
+        credentials=None,
+        timeout=5.0,
+        local_hostname=None,
+        error_callback=None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        py: Python,
+        host: String,
+        fromaddr: String,
+        toaddrs: Vec<String>,
+        subject: String,
+        port: Option<u16>,
+        security: &str,
+        credentials: Option<(String, String)>,
+        timeout: f64,
+        local_hostname: Option<String>,
+        error_callback: Option<Py<PyAny>>,
+    ) -> PyResult<Self> {
+        crate::platform::require_network("SMTPHandler")?;
+        let security = SmtpSecurity::from_security_str(security).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "security must be 'none', 'starttls' or 'tls', got {security:?}"
+            ))
+        })?;
+        if toaddrs.is_empty() {
+            return Err(PyValueError::new_err(
+                "toaddrs must name at least one recipient",
+            ));
+        }
+        if !(timeout.is_finite() && timeout > 0.0) {
+            return Err(PyValueError::new_err(
+                "timeout must be a positive number of seconds",
+            ));
+        }
+        let local_hostname = match local_hostname {
+            Some(name) => name,
+            None => py.import("socket")?.call_method0("getfqdn")?.extract()?,
+        };
+        let config = SmtpConfig {
+            host,
+            port: port.unwrap_or(if security == SmtpSecurity::Tls {
+                465
+            } else {
+                25
+            }),
+            security,
+            credentials,
+            timeout: Duration::from_secs_f64(timeout),
+            local_hostname,
+        };
+        Ok(Self {
+            inner: Arc::new(SMTPHandler::new(
+                config,
+                fromaddr,
+                toaddrs,
+                subject,
+                error_callback,
+            )),
+        })
+    }
+
+    fn setLevel(&self, py: Python, level: &Bound<PyAny>) -> PyResult<()> {
+        let level_int = check_level(py, level)?;
+        self.inner
+            .set_level(LogLevel::from_usize(level_int as usize));
+        Ok(())
+    }
+
+    fn flush(&self) -> PyResult<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> PyResult<()> {
+        Ok(())
+    }
+
+    #[pyo3(name = "get_metrics")]
+    fn get_metrics<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let (emitted, sink_acknowledged, delivery_failed) = self.inner.metrics_snapshot();
+        let dict = PyDict::new(py);
+        dict.set_item("emitted", emitted)?;
         dict.set_item("sink_acknowledged", sink_acknowledged)?;
-        dict.set_item("queue_dropped", queue_dropped)?;
         dict.set_item("delivery_failed", delivery_failed)?;
-        dict.set_item("in_flight", in_flight)?;
         Ok(dict)
     }
 
-    /// Set the flush level. Records at or above this level trigger immediate flush.
-    /// Default is ERROR (40). Use logging.CRITICAL (50) to flush only on critical.
-    /// Use logging.DEBUG (10) to flush on every record.
-    #[pyo3(name = "setFlushLevel")]
-    fn set_flush_level(&self, level: u32) -> PyResult<()> {
-        self.inner
-            .set_flush_level(LogLevel::from_usize(level as usize));
+    /// The subject template rendered for `record`.
+    #[pyo3(name = "getSubject")]
+    fn get_subject(&self, record: &Bound<PyAny>) -> PyResult<String> {
+        let rust_record = record.extract::<LogRecord>()?;
+        Ok(self.inner.subject(&rust_record))
+    }
+
+    /// Mail `record`. `subject` overrides the rendered subject template.
+    #[pyo3(signature = (record, subject=None))]
+    fn emit(&self, py: Python, record: &Bound<PyAny>, subject: Option<String>) -> PyResult<()> {
+        let rust_record = record.extract::<LogRecord>()?;
+        let subject = subject.unwrap_or_else(|| self.inner.subject(&rust_record));
+        // The SMTP session runs on this thread; don't hold the GIL over it.
+        py.detach(|| self.inner.deliver(&rust_record, &subject));
         Ok(())
     }
 
-    /// Get the current flush level.
-    #[pyo3(name = "getFlushLevel")]
-    fn get_flush_level(&self) -> PyResult<u32> {
-        Ok(self.inner.get_flush_level() as u32)
+    #[pyo3(name = "setFormatterSpec", signature = (fmt=None, datefmt=None, style="%", defaults=None))]
+    fn set_formatter_spec(
+        &self,
+        fmt: Option<String>,
+        datefmt: Option<String>,
+        style: &str,
+        defaults: Option<&Bound<PyDict>>,
+    ) -> PyResult<()> {
+        match fmt {
+            Some(f) => {
+                let formatter = styled_formatter(f, datefmt, style, defaults)?;
+                self.inner.set_formatter_instance(Arc::new(formatter));
+            }
+            None => self.inner.set_formatter_instance(Arc::new(NoOpFormatter)),
+        }
+        self.inner.set_dispatch_mode(DispatchMode::Native);
+        Ok(())
     }
 
-    fn emit(&self, _py: Python, record: &Bound<PyAny>) -> PyResult<()> {
-        let rust_record = record.extract::<LogRecord>()?;
-        self.inner.emit(&rust_record);
+    /// Escape (`"escape"`) or strip (`"strip"`) control characters in the formatted
+    /// text; None turns sanitization off. `newlines=False` also treats newlines and
+    /// tabs as control characters, keeping each record on one line.
+    #[pyo3(name = "setSanitize", signature = (policy=None, newlines=true))]
+    fn set_sanitize(&self, policy: Option<&str>, newlines: bool) -> PyResult<()> {
+        self.inner.set_sanitize(sanitize_policy(policy, newlines)?);
         Ok(())
     }
-}
 
-#[pyclass(name = "OTLPHandler", subclass)]
-pub struct PyOTLPHandler {
-    pub(crate) inner: Arc<OTLPHandler>,
+    /// Run `filters` in Rust and return True when they are all Rust filters
+    /// (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+    /// otherwise return False and leave them to the wrapper's `handle()`.
+    #[pyo3(name = "setFilters")]
+    fn set_filters(&self, filters: Vec<Bound<PyAny>>) -> bool {
+        self.inner.filters().is_some_and(|slot| slot.set(&filters))
+    }
+
+    #[pyo3(name = "setPythonDispatch")]
+    fn set_python_dispatch(&self) -> PyResult<()> {
+        self.inner.set_formatter_instance(Arc::new(NoOpFormatter));
+        self.inner.set_dispatch_mode(DispatchMode::Python);
+        Ok(())
+    }
 }
 
-impl Drop for PyOTLPHandler {
-    fn drop(&mut self) {}
+#[cfg(feature = "pipe")]
+#[pyclass(name = "PipeHandler", subclass)]
+pub struct PyPipeHandler {
+    pub(crate) inner: Arc<PipeHandler>,
 }
 
+#[cfg(feature = "pipe")]
 #[pymethods]
-impl PyOTLPHandler {
+impl PyPipeHandler {
     #[new]
     #[pyo3(signature = (
-        url,
-        headers=None,
-        service_name="unknown_service".to_string(),
-        capacity=10000,
-        batch_size=1000,
-        flush_interval=30,
+        command=None,
+        path=None,
+        capacity=None,
+        overflow=None,
+        block_timeout=None,
+        restart=true,
+        restart_delay=1.0,
         error_callback=None,
-        overflow="block"
+        shutdown_timeout=Some(30.0),
+        spill_path=None,
+        daemon=None
     ))]
     #[allow(clippy::too_many_arguments)]
     fn new(
-        py: Python,
-        url: String,
-        headers: Option<HashMap<String, String>>,
-        service_name: String,
-        capacity: usize,
-        batch_size: usize,
-        flush_interval: u64,
+        command: Option<Vec<String>>,
+        path: Option<PathBuf>,
+        capacity: Option<usize>,
+        overflow: Option<&str>,
+        block_timeout: Option<f64>,
+        restart: bool,
+        restart_delay: f64,
         error_callback: Option<Py<PyAny>>,
-        overflow: &str,
+        shutdown_timeout: Option<f64>,
+        spill_path: Option<PathBuf>,
+        daemon: Option<bool>,
     ) -> PyResult<Self> {
-        let h_map = headers.unwrap_or_default();
-
-        let config = OTLPHandlerConfig {
-            url,
-            headers: h_map,
-            service_name,
-            error_callback: error_callback.map(|cb| cb.clone_ref(py)),
-            overflow: OverflowStrategy::from_overflow_str(overflow),
+        crate::platform::require_subprocess("PipeHandler")?;
+        let target = match (command, path) {
+            (Some(argv), None) if !argv.is_empty() => PipeTarget::Command(argv),
+            (Some(_), None) => {
+                return Err(PyValueError::new_err("command must not be empty"));
+            }
+            (None, Some(path)) => PipeTarget::Path(path),
+            _ => {
+                return Err(PyValueError::new_err(
+                    "PipeHandler takes exactly one of command or path",
+                ));
+            }
         };
+        if !restart_delay.is_finite() || restart_delay < 0.0 {
+            return Err(PyValueError::new_err(
+                "restart_delay must be a non-negative number of seconds",
+            ));
+        }
 
-        let h = OTLPHandler::with_config(config, capacity, batch_size, flush_interval);
-        Ok(Self { inner: Arc::new(h) })
+        let config = PipeHandlerConfig {
+            target,
+            overflow: parse_overflow(overflow, "block", block_timeout)?,
+            restart,
+            restart_delay: Duration::from_secs_f64(restart_delay),
+            error_callback,
+            shutdown_timeout: parse_shutdown_timeout(shutdown_timeout)?,
+            spill_path,
+            daemon: crate::worker::resolve_daemon(daemon),
+        };
+        Ok(Self {
+            inner: Arc::new(PipeHandler::with_config(
+                config,
+                capacity.unwrap_or(crate::preset::current().capacity),
+            )),
+        })
     }
 
     fn setLevel(&self, py: Python, level: &Bound<PyAny>) -> PyResult<()> {
@@ -562,28 +2643,65 @@ impl PyOTLPHandler {
         Ok(())
     }
 
+    /// Whether the worker is a daemon (abandoned at interpreter exit instead of drained).
+    #[getter]
+    fn daemon(&self) -> bool {
+        self.inner.is_daemon()
+    }
+
     #[pyo3(name = "get_metrics")]
     fn get_metrics<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
         let (emitted, sink_acknowledged, queue_dropped, delivery_failed) =
             self.inner.metrics_snapshot();
+        let (shutdown_dropped, shutdown_spilled) = self.inner.shutdown_snapshot();
         let in_flight = emitted
             .saturating_sub(sink_acknowledged)
             .saturating_sub(queue_dropped)
-            .saturating_sub(delivery_failed);
+            .saturating_sub(delivery_failed)
+            .saturating_sub(shutdown_dropped)
+            .saturating_sub(shutdown_spilled);
         let dict = PyDict::new(py);
         dict.set_item("emitted", emitted)?;
         dict.set_item("sink_acknowledged", sink_acknowledged)?;
         dict.set_item("queue_dropped", queue_dropped)?;
         dict.set_item("delivery_failed", delivery_failed)?;
+        dict.set_item("shutdown_dropped", shutdown_dropped)?;
+        dict.set_item("shutdown_spilled", shutdown_spilled)?;
         dict.set_item("in_flight", in_flight)?;
+        dict.set_item("restarts", self.inner.restarts())?;
+        self.inner.queue_stats().fill_metrics(&dict)?;
         Ok(dict)
     }
 
-    fn emit(&self, _py: Python, record: &Bound<PyAny>) -> PyResult<()> {
+    fn emit(&self, py: Python, record: &Bound<PyAny>) -> PyResult<()> {
         let rust_record = record.extract::<LogRecord>()?;
-        self.inner.emit(&rust_record);
+        // A Block enqueue may wait on a full queue; let the worker's callbacks run.
+        py.detach(|| self.inner.emit(&Arc::new(rust_record)));
         Ok(())
     }
+
+    /// Run `filters` in Rust and return True when they are all Rust filters
+    /// (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+    /// otherwise return False and leave them to the wrapper's `handle()`.
+    #[pyo3(name = "setFilters")]
+    fn set_filters(&self, filters: Vec<Bound<PyAny>>) -> bool {
+        self.inner.filters().is_some_and(|slot| slot.set(&filters))
+    }
+
+    /// Route records through the wrapper's `handle()` (it has filters) or not.
+    #[pyo3(name = "setPythonDispatch", signature = (enabled=true))]
+    fn set_python_dispatch(&self, enabled: bool) {
+        self.inner.set_dispatch_mode(if enabled {
+            DispatchMode::Python
+        } else {
+            DispatchMode::Native
+        });
+    }
+
+    #[pyo3(name = "isNative")]
+    fn is_native(&self) -> bool {
+        self.inner.dispatch_mode() == DispatchMode::Native
+    }
 }
 
 #[pyclass(name = "MemoryHandler", subclass)]
@@ -613,13 +2731,17 @@ impl PyMemoryHandler {
     /// Returns all captured log records.
     #[pyo3(name = "getRecords")]
     pub fn get_records(&self) -> Vec<LogRecord> {
-        self.inner.get_records()
+        self.inner
+            .get_records()
+            .iter()
+            .map(|r| (**r).clone())
+            .collect()
     }
 
     /// Alias for getRecords() - Python naming convention.
     #[getter]
     pub fn records(&self) -> Vec<LogRecord> {
-        self.inner.get_records()
+        self.get_records()
     }
 
     /// Returns all captured messages as a single newline-separated string.
@@ -659,7 +2781,280 @@ impl PyMemoryHandler {
 
     pub fn emit(&self, _py: Python, record: &Bound<PyAny>) -> PyResult<()> {
         let rust_record = record.extract::<LogRecord>()?;
-        self.inner.emit(&rust_record);
+        self.inner.emit(&Arc::new(rust_record));
+        Ok(())
+    }
+
+    /// Run `filters` in Rust and return True when they are all Rust filters
+    /// (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+    /// otherwise return False and leave them to the wrapper's `handle()`.
+    #[pyo3(name = "setFilters")]
+    fn set_filters(&self, filters: Vec<Bound<PyAny>>) -> bool {
+        self.inner.filters().is_some_and(|slot| slot.set(&filters))
+    }
+
+    /// Route records through the wrapper's `handle()` (it has filters) or not.
+    #[pyo3(name = "setPythonDispatch", signature = (enabled=true))]
+    fn set_python_dispatch(&self, enabled: bool) {
+        self.inner.set_dispatch_mode(if enabled {
+            DispatchMode::Python
+        } else {
+            DispatchMode::Native
+        });
+    }
+
+    #[pyo3(name = "isNative")]
+    fn is_native(&self) -> bool {
+        self.inner.dispatch_mode() == DispatchMode::Native
+    }
+}
+
+#[pyclass(name = "QueueHandler", subclass)]
+pub struct PyQueueHandler {
+    pub(crate) inner: Arc<QueueHandler>,
+}
+
+#[pymethods]
+impl PyQueueHandler {
+    /// `capacity` bounds each of the queue's two lanes (ERROR and above, the rest).
+    /// `overflow` is "block", "drop_newest" or "drop_oldest"; `block_timeout` is how
+    /// many seconds "block" waits for room before dropping (None = no limit).
+    #[new]
+    #[pyo3(signature = (capacity=None, overflow=None, block_timeout=None))]
+    fn new(
+        capacity: Option<usize>,
+        overflow: Option<&str>,
+        block_timeout: Option<f64>,
+    ) -> PyResult<Self> {
+        let capacity = capacity.unwrap_or(crate::preset::current().capacity);
+        if capacity == 0 {
+            return Err(PyValueError::new_err("capacity must be at least 1"));
+        }
+        Ok(Self {
+            inner: Arc::new(QueueHandler::new(
+                capacity,
+                parse_overflow(overflow, "block", block_timeout)?,
+            )),
+        })
+    }
+
+    fn setLevel(&self, py: Python, level: &Bound<PyAny>) -> PyResult<()> {
+        let level_int = check_level(py, level)?;
+        self.inner
+            .set_level(LogLevel::from_usize(level_int as usize));
+        Ok(())
+    }
+
+    /// Wait until a running listener has delivered the queued records and flushed
+    /// its handlers.
+    fn flush(&self, py: Python) -> PyResult<()> {
+        py.detach(|| self.inner.flush());
+        Ok(())
+    }
+
+    fn shutdown(&self) -> PyResult<()> {
+        Ok(())
+    }
+
+    fn emit(&self, py: Python, record: &Bound<PyAny>) -> PyResult<()> {
+        let rust_record = record.extract::<LogRecord>()?;
+        let _guard = crate::handler::BlockWaitGuard::enter();
+        py.detach(|| self.inner.emit(&Arc::new(rust_record)));
+        Ok(())
+    }
+
+    /// Run `filters` in Rust and return True when they are all Rust filters
+    /// (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+    /// otherwise return False and leave them to the wrapper's `handle()`.
+    #[pyo3(name = "setFilters")]
+    fn set_filters(&self, filters: Vec<Bound<PyAny>>) -> bool {
+        self.inner.filters().is_some_and(|slot| slot.set(&filters))
+    }
+
+    /// Route records through the wrapper's `handle()` (it has filters) or not.
+    #[pyo3(name = "setPythonDispatch", signature = (enabled=true))]
+    fn set_python_dispatch(&self, enabled: bool) {
+        self.inner.set_dispatch_mode(if enabled {
+            DispatchMode::Python
+        } else {
+            DispatchMode::Native
+        });
+    }
+
+    #[pyo3(name = "isNative")]
+    fn is_native(&self) -> bool {
+        self.inner.dispatch_mode() == DispatchMode::Native
+    }
+
+    #[pyo3(name = "get_metrics")]
+    fn get_metrics<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("emitted", self.inner.emitted())?;
+        self.inner.queue_stats().fill_metrics(&dict)?;
+        Ok(dict)
+    }
+}
+
+#[pyclass(name = "QueueListener", subclass)]
+pub struct PyQueueListener {
+    inner: QueueListener,
+    handlers: Py<PyTuple>,
+}
+
+#[pymethods]
+impl PyQueueListener {
+    /// Deliver the records of `queue` (a QueueHandler) to `handlers` on a background
+    /// thread. With `respect_handler_level`, a Python handler only gets records at or
+    /// above its level; Rust handlers always apply their own level.
+    #[new]
+    #[pyo3(signature = (queue, *handlers, respect_handler_level=false))]
+    fn new(
+        queue: &Bound<PyAny>,
+        handlers: &Bound<PyTuple>,
+        respect_handler_level: bool,
+    ) -> PyResult<Self> {
+        crate::platform::require_threads("QueueListener")?;
+        let queue_handler = match queue.extract::<PyRef<PyQueueHandler>>() {
+            Ok(h) => h.inner.clone(),
+            Err(_) => queue
+                .getattr("_inner")
+                .ok()
+                .and_then(|inner| {
+                    inner
+                        .extract::<PyRef<PyQueueHandler>>()
+                        .ok()
+                        .map(|h| h.inner.clone())
+                })
+                .ok_or_else(|| PyTypeError::new_err("queue must be a logxide QueueHandler"))?,
+        };
+        let sinks = handlers
+            .iter()
+            .map(|handler| crate::globals::listener_sink(&handler))
+            .collect();
+        Ok(Self {
+            inner: QueueListener::new(queue_handler.queue(), sinks, respect_handler_level),
+            handlers: handlers.clone().unbind(),
+        })
+    }
+
+    #[getter]
+    fn handlers(&self, py: Python) -> Py<PyTuple> {
+        self.handlers.clone_ref(py)
+    }
+
+    #[getter]
+    fn respect_handler_level(&self) -> bool {
+        self.inner.respect_handler_level()
+    }
+
+    /// Start the background thread.
+    fn start(&self) -> PyResult<()> {
+        if !self.inner.start() {
+            return Err(pyo3::exceptions::PyRuntimeError::new_err(
+                "Listener already started",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Deliver the records still queued, then stop the thread and wait for it.
+    fn stop(&self, py: Python) -> PyResult<()> {
+        py.detach(|| self.inner.stop());
+        Ok(())
+    }
+
+    #[getter]
+    fn running(&self) -> bool {
+        self.inner.is_running()
+    }
+}
+
+#[pyclass(name = "AggregatingHandler", subclass)]
+pub struct PyAggregatingHandler {
+    pub(crate) inner: Arc<AggregatingHandler>,
+    handlers: Py<PyTuple>,
+}
+
+#[pymethods]
+impl PyAggregatingHandler {
+    /// Count records per (logger, level, message template) and hand `handlers` one
+    /// record per combination every `window` seconds. A Python handler only gets
+    /// records at or above its level; Rust handlers apply their own level.
+    #[new]
+    #[pyo3(signature = (*handlers, window=60.0))]
+    fn new(handlers: &Bound<PyTuple>, window: f64) -> PyResult<Self> {
+        crate::platform::require_threads("AggregatingHandler")?;
+        if !(window.is_finite() && window > 0.0) {
+            return Err(PyValueError::new_err("window must be a positive number"));
+        }
+        let sinks = handlers
+            .iter()
+            .map(|handler| crate::globals::listener_sink(&handler))
+            .collect();
+        let inner = AggregatingHandler::new(Duration::from_secs_f64(window), sinks)
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+        Ok(Self {
+            inner: Arc::new(inner),
+            handlers: handlers.clone().unbind(),
+        })
+    }
+
+    #[getter]
+    fn handlers(&self, py: Python) -> Py<PyTuple> {
+        self.handlers.clone_ref(py)
+    }
+
+    /// Window length in seconds.
+    #[getter]
+    fn window(&self) -> f64 {
+        self.inner.window().as_secs_f64()
+    }
+
+    fn setLevel(&self, py: Python, level: &Bound<PyAny>) -> PyResult<()> {
+        let level_int = check_level(py, level)?;
+        self.inner
+            .set_level(LogLevel::from_usize(level_int as usize));
+        Ok(())
+    }
+
+    /// Flush the target handlers. The open window keeps counting.
+    fn flush(&self, py: Python) -> PyResult<()> {
+        py.detach(|| self.inner.flush());
+        Ok(())
+    }
+
+    /// Stop the window timer and emit the open window now.
+    fn shutdown(&self, py: Python) -> PyResult<()> {
+        py.detach(|| self.inner.shutdown());
+        Ok(())
+    }
+
+    fn emit(&self, record: &Bound<PyAny>) -> PyResult<()> {
+        let rust_record = record.extract::<LogRecord>()?;
+        self.inner.emit(&Arc::new(rust_record));
         Ok(())
     }
+
+    /// Run `filters` in Rust and return True when they are all Rust filters
+    /// (`Filter`, `RegexFilter`, `LevelRangeFilter`, `Redactor`, `FieldProjection`);
+    /// otherwise return False and leave them to the wrapper's `handle()`.
+    #[pyo3(name = "setFilters")]
+    fn set_filters(&self, filters: Vec<Bound<PyAny>>) -> bool {
+        self.inner.filters().is_some_and(|slot| slot.set(&filters))
+    }
+
+    /// Route records through the wrapper's `handle()` (it has filters) or not.
+    #[pyo3(name = "setPythonDispatch", signature = (enabled=true))]
+    fn set_python_dispatch(&self, enabled: bool) {
+        self.inner.set_dispatch_mode(if enabled {
+            DispatchMode::Python
+        } else {
+            DispatchMode::Native
+        });
+    }
+
+    #[pyo3(name = "isNative")]
+    fn is_native(&self) -> bool {
+        self.inner.dispatch_mode() == DispatchMode::Native
+    }
 }
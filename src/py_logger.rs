@@ -3,7 +3,7 @@
 #![allow(non_snake_case)]
 
 use pyo3::prelude::*;
-use pyo3::types::{PyAny, PyDict, PyList, PyTuple};
+use pyo3::types::{PyAny, PyDict, PyList, PySet, PyString, PyTuple};
 use pyo3::IntoPyObjectExt;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -12,15 +12,15 @@ use std::sync::{Arc, Mutex};
 use crate::core::{create_log_record_with_extra, LogLevel, LogRecord, Logger};
 use crate::fast_logger::FastLogger;
 use crate::globals::{
-    add_handler_to_registry, remove_handler_from_registry, PyEntry, RustEntry, GLOBAL_PY_HANDLERS,
-    HANDLERS,
+    add_handler_to_registry, arc_id, remove_handler_from_registry, HandlerId, PyEntry, RustEntry,
+    GLOBAL_PY_HANDLERS, HANDLERS, HANDLER_OBJECTS, PY_LOGGER_KEEP_ALIVE,
 };
 use crate::handler::{DispatchMode, Handler};
 
 /// Check and resolve a log level from either an integer or a string name.
-/// Handles: int passthrough, string lookup (CRITICAL/FATAL/ERROR/WARN/WARNING/INFO/DEBUG/NOTSET).
+/// Handles: int passthrough, string lookup (the standard names and `addLevelName()` ones).
 /// Raises TypeError for unsupported types, ValueError for unknown level names.
-pub fn check_level(py: Python, level: &Bound<PyAny>) -> PyResult<u32> {
+pub fn check_level(_py: Python, level: &Bound<PyAny>) -> PyResult<u32> {
     // Try int first
     if let Ok(i) = level.extract::<u32>() {
         return Ok(i);
@@ -29,28 +29,11 @@ pub fn check_level(py: Python, level: &Bound<PyAny>) -> PyResult<u32> {
     if let Ok(i) = level.extract::<i64>() {
         return Ok(i as u32);
     }
-    // Try string
+    // Try string: a standard name or one registered with addLevelName()
     if let Ok(s) = level.extract::<String>() {
-        let upper = s.to_uppercase();
-        return match upper.as_str() {
-            "CRITICAL" | "FATAL" => Ok(50),
-            "ERROR" => Ok(40),
-            "WARN" | "WARNING" => Ok(30),
-            "INFO" => Ok(20),
-            "DEBUG" => Ok(10),
-            "NOTSET" => Ok(0),
-            _ => {
-                // Try Python-side _nameToLevel lookup for custom levels
-                let compat = py.import("logxide.compat_functions")?;
-                let name_to_level = compat.getattr("_nameToLevel")?;
-                match name_to_level.call_method1("get", (upper.as_str(),)) {
-                    Ok(val) if !val.is_none() => val.extract::<u32>(),
-                    _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
-                        "Unknown level: '{s}'"
-                    ))),
-                }
-            }
-        };
+        return crate::levels::level(&s).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!("Unknown level: '{s}'"))
+        });
     }
     Err(pyo3::exceptions::PyTypeError::new_err(format!(
         "Level not an integer or a valid string: {}",
@@ -58,15 +41,132 @@ pub fn check_level(py: Python, level: &Bound<PyAny>) -> PyResult<u32> {
     )))
 }
 
+/// Build a stdlib `logging.LogRecord`, as `Logger.makeRecord` does.
+#[allow(clippy::too_many_arguments)]
+fn std_log_record(
+    py: Python,
+    name: String,
+    level: i32,
+    pathname: String,
+    lineno: i32,
+    msg: Py<PyAny>,
+    args: Py<PyAny>,
+    exc_info: Option<Py<PyAny>>,
+) -> PyResult<Py<PyAny>> {
+    let logging = py.import("logging")?;
+    let log_record_cls = logging.getattr("LogRecord")?;
+
+    // Standard LogRecord constructor:
+    // name, level, pathname, lineno, msg, args, exc_info, func=None, sinfo=None
+    let args_tuple = (
+        name,
+        level,
+        pathname,
+        lineno,
+        msg,
+        args,
+        exc_info,
+        py.None(), // func
+        py.None(), // sinfo
+    );
+
+    let record = log_record_cls.call1(args_tuple)?;
+    Ok(record.unbind())
+}
+
+/// The stdlib record that Python handlers receive for `record`: the merged message,
+/// `exc_text`, `stack_info`, the logxide-only fields (`seq`, `ppid`,
+/// `thread_native_id`), extras as attributes and the function name.
+pub(crate) fn python_record(
+    py: Python,
+    record: &LogRecord,
+    exc_info: Option<Py<PyAny>>,
+) -> PyResult<Py<PyAny>> {
+    let py_record = std_log_record(
+        py,
+        record.name.clone(),
+        record.levelno,
+        record.pathname.clone(),
+        record.lineno as i32,
+        record.get_message().into_py_any(py)?,
+        py.None(),
+        exc_info,
+    )?;
+    let bound = py_record.bind(py);
+
+    if let Some(ref exc_text) = record.exc_text {
+        let _ = bound.setattr("exc_text", exc_text.as_str());
+    }
+    if let Some(ref stack_info) = record.stack_info {
+        let _ = bound.setattr("stack_info", stack_info.as_str());
+    }
+    let _ = bound.setattr("seq", record.seq);
+    let _ = bound.setattr("ppid", record.ppid);
+    let _ = bound.setattr("thread_native_id", record.thread_native_id);
+
+    // Extras become record attributes, as logging.Logger.makeRecord sets them.
+    if let Some(ref extra) = record.extra {
+        for (key, value) in extra {
+            if bound.hasattr(key.as_str()).unwrap_or(true) {
+                continue;
+            }
+            if let Ok(value) = crate::core::json_value_to_py_as_list(py, value) {
+                let _ = bound.setattr(key.as_str(), value);
+            }
+        }
+    }
+
+    if !record.func_name.is_empty() {
+        let _ = bound.setattr("func_name", record.func_name.as_str());
+        let _ = bound.setattr("funcName", record.func_name.as_str());
+    }
+    Ok(py_record)
+}
+
+/// Run `filters` over `record` as `logging.Filterer.filter` does: an object with a
+/// `filter` method has it called, any other callable is called itself, and a false
+/// result (`False`, `None`) drops the record. Filters get a `LogRecord` and may change
+/// it, or return a `LogRecord` to pass on instead; changes are copied back to
+/// `record`. A filter that raises keeps the record. Returns the filter that dropped it.
+pub(crate) fn apply_filters(
+    py: Python,
+    filters: &[Py<PyAny>],
+    record: &mut LogRecord,
+) -> Option<Py<PyAny>> {
+    let Ok(mut py_record) = Bound::new(py, record.clone()) else {
+        return None;
+    };
+    for filter in filters {
+        let bound = filter.bind(py);
+        let result = match bound.getattr(pyo3::intern!(py, "filter")) {
+            Ok(method) => method.call1((&py_record,)),
+            Err(_) if bound.is_callable() => bound.call1((&py_record,)),
+            Err(_) => continue,
+        };
+        let Ok(result) = result else {
+            continue;
+        };
+        if let Ok(replacement) = result.cast::<LogRecord>() {
+            py_record = replacement.clone();
+        } else if !result.is_truthy().unwrap_or(true) {
+            return Some(filter.clone_ref(py));
+        }
+    }
+    *record = py_record.borrow().clone();
+    None
+}
+
 /// Coerce a log `msg` to `String` like `str(msg)`. Exact-`str` fast path reads the
 /// UTF-8 buffer directly and skips `PyObject_Str`; the exact-type check is required for
 /// byte-identical output because `str` subclasses may override `__str__`.
 #[inline]
 fn coerce_msg_to_string(msg: &Bound<PyAny>) -> PyResult<String> {
     if msg.is_exact_instance_of::<pyo3::types::PyString>() {
-        Ok(msg.cast::<pyo3::types::PyString>()?.to_string())
+        Ok(crate::sanitize::py_text(
+            msg.cast::<pyo3::types::PyString>()?,
+        ))
     } else {
-        Ok(msg.str()?.to_string())
+        Ok(crate::sanitize::py_text(&msg.str()?))
     }
 }
 
@@ -90,7 +190,7 @@ pub fn py_to_json_value(obj: &Bound<PyAny>) -> Value {
             .map(Value::Number)
             .unwrap_or(Value::Null)
     } else if let Ok(py_str) = obj.cast::<pyo3::types::PyString>() {
-        Value::String(py_str.to_string())
+        Value::String(crate::sanitize::py_text(py_str))
     } else if let Ok(list) = obj.cast::<pyo3::types::PyList>() {
         let arr: Vec<Value> = list.iter().map(|item| py_to_json_value(&item)).collect();
         Value::Array(arr)
@@ -106,12 +206,24 @@ pub fn py_to_json_value(obj: &Bound<PyAny>) -> Value {
         }
         Value::Object(map)
     } else if let Ok(s) = obj.str() {
-        Value::String(s.to_string())
+        Value::String(crate::sanitize::py_text(&s))
     } else {
         Value::Null
     }
 }
 
+/// A stop on the way up from a logger: an existing ancestor, or a namespace route.
+enum PathStop {
+    Logger(PyLogger),
+    Route(String, Arc<crate::routing::Route>),
+}
+
+/// One logger's handler lists, as visited by the propagation walk.
+struct Hop {
+    rust_dispatch: Arc<Mutex<Vec<RustEntry>>>,
+    py_dispatch: Arc<Mutex<Vec<PyEntry>>>,
+}
+
 #[pyclass(skip_from_py_object)]
 pub struct PyLogger {
     pub(crate) inner: Arc<Mutex<Logger>>,
@@ -123,6 +235,8 @@ pub struct PyLogger {
     pub(crate) propagate: Arc<Mutex<bool>>,
     pub(crate) parent: Arc<Mutex<Option<Py<PyAny>>>>,
     pub(crate) manager: Arc<Mutex<Option<Py<PyAny>>>>,
+    /// Extras added to every record, set by `bind()` on a view of the logger.
+    pub(crate) context: Option<Arc<HashMap<String, Value>>>,
 }
 
 impl PyLogger {
@@ -138,6 +252,7 @@ impl PyLogger {
             propagate: Arc::new(Mutex::new(true)),
             parent: Arc::new(Mutex::new(None)),
             manager: Arc::new(Mutex::new(None)),
+            context: None,
         }
     }
 
@@ -156,6 +271,7 @@ impl PyLogger {
             propagate: Arc::new(Mutex::new(true)),
             parent: Arc::new(Mutex::new(None)),
             manager: Arc::new(Mutex::new(manager)),
+            context: None,
         }
     }
 }
@@ -172,6 +288,7 @@ impl Clone for PyLogger {
             propagate: self.propagate.clone(),
             parent: self.parent.clone(),
             manager: self.manager.clone(),
+            context: self.context.clone(),
         }
     }
 }
@@ -181,25 +298,64 @@ impl Drop for PyLogger {
 }
 
 impl PyLogger {
+    /// `extra` on top of the bound context: the call's keys win.
+    fn with_context(
+        &self,
+        extra: Option<HashMap<String, Value>>,
+    ) -> Option<HashMap<String, Value>> {
+        let Some(context) = &self.context else {
+            return extra;
+        };
+        let mut fields = HashMap::clone(context);
+        fields.extend(extra.unwrap_or_default());
+        Some(fields)
+    }
+
+    /// The record's extras: the `extra=` dict, then any keyword argument the logging
+    /// methods don't take themselves (`logger.info("login", user_id=42)`), which wins
+    /// over the same key in `extra`. Both keep their JSON type.
     fn extract_extra_fields(
         &self,
         kwargs: Option<&Bound<PyDict>>,
     ) -> Option<HashMap<String, Value>> {
-        kwargs.and_then(|dict| {
+        let extra = kwargs.and_then(|dict| {
+            let mut limits = crate::extra_limits::ExtraLimits::current();
+            let mut extra_map: Option<HashMap<String, Value>> = None;
+            let mut add =
+                |extra_map: &mut HashMap<String, Value>, key: String, value: &Bound<PyAny>| {
+                    if extra_map.contains_key(&key) || limits.admit(extra_map.len()) {
+                        extra_map.insert(key, limits.value(py_to_json_value(value)));
+                    }
+                };
             if let Ok(Some(extra_bound)) = dict.get_item("extra") {
                 if let Ok(extra_dict) = extra_bound.cast::<PyDict>() {
-                    let mut extra_map = HashMap::new();
+                    let map = extra_map.get_or_insert_with(HashMap::new);
                     for (key, value) in extra_dict.iter() {
                         if let Ok(key_str) = key.str() {
-                            let json_value = py_to_json_value(&value);
-                            extra_map.insert(key_str.to_string(), json_value);
+                            add(map, key_str.to_string(), &value);
                         }
                     }
-                    return Some(extra_map);
                 }
             }
-            None
-        })
+            for (key, value) in dict.iter() {
+                let Ok(name) = key.cast::<PyString>() else {
+                    continue;
+                };
+                let Ok(key) = name.to_str() else {
+                    continue;
+                };
+                if matches!(key, "extra" | "exc_info" | "stack_info" | "stacklevel") {
+                    continue;
+                }
+                let map = extra_map.get_or_insert_with(HashMap::new);
+                add(map, key.to_string(), &value);
+            }
+            if let Some(map) = extra_map.as_mut() {
+                limits.finish(map);
+            }
+            extra_map
+        });
+        self.with_context(extra)
     }
 
     /// Extract exc_info from kwargs and format it as traceback text.
@@ -337,62 +493,78 @@ impl PyLogger {
         }
     }
 
-    /// Capture the current active exception via traceback.format_exc().
+    /// Capture the current active exception (sys.exc_info()) as traceback text.
     fn capture_current_exception(&self, py: Python) -> Option<String> {
-        py.import("traceback")
-            .and_then(|m| m.call_method0("format_exc"))
-            .map(|s| s.to_string())
-            .ok()
-            .filter(|s| s != "NoneType: None\n" && !s.is_empty())
+        let info = py.import("sys").ok()?.call_method0("exc_info").ok()?;
+        let tuple = info.cast::<PyTuple>().ok()?;
+        crate::traceback::render_exception(
+            py,
+            &tuple.get_item(0).ok()?,
+            &tuple.get_item(1).ok()?,
+            &tuple.get_item(2).ok()?,
+        )
     }
 
     /// Format a (type, value, tb) tuple into traceback text.
     fn format_exception_tuple(&self, py: Python, tuple: &Bound<PyTuple>) -> Option<String> {
-        let tb_mod = py.import("traceback").ok()?;
-        let formatted = tb_mod
-            .call_method1(
-                "format_exception",
-                (
-                    tuple.get_item(0).ok()?,
-                    tuple.get_item(1).ok()?,
-                    tuple.get_item(2).ok()?,
-                ),
-            )
-            .ok()?;
-        let empty_str = "".into_pyobject(py).ok()?;
-        let joined = empty_str.call_method1("join", (&formatted,)).ok()?;
-        let result = joined.to_string();
-        if result.is_empty() {
-            None
-        } else {
-            Some(result)
-        }
+        crate::traceback::render_exception(
+            py,
+            &tuple.get_item(0).ok()?,
+            &tuple.get_item(1).ok()?,
+            &tuple.get_item(2).ok()?,
+        )
     }
 
     /// Format an exception instance into traceback text.
     fn format_exception_instance(&self, py: Python, exc: &Bound<PyAny>) -> Option<String> {
         let exc_type = exc.get_type();
         let tb = exc.getattr("__traceback__").ok()?;
-        let tb_mod = py.import("traceback").ok()?;
-        let formatted = tb_mod
-            .call_method1("format_exception", (exc_type, exc, tb))
-            .ok()?;
-        let empty_str = "".into_pyobject(py).ok()?;
-        let joined = empty_str.call_method1("join", (&formatted,)).ok()?;
-        let result = joined.to_string();
-        if result.is_empty() {
-            None
-        } else {
-            Some(result)
+        crate::traceback::render_exception(py, exc_type.as_any(), exc, &tb)
+    }
+
+    /// Read the `stacklevel` kwarg: how many frames outside logging to walk up for the
+    /// caller, as in `logging.Logger.findCaller`. Defaults to 1, the direct caller.
+    fn extract_stacklevel(kwargs: Option<&Bound<PyDict>>) -> PyResult<i64> {
+        match kwargs.and_then(|dict| dict.get_item("stacklevel").ok().flatten()) {
+            Some(value) => value.extract(),
+            None => Ok(1),
         }
     }
 
+    /// The `stack_info` kwarg: when true, the Python stack up to the frame `stacklevel`
+    /// picks, headed "Stack (most recent call last):" as in the stdlib.
+    fn extract_stack_info(
+        py: Python,
+        kwargs: Option<&Bound<PyDict>>,
+        stacklevel: i64,
+    ) -> PyResult<Option<String>> {
+        let wanted = match kwargs.and_then(|dict| dict.get_item("stack_info").ok().flatten()) {
+            Some(value) => value.is_truthy()?,
+            None => false,
+        };
+        if !wanted {
+            return Ok(None);
+        }
+        static HELPER: std::sync::OnceLock<Py<PyAny>> = std::sync::OnceLock::new();
+        let helper = match HELPER.get() {
+            Some(h) => h,
+            None => {
+                let fun = py
+                    .import("logxide.compat_functions")?
+                    .getattr("_get_stack_info")?;
+                HELPER.get_or_init(|| fun.unbind())
+            }
+        };
+        helper.call1(py, (stacklevel,))?.extract(py).map(Some)
+    }
+
     /// Populate pathname, filename, lineno, func_name on record via Python frame introspection.
     /// Uses a cached Python helper that returns (filename, funcName, lineno) in one call,
     /// roughly halving the number of cross-language attribute lookups vs walking the frame
-    /// from Rust.
-    fn populate_caller_info(py: Python, record: &mut LogRecord) {
-        if !crate::globals::CALLER_INFO_REQUIRED.load(std::sync::atomic::Ordering::Relaxed) {
+    /// from Rust. `stacklevel` picks the frame the same way the stdlib does, skipping
+    /// frames inside logxide and the stdlib logging module.
+    fn populate_caller_info(py: Python, record: &mut LogRecord, stacklevel: i64) {
+        if !crate::globals::caller_info_wanted() {
             return;
         }
 
@@ -408,7 +580,7 @@ impl PyLogger {
             },
         };
 
-        let Ok(result) = helper.call0(py) else {
+        let Ok(result) = helper.call1(py, (stacklevel,)) else {
             return;
         };
         let Ok((path, func_name, lineno)) = result.extract::<(String, String, u32)>(py) else {
@@ -461,7 +633,7 @@ impl PyLogger {
     fn run_rust_dispatch(
         rust_arcs: &[Arc<dyn Handler + Send + Sync>],
         global_handlers: Option<&[Arc<dyn Handler + Send + Sync>]>,
-        record: &LogRecord,
+        record: &Arc<LogRecord>,
     ) {
         for arc in rust_arcs.iter() {
             arc.emit(record);
@@ -473,60 +645,138 @@ impl PyLogger {
         }
     }
 
-    /// Snapshot the per-logger rust handler arcs, the propagation decision, and whether
-    /// every entry is native (so §4 detached dispatch is allowed). Releases every Mutex
-    /// guard before returning so nothing is held across a later `py.detach`.
-    fn dispatch_snapshot(&self) -> (Vec<Arc<dyn Handler + Send + Sync>>, bool, bool, bool) {
-        let (rust_arcs, all_native): (Vec<Arc<dyn Handler + Send + Sync>>, bool) = {
-            let lock = self.rust_dispatch.lock().unwrap();
-            let mut arcs = Vec::with_capacity(lock.len());
-            let mut all_native = true;
-            for e in lock.iter() {
+    /// The handler lists that see a record from this one, as in
+    /// `logging.Logger.callHandlers`: this logger, then each existing ancestor nearest
+    /// first, stopping after the first with `propagate = False`. A namespace route is
+    /// visited after the logger of the same name, and a terminal one stops the walk too.
+    /// The bool is whether the walk reaches root, whose handlers are the global lists.
+    fn propagation_path(&self, py: Python) -> (Vec<Hop>, bool) {
+        let own = Hop {
+            rust_dispatch: self.rust_dispatch.clone(),
+            py_dispatch: self.py_dispatch.clone(),
+        };
+        let name: &str = &self.fast_logger.name;
+        if name == "root" {
+            return (Vec::new(), true);
+        }
+        // A namespace route is visited right after the logger of the same name.
+        let routes = crate::routing::table();
+        let route_hop = |namespace: &str| {
+            crate::routing::get(&routes, namespace).map(|route| {
+                let hop = Hop {
+                    rust_dispatch: route.rust_dispatch.clone(),
+                    py_dispatch: route.py_dispatch.clone(),
+                };
+                (hop, !route.terminal)
+            })
+        };
+        let mut path = vec![own];
+        let mut propagate = *self.propagate.lock().unwrap();
+        if let Some((hop, onward)) = route_hop(name) {
+            path.push(hop);
+            propagate &= onward;
+        }
+        if !propagate {
+            return (path, false);
+        }
+        if !name.contains('.') {
+            return (path, true);
+        }
+        // (hop, whether the walk goes on after it)
+        let ancestors: Vec<(Hop, bool)> = {
+            let alive = PY_LOGGER_KEEP_ALIVE.lock().unwrap();
+            let mut ancestors = Vec::new();
+            let mut current = name;
+            while let Some((parent, _)) = current.rsplit_once('.') {
+                if let Some(logger) = alive.get(parent) {
+                    let logger = logger.bind(py).borrow();
+                    let hop = Hop {
+                        rust_dispatch: logger.rust_dispatch.clone(),
+                        py_dispatch: logger.py_dispatch.clone(),
+                    };
+                    ancestors.push((hop, *logger.propagate.lock().unwrap()));
+                }
+                ancestors.extend(route_hop(parent));
+                current = parent;
+            }
+            ancestors
+        };
+        for (hop, onward) in ancestors {
+            path.push(hop);
+            if !onward {
+                return (path, false);
+            }
+        }
+        (path, true)
+    }
+
+    /// Snapshot the rust handler arcs along the propagation path, whether it reaches the
+    /// global handlers, whether it has no py_dispatch entries, and whether every entry is
+    /// native (so §4 detached dispatch is allowed). Releases every Mutex guard before
+    /// returning so nothing is held across a later `py.detach`.
+    fn dispatch_snapshot(
+        &self,
+        py: Python,
+    ) -> (Vec<Arc<dyn Handler + Send + Sync>>, bool, bool, bool) {
+        let (path, dispatch_global) = self.propagation_path(py);
+        let mut rust_arcs: Vec<Arc<dyn Handler + Send + Sync>> = Vec::new();
+        let mut all_native = true;
+        let mut py_dispatch_empty = true;
+        for hop in path.iter() {
+            for e in hop.rust_dispatch.lock().unwrap().iter() {
                 if e.wrapper.is_some() && e.arc.dispatch_mode() == DispatchMode::Python {
                     all_native = false;
                 }
-                arcs.push(e.arc.clone());
+                rust_arcs.push(e.arc.clone());
             }
-            (arcs, all_native)
-        };
-        let py_dispatch_empty = self.py_dispatch.lock().unwrap().is_empty();
-        let has_local = !rust_arcs.is_empty() || !py_dispatch_empty;
-        let dispatch_global = !has_local || *self.propagate.lock().unwrap();
+            py_dispatch_empty &= hop.py_dispatch.lock().unwrap().is_empty();
+        }
         (rust_arcs, dispatch_global, py_dispatch_empty, all_native)
     }
 
     /// Route a fully-built record. When no Python code needs to run during dispatch
-    /// (no filters, no Python-dispatch handlers, every rust entry native), the Rust handler
+    /// (only `logxide.Filter` filters, no Python-dispatch handlers, every rust entry
+    /// native), the Rust handler
     /// emit runs with the GIL released so producers scale across threads (§4). Otherwise
-    /// fall back to the fully-attached emit_record path (filters may mutate the record;
+    /// fall back to the fully-attached deliver_record path (filters may mutate the record;
     /// Python-mode text-sink wrappers + py_dispatch handlers need a py_record).
     ///
     /// Caveat: %-args formatting still calls record.get_message() -> Python __mod__ under
     /// Python::attach (core.rs), so an args-bearing record re-acquires the GIL inside a Rust
     /// formatter's emit and won't fully parallelize until P1-3. No-args / pre-formatted
     /// records scale.
-    fn dispatch(&self, py: Python, record: LogRecord, exc_info_py: Option<Py<PyAny>>) {
-        let has_filters = !self.filters.lock().unwrap().is_empty();
-        let (rust_arcs, dispatch_global, py_dispatch_empty, all_native) = self.dispatch_snapshot();
-        let global_py_nonempty = !GLOBAL_PY_HANDLERS.lock().unwrap().is_empty();
-
-        let eligible = !has_filters
-            && py_dispatch_empty
-            && !(dispatch_global && global_py_nonempty)
-            && all_native;
-
-        if !eligible {
-            self.emit_record(record, exc_info_py);
+    pub(crate) fn dispatch(
+        &self,
+        py: Python,
+        mut record: LogRecord,
+        exc_info_py: Option<Py<PyAny>>,
+    ) {
+        if let Some(exc_info) = exc_info_py.as_ref() {
+            crate::fingerprint::apply(&mut record, Some(exc_info.bind(py)));
+        }
+        crate::run_id::apply(&mut record);
+        crate::secret_keys::apply(&mut record);
+        #[cfg(feature = "regex-filters")]
+        crate::redact::apply(&mut record);
+        crate::template::apply(&mut record);
+        if let Some(trace) = crate::dispatch_trace::Trace::start(&record) {
+            self.deliver_record(record, exc_info_py, Some(trace));
             return;
         }
-
-        let global_handlers = if dispatch_global {
-            Some(HANDLERS.load_full())
-        } else {
-            None
+        let targets = match self.native_filter(py, &record) {
+            Some(false) => return,
+            Some(true) => self.native_targets(py),
+            None => None,
         };
+        let Some((rust_arcs, global_handlers)) = targets else {
+            self.deliver_record(record, exc_info_py, None);
+            return;
+        };
+        // Shared by every handler; queued handlers keep the Arc, not a copy.
+        let record = Arc::new(record);
         py.detach(move || {
             let _block_scope = crate::handler::BlockWaitGuard::enter();
+            let _dedup_scope = crate::handler::ConsoleDedupScope::enter();
             PyLogger::run_rust_dispatch(
                 &rust_arcs,
                 global_handlers.as_deref().map(|v| v.as_slice()),
@@ -534,192 +784,432 @@ impl PyLogger {
             );
         });
     }
+
+    /// `dispatch` for the records of one `log_batch()` call: one snapshot, and on the
+    /// native path one GIL release with a single `emit_batch` per handler.
+    fn dispatch_batch(&self, py: Python, mut records: Vec<LogRecord>) {
+        // Traced one at a time, so each record can be sampled.
+        if crate::dispatch_trace::enabled() {
+            for record in records {
+                self.dispatch(py, record, None);
+            }
+            return;
+        }
+        records.iter_mut().for_each(crate::run_id::apply);
+        records.iter_mut().for_each(crate::secret_keys::apply);
+        #[cfg(feature = "regex-filters")]
+        records.iter_mut().for_each(crate::redact::apply);
+        if crate::template::enabled() {
+            records.iter_mut().for_each(crate::template::apply);
+        }
+        let mut filtered_natively = true;
+        records.retain(|record| {
+            self.native_filter(py, record).unwrap_or_else(|| {
+                filtered_natively = false;
+                true
+            })
+        });
+        let targets = filtered_natively.then(|| self.native_targets(py)).flatten();
+        let Some((rust_arcs, global_handlers)) = targets else {
+            for record in records {
+                self.deliver_record(record, None, None);
+            }
+            return;
+        };
+        let records: Vec<Arc<LogRecord>> = records.into_iter().map(Arc::new).collect();
+        py.detach(move || {
+            let _block_scope = crate::handler::BlockWaitGuard::enter();
+            let _dedup_scope = crate::handler::ConsoleDedupScope::enter();
+            let global = global_handlers.as_deref().map(|v| v.as_slice());
+            for handler in rust_arcs.iter().chain(global.into_iter().flatten()) {
+                handler.emit_batch(&records);
+            }
+        });
+    }
+
+    /// The handlers a record goes to when no Python code needs to run during dispatch
+    /// (no Python-dispatch handlers, every rust entry native): the rust arcs along the
+    /// propagation path, plus the global HANDLERS when propagation reaches them. None
+    /// means the record must take the attached `deliver_record` path. Callers run the
+    /// logger's filters first with `native_filter`.
+    #[allow(clippy::type_complexity)]
+    fn native_targets(
+        &self,
+        py: Python,
+    ) -> Option<(
+        Vec<Arc<dyn Handler + Send + Sync>>,
+        Option<Arc<Vec<Arc<dyn Handler + Send + Sync>>>>,
+    )> {
+        crate::config::consistent(py, || {
+            let (rust_arcs, dispatch_global, py_dispatch_empty, all_native) =
+                self.dispatch_snapshot(py);
+            let global_py_nonempty = !GLOBAL_PY_HANDLERS.lock().unwrap().is_empty();
+
+            let eligible =
+                py_dispatch_empty && !(dispatch_global && global_py_nonempty) && all_native;
+            if !eligible {
+                return None;
+            }
+            let global_handlers = dispatch_global.then(|| HANDLERS.load_full());
+            Some((rust_arcs, global_handlers))
+        })
+    }
+
+    /// Run this logger's filters in Rust when every one is a `logxide.Filter` or
+    /// `logxide.RegexFilter` (not a subclass): Some(whether `record` passes), or None when a Python filter needs a
+    /// Python record and so the attached path.
+    fn native_filter(&self, py: Python, record: &LogRecord) -> Option<bool> {
+        let filters = self.filters.lock().unwrap();
+        let mut kept = true;
+        for filter in filters.iter() {
+            kept = kept && crate::filter::native_filter(filter.bind(py), record)?;
+        }
+        Some(kept)
+    }
 }
 
-#[pymethods]
 impl PyLogger {
-    fn emit_record(&self, mut record: LogRecord, exc_info_py: Option<Py<PyAny>>) {
-        // Filters can modify the record (especially record.msg) and return False to suppress.
+    /// Detach every handler from this logger (`dictConfig` reconfiguring it).
+    pub(crate) fn clear_handlers(&self, py: Python) {
+        crate::globals::clear_logger_handlers(
+            py,
+            &self.fast_logger.name,
+            &self.rust_dispatch,
+            &self.py_dispatch,
+            &self.lifecycle,
+        );
+    }
+
+    /// Return this logger to its freshly created state (`reinitialize()`): no handlers
+    /// or filters, propagating, enabled, and level NOTSET (WARNING for the root).
+    pub(crate) fn reset(&self, py: Python) {
+        self.clear_handlers(py);
+        self.filters.lock().unwrap().clear();
+        *self.propagate.lock().unwrap() = true;
+        self.fast_logger.set_disabled(false);
+        let level = if self.fast_logger.name.as_ref() == "root" {
+            LogLevel::Warning
+        } else {
+            LogLevel::NotSet
+        };
+        self.fast_logger.set_level(level);
+        let mut inner = self.inner.lock().unwrap();
+        inner.set_level(level);
+        inner.filters.clear();
+    }
+
+    /// What a record from this one is handed to after it, nearest first: the existing
+    /// ancestors and namespace routes up to the first that stops propagation, then the
+    /// root when propagation gets that far. Mirrors `propagation_path`.
+    fn path_stops(&self, py: Python) -> PyResult<Vec<PathStop>> {
+        let name: &str = &self.fast_logger.name;
+        if name == "root" {
+            return Ok(Vec::new());
+        }
+        let routes = crate::routing::table();
+        let mut chain = Vec::new();
+        let mut propagate = *self.propagate.lock().unwrap();
+        if let Some(route) = crate::routing::get(&routes, name) {
+            propagate &= !route.terminal;
+            chain.push(PathStop::Route(name.to_string(), route));
+        }
+        if !propagate {
+            return Ok(chain);
+        }
+        {
+            let alive = PY_LOGGER_KEEP_ALIVE.lock().unwrap();
+            let mut current = name;
+            while let Some((parent, _)) = current.rsplit_once('.') {
+                let mut onward = true;
+                if let Some(logger) = alive.get(parent) {
+                    let logger = logger.bind(py).borrow().clone();
+                    onward = *logger.propagate.lock().unwrap();
+                    chain.push(PathStop::Logger(logger));
+                }
+                if onward {
+                    if let Some(route) = crate::routing::get(&routes, parent) {
+                        onward = !route.terminal;
+                        chain.push(PathStop::Route(parent.to_string(), route));
+                    }
+                }
+                if !onward {
+                    return Ok(chain);
+                }
+                current = parent;
+            }
+        }
+        let root = crate::globals::get_logger(py, Some("root"), None)?;
+        chain.push(PathStop::Logger(root.borrow(py).clone()));
+        Ok(chain)
+    }
+
+    /// The loggers among [`PyLogger::path_stops`].
+    fn ancestor_chain(&self, py: Python) -> PyResult<Vec<PyLogger>> {
+        Ok(self
+            .path_stops(py)?
+            .into_iter()
+            .filter_map(|stop| match stop {
+                PathStop::Logger(logger) => Some(logger),
+                PathStop::Route(..) => None,
+            })
+            .collect())
+    }
+
+    /// This logger's handlers as (identity, object it was added as), Rust-backed ones
+    /// first. Rust handlers registered without an object have None.
+    fn handler_objects(&self, py: Python) -> Vec<(HandlerId, Option<Py<PyAny>>)> {
+        let (ids, foreign): (Vec<HandlerId>, Vec<Py<PyAny>>) =
+            if self.fast_logger.name.as_ref() == "root" {
+                let ids = HANDLERS.load().iter().map(arc_id).collect();
+                let foreign = GLOBAL_PY_HANDLERS.lock().unwrap();
+                (ids, foreign.iter().map(|e| e.obj.clone_ref(py)).collect())
+            } else {
+                let ids = self
+                    .rust_dispatch
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .map(|e| e.id)
+                    .collect();
+                let foreign = self.py_dispatch.lock().unwrap();
+                (ids, foreign.iter().map(|e| e.obj.clone_ref(py)).collect())
+            };
+        let objects = HANDLER_OBJECTS.lock().unwrap();
+        let mut handlers: Vec<(HandlerId, Option<Py<PyAny>>)> = ids
+            .into_iter()
+            .map(|id| (id, objects.get(&id).map(|o| o.clone_ref(py))))
+            .collect();
+        handlers.extend(foreign.into_iter().map(|o| (o.as_ptr() as usize, Some(o))));
+        handlers
+    }
+
+    /// One entry per attached handler: the object it was added as (type name, `name`,
+    /// `level`), or just `type: "native"` for a handler registered without one.
+    fn describe_handlers<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let list = PyList::empty(py);
+        // Reading `name`/`level` can run Python code, so no registry lock is held here.
+        for (_, obj) in self.handler_objects(py) {
+            let entry = PyDict::new(py);
+            match obj {
+                Some(obj) => {
+                    let obj = obj.bind(py);
+                    entry.set_item("type", obj.get_type().name()?)?;
+                    entry.set_item("name", obj.getattr("name").ok())?;
+                    entry.set_item("level", obj.getattr("level").ok())?;
+                }
+                None => {
+                    entry.set_item("type", "native")?;
+                    entry.set_item("name", py.None())?;
+                    entry.set_item("level", py.None())?;
+                }
+            }
+            list.append(entry)?;
+        }
+        Ok(list)
+    }
+
+    /// Add the loggers a traced record propagates through to `trace`, and label each of
+    /// their handlers with its logger so the per-handler lines say where it is attached.
+    fn trace_path(&self, py: Python, trace: &mut crate::dispatch_trace::Trace) {
+        let mut stops = vec![PathStop::Logger(self.clone())];
+        stops.extend(self.path_stops(py).unwrap_or_default());
+        let names: Vec<String> = stops
+            .iter()
+            .map(|stop| match stop {
+                PathStop::Logger(l) => l.fast_logger.name.to_string(),
+                PathStop::Route(namespace, _) => format!("route {namespace}"),
+            })
+            .collect();
+        trace.line(format_args!("path: {}", names.join(" -> ")));
+        for stop in stops.iter() {
+            let (owner, handlers) = match stop {
+                PathStop::Logger(logger) => (
+                    format!("{:?}", logger.fast_logger.name.as_ref()),
+                    logger.handler_objects(py),
+                ),
+                PathStop::Route(namespace, route) => {
+                    (format!("route {namespace:?}"), route.handler_objects(py))
+                }
+            };
+            for (id, obj) in handlers {
+                let handler = match obj.as_ref() {
+                    Some(obj) => crate::dispatch_trace::object_label(obj.bind(py)),
+                    None => "native handler".to_string(),
+                };
+                let label = format!("{owner} {handler}");
+                // Python-mode wrappers are called through the object, not the arc.
+                if let Some(obj) = obj {
+                    trace.label(obj.as_ptr() as usize, label.clone());
+                }
+                trace.label(id, label);
+            }
+        }
+    }
+
+    /// The level check of the logging methods. A record it drops may still be sampled
+    /// by `debug_dispatch()`, which then reports why.
+    #[inline]
+    fn enabled_for(&self, level: LogLevel) -> bool {
+        let enabled = self.fast_logger.is_enabled_for(level);
+        if !enabled && crate::dispatch_trace::enabled() {
+            crate::dispatch_trace::logger_rejected(
+                &self.fast_logger.name,
+                level,
+                self.fast_logger.get_effective_level(),
+                self.fast_logger.is_disabled(),
+            );
+        }
+        enabled
+    }
+
+    /// Log a record built on the Rust side (timers): same level check, caller info and
+    /// dispatch as the level methods, with `extra` attached.
+    pub(crate) fn log_with_extra(
+        &self,
+        py: Python,
+        level: LogLevel,
+        msg: String,
+        extra: HashMap<String, Value>,
+    ) {
+        if !self.enabled_for(level) {
+            return;
+        }
+        let mut record = create_log_record_with_extra(
+            self.fast_logger.name.to_string(),
+            level,
+            msg,
+            self.with_context(Some(extra)),
+        );
+        PyLogger::populate_caller_info(py, &mut record, 1);
+        self.dispatch(py, record, None);
+    }
+
+    /// Hand a record read back from a log file (`replay_log_file`) to the handlers, with
+    /// the level check of the logging methods. Returns whether the level let it through.
+    #[cfg(feature = "json")]
+    pub(crate) fn replay(&self, py: Python, record: LogRecord) -> bool {
+        if !self.enabled_for(LogLevel::from_usize(record.levelno.max(0) as usize)) {
+            return false;
+        }
+        self.dispatch(py, record, None);
+        true
+    }
+
+    /// The attached dispatch path: logger filters, then every handler along the
+    /// propagation path. `trace` collects the decisions for `debug_dispatch()`.
+    fn deliver_record(
+        &self,
+        mut record: LogRecord,
+        exc_info_py: Option<Py<PyAny>>,
+        mut trace: Option<crate::dispatch_trace::Trace>,
+    ) {
+        // Filters can change the record and return False to suppress it.
         // Only enter the GIL when filters are actually present.
         let has_filters = !self.filters.lock().unwrap().is_empty();
         if has_filters {
-            let should_emit = Python::attach(|py| {
+            let rejected_by = Python::attach(|py| {
                 let filters: Vec<Py<PyAny>> = {
                     let lock = self.filters.lock().unwrap();
                     lock.iter().map(|f| f.clone_ref(py)).collect()
                 };
-                for filter_obj in filters.iter() {
-                    let filter_bound = filter_obj.bind(py);
-
-                    let result = if let Ok(filter_method) = filter_bound.getattr("filter") {
-                        let py_record = pyo3::types::PyDict::new(py);
-                        let _ = py_record.set_item("name", &record.name);
-                        let _ = py_record.set_item("levelno", record.levelno);
-                        let _ = py_record.set_item("levelname", &record.levelname);
-                        let _ = py_record.set_item("msg", &record.msg);
-                        let _ = py_record.set_item("pathname", &record.pathname);
-                        let _ = py_record.set_item("lineno", record.lineno);
-                        let _ = py_record.set_item("func_name", &record.func_name);
-
-                        let call_result = filter_method.call1((&py_record,));
-
-                        if let Ok(Some(new_msg)) = py_record.get_item("msg") {
-                            if let Ok(msg_str) = new_msg.extract::<String>() {
-                                record.msg = msg_str;
-                            }
-                        }
-
-                        match call_result {
-                            Ok(res) => res.is_truthy().unwrap_or(true),
-                            Err(_) => true,
-                        }
-                    } else if filter_bound.is_callable() {
-                        let py_record = pyo3::types::PyDict::new(py);
-                        let _ = py_record.set_item("name", &record.name);
-                        let _ = py_record.set_item("levelno", record.levelno);
-                        let _ = py_record.set_item("levelname", &record.levelname);
-                        let _ = py_record.set_item("msg", &record.msg);
-                        let _ = py_record.set_item("pathname", &record.pathname);
-                        let _ = py_record.set_item("lineno", record.lineno);
-                        let _ = py_record.set_item("func_name", &record.func_name);
-
-                        let call_result = filter_bound.call1((&py_record,));
-
-                        if let Ok(Some(new_msg)) = py_record.get_item("msg") {
-                            if let Ok(msg_str) = new_msg.extract::<String>() {
-                                record.msg = msg_str;
-                            }
-                        }
-
-                        match call_result {
-                            Ok(res) => res.is_truthy().unwrap_or(true),
-                            Err(_) => true,
-                        }
-                    } else {
-                        true
-                    };
-
-                    if !result {
-                        return false;
-                    }
-                }
-                true
+                apply_filters(py, &filters, &mut record)
             });
 
-            if !should_emit {
+            if let Some(filter) = rejected_by {
+                if let Some(trace) = trace.as_mut() {
+                    let label =
+                        Python::attach(|py| crate::dispatch_trace::object_label(filter.bind(py)));
+                    trace.line(format_args!(
+                        "logger {:?}: dropped by filter {label}",
+                        self.fast_logger.name.as_ref()
+                    ));
+                }
                 return;
             }
         }
-
-        // Propagation-gated dispatch. Each rust_dispatch entry emits natively unless it is
-        // a text-sink wrapper flipped to Python mode (custom Formatter / {,$ style / handler
-        // filter), in which case its wrapper.handle() runs once in the Python half below.
-        let (native_arcs, python_wrappers, dispatch_global, py_dispatch_empty) = {
-            let lock = self.rust_dispatch.lock().unwrap();
-            let mut native_arcs: Vec<Arc<dyn Handler + Send + Sync>> =
-                Vec::with_capacity(lock.len());
-            let mut python_wrappers: Vec<Py<PyAny>> = Vec::new();
-            Python::attach(|py| {
-                for e in lock.iter() {
-                    match &e.wrapper {
-                        Some(w) if e.arc.dispatch_mode() == DispatchMode::Python => {
-                            python_wrappers.push(w.clone_ref(py));
+        let record = Arc::new(record);
+
+        // Propagation-gated dispatch along the logger hierarchy. Each rust_dispatch entry
+        // emits natively unless it is a text-sink wrapper flipped to Python mode (custom
+        // Formatter / {,$ style / handler filter), in which case its wrapper.handle() runs
+        // once in the Python half below. Root's handlers are the global lists.
+        let (native_arcs, python_wrappers, py_handlers) = Python::attach(|py| {
+            crate::config::consistent(py, || {
+                let (path, dispatch_global) = self.propagation_path(py);
+                let mut native_arcs: Vec<Arc<dyn Handler + Send + Sync>> = Vec::new();
+                let mut python_wrappers: Vec<Py<PyAny>> = Vec::new();
+                let mut py_handlers: Vec<Py<PyAny>> = Vec::new();
+                for hop in path.iter() {
+                    for e in hop.rust_dispatch.lock().unwrap().iter() {
+                        match &e.wrapper {
+                            Some(w) if e.arc.dispatch_mode() == DispatchMode::Python => {
+                                python_wrappers.push(w.clone_ref(py));
+                            }
+                            _ => native_arcs.push(e.arc.clone()),
                         }
-                        _ => native_arcs.push(e.arc.clone()),
                     }
+                    let lock = hop.py_dispatch.lock().unwrap();
+                    py_handlers.extend(lock.iter().map(|e| e.obj.clone_ref(py)));
                 }
-            });
-            let py_dispatch_empty = self.py_dispatch.lock().unwrap().is_empty();
-            let has_local =
-                !native_arcs.is_empty() || !python_wrappers.is_empty() || !py_dispatch_empty;
-            let dispatch_global = !has_local || *self.propagate.lock().unwrap();
-            (
-                native_arcs,
-                python_wrappers,
-                dispatch_global,
-                py_dispatch_empty,
-            )
-        };
+                if dispatch_global {
+                    native_arcs.extend(HANDLERS.load().iter().cloned());
+                    let lock = GLOBAL_PY_HANDLERS.lock().unwrap();
+                    py_handlers.extend(lock.iter().map(|e| e.obj.clone_ref(py)));
+                }
+                (native_arcs, python_wrappers, py_handlers)
+            })
+        });
 
-        for arc in native_arcs.iter() {
-            arc.emit(&record);
+        // Spans the Python half too: a Python-mode StreamHandler wrapper still writes
+        // through its Rust handler.
+        let _dedup_scope = crate::handler::ConsoleDedupScope::enter();
+        if let Some(trace) = trace.as_mut() {
+            Python::attach(|py| self.trace_path(py, trace));
+            if native_arcs.is_empty() && python_wrappers.is_empty() && py_handlers.is_empty() {
+                trace.line("no handlers along the propagation path");
+            }
         }
-        if dispatch_global {
-            let global = HANDLERS.load_full();
-            for handler in global.iter() {
-                handler.emit(&record);
+        for arc in native_arcs.iter() {
+            match trace.as_mut() {
+                Some(trace) => {
+                    trace.native(arc_id(arc), arc.level(), &record, || arc.emit(&record))
+                }
+                None => arc.emit(&record),
             }
         }
-
-        let global_py_nonempty = !GLOBAL_PY_HANDLERS.lock().unwrap().is_empty();
-        let need_py = !python_wrappers.is_empty()
-            || !py_dispatch_empty
-            || (dispatch_global && global_py_nonempty);
-        if !need_py {
+        if python_wrappers.is_empty() && py_handlers.is_empty() {
             return;
         }
 
         Python::attach(|py| {
-            let local_py_handlers: Vec<Py<PyAny>> = {
-                let lock = self.py_dispatch.lock().unwrap();
-                lock.iter().map(|e| e.obj.clone_ref(py)).collect()
-            };
-
-            let py_record = match self.makeRecord(
-                py,
-                record.name.clone(),
-                record.levelno,
-                record.pathname.clone(),
-                record.lineno as i32,
-                record
-                    .get_message()
-                    .into_py_any(py)
-                    .expect("Failed to convert getMessage to PyAny")
-                    .into(),
-                py.None().into(),
-                exc_info_py.as_ref().map(|e| e.clone_ref(py)),
-            ) {
+            let py_record = match python_record(py, &record, exc_info_py) {
                 Ok(r) => r,
                 Err(_) => {
                     return;
                 }
             };
 
-            if let Some(ref exc_text) = record.exc_text {
-                let _ = py_record.bind(py).setattr("exc_text", exc_text.as_str());
-            }
-
-            if !record.func_name.is_empty() {
-                let _ = py_record
-                    .bind(py)
-                    .setattr("func_name", record.func_name.as_str());
-                let _ = py_record
-                    .bind(py)
-                    .setattr("funcName", record.func_name.as_str());
-            }
-
-            // Python-mode text-sink wrappers (local): one handle() each.
-            for wrapper in python_wrappers.iter() {
-                let _ = wrapper.bind(py).call_method1("handle", (&py_record,));
-            }
-
-            for handler in local_py_handlers.iter() {
-                let b_handler = handler.bind(py);
-                let _ = b_handler.call_method1("handle", (&py_record,));
-            }
-
-            if dispatch_global {
-                let global_py_handlers: Vec<Py<PyAny>> = {
-                    let lock = GLOBAL_PY_HANDLERS.lock().unwrap();
-                    lock.iter().map(|e| e.obj.clone_ref(py)).collect()
-                };
-                for handler in global_py_handlers.iter() {
-                    let b_handler = handler.bind(py);
-                    let _ = b_handler.call_method1("handle", (&py_record,));
+            // Python-mode text-sink wrappers, then foreign handlers: one handle() each.
+            for handler in python_wrappers.iter().chain(py_handlers.iter()) {
+                let handle = || handler.bind(py).call_method1("handle", (&py_record,));
+                match trace.as_mut() {
+                    Some(trace) => trace.python(handler.as_ptr() as usize, || {
+                        handle().map_or(true, |kept| kept.is_truthy().unwrap_or(true))
+                    }),
+                    None => {
+                        let _ = handle();
+                    }
                 }
             }
         });
     }
+}
+
+#[pymethods]
+impl PyLogger {
+    fn emit_record(&self, record: LogRecord, exc_info_py: Option<Py<PyAny>>) {
+        self.deliver_record(record, exc_info_py, None);
+    }
 
     #[getter]
     fn name(&self) -> PyResult<String> {
@@ -728,7 +1218,7 @@ impl PyLogger {
 
     #[getter]
     fn level(&self) -> PyResult<u32> {
-        Ok(self.fast_logger.get_level() as u32)
+        Ok(self.fast_logger.get_level().value())
     }
 
     #[getter]
@@ -741,9 +1231,23 @@ impl PyLogger {
         Ok(())
     }
 
+    /// The filters added with `addFilter()`, in order. A copy: changing it doesn't
+    /// change the logger.
+    #[getter]
+    fn filters<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let filters = self.filters.lock().unwrap();
+        PyList::new(py, filters.iter().map(|f| f.clone_ref(py)))
+    }
+
     #[getter]
     fn disabled(&self) -> PyResult<bool> {
-        Ok(false)
+        Ok(self.fast_logger.is_disabled())
+    }
+
+    #[setter]
+    fn set_disabled(&self, value: bool) -> PyResult<()> {
+        self.fast_logger.set_disabled(value);
+        Ok(())
     }
 
     #[getter]
@@ -786,25 +1290,25 @@ impl PyLogger {
     }
 
     #[getter]
-    fn root(&self, py: Python) -> PyResult<PyLogger> {
+    fn root(&self, py: Python) -> PyResult<Py<PyLogger>> {
         crate::globals::get_logger(py, Some("root"), None)
     }
 
-    fn filter(&self, record: Py<PyAny>) -> PyResult<bool> {
-        Python::attach(|py| {
-            let record_bound = record.bind(py);
-            let rust_record = record_bound.extract::<LogRecord>()?;
-            let inner_logger = self.inner.lock().unwrap();
-            for filter in &inner_logger.filters {
-                if !filter.filter(&rust_record) {
-                    return Ok(false);
-                }
-            }
-            Ok(true)
-        })
+    /// Whether `record` passes this logger's filters. A filter may change `record`.
+    fn filter(&self, py: Python, record: &Bound<LogRecord>) -> bool {
+        let filters: Vec<Py<PyAny>> = {
+            let lock = self.filters.lock().unwrap();
+            lock.iter().map(|f| f.clone_ref(py)).collect()
+        };
+        let mut rust_record = record.borrow().clone();
+        if apply_filters(py, &filters, &mut rust_record).is_some() {
+            return false;
+        }
+        *record.borrow_mut() = rust_record;
+        true
     }
 
-    fn setLevel(&mut self, py: Python, level: &Bound<PyAny>) -> PyResult<()> {
+    fn setLevel(&self, py: Python, level: &Bound<PyAny>) -> PyResult<()> {
         let level_int = check_level(py, level)?;
         let level = LogLevel::from_usize(level_int as usize);
         self.fast_logger.set_level(level);
@@ -817,6 +1321,39 @@ impl PyLogger {
         Ok(self.fast_logger.get_effective_level())
     }
 
+    /// Explain how this logger is configured: its own and effective level, handlers,
+    /// filters and propagate flag, plus the ancestors a record is handed to after it
+    /// (each with its level, propagate flag and handlers), ending at the root when
+    /// propagation reaches it.
+    fn describe<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let filters: Vec<Py<PyAny>> = self
+            .filters
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|f| f.clone_ref(py))
+            .collect();
+        let ancestors = PyList::empty(py);
+        for logger in self.ancestor_chain(py)? {
+            let entry = PyDict::new(py);
+            entry.set_item("name", logger.fast_logger.name.as_ref())?;
+            entry.set_item("level", logger.fast_logger.get_level().value())?;
+            entry.set_item("propagate", *logger.propagate.lock().unwrap())?;
+            entry.set_item("handlers", logger.describe_handlers(py)?)?;
+            ancestors.append(entry)?;
+        }
+        let info = PyDict::new(py);
+        info.set_item("name", self.fast_logger.name.as_ref())?;
+        info.set_item("level", self.fast_logger.get_level().value())?;
+        info.set_item("effective_level", self.fast_logger.get_effective_level())?;
+        info.set_item("disabled", self.fast_logger.is_disabled())?;
+        info.set_item("propagate", *self.propagate.lock().unwrap())?;
+        info.set_item("handlers", self.describe_handlers(py)?)?;
+        info.set_item("filters", filters)?;
+        info.set_item("ancestors", ancestors)?;
+        Ok(info)
+    }
+
     fn addHandler(&self, _py: Python, handler: &Bound<PyAny>) -> PyResult<()> {
         add_handler_to_registry(
             handler,
@@ -839,16 +1376,16 @@ impl PyLogger {
         )
     }
 
-    /// Add a filter to this logger.
-    /// The filter can be:
-    /// - An object with a `filter(record)` method that returns True/False
-    /// - A callable that takes a record dict and returns True/False
-    ///
-    /// The record dict has keys: name, levelno, levelname, msg, pathname, lineno, func_name
-    /// Filters can modify record['msg'] to transform the log message.
+    /// Add a filter to this logger: a `logging.Filter`, any object with a
+    /// `filter(record)` method, or a callable taking the record. A false or None result
+    /// drops the record. Filters get the `LogRecord` and may change it (`record.msg`,
+    /// extras), or return a `LogRecord` to log instead. Adding the same filter twice
+    /// has no effect.
     fn addFilter(&self, py: Python, filter_obj: Py<PyAny>) -> PyResult<()> {
         let mut filters = self.filters.lock().unwrap();
-        filters.push(filter_obj.clone_ref(py));
+        if !filters.iter().any(|f| f.bind(py).is(filter_obj.bind(py))) {
+            filters.push(filter_obj);
+        }
         Ok(())
     }
 
@@ -867,10 +1404,11 @@ impl PyLogger {
         args: &Bound<PyAny>,
         kwargs: Option<&Bound<PyDict>>,
     ) -> PyResult<()> {
-        if !self.fast_logger.is_enabled_for(LogLevel::Debug) {
+        if !self.enabled_for(LogLevel::Debug) {
             return Ok(());
         }
         let extra_fields = self.extract_extra_fields(kwargs);
+        let stacklevel = PyLogger::extract_stacklevel(kwargs)?;
         let msg_str = coerce_msg_to_string(msg.bind(py))?;
         let serialized_args = self.serialize_args(py, args);
         let mut record = create_log_record_with_extra(
@@ -879,7 +1417,8 @@ impl PyLogger {
             msg_str,
             extra_fields,
         );
-        PyLogger::populate_caller_info(py, &mut record);
+        PyLogger::populate_caller_info(py, &mut record, stacklevel);
+        record.stack_info = PyLogger::extract_stack_info(py, kwargs, stacklevel)?;
         record.args = serialized_args;
         record.exc_text = self.extract_exc_info_text(py, kwargs, false);
         let exc_info_py = self.extract_exc_info_raw(py, kwargs, false);
@@ -895,10 +1434,11 @@ impl PyLogger {
         args: &Bound<PyAny>,
         kwargs: Option<&Bound<PyDict>>,
     ) -> PyResult<()> {
-        if !self.fast_logger.is_enabled_for(LogLevel::Info) {
+        if !self.enabled_for(LogLevel::Info) {
             return Ok(());
         }
         let extra_fields = self.extract_extra_fields(kwargs);
+        let stacklevel = PyLogger::extract_stacklevel(kwargs)?;
         let msg_str = coerce_msg_to_string(msg.bind(py))?;
         let serialized_args = self.serialize_args(py, args);
         let mut record = create_log_record_with_extra(
@@ -907,7 +1447,8 @@ impl PyLogger {
             msg_str,
             extra_fields,
         );
-        PyLogger::populate_caller_info(py, &mut record);
+        PyLogger::populate_caller_info(py, &mut record, stacklevel);
+        record.stack_info = PyLogger::extract_stack_info(py, kwargs, stacklevel)?;
         record.args = serialized_args;
         record.exc_text = self.extract_exc_info_text(py, kwargs, false);
         let exc_info_py = self.extract_exc_info_raw(py, kwargs, false);
@@ -923,10 +1464,11 @@ impl PyLogger {
         args: &Bound<PyAny>,
         kwargs: Option<&Bound<PyDict>>,
     ) -> PyResult<()> {
-        if !self.fast_logger.is_enabled_for(LogLevel::Warning) {
+        if !self.enabled_for(LogLevel::Warning) {
             return Ok(());
         }
         let extra_fields = self.extract_extra_fields(kwargs);
+        let stacklevel = PyLogger::extract_stacklevel(kwargs)?;
         let msg_str = coerce_msg_to_string(msg.bind(py))?;
         let serialized_args = self.serialize_args(py, args);
         let mut record = create_log_record_with_extra(
@@ -935,7 +1477,8 @@ impl PyLogger {
             msg_str,
             extra_fields,
         );
-        PyLogger::populate_caller_info(py, &mut record);
+        PyLogger::populate_caller_info(py, &mut record, stacklevel);
+        record.stack_info = PyLogger::extract_stack_info(py, kwargs, stacklevel)?;
         record.args = serialized_args;
         record.exc_text = self.extract_exc_info_text(py, kwargs, false);
         let exc_info_py = self.extract_exc_info_raw(py, kwargs, false);
@@ -951,10 +1494,11 @@ impl PyLogger {
         args: &Bound<PyAny>,
         kwargs: Option<&Bound<PyDict>>,
     ) -> PyResult<()> {
-        if !self.fast_logger.is_enabled_for(LogLevel::Error) {
+        if !self.enabled_for(LogLevel::Error) {
             return Ok(());
         }
         let extra_fields = self.extract_extra_fields(kwargs);
+        let stacklevel = PyLogger::extract_stacklevel(kwargs)?;
         let msg_str = coerce_msg_to_string(msg.bind(py))?;
         let serialized_args = self.serialize_args(py, args);
         let mut record = create_log_record_with_extra(
@@ -963,7 +1507,8 @@ impl PyLogger {
             msg_str,
             extra_fields,
         );
-        PyLogger::populate_caller_info(py, &mut record);
+        PyLogger::populate_caller_info(py, &mut record, stacklevel);
+        record.stack_info = PyLogger::extract_stack_info(py, kwargs, stacklevel)?;
         record.args = serialized_args;
         record.exc_text = self.extract_exc_info_text(py, kwargs, false);
         let exc_info_py = self.extract_exc_info_raw(py, kwargs, false);
@@ -979,10 +1524,11 @@ impl PyLogger {
         args: &Bound<PyAny>,
         kwargs: Option<&Bound<PyDict>>,
     ) -> PyResult<()> {
-        if !self.fast_logger.is_enabled_for(LogLevel::Critical) {
+        if !self.enabled_for(LogLevel::Critical) {
             return Ok(());
         }
         let extra_fields = self.extract_extra_fields(kwargs);
+        let stacklevel = PyLogger::extract_stacklevel(kwargs)?;
         let msg_str = coerce_msg_to_string(msg.bind(py))?;
         let serialized_args = self.serialize_args(py, args);
         let mut record = create_log_record_with_extra(
@@ -991,7 +1537,8 @@ impl PyLogger {
             msg_str,
             extra_fields,
         );
-        PyLogger::populate_caller_info(py, &mut record);
+        PyLogger::populate_caller_info(py, &mut record, stacklevel);
+        record.stack_info = PyLogger::extract_stack_info(py, kwargs, stacklevel)?;
         record.args = serialized_args;
         record.exc_text = self.extract_exc_info_text(py, kwargs, false);
         let exc_info_py = self.extract_exc_info_raw(py, kwargs, false);
@@ -1007,10 +1554,11 @@ impl PyLogger {
         args: &Bound<PyAny>,
         kwargs: Option<&Bound<PyDict>>,
     ) -> PyResult<()> {
-        if !self.fast_logger.is_enabled_for(LogLevel::Error) {
+        if !self.enabled_for(LogLevel::Error) {
             return Ok(());
         }
         let extra_fields = self.extract_extra_fields(kwargs);
+        let stacklevel = PyLogger::extract_stacklevel(kwargs)?;
         let msg_str = coerce_msg_to_string(msg.bind(py))?;
         let serialized_args = self.serialize_args(py, args);
         let mut record = create_log_record_with_extra(
@@ -1019,7 +1567,8 @@ impl PyLogger {
             msg_str,
             extra_fields,
         );
-        PyLogger::populate_caller_info(py, &mut record);
+        PyLogger::populate_caller_info(py, &mut record, stacklevel);
+        record.stack_info = PyLogger::extract_stack_info(py, kwargs, stacklevel)?;
         record.args = serialized_args;
         record.exc_text = self.extract_exc_info_text(py, kwargs, true);
         let exc_info_py = self.extract_exc_info_raw(py, kwargs, true);
@@ -1037,10 +1586,11 @@ impl PyLogger {
         kwargs: Option<&Bound<PyDict>>,
     ) -> PyResult<()> {
         let log_level = LogLevel::from_usize(level as usize);
-        if !self.fast_logger.is_enabled_for(log_level) {
+        if !self.enabled_for(log_level) {
             return Ok(());
         }
         let extra_fields = self.extract_extra_fields(kwargs);
+        let stacklevel = PyLogger::extract_stacklevel(kwargs)?;
         let msg_str = coerce_msg_to_string(msg.bind(py))?;
         let serialized_args = self.serialize_args(py, args);
         let mut record = create_log_record_with_extra(
@@ -1049,7 +1599,8 @@ impl PyLogger {
             msg_str,
             extra_fields,
         );
-        PyLogger::populate_caller_info(py, &mut record);
+        PyLogger::populate_caller_info(py, &mut record, stacklevel);
+        record.stack_info = PyLogger::extract_stack_info(py, kwargs, stacklevel)?;
         record.args = serialized_args;
         record.exc_text = self.extract_exc_info_text(py, kwargs, false);
         let exc_info_py = self.extract_exc_info_raw(py, kwargs, false);
@@ -1069,25 +1620,7 @@ impl PyLogger {
         args: Py<PyAny>,
         exc_info: Option<Py<PyAny>>,
     ) -> PyResult<Py<PyAny>> {
-        let logging = py.import("logging")?;
-        let log_record_cls = logging.getattr("LogRecord")?;
-
-        // Standard LogRecord constructor:
-        // name, level, pathname, lineno, msg, args, exc_info, func=None, sinfo=None
-        let args_tuple = (
-            name,
-            level,
-            fn_,
-            lno,
-            msg,
-            args,
-            exc_info,
-            py.None(), // func
-            py.None(), // sinfo
-        );
-
-        let record = log_record_cls.call1(args_tuple)?;
-        Ok(record.unbind())
+        std_log_record(py, name, level, fn_, lno, msg, args, exc_info)
     }
 
     fn handle(&self, record: Py<PyAny>) -> PyResult<()> {
@@ -1108,7 +1641,7 @@ impl PyLogger {
     }
 
     #[pyo3(signature = (suffix))]
-    fn getChild(slf: PyRef<Self>, py: Python, suffix: &str) -> PyResult<PyLogger> {
+    fn getChild(slf: PyRef<Self>, py: Python, suffix: &str) -> PyResult<Py<PyLogger>> {
         let logger_name = if slf.fast_logger.name.is_empty() {
             suffix.to_string()
         } else {
@@ -1117,6 +1650,177 @@ impl PyLogger {
         crate::globals::get_logger(py, Some(&logger_name), None)
     }
 
+    /// The existing loggers one level below this one, as a set, like
+    /// `logging.Logger.getChildren`: `app.db` and `app.api` for `app`, but not
+    /// `app.db.pool`, even while `app.db` doesn't exist.
+    fn getChildren<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PySet>> {
+        let name: &str = &self.fast_logger.name;
+        let prefix = if name == "root" {
+            String::new()
+        } else {
+            format!("{name}.")
+        };
+        let children: Vec<Py<PyLogger>> = PY_LOGGER_KEEP_ALIVE
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(child, _)| {
+                child.as_str() != "root"
+                    && child
+                        .strip_prefix(&prefix)
+                        .is_some_and(|rest| !rest.is_empty() && !rest.contains('.'))
+            })
+            .map(|(_, logger)| logger.clone_ref(py))
+            .collect();
+        PySet::new(py, children)
+    }
+
+    /// Log many pre-formed messages at one level: every record is built under this one
+    /// GIL hold (sharing caller info and `extra`), then dispatched together so each
+    /// queued handler takes the run as a single queue push.
+    #[pyo3(signature = (level, messages, extra=None))]
+    fn log_batch(
+        &self,
+        py: Python,
+        level: &Bound<PyAny>,
+        messages: &Bound<PyAny>,
+        extra: Option<&Bound<PyDict>>,
+    ) -> PyResult<()> {
+        if messages.is_instance_of::<pyo3::types::PyString>() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "log_batch() takes a list of messages, not a single str",
+            ));
+        }
+        let level = LogLevel::from_usize(check_level(py, level)? as usize);
+        if !self.enabled_for(level) {
+            return Ok(());
+        }
+        let extra_fields = match extra {
+            Some(extra) => {
+                let mut fields = HashMap::new();
+                for (key, value) in extra.iter() {
+                    fields.insert(key.str()?.to_string(), py_to_json_value(&value));
+                }
+                Some(fields)
+            }
+            None => None,
+        };
+        let extra_fields = self.with_context(extra_fields);
+        let mut records: Vec<LogRecord> = Vec::with_capacity(messages.len().unwrap_or(0));
+        for msg in messages.try_iter()? {
+            let mut record = create_log_record_with_extra(
+                self.fast_logger.name.to_string(),
+                level,
+                coerce_msg_to_string(&msg?)?,
+                extra_fields.clone(),
+            );
+            // One call site for the run: looked up for the first record, copied after.
+            match records.first() {
+                Some(caller) => {
+                    record.pathname.clone_from(&caller.pathname);
+                    record.filename.clone_from(&caller.filename);
+                    record.module.clone_from(&caller.module);
+                    record.func_name.clone_from(&caller.func_name);
+                    record.lineno = caller.lineno;
+                }
+                None => PyLogger::populate_caller_info(py, &mut record, 1),
+            }
+            records.push(record);
+        }
+        if !records.is_empty() {
+            self.dispatch_batch(py, records);
+        }
+        Ok(())
+    }
+
+    /// Time a block or function: logs "<name> started" and "<name> finished in Xms"
+    /// with the elapsed milliseconds in the `elapsed_ms` extra.
+    #[pyo3(signature = (name, level=None, extra=None, log_start=true))]
+    fn timer(
+        &self,
+        py: Python,
+        name: String,
+        level: Option<&Bound<PyAny>>,
+        extra: Option<&Bound<PyDict>>,
+        log_start: bool,
+    ) -> PyResult<crate::timer::PyTimer> {
+        let level = match level {
+            Some(level) => check_level(py, level)?,
+            None => LogLevel::Debug.value(),
+        };
+        let mut fields = HashMap::new();
+        if let Some(extra) = extra {
+            for (key, value) in extra.iter() {
+                fields.insert(key.str()?.to_string(), py_to_json_value(&value));
+            }
+        }
+        Ok(crate::timer::PyTimer::new(
+            self.clone(),
+            name,
+            LogLevel::from_usize(level as usize),
+            fields,
+            log_start,
+        ))
+    }
+
+    /// Wrap `iterable`, logging throttled progress (count, rate, ETA) every `every`
+    /// items and/or `seconds` of wall time, plus a summary when it is exhausted.
+    #[pyo3(signature = (iterable, name=None, total=None, every=None, seconds=None, level=None))]
+    fn progress(
+        &self,
+        py: Python,
+        iterable: &Bound<PyAny>,
+        name: Option<String>,
+        total: Option<u64>,
+        every: Option<u64>,
+        seconds: Option<f64>,
+        level: Option<&Bound<PyAny>>,
+    ) -> PyResult<crate::progress::PyProgress> {
+        let level = match level {
+            Some(level) => check_level(py, level)?,
+            None => LogLevel::Info.value(),
+        };
+        crate::progress::PyProgress::new(
+            self.clone(),
+            iterable,
+            name,
+            total,
+            every,
+            seconds,
+            LogLevel::from_usize(level as usize),
+        )
+    }
+
+    /// A view of this logger whose records all carry `context` as extras, on top of
+    /// any context bound already; a call's own `extra` wins over both. The view
+    /// shares the logger's level, handlers and filters, and binding is cheap: the
+    /// context is converted once and kept on the Rust side. `getChild()` on a view
+    /// returns the plain child logger.
+    #[pyo3(signature = (**context))]
+    fn bind(&self, context: Option<&Bound<PyDict>>) -> PyResult<PyLogger> {
+        let mut fields = self.context.as_deref().cloned().unwrap_or_default();
+        if let Some(context) = context {
+            for (key, value) in context.iter() {
+                fields.insert(key.str()?.to_string(), py_to_json_value(&value));
+            }
+        }
+        let mut view = self.clone();
+        view.context = Some(Arc::new(fields));
+        Ok(view)
+    }
+
+    /// The extras bound with `bind()`, as a dict (empty for the logger itself).
+    #[getter]
+    fn context<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        if let Some(context) = &self.context {
+            for (key, value) in context.iter() {
+                dict.set_item(key, crate::core::json_value_to_py_as_list(py, value)?)?;
+            }
+        }
+        Ok(dict)
+    }
+
     #[pyo3(signature = (level))]
     fn isEnabledFor(&self, level: u32) -> PyResult<bool> {
         Ok(self
@@ -0,0 +1,455 @@
+//! # Queue Diagnostics
+//!
+//! Shared bookkeeping for the bounded channels behind StreamHandler, HTTPHandler,
+//! OTLPHandler and QueueHandler: enqueued / dequeued / dropped totals, the high-water mark of the queue
+//! depth, and a histogram of `flush()` round-trip latency.
+//!
+//! Each queue is split into two lanes: records at ERROR and above go to the high
+//! priority lane, which workers always drain first, so they never wait behind a backlog
+//! of lower-level records. Order is preserved within each lane. A handler's `capacity`
+//! bounds each lane, so a queue holds up to twice that; `queue_capacity` and
+//! `queue_depth` count both lanes.
+//!
+//! Flush and stop requests travel on the normal lane behind the records they follow.
+//! The drop-oldest overflow strategy evicts records only: a request taken off the head
+//! of a full lane is queued again.
+//!
+//! Records may carry a `deadline_ms` extra: a budget for how long they may wait in the
+//! queue. Items travel through the channel as [`Queued`], tagged with the resulting
+//! deadline, and the worker counts the ones dequeued past it as `late`.
+//!
+//! A [`Queued`] item may also stand for a whole batch of records (`logger.log_batch()`),
+//! which takes one queue slot; the enqueued / dequeued / dropped totals still count
+//! records.
+//!
+//! Every queue registers itself in a process-wide registry of weak references, so
+//! `queue_stats()` reports on all live queued handlers without keeping any alive.
+
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use crate::core::{LogLevel, LogRecord};
+use crate::handler::{block_can_wait, OverflowStrategy};
+
+/// Upper bounds (inclusive, milliseconds) of the flush latency histogram buckets.
+/// One extra overflow bucket counts flushes slower than the last bound.
+pub const FLUSH_BUCKETS_MS: [u64; 8] = [1, 5, 10, 50, 100, 500, 1000, 5000];
+
+/// Name of the record extra holding the queueing budget in milliseconds.
+pub const DEADLINE_EXTRA: &str = "deadline_ms";
+
+static QUEUES: Lazy<Mutex<Vec<Weak<QueueStats>>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+pub struct QueueStats {
+    kind: &'static str,
+    capacity: usize,
+    depth: Box<dyn Fn() -> usize + Send + Sync>,
+    enqueued: AtomicU64,
+    dequeued: AtomicU64,
+    dropped: AtomicU64,
+    max_depth: AtomicU64,
+    deadline_records: AtomicU64,
+    late: AtomicU64,
+    flush_latency: [AtomicU64; FLUSH_BUCKETS_MS.len() + 1],
+}
+
+impl QueueStats {
+    /// Create stats for the lanes read by `rx` and add them to the global registry.
+    pub fn register<T: Send + 'static>(kind: &'static str, rx: LaneReceiver<T>) -> Arc<Self> {
+        let stats = Arc::new(Self {
+            kind,
+            capacity: rx.capacity(),
+            depth: Box::new(move || rx.depth()),
+            enqueued: AtomicU64::new(0),
+            dequeued: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            max_depth: AtomicU64::new(0),
+            deadline_records: AtomicU64::new(0),
+            late: AtomicU64::new(0),
+            flush_latency: Default::default(),
+        });
+        let mut queues = QUEUES.lock().unwrap();
+        queues.retain(|q| q.strong_count() > 0);
+        queues.push(Arc::downgrade(&stats));
+        stats
+    }
+
+    pub fn record_enqueued(&self, records: u64) {
+        self.enqueued.fetch_add(records, Ordering::Relaxed);
+        self.max_depth
+            .fetch_max((self.depth)() as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_dequeued(&self, records: u64) {
+        self.dequeued.fetch_add(records, Ordering::Relaxed);
+    }
+
+    /// Count `queued` as dequeued and unwrap it. Returns whether it left the queue
+    /// after its deadline.
+    pub fn dequeue<T>(&self, queued: Queued<T>) -> (T, bool) {
+        self.record_dequeued(queued.records);
+        let late = match queued.deadline {
+            Some(deadline) => {
+                self.deadline_records
+                    .fetch_add(queued.records, Ordering::Relaxed);
+                let late = Instant::now() > deadline;
+                if late {
+                    self.late.fetch_add(queued.records, Ordering::Relaxed);
+                }
+                late
+            }
+            None => false,
+        };
+        (queued.item, late)
+    }
+
+    /// In a forked child: discard what the parent had queued on `rx` (the parent still
+    /// delivers it) and start the counters over, so they describe the child alone.
+    pub fn reset_after_fork<T>(&self, rx: &LaneReceiver<T>) {
+        drop(rx.drain());
+        for counter in [
+            &self.enqueued,
+            &self.dequeued,
+            &self.dropped,
+            &self.max_depth,
+            &self.deadline_records,
+            &self.late,
+        ]
+        .into_iter()
+        .chain(&self.flush_latency)
+        {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+
+    pub fn record_drop(&self, records: u64) {
+        self.dropped.fetch_add(records, Ordering::Relaxed);
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    pub fn observe_flush(&self, started: Instant) {
+        let ms = started.elapsed().as_millis() as u64;
+        let bucket = FLUSH_BUCKETS_MS
+            .iter()
+            .position(|&bound| ms <= bound)
+            .unwrap_or(FLUSH_BUCKETS_MS.len());
+        self.flush_latency[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Write the queue counters into a metrics dict (shared by `get_metrics()` and
+    /// `queue_stats()`).
+    pub fn fill_metrics(&self, dict: &Bound<PyDict>) -> PyResult<()> {
+        let py = dict.py();
+        dict.set_item("enqueued", self.enqueued.load(Ordering::Relaxed))?;
+        dict.set_item("dequeued", self.dequeued.load(Ordering::Relaxed))?;
+        dict.set_item("queue_dropped", self.dropped())?;
+        dict.set_item("queue_depth", (self.depth)())?;
+        dict.set_item("max_queue_depth", self.max_depth.load(Ordering::Relaxed))?;
+        dict.set_item("queue_capacity", self.capacity)?;
+        dict.set_item(
+            "deadline_records",
+            self.deadline_records.load(Ordering::Relaxed),
+        )?;
+        dict.set_item("late", self.late.load(Ordering::Relaxed))?;
+
+        let histogram = PyDict::new(py);
+        for (i, count) in self.flush_latency.iter().enumerate() {
+            let label = match FLUSH_BUCKETS_MS.get(i) {
+                Some(bound) => format!("<={bound}"),
+                None => format!(">{}", FLUSH_BUCKETS_MS[FLUSH_BUCKETS_MS.len() - 1]),
+            };
+            histogram.set_item(label, count.load(Ordering::Relaxed))?;
+        }
+        dict.set_item("flush_latency_ms", histogram)?;
+        Ok(())
+    }
+}
+
+/// A channel item tagged with its lane, the instant it should have left the queue by,
+/// and how many records it carries.
+pub struct Queued<T> {
+    item: T,
+    high: bool,
+    deadline: Option<Instant>,
+    records: u64,
+}
+
+impl<T> Queued<T> {
+    /// Wrap `item`, taking the lane from the level of `record` and the deadline from
+    /// its `deadline_ms` extra.
+    pub fn new(item: T, record: &LogRecord) -> Self {
+        let deadline = record
+            .extra
+            .as_ref()
+            .and_then(|extra| extra.get(DEADLINE_EXTRA))
+            .and_then(|budget| budget.as_f64())
+            .filter(|ms| ms.is_finite() && *ms >= 0.0)
+            .map(|ms| Instant::now() + Duration::from_secs_f64(ms / 1000.0));
+        Self {
+            item,
+            high: record.levelno >= LogLevel::Error.value() as i32,
+            deadline,
+            records: 1,
+        }
+    }
+
+    /// Wrap `item` standing for `records` records that share the level and extras of
+    /// `record` (one `log_batch()` call).
+    pub fn batch(item: T, record: &LogRecord, records: usize) -> Self {
+        Self {
+            records: records as u64,
+            ..Self::new(item, record)
+        }
+    }
+
+    /// Wrap a control item that carries no records (a flush or stop request). It
+    /// travels on the normal lane and is never evicted.
+    pub fn control(item: T) -> Self {
+        Self {
+            item,
+            high: false,
+            deadline: None,
+            records: 0,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.item
+    }
+}
+
+impl Queued<Vec<Arc<LogRecord>>> {
+    /// Wrap a non-empty run of records as one item, tagged by its first record.
+    pub fn records(records: Vec<Arc<LogRecord>>) -> Self {
+        let mut queued = Self::batch(Vec::new(), &records[0], records.len());
+        queued.item = records;
+        queued
+    }
+}
+
+/// Create a two-lane queue with `capacity` slots per lane (twice `capacity` in all).
+pub fn lanes<T>(capacity: usize) -> (LaneSender<T>, LaneReceiver<T>) {
+    let (high_tx, high_rx) = crossbeam_channel::bounded(capacity);
+    let (normal_tx, normal_rx) = crossbeam_channel::bounded(capacity);
+    let rx = LaneReceiver {
+        high: high_rx,
+        normal: normal_rx,
+    };
+    let tx = LaneSender {
+        high: high_tx,
+        normal: normal_tx,
+        drop_rx: rx.clone(),
+    };
+    (tx, rx)
+}
+
+/// Producer side of a two-lane queue. Dropping it disconnects both lanes.
+pub struct LaneSender<T> {
+    high: crossbeam_channel::Sender<Queued<T>>,
+    normal: crossbeam_channel::Sender<Queued<T>>,
+    /// Used by the drop-oldest overflow strategy to evict from a full lane.
+    drop_rx: LaneReceiver<T>,
+}
+
+impl<T> LaneSender<T> {
+    /// Queue a control item behind everything already queued, waiting up to `timeout`
+    /// for room. High priority items queued before it are still received first, since
+    /// receivers drain that lane first.
+    pub fn send_control(&self, item: T, timeout: Duration) -> bool {
+        self.normal
+            .send_timeout(Queued::control(item), timeout)
+            .is_ok()
+    }
+
+    fn lane(
+        &self,
+        high: bool,
+    ) -> (
+        &crossbeam_channel::Sender<Queued<T>>,
+        &crossbeam_channel::Receiver<Queued<T>>,
+    ) {
+        if high {
+            (&self.high, &self.drop_rx.high)
+        } else {
+            (&self.normal, &self.drop_rx.normal)
+        }
+    }
+}
+
+/// Consumer side of a two-lane queue: every receive takes from the high priority lane
+/// while it has items.
+pub struct LaneReceiver<T> {
+    high: crossbeam_channel::Receiver<Queued<T>>,
+    normal: crossbeam_channel::Receiver<Queued<T>>,
+}
+
+impl<T> Clone for LaneReceiver<T> {
+    fn clone(&self) -> Self {
+        Self {
+            high: self.high.clone(),
+            normal: self.normal.clone(),
+        }
+    }
+}
+
+impl<T> LaneReceiver<T> {
+    pub fn try_recv(&self) -> Result<Queued<T>, crossbeam_channel::TryRecvError> {
+        use crossbeam_channel::TryRecvError;
+        match self.high.try_recv() {
+            Ok(item) => Ok(item),
+            Err(high) => match self.normal.try_recv() {
+                Ok(item) => Ok(item),
+                Err(TryRecvError::Disconnected) if high.is_disconnected() => {
+                    Err(TryRecvError::Disconnected)
+                }
+                Err(_) => Err(TryRecvError::Empty),
+            },
+        }
+    }
+
+    pub fn recv_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<Queued<T>, crossbeam_channel::RecvTimeoutError> {
+        use crossbeam_channel::{RecvTimeoutError, TryRecvError};
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.try_recv() {
+                Ok(item) => return Ok(item),
+                Err(TryRecvError::Disconnected) => return Err(RecvTimeoutError::Disconnected),
+                Err(TryRecvError::Empty) => {}
+            }
+            // Wait for either lane, then go back through try_recv so the high lane wins.
+            let mut select = crossbeam_channel::Select::new();
+            select.recv(&self.high);
+            select.recv(&self.normal);
+            if select.ready_deadline(deadline).is_err() {
+                return Err(RecvTimeoutError::Timeout);
+            }
+        }
+    }
+
+    /// Take everything still queued, high priority lane first.
+    pub fn drain(&self) -> Vec<T> {
+        self.high
+            .try_iter()
+            .chain(self.normal.try_iter())
+            .map(Queued::into_inner)
+            .collect()
+    }
+
+    /// Items queued across both lanes.
+    pub fn depth(&self) -> usize {
+        self.high.len() + self.normal.len()
+    }
+
+    /// Slots across both lanes.
+    pub fn capacity(&self) -> usize {
+        self.high.capacity().unwrap_or(0) + self.normal.capacity().unwrap_or(0)
+    }
+}
+
+/// Enqueue `item` on its lane honoring the overflow strategy, counting enqueues and
+/// drops.
+pub fn enqueue<T>(
+    lanes: &LaneSender<T>,
+    overflow: OverflowStrategy,
+    flush_timeout: Duration,
+    stats: &QueueStats,
+    item: Queued<T>,
+) {
+    let (sender, drop_rx) = lanes.lane(item.high);
+    let records = item.records;
+    let sent = match overflow {
+        OverflowStrategy::DropNewest => sender.try_send(item).is_ok(),
+        OverflowStrategy::DropOldest => {
+            // Flush and stop requests (items without records) taken off the head are
+            // queued again ahead of `item`, so they still follow what they followed.
+            let mut item = Some(item);
+            let mut requests = VecDeque::new();
+            let mut requeued = 0;
+            let mut sent = false;
+            while let Some(next) = requests.pop_front().or_else(|| item.take()) {
+                let request = next.records == 0;
+                match sender.try_send(next) {
+                    Ok(()) => sent |= !request,
+                    Err(crossbeam_channel::TrySendError::Full(next)) => {
+                        if request {
+                            requests.push_front(next);
+                        } else {
+                            item = Some(next);
+                        }
+                        let Ok(evicted) = drop_rx.try_recv() else {
+                            continue;
+                        };
+                        if evicted.records > 0 {
+                            stats.record_drop(evicted.records);
+                            crate::dispatch_trace::note(
+                                crate::dispatch_trace::Outcome::EvictedOlder,
+                            );
+                        } else {
+                            requests.push_back(evicted);
+                            requeued += 1;
+                            if requeued > sender.capacity().unwrap_or(0) {
+                                // The lane holds nothing but requests: drop `item`.
+                                item = None;
+                            }
+                        }
+                    }
+                    Err(crossbeam_channel::TrySendError::Disconnected(_)) => break,
+                }
+            }
+            sent
+        }
+        OverflowStrategy::Block(Some(timeout)) => sender.send_timeout(item, timeout).is_ok(),
+        OverflowStrategy::Block(None) => {
+            if block_can_wait() {
+                // Detached producer path (§4): GIL is released, so a true blocking
+                // send is safe (a same-GIL sink can still make progress) and never
+                // drops — it only errors on channel disconnect.
+                sender.send(item).is_ok()
+            } else {
+                // Attached path (GIL may be held): bound the wait by flush_timeout so a
+                // stalled same-GIL sink degrades to a counted drop instead of a
+                // deadlock. Fully GIL-safe blocking is the detached branch above.
+                sender.send_timeout(item, flush_timeout).is_ok()
+            }
+        }
+    };
+    if sent {
+        stats.record_enqueued(records);
+    } else {
+        stats.record_drop(records);
+        crate::dispatch_trace::note(crate::dispatch_trace::Outcome::QueueFull);
+    }
+}
+
+/// Snapshot the queue counters of every live queued handler.
+///
+/// Returns a list of dicts with `kind`, `enqueued`, `dequeued`, `queue_dropped`,
+/// `queue_depth`, `max_queue_depth`, `queue_capacity`, `deadline_records`, `late` and
+/// `flush_latency_ms`.
+#[pyfunction]
+pub fn queue_stats(py: Python) -> PyResult<Bound<PyList>> {
+    let live: Vec<Arc<QueueStats>> = {
+        let mut queues = QUEUES.lock().unwrap();
+        queues.retain(|q| q.strong_count() > 0);
+        queues.iter().filter_map(Weak::upgrade).collect()
+    };
+    let list = PyList::empty(py);
+    for stats in live {
+        let dict = PyDict::new(py);
+        dict.set_item("kind", stats.kind)?;
+        stats.fill_metrics(&dict)?;
+        list.append(dict)?;
+    }
+    Ok(list)
+}
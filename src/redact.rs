@@ -0,0 +1,388 @@
+//! # PII Redaction
+//!
+//! A `Redactor` rewrites personal data and secrets out of records before they are
+//! written: every match of its built-in detectors and regex rules is replaced with
+//! `replacement`. When a pattern has a capture group only the first group is replaced,
+//! so `password=(\S+)` keeps `password=` and the bearer detector keeps `Bearer`.
+//!
+//! | Detector | Matches |
+//! |----------|---------|
+//! | `email` | `user@example.com` |
+//! | `credit_card` | 13 to 19 digits, optionally grouped by spaces or dashes, passing the Luhn check |
+//! | `ssn` | US social security numbers, `123-45-6789` (never-issued areas excluded) |
+//! | `bearer` | the token after `Bearer ` |
+//!
+//! The message, every string among its `%` arguments and every string in its extras
+//! (nested in lists and mappings too) are rewritten; numbers are left as they are.
+//!
+//! `configure_redaction()` applies a redactor to every record logged through logxide
+//! loggers, before logger filters and handlers see it. A redactor added to a logxide
+//! handler with `addFilter()` runs in Rust on that handler's own copy of the record,
+//! so the other handlers still see the original.
+
+use arc_swap::ArcSwapOption;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyString};
+use regex::{Captures, Regex};
+use serde_json::Value;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::core::{JsonCache, LogRecord};
+use crate::filter::Filter;
+
+/// Applied to every record logged through logxide loggers; None when off.
+static GLOBAL: ArcSwapOption<Redactor> = ArcSwapOption::const_empty();
+
+const DEFAULT_REPLACEMENT: &str = "[REDACTED]";
+
+/// Whether a match is really the data a detector looks for (a checksum, say).
+type Check = fn(&str) -> bool;
+
+/// `(name, pattern, check)`: a match is redacted only when `check` accepts it.
+const DETECTORS: &[(&str, &str, Check)] = &[
+    (
+        "email",
+        r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}",
+        any,
+    ),
+    ("credit_card", r"\b\d(?:[ -]?\d){12,18}\b", luhn),
+    ("ssn", r"\b\d{3}-\d{2}-\d{4}\b", ssn),
+    ("bearer", r"(?i)\bbearer\s+([A-Za-z0-9\-._~+/]+=*)", any),
+];
+
+fn any(_: &str) -> bool {
+    true
+}
+
+/// Whether the digits of `candidate` pass the Luhn checksum of card numbers.
+fn luhn(candidate: &str) -> bool {
+    let digits: Vec<u32> = candidate.chars().filter_map(|c| c.to_digit(10)).collect();
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| match (i % 2, d * 2) {
+            (0, _) => d,
+            (_, doubled) if doubled > 9 => doubled - 9,
+            (_, doubled) => doubled,
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+/// Whether `candidate` (`AAA-GG-SSSS`) could be an issued SSN: no all-zero part, and
+/// an area other than 666 and 900-999.
+fn ssn(candidate: &str) -> bool {
+    let mut parts = candidate.split('-');
+    let (Some(area), Some(group), Some(serial)) = (parts.next(), parts.next(), parts.next()) else {
+        return false;
+    };
+    area != "000" && area != "666" && !area.starts_with('9') && group != "00" && serial != "0000"
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    regex: Regex,
+    check: Check,
+}
+
+/// Replaces personal data and secrets in records with a placeholder.
+///
+/// The built-in `detectors` are `"email"`, `"credit_card"` (Luhn-checked), `"ssn"` and
+/// `"bearer"` (the token after `Bearer `); `rules` adds regexes of your own. When a
+/// pattern has a capture group, only the first group is replaced. The message, its
+/// string arguments and string extras are rewritten, in Rust.
+///
+/// Pass one to `configure_redaction()` to redact every record logged through logxide
+/// loggers, or add it to a handler with `addFilter()` to redact only what that
+/// handler writes.
+///
+/// Args:
+///     detectors: Built-in detectors to run; None runs all of them.
+///     rules: Extra regexes, in the syntax of the Rust `regex` crate.
+///     replacement: Text each match is replaced with.
+#[pyclass(name = "Redactor", module = "logxide", frozen, skip_from_py_object)]
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    detectors: Vec<&'static str>,
+    patterns: Vec<String>,
+    rules: Vec<Rule>,
+    replacement: String,
+}
+
+impl Redactor {
+    /// `text` with every match replaced, or None when nothing matched.
+    pub fn text(&self, text: &str) -> Option<String> {
+        let mut current = Cow::Borrowed(text);
+        for rule in &self.rules {
+            if !rule.regex.is_match(&current) {
+                continue;
+            }
+            let replaced = rule.regex.replace_all(&current, |caps: &Captures| {
+                let whole = caps.get(0).expect("group 0 is the match");
+                if !(rule.check)(whole.as_str()) {
+                    return whole.as_str().to_string();
+                }
+                match caps.get(1) {
+                    Some(group) => format!(
+                        "{}{}{}",
+                        &whole.as_str()[..group.start() - whole.start()],
+                        self.replacement,
+                        &whole.as_str()[group.end() - whole.start()..]
+                    ),
+                    None => self.replacement.clone(),
+                }
+            });
+            if replaced != current {
+                current = Cow::Owned(replaced.into_owned());
+            }
+        }
+        match current {
+            Cow::Owned(text) => Some(text),
+            Cow::Borrowed(_) => None,
+        }
+    }
+
+    /// Redact the strings in `value`, however deeply nested; true when any changed.
+    fn value(&self, value: &mut Value) -> bool {
+        match value {
+            Value::String(text) => match self.text(text) {
+                Some(redacted) => {
+                    *text = redacted;
+                    true
+                }
+                None => false,
+            },
+            Value::Array(items) => items
+                .iter_mut()
+                .fold(false, |changed, item| self.value(item) | changed),
+            Value::Object(fields) => fields
+                .values_mut()
+                .fold(false, |changed, item| self.value(item) | changed),
+            _ => false,
+        }
+    }
+
+    /// Redact the message, arguments and extras of `record`; true when any changed.
+    pub fn apply(&self, record: &mut LogRecord) -> bool {
+        self.matches(record) && self.rewrite_fields(record)
+    }
+
+    fn rewrite_fields(&self, record: &mut LogRecord) -> bool {
+        let mut changed = false;
+        if let Some(msg) = self.text(&record.msg) {
+            record.msg = msg;
+            changed = true;
+        }
+        if let Some(args) = record.args.as_mut() {
+            let mut redacted = Value::clone(args);
+            if self.value(&mut redacted) {
+                *args = Arc::new(redacted);
+                changed = true;
+            }
+        }
+        if let Some(extra) = record.extra.as_mut() {
+            for value in extra.values_mut() {
+                changed |= self.value(value);
+            }
+        }
+        changed
+    }
+
+    /// Whether any rule matches `record`, so it is worth copying to redact.
+    fn matches(&self, record: &LogRecord) -> bool {
+        let matches = |text: &str| self.rules.iter().any(|rule| rule.regex.is_match(text));
+        fn strings<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
+            match value {
+                Value::String(text) => out.push(text),
+                Value::Array(items) => items.iter().for_each(|item| strings(item, out)),
+                Value::Object(fields) => fields.values().for_each(|item| strings(item, out)),
+                _ => {}
+            }
+        }
+        let mut texts = vec![record.msg.as_str()];
+        if let Some(args) = record.args.as_deref() {
+            strings(args, &mut texts);
+        }
+        for value in record.extra.iter().flat_map(|extra| extra.values()) {
+            strings(value, &mut texts);
+        }
+        texts.into_iter().any(matches)
+    }
+
+    /// Redact a record from Python that is not a logxide `LogRecord`: its formatted
+    /// message, and the strings among its other attributes (the extras).
+    fn apply_foreign(&self, record: &Bound<PyAny>) -> PyResult<()> {
+        let py = record.py();
+        let message = record.call_method0(pyo3::intern!(py, "getMessage"))?;
+        if let Some(redacted) = self.text(&message.str()?.to_cow()?) {
+            record.setattr(pyo3::intern!(py, "msg"), redacted)?;
+            record.setattr(pyo3::intern!(py, "args"), py.None())?;
+        }
+        let Ok(attrs) = record.getattr(pyo3::intern!(py, "__dict__")) else {
+            return Ok(());
+        };
+        let attrs = attrs.cast_into::<PyDict>()?;
+        for (key, value) in attrs.copy()?.iter() {
+            let Ok(text) = value.cast::<PyString>() else {
+                continue;
+            };
+            if RECORD_ATTRS.contains(&&*key.str()?.to_cow()?) {
+                continue;
+            }
+            if let Some(redacted) = self.text(&text.to_cow()?) {
+                attrs.set_item(key, redacted)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Attributes every `logging.LogRecord` has, which are not extras.
+const RECORD_ATTRS: &[&str] = &[
+    "name",
+    "msg",
+    "levelname",
+    "pathname",
+    "filename",
+    "module",
+    "exc_text",
+    "stack_info",
+    "funcName",
+    "threadName",
+    "processName",
+    "taskName",
+    "message",
+    "asctime",
+];
+
+impl Filter for Redactor {
+    fn filter(&self, _record: &LogRecord) -> bool {
+        true
+    }
+
+    fn rewrite(&self, record: &LogRecord) -> Option<LogRecord> {
+        if !self.matches(record) {
+            return None;
+        }
+        let mut copy = record.detached();
+        self.rewrite_fields(&mut copy).then_some(copy)
+    }
+}
+
+#[pymethods]
+impl Redactor {
+    #[new]
+    #[pyo3(signature = (detectors=None, rules=Vec::new(), replacement=DEFAULT_REPLACEMENT.to_string()))]
+    fn new(
+        detectors: Option<Vec<String>>,
+        rules: Vec<String>,
+        replacement: String,
+    ) -> PyResult<Self> {
+        let names: Vec<&'static str> = match detectors {
+            None => DETECTORS.iter().map(|(name, _, _)| *name).collect(),
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    DETECTORS
+                        .iter()
+                        .find(|(known, _, _)| known == name)
+                        .map(|(known, _, _)| *known)
+                        .ok_or_else(|| {
+                            let known: Vec<_> = DETECTORS.iter().map(|(n, _, _)| *n).collect();
+                            PyValueError::new_err(format!(
+                                "unknown detector {name:?}: expected one of {}",
+                                known.join(", ")
+                            ))
+                        })
+                })
+                .collect::<PyResult<_>>()?,
+        };
+        let mut compiled = Vec::with_capacity(names.len() + rules.len());
+        for (name, pattern, check) in DETECTORS {
+            if names.contains(name) {
+                let regex = Regex::new(pattern).expect("built-in detector pattern");
+                compiled.push(Rule {
+                    regex,
+                    check: *check,
+                });
+            }
+        }
+        for pattern in &rules {
+            let regex = Regex::new(pattern).map_err(|e| {
+                PyValueError::new_err(format!("invalid redaction rule {pattern:?}: {e}"))
+            })?;
+            compiled.push(Rule { regex, check: any });
+        }
+        Ok(Self {
+            detectors: names,
+            patterns: rules,
+            rules: compiled,
+            replacement,
+        })
+    }
+
+    #[getter]
+    fn detectors(&self) -> Vec<&'static str> {
+        self.detectors.clone()
+    }
+
+    #[getter]
+    fn rules(&self) -> Vec<String> {
+        self.patterns.clone()
+    }
+
+    #[getter]
+    fn replacement(&self) -> &str {
+        &self.replacement
+    }
+
+    /// `text` with every match replaced.
+    fn redact(&self, text: &str) -> String {
+        self.text(text).unwrap_or_else(|| text.to_string())
+    }
+
+    /// Redact `record` in place and pass it, for loggers, Python handlers and handlers
+    /// that fall back to Python dispatch.
+    fn filter(&self, record: &Bound<PyAny>) -> PyResult<bool> {
+        match record.cast::<LogRecord>() {
+            Ok(record) => {
+                let mut record = record.borrow_mut();
+                if self.apply(&mut record) {
+                    // Its encodings may be shared with the record it was copied from.
+                    record.json_cache = JsonCache::default();
+                }
+            }
+            Err(_) => self.apply_foreign(record)?,
+        }
+        Ok(true)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<Redactor detectors={:?} rules={}>",
+            self.detectors,
+            self.patterns.len()
+        )
+    }
+}
+
+/// Redact `record` with the redactor set by `configure_redaction()`, if any.
+pub fn apply(record: &mut LogRecord) {
+    if let Some(redactor) = &*GLOBAL.load() {
+        redactor.apply(record);
+    }
+}
+
+/// Redact every record logged through logxide loggers with `redactor`, before logger
+/// filters and handlers see it.
+///
+/// Args:
+///     redactor: A `Redactor`, or None to stop redacting.
+#[pyfunction]
+#[pyo3(signature = (redactor=None))]
+pub fn configure_redaction(redactor: Option<Bound<'_, Redactor>>) {
+    GLOBAL.store(redactor.map(|redactor| Arc::new(redactor.get().clone())));
+}
@@ -0,0 +1,83 @@
+//! # Backup Retention
+//!
+//! Age and size limits for the backups of the rotating file handlers, applied after
+//! each rollover on top of `backupCount`: `max_age_days` deletes backups last modified
+//! longer ago than that, and `max_total_bytes` deletes the oldest backups until the
+//! log file and its backups fit in the budget. The live log file is never deleted.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Retention {
+    max_age: Option<Duration>,
+    max_total_bytes: Option<u64>,
+}
+
+impl Retention {
+    pub fn new(max_age_days: Option<f64>, max_total_bytes: Option<u64>) -> Result<Self, String> {
+        let max_age = match max_age_days {
+            Some(days) if !(days.is_finite() && days > 0.0) => {
+                return Err("max_age_days must be a positive number".to_string())
+            }
+            Some(days) => Some(Duration::from_secs_f64(days * 86_400.0)),
+            None => None,
+        };
+        if max_total_bytes == Some(0) {
+            return Err("max_total_bytes must be positive".to_string());
+        }
+        Ok(Self {
+            max_age,
+            max_total_bytes,
+        })
+    }
+
+    /// Delete the backups of the log file at `path` that fall outside the limits.
+    /// `backups` lists them oldest first.
+    pub fn apply(&self, path: &Path, backups: &[PathBuf]) {
+        if self.max_age.is_none() && self.max_total_bytes.is_none() {
+            return;
+        }
+        let now = SystemTime::now();
+        let mut kept = Vec::with_capacity(backups.len());
+        for backup in backups {
+            let Ok(meta) = std::fs::metadata(backup) else {
+                continue;
+            };
+            let expired = self.max_age.is_some_and(|max_age| {
+                meta.modified()
+                    .ok()
+                    .and_then(|modified| now.duration_since(modified).ok())
+                    .is_some_and(|age| age > max_age)
+            });
+            if expired {
+                remove(backup);
+            } else {
+                kept.push((backup, meta.len()));
+            }
+        }
+        if let Some(budget) = self.max_total_bytes {
+            let live = std::fs::metadata(path).map_or(0, |meta| meta.len());
+            let mut total = live + kept.iter().map(|(_, len)| len).sum::<u64>();
+            for (backup, len) in kept {
+                if total <= budget {
+                    break;
+                }
+                remove(backup);
+                total -= len;
+            }
+        }
+    }
+}
+
+fn remove(backup: &Path) {
+    crate::index::remove(backup);
+    if let Err(e) = std::fs::remove_file(backup) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            eprintln!(
+                "[LogXide Error] failed to delete expired backup {}: {e}",
+                backup.display()
+            );
+        }
+    }
+}
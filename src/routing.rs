@@ -0,0 +1,206 @@
+//! # Namespace Routes
+//!
+//! A route gives a logger namespace (`botocore`: that logger and every logger below
+//! it) a handler set of its own without creating a logger for it. Records from the
+//! namespace reach the route's handlers after those of the namespace's own logger, if
+//! one exists, and a terminal route stops propagation there, so third-party records
+//! never reach the root's handlers. One `route()` call, or a `routes` section in a
+//! `dictConfig` dict or YAML file, replaces a logger per namespace with
+//! `propagate = False`.
+//!
+//! The table is copy-on-write: the propagation walk reads a snapshot with one atomic
+//! load, and skips the lookups entirely while no route is set.
+
+use arc_swap::ArcSwap;
+use once_cell::sync::Lazy;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyString};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::globals::{self, PyEntry, RustEntry, HANDLER_OBJECTS};
+use crate::handler::Handler;
+
+/// A namespace's handler lists, laid out like a logger's so the propagation walk can
+/// visit it as one more hop.
+pub(crate) struct Route {
+    pub(crate) rust_dispatch: Arc<Mutex<Vec<RustEntry>>>,
+    pub(crate) py_dispatch: Arc<Mutex<Vec<PyEntry>>>,
+    lifecycle: Mutex<Vec<Arc<dyn Handler + Send + Sync>>>,
+    /// Stop propagation after the route's handlers.
+    pub(crate) terminal: bool,
+}
+
+static ROUTES: Lazy<ArcSwap<HashMap<String, Arc<Route>>>> = Lazy::new(Default::default);
+
+/// The route for exactly `namespace`, if any.
+pub(crate) fn get(routes: &HashMap<String, Arc<Route>>, namespace: &str) -> Option<Arc<Route>> {
+    if routes.is_empty() {
+        return None;
+    }
+    routes.get(namespace).cloned()
+}
+
+/// The current route table.
+pub(crate) fn table() -> arc_swap::Guard<Arc<HashMap<String, Arc<Route>>>> {
+    ROUTES.load()
+}
+
+impl Route {
+    /// The route's handlers as (identity, object they were added as).
+    pub(crate) fn handler_objects(&self, py: Python) -> Vec<(usize, Option<Py<PyAny>>)> {
+        let mut handlers: Vec<(usize, Option<Py<PyAny>>)> = {
+            let objects = HANDLER_OBJECTS.lock().unwrap();
+            self.rust_dispatch
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|e| (e.id, objects.get(&e.id).map(|o| o.clone_ref(py))))
+                .collect()
+        };
+        handlers.extend(
+            self.py_dispatch
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|e| (e.id, Some(e.obj.clone_ref(py)))),
+        );
+        handlers
+    }
+}
+
+/// Detach and shut down a route's handlers.
+fn clear(py: Python, namespace: &str, route: &Route) {
+    globals::clear_logger_handlers(
+        py,
+        namespace,
+        &route.rust_dispatch,
+        &route.py_dispatch,
+        &route.lifecycle,
+    );
+}
+
+/// `namespaces` as a list of names: one string, or an iterable of them.
+fn namespace_list(namespaces: &Bound<PyAny>) -> PyResult<Vec<String>> {
+    let names: Vec<String> = match namespaces.cast::<PyString>() {
+        Ok(name) => vec![name.to_string()],
+        Err(_) => namespaces.extract()?,
+    };
+    if let Some(bad) = names.iter().find(|n| n.is_empty() || n.as_str() == "root") {
+        return Err(PyValueError::new_err(format!(
+            "cannot route {bad:?}: a namespace is a logger name other than root"
+        )));
+    }
+    Ok(names)
+}
+
+/// Send the records of one or more logger namespaces to their own handlers.
+///
+/// Records from each namespace (the logger of that name and every logger below it)
+/// are handed to `handlers` after the namespace's own logger, if it exists. With
+/// `terminal=True` they go no further, so they never reach the root's handlers; no
+/// handlers and `terminal=True` drops them from every sink above the namespace. A
+/// namespace that already has a route gets the new one, and the old route's handlers
+/// are closed.
+///
+/// Args:
+///     namespaces: A logger name, or a list of them.
+///     handlers: Handlers for the namespaces' records.
+///     terminal: Stop propagation after these handlers.
+#[pyfunction]
+#[pyo3(signature = (namespaces, handlers=Vec::new(), terminal=true))]
+pub fn route(
+    py: Python,
+    namespaces: &Bound<PyAny>,
+    handlers: Vec<Bound<PyAny>>,
+    terminal: bool,
+) -> PyResult<()> {
+    let names = namespace_list(namespaces)?;
+    let mut built = Vec::with_capacity(names.len());
+    for namespace in names {
+        let route = Route {
+            rust_dispatch: Default::default(),
+            py_dispatch: Default::default(),
+            lifecycle: Default::default(),
+            terminal,
+        };
+        for handler in &handlers {
+            globals::add_handler_to_registry(
+                handler,
+                &namespace,
+                &route.rust_dispatch,
+                &route.py_dispatch,
+                &route.lifecycle,
+            )?;
+        }
+        built.push((namespace, Arc::new(route)));
+    }
+    let mut table = HashMap::clone(&ROUTES.load());
+    let mut replaced = Vec::new();
+    for (namespace, route) in built {
+        if let Some(old) = table.insert(namespace.clone(), route) {
+            replaced.push((namespace, old));
+        }
+    }
+    ROUTES.store(Arc::new(table));
+    for (namespace, old) in replaced {
+        clear(py, &namespace, &old);
+    }
+    Ok(())
+}
+
+/// Remove the routes of `namespaces` and close their handlers; their records
+/// propagate as usual again. Namespaces without a route are ignored.
+///
+/// Args:
+///     namespaces: A logger name, or a list of them.
+#[pyfunction]
+pub fn unroute(py: Python, namespaces: &Bound<PyAny>) -> PyResult<()> {
+    let names = namespace_list(namespaces)?;
+    let mut table = HashMap::clone(&ROUTES.load());
+    let removed: Vec<(String, Arc<Route>)> = names
+        .into_iter()
+        .filter_map(|namespace| {
+            let route = table.remove(&namespace)?;
+            Some((namespace, route))
+        })
+        .collect();
+    ROUTES.store(Arc::new(table));
+    for (namespace, route) in removed {
+        clear(py, &namespace, &route);
+    }
+    Ok(())
+}
+
+/// Remove every route and close its handlers (`dictConfig`, `reinitialize()`).
+pub(crate) fn clear_all(py: Python) {
+    let table = ROUTES.swap(Default::default());
+    for (namespace, route) in table.iter() {
+        clear(py, namespace, route);
+    }
+}
+
+/// The routes set with `route()` or a `dictConfig` `routes` section.
+///
+/// Returns:
+///     dict: namespace -> `{"handlers": [...], "terminal": bool}`.
+#[pyfunction]
+pub fn routes(py: Python) -> PyResult<Bound<PyDict>> {
+    let table = ROUTES.load_full();
+    let dict = PyDict::new(py);
+    let mut namespaces: Vec<&String> = table.keys().collect();
+    namespaces.sort();
+    for namespace in namespaces {
+        let route = &table[namespace];
+        let handlers = PyList::empty(py);
+        for (_, obj) in route.handler_objects(py) {
+            handlers.append(obj)?;
+        }
+        let info = PyDict::new(py);
+        info.set_item("handlers", handlers)?;
+        info.set_item("terminal", route.terminal)?;
+        dict.set_item(namespace, info)?;
+    }
+    Ok(dict)
+}
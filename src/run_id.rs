@@ -0,0 +1,73 @@
+//! # Run Id
+//!
+//! A random UUID generated once per process and attached to every record as the
+//! `run_id` extra, so the records of one process instance can be told apart from
+//! those of the same service before or after a restart, whichever sink they end up in.
+//! A child created by `os.fork()` gets an id of its own.
+//!
+//! Applied to records logged through logxide loggers before logger filters and
+//! handlers see them. On by default; a record that already has a `run_id` extra keeps
+//! its own.
+
+use arc_swap::ArcSwap;
+use once_cell::sync::Lazy;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::core::LogRecord;
+use crate::fork::ForkHook;
+use crate::handler::random_uuid;
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+struct RunId {
+    id: ArcSwap<String>,
+    _fork_hook: Arc<ForkHook>,
+}
+
+static RUN_ID: Lazy<RunId> = Lazy::new(|| RunId {
+    id: ArcSwap::from_pointee(random_uuid()),
+    _fork_hook: ForkHook::default()
+        .with_in_child(|| RUN_ID.id.store(Arc::new(random_uuid())))
+        .register(),
+});
+
+/// Attach the `run_id` extra to `record`, unless turned off.
+pub fn apply(record: &mut LogRecord) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    record
+        .extra
+        .get_or_insert_with(Default::default)
+        .entry("run_id".to_string())
+        .or_insert_with(|| Value::String(RUN_ID.id.load().to_string()));
+}
+
+/// Attach this process's run id to every record as the `run_id` extra.
+///
+/// Args:
+///     enabled: Attach it to records logged from now on; False turns it off.
+///     run_id: Use this id for the process instead of the generated one; None keeps
+///         the current id.
+#[pyfunction]
+#[pyo3(signature = (enabled=true, run_id=None))]
+pub fn configure_run_id(enabled: bool, run_id: Option<String>) -> PyResult<()> {
+    if let Some(run_id) = run_id {
+        if run_id.is_empty() {
+            return Err(PyValueError::new_err("run_id must not be empty"));
+        }
+        RUN_ID.id.store(Arc::new(run_id));
+    }
+    ENABLED.store(enabled, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Return the run id of this process, as attached to its records.
+#[pyfunction]
+pub fn run_id() -> String {
+    RUN_ID.id.load().to_string()
+}
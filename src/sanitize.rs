@@ -0,0 +1,153 @@
+//! # Text Sanitization
+//!
+//! Per-handler cleanup of formatted text before it reaches a sink. Control characters
+//! (C0 other than newline and tab, DEL and C1) can rewrite terminal output or forge
+//! log lines, and some collectors reject them outright; a handler's policy escapes
+//! them (`\x1b`) or strips them. With `newlines=False` newline, carriage return and
+//! tab are treated the same way, so every record stays on one line.
+//!
+//! Rust strings cannot hold lone surrogates, so [`py_text`] replaces each one with
+//! U+FFFD when Python text is read into a record. The policies treat U+FFFD like a
+//! control character: `escape` writes it as `\ufffd` and `strip` removes it.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString};
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SanitizeMode {
+    /// Write the text unchanged.
+    Off = 0,
+    /// Replace offending characters with backslash escapes.
+    Escape = 1,
+    /// Remove offending characters; newlines and tabs become spaces.
+    Strip = 2,
+}
+
+/// A handler's sanitization policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SanitizePolicy {
+    pub mode: SanitizeMode,
+    /// Keep `\n` and `\t` as they are (multi-line tracebacks stay readable).
+    pub newlines: bool,
+}
+
+impl SanitizePolicy {
+    pub const OFF: SanitizePolicy = SanitizePolicy {
+        mode: SanitizeMode::Off,
+        newlines: true,
+    };
+
+    /// Parse the Python-facing policy name: None, `"escape"` or `"strip"`.
+    pub fn new(mode: Option<&str>, newlines: bool) -> Result<Self, String> {
+        let mode = match mode {
+            None => SanitizeMode::Off,
+            Some("escape") => SanitizeMode::Escape,
+            Some("strip") => SanitizeMode::Strip,
+            Some(other) => {
+                return Err(format!(
+                    "unknown sanitize policy {other:?}: expected None, \"escape\" or \"strip\""
+                ))
+            }
+        };
+        Ok(Self { mode, newlines })
+    }
+
+    fn to_bits(self) -> u8 {
+        self.mode as u8 | if self.newlines { 0 } else { 0x80 }
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        let mode = match bits & 0x7f {
+            1 => SanitizeMode::Escape,
+            2 => SanitizeMode::Strip,
+            _ => SanitizeMode::Off,
+        };
+        Self {
+            mode,
+            newlines: bits & 0x80 == 0,
+        }
+    }
+
+    fn offending(self, c: char) -> bool {
+        match c {
+            '\n' | '\t' => !self.newlines,
+            '\u{fffd}' => true,
+            c => c.is_control(),
+        }
+    }
+
+    /// `text` with the offending characters escaped or removed.
+    pub fn apply(self, text: String) -> String {
+        if self.mode == SanitizeMode::Off || !text.chars().any(|c| self.offending(c)) {
+            return text;
+        }
+        let mut out = String::with_capacity(text.len() + 8);
+        for c in text.chars() {
+            if !self.offending(c) {
+                out.push(c);
+                continue;
+            }
+            match (self.mode, c) {
+                (SanitizeMode::Strip, '\n' | '\r' | '\t') => out.push(' '),
+                (SanitizeMode::Strip, _) => {}
+                (_, '\n') => out.push_str("\\n"),
+                (_, '\r') => out.push_str("\\r"),
+                (_, '\t') => out.push_str("\\t"),
+                (_, c) if (c as u32) < 0x100 => {
+                    let _ = write!(out, "\\x{:02x}", c as u32);
+                }
+                (_, c) => {
+                    let _ = write!(out, "\\u{:04x}", c as u32);
+                }
+            }
+        }
+        out
+    }
+}
+
+impl Default for SanitizePolicy {
+    fn default() -> Self {
+        Self::OFF
+    }
+}
+
+/// A handler's policy, changeable while the handler is in use.
+#[derive(Debug, Default)]
+pub struct SanitizeSlot(AtomicU8);
+
+impl SanitizeSlot {
+    pub fn get(&self) -> SanitizePolicy {
+        SanitizePolicy::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    pub fn set(&self, policy: SanitizePolicy) {
+        self.0.store(policy.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn apply(&self, text: String) -> String {
+        self.get().apply(text)
+    }
+}
+
+/// The text of a Python string, with each lone surrogate replaced by U+FFFD.
+pub fn py_text(s: &Bound<PyString>) -> String {
+    if let Ok(text) = s.to_cow() {
+        return text.into_owned();
+    }
+    let units = s
+        .call_method1("encode", ("utf-16-le", "surrogatepass"))
+        .ok()
+        .and_then(|bytes| bytes.cast_into::<PyBytes>().ok())
+        .map(|bytes| {
+            bytes
+                .as_bytes()
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .collect::<Vec<u16>>()
+        })
+        .unwrap_or_default();
+    String::from_utf16_lossy(&units)
+}
@@ -0,0 +1,133 @@
+//! # Schema Versions
+//!
+//! `JsonFormatter(schema_version=N)` starts every line with `"schema_version": N`, so
+//! whoever reads the logs can tell which layout a line was written in. When the layout
+//! changes (a field renamed, split or dropped), the application registers a migration
+//! from the old version to the next: a function that takes a record as a dict and
+//! returns it in the new layout. `migrate_record()` runs the chain of migrations up to
+//! a target version, and `convert_log_file(schema_version=...)` (`python -m logxide
+//! convert --schema-version`) applies it to every record of a historical file.
+//!
+//! Versions are integers, and a migration registered for version N turns N into N + 1.
+//! A record without a `schema_version` field is version 0.
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// Migration from each version to the next, by the version it starts from.
+static MIGRATIONS: Mutex<BTreeMap<u32, Py<PyAny>>> = Mutex::new(BTreeMap::new());
+
+/// The field that holds a record's schema version.
+pub const FIELD: &str = "schema_version";
+
+/// The schema version `record` says it is in.
+fn version_of(record: &Map<String, Value>) -> PyResult<u32> {
+    match record.get(FIELD) {
+        None | Some(Value::Null) => Ok(0),
+        Some(value) => value
+            .as_u64()
+            .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| {
+                PyValueError::new_err(format!("{FIELD} must be a whole number, not {value}"))
+            }),
+    }
+}
+
+/// Bring `record` to schema version `to` through the registered migrations, one
+/// version at a time, and set its `schema_version` to `to`.
+pub(crate) fn migrate(
+    py: Python,
+    mut record: Map<String, Value>,
+    to: u32,
+) -> PyResult<Map<String, Value>> {
+    let mut version = version_of(&record)?;
+    if version > to {
+        return Err(PyValueError::new_err(format!(
+            "record has {FIELD} {version}, newer than {to}"
+        )));
+    }
+    while version < to {
+        // Not held while the migration runs, so it may register others.
+        let migration = MIGRATIONS
+            .lock()
+            .unwrap()
+            .get(&version)
+            .map(|migration| migration.clone_ref(py));
+        let Some(migration) = migration else {
+            return Err(PyValueError::new_err(format!(
+                "no schema migration registered from version {version}"
+            )));
+        };
+        let fields = crate::core::json_value_to_py_as_list(py, &Value::Object(record))?;
+        let result = migration.call1(py, (fields.clone_ref(py),))?;
+        // A migration that changes the dict in place may return None.
+        let result = if result.is_none(py) { fields } else { result };
+        record = match crate::py_logger::py_to_json_value(result.bind(py)) {
+            Value::Object(record) => record,
+            _ => {
+                return Err(PyTypeError::new_err(format!(
+                    "schema migration from version {version} must return a dict"
+                )))
+            }
+        };
+        version += 1;
+        record.insert(FIELD.to_string(), version.into());
+    }
+    Ok(record)
+}
+
+/// Register the migration from schema version `from_version` to the next.
+///
+/// `migrate` is called with a record as a dict (the keys `JsonFormatter` writes) and
+/// returns the record in the layout of `from_version + 1`, or None after changing the
+/// dict in place. `schema_version` is updated afterwards. Registering a second
+/// migration for the same version replaces the first.
+///
+/// Args:
+///     from_version: The version the migration reads.
+///     migrate: Callable taking and returning a dict.
+#[pyfunction]
+pub fn register_schema_migration(from_version: u32, migrate: Bound<PyAny>) -> PyResult<()> {
+    if !migrate.is_callable() {
+        return Err(PyTypeError::new_err("migrate must be callable"));
+    }
+    MIGRATIONS
+        .lock()
+        .unwrap()
+        .insert(from_version, migrate.unbind());
+    Ok(())
+}
+
+/// The registered migrations, as {from_version: migrate}.
+#[pyfunction]
+pub fn schema_migrations(py: Python) -> PyResult<Bound<PyDict>> {
+    let migrations = PyDict::new(py);
+    for (version, migrate) in MIGRATIONS.lock().unwrap().iter() {
+        migrations.set_item(version, migrate.clone_ref(py))?;
+    }
+    Ok(migrations)
+}
+
+/// Bring a record to schema version `to_version` through the registered migrations.
+///
+/// `record` is a dict such as a parsed `JsonFormatter` line; one without a
+/// `schema_version` is version 0. Returns a new dict with `schema_version` set to
+/// `to_version`. Raises ValueError when a migration along the way is missing or the
+/// record is newer than `to_version`.
+#[pyfunction]
+pub fn migrate_record<'py>(
+    py: Python<'py>,
+    record: &Bound<'py, PyDict>,
+    to_version: u32,
+) -> PyResult<Bound<'py, PyAny>> {
+    let Value::Object(fields) = crate::py_logger::py_to_json_value(record.as_any()) else {
+        return Err(PyTypeError::new_err("record must be a dict"));
+    };
+    let migrated = migrate(py, fields, to_version)?;
+    Ok(crate::core::json_value_to_py_as_list(py, &Value::Object(migrated))?.into_bound(py))
+}
@@ -0,0 +1,109 @@
+//! # Secret Keys
+//!
+//! Scrubbing keyed on field names rather than on values: every `extra` field whose
+//! name is on the denylist has its value replaced with `***`, and so does every
+//! matching key in a dict nested inside `extra` (lists of dicts included). Names are
+//! compared ignoring case, with `-` taken as `_`, so `Authorization` and `api-key`
+//! match `authorization` and `api_key`.
+//!
+//! Applied to records logged through logxide loggers before logger filters and
+//! handlers see them, so every formatter and network handler gets the scrubbed
+//! values. Off by default; independent of the value patterns of
+//! [`Redactor`](crate::redact::Redactor).
+
+use arc_swap::ArcSwapOption;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::core::LogRecord;
+
+/// Keys scrubbed when `configure_secret_keys()` is given none.
+const DEFAULT_KEYS: &[&str] = &["password", "authorization", "api_key"];
+
+const DEFAULT_REPLACEMENT: &str = "***";
+
+/// The denylist in effect; None when off.
+static SECRET_KEYS: ArcSwapOption<SecretKeys> = ArcSwapOption::const_empty();
+
+struct SecretKeys {
+    /// Normalized with [`normalize`].
+    keys: HashSet<String>,
+    replacement: String,
+}
+
+fn normalize(key: &str) -> String {
+    key.to_ascii_lowercase().replace('-', "_")
+}
+
+impl SecretKeys {
+    fn is_secret(&self, key: &str) -> bool {
+        self.keys.contains(&normalize(key))
+    }
+
+    /// Replace the values of secret keys nested anywhere in `value`.
+    fn scrub(&self, value: &mut Value) {
+        match value {
+            Value::Object(fields) => {
+                for (key, field) in fields.iter_mut() {
+                    if self.is_secret(key) {
+                        *field = Value::String(self.replacement.clone());
+                    } else {
+                        self.scrub(field);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.scrub(item)),
+            _ => {}
+        }
+    }
+}
+
+/// Scrub the extras of `record` with the denylist set by `configure_secret_keys()`.
+pub fn apply(record: &mut LogRecord) {
+    let (Some(secrets), Some(extra)) = (&*SECRET_KEYS.load(), record.extra.as_mut()) else {
+        return;
+    };
+    for (key, value) in extra.iter_mut() {
+        if secrets.is_secret(key) {
+            *value = Value::String(secrets.replacement.clone());
+        } else {
+            secrets.scrub(value);
+        }
+    }
+}
+
+/// Replace the values of `extra` fields with secret names, at any depth, before any
+/// formatter or handler sees them.
+///
+/// Names are compared ignoring case, with `-` read as `_`. The whole value is
+/// replaced, whatever its type.
+///
+/// Args:
+///     enabled: Scrub records logged from now on; False turns scrubbing off.
+///     keys: Field names to scrub; None means `password`, `authorization` and
+///         `api_key`.
+///     replacement: Text the values are replaced with.
+#[pyfunction]
+#[pyo3(signature = (enabled=true, keys=None, replacement=DEFAULT_REPLACEMENT.to_string()))]
+pub fn configure_secret_keys(
+    enabled: bool,
+    keys: Option<Vec<String>>,
+    replacement: String,
+) -> PyResult<()> {
+    if !enabled {
+        SECRET_KEYS.store(None);
+        return Ok(());
+    }
+    let keys: HashSet<String> = match keys {
+        Some(keys) => keys.iter().map(|key| normalize(key)).collect(),
+        None => DEFAULT_KEYS.iter().map(|key| key.to_string()).collect(),
+    };
+    if keys.contains("") {
+        return Err(PyValueError::new_err("secret keys must not be empty"));
+    }
+    SECRET_KEYS.store(Some(Arc::new(SecretKeys { keys, replacement })));
+    Ok(())
+}
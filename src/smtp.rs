@@ -0,0 +1,377 @@
+//! # SMTP Client
+//!
+//! Minimal SMTP submission client for `SMTPHandler`: one session per message, the
+//! way `logging.handlers.SMTPHandler` uses `smtplib`. The session greets with EHLO
+//! (falling back to HELO), upgrades with STARTTLS or starts in TLS when asked,
+//! authenticates with AUTH PLAIN or LOGIN, then sends one message with MAIL / RCPT /
+//! DATA and quits. Server certificates are checked against the Mozilla root store.
+//! The message itself is built by [`build_message`]: a single text/plain UTF-8 part,
+//! base64-encoded when it is not plain short-lined ASCII.
+
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+
+/// How the session is protected.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SmtpSecurity {
+    /// Plain text for the whole session.
+    None,
+    /// Plain text greeting, then STARTTLS before authenticating (port 587).
+    StartTls,
+    /// TLS from the first byte (SMTPS, port 465).
+    Tls,
+}
+
+impl SmtpSecurity {
+    pub fn from_security_str(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Some(SmtpSecurity::None),
+            "starttls" => Some(SmtpSecurity::StartTls),
+            "tls" | "ssl" => Some(SmtpSecurity::Tls),
+            _ => None,
+        }
+    }
+}
+
+/// Where and how to submit messages.
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub security: SmtpSecurity,
+    /// (username, password) for AUTH.
+    pub credentials: Option<(String, String)>,
+    /// Bounds the connect and every read and write of the session.
+    pub timeout: Duration,
+    /// Name announced in EHLO / HELO.
+    pub local_hostname: String,
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub use client::send_mail;
+
+/// wasm32 has no sockets: every send fails.
+#[cfg(target_family = "wasm")]
+pub fn send_mail(
+    _config: &SmtpConfig,
+    _from: &str,
+    _to: &[String],
+    _message: &[u8],
+) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(not(target_family = "wasm"))]
+mod client {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpStream, ToSocketAddrs};
+    use std::sync::{Arc, OnceLock};
+
+    use super::{dot_stuff, SmtpConfig, SmtpSecurity, BASE64};
+    use base64::Engine as _;
+
+    fn error(message: String) -> std::io::Error {
+        std::io::Error::other(message)
+    }
+
+    fn tls_config() -> Arc<rustls::ClientConfig> {
+        static CONFIG: OnceLock<Arc<rustls::ClientConfig>> = OnceLock::new();
+        CONFIG
+            .get_or_init(|| {
+                let roots = rustls::RootCertStore {
+                    roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+                };
+                let config = rustls::ClientConfig::builder_with_provider(Arc::new(
+                    rustls::crypto::ring::default_provider(),
+                ))
+                .with_safe_default_protocol_versions()
+                .expect("ring supports the default TLS versions")
+                .with_root_certificates(roots)
+                .with_no_client_auth();
+                Arc::new(config)
+            })
+            .clone()
+    }
+
+    enum Connection {
+        Plain(TcpStream),
+        Tls(Box<rustls::StreamOwned<rustls::ClientConnection, TcpStream>>),
+    }
+
+    impl Connection {
+        fn into_tls(self, host: &str) -> std::io::Result<Self> {
+            let Connection::Plain(stream) = self else {
+                return Ok(self);
+            };
+            let name = rustls::pki_types::ServerName::try_from(host.to_string())
+                .map_err(|e| error(format!("invalid TLS server name {host:?}: {e}")))?;
+            let client = rustls::ClientConnection::new(tls_config(), name)
+                .map_err(|e| error(format!("TLS setup failed: {e}")))?;
+            let mut tls = rustls::StreamOwned::new(client, stream);
+            // Handshake now, so a bad certificate is reported as such and not as a
+            // failed SMTP command.
+            while tls.conn.is_handshaking() {
+                tls.conn.complete_io(&mut tls.sock)?;
+            }
+            Ok(Connection::Tls(Box::new(tls)))
+        }
+    }
+
+    impl Read for Connection {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            match self {
+                Connection::Plain(stream) => stream.read(buf),
+                Connection::Tls(stream) => stream.read(buf),
+            }
+        }
+    }
+
+    impl Write for Connection {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            match self {
+                Connection::Plain(stream) => stream.write(buf),
+                Connection::Tls(stream) => stream.write(buf),
+            }
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            match self {
+                Connection::Plain(stream) => stream.flush(),
+                Connection::Tls(stream) => stream.flush(),
+            }
+        }
+    }
+
+    /// A server reply: the code and the text of every line.
+    struct Reply {
+        code: u16,
+        lines: Vec<String>,
+    }
+
+    struct Session {
+        connection: BufReader<Connection>,
+        /// EHLO keywords, upper-cased (`STARTTLS`, `AUTH PLAIN LOGIN`, ...).
+        extensions: Vec<String>,
+    }
+
+    impl Session {
+        fn read_reply(&mut self) -> std::io::Result<Reply> {
+            let mut lines = Vec::new();
+            loop {
+                let mut line = String::new();
+                if self.connection.read_line(&mut line)? == 0 {
+                    return Err(error("connection closed by the SMTP server".into()));
+                }
+                let line = line.trim_end_matches(['\r', '\n']);
+                let code = line
+                    .get(..3)
+                    .and_then(|code| code.parse::<u16>().ok())
+                    .ok_or_else(|| error(format!("malformed SMTP reply {line:?}")))?;
+                lines.push(line.get(4..).unwrap_or("").to_string());
+                if line.as_bytes().get(3) != Some(&b'-') {
+                    return Ok(Reply { code, lines });
+                }
+            }
+        }
+
+        /// Send `line` and return the reply, which must have one of the `expected` codes.
+        /// `shown` replaces the line in error messages (for credentials).
+        fn command(&mut self, line: &str, shown: &str, expected: &[u16]) -> std::io::Result<Reply> {
+            let connection = self.connection.get_mut();
+            connection.write_all(line.as_bytes())?;
+            connection.write_all(b"\r\n")?;
+            connection.flush()?;
+            self.expect(shown, expected)
+        }
+
+        fn expect(&mut self, shown: &str, expected: &[u16]) -> std::io::Result<Reply> {
+            let reply = self.read_reply()?;
+            if expected.contains(&reply.code) {
+                Ok(reply)
+            } else {
+                Err(error(format!(
+                    "SMTP {shown} failed: {} {}",
+                    reply.code,
+                    reply.lines.join(" ")
+                )))
+            }
+        }
+
+        fn hello(&mut self, name: &str) -> std::io::Result<()> {
+            match self.command(&format!("EHLO {name}"), "EHLO", &[250]) {
+                Ok(reply) => {
+                    self.extensions = reply
+                        .lines
+                        .iter()
+                        .skip(1)
+                        .map(|line| line.to_ascii_uppercase())
+                        .collect();
+                    Ok(())
+                }
+                Err(_) => {
+                    self.extensions.clear();
+                    self.command(&format!("HELO {name}"), "HELO", &[250])
+                        .map(|_| ())
+                }
+            }
+        }
+
+        fn extension(&self, keyword: &str) -> Option<&str> {
+            self.extensions.iter().find_map(|line| {
+                let rest = line.strip_prefix(keyword)?;
+                (rest.is_empty() || rest.starts_with([' ', '=']))
+                    .then(|| rest.trim_start_matches([' ', '=']))
+            })
+        }
+
+        fn login(&mut self, user: &str, password: &str) -> std::io::Result<()> {
+            let mechanisms: Vec<&str> = self
+                .extension("AUTH")
+                .ok_or_else(|| error("SMTP server does not support AUTH".into()))?
+                .split_whitespace()
+                .collect();
+            if mechanisms.contains(&"PLAIN") {
+                let token = BASE64.encode(format!("\0{user}\0{password}"));
+                self.command(&format!("AUTH PLAIN {token}"), "AUTH PLAIN", &[235])?;
+            } else if mechanisms.contains(&"LOGIN") {
+                self.command("AUTH LOGIN", "AUTH LOGIN", &[334])?;
+                self.command(&BASE64.encode(user), "AUTH LOGIN", &[334])?;
+                self.command(&BASE64.encode(password), "AUTH LOGIN", &[235])?;
+            } else {
+                return Err(error(format!(
+                    "SMTP server offers no supported AUTH mechanism (PLAIN, LOGIN): {}",
+                    mechanisms.join(" ")
+                )));
+            }
+            Ok(())
+        }
+    }
+
+    fn connect(config: &SmtpConfig) -> std::io::Result<TcpStream> {
+        let mut last = None;
+        for addr in (config.host.as_str(), config.port).to_socket_addrs()? {
+            match TcpStream::connect_timeout(&addr, config.timeout) {
+                Ok(stream) => {
+                    stream.set_read_timeout(Some(config.timeout))?;
+                    stream.set_write_timeout(Some(config.timeout))?;
+                    return Ok(stream);
+                }
+                Err(e) => last = Some(e),
+            }
+        }
+        Err(last
+            .unwrap_or_else(|| error(format!("{}:{} did not resolve", config.host, config.port))))
+    }
+
+    /// Deliver `message` (headers and body, CRLF line endings) from `from` to `to` in
+    /// one SMTP session.
+    pub fn send_mail(
+        config: &SmtpConfig,
+        from: &str,
+        to: &[String],
+        message: &[u8],
+    ) -> std::io::Result<()> {
+        let mut connection = Connection::Plain(connect(config)?);
+        if config.security == SmtpSecurity::Tls {
+            connection = connection.into_tls(&config.host)?;
+        }
+        let mut session = Session {
+            connection: BufReader::new(connection),
+            extensions: Vec::new(),
+        };
+        session.expect("greeting", &[220])?;
+        session.hello(&config.local_hostname)?;
+        if config.security == SmtpSecurity::StartTls {
+            if session.extension("STARTTLS").is_none() {
+                return Err(error("SMTP server does not support STARTTLS".into()));
+            }
+            session.command("STARTTLS", "STARTTLS", &[220])?;
+            // Nothing may follow the 220 before the handshake, so the buffer is empty.
+            let connection = session.connection.into_inner().into_tls(&config.host)?;
+            session.connection = BufReader::new(connection);
+            session.hello(&config.local_hostname)?;
+        }
+        if let Some((ref user, ref password)) = config.credentials {
+            session.login(user, password)?;
+        }
+        session.command(&format!("MAIL FROM:<{from}>"), "MAIL FROM", &[250])?;
+        for recipient in to {
+            session.command(&format!("RCPT TO:<{recipient}>"), "RCPT TO", &[250, 251])?;
+        }
+        session.command("DATA", "DATA", &[354])?;
+        let connection = session.connection.get_mut();
+        connection.write_all(&dot_stuff(message))?;
+        connection.write_all(b".\r\n")?;
+        connection.flush()?;
+        session.expect("DATA", &[250])?;
+        // The message is accepted; a failed QUIT changes nothing.
+        let _ = session.command("QUIT", "QUIT", &[221]);
+        Ok(())
+    }
+}
+
+/// Double every line-leading `.` and make sure the message ends with CRLF.
+fn dot_stuff(message: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(message.len() + 16);
+    let mut line_start = true;
+    for &b in message {
+        if line_start && b == b'.' {
+            out.push(b'.');
+        }
+        out.push(b);
+        line_start = b == b'\n';
+    }
+    if !out.ends_with(b"\r\n") {
+        out.extend_from_slice(b"\r\n");
+    }
+    out
+}
+
+/// A header value, RFC 2047-encoded when it is not printable ASCII.
+fn header_value(value: &str) -> String {
+    let value = value.replace(['\r', '\n'], " ");
+    if value.chars().all(|c| c.is_ascii() && !c.is_ascii_control()) {
+        return value;
+    }
+    // Encoded words are at most 75 chars: 45 input bytes per word, split at chars.
+    let mut words = Vec::new();
+    let mut chunk = String::new();
+    for c in value.chars() {
+        if chunk.len() + c.len_utf8() > 45 {
+            words.push(format!("=?utf-8?b?{}?=", BASE64.encode(&chunk)));
+            chunk.clear();
+        }
+        chunk.push(c);
+    }
+    if !chunk.is_empty() {
+        words.push(format!("=?utf-8?b?{}?=", BASE64.encode(&chunk)));
+    }
+    words.join("\r\n ")
+}
+
+/// The message `SMTPHandler.emit` sends: From, To, Subject and Date headers and a
+/// text/plain body.
+pub fn build_message(from: &str, to: &[String], subject: &str, date: &str, body: &str) -> Vec<u8> {
+    let body = body.replace("\r\n", "\n");
+    let plain = body.is_ascii() && body.lines().all(|line| line.len() <= 998);
+    let mut message = String::with_capacity(body.len() + 256);
+    message.push_str(&format!("From: {}\r\n", header_value(from)));
+    message.push_str(&format!("To: {}\r\n", header_value(&to.join(", "))));
+    message.push_str(&format!("Subject: {}\r\n", header_value(subject)));
+    message.push_str(&format!("Date: {date}\r\n"));
+    message.push_str("MIME-Version: 1.0\r\n");
+    message.push_str("Content-Type: text/plain; charset=\"utf-8\"\r\n");
+    if plain {
+        message.push_str("Content-Transfer-Encoding: 7bit\r\n\r\n");
+        message.push_str(&body.replace('\n', "\r\n"));
+    } else {
+        message.push_str("Content-Transfer-Encoding: base64\r\n\r\n");
+        let encoded = BASE64.encode(body.as_bytes());
+        for line in encoded.as_bytes().chunks(76) {
+            message.push_str(std::str::from_utf8(line).unwrap_or_default());
+            message.push_str("\r\n");
+        }
+    }
+    message.into_bytes()
+}
@@ -0,0 +1,391 @@
+//! # Tail
+//!
+//! `logxide.tail(path_or_handler, n)` returns the last `n` lines of a log file for
+//! admin endpoints and debugging consoles. The file is read backwards in blocks, so
+//! the cost depends on `n`, not on the size of the file. A line still being written
+//! (no newline yet) is left out. When the current file holds fewer than `n` lines the
+//! rest come from its rotated backups: `app.log.1`, `app.log.2`, ... as
+//! RotatingFileHandler names them, or the dated `app.log.2024-05-01` names of
+//! TimedRotatingFileHandler, gzipped or not.
+//!
+//! With `follow=True` it returns a `LogTail` iterator instead. It yields those lines,
+//! then waits for new ones, like `tail -F`. When the file is rotated or truncated it
+//! finishes the old file and then continues at the start of the new one.
+//!
+//! Checksum trailer lines (`checksum_every=`) are not log output and are left out.
+
+use crate::checksum::is_trailer;
+use pyo3::prelude::*;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Bytes read per step when scanning a file backwards.
+const BLOCK: u64 = 64 * 1024;
+
+/// How often a following `LogTail` checks the file for new lines.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Return the last `n` lines of a log file.
+///
+/// `target` is a path, or a handler with a `baseFilename` (FileHandler and the rotating
+/// handlers). A last line without its newline yet is left out. When the file has fewer
+/// than `n` lines, the older ones come from its rotated backups (`app.log.1`, ... or
+/// dated suffixes, gzipped or not), oldest first. Each line is one list item, so a
+/// record with a traceback takes several. With `follow=True`, returns a `LogTail`
+/// that yields these lines and then waits for new ones.
+#[pyfunction]
+#[pyo3(signature = (target, n=100, follow=false))]
+pub fn tail(py: Python, target: &Bound<PyAny>, n: usize, follow: bool) -> PyResult<Py<PyAny>> {
+    let path = resolve_path(py, target)?;
+    if follow {
+        let tail = py.detach(|| LogTail::open(&path, n))?;
+        return Ok(Py::new(py, tail)?.into_any());
+    }
+    let lines = py.detach(|| last_lines(&path, n))?;
+    Ok(lines.into_pyobject(py)?.into_any().unbind())
+}
+
+/// The file a path or a file handler writes to.
+fn resolve_path(py: Python, target: &Bound<PyAny>) -> PyResult<PathBuf> {
+    let path = match target.getattr("baseFilename") {
+        Ok(name) => name,
+        Err(_) => target.clone(),
+    };
+    py.import("os")?
+        .call_method1("fsdecode", (path,))?
+        .extract::<PathBuf>()
+}
+
+/// The last `n` complete lines of `path`, continued into its backups if needed.
+fn last_lines(path: &Path, n: usize) -> io::Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    let len = file.seek(SeekFrom::End(0))?;
+    last_lines_of(&mut file, len, path, n)
+}
+
+/// The last `n` complete lines of the first `len` bytes of `file`, which is open on
+/// `path`, continued into the backups of `path` if needed.
+fn last_lines_of(file: &mut File, len: u64, path: &Path, n: usize) -> io::Result<Vec<String>> {
+    let mut lines = read_last_lines(file, len, n, true)?;
+    if lines.len() < n {
+        for backup in backups(path) {
+            let need = n - lines.len();
+            let older = match backup.extension().is_some_and(|ext| ext == "gz") {
+                true => match crate::platform::gunzip_file(&backup) {
+                    Ok(data) => {
+                        let len = data.len() as u64;
+                        read_last_lines(&mut Cursor::new(data), len, need, false)?
+                    }
+                    // Built without the `compression` feature.
+                    Err(e) if e.kind() == io::ErrorKind::Unsupported => continue,
+                    Err(e) => return Err(e),
+                },
+                false => match File::open(&backup) {
+                    Ok(mut file) => {
+                        let len = file.seek(SeekFrom::End(0))?;
+                        read_last_lines(&mut file, len, need, false)?
+                    }
+                    // Pruned since it was listed.
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                    Err(e) => return Err(e),
+                },
+            };
+            lines.splice(0..0, older);
+            if lines.len() >= n {
+                break;
+            }
+        }
+    }
+    Ok(lines)
+}
+
+/// The last `n` lines of the first `len` bytes of `reader`, read backwards in blocks.
+/// With `drop_partial`, bytes after the last newline are a line still being written
+/// and are left out.
+fn read_last_lines<R: Read + Seek>(
+    reader: &mut R,
+    len: u64,
+    n: usize,
+    drop_partial: bool,
+) -> io::Result<Vec<String>> {
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    // `data` holds the bytes from `start` to `len`.
+    let mut start = len;
+    let mut data: Vec<u8> = Vec::new();
+    let mut newlines = 0;
+    let mut trailers = 0;
+    // n + 1 newlines bound n whole lines, whether or not the file ends with one.
+    while start > 0 && newlines <= n + trailers {
+        let from = start.saturating_sub(BLOCK);
+        let mut block = vec![0; (start - from) as usize];
+        reader.seek(SeekFrom::Start(from))?;
+        reader.read_exact(&mut block)?;
+        newlines += block.iter().filter(|&&b| b == b'\n').count();
+        block.extend_from_slice(&data);
+        data = block;
+        start = from;
+        trailers = data
+            .split(|&b| b == b'\n')
+            .filter(|line| is_trailer(line))
+            .count();
+    }
+    if drop_partial {
+        let end = data.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+        data.truncate(end);
+    }
+    let mut lines: Vec<&[u8]> = data.split(|&b| b == b'\n').collect();
+    // The empty piece after the final newline.
+    if lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    // A scan that stopped mid-file starts mid-line.
+    if start > 0 {
+        lines.remove(0);
+    }
+    lines.retain(|line| !is_trailer(line));
+    let skip = lines.len().saturating_sub(n);
+    Ok(lines[skip..].iter().map(|line| decode(line)).collect())
+}
+
+/// A line as text, without a trailing carriage return.
+fn decode(line: &[u8]) -> String {
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    String::from_utf8_lossy(line).into_owned()
+}
+
+/// The rotated backups of `path`, newest first: `<name>.<N>` by index, then
+/// `<name>.<date>` in reverse date order, each possibly with `.gz`.
+fn backups(path: &Path) -> Vec<PathBuf> {
+    let Some(name) = path.file_name() else {
+        return Vec::new();
+    };
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut prefix = name.as_encoded_bytes().to_vec();
+    prefix.push(b'.');
+    let mut numbered: Vec<(u64, PathBuf)> = Vec::new();
+    let mut dated: Vec<(String, PathBuf)> = Vec::new();
+    for entry in entries.flatten() {
+        let file = entry.file_name();
+        let Some(suffix) = file
+            .as_encoded_bytes()
+            .strip_prefix(prefix.as_slice())
+            .and_then(|suffix| std::str::from_utf8(suffix).ok())
+        else {
+            continue;
+        };
+        let suffix = suffix.strip_suffix(".gz").unwrap_or(suffix);
+        if let Ok(index) = suffix.parse::<u64>() {
+            numbered.push((index, dir.join(&file)));
+        } else if suffix.starts_with(|c: char| c.is_ascii_digit())
+            && suffix
+                .chars()
+                .all(|c| c.is_ascii_digit() || "-_.".contains(c))
+        {
+            dated.push((suffix.to_string(), dir.join(&file)));
+        }
+    }
+    numbered.sort();
+    // The date suffixes sort chronologically.
+    dated.sort_by(|a, b| b.cmp(a));
+    numbered
+        .into_iter()
+        .map(|(_, path)| path)
+        .chain(dated.into_iter().map(|(_, path)| path))
+        .collect()
+}
+
+/// Identifies the file behind a path, to notice when it is replaced by a rotation.
+#[cfg(unix)]
+fn file_id(meta: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((meta.dev(), meta.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(_meta: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Lines of a followed log file, as returned by `tail(..., follow=True)`.
+///
+/// Iterating yields the last lines of the file and then blocks, checking for new lines
+/// a few times a second, until `close()` is called. After a rotation or truncation the
+/// rest of the old file comes first, then the new file from its start. The GIL is
+/// released while waiting, and Ctrl-C interrupts the wait.
+#[pyclass(name = "LogTail", frozen)]
+pub struct LogTail {
+    path: PathBuf,
+    state: Mutex<Follow>,
+    closed: AtomicBool,
+}
+
+/// The open file and what has been read of it.
+struct Follow {
+    file: Option<File>,
+    id: Option<(u64, u64)>,
+    /// Offset of the next unread byte.
+    pos: u64,
+    /// Bytes after the last newline read so far.
+    partial: Vec<u8>,
+    pending: VecDeque<String>,
+}
+
+impl LogTail {
+    fn open(path: &Path, n: usize) -> io::Result<Self> {
+        let mut file = File::open(path)?;
+        let id = file_id(&file.metadata()?);
+        let len = file.seek(SeekFrom::End(0))?;
+        let lines = last_lines_of(&mut file, len, path, n)?;
+        // Resume after the last complete line: a partial one is yielded once finished.
+        let mut tail = Vec::new();
+        let from = len.saturating_sub(BLOCK);
+        file.seek(SeekFrom::Start(from))?;
+        file.by_ref().take(len - from).read_to_end(&mut tail)?;
+        let partial = match tail.iter().rposition(|&b| b == b'\n') {
+            Some(i) => tail[i + 1..].to_vec(),
+            None if from == 0 => tail,
+            // A line longer than a block is left for the reader to finish.
+            None => Vec::new(),
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            state: Mutex::new(Follow {
+                file: Some(file),
+                id,
+                pos: len,
+                partial,
+                pending: lines.into(),
+            }),
+            closed: AtomicBool::new(false),
+        })
+    }
+}
+
+impl Follow {
+    /// Queue the complete lines written since the last call.
+    fn read_new(&mut self) -> io::Result<()> {
+        let Some(file) = self.file.as_mut() else {
+            return Ok(());
+        };
+        let len = file.metadata()?.len();
+        if len < self.pos {
+            // Truncated in place (copytruncate rotation): start over.
+            self.pos = 0;
+            self.partial.clear();
+        }
+        if len == self.pos {
+            return Ok(());
+        }
+        file.seek(SeekFrom::Start(self.pos))?;
+        let mut data = std::mem::take(&mut self.partial);
+        let read = file.by_ref().take(len - self.pos).read_to_end(&mut data)?;
+        self.pos += read as u64;
+        let end = data.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+        self.partial = data.split_off(end);
+        data.pop();
+        if end > 0 {
+            self.pending.extend(
+                data.split(|&b| b == b'\n')
+                    .filter(|line| !is_trailer(line))
+                    .map(decode),
+            );
+        }
+        Ok(())
+    }
+
+    /// Read what is left of the current file, then switch to the file now at `path`
+    /// if it is a different one.
+    fn poll(&mut self, path: &Path) -> io::Result<()> {
+        self.read_new()?;
+        if !self.pending.is_empty() {
+            return Ok(());
+        }
+        let Ok(meta) = std::fs::metadata(path) else {
+            // Renamed away and not created again yet.
+            return Ok(());
+        };
+        let id = file_id(&meta);
+        if id.is_some() && id != self.id {
+            // Lines written to the old file just before the rename.
+            self.read_new()?;
+            if !self.partial.is_empty() {
+                let line = std::mem::take(&mut self.partial);
+                self.pending.push_back(decode(&line));
+            }
+            self.file = Some(File::open(path)?);
+            self.id = id;
+            self.pos = 0;
+            self.read_new()?;
+        }
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl LogTail {
+    /// The followed file.
+    #[getter]
+    fn path(&self) -> PathBuf {
+        self.path.clone()
+    }
+
+    /// Stop following: iteration ends after the lines already read.
+    fn close(&self) {
+        self.closed.store(true, Ordering::Relaxed);
+        self.state.lock().unwrap().file = None;
+    }
+
+    fn __iter__(slf: Bound<'_, Self>) -> Bound<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python) -> PyResult<Option<String>> {
+        loop {
+            if let Some(line) = self.state.lock().unwrap().pending.pop_front() {
+                return Ok(Some(line));
+            }
+            if self.closed.load(Ordering::Relaxed) {
+                return Ok(None);
+            }
+            let found = py.detach(|| -> io::Result<bool> {
+                let mut state = self.state.lock().unwrap();
+                state.poll(&self.path)?;
+                if state.pending.is_empty() {
+                    drop(state);
+                    std::thread::sleep(POLL_INTERVAL);
+                    return Ok(false);
+                }
+                Ok(true)
+            })?;
+            if !found {
+                py.check_signals()?;
+            }
+        }
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&self, _args: &Bound<pyo3::types::PyTuple>) {
+        self.close();
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<LogTail {}>", self.path.display())
+    }
+}
@@ -0,0 +1,156 @@
+//! # Message Templates
+//!
+//! Reduces a formatted message to its template by replacing the variable parts with
+//! placeholders: UUIDs become `<uuid>`, `0x`-prefixed and long mixed hex runs (ids,
+//! hashes) become `<hex>`, and numbers become `<num>`. `"user 42 took 10ms"` and
+//! `"user 7 took 3ms"` share the template `"user <num> took <num>ms"`, which makes
+//! it a grouping key for records logged with f-strings or pre-formatted messages.
+//!
+//! When enabled, the template is stored in the record's extras as `message_template`.
+//! Only whole words are replaced, so identifiers such as `utf8` or `ipv4` are kept.
+
+use pyo3::prelude::*;
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::core::LogRecord;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Shortest bare hex run (without `0x`) treated as an id rather than a word.
+const MIN_BARE_HEX: usize = 8;
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn is_word(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+/// Length of the UUID (8-4-4-4-12 hex digits) at the start of `bytes`, if any.
+fn uuid_len(bytes: &[u8]) -> Option<usize> {
+    let mut pos = 0;
+    for (i, group) in [8, 4, 4, 4, 12].into_iter().enumerate() {
+        if i > 0 {
+            if bytes.get(pos) != Some(&b'-') {
+                return None;
+            }
+            pos += 1;
+        }
+        let digits = bytes[pos.min(bytes.len())..]
+            .iter()
+            .take_while(|b| b.is_ascii_hexdigit())
+            .count();
+        if digits != group {
+            return None;
+        }
+        pos += group;
+    }
+    (!bytes.get(pos).copied().is_some_and(is_word)).then_some(pos)
+}
+
+/// Length of the `0x`-prefixed or bare hex id at the start of `bytes`, if any.
+fn hex_len(bytes: &[u8]) -> Option<usize> {
+    if bytes.len() > 2 && bytes[0] == b'0' && matches!(bytes[1], b'x' | b'X') {
+        let digits = bytes[2..]
+            .iter()
+            .take_while(|b| b.is_ascii_hexdigit())
+            .count();
+        let end = 2 + digits;
+        return (digits > 0 && !bytes.get(end).copied().is_some_and(is_word)).then_some(end);
+    }
+    let run = bytes.iter().take_while(|b| b.is_ascii_hexdigit()).count();
+    let whole_word = !bytes.get(run).copied().is_some_and(is_word);
+    let run_bytes = &bytes[..run];
+    (run >= MIN_BARE_HEX
+        && whole_word
+        && run_bytes.iter().any(u8::is_ascii_digit)
+        && run_bytes.iter().any(u8::is_ascii_alphabetic))
+    .then_some(run)
+}
+
+/// Length of the number (`12`, `3.5`, `1.2.3`) at the start of `bytes`, if any. A
+/// unit suffix such as `ms` or `KB` is left in the template.
+fn number_len(bytes: &[u8]) -> Option<usize> {
+    let digits = |from: usize| {
+        bytes[from..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count()
+    };
+    let mut end = digits(0);
+    if end == 0 {
+        return None;
+    }
+    while bytes.get(end) == Some(&b'.') && bytes.get(end + 1).is_some_and(u8::is_ascii_digit) {
+        end += 1 + digits(end + 1);
+    }
+    Some(end)
+}
+
+/// The template of `message`: its variable parts replaced with placeholders.
+pub fn extract(message: &str) -> String {
+    let bytes = message.as_bytes();
+    let mut out = String::with_capacity(message.len());
+    let mut copied = 0;
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let at_word_start = pos == 0 || !is_word(bytes[pos - 1]);
+        if !(at_word_start && bytes[pos].is_ascii_alphanumeric()) {
+            pos += 1;
+            continue;
+        }
+        let rest = &bytes[pos..];
+        let matched = uuid_len(rest)
+            .map(|len| (len, "<uuid>"))
+            .or_else(|| hex_len(rest).map(|len| (len, "<hex>")))
+            .or_else(|| number_len(rest).map(|len| (len, "<num>")));
+        match matched {
+            Some((len, placeholder)) => {
+                out.push_str(&message[copied..pos]);
+                out.push_str(placeholder);
+                pos += len;
+                copied = pos;
+            }
+            None => {
+                // Skip the rest of the word so digits inside it are kept.
+                pos += rest.iter().take_while(|b| is_word(**b)).count();
+            }
+        }
+    }
+    out.push_str(&message[copied..]);
+    out
+}
+
+/// Attach the `message_template` extra to `record` when templates are enabled.
+pub fn apply(record: &mut LogRecord) {
+    if !enabled() {
+        return;
+    }
+    let template = extract(&record.get_message());
+    record
+        .extra
+        .get_or_insert_with(Default::default)
+        .insert("message_template".to_string(), Value::String(template));
+}
+
+/// Attach a `message_template` extra to every record logged through logxide loggers.
+///
+/// The template is the formatted message with numbers, UUIDs and hex ids replaced by
+/// `<num>`, `<uuid>` and `<hex>`, so records that differ only in those values share
+/// it. `AggregatingHandler` groups by it when present.
+///
+/// Args:
+///     enabled: Turn template extraction on or off.
+#[pyfunction]
+#[pyo3(signature = (enabled=true))]
+pub fn configure_message_templates(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Return the template of `message`, as stored in the `message_template` extra.
+#[pyfunction]
+pub fn message_template(message: &str) -> String {
+    extract(message)
+}
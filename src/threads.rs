@@ -0,0 +1,187 @@
+//! # Background Threads
+//!
+//! Every thread logxide starts is named `logxide-<kind>-<n>` (`logxide-http-0`,
+//! `logxide-stream-1`), `n` counting the threads of that kind started in the process,
+//! so profilers, `ps -L` and `top -H` show what each one is. Linux shows the first 15
+//! bytes of a name.
+//!
+//! `configure_threads(nice=...)` sets the nice value the threads start with, so
+//! logging IO can yield to latency-critical application threads. Only Linux keeps a
+//! nice value per thread; elsewhere setting one raises.
+//!
+//! `tune(io_threads=..., pin_to_cores=[...])` pins the threads to a set of cores,
+//! split into `io_threads` groups that new threads take in turn. Pinning is Linux
+//! only; elsewhere `tune()` checks its arguments and does nothing.
+
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+
+/// Sentinel for "leave the nice value alone".
+const INHERIT: i32 = i32::MIN;
+
+static NICE: AtomicI32 = AtomicI32::new(INHERIT);
+static NICE_FAILED: AtomicBool = AtomicBool::new(false);
+static STARTED: Lazy<Mutex<HashMap<&'static str, usize>>> = Lazy::new(Default::default);
+
+/// Core groups set by `tune()`; each thread started takes the next one. Empty means
+/// not pinned.
+static CORE_GROUPS: Lazy<Mutex<Vec<Vec<usize>>>> = Lazy::new(Default::default);
+static NEXT_GROUP: AtomicUsize = AtomicUsize::new(0);
+static AFFINITY_FAILED: AtomicBool = AtomicBool::new(false);
+
+/// Start a background thread of `kind`, named and prioritized as configured. Fails on
+/// builds without threads (wasm32), whose callers work synchronously instead.
+pub fn spawn<F, T>(kind: &'static str, f: F) -> std::io::Result<JoinHandle<T>>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    if !crate::platform::THREADS {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            format!("cannot start a {kind} thread: this build has no threads"),
+        ));
+    }
+    let index = {
+        let mut started = STARTED.lock().unwrap_or_else(|e| e.into_inner());
+        let count = started.entry(kind).or_default();
+        *count += 1;
+        *count - 1
+    };
+    let nice = NICE.load(Ordering::Relaxed);
+    let cores = next_cores();
+    std::thread::Builder::new()
+        .name(format!("logxide-{kind}-{index}"))
+        .spawn(move || {
+            if nice != INHERIT {
+                set_nice(nice);
+            }
+            if let Some(cores) = cores {
+                pin(&cores);
+            }
+            f()
+        })
+}
+
+/// The cores the next thread is pinned to, or None when `tune()` pinned nothing.
+fn next_cores() -> Option<Vec<usize>> {
+    let groups = CORE_GROUPS.lock().unwrap_or_else(|e| e.into_inner());
+    if groups.is_empty() {
+        return None;
+    }
+    let next = NEXT_GROUP.fetch_add(1, Ordering::Relaxed);
+    Some(groups[next % groups.len()].clone())
+}
+
+fn pin(cores: &[usize]) {
+    if let Err(e) = crate::platform::set_thread_affinity(cores) {
+        if !AFFINITY_FAILED.swap(true, Ordering::Relaxed) {
+            eprintln!("[LogXide Error] could not pin thread to cores {cores:?}: {e}");
+        }
+    }
+}
+
+fn set_nice(nice: i32) {
+    if let Err(e) = crate::platform::set_thread_nice(nice) {
+        // Once: every thread would fail the same way.
+        if !NICE_FAILED.swap(true, Ordering::Relaxed) {
+            eprintln!("[LogXide Error] could not set thread nice value {nice}: {e}");
+        }
+    }
+}
+
+/// Set the nice value of logxide's background threads (Linux only).
+///
+/// Applies to threads started afterwards, so call it before creating handlers.
+/// Higher values yield the CPU to the application's threads; values below the
+/// process's own usually need `CAP_SYS_NICE`.
+///
+/// Args:
+///     nice: Nice value from -20 to 19, or None to inherit the process's.
+#[pyfunction]
+#[pyo3(signature = (nice=None))]
+pub fn configure_threads(nice: Option<i32>) -> PyResult<()> {
+    if let Some(nice) = nice {
+        if !(-20..=19).contains(&nice) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "nice must be between -20 and 19",
+            ));
+        }
+        if !crate::platform::THREAD_NICE {
+            return Err(pyo3::exceptions::PyNotImplementedError::new_err(
+                "per-thread nice values are only supported on Linux",
+            ));
+        }
+    }
+    NICE.store(nice.unwrap_or(INHERIT), Ordering::Relaxed);
+    NICE_FAILED.store(false, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Dedicate CPU cores to logxide's background threads (Linux only).
+///
+/// Threads started afterwards are pinned to `pin_to_cores`. logxide runs one thread
+/// per handler rather than a shared pool, so `io_threads` splits the cores into that
+/// many groups and each new thread takes the next group in turn; without it every
+/// thread may run on any of the cores. On platforms without CPU affinity the
+/// arguments are checked and nothing is pinned. `tune()` unpins.
+///
+/// Args:
+///     io_threads: Number of core groups, at most `len(pin_to_cores)`.
+///     pin_to_cores: CPU indices to run the threads on.
+///
+/// Returns:
+///     dict: `io_threads`, `pin_to_cores` and `pinned` (False when nothing is pinned).
+#[pyfunction]
+#[pyo3(signature = (io_threads=None, pin_to_cores=None))]
+pub fn tune(
+    py: Python,
+    io_threads: Option<usize>,
+    pin_to_cores: Option<Vec<usize>>,
+) -> PyResult<Bound<PyDict>> {
+    let invalid = |msg: &str| Err(pyo3::exceptions::PyValueError::new_err(msg.to_string()));
+    let mut cores: Vec<usize> = Vec::new();
+    for core in pin_to_cores.iter().flatten() {
+        if *core >= crate::platform::MAX_CORES {
+            return invalid("core indices must be below 1024");
+        }
+        if !cores.contains(core) {
+            cores.push(*core);
+        }
+    }
+    if pin_to_cores.is_some() && cores.is_empty() {
+        return invalid("pin_to_cores must list at least one core");
+    }
+    let groups = match io_threads {
+        Some(0) => return invalid("io_threads must be positive"),
+        Some(_) if cores.is_empty() => return invalid("io_threads needs pin_to_cores"),
+        Some(n) if n > cores.len() => {
+            return invalid("io_threads must not exceed the number of cores")
+        }
+        Some(n) => n,
+        None => 1,
+    };
+    let pinned = crate::platform::THREAD_AFFINITY && !cores.is_empty();
+    {
+        let mut slot = CORE_GROUPS.lock().unwrap_or_else(|e| e.into_inner());
+        *slot = if pinned {
+            (0..groups)
+                .map(|i| cores[i * cores.len() / groups..(i + 1) * cores.len() / groups].to_vec())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        NEXT_GROUP.store(0, Ordering::Relaxed);
+        AFFINITY_FAILED.store(false, Ordering::Relaxed);
+    }
+    let result = PyDict::new(py);
+    result.set_item("io_threads", io_threads)?;
+    result.set_item("pin_to_cores", cores)?;
+    result.set_item("pinned", pinned)?;
+    Ok(result)
+}
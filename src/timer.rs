@@ -0,0 +1,189 @@
+//! # Timers
+//!
+//! `logger.timer(name)` returns a context manager (also usable as a decorator) that logs
+//! a start record and an end record carrying the elapsed time. Time is measured with the
+//! monotonic `std::time::Instant`, starting after the start record has been dispatched
+//! so logging overhead is not counted.
+//!
+//! Records carry typed extras: `timer` (the name), `timer_event` ("start" / "end"),
+//! `elapsed_ms` (float, end only) and `timer_error` (exception type name, when the timed
+//! block raised).
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::core::LogLevel;
+use crate::py_logger::PyLogger;
+
+#[pyclass(name = "Timer", frozen)]
+pub struct PyTimer {
+    logger: PyLogger,
+    name: String,
+    level: LogLevel,
+    extra: HashMap<String, Value>,
+    log_start: bool,
+    /// Start instants of the active `with` blocks (a timer may be re-entered).
+    starts: Mutex<Vec<Instant>>,
+    last_elapsed_ms: Mutex<Option<f64>>,
+}
+
+impl PyTimer {
+    pub fn new(
+        logger: PyLogger,
+        name: String,
+        level: LogLevel,
+        extra: HashMap<String, Value>,
+        log_start: bool,
+    ) -> Self {
+        Self {
+            logger,
+            name,
+            level,
+            extra,
+            log_start,
+            starts: Mutex::new(Vec::new()),
+            last_elapsed_ms: Mutex::new(None),
+        }
+    }
+
+    fn fields(&self, event: &str) -> HashMap<String, Value> {
+        let mut fields = self.extra.clone();
+        fields.insert("timer".into(), Value::String(self.name.clone()));
+        fields.insert("timer_event".into(), Value::String(event.into()));
+        fields
+    }
+
+    fn begin(&self, py: Python) -> Instant {
+        if self.log_start {
+            self.logger.log_with_extra(
+                py,
+                self.level,
+                format!("{} started", self.name),
+                self.fields("start"),
+            );
+        }
+        Instant::now()
+    }
+
+    fn finish(&self, py: Python, started: Instant, exc_type: Option<&Bound<PyAny>>) {
+        let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+        *self.last_elapsed_ms.lock().unwrap() = Some(elapsed_ms);
+
+        let mut fields = self.fields("end");
+        fields.insert(
+            "elapsed_ms".into(),
+            serde_json::Number::from_f64(elapsed_ms)
+                .map(Value::Number)
+                .unwrap_or(Value::Null),
+        );
+        let msg = match exc_type {
+            Some(exc_type) => {
+                let type_name = exc_type
+                    .getattr("__name__")
+                    .and_then(|n| n.extract::<String>())
+                    .unwrap_or_else(|_| "Exception".to_string());
+                let msg = format!("{} failed after {elapsed_ms:.3}ms ({type_name})", self.name);
+                fields.insert("timer_error".into(), Value::String(type_name));
+                msg
+            }
+            None => format!("{} finished in {elapsed_ms:.3}ms", self.name),
+        };
+        self.logger.log_with_extra(py, self.level, msg, fields);
+    }
+}
+
+#[pymethods]
+impl PyTimer {
+    #[getter]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Elapsed milliseconds of the most recently finished measurement (None before the
+    /// first one completes).
+    #[getter]
+    fn elapsed_ms(&self) -> Option<f64> {
+        *self.last_elapsed_ms.lock().unwrap()
+    }
+
+    fn __enter__(slf: Bound<'_, Self>) -> Bound<'_, Self> {
+        let started = slf.get().begin(slf.py());
+        slf.get().starts.lock().unwrap().push(started);
+        slf
+    }
+
+    #[pyo3(signature = (exc_type=None, _exc_value=None, _traceback=None))]
+    fn __exit__(
+        &self,
+        py: Python,
+        exc_type: Option<&Bound<PyAny>>,
+        _exc_value: Option<&Bound<PyAny>>,
+        _traceback: Option<&Bound<PyAny>>,
+    ) -> bool {
+        let started = self.starts.lock().unwrap().pop();
+        if let Some(started) = started {
+            self.finish(py, started, exc_type.filter(|t| !t.is_none()));
+        }
+        false
+    }
+
+    /// Decorator form: every call of `func` is timed separately.
+    fn __call__(slf: Bound<'_, Self>, func: Bound<'_, PyAny>) -> PyResult<Py<PyTimedFunction>> {
+        let py = slf.py();
+        let wrapper = Py::new(
+            py,
+            PyTimedFunction {
+                timer: slf.unbind(),
+                func: func.clone().unbind(),
+            },
+        )?;
+        py.import("functools")?
+            .call_method1("update_wrapper", (wrapper.bind(py), func))?;
+        Ok(wrapper)
+    }
+}
+
+/// Function wrapped by a `Timer` used as a decorator.
+#[pyclass(name = "TimedFunction", dict)]
+pub struct PyTimedFunction {
+    timer: Py<PyTimer>,
+    func: Py<PyAny>,
+}
+
+#[pymethods]
+impl PyTimedFunction {
+    #[pyo3(signature = (*args, **kwargs))]
+    fn __call__(
+        &self,
+        py: Python,
+        args: &Bound<PyTuple>,
+        kwargs: Option<&Bound<PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        let timer = self.timer.get();
+        let started = timer.begin(py);
+        let result = self.func.bind(py).call(args, kwargs);
+        let exc_type = result.as_ref().err().map(|e| e.get_type(py).into_any());
+        timer.finish(py, started, exc_type.as_ref());
+        result.map(Bound::unbind)
+    }
+
+    /// Bind as a method when the decorated function is a class attribute.
+    fn __get__(
+        slf: Bound<'_, Self>,
+        obj: Option<Bound<'_, PyAny>>,
+        _objtype: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<Py<PyAny>> {
+        match obj.filter(|o| !o.is_none()) {
+            None => Ok(slf.into_any().unbind()),
+            Some(obj) => {
+                let py = slf.py();
+                let method = py.import("types")?.getattr("MethodType")?;
+                Ok(method.call1((slf, obj))?.unbind())
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! # Traceback Rendering
+//!
+//! Renders exception tracebacks for `exc_info` / `.exception()` records. The default
+//! output is byte-identical to `traceback.format_exception`; the process-wide options
+//! set through `configure_tracebacks` switch rendering to a `TracebackException` walk
+//! that can capture per-frame locals, cap the number of frames, and print the most
+//! recent call first.
+//!
+//! Rendering always runs on the producer thread under the GIL (the walk touches live
+//! frame objects), so the cost is paid only by records that actually carry exc_info.
+
+use arc_swap::ArcSwap;
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple};
+use std::sync::Arc;
+
+const HEADER_LAST: &str = "Traceback (most recent call last):\n";
+const HEADER_FIRST: &str = "Traceback (most recent call first):\n";
+
+/// Process-wide traceback rendering options.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TracebackOptions {
+    /// Append `name = repr(value)` lines for every local of each rendered frame.
+    pub show_locals: bool,
+    /// Keep at most this many frames, dropping the outermost ones first.
+    pub max_frames: Option<usize>,
+    /// Print frames innermost-first ("most recent call first").
+    pub most_recent_first: bool,
+}
+
+impl TracebackOptions {
+    fn is_default(&self) -> bool {
+        *self == TracebackOptions::default()
+    }
+}
+
+static TRACEBACK_OPTIONS: Lazy<ArcSwap<TracebackOptions>> =
+    Lazy::new(|| ArcSwap::from_pointee(TracebackOptions::default()));
+
+/// Current traceback rendering options.
+pub fn traceback_options() -> Arc<TracebackOptions> {
+    TRACEBACK_OPTIONS.load_full()
+}
+
+/// Render `(exc_type, exc_value, tb)` into traceback text honoring the configured
+/// options. Returns `None` when there is no exception or rendering fails.
+pub fn render_exception(
+    py: Python,
+    exc_type: &Bound<PyAny>,
+    exc_value: &Bound<PyAny>,
+    tb: &Bound<PyAny>,
+) -> Option<String> {
+    if exc_value.is_none() {
+        return None;
+    }
+    let opts = traceback_options();
+    let tb_mod = py.import("traceback").ok()?;
+
+    let lines = if opts.is_default() {
+        tb_mod
+            .call_method1("format_exception", (exc_type, exc_value, tb))
+            .ok()?
+    } else {
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("capture_locals", opts.show_locals).ok()?;
+        if let Some(n) = opts.max_frames {
+            // A negative limit keeps the innermost frames, which carry the failure site.
+            kwargs.set_item("limit", -(n as i64)).ok()?;
+        }
+        let te = tb_mod
+            .getattr("TracebackException")
+            .ok()?
+            .call((exc_type, exc_value, tb), Some(&kwargs))
+            .ok()?;
+        if opts.most_recent_first {
+            reverse_stacks(&te);
+        }
+        te.call_method0("format").ok()?
+    };
+
+    let empty_str = "".into_pyobject(py).ok()?;
+    let mut result = empty_str.call_method1("join", (lines,)).ok()?.to_string();
+    if opts.most_recent_first {
+        result = result.replace(HEADER_LAST, HEADER_FIRST);
+    }
+    if result.is_empty() {
+        None
+    } else {
+        Some(result)
+    }
+}
+
+/// Reverse the frame summaries of a `TracebackException` and of every exception
+/// chained to it through `__cause__` / `__context__`.
+fn reverse_stacks(te: &Bound<PyAny>) {
+    let mut pending = vec![te.clone()];
+    let mut seen: Vec<usize> = Vec::new();
+    while let Some(current) = pending.pop() {
+        let id = current.as_ptr() as usize;
+        if seen.contains(&id) {
+            continue;
+        }
+        seen.push(id);
+        if let Ok(stack) = current.getattr("stack") {
+            let _ = stack.call_method0("reverse");
+        }
+        for attr in ["__cause__", "__context__"] {
+            if let Ok(chained) = current.getattr(attr) {
+                if !chained.is_none() {
+                    pending.push(chained);
+                }
+            }
+        }
+        if let Ok(group) = current.getattr("exceptions") {
+            if let Ok(items) = group.try_iter() {
+                pending.extend(items.flatten());
+            }
+        }
+    }
+}
+
+/// Configure how exception tracebacks are rendered for `exc_info` records.
+///
+/// Args:
+///     show_locals: Include `name = repr(value)` for each local of every frame.
+///     max_frames: Keep only the innermost N frames (None = unlimited).
+///     most_recent_first: Print the failing frame first instead of last.
+#[pyfunction]
+#[pyo3(signature = (show_locals=false, max_frames=None, most_recent_first=false))]
+pub fn configure_tracebacks(
+    show_locals: bool,
+    max_frames: Option<usize>,
+    most_recent_first: bool,
+) -> PyResult<()> {
+    if max_frames == Some(0) {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "max_frames must be a positive integer or None",
+        ));
+    }
+    TRACEBACK_OPTIONS.store(Arc::new(TracebackOptions {
+        show_locals,
+        max_frames,
+        most_recent_first,
+    }));
+    Ok(())
+}
+
+/// Render an `exc_info` tuple with the configured traceback options, so the
+/// Python-side record conversion matches the Rust logger path.
+#[pyfunction]
+pub fn format_exception(py: Python, exc_info: &Bound<PyTuple>) -> PyResult<Option<String>> {
+    if exc_info.len() != 3 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "exc_info must be a (type, value, traceback) tuple",
+        ));
+    }
+    Ok(render_exception(
+        py,
+        &exc_info.get_item(0)?,
+        &exc_info.get_item(1)?,
+        &exc_info.get_item(2)?,
+    ))
+}
@@ -0,0 +1,244 @@
+//! # Record Transforms
+//!
+//! A declarative alternative to HTTPHandler's `transform_callback` for the common
+//! reshaping jobs: renaming, dropping and adding fields, string templates and level
+//! remapping. The spec is parsed once when the handler is built and applied to each
+//! serialized record on the worker thread, with no Python call and no GIL.
+//!
+//! ```text
+//! {
+//!     "levels": {"WARNING": "warn", "50": "fatal"},
+//!     "add":    {"service": "billing", "summary": "{levelname}: {msg}"},
+//!     "rename": {"msg": "message", "extra.request_id": "request_id"},
+//!     "drop":   ["args", "exc_info", "extra"],
+//! }
+//! ```
+//!
+//! The steps run in that order. `levels` rewrites `levelname`, looked up by level
+//! name or by number. `add` values that are strings are templates: `{path}` is
+//! replaced by the record's value there, and `{{` / `}}` are literal braces. A
+//! template that is exactly one `{path}` keeps the value's JSON type. All templates
+//! see the record as it was before any of them were added. Paths are dotted, so
+//! `extra.request_id` reaches into the record's extras.
+
+use serde_json::{Map, Value};
+
+/// A parsed `record_transform` spec.
+#[derive(Debug, Default)]
+pub struct RecordTransform {
+    levels: Vec<(String, String)>,
+    add: Vec<(String, FieldValue)>,
+    rename: Vec<(String, String)>,
+    drop: Vec<String>,
+}
+
+#[derive(Debug)]
+enum FieldValue {
+    Constant(Value),
+    Template(Vec<Segment>),
+}
+
+#[derive(Debug)]
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+impl RecordTransform {
+    /// Parse a spec. The error names the offending key or template.
+    pub fn from_spec(spec: &Value) -> Result<Self, String> {
+        let Value::Object(spec) = spec else {
+            return Err("record_transform must be a mapping".to_string());
+        };
+        let mut transform = RecordTransform::default();
+        for (key, value) in spec {
+            match key.as_str() {
+                "levels" => transform.levels = string_pairs(key, value)?,
+                "rename" => transform.rename = string_pairs(key, value)?,
+                "add" => {
+                    let Value::Object(fields) = value else {
+                        return Err("record_transform 'add' must be a mapping".to_string());
+                    };
+                    for (field, value) in fields {
+                        let value = match value {
+                            Value::String(template) => {
+                                FieldValue::Template(parse_template(template)?)
+                            }
+                            other => FieldValue::Constant(other.clone()),
+                        };
+                        transform.add.push((field.clone(), value));
+                    }
+                }
+                "drop" => {
+                    let fields = value.as_array().and_then(|fields| {
+                        fields
+                            .iter()
+                            .map(|field| field.as_str().map(str::to_string))
+                            .collect::<Option<Vec<_>>>()
+                    });
+                    transform.drop = fields.ok_or_else(|| {
+                        "record_transform 'drop' must be a list of field names".to_string()
+                    })?;
+                }
+                other => {
+                    return Err(format!(
+                        "unknown record_transform key '{other}' \
+                         (expected levels, add, rename or drop)"
+                    ))
+                }
+            }
+        }
+        Ok(transform)
+    }
+
+    /// Reshape one serialized record in place.
+    pub fn apply(&self, record: &mut Value) {
+        let Value::Object(obj) = record else {
+            return;
+        };
+        if !self.levels.is_empty() {
+            let name = obj.get("levelname").and_then(Value::as_str);
+            let number = obj.get("levelno").map(Value::to_string);
+            let remapped = self
+                .levels
+                .iter()
+                .find(|(level, _)| Some(level.as_str()) == name || Some(level) == number.as_ref());
+            if let Some((_, to)) = remapped {
+                obj.insert("levelname".to_string(), Value::String(to.clone()));
+            }
+        }
+        let added: Vec<(&str, Value)> = self
+            .add
+            .iter()
+            .map(|(field, value)| (field.as_str(), value.render(obj)))
+            .collect();
+        for (field, value) in added {
+            insert_path(obj, field, value);
+        }
+        for (from, to) in &self.rename {
+            if let Some(value) = remove_path(obj, from) {
+                insert_path(obj, to, value);
+            }
+        }
+        for field in &self.drop {
+            remove_path(obj, field);
+        }
+    }
+}
+
+impl FieldValue {
+    fn render(&self, record: &Map<String, Value>) -> Value {
+        let segments = match self {
+            FieldValue::Constant(value) => return value.clone(),
+            FieldValue::Template(segments) => segments,
+        };
+        if let [Segment::Field(path)] = segments.as_slice() {
+            return get_path(record, path).cloned().unwrap_or(Value::Null);
+        }
+        let mut out = String::new();
+        for segment in segments {
+            match segment {
+                Segment::Literal(text) => out.push_str(text),
+                Segment::Field(path) => match get_path(record, path) {
+                    Some(Value::String(s)) => out.push_str(s),
+                    Some(Value::Null) | None => {}
+                    Some(other) => out.push_str(&other.to_string()),
+                },
+            }
+        }
+        Value::String(out)
+    }
+}
+
+fn string_pairs(key: &str, value: &Value) -> Result<Vec<(String, String)>, String> {
+    let pairs = value.as_object().and_then(|map| {
+        map.iter()
+            .map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+            .collect::<Option<Vec<_>>>()
+    });
+    pairs.ok_or_else(|| format!("record_transform '{key}' must map field names to strings"))
+}
+
+fn parse_template(template: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut path = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => path.push(c),
+                        None => return Err(format!("unclosed '{{' in template {template:?}")),
+                    }
+                }
+                if path.is_empty() {
+                    return Err(format!("empty '{{}}' in template {template:?}"));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field(path));
+            }
+            '}' => return Err(format!("unmatched '}}' in template {template:?}")),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+fn get_path<'a>(record: &'a Map<String, Value>, path: &str) -> Option<&'a Value> {
+    let mut parts = path.split('.');
+    let mut value = record.get(parts.next()?)?;
+    for part in parts {
+        value = value.as_object()?.get(part)?;
+    }
+    Some(value)
+}
+
+fn remove_path(record: &mut Map<String, Value>, path: &str) -> Option<Value> {
+    match path.rsplit_once('.') {
+        None => record.remove(path),
+        Some((parent, last)) => {
+            let mut parts = parent.split('.');
+            let mut obj = record.get_mut(parts.next()?)?.as_object_mut()?;
+            for part in parts {
+                obj = obj.get_mut(part)?.as_object_mut()?;
+            }
+            obj.remove(last)
+        }
+    }
+}
+
+/// Set `path`, creating (or replacing non-object values with) objects on the way.
+fn insert_path(record: &mut Map<String, Value>, path: &str, value: Value) {
+    let mut parts: Vec<&str> = path.split('.').collect();
+    let last = parts.pop().unwrap_or(path);
+    let mut obj = record;
+    for part in parts {
+        let entry = obj
+            .entry(part.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !entry.is_object() {
+            *entry = Value::Object(Map::new());
+        }
+        let Value::Object(next) = entry else {
+            unreachable!()
+        };
+        obj = next;
+    }
+    obj.insert(last.to_string(), value);
+}
@@ -0,0 +1,318 @@
+//! # Config File Watcher
+//!
+//! `logxide.watch_config(path)` applies a logging config file (the `dictConfig` schema,
+//! as YAML or JSON) and applies it again whenever the file changes. That way a running
+//! service can be moved from INFO to DEBUG by editing a file. The file's directory is
+//! watched through `notify`, so editors that save by writing a new file and renaming
+//! it over the old one are seen as well as in-place writes. A burst of events becomes
+//! one reload, once the file has been quiet for `debounce` seconds, and a save that
+//! leaves the content unchanged does nothing.
+//!
+//! Reloads go through `logxide.config.dictConfig`, which rewires every logger while
+//! dispatch waits (see [`crate::config`]). A record logged during a reload reaches
+//! either the old handlers or the new ones, and the old handlers drain their queues as
+//! they close. A file that fails to read, parse or configure leaves the previous
+//! configuration in place. The error goes to stderr and is kept in `last_error`.
+//!
+//! wasm32 builds have no threads to watch from; `watch_config` raises
+//! NotImplementedError there.
+
+use once_cell::sync::Lazy;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+#[cfg(not(target_family = "wasm"))]
+use std::time::Duration;
+
+/// Running watchers. A watcher runs until stopped, whether or not its `ConfigWatcher`
+/// is still referenced; `stop_config_watchers()` stops them all at interpreter exit.
+static WATCHERS: Lazy<Mutex<Vec<Arc<Watch>>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// State shared between a `ConfigWatcher` and its reload thread.
+struct Watch {
+    path: PathBuf,
+    reloads: AtomicU64,
+    last_error: Mutex<Option<String>>,
+    /// Content of the last successful apply; a save with the same bytes is skipped.
+    applied: Mutex<Vec<u8>>,
+    running: Mutex<Option<Running>>,
+}
+
+/// The notify watcher and the reload thread, dropped / joined by `stop()`.
+#[cfg(not(target_family = "wasm"))]
+struct Running {
+    _watcher: notify::RecommendedWatcher,
+    stop: crossbeam_channel::Sender<()>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+#[cfg(target_family = "wasm")]
+struct Running;
+
+impl Watch {
+    /// Read, parse and apply the file, unless its content is what was applied last.
+    /// Returns whether a configuration was applied.
+    fn apply(&self, py: Python) -> PyResult<bool> {
+        let content = std::fs::read(&self.path)?;
+        if *self.applied.lock().unwrap() == content {
+            return Ok(false);
+        }
+        let config = parse(&self.path, &content)?;
+        let config = crate::core::json_value_to_py_as_list(py, &config)?;
+        py.import("logxide.config")?
+            .getattr("dictConfig")?
+            .call1((config,))?;
+        *self.applied.lock().unwrap() = content;
+        Ok(true)
+    }
+
+    /// Apply a changed file from the reload thread: count it, or report the error and
+    /// keep the previous configuration.
+    #[cfg(not(target_family = "wasm"))]
+    fn reload(&self) {
+        Python::attach(|py| match self.apply(py) {
+            Ok(applied) => {
+                if applied {
+                    self.reloads.fetch_add(1, Ordering::Relaxed);
+                }
+                *self.last_error.lock().unwrap() = None;
+            }
+            Err(e) => {
+                let message = e.to_string();
+                eprintln!(
+                    "[LogXide Error] config reload of {} failed, keeping the previous \
+                     configuration: {message}",
+                    self.path.display()
+                );
+                *self.last_error.lock().unwrap() = Some(message);
+            }
+        });
+    }
+
+    /// Stop watching and wait for an in-progress reload to finish.
+    fn stop(self: &Arc<Self>, py: Python) {
+        WATCHERS.lock().unwrap().retain(|w| !Arc::ptr_eq(w, self));
+        let running = self.running.lock().unwrap().take();
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(running) = running {
+            drop(running._watcher);
+            let _ = running.stop.send(());
+            // The thread may be waiting for the GIL to apply a reload.
+            py.detach(|| {
+                let _ = running.thread.join();
+            });
+        }
+        #[cfg(target_family = "wasm")]
+        let _ = (py, running);
+    }
+}
+
+/// Parse a config file: JSON for `.json`, YAML (which also reads JSON) otherwise.
+fn parse(path: &Path, content: &[u8]) -> PyResult<serde_json::Value> {
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let parsed = if is_json {
+        serde_json::from_slice(content).map_err(|e| e.to_string())
+    } else {
+        serde_yaml::from_slice(content).map_err(|e| e.to_string())
+    };
+    match parsed {
+        Ok(config @ serde_json::Value::Object(_)) => Ok(config),
+        Ok(_) => Err(PyValueError::new_err(format!(
+            "{}: a logging config must be a mapping",
+            path.display()
+        ))),
+        Err(e) => Err(PyValueError::new_err(format!(
+            "{}: invalid config: {e}",
+            path.display()
+        ))),
+    }
+}
+
+/// Start the notify watcher on the file's directory and the thread that debounces its
+/// events into reloads.
+#[cfg(not(target_family = "wasm"))]
+fn start(watch: &Arc<Watch>, debounce: Duration) -> PyResult<Running> {
+    use notify::{EventKind, RecursiveMode, Watcher};
+
+    let directory = match watch.path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let file_name = watch.path.file_name().map(|name| name.to_os_string());
+    let (changed_tx, changed) = crossbeam_channel::unbounded::<()>();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else { return };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        if event
+            .paths
+            .iter()
+            .any(|path| path.file_name().map(|n| n.to_os_string()) == file_name)
+        {
+            let _ = changed_tx.send(());
+        }
+    })
+    .map_err(watch_error)?;
+    watcher
+        .watch(&directory, RecursiveMode::NonRecursive)
+        .map_err(watch_error)?;
+
+    let (stop, stopped) = crossbeam_channel::bounded::<()>(1);
+    let thread_watch = Arc::downgrade(watch);
+    let thread = crate::threads::spawn("watch", move || loop {
+        crossbeam_channel::select! {
+            recv(stopped) -> _ => return,
+            recv(changed) -> event => if event.is_err() { return },
+        }
+        // Wait for the file to go quiet: editors write, truncate and rename in bursts.
+        loop {
+            crossbeam_channel::select! {
+                recv(stopped) -> _ => return,
+                recv(changed) -> event => if event.is_err() { return },
+                default(debounce) => break,
+            }
+        }
+        match thread_watch.upgrade() {
+            Some(watch) => watch.reload(),
+            None => return,
+        }
+    })
+    .map_err(|e| pyo3::exceptions::PyOSError::new_err(e.to_string()))?;
+    Ok(Running {
+        _watcher: watcher,
+        stop,
+        thread,
+    })
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn watch_error(e: notify::Error) -> PyErr {
+    pyo3::exceptions::PyOSError::new_err(format!("cannot watch config file: {e}"))
+}
+
+/// Handle returned by `watch_config()`. Reloads continue until `stop()` is called,
+/// the `with` block exits or the interpreter shuts down; dropping the handle does not
+/// stop them.
+#[pyclass(name = "ConfigWatcher", frozen)]
+pub struct PyConfigWatcher {
+    watch: Arc<Watch>,
+}
+
+#[pymethods]
+impl PyConfigWatcher {
+    /// The watched config file.
+    #[getter]
+    fn path(&self) -> PathBuf {
+        self.watch.path.clone()
+    }
+
+    /// Number of times a changed file has been applied (the initial apply not included).
+    #[getter]
+    fn reloads(&self) -> u64 {
+        self.watch.reloads.load(Ordering::Relaxed)
+    }
+
+    /// Error of the last reload attempt, None once a reload succeeds.
+    #[getter]
+    fn last_error(&self) -> Option<String> {
+        self.watch.last_error.lock().unwrap().clone()
+    }
+
+    /// Whether the file is still being watched.
+    #[getter]
+    fn running(&self) -> bool {
+        self.watch.running.lock().unwrap().is_some()
+    }
+
+    /// Apply the file now, as if it had changed. Raises on an invalid config, leaving
+    /// the previous configuration in place.
+    fn reload(&self, py: Python) -> PyResult<()> {
+        if self.watch.apply(py)? {
+            self.watch.reloads.fetch_add(1, Ordering::Relaxed);
+        }
+        *self.watch.last_error.lock().unwrap() = None;
+        Ok(())
+    }
+
+    /// Stop watching. Safe to call more than once.
+    fn stop(&self, py: Python) {
+        self.watch.stop(py);
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&self, py: Python, _args: &Bound<pyo3::types::PyTuple>) {
+        self.watch.stop(py);
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<ConfigWatcher {} ({})>",
+            self.watch.path.display(),
+            if self.running() { "running" } else { "stopped" }
+        )
+    }
+}
+
+/// Apply a YAML or JSON logging config file and re-apply it whenever it changes.
+///
+/// Args:
+///     path: Config file in the `logging.config.dictConfig` schema. `.json` files are
+///         read as JSON, anything else as YAML.
+///     debounce: Seconds the file must stay unchanged before a reload (default 0.2).
+///
+/// Returns a `ConfigWatcher`. The file is applied once before this returns, and an
+/// invalid file raises. Later changes that fail to apply keep the previous
+/// configuration and are reported on stderr and in `ConfigWatcher.last_error`.
+#[pyfunction]
+#[pyo3(signature = (path, debounce=0.2))]
+pub fn watch_config(
+    py: Python,
+    path: crate::platform::FsPath,
+    debounce: f64,
+) -> PyResult<PyConfigWatcher> {
+    if !debounce.is_finite() || debounce < 0.0 {
+        return Err(PyValueError::new_err(
+            "debounce must be a non-negative number of seconds",
+        ));
+    }
+    crate::platform::require_threads("watch_config")?;
+    let path = std::path::absolute(&path.0)?;
+    let watch = Arc::new(Watch {
+        path,
+        reloads: AtomicU64::new(0),
+        last_error: Mutex::new(None),
+        applied: Mutex::new(Vec::new()),
+        running: Mutex::new(None),
+    });
+    // Watch before the first apply, so an edit made meanwhile is not missed.
+    #[cfg(not(target_family = "wasm"))]
+    {
+        let running = start(&watch, Duration::from_secs_f64(debounce))?;
+        *watch.running.lock().unwrap() = Some(running);
+    }
+    if let Err(e) = watch.apply(py) {
+        watch.stop(py);
+        return Err(e);
+    }
+    WATCHERS.lock().unwrap().push(watch.clone());
+    Ok(PyConfigWatcher { watch })
+}
+
+/// Stop every live config watcher. Registered with `atexit`, so no reload starts while
+/// the interpreter is shutting down; safe to call more than once.
+#[pyfunction]
+pub fn stop_config_watchers(py: Python) {
+    let live: Vec<Arc<Watch>> = WATCHERS.lock().unwrap().clone();
+    for watch in live {
+        watch.stop(py);
+    }
+}
@@ -0,0 +1,301 @@
+//! # Worker Lifecycle
+//!
+//! Shutdown-side state of the background sender threads behind HTTPHandler,
+//! OTLPHandler and PipeHandler: the bounded shutdown wait, the spill/drop accounting for records left
+//! behind when it expires, and the daemon / non-daemon exit policy.
+//!
+//! Every worker registers in a process-wide registry of weak references. At
+//! interpreter exit `shutdown_workers()` (registered with `atexit`) drains non-daemon
+//! workers (bounded by their `shutdown_timeout`) and abandons daemon workers at once,
+//! so a daemon worker never delays exit.
+//!
+//! Without the `network` and `pipe` features no workers are ever created;
+//! `configure_workers()` and `shutdown_workers()` stay available as no-ops so the Python
+//! API is unchanged.
+#![cfg_attr(not(any(feature = "network", feature = "pipe")), allow(dead_code))]
+
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+#[cfg(feature = "json")]
+use std::fs::OpenOptions;
+#[cfg(feature = "json")]
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::core::LogRecord;
+use crate::queue::LaneReceiver;
+
+/// Global default for handlers created without an explicit `daemon=` argument.
+static DAEMON_DEFAULT: AtomicBool = AtomicBool::new(false);
+
+static WORKERS: Lazy<Mutex<Vec<Weak<WorkerHandle>>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Resolve a per-handler `daemon` argument against the global default.
+pub fn resolve_daemon(daemon: Option<bool>) -> bool {
+    daemon.unwrap_or_else(|| DAEMON_DEFAULT.load(Ordering::Relaxed))
+}
+
+/// Destination for records still pending when a worker is abandoned: appended as JSON
+/// lines to `path` when set, otherwise dropped. Either way they are counted, so
+/// `get_metrics()` still accounts for every emitted record.
+pub struct ShutdownSpill {
+    path: Option<PathBuf>,
+    abandoned: AtomicBool,
+    dropped: AtomicU64,
+    spilled: AtomicU64,
+    lock: parking_lot::Mutex<()>,
+}
+
+impl ShutdownSpill {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            abandoned: AtomicBool::new(false),
+            dropped: AtomicU64::new(0),
+            spilled: AtomicU64::new(0),
+            lock: parking_lot::Mutex::new(()),
+        }
+    }
+
+    /// True once shutdown gave up on the worker; the worker then discards instead of
+    /// sending.
+    pub fn is_abandoned(&self) -> bool {
+        self.abandoned.load(Ordering::Relaxed)
+    }
+
+    /// Spill (or drop) `records`, returning how many were handed over.
+    pub fn discard(&self, records: Vec<Arc<LogRecord>>) -> u64 {
+        let count = records.len() as u64;
+        if count == 0 {
+            return 0;
+        }
+        if let Some(ref path) = self.path {
+            let _guard = self.lock.lock();
+            match write_spill(path, &records) {
+                Ok(()) => {
+                    self.spilled.fetch_add(count, Ordering::Relaxed);
+                    return count;
+                }
+                Err(e) => {
+                    eprintln!("[LogXide Error] failed to spill pending records: {e}");
+                }
+            }
+        }
+        self.dropped.fetch_add(count, Ordering::Relaxed);
+        count
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    pub fn spilled(&self) -> u64 {
+        self.spilled.load(Ordering::Relaxed)
+    }
+}
+
+/// Append `records` to `path` as JSON lines.
+#[cfg(feature = "json")]
+fn write_spill(path: &Path, records: &[Arc<LogRecord>]) -> std::io::Result<()> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut writer = BufWriter::new(file);
+    for rec in records {
+        writer.write_all(rec.to_json().as_bytes())?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()
+}
+
+#[cfg(not(feature = "json"))]
+fn write_spill(_path: &Path, _records: &[Arc<LogRecord>]) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "spill files require the `json` feature",
+    ))
+}
+
+/// Shutdown handle for one network handler's worker thread.
+pub struct WorkerHandle {
+    kind: &'static str,
+    stop: Arc<AtomicBool>,
+    stopped: AtomicBool,
+    wake: crossbeam_channel::Sender<()>,
+    join_handle: Mutex<Option<JoinHandle<()>>>,
+    pending: LaneReceiver<Vec<Arc<LogRecord>>>,
+    spill: Arc<ShutdownSpill>,
+    shutdown_timeout: Option<Duration>,
+    daemon: bool,
+}
+
+pub struct WorkerConfig {
+    pub kind: &'static str,
+    /// Flag polled by the worker loop; set to request drain-and-exit.
+    pub stop: Arc<AtomicBool>,
+    /// Flush signal used to wake the worker promptly.
+    pub wake: crossbeam_channel::Sender<()>,
+    /// Receiver clone of the record queue, drained when the worker is abandoned.
+    pub pending: LaneReceiver<Vec<Arc<LogRecord>>>,
+    pub spill: Arc<ShutdownSpill>,
+    pub shutdown_timeout: Option<Duration>,
+    pub daemon: bool,
+}
+
+impl WorkerHandle {
+    /// Wrap a spawned worker thread and add it to the exit registry.
+    pub fn register(config: WorkerConfig, handle: JoinHandle<()>) -> Arc<Self> {
+        let worker = Arc::new(Self {
+            kind: config.kind,
+            stop: config.stop,
+            stopped: AtomicBool::new(false),
+            wake: config.wake,
+            join_handle: Mutex::new(Some(handle)),
+            pending: config.pending,
+            spill: config.spill,
+            shutdown_timeout: config.shutdown_timeout,
+            daemon: config.daemon,
+        });
+        let mut workers = WORKERS.lock().unwrap();
+        workers.retain(|w| w.strong_count() > 0);
+        workers.push(Arc::downgrade(&worker));
+        worker
+    }
+
+    /// In a forked child, start the worker thread again with `spawn` in place of the
+    /// parent's, which did not survive the fork. Nothing is started when the worker was
+    /// already shut down.
+    pub fn restart_after_fork(&self, spawn: impl FnOnce() -> JoinHandle<()>) {
+        if self.stopped.load(Ordering::SeqCst) || self.stop.load(Ordering::Relaxed) {
+            return;
+        }
+        let handle = spawn();
+        // The parent's handle names a thread that does not exist here; joining it would
+        // never return.
+        if let Ok(mut slot) = self.join_handle.try_lock() {
+            std::mem::forget(slot.replace(handle));
+        }
+    }
+
+    /// Ask the worker to drain and exit without waiting for it.
+    pub fn signal_stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let _ = self.wake.try_send(());
+    }
+
+    /// Drain and join the worker, waiting at most `shutdown_timeout`; records still
+    /// pending after that are spilled or dropped.
+    pub fn shutdown(&self) {
+        if self.stopped.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.signal_stop();
+        let Some(handle) = self.join_handle.lock().unwrap().take() else {
+            return;
+        };
+        if !join_with_timeout(handle, self.shutdown_timeout) {
+            let waited = self.shutdown_timeout.unwrap_or_default().as_secs_f64();
+            self.abandon(&format!("shutdown timed out after {waited:.1}s"));
+        }
+    }
+
+    /// Interpreter-exit shutdown: daemon workers are abandoned immediately, non-daemon
+    /// workers get the regular bounded drain.
+    fn exit(&self) {
+        if !self.daemon {
+            self.shutdown();
+            return;
+        }
+        if self.stopped.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.signal_stop();
+        let finished = self
+            .join_handle
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_none_or(|h| h.is_finished());
+        if !finished {
+            self.abandon("daemon worker abandoned at exit");
+        }
+    }
+
+    /// Give up on the worker: discard everything still queued and report it.
+    fn abandon(&self, reason: &str) {
+        self.spill.abandoned.store(true, Ordering::Relaxed);
+        let count = self
+            .spill
+            .discard(self.pending.drain().into_iter().flatten().collect());
+        if count == 0 {
+            return;
+        }
+        let outcome = match self.spill.path {
+            Some(ref path) if self.spill.dropped() == 0 => {
+                format!("spilled to {}", path.display())
+            }
+            _ => "dropped".to_string(),
+        };
+        eprintln!(
+            "[LogXide Error] {} {reason}; {count} pending records {outcome}",
+            self.kind
+        );
+    }
+
+    /// Records dropped / spilled to disk because the worker was abandoned.
+    pub fn shutdown_snapshot(&self) -> (u64, u64) {
+        (self.spill.dropped(), self.spill.spilled())
+    }
+
+    pub fn is_daemon(&self) -> bool {
+        self.daemon
+    }
+}
+
+/// Join `handle`, waiting at most `timeout` (None = indefinitely). Returns false when
+/// the worker is still running; it is then left detached.
+fn join_with_timeout(handle: JoinHandle<()>, timeout: Option<Duration>) -> bool {
+    if let Some(timeout) = timeout {
+        let deadline = Instant::now() + timeout;
+        while !handle.is_finished() {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+    let _ = handle.join();
+    true
+}
+
+/// Set the global default for whether network and pipe handler workers run as daemons.
+///
+/// Args:
+///     daemon: True = never block interpreter exit (pending records are spilled or
+///         dropped at exit); False = drain at exit, bounded by each handler's
+///         shutdown_timeout. Applies to handlers created afterwards without an
+///         explicit `daemon=` argument.
+#[pyfunction]
+#[pyo3(signature = (daemon=false))]
+pub fn configure_workers(daemon: bool) {
+    DAEMON_DEFAULT.store(daemon, Ordering::Relaxed);
+}
+
+/// Stop every live network and pipe handler worker according to its daemon policy.
+/// Registered with `atexit`; safe to call more than once.
+#[pyfunction]
+pub fn shutdown_workers(py: Python) {
+    let live: Vec<Arc<WorkerHandle>> = WORKERS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(Weak::upgrade)
+        .collect();
+    py.detach(|| {
+        for worker in live {
+            worker.exit();
+        }
+    });
+}
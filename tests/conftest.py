@@ -4,10 +4,14 @@ Test configuration and fixtures for logxide tests.
 
 import contextlib
 import io
+import itertools
+import json
 import sys
 import threading
 import time
+from collections import namedtuple
 from contextlib import contextmanager
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
 
 import pytest
 
@@ -310,6 +314,127 @@ def async_wait():
     return wait_for_async_logging
 
 
+_logger_names = itertools.count()
+
+
+class IsolatedLoggers:
+    """Factory for loggers no other test uses, so each test sees only its records."""
+
+    def __init__(self):
+        self._attached = []
+
+    def name(self, prefix="test"):
+        """A logger name no other test uses, under ``prefix``."""
+        return f"{prefix}.{next(_logger_names)}"
+
+    def __call__(self, *handlers, name="test", level=logging.INFO):
+        """A new logger that doesn't propagate, with ``handlers`` attached."""
+        logger = logging.getLogger(self.name(name))
+        logger.propagate = False
+        logger.setLevel(level)
+        for handler in handlers:
+            logger.addHandler(handler)
+        self._attached.append((logger, handlers))
+        return logger
+
+    def send(self, handler, *messages):
+        """Log ``messages`` at INFO to ``handler``, then flush and close it.
+
+        Returns the handler's ``get_metrics()`` taken after the flush.
+        """
+        logger = self(handler)
+        try:
+            for message in messages:
+                logger.info(message)
+            handler.flush()
+            return handler.get_metrics()
+        finally:
+            logger.removeHandler(handler)
+            handler.close()
+
+    def detach(self):
+        for logger, handlers in self._attached:
+            for handler in handlers:
+                logger.removeHandler(handler)
+
+
+@pytest.fixture
+def isolated_logger():
+    """Fresh, non-propagating loggers; their handlers are detached afterwards."""
+    loggers = IsolatedLoggers()
+    yield loggers
+    loggers.detach()
+
+
+CollectedRequest = namedtuple("CollectedRequest", "method path headers body time")
+
+
+class HTTPCollector:
+    """A local HTTP server that records every request it receives.
+
+    ``replies`` holds ``(status, headers)`` answers for the next requests; once it
+    is empty, requests get a 200. Each answer waits ``delay`` seconds.
+    """
+
+    def __init__(self):
+        self.requests = []
+        self.replies = []
+        self.delay = 0.0
+        collector = self
+
+        class Handler(BaseHTTPRequestHandler):
+            protocol_version = "HTTP/1.0"
+
+            def do_GET(self):
+                collector._receive(self, b"")
+
+            def do_POST(self):
+                length = int(self.headers.get("Content-Length", 0))
+                collector._receive(self, self.rfile.read(length))
+
+            def log_message(self, *args):
+                pass
+
+        self._server = ThreadingHTTPServer(("127.0.0.1", 0), Handler)
+        self.url = f"http://127.0.0.1:{self._server.server_address[1]}"
+        threading.Thread(target=self._server.serve_forever, daemon=True).start()
+
+    def _receive(self, handler, body):
+        self.requests.append(
+            CollectedRequest(
+                handler.command, handler.path, handler.headers, body, time.monotonic()
+            )
+        )
+        if self.delay:
+            time.sleep(self.delay)
+        status, headers = self.replies.pop(0) if self.replies else (200, {})
+        handler.send_response(status)
+        for name, value in headers.items():
+            handler.send_header(name, value)
+        handler.end_headers()
+
+    @property
+    def bodies(self):
+        return [request.body for request in self.requests]
+
+    @property
+    def records(self):
+        """The records of every JSON request body, in the order received."""
+        return [record for body in self.bodies for record in json.loads(body)]
+
+    def close(self):
+        self._server.shutdown()
+        self._server.server_close()
+
+
+@pytest.fixture
+def http_collector():
+    """A local HTTP collector (see HTTPCollector), shut down after the test."""
+    collector = HTTPCollector()
+    yield collector
+    collector.close()
+
+
 # Test markers for categorizing tests
 pytest.mark.unit = pytest.mark.unit
 pytest.mark.integration = pytest.mark.integration
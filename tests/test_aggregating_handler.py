@@ -0,0 +1,118 @@
+"""
+Tests for AggregatingHandler: records are counted per (logger, level, template) and
+passed on once per window.
+"""
+
+import logging
+import time
+
+import pytest
+
+from logxide import handlers
+from logxide.logxide import logging as native_logging
+
+
+class _Recorder(logging.Handler):
+    def __init__(self, level=logging.NOTSET):
+        super().__init__(level)
+        self.records = []
+
+    def emit(self, record):
+        self.records.append(record)
+
+
+def _logger(isolated_logger, handler):
+    logger = native_logging.getLogger(isolated_logger.name("aggregate"))
+    logger.setLevel(logging.DEBUG)
+    logger.propagate = False
+    logger.addHandler(handler)
+    return logger
+
+
+def test_repeats_become_one_summary_record(isolated_logger):
+    target = handlers.MemoryHandler()
+    aggregator = handlers.AggregatingHandler(target, window=30)
+    logger = _logger(isolated_logger, aggregator)
+    for path in ["/a", "/b", "/c"]:
+        logger.info("GET %s", path)
+    logger.warning("disk at %d%%", 91)
+    aggregator.close()
+
+    summary, single = target.records
+    assert summary.getMessage() == "GET %s (logged 3 times in 30s)"
+    assert summary.aggregate_count == 3
+    assert summary.aggregate_template == "GET %s"
+    assert summary.aggregate_first <= summary.aggregate_last
+    assert single.getMessage() == "disk at 91%"
+    assert single.aggregate_count == 1
+
+
+def test_logger_and_level_are_part_of_the_key(isolated_logger):
+    target = handlers.MemoryHandler()
+    aggregator = handlers.AggregatingHandler(target, window=30)
+    first = _logger(isolated_logger, aggregator)
+    second = _logger(isolated_logger, aggregator)
+    first.info("tick")
+    first.info("tick")
+    first.warning("tick")
+    second.info("tick")
+    aggregator.close()
+
+    assert [(name, level) for name, level, _ in target.record_tuples] == [
+        (first.name, logging.INFO),
+        (first.name, logging.WARNING),
+        (second.name, logging.INFO),
+    ]
+    assert [r.aggregate_count for r in target.records] == [2, 1, 1]
+
+
+def test_window_closes_on_its_own(isolated_logger):
+    target = handlers.MemoryHandler()
+    aggregator = handlers.AggregatingHandler(target, window=0.2)
+    logger = _logger(isolated_logger, aggregator)
+    logger.info("ping")
+    logger.info("ping")
+    deadline = time.monotonic() + 5
+    while not target.records and time.monotonic() < deadline:
+        time.sleep(0.05)
+    logger.info("ping")
+    aggregator.close()
+
+    assert [r.aggregate_count for r in target.records] == [2, 1]
+
+
+def test_python_handlers_get_stdlib_records_at_their_level(isolated_logger):
+    everything = _Recorder()
+    warnings = _Recorder(logging.WARNING)
+    aggregator = handlers.AggregatingHandler(everything, warnings, window=30)
+    logger = _logger(isolated_logger, aggregator)
+    logger.info("started %s", "job")
+    logger.error("failed")
+    logger.error("failed")
+    aggregator.close()
+
+    assert [r.getMessage() for r in everything.records] == [
+        "started job",
+        "failed (logged 2 times in 30s)",
+    ]
+    (failed,) = warnings.records
+    assert isinstance(failed, logging.LogRecord)
+    assert failed.aggregate_count == 2
+
+
+def test_filters_apply_before_counting(isolated_logger):
+    target = handlers.MemoryHandler()
+    aggregator = handlers.AggregatingHandler(target, window=30)
+    aggregator.addFilter(lambda record: record.levelno >= logging.WARNING)
+    logger = _logger(isolated_logger, aggregator)
+    logger.info("noise")
+    logger.warning("signal")
+    aggregator.close()
+
+    assert [r.getMessage() for r in target.records] == ["signal"]
+
+
+@pytest.mark.parametrize("window", [0, -1, float("inf")])
+def test_window_is_validated(window):
+    with pytest.raises(ValueError):
+        handlers.AggregatingHandler(window=window)
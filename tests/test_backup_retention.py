@@ -0,0 +1,101 @@
+"""
+Tests for the max_age_days / max_total_bytes retention options of the rotating file
+handlers.
+"""
+
+import glob
+import logging as std_logging
+import os
+import time
+
+import pytest
+
+from logxide import logging
+from logxide.handlers import RotatingFileHandler, TimedRotatingFileHandler
+
+THREE_DAYS = 3 * 86_400
+
+
+def _logger(name, handler):
+    logger = logging.getLogger(f"test.retention.{name}")
+    logger.addHandler(handler)
+    logger.setLevel(std_logging.DEBUG)
+    return logger
+
+
+def _age(path, seconds):
+    past = time.time() - seconds
+    os.utime(path, (past, past))
+
+
+def test_max_total_bytes_drops_oldest_backups(tmp_path):
+    log_file = str(tmp_path / "app.log")
+    # Every 10-byte line fills the file, so each record after the first rotates.
+    handler = RotatingFileHandler(
+        log_file, maxBytes=10, backupCount=10, max_total_bytes=35
+    )
+    handler.setFormatter(std_logging.Formatter("%(message)s"))
+    logger = _logger("bytes", handler)
+    for n in range(6):
+        logger.info("record %02d", n)
+    handler.flush()
+
+    assert sorted(os.listdir(tmp_path)) == [
+        "app.log",
+        "app.log.1",
+        "app.log.2",
+        "app.log.3",
+    ]
+    with open(f"{log_file}.3") as f:
+        assert f.read() == "record 02\n"
+
+
+def test_max_age_days_drops_expired_backups(tmp_path):
+    log_file = str(tmp_path / "app.log")
+    for n in (1, 2):
+        with open(f"{log_file}.{n}", "w") as f:
+            f.write("old\n")
+        _age(f"{log_file}.{n}", THREE_DAYS)
+    handler = RotatingFileHandler(log_file, maxBytes=10, backupCount=5, max_age_days=1)
+    handler.setFormatter(std_logging.Formatter("%(message)s"))
+    logger = _logger("age", handler)
+    logger.info("record 00")
+    logger.info("record 01")
+    handler.flush()
+
+    assert sorted(os.listdir(tmp_path)) == ["app.log", "app.log.1"]
+
+
+def test_timed_rollover_applies_max_age(tmp_path):
+    log_file = str(tmp_path / "app.log")
+    stale = f"{log_file}.2020-01-01_00-00-00"
+    with open(stale, "w") as f:
+        f.write("old\n")
+    _age(stale, THREE_DAYS)
+    handler = TimedRotatingFileHandler(log_file, when="S", max_age_days=1)
+    logger = _logger("timed", handler)
+    logger.info("first")
+    handler.flush()
+    time.sleep(1.2)
+    logger.info("second")
+    handler.flush()
+
+    backups = glob.glob(f"{log_file}.*")
+    assert stale not in backups
+    assert len(backups) == 1
+
+
+@pytest.mark.parametrize(
+    "kwargs",
+    [
+        {"max_age_days": 0},
+        {"max_age_days": -1},
+        {"max_age_days": float("nan")},
+        {"max_total_bytes": 0},
+    ],
+)
+def test_invalid_limits_are_rejected(tmp_path, kwargs):
+    with pytest.raises(ValueError):
+        RotatingFileHandler(str(tmp_path / "app.log"), **kwargs)
+    with pytest.raises(ValueError):
+        TimedRotatingFileHandler(str(tmp_path / "app.log"), **kwargs)
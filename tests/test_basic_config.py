@@ -0,0 +1,86 @@
+"""
+Tests for basicConfig: stdlib arguments and semantics, with Rust handlers for files and
+the standard streams.
+"""
+
+import io
+import logging as std_logging
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+
+@pytest.fixture(autouse=True)
+def unconfigured_root():
+    logging.basicConfig(force=True, handlers=[])
+    yield
+    logging.basicConfig(force=True, handlers=[])
+    logging.getLogger().setLevel(logging.WARNING)
+
+
+def test_stream_style_and_level():
+    stream = io.StringIO()
+    logging.basicConfig(
+        stream=stream, format="{levelname}|{name}|{message}", style="{", level="INFO"
+    )
+    root = logging.getLogger()
+    assert root.level == logging.INFO
+    (handler,) = root.handlers
+    assert std_logging.root.handlers == [handler]
+
+    logging.getLogger("basic.app").info("hello")
+    logging.getLogger("basic.app").debug("below the level")
+    logxide.flush()
+    assert stream.getvalue() == "INFO|basic.app|hello\n"
+
+
+def test_configures_once_unless_forced(tmp_path):
+    first, second = tmp_path / "first.log", tmp_path / "second.log"
+    logging.basicConfig(filename=str(first), format="%(message)s")
+    (handler,) = logging.getLogger().handlers
+    logging.basicConfig(filename=str(second), format="%(message)s")
+    assert logging.getLogger().handlers == [handler]
+    assert not second.exists()
+
+    logging.basicConfig(filename=str(second), format="%(message)s", force=True)
+    (forced,) = logging.getLogger().handlers
+    assert isinstance(forced, handlers.FileHandler)
+    assert forced is not handler
+    logging.getLogger("basic.forced").warning("to the second file")
+    forced.flush()
+    assert second.read_text() == "to the second file\n"
+    assert first.read_text() == ""
+
+
+def test_filemode_and_default_format(tmp_path):
+    path = tmp_path / "app.log"
+    path.write_text("old content\n")
+    logging.basicConfig(filename=str(path), filemode="w")
+    logging.getLogger("basic.file").warning("fresh")
+    logging.getLogger().handlers[0].flush()
+    assert path.read_text() == "WARNING:basic.file:fresh\n"
+
+
+def test_handlers_keep_their_formatter():
+    stream = io.StringIO()
+    own = std_logging.StreamHandler(stream)
+    own.setFormatter(logging.Formatter("own %(message)s"))
+    plain = std_logging.StreamHandler(stream)
+    logging.basicConfig(handlers=[own, plain], format="basic %(message)s")
+    assert logging.getLogger().handlers == [own, plain]
+    logging.getLogger("basic.handlers").warning("x")
+    assert stream.getvalue() == "own x\nbasic x\n"
+
+
+def test_invalid_arguments():
+    with pytest.raises(ValueError, match="should not be specified together"):
+        logging.basicConfig(stream=io.StringIO(), filename="app.log")
+    with pytest.raises(ValueError, match="together with 'handlers'"):
+        logging.basicConfig(stream=io.StringIO(), handlers=[])
+    with pytest.raises(ValueError, match="Unrecognised argument"):
+        logging.basicConfig(colour=True)
+    with pytest.raises(ValueError, match="Style must be one of"):
+        logging.basicConfig(style="#")
+    assert logging.getLogger().handlers == []
@@ -0,0 +1,84 @@
+"""
+Tests for Logger.bind(): views of a logger whose records carry bound extras, and
+LoggerAdapter wrapping loggers and views.
+"""
+
+import logging as std_logging
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def memory():
+    logger = logging.getLogger("bind_tests")
+    mh = handlers.MemoryHandler()
+    logger.addHandler(mh)
+    logger.setLevel(logging.INFO)
+    yield logger, mh
+    logger.removeHandler(mh)
+    logger.setLevel(logging.NOTSET)
+
+
+def test_bound_records_carry_the_context(memory):
+    logger, mh = memory
+    request = logger.bind(request_id="r1", user=7)
+    request.info("one")
+    request.info("two", extra={"user": 8, "step": "save"})
+    request.bind(step="load").warning("three")
+    logger.info("plain")
+    one, two, three, plain = mh.records
+    assert (one.request_id, one.user) == ("r1", 7)
+    assert (two.request_id, two.user, two.step) == ("r1", 8, "save")
+    assert (three.request_id, three.step) == ("r1", "load")
+    assert not hasattr(plain, "request_id")
+    assert request.context == {"request_id": "r1", "user": 7}
+    assert logxide.getLogger("bind_tests").context == {}
+
+
+def test_views_share_the_logger(memory):
+    logger, mh = memory
+    view = logger.bind(request_id="r1")
+    assert view.name == logger.name
+    logger.setLevel(logging.ERROR)
+    view.warning("dropped")
+    assert mh.records == []
+    assert view.getEffectiveLevel() == logging.ERROR
+
+
+def test_native_formatters_see_the_context(memory, tmp_path):
+    logger, _ = memory
+    path = tmp_path / "bind.log"
+    handler = handlers.FileHandler(str(path))
+    handler.setFormatter(logging.Formatter("%(request_id)s %(message)s"))
+    logger.addHandler(handler)
+    try:
+        logger.bind(request_id="r9").info("saved")
+        handler.flush()
+    finally:
+        logger.removeHandler(handler)
+    assert path.read_text() == "r9 saved\n"
+
+
+@pytest.mark.parametrize("adapter", [std_logging.LoggerAdapter, logging.LoggerAdapter])
+def test_logger_adapter_wraps_loggers_and_views(memory, adapter):
+    logger, mh = memory
+    adapter(logger, {"tenant": "acme"}).info("plain")
+    adapter(logger.bind(request_id="r1"), {"tenant": "acme"}).info("bound")
+    plain, bound = mh.records
+    assert plain.tenant == "acme"
+    assert (bound.tenant, bound.request_id) == ("acme", "r1")
+
+
+def test_adapter_bind_keeps_the_adapter_extra(memory):
+    logger, mh = memory
+    adapter = logging.LoggerAdapter(logger, {"tenant": "acme"}).bind(request_id="r2")
+    adapter.info("bound")
+    assert (mh.records[0].tenant, mh.records[0].request_id) == ("acme", "r2")
+
+
+def test_stdlib_get_logger_binds():
+    view = std_logging.getLogger("bind_tests.stdlib").bind(job=1)
+    assert view.context == {"job": 1}
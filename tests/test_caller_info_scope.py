@@ -88,3 +88,38 @@ def test_lineno_formatter_still_populates_caller_info():
 
     m = re.search(r"RESULT True (\d+)", result.stdout)
     assert m is not None and int(m.group(1)) > 0, result.stdout
+
+
+def test_configure_caller_info_overrides_the_format_check():
+    result = _run(
+        """
+        import logxide
+        from logxide import handlers, logging
+
+        log = logging.getLogger("cis.override")
+        log.setLevel(logging.DEBUG)
+        mh = handlers.MemoryHandler()
+        log.addHandler(mh)
+
+        def collected():
+            log.info("z")
+            rec = mh.records[-1]
+            return (rec.filename, rec.funcName, rec.lineno > 0)
+
+        print("RESULT", collected())
+        logxide.configure_caller_info(True)
+        print("RESULT", collected())
+        mh.setFormatter(logging.Formatter("%(lineno)d %(message)s"))
+        logxide.configure_caller_info(False)
+        print("RESULT", collected())
+        logxide.configure_caller_info(None)
+        print("RESULT", collected())
+        """
+    )
+    assert result.returncode == 0, result.stderr
+    assert result.stdout.splitlines() == [
+        "RESULT ('', '', False)",
+        "RESULT ('<string>', 'collected', True)",
+        "RESULT ('', '', False)",
+        "RESULT ('<string>', 'collected', True)",
+    ]
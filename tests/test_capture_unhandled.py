@@ -0,0 +1,99 @@
+"""
+Tests for capture_unhandled: uncaught exceptions in the main thread, other threads and
+asyncio callbacks are logged at CRITICAL with their traceback, and the previous hooks
+come back when it is turned off.
+"""
+
+import asyncio
+import os
+import subprocess
+import sys
+import textwrap
+import threading
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+
+@pytest.fixture
+def captured():
+    logger = logging.getLogger("logxide.unhandled")
+    mh = handlers.MemoryHandler()
+    logger.addHandler(mh)
+    logxide.capture_unhandled()
+    yield mh
+    logxide.capture_unhandled(False)
+    logger.removeHandler(mh)
+
+
+def test_uncaught_exception_is_logged_before_exit(tmp_path):
+    path = tmp_path / "crash.log"
+    script = textwrap.dedent(
+        f"""
+        import logxide
+        from logxide import FileHandler, logging
+
+        handler = FileHandler({str(path)!r})
+        handler.setFormatter(logging.Formatter("%(levelname)s %(message)s"))
+        logging.getLogger().addHandler(handler)
+        logxide.capture_unhandled()
+        raise RuntimeError("crashed")
+        """
+    )
+    env = dict(os.environ, PYTHONPATH=REPO_ROOT)
+    result = subprocess.run(
+        [sys.executable, "-c", script],
+        capture_output=True,
+        text=True,
+        timeout=30,
+        env=env,
+    )
+    assert result.returncode == 1
+    assert "RuntimeError: crashed" in result.stderr
+    logged = path.read_text()
+    assert logged.startswith("CRITICAL Uncaught exception\nTraceback")
+    assert "RuntimeError: crashed" in logged
+
+
+def test_thread_exception_is_logged(captured):
+    def fail():
+        raise ValueError("in thread")
+
+    thread = threading.Thread(target=fail, name="worker-1")
+    thread.start()
+    thread.join()
+    record = captured.records[-1]
+    assert record.levelno == logging.CRITICAL
+    assert record.getMessage() == "Uncaught exception in thread worker-1"
+    assert (record.unhandled, record.unhandled_thread) == ("thread", "worker-1")
+    assert "ValueError: in thread" in record.exc_text
+
+
+def test_asyncio_exception_is_logged(captured):
+    def fail():
+        raise KeyError("in callback")
+
+    async def main():
+        asyncio.get_running_loop().call_soon(fail)
+        await asyncio.sleep(0.01)
+
+    asyncio.run(main())
+    record = captured.records[-1]
+    assert record.levelno == logging.CRITICAL
+    assert record.unhandled == "asyncio"
+    assert "handle" in record.asyncio_context
+    assert "KeyError: 'in callback'" in record.exc_text
+
+
+def test_disabling_restores_previous_hooks():
+    before = (sys.excepthook, threading.excepthook)
+    logxide.capture_unhandled()
+    assert sys.excepthook is not before[0]
+    logxide.capture_unhandled(False)
+    assert (sys.excepthook, threading.excepthook) == before
+    default = asyncio.BaseEventLoop.default_exception_handler
+    assert default.__module__ == "asyncio.base_events"
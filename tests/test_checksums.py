@@ -0,0 +1,143 @@
+"""
+Tests for checksum trailers: file handlers with `checksum_every=N` follow every N
+records (and each flush, close and rotation) with a Blake3 trailer line, which
+verify_log_file() and `python -m logxide verify` check to find bit rot and truncation.
+"""
+
+import os
+import subprocess
+import sys
+
+import pytest
+
+import logxide
+from logxide import FileHandler, RotatingFileHandler, logging
+from logxide.logxide import logging as native_logging
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+
+def _write(isolated_logger, handler, count):
+    logger = isolated_logger(handler, name="test.checksums", level=logging.DEBUG)
+    try:
+        for i in range(count):
+            logger.info("record %d", i)
+    finally:
+        handler.close()
+
+
+@pytest.fixture
+def app_log(tmp_path, isolated_logger):
+    path = tmp_path / "app.log"
+    handler = FileHandler(str(path), checksum_every=3)
+    handler.setFormatter(logging.Formatter("%(message)s"))
+    _write(isolated_logger, handler, 7)
+    return path
+
+
+def test_trailers_follow_every_n_records_and_close(app_log):
+    lines = app_log.read_text().splitlines()
+    trailers = [i for i, line in enumerate(lines) if line.startswith("#logxide-")]
+    assert trailers == [3, 7, 9]
+    assert lines[3].startswith("#logxide-checksum records=3 bytes=27 blake3=")
+    assert lines[9].startswith("#logxide-checksum records=1 ")
+    assert logxide.verify_log_file(app_log) == {
+        "ok": True,
+        "segments": 3,
+        "records": 7,
+        "corrupt": [],
+        "unverified": [],
+        "unverified_bytes": 0,
+    }
+
+
+def test_changed_and_missing_content_fail(app_log, tmp_path):
+    lines = app_log.read_text().splitlines(keepends=True)
+    flipped = tmp_path / "flipped.log"
+    flipped.write_text("".join(lines).replace("record 5", "record 9"))
+    report = logxide.verify_log_file(flipped)
+    assert not report["ok"]
+    assert (report["segments"], report["records"]) == (2, 4)
+    assert report["corrupt"] == [
+        {"first_line": 5, "trailer_line": 8, "reason": "checksum mismatch"}
+    ]
+
+    cut = tmp_path / "cut.log"
+    cut.write_text("".join(lines[:1] + lines[2:]))
+    (corrupt,) = logxide.verify_log_file(cut)["corrupt"]
+    assert corrupt == {"first_line": 1, "trailer_line": 3, "reason": "content missing"}
+
+    garbled = tmp_path / "garbled.log"
+    garbled.write_text("".join(lines[:3] + ["#logxide-checksum records=x\n"]))
+    (corrupt,) = logxide.verify_log_file(garbled)["corrupt"]
+    assert corrupt["reason"] == "malformed trailer"
+
+
+def test_lines_without_a_trailer_are_unverified(app_log, tmp_path):
+    # Left by a crash before the handler was recreated, and cut off mid-segment.
+    path = tmp_path / "resumed.log"
+    path.write_text("lost 1\nlost 2\n" + app_log.read_text() + "record 7\nrec")
+    report = logxide.verify_log_file(path)
+    assert report["ok"]
+    assert report["segments"] == 3
+    assert report["unverified"] == [(1, 2), (13, 14)]
+    assert report["unverified_bytes"] == len("lost 1\nlost 2\nrecord 7\nrec")
+
+
+def test_rotated_backups_end_with_a_trailer(tmp_path, isolated_logger):
+    path = tmp_path / "rot.log"
+    handler = RotatingFileHandler(
+        str(path), maxBytes=200, backupCount=5, checksum_every=4
+    )
+    handler.setFormatter(logging.Formatter("%(message)s"))
+    _write(isolated_logger, handler, 40)
+    files = [path, *sorted(tmp_path.glob("rot.log.*"))]
+    assert len(files) > 2
+    for file in files:
+        assert file.read_text().splitlines()[-1].startswith("#logxide-checksum")
+        report = logxide.verify_log_file(file)
+        assert report["ok"] and not report["unverified"], file
+    assert sum(logxide.verify_log_file(f)["records"] for f in files) == 40
+
+
+def test_readers_skip_trailers(app_log):
+    assert logxide.tail(app_log, 2) == ["record 5", "record 6"]
+    assert logxide.tail(app_log, 5) == [f"record {i}" for i in range(2, 7)]
+    records = logxide.read_log_file(app_log, format="%(message)s")
+    assert [r.msg for r in records] == [f"record {i}" for i in range(7)]
+    text = native_logging.convert_log_file(app_log, to="text", format="%(message)s")
+    assert "#logxide" not in text
+
+
+def test_checksum_every_must_be_positive(tmp_path):
+    with pytest.raises(ValueError, match="at least 1"):
+        FileHandler(str(tmp_path / "zero.log"), checksum_every=0)
+
+
+def test_cli_verify_exit_status(app_log, tmp_path):
+    def verify(*args):
+        return subprocess.run(
+            [sys.executable, "-m", "logxide", "verify", *map(str, args)],
+            env={**os.environ, "PYTHONPATH": REPO_ROOT},
+            capture_output=True,
+            text=True,
+            timeout=60,
+        )
+
+    result = verify(app_log)
+    assert result.returncode == 0, result.stderr
+    assert result.stdout == f"{app_log}: ok (3 segments, 7 records verified)\n"
+
+    partial = tmp_path / "partial.log"
+    partial.write_text(app_log.read_text() + "record 7\n")
+    assert verify(partial).returncode == 0
+    strict = verify(partial, "--strict")
+    assert strict.returncode == 1
+    assert "not covered: line 11" in strict.stdout
+
+    flipped = tmp_path / "flipped.log"
+    flipped.write_text(app_log.read_text().replace("record 0", "record 8"))
+    result = verify(app_log, flipped)
+    assert result.returncode == 1
+    assert f"{flipped}: CORRUPT" in result.stdout
+    assert "  lines 1-4: checksum mismatch\n" in result.stdout
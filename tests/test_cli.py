@@ -0,0 +1,121 @@
+"""
+Tests for ``python -m logxide``: converting, merging and counting log files through
+the extension's parsers and formatters.
+"""
+
+import json
+import os
+import subprocess
+import sys
+
+import pytest
+
+import logxide
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+FORMAT = "%(asctime)s %(levelname)s %(name)s: %(message)s"
+
+APP_LOG = """\
+2024-05-01 12:00:00,100 INFO app.web: started
+2024-05-01 12:00:02,000 ERROR app.db: failed
+Traceback (most recent call last):
+  File "app.py", line 1, in <module>
+ValueError: boom
+2024-05-01 12:00:04,500 WARNING app.web: slow
+"""
+
+WORKER_LOG = """\
+2024-05-01 12:00:01,000 INFO worker: job 1
+2024-05-01 12:00:03,000 DEBUG worker: job 2
+"""
+
+
+def _cli(*args):
+    return subprocess.run(
+        [sys.executable, "-m", "logxide", *args],
+        env={**os.environ, "PYTHONPATH": REPO_ROOT},
+        capture_output=True,
+        text=True,
+        timeout=60,
+    )
+
+
+@pytest.fixture
+def logs(tmp_path):
+    (tmp_path / "app.log").write_text(APP_LOG)
+    (tmp_path / "worker.log").write_text(WORKER_LOG)
+    return tmp_path
+
+
+def test_convert_text_to_json_and_back(logs):
+    result = _cli("convert", str(logs / "app.log"), "--format", FORMAT)
+    assert result.returncode == 0, result.stderr
+    records = [json.loads(line) for line in result.stdout.splitlines()]
+    assert [(r["levelname"], r["name"], r["message"]) for r in records] == [
+        ("INFO", "app.web", "started"),
+        ("ERROR", "app.db", "failed"),
+        ("WARNING", "app.web", "slow"),
+    ]
+    assert records[1]["exc_info"].endswith("ValueError: boom")
+    assert records[0]["created"] % 1 == pytest.approx(0.1)
+
+    (logs / "app.jsonl").write_text(result.stdout)
+    result = _cli("convert", str(logs / "app.jsonl"), "--to", "text")
+    assert result.returncode == 0, result.stderr
+    assert result.stdout.splitlines() == [
+        "INFO:app.web:started",
+        "ERROR:app.db:failed",
+        "Traceback (most recent call last):",
+        '  File "app.py", line 1, in <module>',
+        "ValueError: boom",
+        "WARNING:app.web:slow",
+    ]
+
+
+def test_merge_orders_records_by_time(logs):
+    out = logs / "all.log"
+    files = [str(logs / "app.log"), str(logs / "worker.log")]
+    result = _cli("merge", *files, "-o", str(out), "--format", FORMAT)
+    assert result.returncode == 0, result.stderr
+    assert result.stdout == ""
+    app = APP_LOG.splitlines(keepends=True)
+    worker = WORKER_LOG.splitlines(keepends=True)
+    expected = [app[0], worker[0], *app[1:5], worker[1], app[5]]
+    assert out.read_text() == "".join(expected)
+
+
+def test_stats_counts_per_logger_and_level(logs):
+    files = [str(logs / "app.log"), str(logs / "worker.log")]
+    result = _cli("stats", *files, "--format", FORMAT, "--json")
+    assert result.returncode == 0, result.stderr
+    stats = json.loads(result.stdout)
+    assert stats["records"] == 5
+    assert stats["last"] - stats["first"] == pytest.approx(4.4)
+    assert stats["levels"] == {"DEBUG": 1, "INFO": 2, "WARNING": 1, "ERROR": 1}
+    assert stats["loggers"]["worker"] == {"DEBUG": 1, "INFO": 1}
+
+    table = _cli("stats", *files, "--format", FORMAT).stdout.splitlines()
+    assert table[1].split() == ["logger", "DEBUG", "INFO", "WARNING", "ERROR", "total"]
+    assert table[2].split() == ["app.web", "0", "1", "1", "0", "2"]
+
+
+def test_parquet_round_trip(logs):
+    if "parquet" not in logxide.runtime_info()["features"]:
+        pytest.skip("built without the parquet feature")
+    parquet = logs / "app.parquet"
+    result = _cli(
+        "convert", str(logs / "app.log"), "-o", str(parquet), "--format", FORMAT
+    )
+    assert result.returncode == 0, result.stderr
+    result = _cli("convert", str(parquet), "--to", "text", "--format", FORMAT)
+    assert result.returncode == 0, result.stderr
+    lines = result.stdout.splitlines()
+    assert lines[1].endswith(" ERROR app.db: failed")
+    assert lines[4] == "ValueError: boom"
+
+
+def test_missing_file_fails_with_its_name(tmp_path):
+    result = _cli("stats", str(tmp_path / "missing.log"))
+    assert result.returncode == 1
+    assert "missing.log" in result.stderr
@@ -284,8 +284,8 @@ class TestFilterCallback:
         class ModifyFilter:
             def filter(self, record):
                 # Modify the message - replace 'secret' with '***'
-                if "secret" in record.get("msg", ""):
-                    record["msg"] = record["msg"].replace("secret", "***")
+                if "secret" in record.msg:
+                    record.msg = record.msg.replace("secret", "***")
                 return True
 
         f = ModifyFilter()
@@ -314,7 +314,7 @@ class TestFilterCallback:
 
         # Plain callable that filters based on level
         def level_filter(record):
-            return record.get("levelno", 0) >= 30  # Only WARNING and above
+            return record.levelno >= 30  # Only WARNING and above
 
         logger.addFilter(level_filter)
 
@@ -0,0 +1,103 @@
+"""
+Tests for ColumnFormatter: level and logger name columns at fixed widths,
+truncated with an ellipsis, for both the native and the Python dispatch path.
+"""
+
+import os
+import subprocess
+import sys
+import textwrap
+
+import pytest
+
+from logxide import ColumnFormatter
+from logxide.logxide import logging as native_logging
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+
+def _record(name, levelname, levelno, msg):
+    record = native_logging.LogRecord(name, levelno, "", 0, msg)
+    record.levelname = levelname
+    return record
+
+
+def test_pads_and_truncates_columns():
+    fmt = ColumnFormatter("%(levelname)s|%(name)s|%(message)s", name_width=8)
+
+    assert fmt.format(_record("db", "INFO", 20, "up")) == "INFO    |db      |up"
+    assert (
+        fmt.format(_record("payments.card", "CRITICAL", 50, "down"))
+        == "CRITICAL|payment…|down"
+    )
+
+
+def test_truncation_counts_characters():
+    fmt = ColumnFormatter("%(name)s", name_width=4)
+
+    assert fmt.format(_record("ünïcode", "INFO", 20, "")) == "ünï…"
+    assert fmt.format(_record("ünïc", "INFO", 20, "")) == "ünïc"
+
+
+def test_zero_width_leaves_the_field_alone():
+    fmt = ColumnFormatter("%(levelname)s %(name)s", level_width=0, name_width=0)
+
+    assert fmt.format(_record("a.long.name", "INFO", 20, "")) == "INFO a.long.name"
+
+
+def test_default_layout():
+    line = ColumnFormatter().format(_record("app", "WARNING", 30, "hi"))
+
+    assert line.endswith(" WARNING  app                  hi")
+
+
+SCRIPT = textwrap.dedent(
+    """
+    import sys
+    from logxide import ColumnFormatter, handlers, logging
+    handler = handlers.StreamHandler(sys.stdout)
+    handler.setFormatter(
+        ColumnFormatter("%(levelname)s %(name)s %(message)s", name_width=10)
+    )
+    if {python_path}:
+        handler.addFilter(lambda record: True)
+    print("native" if handler._native else "python", flush=True)
+    for name in ("db", "payments.processor"):
+        logger = logging.getLogger(name)
+        logger.propagate = False
+        logger.setLevel(logging.INFO)
+        logger.addHandler(handler)
+    logging.getLogger("db").warning("connected %s", 1)
+    try:
+        1 / 0
+    except ZeroDivisionError:
+        logging.getLogger("payments.processor").warning("declined", exc_info=True)
+    handler.flush()
+    """
+)
+
+
+@pytest.mark.parametrize("python_path", [False, True])
+def test_stream_handler_lines_up(python_path):
+    result = subprocess.run(
+        [sys.executable, "-c", SCRIPT.format(python_path=python_path)],
+        capture_output=True,
+        text=True,
+        timeout=30,
+        env=dict(os.environ, PYTHONPATH=REPO_ROOT),
+    )
+    lines = result.stdout.splitlines()
+
+    assert lines[:3] == [
+        "python" if python_path else "native",
+        "WARNING  db         connected 1",
+        "WARNING  payments.… declined",
+    ]
+    assert "ZeroDivisionError: division by zero" in lines
+
+
+def test_rust_handler_rejects_other_formatters():
+    from logxide import RustStreamHandler
+
+    with pytest.raises(TypeError):
+        RustStreamHandler("stderr").setFormatter(object())
@@ -0,0 +1,142 @@
+"""
+Tests for logxide.watch_config: a YAML / JSON dictConfig file applied at start and
+re-applied when it changes.
+"""
+
+import itertools
+import json
+import logging as std_logging
+import os
+import sys
+import threading
+import time
+
+import pytest
+
+import logxide
+from logxide.logxide import logging as native_logging
+
+
+def _wait_for(condition, timeout=5.0):
+    deadline = time.monotonic() + timeout
+    while not condition():
+        if time.monotonic() > deadline:
+            return False
+        time.sleep(0.02)
+    return True
+
+
+def _yaml(name, level, log_file=None):
+    lines = ["version: 1", "disable_existing_loggers: false"]
+    if log_file:
+        lines += [
+            "formatters:",
+            "  plain: {format: '%(message)s'}",
+            "handlers:",
+            "  out:",
+            "    class: logging.FileHandler",
+            f"    filename: {log_file}",
+            "    formatter: plain",
+        ]
+    handlers = "[out]" if log_file else "[]"
+    lines += [
+        "loggers:",
+        f"  {name}: {{level: {level}, handlers: {handlers}, propagate: false}}",
+    ]
+    return "\n".join(lines) + "\n"
+
+
+def test_applies_at_start_and_reloads_on_change(tmp_path, isolated_logger):
+    name = isolated_logger.name("test.watch")
+    path = tmp_path / "logging.yaml"
+    path.write_text(_yaml(name, "INFO"))
+    logger = native_logging.getLogger(name)
+
+    with logxide.watch_config(str(path), debounce=0.05) as watcher:
+        assert logger.level == 20
+        assert watcher.running and watcher.reloads == 0
+
+        path.write_text(_yaml(name, "DEBUG"))
+        assert _wait_for(lambda: logger.level == 10)
+        assert watcher.reloads == 1
+
+        # Editors that save to a temporary file and rename it over the original.
+        tmp = tmp_path / "logging.yaml.swp"
+        tmp.write_text(_yaml(name, "ERROR"))
+        os.replace(tmp, path)
+        assert _wait_for(lambda: logger.level == 40)
+
+    assert not watcher.running
+    path.write_text(_yaml(name, "WARNING"))
+    time.sleep(0.3)
+    assert logger.level == 40
+
+
+def test_invalid_file_keeps_the_previous_config(tmp_path, isolated_logger):
+    name = isolated_logger.name("test.watch")
+    path = tmp_path / "logging.json"
+    path.write_text("[1, 2]")
+    with pytest.raises(ValueError, match="must be a mapping"):
+        logxide.watch_config(str(path))
+
+    config = {
+        "version": 1,
+        "disable_existing_loggers": False,
+        "loggers": {name: {"level": "WARNING"}},
+    }
+    path.write_text(json.dumps(config))
+    watcher = logxide.watch_config(path, debounce=0.05)
+    try:
+        path.write_text('{"version": 1, "loggers": ')
+        assert _wait_for(lambda: watcher.last_error is not None)
+        assert "invalid config" in watcher.last_error
+        assert native_logging.getLogger(name).level == 30
+
+        config["loggers"][name]["level"] = "ERROR"
+        path.write_text(json.dumps(config))
+        assert _wait_for(lambda: watcher.last_error is None)
+        assert native_logging.getLogger(name).level == 40
+        assert watcher.reloads == 1
+    finally:
+        watcher.stop()
+
+
+def test_no_records_are_lost_across_reloads(tmp_path, isolated_logger):
+    name = isolated_logger.name("test.watch")
+    path = tmp_path / "logging.yaml"
+    log_file = tmp_path / "out.log"
+    path.write_text(_yaml(name, "INFO", log_file))
+    watcher = logxide.watch_config(path, debounce=60)
+    logger = native_logging.getLogger(name)
+    threads, count = 4, 10000
+
+    def produce(thread):
+        for i in range(count):
+            logger.warning("record %d.%d", thread, i)
+
+    # Switch threads often so records are logged in the middle of reloads.
+    interval = sys.getswitchinterval()
+    sys.setswitchinterval(1e-6)
+    try:
+        producers = [
+            threading.Thread(target=produce, args=(t,)) for t in range(threads)
+        ]
+        for producer in producers:
+            producer.start()
+        levels = itertools.cycle(["DEBUG", "INFO"])
+        while any(producer.is_alive() for producer in producers):
+            path.write_text(_yaml(name, next(levels), log_file))
+            watcher.reload()
+        for producer in producers:
+            producer.join()
+    finally:
+        sys.setswitchinterval(interval)
+        watcher.stop()
+    for handler in std_logging.getLogger(name).handlers:
+        handler.flush()
+
+    lines = log_file.read_text().splitlines()
+    assert sorted(lines) == sorted(
+        f"record {t}.{i}" for t in range(threads) for i in range(count)
+    )
+    assert watcher.reloads > 0
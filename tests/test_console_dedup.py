@@ -0,0 +1,102 @@
+"""
+Tests for configure_console_dedup: a record that reaches several StreamHandlers on
+the same console through propagation is written there once.
+"""
+
+import os
+import subprocess
+import sys
+import textwrap
+
+import pytest
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+
+def _run(body):
+    script = textwrap.dedent(
+        """
+        import sys
+        import logxide
+        from logxide import StreamHandler, logging
+
+        child = logging.getLogger("app.db")
+        child.setLevel(logging.DEBUG)
+        parent = logging.getLogger("app")
+        """
+    ) + textwrap.dedent(body)
+    env = dict(os.environ, PYTHONPATH=REPO_ROOT)
+    result = subprocess.run(
+        [sys.executable, "-c", script],
+        capture_output=True,
+        text=True,
+        timeout=30,
+        env=env,
+    )
+    assert result.returncode == 0, result.stderr
+    return result.stdout.splitlines(), result.stderr.splitlines()
+
+
+@pytest.mark.parametrize("dedup", [False, True])
+def test_propagated_record_written_once_per_console(dedup):
+    _, stderr = _run(
+        f"""
+        logxide.configure_console_dedup({dedup})
+        child.addHandler(StreamHandler())
+        parent.addHandler(StreamHandler())
+        child.info("once")
+        logxide.flush()
+        """
+    )
+    assert stderr == (["once"] if dedup else ["once", "once"])
+
+
+def test_other_console_still_written():
+    stdout, stderr = _run(
+        """
+        logxide.configure_console_dedup()
+        child.addHandler(StreamHandler())
+        parent.addHandler(StreamHandler(sys.stdout))
+        parent.addHandler(StreamHandler())
+        child.warning("both")
+        logxide.flush()
+        """
+    )
+    assert stdout == ["both"]
+    assert stderr == ["both"]
+
+
+def test_handler_below_its_level_does_not_claim():
+    _, stderr = _run(
+        """
+        logxide.configure_console_dedup()
+        quiet = StreamHandler()
+        quiet.setLevel(logging.ERROR)
+        quiet.setFormatter(logging.Formatter("child %(message)s"))
+        child.addHandler(quiet)
+        loud = StreamHandler()
+        loud.setFormatter(logging.Formatter("parent %(message)s"))
+        parent.addHandler(loud)
+        child.info("info")
+        child.error("error")
+        logxide.flush()
+        """
+    )
+    # Each StreamHandler has its own writer thread, so the two lines come in any order.
+    assert sorted(stderr) == ["child error", "parent info"]
+
+
+def test_python_dispatch_and_batches():
+    _, stderr = _run(
+        """
+        logxide.configure_console_dedup()
+        filtered = StreamHandler()
+        filtered.addFilter(lambda record: True)
+        child.addHandler(filtered)
+        parent.addHandler(StreamHandler())
+        child.info("filtered")
+        child.log_batch(logging.INFO, ["a", "b"])
+        logxide.flush()
+        """
+    )
+    assert stderr == ["filtered", "a", "b"]
@@ -0,0 +1,102 @@
+"""
+Tests for configure_crash_dump: a process killed by a fatal signal leaves a CRITICAL
+line with the native and Python backtraces in its log file. Each scenario crashes a
+fresh interpreter.
+"""
+
+import os
+import re
+import signal
+import subprocess
+import sys
+import textwrap
+
+import pytest
+
+import logxide
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+pytestmark = pytest.mark.skipif(
+    not hasattr(signal, "SIGSEGV") or sys.platform == "win32",
+    reason="needs POSIX signals",
+)
+
+
+def _crash(path, body):
+    script = textwrap.dedent(
+        f"""
+        import ctypes
+        import os
+
+        import logxide
+        from logxide import FileHandler, logging
+
+        handler = FileHandler({str(path)!r})
+        logging.getLogger().addHandler(handler)
+        logging.getLogger().warning("before the crash")
+        logxide.flush()
+        handler.flush()
+        """
+    ) + textwrap.dedent(body)
+    return subprocess.run(
+        [sys.executable, "-c", script],
+        env={**os.environ, "PYTHONPATH": REPO_ROOT},
+        capture_output=True,
+        text=True,
+        timeout=30,
+    )
+
+
+def test_segfault_is_written_to_the_log_file(tmp_path):
+    path = tmp_path / "app.log"
+    result = _crash(
+        path,
+        """
+        logxide.configure_crash_dump(handler)
+
+        def read_null():
+            ctypes.string_at(0)
+
+        read_null()
+        """,
+    )
+    assert result.returncode == -signal.SIGSEGV
+    lines = path.read_text().splitlines()
+    assert lines[0] == "before the crash"
+    assert re.fullmatch(
+        r"\d{4}-\d\d-\d\dT\d\d:\d\d:\d\d\.\d{3}Z CRITICAL logxide\.crash: "
+        rf"Fatal signal {int(signal.SIGSEGV)} \(SIGSEGV\) in process \d+, thread \d+",
+        lines[1],
+    )
+    assert lines[2] == "Native backtrace (most recent call first):"
+    assert "Fatal Python error: Segmentation fault" in lines
+    assert any("in read_null" in line for line in lines)
+
+
+def test_abort_is_written_to_the_path(tmp_path):
+    path = tmp_path / "app.log"
+    dump = tmp_path / "crash.log"
+    result = _crash(path, f"logxide.configure_crash_dump({str(dump)!r})\nos.abort()\n")
+    assert result.returncode == -signal.SIGABRT
+    assert "(SIGABRT)" in dump.read_text()
+    assert path.read_text() == "before the crash\n"
+
+
+def test_disabled_dump_writes_nothing(tmp_path):
+    path = tmp_path / "app.log"
+    result = _crash(
+        path,
+        """
+        logxide.configure_crash_dump(handler)
+        logxide.configure_crash_dump(None)
+        ctypes.string_at(0)
+        """,
+    )
+    assert result.returncode == -signal.SIGSEGV
+    assert path.read_text() == "before the crash\n"
+
+
+def test_missing_directory_raises(tmp_path):
+    with pytest.raises(FileNotFoundError):
+        logxide.configure_crash_dump(tmp_path / "missing" / "crash.log")
@@ -0,0 +1,74 @@
+"""
+Tests for custom levels: addLevelName() names a level for records, formatters,
+setLevel() and getLevelName(), in logxide and in the stdlib alike.
+"""
+
+import io
+import logging as std_logging
+
+import pytest
+
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def memory():
+    logger = logging.getLogger("custom_levels_tests")
+    mh = handlers.MemoryHandler()
+    logger.addHandler(mh)
+    yield logger, mh
+    logger.removeHandler(mh)
+    logger.setLevel(logging.NOTSET)
+
+
+def test_records_and_formatters_carry_the_registered_name(memory):
+    logger, mh = memory
+    logging.addLevelName(5, "TRACE")
+    logging.addLevelName(300, "AUDIT")
+    stream = io.StringIO()
+    handler = std_logging.StreamHandler(stream)
+    handler.setFormatter(logging.Formatter("%(levelname)s %(message)s"))
+    logger.addHandler(handler)
+    try:
+        logger.setLevel("TRACE")
+        logger.log(5, "traced")
+        logger.log(300, "audited")
+        logger.log(7, "unnamed")
+    finally:
+        logger.removeHandler(handler)
+    assert [(r.levelno, r.levelname) for r in mh.records] == [
+        (5, "TRACE"),
+        (300, "AUDIT"),
+        (7, "Level 7"),
+    ]
+    assert stream.getvalue() == "TRACE traced\nAUDIT audited\nLevel 7 unnamed\n"
+
+
+def test_native_formatter_renders_the_name(memory, tmp_path):
+    logger, _ = memory
+    path = tmp_path / "levels.log"
+    handler = handlers.FileHandler(str(path))
+    handler.setFormatter(logging.Formatter("%(levelname)s:%(message)s"))
+    logger.addHandler(handler)
+    try:
+        logging.addLevelName(25, "NOTICE")
+        logger.setLevel(logging.DEBUG)
+        logger.log(25, "noticed")
+        handler.flush()
+    finally:
+        logger.removeHandler(handler)
+    assert path.read_text() == "NOTICE:noticed\n"
+
+
+def test_lookups_are_shared_with_the_stdlib():
+    std_logging.addLevelName(15, "VERBOSE")
+    assert logging.getLevelName(15) == "VERBOSE"
+    assert logging.getLevelName("VERBOSE") == 15
+    assert logging.getLevelNamesMapping()["VERBOSE"] == 15
+
+    logging.addLevelName(60, "SECURITY")
+    assert std_logging.getLevelName(60) == "SECURITY"
+    assert std_logging.getLevelName("SECURITY") == 60
+    assert logging.getLevelName(61) == "Level 61"
+    with pytest.raises(ValueError, match="Unknown level"):
+        logging.getLogger("custom_levels_tests").setLevel("UNREGISTERED")
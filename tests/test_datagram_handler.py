@@ -0,0 +1,196 @@
+"""
+Tests for DatagramHandler: one record per UDP / Unix datagram, as a stdlib-
+compatible pickle or as JSON.
+"""
+
+import json
+import logging as std_logging
+import logging.handlers as std_handlers
+import pickle
+import socket
+import struct
+
+import pytest
+
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def udp():
+    sock = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+    sock.bind(("127.0.0.1", 0))
+    sock.settimeout(5)
+    yield sock
+    sock.close()
+
+
+def _unpickle(data):
+    (length,) = struct.unpack(">L", data[:4])
+    assert length == len(data) - 4
+    return std_logging.makeLogRecord(pickle.loads(data[4:]))
+
+
+def test_pickle_payload_is_read_by_stdlib_receivers(udp, isolated_logger):
+    handler = handlers.DatagramHandler("127.0.0.1", udp.getsockname()[1])
+    logger = isolated_logger(handler)
+    logger.warning("disk %s at %d%%", "/var", 93, extra={"host_id": 7})
+
+    record = _unpickle(udp.recv(65536))
+    assert record.getMessage() == "disk /var at 93%"
+    assert record.args is None
+    assert record.levelname == "WARNING"
+    assert record.levelno == logging.WARNING
+    assert record.name == logger.name
+    assert record.host_id == 7
+    assert handler.get_metrics()["sink_acknowledged"] == 1
+
+
+def test_pickle_dict_has_the_stdlib_keys(udp, isolated_logger):
+    handler = handlers.DatagramHandler("127.0.0.1", udp.getsockname()[1])
+    logger = isolated_logger(handler)
+    logger.info("hello")
+    ours = pickle.loads(udp.recv(65536)[4:])
+
+    reference = std_logging.LogRecord("x", logging.INFO, __file__, 1, "m", None, None)
+    expected = pickle.loads(std_handlers.DatagramHandler("", 0).makePickle(reference)[4:])
+    assert set(expected) <= set(ours)
+    assert isinstance(ours["created"], float)
+    assert isinstance(ours["thread"], int)
+
+
+def test_exception_text_is_sent(udp, isolated_logger):
+    handler = handlers.DatagramHandler("127.0.0.1", udp.getsockname()[1])
+    logger = isolated_logger(handler)
+    try:
+        raise ValueError("boom")
+    except ValueError:
+        logger.exception("failed")
+
+    record = _unpickle(udp.recv(65536))
+    assert record.exc_info is None
+    assert "ValueError: boom" in record.exc_text
+
+
+def test_json_format(udp, isolated_logger):
+    handler = handlers.DatagramHandler(
+        "127.0.0.1", udp.getsockname()[1], format="json"
+    )
+    logger = isolated_logger(handler)
+    logger.error("as json", extra={"job": "sync"})
+
+    payload = json.loads(udp.recv(65536))
+    assert payload["msg"] == "as json"
+    assert payload["levelname"] == "ERROR"
+    assert payload["extra"]["job"] == "sync"
+
+
+def test_handler_level_filters(udp, isolated_logger):
+    handler = handlers.DatagramHandler("127.0.0.1", udp.getsockname()[1])
+    handler.setLevel(logging.ERROR)
+    logger = isolated_logger(handler)
+    logger.info("skipped")
+    logger.error("sent")
+
+    assert _unpickle(udp.recv(65536)).getMessage() == "sent"
+    assert handler.get_metrics()["emitted"] == 1
+
+
+@pytest.mark.skipif(not hasattr(socket, "AF_UNIX"), reason="needs Unix sockets")
+def test_unix_datagram_socket(tmp_path, isolated_logger):
+    path = str(tmp_path / "log.sock")
+    sock = socket.socket(socket.AF_UNIX, socket.SOCK_DGRAM)
+    sock.bind(path)
+    sock.settimeout(5)
+    try:
+        handler = handlers.DatagramHandler(path)
+        logger = isolated_logger(handler)
+        logger.info("over unix")
+        assert _unpickle(sock.recv(65536)).getMessage() == "over unix"
+    finally:
+        sock.close()
+
+
+@pytest.mark.skipif(not hasattr(socket, "AF_UNIX"), reason="needs Unix sockets")
+def test_failed_send_is_counted_and_reported(tmp_path, isolated_logger):
+    errors = []
+    handler = handlers.DatagramHandler(
+        str(tmp_path / "missing.sock"), error_callback=errors.append
+    )
+    logger = isolated_logger(handler)
+    logger.info("nobody listening")
+
+    metrics = handler.get_metrics()
+    assert metrics["delivery_failed"] == 1, metrics
+    assert metrics["sink_acknowledged"] == 0, metrics
+    assert errors and "missing.sock" in errors[0]
+
+
+def test_unknown_format_is_rejected():
+    with pytest.raises(ValueError):
+        handlers.DatagramHandler("127.0.0.1", 9, format="msgpack")
+
+
+def test_text_format_uses_the_formatter(udp, isolated_logger):
+    handler = handlers.DatagramHandler(
+        "127.0.0.1", udp.getsockname()[1], format="text"
+    )
+    handler.setFormatter(logging.Formatter("%(levelname)s %(name)s: %(message)s"))
+    logger = isolated_logger(handler)
+    logger.warning("low on %s", "disk")
+
+    assert udp.recv(65536).decode() == f"WARNING {logger.name}: low on disk"
+
+
+def test_oversized_record_is_dropped_and_reported(udp, isolated_logger):
+    errors = []
+    handler = handlers.DatagramHandler(
+        "127.0.0.1",
+        udp.getsockname()[1],
+        max_packet_size=512,
+        error_callback=errors.append,
+    )
+    logger = isolated_logger(handler)
+    logger.info("x" * 1000)
+    logger.info("fits")
+
+    assert _unpickle(udp.recv(65536)).getMessage() == "fits"
+    metrics = handler.get_metrics()
+    assert metrics["delivery_failed"] == 1, metrics
+    assert metrics["sink_acknowledged"] == 1, metrics
+    assert errors and "max_packet_size" in errors[0]
+
+
+@pytest.mark.parametrize("format", ["pickle", "json"])
+def test_truncate_shortens_the_message_to_fit(udp, format, isolated_logger):
+    handler = handlers.DatagramHandler(
+        "127.0.0.1",
+        udp.getsockname()[1],
+        format=format,
+        max_packet_size=1024,
+        truncate=True,
+    )
+    logger = isolated_logger(handler)
+    logger.info("é" * 2000)
+
+    data = udp.recv(65536)
+    assert len(data) <= 1024
+    if format == "pickle":
+        message = _unpickle(data).getMessage()
+    else:
+        message = json.loads(data)["msg"]
+    assert message and set(message) == {"é"}
+    assert handler.get_metrics()["truncated"] == 1
+
+
+def test_truncate_cuts_text_payloads_at_the_limit(udp, isolated_logger):
+    handler = handlers.DatagramHandler(
+        "127.0.0.1",
+        udp.getsockname()[1],
+        format="text",
+        max_packet_size=100,
+        truncate=True,
+    )
+    logger = isolated_logger(handler)
+    logger.info("a" * 500)
+
+    assert udp.recv(65536) == b"a" * 100
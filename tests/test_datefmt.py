@@ -0,0 +1,133 @@
+"""
+Tests for datefmt in the Rust formatters: CPython strftime directives chrono spells
+differently (%f, %Z, locale names) and specifiers chrono doesn't know.
+"""
+
+import locale
+import os
+import subprocess
+import sys
+import textwrap
+import time
+
+from logxide import RustFormatter
+from logxide.compat_handlers import Formatter as CompatFormatter
+from logxide.logxide import logging as native_logging
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+# 2024-07-03 09:46:40.123456 UTC
+CREATED = 1720000000.123456
+
+
+def _record(created=CREATED):
+    record = native_logging.LogRecord("app", 20, "", 0, "hello")
+    record.levelname = "INFO"
+    record.created = created
+    return record
+
+
+def _asctime(datefmt, created=CREATED):
+    return RustFormatter("%(asctime)s", datefmt).format(_record(created))
+
+
+def test_percent_f_is_microseconds():
+    assert _asctime("%S.%f").endswith(".123456")
+    # Rounded like datetime.fromtimestamp, carrying into the seconds.
+    assert _asctime("%S.%f", 1720000000.9999996) == "41.000000"
+
+
+def test_chrono_fraction_specifiers_still_work():
+    assert _asctime("%3f|%.6f") == "123|.123456"
+
+
+def test_matches_time_strftime():
+    datefmt = "%Y-%m-%d %H:%M:%S %a %A %b %B %p %j %z %c %x %X %%"
+    assert _asctime(datefmt) == time.strftime(datefmt, time.localtime(CREATED))
+
+
+def test_unknown_specifiers_are_kept():
+    assert _asctime("%Y %Q 100%") == "2024 %Q 100%"
+
+
+def test_zone_name_follows_tzname():
+    script = textwrap.dedent(
+        """
+        from logxide import RustFormatter
+        from logxide.logxide import logging as native_logging
+
+        record = native_logging.LogRecord("app", 20, "", 0, "hello")
+        for created in (1720000000.0, 1705000000.0):
+            record.created = created
+            print(RustFormatter("%(asctime)s", "%H:%M %Z %z").format(record))
+        """
+    )
+    out = subprocess.run(
+        [sys.executable, "-c", script],
+        env={**os.environ, "PYTHONPATH": REPO_ROOT, "TZ": "Europe/Berlin"},
+        capture_output=True,
+        text=True,
+        check=True,
+    ).stdout.splitlines()
+
+    assert out == ["11:46 CEST +0200", "20:06 CET +0100"]
+
+
+DAYS = ["lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche"]
+MONTHS = [
+    "janvier",
+    "février",
+    "mars",
+    "avril",
+    "mai",
+    "juin",
+    "juillet",
+    "août",
+    "septembre",
+    "octobre",
+    "novembre",
+    "décembre",
+]
+
+
+def _french_strftime(fmt, t):
+    return {
+        "%A": DAYS[t[6]],
+        "%a": DAYS[t[6]][:3] + ".",
+        "%B": MONTHS[t[1] - 1],
+        "%b": MONTHS[t[1] - 1][:4] + ".",
+        "%p": "",
+    }[fmt]
+
+
+def test_names_follow_lc_time(monkeypatch):
+    # The names are read from time.strftime when the formatter is created.
+    monkeypatch.setattr(locale, "setlocale", lambda category, value=None: "fr_FR")
+    monkeypatch.setattr(time, "strftime", _french_strftime)
+    monkeypatch.setattr(
+        locale,
+        "nl_langinfo",
+        lambda item: "%d/%m/%Y" if item == locale.D_FMT else "",
+        raising=False,
+    )
+    fmt = RustFormatter("%(asctime)s", "%a %A %b %B%p|%x")
+    monkeypatch.undo()
+
+    local = time.localtime(CREATED)
+    expected = "{}. {} {}. {}|{}".format(
+        DAYS[local.tm_wday][:3],
+        DAYS[local.tm_wday],
+        MONTHS[local.tm_mon - 1][:4],
+        MONTHS[local.tm_mon - 1],
+        time.strftime("%d/%m/%Y", local),
+    )
+    assert fmt.format(_record()) == expected
+
+
+def test_compat_formatter_supports_percent_f():
+    fmt = CompatFormatter("%(asctime)s", datefmt="%S.%f %%f")
+
+    class Record:
+        created = CREATED
+
+    assert fmt.formatTime(Record(), fmt.datefmt).endswith(".123456 %f")
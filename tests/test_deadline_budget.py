@@ -0,0 +1,91 @@
+"""
+Tests for the `deadline_ms` latency budget: queued handlers count records that
+wait past their budget as late, and network handlers ship them immediately.
+"""
+
+import time
+
+import pytest
+
+from logxide import LogRecord, RustHTTPHandler, handlers, logging
+
+
+def _record(i, deadline_ms=None):
+    record = LogRecord(
+        name="deadline.budget.test",
+        levelno=20,
+        pathname="test.py",
+        lineno=i,
+        msg=f"message-{i}",
+    )
+    if deadline_ms is not None:
+        record.deadline_ms = deadline_ms
+    return record
+
+
+@pytest.fixture
+def slow_server(http_collector):
+    http_collector.delay = 0.05
+    return http_collector.url
+
+
+def test_records_past_budget_are_late(slow_server):
+    handler = RustHTTPHandler(slow_server, batch_size=1, flush_interval=3600)
+    try:
+        for i in range(5):
+            handler.emit(_record(i, deadline_ms=1))
+        handler.emit(_record(5))
+        handler.flush()
+        metrics = handler.get_metrics()
+    finally:
+        handler.shutdown()
+
+    assert metrics["deadline_records"] == 5, metrics
+    # The first record may be dequeued in time; the rest queue behind slow POSTs.
+    assert metrics["late"] >= 4, metrics
+    assert metrics["dequeued"] == 6, metrics
+
+
+def test_generous_budget_is_not_late(slow_server):
+    handler = RustHTTPHandler(slow_server, batch_size=1, flush_interval=3600)
+    try:
+        handler.emit(_record(0, deadline_ms=60_000))
+        handler.flush()
+        metrics = handler.get_metrics()
+    finally:
+        handler.shutdown()
+
+    assert metrics["deadline_records"] == 1, metrics
+    assert metrics["late"] == 0, metrics
+
+
+def test_late_record_ships_without_waiting_for_batch(slow_server):
+    handler = RustHTTPHandler(slow_server, batch_size=100, flush_interval=3600)
+    try:
+        handler.emit(_record(0, deadline_ms=0))
+        deadline = time.monotonic() + 5
+        while (
+            handler.get_metrics()["sink_acknowledged"] == 0
+            and time.monotonic() < deadline
+        ):
+            time.sleep(0.02)
+        metrics = handler.get_metrics()
+    finally:
+        handler.shutdown()
+
+    assert metrics["late"] == 1, metrics
+    assert metrics["sink_acknowledged"] == 1, metrics
+
+
+def test_stream_handler_counts_budget_from_logger_extra(isolated_logger):
+    handler = handlers.StreamHandler()
+    logger = isolated_logger(handler, name="deadline.stream", level=logging.DEBUG)
+    logger.info("tight", extra={"deadline_ms": 0})
+    logger.info("no budget")
+    deadline = time.monotonic() + 5
+    while handler.get_metrics()["dequeued"] < 2 and time.monotonic() < deadline:
+        time.sleep(0.02)
+    metrics = handler.get_metrics()
+
+    assert metrics["deadline_records"] == 1, metrics
+    assert metrics["late"] == 1, metrics
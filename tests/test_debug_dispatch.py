@@ -0,0 +1,183 @@
+"""
+Tests for debug_dispatch: sampled records are traced on stderr with the logger, filter
+and handler decisions that decided where they went.
+"""
+
+import os
+import subprocess
+import sys
+import textwrap
+
+import pytest
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+
+def _trace(body):
+    script = textwrap.dedent(
+        """
+        import logging
+        import sys
+
+        import logxide
+        from logxide import handlers
+
+        app = logging.getLogger("app")
+        db = logging.getLogger("app.db")
+        db.setLevel(logging.DEBUG)
+        """
+    ) + textwrap.dedent(body)
+    env = dict(os.environ, PYTHONPATH=REPO_ROOT)
+    result = subprocess.run(
+        [sys.executable, "-c", script],
+        capture_output=True,
+        text=True,
+        timeout=30,
+        env=env,
+    )
+    assert result.returncode == 0, result.stderr
+    return result.stderr.splitlines()
+
+
+def test_logger_level_drop_is_explained():
+    lines = _trace(
+        """
+        logxide.debug_dispatch()
+        app.debug("quiet")
+        """
+    )
+    assert lines == [
+        "logxide dispatch: app DEBUG",
+        '  logger "app": dropped, DEBUG is below the effective level WARNING',
+    ]
+
+
+def test_filter_drop_names_the_filter():
+    lines = _trace(
+        """
+        def no_secrets(record):
+            return "secret" not in record.msg
+
+        db.addFilter(no_secrets)
+        logxide.debug_dispatch()
+        db.info("a secret")
+        """
+    )
+    assert lines == [
+        'logxide dispatch: app.db INFO "a secret"',
+        '  logger "app.db": dropped by filter no_secrets',
+    ]
+
+
+def test_handlers_along_the_path():
+    lines = _trace(
+        """
+        console = handlers.StreamHandler(sys.stdout)
+        console.name = "console"
+        console.setLevel(logging.WARNING)
+        db.addHandler(console)
+
+        class Recorder(logging.Handler):
+            def emit(self, record):
+                pass
+
+        logging.getLogger().addHandler(Recorder())
+        logxide.debug_dispatch()
+        db.info("hello %s", "world")
+        logxide.flush()
+        """
+    )
+    assert lines == [
+        'logxide dispatch: app.db INFO "hello world"',
+        "  path: app.db -> app -> root",
+        '  "app.db" StreamHandler "console": skipped, INFO is below the handler level '
+        "WARNING",
+        '  "root" Recorder: handled',
+    ]
+
+
+def test_no_handlers():
+    lines = _trace(
+        """
+        logxide.debug_dispatch()
+        db.info("lost")
+        """
+    )
+    assert lines[1:] == [
+        "  path: app.db -> app -> root",
+        "  no handlers along the propagation path",
+    ]
+
+
+def test_route_on_the_path():
+    lines = _trace(
+        """
+        logxide.route("app", [handlers.MemoryHandler()])
+        logging.getLogger().addHandler(handlers.MemoryHandler())
+        logxide.debug_dispatch()
+        db.info("routed")
+        """
+    )
+    assert lines[1:] == [
+        "  path: app.db -> app -> route app",
+        '  route "app" MemoryHandler: accepted',
+    ]
+
+
+def test_queue_overflow_is_reported():
+    lines = _trace(
+        """
+        queue = handlers.QueueHandler(capacity=1, overflow="drop_newest")
+        db.addHandler(queue)
+        logxide.debug_dispatch()
+        db.info("first")
+        db.info("second")
+        """
+    )
+    assert lines[2] == '  "app.db" QueueHandler: accepted'
+    assert lines[5] == '  "app.db" QueueHandler: dropped, queue full'
+
+
+def test_console_dedup_is_reported():
+    lines = _trace(
+        """
+        logxide.configure_console_dedup()
+        db.addHandler(handlers.StreamHandler(sys.stdout))
+        app.addHandler(handlers.StreamHandler(sys.stdout))
+        logxide.debug_dispatch()
+        db.info("once")
+        logxide.flush()
+        """
+    )
+    assert lines[2:] == [
+        '  "app.db" StreamHandler: accepted',
+        '  "app" StreamHandler: skipped, an earlier handler already wrote it to this '
+        "console",
+    ]
+
+
+def test_sample_rate_spreads_traced_records():
+    lines = _trace(
+        """
+        logxide.debug_dispatch(sample_rate=0.25)
+        for n in range(8):
+            app.debug("quiet %d", n)
+        db.log_batch(logging.INFO, ["a", "b", "c", "d"])
+        logxide.debug_dispatch(False)
+        app.debug("untraced")
+        """
+    )
+    headers = [line for line in lines if line.startswith("logxide dispatch:")]
+    assert headers == [
+        "logxide dispatch: app DEBUG",
+        "logxide dispatch: app DEBUG",
+        'logxide dispatch: app.db INFO "d"',
+    ]
+
+
+@pytest.mark.parametrize("rate", [0, -1, 1.5, float("nan")])
+def test_sample_rate_is_validated(rate):
+    import logxide
+
+    with pytest.raises(ValueError):
+        logxide.debug_dispatch(sample_rate=rate)
@@ -0,0 +1,104 @@
+"""
+Tests for logxide.DedupFilter: runs of identical messages collapse into the first
+record plus one "(repeated N more times)" summary with a dedup_count extra.
+"""
+
+import time
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def capture(isolated_logger):
+    mh = handlers.MemoryHandler()
+    logger = isolated_logger(mh, name="app.dedup", level=logging.DEBUG)
+    yield logger, mh
+    for f in logger.filters:
+        logger.removeFilter(f)
+
+
+def _messages(mh):
+    return [r.getMessage() for r in mh.records]
+
+
+def test_a_different_message_ends_the_run(capture):
+    logger, mh = capture
+    logger.addFilter(logxide.DedupFilter(60))
+    for _ in range(4):
+        logger.warning("disk %s full", "/var")
+    logger.info("recovered")
+    assert _messages(mh) == [
+        "disk /var full",
+        "disk /var full (repeated 3 more times)",
+        "recovered",
+    ]
+    summary = mh.records[1]
+    assert summary.dedup_count == 3
+    assert summary.levelno == logging.WARNING
+    assert summary.dedup_first <= summary.dedup_last
+
+
+def test_the_window_closing_logs_the_summary(capture):
+    logger, mh = capture
+    logger.addFilter(logxide.DedupFilter(0.2))
+    logger.error("timeout")
+    logger.error("timeout")
+    deadline = time.monotonic() + 5
+    while len(mh.records) < 2 and time.monotonic() < deadline:
+        time.sleep(0.05)
+    assert _messages(mh) == ["timeout", "timeout (repeated 1 more time)"]
+    logger.error("timeout")
+    assert _messages(mh)[-1] == "timeout"
+
+
+def test_level_and_logger_are_part_of_the_key(capture):
+    logger, mh = capture
+    logger.addFilter(logxide.DedupFilter(60))
+    logger.info("ping")
+    logger.warning("ping")
+    logger.warning("ping")
+    logger.info("ping")
+    assert _messages(mh) == [
+        "ping",
+        "ping",
+        "ping (repeated 1 more time)",
+        "ping",
+    ]
+
+
+def test_no_summary_without_repeats(capture):
+    logger, mh = capture
+    logger.addFilter(logxide.DedupFilter(60))
+    for message in ("a", "b", "a"):
+        logger.info(message)
+    assert _messages(mh) == ["a", "b", "a"]
+
+
+class _ListHandler(logging.Handler):
+    def __init__(self):
+        super().__init__()
+        self.messages = []
+
+    def emit(self, record):
+        self.messages.append(record.getMessage())
+
+
+def test_on_a_handler(isolated_logger):
+    handler = _ListHandler()
+    handler.addFilter(logxide.DedupFilter(60))
+    logger = isolated_logger(handler, name="app.dedup")
+    logger.info("retry")
+    logger.info("retry")
+    logger.info("done")
+    logger.removeHandler(handler)
+    assert handler.messages == ["retry", "retry (repeated 1 more time)", "done"]
+
+
+def test_attributes_and_errors():
+    assert logxide.DedupFilter(1.5).window_seconds == 1.5
+    for window in (0, -1, float("inf")):
+        with pytest.raises(ValueError):
+            logxide.DedupFilter(window)
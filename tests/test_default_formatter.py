@@ -0,0 +1,100 @@
+"""
+Tests for logxide.set_default_formatter: handlers without a formatter of their own take
+the default for their kind, new and existing ones alike.
+"""
+
+import logging as std_logging
+
+import pytest
+
+import logxide
+from logxide import FileHandler, JsonFormatter, RotatingFileHandler, StreamHandler
+from logxide.config import dictConfig
+
+
+@pytest.fixture(autouse=True)
+def reset_defaults():
+    yield
+    logxide.set_default_formatter(None)
+
+
+def _write(isolated_logger, handler, message):
+    logger = isolated_logger(handler)
+    logger.warning(message)
+    handler.flush()
+    logger.removeHandler(handler)
+    handler.close()
+
+
+def test_file_default_applies_to_new_and_existing_handlers(tmp_path, isolated_logger):
+    existing = FileHandler(str(tmp_path / "existing.log"))
+    explicit = FileHandler(str(tmp_path / "explicit.log"))
+    own = std_logging.Formatter("own %(message)s")
+    explicit.setFormatter(own)
+    default = std_logging.Formatter("[%(levelname)s] %(message)s")
+    logxide.set_default_formatter(default, handler_type="file")
+
+    rotating = RotatingFileHandler(str(tmp_path / "rotating.log"))
+    assert existing.formatter is default
+    assert rotating.formatter is default
+    assert explicit.formatter is own
+    assert StreamHandler().formatter is None
+
+    _write(isolated_logger, existing, "one")
+    _write(isolated_logger, rotating, "two")
+    assert (tmp_path / "existing.log").read_text() == "[WARNING] one\n"
+    assert (tmp_path / "rotating.log").read_text() == "[WARNING] two\n"
+
+
+def test_replacing_and_removing_the_default():
+    handler = StreamHandler()
+    first = std_logging.Formatter("first %(message)s")
+    logxide.set_default_formatter(first, handler_type="stream")
+    assert handler.formatter is first
+
+    second = JsonFormatter()
+    logxide.set_default_formatter(second)
+    assert handler.formatter is second
+
+    own = std_logging.Formatter("%(message)s")
+    handler.setFormatter(own)
+    logxide.set_default_formatter(None)
+    assert handler.formatter is own
+    assert StreamHandler().formatter is None
+
+    with pytest.raises(ValueError, match="handler_type"):
+        logxide.set_default_formatter(first, handler_type="http")
+
+
+def test_dict_config_handlers_take_the_default(tmp_path, isolated_logger):
+    name = isolated_logger.name("app.default_formatter")
+    logxide.set_default_formatter(JsonFormatter(), handler_type="file")
+    dictConfig(
+        {
+            "version": 1,
+            "disable_existing_loggers": False,
+            "formatters": {"plain": {"format": "%(message)s"}},
+            "handlers": {
+                "json": {
+                    "class": "logging.FileHandler",
+                    "filename": str(tmp_path / "a"),
+                },
+                "plain": {
+                    "class": "logging.FileHandler",
+                    "filename": str(tmp_path / "b"),
+                    "formatter": "plain",
+                },
+            },
+            "loggers": {name: {"handlers": ["json", "plain"]}},
+        }
+    )
+    json_handler, plain = std_logging.getLogger(name).handlers
+    assert isinstance(json_handler.formatter, JsonFormatter)
+    assert not isinstance(plain.formatter, JsonFormatter)
+    dictConfig({"version": 1, "disable_existing_loggers": False})
+
+
+def test_reinitialize_forgets_the_default():
+    logxide.set_default_formatter(JsonFormatter())
+    logxide.reinitialize()
+    assert StreamHandler().formatter is None
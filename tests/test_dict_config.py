@@ -0,0 +1,280 @@
+"""
+Tests for logxide.config.dictConfig: stdlib dictConfig semantics applied to both the
+stdlib logger and logxide's native logger.
+"""
+
+import io
+import logging as std_logging
+import logging.handlers as std_handlers
+import os
+import subprocess
+import sys
+import textwrap
+
+import pytest
+
+from logxide import handlers
+from logxide.config import dictConfig
+from logxide.logxide import logging as native_logging
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+
+def _run(script):
+    return subprocess.run(
+        [sys.executable, "-c", textwrap.dedent(script)],
+        env={**os.environ, "PYTHONPATH": REPO_ROOT},
+        capture_output=True,
+        text=True,
+        check=True,
+    ).stdout.splitlines()
+
+
+def test_configures_stdlib_and_native_logger(tmp_path, isolated_logger):
+    name = isolated_logger.name("test.dict_config")
+    dictConfig(
+        {
+            "version": 1,
+            "disable_existing_loggers": False,
+            "handlers": {
+                "file": {"class": "logging.FileHandler", "filename": str(tmp_path / "a")}
+            },
+            "loggers": {
+                name: {"handlers": ["file"], "level": "DEBUG", "propagate": False}
+            },
+        }
+    )
+    shell = std_logging.getLogger(name)
+    native = native_logging.getLogger(name)
+
+    assert [type(h) for h in shell.handlers] == [handlers.FileHandler]
+    assert shell.level == native.level == std_logging.DEBUG
+    assert shell.propagate is False
+    assert native.propagate is False
+    assert native.disabled is False
+
+    native.debug("through the native logger")
+    shell.handlers[0].flush()
+    assert "through the native logger" in (tmp_path / "a").read_text()
+
+
+def test_reconfiguring_replaces_handlers(tmp_path, isolated_logger):
+    name = isolated_logger.name("test.dict_config")
+
+    def configure(path):
+        dictConfig(
+            {
+                "version": 1,
+                "disable_existing_loggers": False,
+                "handlers": {"file": {"class": "logging.FileHandler", "filename": path}},
+                "loggers": {name: {"handlers": ["file"], "level": "INFO"}},
+            }
+        )
+
+    configure(str(tmp_path / "first"))
+    configure(str(tmp_path / "second"))
+    native = native_logging.getLogger(name)
+    native.propagate = False
+    native.info("only the second")
+    shell = std_logging.getLogger(name)
+    shell.handlers[0].flush()
+
+    assert len(shell.handlers) == 1
+    assert "only the second" not in (tmp_path / "first").read_text()
+    assert "only the second" in (tmp_path / "second").read_text()
+
+
+def test_filters_formatters_and_references(isolated_logger):
+    name = isolated_logger.name("test.dict_config")
+    stream = io.StringIO()
+    dictConfig(
+        {
+            "version": 1,
+            "disable_existing_loggers": False,
+            "streams": {"main": stream},
+            "formatters": {"brief": {"format": "%(levelname)s|%(message)s"}},
+            "filters": {"only_app": {"name": name}},
+            "handlers": {
+                "console": {
+                    "()": "logging.StreamHandler",
+                    "stream": "cfg://streams.main",
+                    "formatter": "brief",
+                    "filters": ["only_app"],
+                    ".": {"terminator": "!\n"},
+                },
+                "buffer": {
+                    "class": "logging.handlers.MemoryHandler",
+                    "capacity": 10,
+                    "target": "console",
+                },
+            },
+            "loggers": {name: {"handlers": ["console", "buffer"], "level": "INFO"}},
+        }
+    )
+    shell = std_logging.getLogger(name)
+    console, buffer = shell.handlers
+
+    assert console.stream is stream
+    assert console.terminator == "!\n"
+    assert console.filters[0].name == name
+    assert console.name == "console"
+    assert buffer.target is console
+
+    shell.propagate = False
+    shell.info("hello")
+    assert stream.getvalue() == "INFO|hello!\n"
+    buffer.flush()
+    assert stream.getvalue() == "INFO|hello!\n" * 2
+
+
+def test_ext_values_and_syslog_address(isolated_logger):
+    name = isolated_logger.name("test.dict_config")
+    config = {
+        "version": 1,
+        "disable_existing_loggers": False,
+        "handlers": {
+            "out": {"class": "logging.StreamHandler", "stream": "ext://sys.stderr"},
+            "syslog": {
+                "class": "logging.handlers.SysLogHandler",
+                "address": ["127.0.0.1", 514],
+            },
+        },
+        "loggers": {name: {"handlers": ["out", "syslog"]}},
+    }
+    dictConfig(config)
+    out, syslog = std_logging.getLogger(name).handlers
+
+    assert out.stream is sys.stderr
+    assert isinstance(syslog, std_handlers.SysLogHandler)
+    # The caller's dictionary is left alone.
+    assert config["handlers"]["syslog"]["address"] == ["127.0.0.1", 514]
+    assert config["disable_existing_loggers"] is False
+
+
+def test_errors_match_stdlib(isolated_logger):
+    with pytest.raises(ValueError, match="doesn't specify a version"):
+        dictConfig({})
+    with pytest.raises(ValueError, match="Unsupported version: 2"):
+        dictConfig({"version": 2})
+
+    with pytest.raises(ValueError, match="Unable to configure handler 'bad'") as info:
+        dictConfig(
+            {
+                "version": 1,
+                "disable_existing_loggers": False,
+                "handlers": {"bad": {"class": "no.such.Handler"}},
+            }
+        )
+    assert "Cannot resolve 'no.such.Handler'" in str(info.value.__cause__)
+
+    name = isolated_logger.name("test.dict_config")
+    with pytest.raises(ValueError, match="Unable to configure logger") as info:
+        dictConfig(
+            {
+                "version": 1,
+                "disable_existing_loggers": False,
+                "loggers": {name: {"handlers": ["missing"]}},
+            }
+        )
+    assert "Unable to add handler 'missing'" in str(info.value.__cause__)
+
+
+def test_incremental_only_updates_levels_and_propagate(tmp_path, isolated_logger):
+    name = isolated_logger.name("test.dict_config")
+    dictConfig(
+        {
+            "version": 1,
+            "disable_existing_loggers": False,
+            "handlers": {
+                "file": {"class": "logging.FileHandler", "filename": str(tmp_path / "a")}
+            },
+            "loggers": {name: {"handlers": ["file"], "level": "INFO"}},
+        }
+    )
+    dictConfig(
+        {
+            "version": 1,
+            "incremental": True,
+            "handlers": {"file": {"level": "ERROR"}},
+            "loggers": {name: {"level": "DEBUG", "propagate": False}},
+        }
+    )
+    shell = std_logging.getLogger(name)
+
+    assert len(shell.handlers) == 1
+    assert shell.handlers[0].level == std_logging.ERROR
+    assert native_logging.getLogger(name).level == std_logging.DEBUG
+    assert native_logging.getLogger(name).propagate is False
+
+
+def test_existing_loggers_are_disabled_or_reset():
+    out = _run(
+        """
+        import logging
+        from logxide.config import dictConfig
+        from logxide.logxide import logging as native_logging
+
+        for name in ("app.child", "other"):
+            logging.getLogger(name).setLevel(logging.ERROR)
+        native_logging.getLogger("native_only")
+        dictConfig(
+            {
+                "version": 1,
+                "loggers": {"app": {"level": "INFO"}},
+            }
+        )
+        for name in ("app", "app.child", "other"):
+            shell = logging.getLogger(name)
+            native = native_logging.getLogger(name)
+            print(name, shell.disabled, native.disabled, shell.level, native.level)
+        print(native_logging.getLogger("native_only").disabled)
+        """
+    )
+    assert out == [
+        "app False False 20 20",
+        "app.child False False 0 0",
+        "other True True 40 40",
+        "True",
+    ]
+
+
+def test_installed_logging_config_routes_natively(tmp_path):
+    # Django-style: stdlib logging.config.dictConfig once logxide is installed.
+    out = _run(
+        f"""
+        import logxide
+        import logging
+        import logging.config
+
+        logging.config.dictConfig(
+            {{
+                "version": 1,
+                "formatters": {{"plain": {{"format": "%(name)s:%(message)s"}}}},
+                "handlers": {{
+                    "file": {{
+                        "class": "logging.FileHandler",
+                        "filename": {str(tmp_path / "app.log")!r},
+                        "formatter": "plain",
+                    }},
+                    "root_file": {{
+                        "class": "logging.FileHandler",
+                        "filename": {str(tmp_path / "root.log")!r},
+                        "formatter": "plain",
+                    }},
+                }},
+                "loggers": {{
+                    "app": {{"handlers": ["file"], "level": "INFO", "propagate": False}}
+                }},
+                "root": {{"handlers": ["root_file"], "level": "INFO"}},
+            }}
+        )
+        logging.getLogger("app").info("to the app file")
+        logging.getLogger("lib").info("to the root file")
+        logging.flush()
+        print(type(logging.getLogger("app").handlers[0]).__module__)
+        """
+    )
+
+    assert out == ["logxide.handlers"]
+    assert (tmp_path / "app.log").read_text() == "app:to the app file\n"
+    assert (tmp_path / "root.log").read_text() == "lib:to the root file\n"
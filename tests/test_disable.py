@@ -0,0 +1,60 @@
+"""
+Tests for logger.disabled and logging.disable(): both drop records in logxide's
+loggers, whether set on the stdlib logger or the native one.
+"""
+
+import logging as std_logging
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+from logxide.logxide import logging as native_logging
+
+
+@pytest.fixture
+def memory():
+    logger = logging.getLogger("disable_tests")
+    native_logging.getLogger("disable_tests").propagate = False
+    mh = handlers.MemoryHandler()
+    logger.addHandler(mh)
+    yield logger, mh
+    logger.removeHandler(mh)
+    logger.disabled = False
+    logging.disable(logging.NOTSET)
+
+
+def test_logger_disabled_drops_records(memory):
+    logger, mh = memory
+    logger.disabled = True
+    assert native_logging.getLogger("disable_tests").disabled is True
+    assert not logger.isEnabledFor(logging.CRITICAL)
+    logger.critical("dropped")
+    assert mh.records == []
+
+    logger.disabled = False
+    logger.warning("kept")
+    assert [r.getMessage() for r in mh.records] == ["kept"]
+
+
+def test_logging_disable_drops_records_up_to_the_level(memory):
+    logger, mh = memory
+    std_logging.disable(logging.ERROR)
+    assert std_logging.root.manager.disable == logging.ERROR
+    assert not logger.isEnabledFor(logging.ERROR)
+    assert not native_logging.getLogger("disable_tests").isEnabledFor(logging.ERROR)
+    logger.error("dropped")
+    logger.critical("kept")
+    assert [r.getMessage() for r in mh.records] == ["kept"]
+
+    logging.disable(logging.NOTSET)
+    logger.warning("back")
+    assert mh.records[-1].getMessage() == "back"
+
+
+def test_reinitialize_lifts_disable():
+    logging.disable()
+    assert not native_logging.getLogger("disable_tests").isEnabledFor(logging.CRITICAL)
+    logxide.reinitialize()
+    assert std_logging.root.manager.disable == logging.NOTSET
+    assert native_logging.getLogger("disable_tests").isEnabledFor(logging.CRITICAL)
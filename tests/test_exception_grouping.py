@@ -0,0 +1,78 @@
+"""
+Tests for configure_exception_grouping(): stable exception fingerprints and
+in-process aggregation of repeated tracebacks.
+"""
+
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+
+def _fail_a():
+    raise ValueError("a failure")
+
+
+def _fail_b():
+    raise KeyError("b failure")
+
+
+
+@pytest.fixture
+def capture(isolated_logger):
+    mh = handlers.MemoryHandler()
+    yield isolated_logger(mh, level=logging.DEBUG), mh
+    logxide.configure_exception_grouping(enabled=False)
+
+
+def _log(logger, fn, msg="failed"):
+    try:
+        fn()
+    except Exception:
+        logger.exception(msg)
+
+
+def test_disabled_by_default(capture):
+    logger, mh = capture
+    _log(logger, _fail_a)
+    assert not hasattr(mh.records[-1], "exc_fingerprint")
+
+
+def test_fingerprint_is_stable_and_distinct(capture):
+    logger, mh = capture
+    logxide.configure_exception_grouping()
+    _log(logger, _fail_a, "first")
+    _log(logger, _fail_a, "second")
+    _log(logger, _fail_b)
+    first, second, other = (r.exc_fingerprint for r in mh.records[-3:])
+    assert first == second
+    assert first != other
+    assert len(first) == 16
+    assert "Traceback" in mh.records[-2].exc_text
+
+
+def test_window_collapses_repeats(capture):
+    logger, mh = capture
+    logxide.configure_exception_grouping(window=60)
+    for _ in range(3):
+        _log(logger, _fail_a)
+    records = mh.records[-3:]
+    assert [r.exc_seen_count for r in records] == [1, 2, 3]
+    assert "Traceback" in records[0].exc_text
+    assert "seen 3 times in the last 60s" in records[2].exc_text
+    assert "Traceback" not in records[2].exc_text
+
+
+def test_records_without_exception_untouched(capture):
+    logger, mh = capture
+    logxide.configure_exception_grouping(window=60)
+    logger.error("plain")
+    assert not hasattr(mh.records[-1], "exc_fingerprint")
+
+
+def test_invalid_arguments():
+    with pytest.raises(ValueError):
+        logxide.configure_exception_grouping(frames=0)
+    with pytest.raises(ValueError):
+        logxide.configure_exception_grouping(window=0)
@@ -0,0 +1,73 @@
+"""
+Tests for configure_extra_limits(): extras beyond max_keys are dropped and values
+over max_value_bytes are truncated with a marker, and both are counted.
+"""
+
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def capture(isolated_logger):
+    mh = handlers.MemoryHandler()
+    yield isolated_logger(mh, level=logging.DEBUG), mh
+    logxide.configure_extra_limits()
+
+
+def test_unlimited_by_default(capture):
+    logger, mh = capture
+    logger.info("big", extra={f"k{i}": "v" * 10_000 for i in range(50)})
+    record = mh.records[-1]
+    assert record.k49 == "v" * 10_000
+    assert not hasattr(record, "extra_keys_dropped")
+
+
+def test_keys_beyond_the_limit_are_dropped_in_order(capture):
+    logger, mh = capture
+    logxide.configure_extra_limits(max_keys=2)
+    logger.info("keys", extra={"c": 1, "a": 2, "b": 3, "d": 4})
+    record = mh.records[-1]
+    assert (record.c, record.a) == (1, 2)
+    assert not hasattr(record, "b") and not hasattr(record, "d")
+    assert record.extra_keys_dropped == 2
+
+
+def test_long_values_are_truncated_with_a_marker(capture):
+    logger, mh = capture
+    logxide.configure_extra_limits(max_value_bytes=5)
+    logger.info(
+        "values",
+        extra={"short": "abc", "text": "abcdefgh", "nested": {"k": [1, 2, 3]}, "n": 7},
+    )
+    record = mh.records[-1]
+    assert record.short == "abc"
+    assert record.text == "abcde...[truncated 3 bytes]"
+    assert record.nested == '{"k":...[truncated 8 bytes]'
+    assert record.n == 7
+
+
+def test_truncation_keeps_whole_characters(capture):
+    logger, mh = capture
+    logxide.configure_extra_limits(max_value_bytes=2)
+    logger.info("utf8", extra={"word": "héllo"})
+    assert mh.records[-1].word == "h...[truncated 5 bytes]"
+
+
+def test_stats_count_limited_records(capture):
+    logger, _ = capture
+    before = logxide.extra_limit_stats()
+    logxide.configure_extra_limits(max_keys=1, max_value_bytes=3)
+    logger.info("one", extra={"a": "long value", "b": 1})
+    logger.info("two", extra={"a": 1})
+    after = logxide.extra_limit_stats()
+    assert after["records_limited"] - before["records_limited"] == 1
+    assert after["keys_dropped"] - before["keys_dropped"] == 1
+    assert after["values_truncated"] - before["values_truncated"] == 1
+
+
+def test_zero_is_rejected():
+    with pytest.raises(ValueError):
+        logxide.configure_extra_limits(max_keys=0)
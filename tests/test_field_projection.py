@@ -0,0 +1,111 @@
+"""
+Tests for logxide.FieldProjection: each handler keeps its own set of extras and
+optional fields, before it formats or serializes the record.
+"""
+
+import json
+import logging as std_logging
+
+import pytest
+
+import logxide
+from logxide import FileHandler, HTTPHandler, JsonFormatter
+
+
+@pytest.fixture
+def logger(isolated_logger):
+    logger = isolated_logger(name="app.projection")
+    yield logger
+    for handler in list(logger.handlers):
+        logger.removeHandler(handler)
+        handler.close()
+
+
+def test_each_handler_gets_its_own_fields(logger, tmp_path):
+    def handler(name, projection):
+        handler = FileHandler(str(tmp_path / name))
+        handler.setFormatter(JsonFormatter())
+        if projection is not None:
+            handler.addFilter(projection)
+        logger.addHandler(handler)
+        return handler
+
+    handlers = [
+        handler("console", logxide.FieldProjection(include=["user"])),
+        handler("collector", logxide.FieldProjection(exclude=["token", "exc_info"])),
+        handler("everything", None),
+    ]
+    assert all(h._native for h in handlers)
+    try:
+        raise RuntimeError("boom")
+    except RuntimeError:
+        logger.exception("failed", extra={"user": "ann", "token": "t", "size": 3})
+    for h in handlers:
+        h.flush()
+    console, collector, everything = (
+        json.loads((tmp_path / name).read_text())
+        for name in ("console", "collector", "everything")
+    )
+    assert console["user"] == "ann"
+    assert "size" not in console and console["exc_info"] is None
+    assert (collector["user"], collector["size"]) == ("ann", 3)
+    assert "token" not in collector and collector["exc_info"] is None
+    assert everything["token"] == "t"
+    assert "RuntimeError: boom" in everything["exc_info"]
+
+
+def test_json_output_is_projected(logger, tmp_path):
+    path = tmp_path / "out.jsonl"
+    handler = FileHandler(str(path))
+    handler.setFormatter(JsonFormatter())
+    handler.addFilter(logxide.FieldProjection(exclude=["payload"]))
+    assert handler._native
+    logger.addHandler(handler)
+    logger.warning("sent", extra={"payload": "x" * 50, "request_id": "r-1"})
+    handler.flush()
+    logger.removeHandler(handler)
+    handler.close()
+    line = json.loads(path.read_text())
+    assert "payload" not in line
+    assert line["request_id"] == "r-1"
+
+
+
+def test_http_sink_is_projected(logger, tmp_path):
+    path = tmp_path / "requests.jsonl"
+    handler = HTTPHandler("http://collector.invalid", dry_run=str(path))
+    handler.addFilter(logxide.FieldProjection(exclude=["debug_blob"]))
+    assert handler._inner.isNative()
+    logger.addHandler(handler)
+    logger.warning("slow", extra={"debug_blob": "x" * 100, "request_id": "r-9"})
+    handler.flush()
+    (request,) = (json.loads(line) for line in path.read_text().splitlines())
+    (record,) = request["body"]
+    assert "debug_blob" not in record["extra"]
+    assert record["extra"]["request_id"] == "r-9"
+
+class _ListHandler(std_logging.Handler):
+    def __init__(self):
+        super().__init__()
+        self.records = []
+
+    def emit(self, record):
+        self.records.append(record)
+
+
+def test_python_handler_and_attributes(isolated_logger):
+    projection = logxide.FieldProjection(include=["b", "a"], exclude=["c"])
+    assert projection.include == ["a", "b"]
+    assert projection.exclude == ["c"]
+    assert logxide.FieldProjection().include is None
+
+    stdlib = std_logging.getLogger(isolated_logger.name("std.projection"))
+    stdlib.propagate = False
+    handler = _ListHandler()
+    handler.addFilter(logxide.FieldProjection(include=["a"]))
+    stdlib.addHandler(handler)
+    stdlib.warning("plain", extra={"a": 1, "b": 2}, stack_info=True)
+    stdlib.removeHandler(handler)
+    record = handler.records[-1]
+    assert record.a == 1 and not hasattr(record, "b")
+    assert record.stack_info is None and record.levelname == "WARNING"
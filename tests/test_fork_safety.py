@@ -0,0 +1,143 @@
+"""
+Tests for fork safety: in a child created by os.fork(), handlers start their threads
+again, write their own records (and not the parent's a second time), and flush()
+returns instead of waiting for a thread that did not survive the fork.
+"""
+
+import json
+import os
+import subprocess
+import sys
+import textwrap
+
+import pytest
+
+pytestmark = pytest.mark.skipif(
+    not hasattr(os, "fork"), reason="os.fork() is not available"
+)
+
+_PRELUDE = textwrap.dedent(
+    """
+    import os, sys
+    import logxide
+    from logxide import handlers, logging
+
+    def logger_for(handler, name="fork"):
+        logger = logging.getLogger(name)
+        logger.propagate = False
+        logger.setLevel(logging.INFO)
+        logger.addHandler(handler)
+        return logger
+
+    def fork(child):
+        pid = os.fork()
+        if pid == 0:
+            child()
+            os._exit(0)
+        _, status = os.waitpid(pid, 0)
+        assert os.waitstatus_to_exitcode(status) == 0
+    """
+)
+
+
+def _run(tmp_path, script):
+    proc = subprocess.run(
+        [sys.executable, "-c", _PRELUDE + textwrap.dedent(script)],
+        capture_output=True,
+        text=True,
+        timeout=60,
+        cwd=tmp_path,
+    )
+    assert proc.returncode == 0, proc.stderr
+    return proc
+
+
+def test_stream_handler_writes_in_the_child(tmp_path):
+    proc = _run(
+        tmp_path,
+        """
+        # logxide.flush() flushes the root logger's handlers.
+        logger = logger_for(handlers.StreamHandler(sys.stdout), name=None)
+        logger.info("parent before")
+
+        def child():
+            logger.info("child")
+            logxide.flush()
+
+        fork(child)
+        logger.info("parent after")
+        logxide.flush()
+        """,
+    )
+    assert proc.stdout.splitlines() == ["parent before", "child", "parent after"]
+
+
+def test_file_buffer_is_written_once(tmp_path):
+    _run(
+        tmp_path,
+        """
+        handler = handlers.FileHandler("app.log")
+        logger = logger_for(handler)
+        logger.info("parent before")
+
+        def child():
+            logger.info("child")
+            handler.flush()
+
+        fork(child)
+        logger.info("parent after")
+        handler.flush()
+        """,
+    )
+    lines = (tmp_path / "app.log").read_text().splitlines()
+    assert lines == ["parent before", "child", "parent after"]
+
+
+def test_queue_listener_runs_in_the_child(tmp_path):
+    _run(
+        tmp_path,
+        """
+        target = handlers.FileHandler("app.log")
+        queue = handlers.QueueHandler()
+        listener = handlers.QueueListener(queue, target)
+        listener.start()
+        logger = logger_for(queue)
+
+        def child():
+            logger.info("child")
+            listener.stop()
+
+        fork(child)
+        logger.info("parent")
+        listener.stop()
+        """,
+    )
+    lines = (tmp_path / "app.log").read_text().splitlines()
+    assert lines == ["child", "parent"]
+
+
+def test_http_worker_sends_from_the_child(tmp_path):
+    _run(
+        tmp_path,
+        """
+        handler = handlers.HTTPHandler(
+            "http://collector.invalid", dry_run="requests.jsonl", flush_interval=3600
+        )
+        logger = logger_for(handler)
+        logger.info("queued in parent")
+
+        def child():
+            logger.info("child")
+            handler.flush()
+            assert handler.get_metrics()["sink_acknowledged"] == 1
+
+        fork(child)
+        handler.flush()
+        """,
+    )
+    requests = [
+        json.loads(line)
+        for line in (tmp_path / "requests.jsonl").read_text().splitlines()
+    ]
+    messages = sorted(r["msg"] for request in requests for r in request["body"])
+    assert messages == ["child", "queued in parent"]
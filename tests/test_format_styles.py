@@ -0,0 +1,134 @@
+"""
+Tests for format styles: the Rust formatter parses `style="{"` (str.format fields) and
+`style="$"` (string.Template) format strings as well as %-style ones, so handlers
+configured with them, e.g. by dictConfig, keep formatting natively.
+"""
+
+import logging.config
+
+import pytest
+
+from logxide import FileHandler, logging
+from logxide.logxide import logging as native_logging
+
+
+@pytest.fixture
+def log_lines(tmp_path, isolated_logger):
+    """Log through ``formatter`` to a new file; return the handler and its lines."""
+
+    def run(formatter, log):
+        logger = isolated_logger(name="test.styles", level=logging.DEBUG)
+        path = tmp_path / f"{logger.name}.log"
+        handler = FileHandler(str(path))
+        handler.setFormatter(formatter)
+        logger.addHandler(handler)
+        try:
+            log(logger)
+            handler.flush()
+        finally:
+            logger.removeHandler(handler)
+            handler.close()
+        return handler, path.read_text().splitlines()
+
+    return run
+
+
+def test_brace_fields_with_alignment_and_width(log_lines):
+    formatter = native_logging.Formatter(
+        "[{levelname:<8}] {lineno:>4}|{process:d} {message!s} {{literal}}", style="{"
+    )
+    _, (line,) = log_lines(formatter, lambda logger: logger.warning("hi %s", 1))
+    level, rest = line.split("] ")
+    assert level == "[WARNING "
+    lineno, rest = rest.split("|")
+    assert len(lineno) == 4 and lineno.strip().isdigit()
+    assert rest.endswith(" hi 1 {literal}")
+
+
+def test_dollar_placeholders(log_lines):
+    formatter = native_logging.Formatter("$levelname ${name}: ${message}$$", style="$")
+    _, (line,) = log_lines(formatter, lambda logger: logger.info("done"))
+    assert line.startswith("INFO test.styles.")
+    assert line.endswith(": done$")
+
+
+def test_default_format_follows_the_style(log_lines):
+    for style in ("{", "$"):
+        formatter = native_logging.Formatter(style=style)
+        _, lines = log_lines(formatter, lambda logger: logger.info("plain"))
+        assert lines == ["plain"]
+
+
+@pytest.mark.parametrize(
+    "fmt, style",
+    [
+        ("{msecs:03.0f}", "{"),
+        ("{args[0]}", "{"),
+        ("{message", "{"),
+        ("message}", "{"),
+        ("$1", "$"),
+        ("${message", "$"),
+    ],
+)
+def test_unparsable_formats_raise(fmt, style):
+    with pytest.raises(ValueError):
+        native_logging.Formatter(fmt, style=style)
+
+
+def test_unknown_style_raises():
+    with pytest.raises(ValueError, match="style"):
+        native_logging.Formatter("%(message)s", style="#")
+
+
+def test_stdlib_formatters_with_styles_stay_native(log_lines):
+    formatter = logging.Formatter("{levelname}:{message}", style="{")
+    handler, lines = log_lines(formatter, lambda logger: logger.error("boom"))
+    assert handler._native
+    assert lines == ["ERROR:boom"]
+
+    # A format spec the Rust formatter doesn't parse falls back to Python.
+    formatter = logging.Formatter("{msecs:03.0f} {message}", style="{")
+    handler, (line,) = log_lines(formatter, lambda logger: logger.info("x"))
+    assert not handler._native
+    stamp, message = line.split(" ")
+    assert len(stamp) == 3 and stamp.isdigit() and message == "x"
+
+
+def test_dict_config_brace_and_dollar_styles(tmp_path):
+    brace, dollar = tmp_path / "brace.log", tmp_path / "dollar.log"
+    logging.config.dictConfig(
+        {
+            "version": 1,
+            "disable_existing_loggers": False,
+            "formatters": {
+                "brace": {"format": "{levelname:>7} {name} {message}", "style": "{"},
+                "dollar": {"format": "$levelname|$name|$message", "style": "$"},
+            },
+            "handlers": {
+                name: {
+                    "class": "logxide.handlers.FileHandler",
+                    "filename": str(path),
+                    "formatter": name,
+                }
+                for name, path in [("brace", brace), ("dollar", dollar)]
+            },
+            "loggers": {
+                "test.styles.dict": {
+                    "handlers": ["brace", "dollar"],
+                    "level": "INFO",
+                    "propagate": False,
+                }
+            },
+        }
+    )
+    logger = logging.getLogger("test.styles.dict")
+    handlers = list(logger.handlers)
+    try:
+        assert all(handler._native for handler in handlers)
+        logger.info("configured")
+    finally:
+        for handler in handlers:
+            logger.removeHandler(handler)
+            handler.close()
+    assert brace.read_text() == "   INFO test.styles.dict configured\n"
+    assert dollar.read_text() == "INFO|test.styles.dict|configured\n"
@@ -0,0 +1,103 @@
+"""
+Tests for Formatter defaults: `Formatter(fmt, defaults={"app": "svc-a"})` fills in
+fields a record doesn't have, as logging.Formatter's `defaults` does since 3.10, and
+handlers keep formatting natively with such a Formatter.
+"""
+
+import logging.config
+
+import pytest
+
+from logxide import FileHandler, logging
+from logxide.compat_handlers import Formatter as CompatFormatter
+from logxide.logxide import logging as native_logging
+from logxide.module_system import _std_logging
+
+
+@pytest.fixture
+def log_lines(tmp_path, isolated_logger):
+    """Log through ``formatter`` to a new file; return the handler and its lines."""
+
+    def run(formatter, log):
+        logger = isolated_logger(name="test.defaults")
+        path = tmp_path / f"{logger.name}.log"
+        handler = FileHandler(str(path))
+        handler.setFormatter(formatter)
+        logger.addHandler(handler)
+        try:
+            log(logger)
+            handler.flush()
+        finally:
+            logger.removeHandler(handler)
+            handler.close()
+        return handler, path.read_text().splitlines()
+
+    return run
+
+
+def _log(logger):
+    logger.warning("started")
+    logger.warning("moved", extra={"app": "svc-b"})
+
+
+def test_native_formatter_fills_missing_fields(log_lines):
+    formatter = native_logging.Formatter(
+        "%(app)s %(region)s %(message)s", defaults={"app": "svc-a", "region": 1}
+    )
+    _, lines = log_lines(formatter, _log)
+    assert lines == ["svc-a 1 started", "svc-b 1 moved"]
+
+
+def test_defaults_in_each_style(log_lines):
+    for fmt, style in [("{app}:{message}", "{"), ("$app:$message", "$")]:
+        formatter = native_logging.Formatter(fmt, style=style, defaults={"app": "a"})
+        _, lines = log_lines(formatter, _log)
+        assert lines == ["a:started", "svc-b:moved"]
+
+
+def test_python_formatters_with_defaults_stay_native(log_lines):
+    for cls in (_std_logging.Formatter, CompatFormatter):
+        formatter = cls("[%(app)s] %(message)s", defaults={"app": "svc-a"})
+        handler, lines = log_lines(formatter, _log)
+        assert handler._native, cls
+        assert lines == ["[svc-a] started", "[svc-b] moved"]
+
+
+def test_compat_formatter_formats_with_defaults():
+    formatter = CompatFormatter("{app} {message}", style="{", defaults={"app": "x"})
+    record = _std_logging.LogRecord("n", 20, __file__, 1, "hi", None, None)
+    assert formatter.format(record) == "x hi"
+
+
+def test_dict_config_formatter_defaults(tmp_path):
+    path = tmp_path / "dict.log"
+    logging.config.dictConfig(
+        {
+            "version": 1,
+            "disable_existing_loggers": False,
+            "formatters": {
+                "app": {
+                    "format": "%(app)s %(levelname)s %(message)s",
+                    "defaults": {"app": "svc-a"},
+                }
+            },
+            "handlers": {
+                "file": {
+                    "class": "logxide.handlers.FileHandler",
+                    "filename": str(path),
+                    "formatter": "app",
+                }
+            },
+            "loggers": {
+                "test.defaults.dict": {"handlers": ["file"], "propagate": False}
+            },
+        }
+    )
+    logger = logging.getLogger("test.defaults.dict")
+    (handler,) = logger.handlers
+    try:
+        logger.warning("configured")
+    finally:
+        logger.removeHandler(handler)
+        handler.close()
+    assert path.read_text() == "svc-a WARNING configured\n"
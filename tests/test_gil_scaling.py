@@ -110,9 +110,9 @@ def test_ineligible_path_runs_filter_for_every_record(tmp_path):
 
     class RedactFilter:
         def filter(self, record):
-            calls.append(record.get("msg", ""))
-            if "secret" in record.get("msg", ""):
-                record["msg"] = record["msg"].replace("secret", "***")
+            calls.append(record.msg)
+            if "secret" in record.msg:
+                record.msg = record.msg.replace("secret", "***")
             return True
 
     logger.addFilter(RedactFilter())
@@ -0,0 +1,126 @@
+"""
+Tests for handler filters on the HTTP, OTLP, socket, pipe and memory handlers:
+Rust filters run in the handler, other filters send records through ``handle()``.
+"""
+
+import base64
+import json
+import pickle
+import socket
+import struct
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+FILTERS = {
+    "python": lambda: (lambda record: record.levelno >= logging.WARNING),
+    "rust": lambda: logxide.LevelRangeFilter(logging.WARNING, logging.CRITICAL),
+}
+
+
+def _http(tmp_path):
+    path = tmp_path / "requests.jsonl"
+    handler = handlers.HTTPHandler("http://collector.invalid", dry_run=str(path))
+
+    def read():
+        handler.flush()
+        lines = path.read_text().splitlines() if path.exists() else []
+        return [r["msg"] for line in lines for r in json.loads(line)["body"]]
+
+    return handler, read
+
+
+def _otlp(tmp_path):
+    path = tmp_path / "requests.jsonl"
+    handler = handlers.OTLPHandler(
+        "http://collector.invalid/v1/logs", service_name="filters", dry_run=str(path)
+    )
+
+    def read():
+        handler.flush()
+        lines = path.read_text().splitlines() if path.exists() else []
+        bodies = b"".join(
+            base64.b64decode(json.loads(line)["body_base64"]) for line in lines
+        )
+        return [m for m in ("dropped", "kept") if m.encode() in bodies]
+
+    return handler, read
+
+
+def _socket(tmp_path):
+    server = socket.socket(socket.AF_INET, socket.SOCK_STREAM)
+    server.bind(("127.0.0.1", 0))
+    server.listen()
+    server.settimeout(5)
+    handler = handlers.SocketHandler("127.0.0.1", server.getsockname()[1])
+
+    def read():
+        # The first record on the wire is the only one sent.
+        conn, _ = server.accept()
+        with conn, server:
+            (length,) = struct.unpack(">L", conn.recv(4, socket.MSG_WAITALL))
+            record = pickle.loads(conn.recv(length, socket.MSG_WAITALL))
+        return [record["msg"]]
+
+    return handler, read
+
+
+def _pipe(tmp_path):
+    path = tmp_path / "out.jsonl"
+    path.touch()
+    handler = handlers.PipeHandler(path=str(path))
+
+    def read():
+        handler.flush()
+        return [json.loads(line)["msg"] for line in path.read_text().splitlines()]
+
+    return handler, read
+
+
+def _memory(tmp_path):
+    handler = handlers.MemoryHandler()
+    return handler, lambda: [r.getMessage() for r in handler.records]
+
+
+HANDLERS = {
+    "http": _http,
+    "otlp": _otlp,
+    "socket": _socket,
+    "pipe": _pipe,
+    "memory": _memory,
+}
+
+
+@pytest.mark.parametrize("kind", sorted(FILTERS))
+@pytest.mark.parametrize("name", sorted(HANDLERS))
+def test_rejected_records_are_not_delivered(tmp_path, name, kind, isolated_logger):
+    handler, read = HANDLERS[name](tmp_path)
+    handler.addFilter(FILTERS[kind]())
+    assert handler._inner.isNative() == (kind == "rust")
+    logger = isolated_logger(handler)
+    try:
+        logger.info("dropped")
+        logger.warning("kept")
+        assert read() == ["kept"]
+    finally:
+        logger.removeHandler(handler)
+        handler.close()
+
+
+@pytest.mark.parametrize("name", sorted(HANDLERS))
+def test_reject_all_filter_until_removed(tmp_path, name, isolated_logger):
+    handler, read = HANDLERS[name](tmp_path)
+    reject = lambda record: False  # noqa: E731
+    handler.addFilter(reject)
+    logger = isolated_logger(handler)
+    try:
+        logger.warning("dropped")
+        handler.removeFilter(reject)
+        assert handler._inner.isNative()
+        logger.warning("kept")
+        assert read() == ["kept"]
+    finally:
+        logger.removeHandler(handler)
+        handler.close()
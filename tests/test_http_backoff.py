@@ -0,0 +1,97 @@
+"""
+Tests for collector-driven backoff: 429/503 responses pause HTTPHandler and
+OTLPHandler for the Retry-After delay and retry the batch, and the throttled
+state shows up in get_metrics().
+"""
+
+import time
+from email.utils import formatdate
+
+from logxide import handlers
+
+
+def _retry_after(status, delay):
+    return status, {"Retry-After": delay}
+
+
+def _hits(collector):
+    return [request.time for request in collector.requests]
+
+
+def test_retry_after_pauses_then_delivers(http_collector, isolated_logger):
+    http_collector.replies = [_retry_after(429, "0.3"), _retry_after(503, "0.3")]
+    handler = handlers.HTTPHandler(http_collector.url)
+    metrics = isolated_logger.send(handler, "a", "b")
+
+    hits = _hits(http_collector)
+    assert len(hits) == 3
+    assert hits[1] - hits[0] >= 0.25
+    assert hits[2] - hits[1] >= 0.25
+    assert metrics["throttled"] == 2, metrics
+    assert metrics["sink_acknowledged"] == 2, metrics
+    assert metrics["delivery_failed"] == 0, metrics
+
+
+def test_pause_is_reported_while_throttled(http_collector, isolated_logger):
+    http_collector.replies = [_retry_after(429, "30")] * 4
+    handler = handlers.HTTPHandler(http_collector.url, shutdown_timeout=0.5)
+    logger = isolated_logger(handler)
+    try:
+        logger.error("flushes at once")
+        deadline = time.monotonic() + 10
+        while not http_collector.requests and time.monotonic() < deadline:
+            time.sleep(0.02)
+        time.sleep(0.1)
+        metrics = handler.get_metrics()
+    finally:
+        logger.removeHandler(handler)
+        handler.close()
+
+    assert metrics["throttled"] == 1, metrics
+    assert 25_000 < metrics["throttle_remaining_ms"] <= 30_000, metrics
+
+
+def test_http_date_in_the_past_retries_immediately(http_collector, isolated_logger):
+    http_collector.replies = [
+        _retry_after(503, formatdate(time.time() - 60, usegmt=True))
+    ]
+    handler = handlers.HTTPHandler(http_collector.url)
+    metrics = isolated_logger.send(handler, "dated")
+
+    assert metrics["throttled"] == 1, metrics
+    assert metrics["sink_acknowledged"] == 1, metrics
+    assert metrics["throttle_remaining_ms"] == 0, metrics
+
+
+def test_retries_are_bounded(http_collector, isolated_logger):
+    http_collector.replies = [_retry_after(429, "0.05")] * 10
+    errors = []
+    handler = handlers.HTTPHandler(http_collector.url, error_callback=errors.append)
+    metrics = isolated_logger.send(handler, "given up")
+
+    assert len(http_collector.requests) == 4
+    assert metrics["throttled"] == 4, metrics
+    assert metrics["delivery_failed"] == 1, metrics
+    assert errors and "429" in errors[0]
+
+
+def test_other_errors_are_not_retried(http_collector, isolated_logger):
+    http_collector.replies = [_retry_after(500, "1")]
+    handler = handlers.HTTPHandler(http_collector.url)
+    metrics = isolated_logger.send(handler, "server error")
+
+    assert len(http_collector.requests) == 1
+    assert metrics["throttled"] == 0, metrics
+    assert metrics["delivery_failed"] == 1, metrics
+
+
+def test_otlp_handler_honors_retry_after(http_collector, isolated_logger):
+    http_collector.replies = [_retry_after(429, "0.2")]
+    handler = handlers.OTLPHandler(http_collector.url, service_name="backoff-test")
+    metrics = isolated_logger.send(handler, "otlp")
+
+    hits = _hits(http_collector)
+    assert len(hits) == 2
+    assert hits[1] - hits[0] >= 0.15
+    assert metrics["throttled"] == 1, metrics
+    assert metrics["sink_acknowledged"] == 1, metrics
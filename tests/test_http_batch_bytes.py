@@ -0,0 +1,118 @@
+"""
+Tests for max_batch_bytes: HTTPHandler and OTLPHandler split a batch into
+several requests so no body exceeds the limit, and drop (and count) records too
+large to send on their own.
+"""
+
+import gzip
+import json
+
+import pytest
+
+from logxide import handlers
+
+
+def _bodies(collector):
+    return [
+        gzip.decompress(r.body)
+        if r.headers["Content-Encoding"] == "gzip"
+        else r.body
+        for r in collector.requests
+    ]
+
+
+def test_batch_is_split_under_the_limit(http_collector, isolated_logger):
+    url = http_collector.url
+    handler = handlers.HTTPHandler(url, max_batch_bytes=2000)
+    messages = [f"record {i:03d} " + "x" * 40 for i in range(40)]
+    metrics = isolated_logger.send(handler, *messages)
+    bodies = _bodies(http_collector)
+
+    assert len(bodies) > 1
+    assert all(len(body) <= 2000 for body in bodies)
+    sent = [record["msg"] for body in bodies for record in json.loads(body)]
+    assert sent == messages
+    assert metrics["sink_acknowledged"] == 40, metrics
+    assert metrics["oversized_dropped"] == 0, metrics
+
+
+def test_requests_are_packed_up_to_the_limit(http_collector, isolated_logger):
+    url = http_collector.url
+    handler = handlers.HTTPHandler(url, max_batch_bytes=2000)
+    isolated_logger.send(handler, *["same size"] * 30)
+    bodies = _bodies(http_collector)
+
+    record_len = len(json.dumps(json.loads(bodies[0])[0], separators=(",", ":")))
+    for body in bodies[:-1]:
+        assert len(body) + record_len + 1 > 2000
+
+
+def test_oversized_record_is_dropped_and_reported(http_collector, isolated_logger):
+    url = http_collector.url
+    errors = []
+    handler = handlers.HTTPHandler(
+        url, max_batch_bytes=1000, error_callback=errors.append
+    )
+    metrics = isolated_logger.send(handler, "before", "y" * 5000, "after")
+    bodies = _bodies(http_collector)
+
+    sent = [record["msg"] for body in bodies for record in json.loads(body)]
+    assert sent == ["before", "after"]
+    assert metrics["sink_acknowledged"] == 2, metrics
+    assert metrics["delivery_failed"] == 1, metrics
+    assert metrics["oversized_dropped"] == 1, metrics
+    assert metrics["in_flight"] == 0, metrics
+    assert errors and "max_batch_bytes" in errors[0]
+
+
+def test_limit_applies_before_compression(http_collector, isolated_logger):
+    url = http_collector.url
+    handler = handlers.HTTPHandler(
+        url, max_batch_bytes=1500, compression="gzip", compression_min_bytes=0
+    )
+    metrics = isolated_logger.send(handler, *["compressible " * 5] * 50)
+    bodies = _bodies(http_collector)
+
+    assert len(bodies) > 1
+    assert all(len(body) <= 1500 for body in bodies)
+    assert metrics["sink_acknowledged"] == 50, metrics
+
+
+def test_reshaped_payload_is_sent_whole_only_if_it_fits(
+    http_collector, isolated_logger
+):
+    url = http_collector.url
+    errors = []
+    handler = handlers.HTTPHandler(
+        url,
+        max_batch_bytes=300,
+        transform_callback=lambda records: {"logs": records},
+        error_callback=errors.append,
+    )
+    metrics = isolated_logger.send(handler, *["reshaped"] * 20)
+    bodies = _bodies(http_collector)
+
+    assert bodies == []
+    assert metrics["delivery_failed"] == 20, metrics
+    assert errors
+
+
+def test_otlp_batch_is_split_under_the_limit(http_collector, isolated_logger):
+    url = http_collector.url
+    handler = handlers.OTLPHandler(
+        url, service_name="batch-bytes", max_batch_bytes=1500
+    )
+    metrics = isolated_logger.send(handler, *[f"otlp {i}" for i in range(30)])
+    bodies = _bodies(http_collector)
+
+    assert len(bodies) > 1
+    assert all(len(body) <= 1500 for body in bodies)
+    assert sum(body.count(b"otlp ") for body in bodies) == 30
+    assert metrics["sink_acknowledged"] == 30, metrics
+
+
+def test_invalid_limits_are_rejected():
+    with pytest.raises(ValueError):
+        handlers.HTTPHandler("http://127.0.0.1:9", max_batch_bytes=0)
+    with pytest.raises(ValueError):
+        handlers.HTTPHandler("http://127.0.0.1:9", method="GET", max_batch_bytes=1024)
@@ -0,0 +1,99 @@
+"""
+Tests for request-body compression in HTTPHandler and OTLPHandler: gzip/zstd
+bodies above compression_min_bytes, labelled with Content-Encoding.
+"""
+
+import gzip
+import json
+
+import pytest
+
+import logxide
+from logxide import handlers
+
+pytestmark = pytest.mark.skipif(
+    "compression" not in logxide.runtime_info()["features"],
+    reason="built without the compression feature",
+)
+
+ZSTD_MAGIC = b"\x28\xb5\x2f\xfd"
+
+
+def _zstd_decompress(data):
+    try:
+        from compression import zstd
+    except ImportError:
+        zstd = pytest.importorskip("zstandard")
+        return zstd.ZstdDecompressor().decompressobj().decompress(data)
+    return zstd.decompress(data)
+
+
+def test_gzip_batch(http_collector, isolated_logger):
+    url, requests = http_collector.url, http_collector.requests
+    handler = handlers.HTTPHandler(url, compression="gzip")
+    messages = [f"repeated message {i}" for i in range(50)]
+    metrics = isolated_logger.send(handler, *messages)
+
+    (request,) = requests
+    assert request.headers["Content-Encoding"] == "gzip"
+    assert request.headers["Content-Type"] == "application/json"
+    records = json.loads(gzip.decompress(request.body))
+    assert [r["msg"] for r in records][:2] == ["repeated message 0", "repeated message 1"]
+    assert metrics["wire_bytes"] == len(request.body)
+    assert metrics["payload_bytes"] > metrics["wire_bytes"], metrics
+    assert metrics["sink_acknowledged"] == 50, metrics
+
+
+def test_zstd_batch(http_collector, isolated_logger):
+    url, requests = http_collector.url, http_collector.requests
+    handler = handlers.HTTPHandler(url, compression="zstd")
+    isolated_logger.send(handler, *["zstd payload"] * 50)
+
+    (request,) = requests
+    assert request.headers["Content-Encoding"] == "zstd"
+    assert request.body.startswith(ZSTD_MAGIC)
+    records = json.loads(_zstd_decompress(request.body))
+    assert len(records) == 50
+
+
+def test_small_bodies_are_sent_uncompressed(http_collector, isolated_logger):
+    url, requests = http_collector.url, http_collector.requests
+    handler = handlers.HTTPHandler(
+        url, compression="gzip", compression_min_bytes=1 << 20
+    )
+    metrics = isolated_logger.send(handler, "tiny")
+
+    (request,) = requests
+    assert request.headers["Content-Encoding"] is None
+    assert json.loads(request.body)[0]["msg"] == "tiny"
+    assert metrics["payload_bytes"] == metrics["wire_bytes"] == len(request.body)
+
+
+def test_uncompressed_by_default(http_collector, isolated_logger):
+    url, requests = http_collector.url, http_collector.requests
+    handler = handlers.HTTPHandler(url)
+    isolated_logger.send(handler, *["plain"] * 200)
+
+    assert requests[0].headers["Content-Encoding"] is None
+    json.loads(requests[0].body)
+
+
+def test_otlp_gzip(http_collector, isolated_logger):
+    url, requests = http_collector.url, http_collector.requests
+    handler = handlers.OTLPHandler(
+        url, service_name="compressed", compression="gzip", compression_min_bytes=0
+    )
+    metrics = isolated_logger.send(handler, "otlp record")
+
+    (request,) = requests
+    assert request.headers["Content-Encoding"] == "gzip"
+    assert request.headers["Content-Type"] == "application/x-protobuf"
+    assert b"otlp record" in gzip.decompress(request.body)
+    assert metrics["sink_acknowledged"] == 1, metrics
+
+
+def test_invalid_options_are_rejected():
+    with pytest.raises(ValueError):
+        handlers.HTTPHandler("http://127.0.0.1:9", compression="brotli")
+    with pytest.raises(ValueError):
+        handlers.HTTPHandler("http://127.0.0.1:9", method="POST", compression="gzip")
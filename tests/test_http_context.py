@@ -0,0 +1,130 @@
+"""
+Tests for HTTPHandler record context: context_ttl caches context_provider
+results across batches, and update_context() pushes fields without any
+per-batch Python call.
+"""
+
+import itertools
+import json
+import time
+
+import pytest
+
+from logxide import handlers
+
+
+class _Harness:
+    def __init__(self, isolated_logger, **kwargs):
+        self.requests = []
+        self.handler = handlers.HTTPHandler(
+            "http://collector.invalid", dry_run=self.requests.append, **kwargs
+        )
+        self.logger = isolated_logger(self.handler)
+
+    def batch(self, message="batch"):
+        """Send one batch and return its single record."""
+        self.logger.info(message)
+        self.handler.flush()
+        (record,) = json.loads(self.requests[-1]["body"])
+        return record
+
+    def close(self):
+        self.handler.close()
+
+
+@pytest.fixture
+def harness(isolated_logger):
+    made = []
+
+    def make(**kwargs):
+        made.append(_Harness(isolated_logger, **kwargs))
+        return made[-1]
+
+    yield make
+    for h in made:
+        h.close()
+
+
+def test_provider_is_called_every_batch_by_default(harness):
+    calls = itertools.count(1)
+    h = harness(context_provider=lambda: {"call": next(calls)})
+
+    assert [h.batch()["call"] for _ in range(3)] == [1, 2, 3]
+    assert h.handler.get_metrics()["context_provider_calls"] == 3
+
+
+def test_ttl_reuses_the_provider_result(harness):
+    calls = itertools.count(1)
+    h = harness(context_provider=lambda: {"call": next(calls)}, context_ttl=0.3)
+
+    assert [h.batch()["call"] for _ in range(3)] == [1, 1, 1]
+    time.sleep(0.35)
+    assert h.batch()["call"] == 2
+    assert h.handler.get_metrics()["context_provider_calls"] == 2
+
+
+def test_ttl_keeps_last_good_result_when_provider_fails(harness):
+    results = iter([{"region": "eu"}])
+
+    def provider():
+        return next(results)  # StopIteration after the first call
+
+    h = harness(context_provider=provider, context_ttl=0.05)
+    assert h.batch()["region"] == "eu"
+    time.sleep(0.1)
+    assert h.batch()["region"] == "eu"
+
+
+def test_update_context_pushes_fields(harness):
+    h = harness(global_context={"app": "billing", "build": "old"})
+    assert h.batch()["build"] == "old"
+
+    h.handler.update_context({"build": "abc123", "region": "eu"})
+    record = h.batch()
+    assert record["app"] == "billing"
+    assert record["build"] == "abc123"
+    assert record["region"] == "eu"
+
+    h.handler.update_context({"region": "us"})
+    assert h.batch()["region"] == "us"
+
+    h.handler.update_context({"zone": "b"}, replace=True)
+    record = h.batch()
+    assert record["zone"] == "b"
+    assert "region" not in record
+    assert record["build"] == "old"
+    assert h.handler.get_metrics()["context_provider_calls"] == 0
+
+
+def test_provider_overrides_pushed_fields(harness):
+    h = harness(context_provider=lambda: {"region": "provider"})
+    h.handler.update_context({"region": "pushed", "build": "abc"})
+
+    record = h.batch()
+    assert record["region"] == "provider"
+    assert record["build"] == "abc"
+
+
+def test_pushed_context_reaches_form_mode(isolated_logger):
+    requests = []
+    handler = handlers.HTTPHandler(
+        "http://collector.invalid/log", method="GET", dry_run=requests.append
+    )
+    handler.update_context({"build": "abc123"})
+    logger = isolated_logger(handler)
+    try:
+        logger.warning("form")
+        handler.flush()
+    finally:
+        handler.close()
+
+    assert "build=abc123" in requests[0]["url"]
+
+
+def test_invalid_ttl_is_rejected():
+    with pytest.raises(ValueError):
+        handlers.HTTPHandler("http://127.0.0.1:9", context_ttl=5)
+    with pytest.raises(ValueError):
+        handlers.HTTPHandler(
+            "http://127.0.0.1:9", context_provider=dict, context_ttl=0
+        )
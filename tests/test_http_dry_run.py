@@ -0,0 +1,153 @@
+"""
+Tests for dry_run: network handlers serialize and batch as usual but write each
+request to a file, or hand it to a callable that simulates the collector.
+"""
+
+import base64
+import gzip
+import json
+import time
+
+import pytest
+
+from logxide import handlers
+
+
+def _lines(path):
+    return [json.loads(line) for line in path.read_text().splitlines()]
+
+
+def test_requests_are_written_to_a_file(tmp_path, isolated_logger):
+    path = tmp_path / "requests.jsonl"
+    handler = handlers.HTTPHandler(
+        "http://collector.invalid/ingest",
+        headers={"Authorization": "Bearer secret", "X-Team": "ops"},
+        dry_run=str(path),
+    )
+    metrics = isolated_logger.send(handler, "one", "two")
+
+    (request,) = _lines(path)
+    assert request["method"] == "POST"
+    assert request["url"] == "http://collector.invalid/ingest"
+    assert request["content_type"] == "application/json"
+    assert request["headers"] == {"Authorization": "<redacted>", "X-Team": "ops"}
+    assert [r["msg"] for r in request["body"]] == ["one", "two"]
+    assert metrics["sink_acknowledged"] == 2, metrics
+
+
+def test_each_split_request_is_a_line(tmp_path, isolated_logger):
+    path = tmp_path / "requests.jsonl"
+    handler = handlers.HTTPHandler(
+        "http://collector.invalid", max_batch_bytes=1000, dry_run=path
+    )
+    isolated_logger.send(handler, *[f"line {i}" for i in range(30)])
+
+    requests = _lines(path)
+    assert len(requests) > 1
+    assert sum(len(r["body"]) for r in requests) == 30
+
+
+def test_compressed_bodies_are_base64(tmp_path, isolated_logger):
+    path = tmp_path / "requests.jsonl"
+    handler = handlers.HTTPHandler(
+        "http://collector.invalid",
+        compression="gzip",
+        compression_min_bytes=0,
+        dry_run=str(path),
+    )
+    isolated_logger.send(handler, "zipped")
+
+    (request,) = _lines(path)
+    assert request["content_encoding"] == "gzip"
+    records = json.loads(gzip.decompress(base64.b64decode(request["body_base64"])))
+    assert records[0]["msg"] == "zipped"
+
+
+def test_form_mode_get_is_recorded(tmp_path, isolated_logger):
+    path = tmp_path / "requests.jsonl"
+    handler = handlers.HTTPHandler(
+        "http://collector.invalid/log", method="GET", dry_run=str(path)
+    )
+    isolated_logger.send(handler, "form")
+
+    (request,) = _lines(path)
+    assert request["method"] == "GET"
+    assert "msg=form" in request["url"]
+
+
+def test_callback_sees_every_request(isolated_logger):
+    seen = []
+    handler = handlers.HTTPHandler(
+        "http://collector.invalid", dry_run=lambda request: seen.append(request)
+    )
+    metrics = isolated_logger.send(handler, "called")
+
+    (request,) = seen
+    assert request["method"] == "POST"
+    assert isinstance(request["body"], bytes)
+    assert json.loads(request["body"])[0]["msg"] == "called"
+    assert metrics["sink_acknowledged"] == 1, metrics
+
+
+def test_simulated_throttling_exercises_the_retry_path(isolated_logger):
+    responses = iter([(429, 0.2), 503])
+    calls = []
+
+    def collector(request):
+        calls.append(time.monotonic())
+        return next(responses, 200)
+
+    handler = handlers.HTTPHandler("http://collector.invalid", dry_run=collector)
+    metrics = isolated_logger.send(handler, "retried")
+
+    assert len(calls) == 3
+    assert calls[1] - calls[0] >= 0.15
+    assert metrics["throttled"] == 2, metrics
+    assert metrics["sink_acknowledged"] == 1, metrics
+
+
+def test_simulated_failure_reaches_error_callback(isolated_logger):
+    errors = []
+    handler = handlers.HTTPHandler(
+        "http://collector.invalid",
+        dry_run=lambda request: 400,
+        error_callback=errors.append,
+    )
+    metrics = isolated_logger.send(handler, "rejected")
+
+    assert metrics["delivery_failed"] == 1, metrics
+    assert errors and "400" in errors[0]
+
+
+def test_raising_callback_fails_the_request(isolated_logger):
+    def collector(request):
+        raise RuntimeError("collector down")
+
+    errors = []
+    handler = handlers.HTTPHandler(
+        "http://collector.invalid", dry_run=collector, error_callback=errors.append
+    )
+    metrics = isolated_logger.send(handler, "boom")
+
+    assert metrics["delivery_failed"] == 1, metrics
+    assert "collector down" in errors[0]
+
+
+def test_otlp_dry_run(tmp_path, isolated_logger):
+    path = tmp_path / "otlp.jsonl"
+    handler = handlers.OTLPHandler(
+        "http://collector.invalid/v1/logs", service_name="dry", dry_run=str(path)
+    )
+    metrics = isolated_logger.send(handler, "otlp dry")
+
+    (request,) = _lines(path)
+    assert request["content_type"] == "application/x-protobuf"
+    assert b"otlp dry" in base64.b64decode(request["body_base64"])
+    assert metrics["sink_acknowledged"] == 1, metrics
+
+
+def test_unwritable_path_is_rejected(tmp_path):
+    with pytest.raises(ValueError):
+        handlers.HTTPHandler(
+            "http://collector.invalid", dry_run=str(tmp_path / "missing" / "x.jsonl")
+        )
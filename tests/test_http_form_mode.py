@@ -0,0 +1,121 @@
+"""
+Tests for HTTPHandler's stdlib form mode (method="GET"/"POST"): one request per
+record with the record's attributes URL-encoded, plus basic-auth credentials.
+"""
+
+import base64
+import logging as std_logging
+from urllib.parse import parse_qs, urlsplit
+
+import pytest
+
+from logxide import handlers, logging
+
+
+def _log(isolated_logger, handler, *messages, **kwargs):
+    logger = isolated_logger(handler)
+    try:
+        for message in messages:
+            if isinstance(message, tuple):
+                logger.warning(*message, **kwargs)
+            else:
+                logger.warning(message, **kwargs)
+        handler.flush()
+    finally:
+        handler.close()
+    return logger
+
+
+def _fields(query):
+    parsed = parse_qs(query, keep_blank_values=True)
+    return {key: values[0] for key, values in parsed.items()}
+
+
+def _query(request):
+    return _fields(urlsplit(request.path).query)
+
+
+def _form(request):
+    return _fields(request.body.decode())
+
+
+def test_get_sends_one_query_string_per_record(http_collector, isolated_logger):
+    url, requests = http_collector.url, http_collector.requests
+    handler = handlers.HTTPHandler(f"{url}/log", method="GET")
+    logger = _log(
+        isolated_logger, handler, "first", "second & more", extra={"job": "nightly"}
+    )
+
+    assert [r.method for r in requests] == ["GET", "GET"]
+    assert {urlsplit(r.path).path for r in requests} == {"/log"}
+    first, second = (_query(r) for r in requests)
+    assert first["msg"] == "first"
+    assert second["msg"] == "second & more"
+    assert second["name"] == logger.name
+    assert second["levelname"] == "WARNING"
+    assert second["levelno"] == "30"
+    assert second["args"] == "None"
+    assert second["job"] == "nightly"
+    float(second["created"])
+
+
+def test_get_appends_to_an_existing_query(http_collector, isolated_logger):
+    url, requests = http_collector.url, http_collector.requests
+    handler = handlers.HTTPHandler(f"{url}/log?app=web", method="get")
+    _log(isolated_logger, handler, "hello")
+
+    query = _query(requests[0])
+    assert query["app"] == "web"
+    assert query["msg"] == "hello"
+
+
+def test_post_sends_a_form_body(http_collector, isolated_logger):
+    url, requests = http_collector.url, http_collector.requests
+    handler = handlers.HTTPHandler(url, method="POST")
+    _log(isolated_logger, handler, ("disk %s full", "/var"))
+
+    (request,) = requests
+    assert request.method == "POST"
+    assert request.headers["Content-Type"] == "application/x-www-form-urlencoded"
+    assert _form(request)["msg"] == "disk /var full"
+    metrics = handler.get_metrics()
+    assert metrics["sink_acknowledged"] == 1, metrics
+
+
+def test_stdlib_fields_are_present(http_collector, isolated_logger):
+    url, requests = http_collector.url, http_collector.requests
+    handler = handlers.HTTPHandler(url, method="POST")
+    _log(isolated_logger, handler, "fields")
+
+    record = std_logging.LogRecord("x", logging.INFO, __file__, 1, "m", None, None)
+    assert set(vars(record)) <= set(_form(requests[0]))
+
+
+def test_credentials_send_basic_auth(http_collector, isolated_logger):
+    url, requests = http_collector.url, http_collector.requests
+    handler = handlers.HTTPHandler(url, method="GET", credentials=("ops", "s3cret"))
+    _log(isolated_logger, handler, "authenticated")
+
+    token = base64.b64encode(b"ops:s3cret").decode()
+    assert requests[0].headers["Authorization"] == f"Basic {token}"
+
+
+def test_json_batches_remain_the_default(http_collector, isolated_logger):
+    url, requests = http_collector.url, http_collector.requests
+    handler = handlers.HTTPHandler(url, credentials=("a", "b"))
+    _log(isolated_logger, handler, "one", "two")
+
+    (request,) = requests
+    assert request.method == "POST"
+    assert request.headers["Content-Type"] == "application/json"
+    token = base64.b64encode(b"a:b").decode()
+    assert request.headers["Authorization"] == f"Basic {token}"
+
+
+def test_invalid_combinations_are_rejected():
+    with pytest.raises(ValueError):
+        handlers.HTTPHandler("http://127.0.0.1:9", method="PUT")
+    with pytest.raises(ValueError):
+        handlers.HTTPHandler(
+            "http://127.0.0.1:9", method="POST", transform_callback=lambda r: r
+        )
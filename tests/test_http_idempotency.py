@@ -0,0 +1,102 @@
+"""
+Tests for idempotency_keys: each request carries a batch UUID (Idempotency-Key
+header and batch_id on its records), each record a per-handler sequence id, and
+retries resend both unchanged.
+"""
+
+import json
+import uuid
+
+import pytest
+
+from logxide import handlers
+
+
+def _requests(collector):
+    """``(Idempotency-Key, body)`` of each request."""
+    return [(r.headers["Idempotency-Key"], r.body) for r in collector.requests]
+
+
+def test_batch_key_and_sequence_ids(http_collector, isolated_logger):
+    url = http_collector.url
+    handler = handlers.HTTPHandler(url, idempotency_keys=True)
+    isolated_logger.send(handler, "a", "b", "c")
+
+    ((key, body),) = _requests(http_collector)
+    assert uuid.UUID(key).version == 4
+    records = json.loads(body)
+    assert [r["batch_id"] for r in records] == [key] * 3
+    assert [r["sequence"] for r in records] == [1, 2, 3]
+
+
+def test_sequence_continues_across_batches(http_collector, isolated_logger):
+    url = http_collector.url
+    handler = handlers.HTTPHandler(url, idempotency_keys=True)
+    logger = isolated_logger(handler)
+    try:
+        logger.info("first")
+        handler.flush()
+        logger.info("second")
+        handler.flush()
+    finally:
+        handler.close()
+
+    (key1, body1), (key2, body2) = _requests(http_collector)
+    assert key1 != key2
+    assert json.loads(body1)[0]["sequence"] == 1
+    assert json.loads(body2)[0]["sequence"] == 2
+
+
+def test_retry_resends_the_same_key_and_body(http_collector, isolated_logger):
+    url = http_collector.url
+    http_collector.replies = [(429, {"Retry-After": "0.05"})]
+    handler = handlers.HTTPHandler(url, idempotency_keys=True)
+    metrics = isolated_logger.send(handler, "retried")
+
+    first, second = _requests(http_collector)
+    assert first == second
+    assert metrics["sink_acknowledged"] == 1, metrics
+
+
+def test_split_batches_get_one_key_each(http_collector, isolated_logger):
+    url = http_collector.url
+    handler = handlers.HTTPHandler(url, idempotency_keys=True, max_batch_bytes=1500)
+    metrics = isolated_logger.send(handler, *[f"split {i}" for i in range(30)])
+
+    requests = _requests(http_collector)
+    assert len(requests) > 1
+    assert len({key for key, _ in requests}) == len(requests)
+    sequences = []
+    for key, body in requests:
+        assert len(body) <= 1500
+        records = json.loads(body)
+        assert {r["batch_id"] for r in records} == {key}
+        sequences += [r["sequence"] for r in records]
+    assert sequences == list(range(1, 31))
+    assert metrics["sink_acknowledged"] == 30, metrics
+
+
+def test_off_by_default(http_collector, isolated_logger):
+    url = http_collector.url
+    isolated_logger.send(handlers.HTTPHandler(url), "plain")
+
+    ((key, body),) = _requests(http_collector)
+    assert key is None
+    assert "batch_id" not in json.loads(body)[0]
+
+
+def test_otlp_attributes(http_collector, isolated_logger):
+    url = http_collector.url
+    handler = handlers.OTLPHandler(url, service_name="dedupe", idempotency_keys=True)
+    isolated_logger.send(handler, "otlp")
+
+    ((key, body),) = _requests(http_collector)
+    assert uuid.UUID(key).version == 4
+    assert b"logxide.batch_id" in body
+    assert key.encode() in body
+    assert b"logxide.sequence" in body
+
+
+def test_form_mode_is_rejected():
+    with pytest.raises(ValueError):
+        handlers.HTTPHandler("http://127.0.0.1:9", method="POST", idempotency_keys=True)
@@ -0,0 +1,136 @@
+"""
+Tests for record_transform: HTTPHandler reshapes each JSON record in Rust from a
+declarative spec (level remap, templated adds, renames, drops) instead of
+calling a Python transform_callback.
+"""
+
+import json
+
+import pytest
+
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def send(isolated_logger):
+    """Log one record through an HTTPHandler and return the record it ships."""
+
+    def send(record_transform, *, level=logging.INFO, extra=None, **kwargs):
+        requests = []
+        handler = handlers.HTTPHandler(
+            "http://collector.invalid",
+            record_transform=record_transform,
+            dry_run=requests.append,
+            **kwargs,
+        )
+        logger = isolated_logger(handler, level=logging.DEBUG)
+        try:
+            logger.log(level, "payment failed", extra=extra)
+            handler.flush()
+        finally:
+            handler.close()
+        (request,) = requests
+        (record,) = json.loads(request["body"])
+        return record
+
+    return send
+
+
+def test_rename_drop_and_add(send):
+    record = send(
+        {
+            "add": {"service": "billing", "attempt": 3},
+            "rename": {"msg": "message"},
+            "drop": ["args", "pathname"],
+        }
+    )
+
+    assert record["message"] == "payment failed"
+    assert "msg" not in record
+    assert "args" not in record
+    assert "pathname" not in record
+    assert record["service"] == "billing"
+    assert record["attempt"] == 3
+
+
+def test_templates_read_the_record(send):
+    record = send(
+        {
+            "add": {
+                "summary": "[{levelname}] {msg} ({extra.order})",
+                "line": "{lineno}",
+                "braces": "{{literal}}",
+                "missing": "<{extra.nope}>",
+            }
+        },
+        level=logging.WARNING,
+        extra={"order": "A-17"},
+    )
+
+    assert record["summary"] == "[WARNING] payment failed (A-17)"
+    assert record["line"] == record["lineno"]
+    assert record["braces"] == "{literal}"
+    assert record["missing"] == "<>"
+
+
+def test_dotted_paths_move_extras_to_the_top(send):
+    record = send(
+        {"rename": {"extra.order": "order_id"}, "drop": ["extra"]},
+        extra={"order": "A-17"},
+    )
+
+    assert record["order_id"] == "A-17"
+    assert "extra" not in record
+
+
+def test_levels_are_remapped_by_name_or_number(send):
+    spec = {"levels": {"WARNING": "warn", "50": "fatal"}}
+
+    assert send(spec, level=logging.WARNING)["levelname"] == "warn"
+    assert send(spec, level=logging.CRITICAL)["levelname"] == "fatal"
+    assert send(spec, level=logging.INFO)["levelname"] == "INFO"
+
+
+def test_yaml_spec(send):
+    pytest.importorskip("yaml")
+    record = send(
+        """
+        levels:
+          ERROR: err
+        rename:
+          msg: message
+        """,
+        level=logging.ERROR,
+    )
+
+    assert record["levelname"] == "err"
+    assert record["message"] == "payment failed"
+
+
+def test_runs_before_transform_callback(send):
+    seen = []
+
+    def transform(records):
+        seen.extend(records)
+        return records
+
+    send({"rename": {"msg": "message"}}, transform_callback=transform)
+
+    assert seen[0]["message"] == "payment failed"
+
+
+def test_invalid_specs_are_rejected():
+    for spec in (
+        {"unknown": {}},
+        {"drop": "msg"},
+        {"rename": {"msg": 1}},
+        {"add": {"x": "{unclosed"}},
+        {"add": {"x": "stray }"}},
+        ["drop"],
+    ):
+        with pytest.raises(ValueError):
+            handlers.HTTPHandler("http://127.0.0.1:9", record_transform=spec)
+    with pytest.raises(ValueError):
+        handlers.HTTPHandler(
+            "http://127.0.0.1:9", method="GET", record_transform={"drop": ["args"]}
+        )
@@ -0,0 +1,105 @@
+"""
+Tests for send_time and max_backdate: delayed records can carry their send time,
+and records older than the backend's ingestion window go out with their
+timestamp moved up to it and the original kept alongside.
+"""
+
+import json
+import logging as std_logging
+import time
+
+import pytest
+
+from logxide import handlers, logging
+
+
+def _record(msg, age=0.0):
+    record = std_logging.LogRecord(
+        "test.timestamps", logging.INFO, __file__, 1, msg, None, None
+    )
+    record.created -= age
+    return record
+
+
+def _send(handler, *records):
+    try:
+        for record in records:
+            handler.handle(record)
+        handler.flush()
+        return handler.get_metrics()
+    finally:
+        handler.close()
+
+
+def test_send_time_is_added(http_collector):
+    url = http_collector.url
+    before = time.time()
+    metrics = _send(handlers.HTTPHandler(url, send_time=True), _record("late", age=60))
+
+    (record,) = json.loads(http_collector.bodies[0])
+    assert before <= record["sent_at"] <= time.time()
+    assert record["sent_at"] - record["created"] >= 59
+    assert "timestamp_capped" not in record
+    assert metrics["timestamps_capped"] == 0, metrics
+
+
+def test_old_records_are_capped_and_annotated(http_collector):
+    url = http_collector.url
+    stale = _record("stale", age=7200)
+    original = stale.created
+    handler = handlers.HTTPHandler(url, max_backdate=600)
+    metrics = _send(handler, stale, _record("fresh", age=10))
+
+    old, fresh = json.loads(http_collector.bodies[0])
+    assert old["timestamp_capped"] is True
+    assert old["original_created"] == pytest.approx(original)
+    assert time.time() - 601 <= old["created"] <= time.time() - 599
+    assert "timestamp_capped" not in fresh
+    assert "original_created" not in fresh
+    assert metrics["timestamps_capped"] == 1, metrics
+
+
+def test_timestamps_unchanged_by_default(http_collector):
+    url = http_collector.url
+    record = _record("untouched", age=7200)
+    _send(handlers.HTTPHandler(url), record)
+
+    (sent,) = json.loads(http_collector.bodies[0])
+    assert "sent_at" not in sent
+    assert sent["created"] == pytest.approx(record.created)
+
+
+def test_transform_callback_sees_the_stamped_records(http_collector):
+    url = http_collector.url
+    seen = []
+
+    def transform(records):
+        seen.extend(records)
+        return records
+
+    handler = handlers.HTTPHandler(
+        url, send_time=True, max_backdate=60, transform_callback=transform
+    )
+    _send(handler, _record("old", age=3600))
+
+    assert seen[0]["timestamp_capped"] is True
+    assert "sent_at" in seen[0]
+
+
+def test_otlp_capped_record_keeps_original_time(http_collector):
+    url = http_collector.url
+    handler = handlers.OTLPHandler(
+        url, service_name="timestamps", send_time=True, max_backdate=600
+    )
+    metrics = _send(handler, _record("otlp stale", age=7200))
+
+    assert b"logxide.original_time_unix_nano" in http_collector.bodies[0]
+    assert b"logxide.timestamp_capped" in http_collector.bodies[0]
+    assert metrics["timestamps_capped"] == 1, metrics
+
+
+def test_invalid_options_are_rejected():
+    with pytest.raises(ValueError):
+        handlers.HTTPHandler("http://127.0.0.1:9", max_backdate=0)
+    with pytest.raises(ValueError):
+        handlers.HTTPHandler("http://127.0.0.1:9", method="GET", send_time=True)
@@ -0,0 +1,172 @@
+"""
+Tests for JsonFormatter: one JSON object per record with the standard attributes
+and typed extras, field whitelisting and renaming, on native and Python dispatch.
+"""
+
+import json
+
+import pytest
+
+from logxide import FileHandler, JsonFormatter, logging
+from logxide.logxide import logging as native_logging
+
+
+def _record(msg="hello"):
+    record = native_logging.LogRecord("app.db", 20, "/srv/app/db.py", 12, msg)
+    record.levelname = "INFO"
+    record.created = 1720000000.5
+    return record
+
+
+@pytest.fixture
+def logged(isolated_logger):
+    """Log a few records through a FileHandler and return the parsed lines."""
+
+    def logged(directory, formatter, *, python_dispatch=False):
+        path = directory / "app.log"
+        handler = FileHandler(str(path))
+        handler.setFormatter(formatter)
+        if python_dispatch:
+            handler.addFilter(lambda record: True)
+        assert handler._native is not python_dispatch
+
+        logger = isolated_logger(handler, level=logging.DEBUG)
+        logger.info(
+            "order %s placed",
+            "A-1",
+            extra={"order": {"id": 7, "total": 12.5, "tags": ["new", None, True]}},
+        )
+        try:
+            1 / 0
+        except ZeroDivisionError:
+            logger.warning("retrying", exc_info=True)
+        handler.flush()
+        return [json.loads(line) for line in path.read_text().splitlines()]
+
+    return logged
+
+
+def test_default_fields_then_extras():
+    record = _record()
+    record.zeta = 1
+    record.alpha = [1, 2]
+    line = json.loads(JsonFormatter().format(record))
+
+    assert list(line)[: len(JsonFormatter().fields)] == JsonFormatter().fields
+    assert list(line)[-2:] == ["alpha", "zeta"]
+    assert line["message"] == "hello"
+    assert line["levelno"] == 20
+    assert line["created"] == 1720000000.5
+    assert line["exc_info"] is None
+    assert "asctime" not in line
+
+
+def test_datefmt_adds_asctime():
+    formatter = JsonFormatter(datefmt="%Y")
+    assert formatter.fields[0] == "asctime"
+    assert json.loads(formatter.format(_record()))["asctime"] == "2024"
+
+
+def test_fields_and_rename():
+    record = _record()
+    record.request_id = "r-9"
+    formatter = JsonFormatter(
+        fields=["created", "levelname", "message", "request_id", "missing"],
+        rename={"created": "ts", "levelname": "level", "request_id": "rid"},
+    )
+    assert formatter.format(record) == (
+        '{"ts":1720000000.5,"level":"INFO","message":"hello","rid":"r-9"}'
+    )
+
+
+@pytest.mark.parametrize("python_dispatch", [False, True])
+def test_file_handler_writes_one_object_per_line(tmp_path, python_dispatch, logged):
+    first, second = logged(
+        tmp_path,
+        JsonFormatter(fields=["levelname", "message", "order", "exc_info", "lineno"]),
+        python_dispatch=python_dispatch,
+    )
+
+    assert first == {
+        "levelname": "INFO",
+        "message": "order A-1 placed",
+        "order": {"id": 7, "total": 12.5, "tags": ["new", None, True]},
+        "exc_info": None,
+        "lineno": first["lineno"],
+    }
+    assert first["lineno"] > 0
+    assert second["levelname"] == "WARNING"
+    assert second["exc_info"].startswith("Traceback (most recent call last):")
+    assert second["exc_info"].endswith("ZeroDivisionError: division by zero")
+
+
+def test_canonical_output_is_stable():
+    record = _record()
+    record.msecs = 500.0
+    record.relativeCreated = 1234.5678
+    record.zeta = {"b": 2.5, "a": [1, 0.1]}
+    record.alpha = 3
+    formatter = JsonFormatter(
+        fields=["zeta", "message", "created", "relativeCreated", "alpha", "asctime"],
+        rename={"message": "msg"},
+        datefmt="%H:%M",
+        canonical=True,
+    )
+    assert formatter.canonical
+    assert formatter.format(record) == (
+        '{"alpha":3,"asctime":"1970-01-01 00:00:00","created":0.000000,'
+        '"msg":"hello","relativeCreated":0.000000,'
+        '"zeta":{"a":[1,0.100000],"b":2.500000}}'
+    )
+
+
+def test_canonical_lines_match_across_runs(tmp_path, logged):
+    runs = []
+    for run in ("a", "b"):
+        (tmp_path / run).mkdir()
+        runs.append(logged(tmp_path / run, JsonFormatter(canonical=True)))
+
+    for line in runs[0]:
+        assert list(line) == sorted(line)
+        assert line["created"] == line["msecs"] == line["relativeCreated"] == 0
+    # Each run logs through its own logger; everything else is identical.
+    for line in runs[0] + runs[1]:
+        del line["name"]
+    assert runs[0] == runs[1]
+
+
+def test_changed_record_is_encoded_again():
+    record = _record()
+    formatter = JsonFormatter(fields=["message", "user"])
+    assert formatter.format(record) == '{"message":"hello"}'
+    record.msg = "bye"
+    record.user = "ann"
+    assert formatter.format(record) == '{"message":"bye","user":"ann"}'
+
+
+def test_handlers_share_encodings_per_layout(tmp_path, isolated_logger):
+    logger = isolated_logger(level=logging.DEBUG)
+    formatters = {
+        "a": JsonFormatter(fields=["levelname", "message"]),
+        "b": JsonFormatter(fields=["levelname", "message"]),
+        "renamed": JsonFormatter(fields=["levelname", "message"], rename={"message": "m"}),
+        "unsorted": JsonFormatter(fields=["message", "levelname"]),
+        "canonical": JsonFormatter(fields=["message", "levelname"], canonical=True),
+    }
+    handlers = []
+    for name, formatter in formatters.items():
+        handler = FileHandler(str(tmp_path / f"{name}.log"))
+        handler.setFormatter(formatter)
+        logger.addHandler(handler)
+        handlers.append(handler)
+    logger.warning("disk %s full", "/var")
+    for handler in handlers:
+        handler.flush()
+
+    def line(name):
+        return (tmp_path / f"{name}.log").read_text()
+
+    assert line("a") == line("b") == '{"levelname":"WARNING","message":"disk /var full"}\n'
+    assert line("renamed") == '{"levelname":"WARNING","m":"disk /var full"}\n'
+    assert line("unsorted") == '{"message":"disk /var full","levelname":"WARNING"}\n'
+    assert line("canonical") == '{"levelname":"WARNING","message":"disk /var full"}\n'
@@ -0,0 +1,73 @@
+"""
+Tests for ColorFormatter level badges: a per-level prefix such as an emoji,
+left off automatically when the console can't encode it.
+"""
+
+import os
+import subprocess
+import sys
+import textwrap
+
+import pytest
+
+from logxide import ColorFormatter
+from logxide.logxide import logging as native_logging
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+
+def _record(levelname, levelno, msg="hello"):
+    record = native_logging.LogRecord("app", levelno, "", 0, msg)
+    record.levelname = levelname
+    return record
+
+
+def test_no_badges_by_default():
+    fmt = ColorFormatter("%(levelname)s %(message)s")
+
+    assert fmt.level_badges == {}
+    assert fmt.format(_record("ERROR", 40)) == "ERROR hello"
+
+
+def test_custom_badges_prefix_matching_levels():
+    fmt = ColorFormatter(
+        "%(levelname)s %(message)s", level_badges={"error": "[x]", "INFO": "[i]"}
+    )
+
+    assert fmt.format(_record("ERROR", 40)) == "[x] ERROR hello"
+    assert fmt.format(_record("INFO", 20)) == "[i] INFO hello"
+    assert fmt.format(_record("WARNING", 30)) == "WARNING hello"
+
+
+def test_rejects_other_values():
+    with pytest.raises(TypeError):
+        ColorFormatter(level_badges=["INFO"])
+
+
+SCRIPT = textwrap.dedent(
+    """
+    from logxide import ColorFormatter
+    fmt = ColorFormatter("%(message)s", level_badges=True)
+    print(sorted(fmt.level_badges))
+    """
+)
+
+
+@pytest.mark.parametrize(
+    "encoding, levels",
+    [
+        ("utf-8", "['CRITICAL', 'DEBUG', 'ERROR', 'INFO', 'WARNING']"),
+        ("ascii", "[]"),
+        ("latin-1", "[]"),
+    ],
+)
+def test_default_badges_follow_console_encoding(encoding, levels):
+    result = subprocess.run(
+        [sys.executable, "-c", SCRIPT],
+        capture_output=True,
+        text=True,
+        timeout=30,
+        env=dict(os.environ, PYTHONPATH=REPO_ROOT, PYTHONIOENCODING=encoding),
+    )
+
+    assert result.stdout.strip() == levels
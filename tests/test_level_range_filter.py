@@ -0,0 +1,95 @@
+"""
+Tests for logxide.LevelRangeFilter and for handlers running Rust filters natively:
+a handler whose filters are all Rust filters keeps its native dispatch path.
+"""
+
+import logging as std_logging
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def logger(isolated_logger):
+    logger = isolated_logger(name="app.levelrange", level=logging.DEBUG)
+    yield logger
+    for handler in list(logger.handlers):
+        logger.removeHandler(handler)
+
+
+def _lines(path):
+    return path.read_text().splitlines()
+
+
+@pytest.mark.parametrize(
+    ("levelno", "passes"),
+    [(10, False), (20, True), (25, True), (30, True), (40, False)],
+)
+def test_bounds_are_inclusive(levelno, passes):
+    record = std_logging.LogRecord("x", levelno, __file__, 1, "msg", None, None)
+    band = logxide.LevelRangeFilter(logging.INFO, logging.WARNING)
+    assert band.filter(record) is passes
+
+
+def test_levels_by_name_and_open_upper_bound():
+    f = logxide.LevelRangeFilter("error")
+    assert (f.min_level, f.max_level) == (40, None)
+    record = std_logging.LogRecord("x", 60, __file__, 1, "msg", None, None)
+    assert f.filter(record) is True
+    assert logxide.LevelRangeFilter().min_level == 0
+
+
+def test_inverted_range_is_rejected():
+    with pytest.raises(ValueError):
+        logxide.LevelRangeFilter(logging.ERROR, logging.INFO)
+
+
+def test_handlers_split_by_level_natively(logger, tmp_path):
+    out = handlers.FileHandler(str(tmp_path / "out.log"))
+    out.addFilter(logxide.LevelRangeFilter(logging.INFO, logging.WARNING))
+    err = handlers.FileHandler(str(tmp_path / "err.log"))
+    err.addFilter(logxide.LevelRangeFilter(logging.ERROR))
+    assert out._inner.isNative() and err._inner.isNative()
+    logger.addHandler(out)
+    logger.addHandler(err)
+    for level in (logging.DEBUG, logging.INFO, logging.WARNING, logging.ERROR):
+        logger.log(level, logging.getLevelName(level))
+    logger.critical("CRITICAL")
+    out.flush()
+    err.flush()
+    assert _lines(tmp_path / "out.log") == ["INFO", "WARNING"]
+    assert _lines(tmp_path / "err.log") == ["ERROR", "CRITICAL"]
+
+
+def test_rust_filters_on_a_handler_apply_to_each_batch_record(logger):
+    queue = handlers.QueueHandler()
+    queue.addFilter(logxide.RegexFilter("^skip", mode="exclude"))
+    memory = handlers.MemoryHandler()
+    listener = handlers.QueueListener(queue, memory)
+    listener.start()
+    logger.addHandler(queue)
+    logger.log_batch(logging.INFO, ["keep 1", "skip 2", "keep 3"])
+    listener.stop()
+    assert [r.getMessage() for r in memory.records] == ["keep 1", "keep 3"]
+
+
+def test_a_python_filter_falls_back_to_python_dispatch(logger, tmp_path):
+    handler = handlers.FileHandler(str(tmp_path / "app.log"))
+    handler.addFilter(logxide.LevelRangeFilter(logging.INFO))
+
+    def python_filter(record):
+        return "secret" not in record.getMessage()
+
+    handler.addFilter(python_filter)
+    assert not handler._inner.isNative()
+    logger.addHandler(handler)
+    logger.debug("debug")
+    logger.info("secret")
+    logger.info("both filters ran")
+    handler.removeFilter(python_filter)
+    assert handler._inner.isNative()
+    logger.info("secret again")
+    handler.flush()
+    assert _lines(tmp_path / "app.log") == ["both filters ran", "secret again"]
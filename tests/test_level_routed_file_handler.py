@@ -0,0 +1,164 @@
+"""
+Tests for LevelRoutedFileHandler: each record lands in the file of its level band, and
+every band rotates on its own settings.
+"""
+
+import logging
+
+import pytest
+
+from logxide import handlers
+from logxide.config import dictConfig
+from logxide.logxide import logging as native_logging
+
+
+def _logger(tmp_path, handler):
+    logger = native_logging.getLogger(f"routed.{tmp_path.name}")
+    logger.setLevel(logging.DEBUG)
+    logger.propagate = False
+    logger.addHandler(handler)
+    return logger
+
+
+def _read(path):
+    with open(path) as f:
+        return f.read().splitlines()
+
+
+def test_records_go_to_their_band(tmp_path):
+    handler = handlers.LevelRoutedFileHandler(
+        {
+            logging.ERROR: str(tmp_path / "errors.log"),
+            "INFO": str(tmp_path / "info.log"),
+            logging.DEBUG: str(tmp_path / "debug.log"),
+        }
+    )
+    logger = _logger(tmp_path, handler)
+    logger.debug("d")
+    logger.info("i")
+    logger.warning("w")
+    logger.error("e")
+    logger.critical("c")
+    handler.flush()
+
+    assert _read(tmp_path / "debug.log") == ["d"]
+    assert _read(tmp_path / "info.log") == ["i", "w"]
+    assert _read(tmp_path / "errors.log") == ["e", "c"]
+
+
+def test_records_below_every_band_are_dropped(tmp_path):
+    handler = handlers.LevelRoutedFileHandler(
+        {logging.WARNING: str(tmp_path / "app.log")}
+    )
+    logger = _logger(tmp_path, handler)
+    logger.info("dropped")
+    logger.warning("kept")
+    handler.flush()
+
+    assert _read(tmp_path / "app.log") == ["kept"]
+
+
+def test_bands_rotate_independently(tmp_path):
+    handler = handlers.LevelRoutedFileHandler(
+        {
+            logging.ERROR: {
+                "filename": str(tmp_path / "errors.log"),
+                "maxBytes": 20,
+                "backupCount": 2,
+            },
+            logging.INFO: str(tmp_path / "info.log"),
+        }
+    )
+    logger = _logger(tmp_path, handler)
+    for n in range(3):
+        logger.error("error number %d", n)
+        logger.info("info number %d", n)
+    handler.flush()
+
+    assert _read(tmp_path / "errors.log") == ["error number 2"]
+    assert _read(tmp_path / "errors.log.1") == ["error number 1"]
+    assert _read(tmp_path / "errors.log.2") == ["error number 0"]
+    assert _read(tmp_path / "info.log") == [f"info number {n}" for n in range(3)]
+
+
+def test_formatter_and_level_apply_to_every_band(tmp_path):
+    handler = handlers.LevelRoutedFileHandler(
+        {
+            logging.ERROR: handlers.RotatingFileHandler(str(tmp_path / "errors.log")),
+            logging.DEBUG: str(tmp_path / "debug.log"),
+        }
+    )
+    handler.setFormatter(logging.Formatter("%(levelname)s %(message)s"))
+    handler.setLevel(logging.INFO)
+    logger = _logger(tmp_path, handler)
+    logger.debug("hidden")
+    logger.info("shown")
+    logger.error("failed")
+    handler.close()
+
+    assert _read(tmp_path / "debug.log") == ["INFO shown"]
+    assert _read(tmp_path / "errors.log") == ["ERROR failed"]
+
+
+def test_python_formatting_fallback(tmp_path):
+    handler = handlers.LevelRoutedFileHandler(
+        {
+            logging.ERROR: str(tmp_path / "errors.log"),
+            logging.INFO: str(tmp_path / "info.log"),
+        }
+    )
+    handler.setFormatter(logging.Formatter("{levelname}: {message}", style="{"))
+    logger = _logger(tmp_path, handler)
+    logger.info("i")
+    logger.error("e")
+    handler.flush()
+
+    assert _read(tmp_path / "info.log") == ["INFO: i"]
+    assert _read(tmp_path / "errors.log") == ["ERROR: e"]
+
+
+def test_duplicate_level_is_rejected(tmp_path):
+    with pytest.raises(ValueError):
+        handlers.LevelRoutedFileHandler(
+            {
+                logging.ERROR: str(tmp_path / "a.log"),
+                "ERROR": str(tmp_path / "b.log"),
+            }
+        )
+
+
+def test_dict_config(tmp_path):
+    dictConfig(
+        {
+            "version": 1,
+            "handlers": {
+                "split": {
+                    "class": "logxide.LevelRoutedFileHandler",
+                    "routes": {
+                        "ERROR": str(tmp_path / "errors.log"),
+                        "INFO": {
+                            "filename": str(tmp_path / "info.log"),
+                            "when": "midnight",
+                            "backupCount": 7,
+                        },
+                    },
+                }
+            },
+            "loggers": {
+                f"routed.{tmp_path.name}": {
+                    "handlers": ["split"],
+                    "level": "INFO",
+                    "propagate": False,
+                }
+            },
+            "disable_existing_loggers": False,
+        }
+    )
+    logger = logging.getLogger(f"routed.{tmp_path.name}")
+    assert isinstance(logger.handlers[0], handlers.LevelRoutedFileHandler)
+    logger.info("i")
+    logger.error("e")
+    logger.handlers[0].flush()
+
+    assert _read(tmp_path / "info.log") == ["i"]
+    assert _read(tmp_path / "errors.log") == ["e"]
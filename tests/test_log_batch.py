@@ -0,0 +1,88 @@
+"""
+Tests for logger.log_batch(): many pre-formed messages built in one call and
+handed to queued handlers as a single queue push.
+"""
+
+import os
+import subprocess
+import sys
+import textwrap
+
+import pytest
+
+from logxide import handlers, logging
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+@pytest.fixture
+def capture(isolated_logger):
+    mh = handlers.MemoryHandler()
+    return isolated_logger(mh, name="test.log_batch", level=logging.DEBUG), mh
+
+
+def test_records_keep_order_level_and_extra(capture):
+    logger, mh = capture
+    logger.log_batch(logging.WARNING, ["a", "b", "c"], extra={"source": "import"})
+
+    assert [r.getMessage() for r in mh.records] == ["a", "b", "c"]
+    assert {r.levelno for r in mh.records} == {logging.WARNING}
+    assert {r.source for r in mh.records} == {"import"}
+    assert len({r.lineno for r in mh.records}) == 1
+
+
+def test_disabled_level_logs_nothing(capture):
+    logger, mh = capture
+    logger.setLevel(logging.ERROR)
+    logger.log_batch("INFO", ["dropped"] * 10)
+    assert mh.records == []
+
+
+def test_single_string_is_rejected(capture):
+    logger, _ = capture
+    with pytest.raises(TypeError):
+        logger.log_batch(logging.INFO, "not a list")
+
+
+def test_http_handler_takes_batch_as_one_queue_push(http_collector, isolated_logger):
+    handler = handlers.HTTPHandler(
+        http_collector.url, batch_size=1000, flush_interval=3600
+    )
+    logger = isolated_logger(handler, name="test.log_batch.http")
+    try:
+        logger.log_batch(logging.INFO, [f"row-{i}" for i in range(500)])
+        handler.flush()
+        metrics = handler.get_metrics()
+    finally:
+        handler.close()
+
+    sent = [r["msg"] for r in http_collector.records]
+    assert sent == [f"row-{i}" for i in range(500)]
+    assert metrics["enqueued"] == metrics["dequeued"] == 500, metrics
+    assert metrics["max_queue_depth"] == 1, metrics
+    assert metrics["sink_acknowledged"] == 500, metrics
+
+
+def test_stream_handler_writes_one_line_per_message():
+    script = textwrap.dedent(
+        """
+        import sys
+        from logxide import handlers, logging
+
+        handler = handlers.StreamHandler(sys.stdout)
+        logger = logging.getLogger("log_batch.stream")
+        logger.propagate = False
+        logger.setLevel(logging.INFO)
+        logger.addHandler(handler)
+        logger.log_batch(logging.INFO, ["first", "second", "third"])
+        handler.flush()
+        """
+    )
+    env = dict(os.environ, PYTHONPATH=REPO_ROOT)
+    result = subprocess.run(
+        [sys.executable, "-c", script],
+        capture_output=True,
+        text=True,
+        timeout=30,
+        env=env,
+    )
+    assert result.stdout.splitlines() == ["first", "second", "third"]
@@ -0,0 +1,146 @@
+"""
+Tests for index sidecars: file handlers with `index_every=N` add every Nth record's
+time and offset to `<file>.idx`, which the log file readers and `python -m logxide`
+use to read only the part of a file a `since`/`until` range needs.
+"""
+
+import datetime
+import os
+import subprocess
+import sys
+
+import pytest
+
+import logxide
+from logxide import FileHandler, RotatingFileHandler, logging
+from logxide.module_system import _std_logging
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+FORMAT = "%(created)s %(message)s"
+START = 1_700_000_000
+
+
+def _write(handler, count, first=0):
+    """Write records `first`.. one minute apart from START, then close `handler`."""
+    handler.setFormatter(logging.Formatter(FORMAT))
+    try:
+        for i in range(first, first + count):
+            record = _std_logging.LogRecord("app", 20, __file__, 1, "r%d", (i,), None)
+            record.created = START + i * 60
+            handler.handle(record)
+    finally:
+        handler.close()
+
+
+def _messages(path, **options):
+    return [r.msg for r in logxide.read_log_file(path, format=FORMAT, **options)]
+
+
+@pytest.fixture
+def app_log(tmp_path):
+    path = tmp_path / "app.log"
+    _write(FileHandler(str(path), index_every=4), 10)
+    return path
+
+
+def test_every_nth_record_is_indexed(app_log):
+    entries = [line.split() for line in (app_log.parent / "app.log.idx").open()]
+    assert [float(created) for created, _ in entries] == [
+        START,
+        START + 4 * 60,
+        START + 8 * 60,
+    ]
+    lines = app_log.read_bytes().splitlines(keepends=True)
+    assert [int(offset) for _, offset in entries] == [
+        sum(map(len, lines[:i])) for i in (0, 4, 8)
+    ]
+
+
+def test_since_and_until_select_records(app_log):
+    assert _messages(app_log, since=START + 300) == ["r5", "r6", "r7", "r8", "r9"]
+    assert _messages(app_log, until=START + 120) == ["r0", "r1", "r2"]
+    assert _messages(app_log, since=START + 150, until=START + 400) == [
+        "r3",
+        "r4",
+        "r5",
+        "r6",
+    ]
+    since = datetime.datetime.fromtimestamp(START + 480, datetime.timezone.utc)
+    assert _messages(app_log, since=since) == ["r8", "r9"]
+    with pytest.raises(TypeError, match="datetime or seconds"):
+        _messages(app_log, since="yesterday")
+
+
+def test_reading_starts_at_the_index_entry(app_log):
+    # An out-of-order time before the entry for `since` is not read at all; without
+    # the index the whole file is scanned and it is found.
+    text = app_log.read_text().replace(f"{START + 60} r1", f"{START + 420} r1")
+    app_log.write_text(text)
+    assert _messages(app_log, since=START + 400) == ["r7", "r8", "r9"]
+    (app_log.parent / "app.log.idx").unlink()
+    assert _messages(app_log, since=START + 400) == ["r1", "r7", "r8", "r9"]
+
+
+def test_an_index_that_does_not_match_is_ignored(app_log):
+    # The index of another file: its offsets land mid-line or on other records.
+    other = app_log.parent / "other.log"
+    other.write_text("".join(f"{START + i * 60} message {i}\n" for i in range(10)))
+    (app_log.parent / "other.log.idx").write_bytes(
+        (app_log.parent / "app.log.idx").read_bytes()
+    )
+    assert _messages(other, since=START + 480) == ["message 8", "message 9"]
+
+
+def test_truncated_file_starts_a_new_index(app_log):
+    _write(FileHandler(str(app_log), mode="w", index_every=4), 2, first=20)
+    assert app_log.read_text().splitlines() == [
+        f"{START + 1200} r20",
+        f"{START + 1260} r21",
+    ]
+    assert (app_log.parent / "app.log.idx").read_text() == f"{START + 1200}.000000 0\n"
+
+    # Appending continues the index.
+    _write(FileHandler(str(app_log), index_every=4), 1, first=22)
+    offsets = [line.split()[1] for line in (app_log.parent / "app.log.idx").open()]
+    assert offsets == ["0", str(len(f"{START + 1200} r20\n") * 2)]
+
+
+def test_rotated_backups_keep_their_index(tmp_path):
+    path = tmp_path / "rot.log"
+    handler = RotatingFileHandler(str(path), maxBytes=100, backupCount=2, index_every=2)
+    _write(handler, 20)
+    for backup in (path, tmp_path / "rot.log.1", tmp_path / "rot.log.2"):
+        index = backup.parent / (backup.name + ".idx")
+        first = backup.read_text().split()[0]
+        assert index.read_text().split()[:2] == [f"{first}.000000", "0"]
+        created, message = backup.read_text().splitlines()[-1].split()
+        assert _messages(backup, since=int(created)) == [message]
+    assert not (tmp_path / "rot.log.3.idx").exists()
+
+
+def test_cli_since_until(app_log):
+    since = datetime.datetime.fromtimestamp(START + 360).isoformat()
+    result = subprocess.run(
+        [
+            sys.executable,
+            "-m",
+            "logxide",
+            "convert",
+            str(app_log),
+            "--to",
+            "text",
+            "--format",
+            FORMAT,
+            "--since",
+            since,
+            "--until",
+            str(START + 420),
+        ],
+        env={**os.environ, "PYTHONPATH": REPO_ROOT},
+        capture_output=True,
+        text=True,
+        timeout=60,
+    )
+    assert result.returncode == 0, result.stderr
+    assert [line.split()[-1] for line in result.stdout.splitlines()] == ["r6", "r7"]
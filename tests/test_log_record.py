@@ -0,0 +1,139 @@
+"""
+Tests for the native LogRecord's debugging helpers: repr/str like the stdlib,
+getMessage(), equality on the logged event, and pickling.
+"""
+
+import logging as std_logging
+import multiprocessing
+import pickle
+
+import pytest
+
+from logxide import JsonFormatter, LogRecord, RustFormatter, handlers, logging
+
+
+def test_repr_matches_the_stdlib_layout():
+    record = LogRecord(
+        "app.db", logging.WARNING, "/srv/db.py", 42, "slow %s", ("query",)
+    )
+    reference = std_logging.LogRecord(
+        "app.db", logging.WARNING, "/srv/db.py", 42, "slow %s", ("query",), None
+    )
+
+    assert repr(record) == repr(reference)
+    assert repr(record) == '<LogRecord: app.db, 30, /srv/db.py, 42, "slow %s">'
+    assert str(record) == repr(record)
+
+
+def test_get_message_merges_args():
+    record = LogRecord("app", logging.INFO, "", 0, "%d of %d", (3, 7))
+    assert record.getMessage() == "3 of 7"
+
+
+def test_equal_records():
+    a = LogRecord("app", logging.INFO, "/a.py", 1, "hello %s", ("bob",))
+    b = LogRecord("app", logging.INFO, "/a.py", 1, "hello bob")
+    b.created = a.created + 5
+    b.thread = 1234
+
+    assert a == b
+    assert not a != b
+
+
+@pytest.mark.parametrize(
+    "field,value",
+    [
+        ("name", "other"),
+        ("levelno", logging.ERROR),
+        ("lineno", 2),
+        ("msg", "goodbye"),
+        ("exc_text", "Traceback ..."),
+        ("user_id", 7),
+    ],
+)
+def test_records_differing_in_the_event_are_unequal(field, value):
+    a = LogRecord("app", logging.INFO, "/a.py", 1, "hello")
+    b = LogRecord("app", logging.INFO, "/a.py", 1, "hello")
+    setattr(b, field, value)
+
+    assert a != b
+
+
+def test_comparison_with_other_types():
+    record = LogRecord("app", logging.INFO, "", 0, "hello")
+    assert record != "hello"
+    assert record != None  # noqa: E711
+
+
+def test_captured_records_compare_against_built_ones():
+    capture = handlers.MemoryHandler()
+    logger = logging.getLogger("test.log_record.captured")
+    logger.propagate = False
+    logger.setLevel(logging.INFO)
+    logger.addHandler(capture)
+    logger.info("ready in %dms", 12)
+
+    (record,) = capture.records
+    assert repr(record).startswith("<LogRecord: test.log_record.captured, 20, ")
+    expected = LogRecord(
+        record.name, logging.INFO, record.pathname, record.lineno, "ready in 12ms"
+    )
+    expected.funcName = record.funcName
+    assert record == expected
+
+
+def _full_record():
+    record = LogRecord(
+        "app.jobs", logging.ERROR, "/srv/jobs.py", 88, "job %s: %d", ("sync", 3)
+    )
+    record.levelname = "ERROR"
+    record.filename = "jobs.py"
+    record.module = "jobs"
+    record.funcName = "run"
+    record.created = 1_700_000_000.25
+    record.msecs = 250.0
+    record.relativeCreated = 1234.5
+    record.thread = 140_000
+    record.threadName = "worker-1"
+    record.process = 4321
+    record.processName = "MainProcess"
+    record.exc_text = "Traceback (most recent call last):\nValueError: boom"
+    record.stack_info = "Stack (most recent call last):"
+    record.task_name = "Task-1"
+    record.user = {"id": 7, "roles": ["admin"]}
+    return record
+
+
+@pytest.mark.parametrize("protocol", range(pickle.HIGHEST_PROTOCOL + 1))
+def test_pickle_round_trips_every_field(protocol):
+    record = _full_record()
+    copy = pickle.loads(pickle.dumps(record, protocol=protocol))
+
+    assert type(copy) is LogRecord
+    assert copy.__getstate__() == record.__getstate__()
+    assert copy == record
+    assert copy.args == ("sync", 3)
+    assert copy.user == {"id": 7, "roles": ["admin"]}
+
+
+def test_unpickled_records_format_identically():
+    record = _full_record()
+    copy = pickle.loads(pickle.dumps(record))
+    formatter = RustFormatter(
+        "%(asctime)s %(levelname)s %(name)s %(funcName)s:%(lineno)d %(message)s"
+        " %(threadName)s %(user)s"
+    )
+
+    assert formatter.format(copy) == formatter.format(record)
+    assert JsonFormatter().format(copy) == JsonFormatter().format(record)
+
+
+def test_records_cross_a_multiprocessing_queue():
+    queue = multiprocessing.get_context("spawn").Queue()
+    record = _full_record()
+    queue.put(record)
+    copy = queue.get(timeout=10)
+    queue.close()
+
+    assert copy == record
+    assert copy.created == record.created
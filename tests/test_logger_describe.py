@@ -0,0 +1,118 @@
+"""
+Tests for Logger.describe(): a logger's levels, handlers, filters and the ancestors a
+record reaches after it.
+"""
+
+import logging as std_logging
+import os
+import subprocess
+import sys
+import textwrap
+
+import logxide
+from logxide import handlers
+from logxide.logxide import logging as native_logging
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+
+class _Recorder(std_logging.Handler):
+    def emit(self, record):
+        pass
+
+
+def test_levels_filters_and_own_handlers(isolated_logger):
+    app = isolated_logger.name("describe")
+    logger = native_logging.getLogger(f"{app}.db")
+    logger.setLevel(std_logging.DEBUG)
+    console = handlers.StreamHandler()
+    console.name = "console"
+    console.setLevel(std_logging.INFO)
+    logger.addHandler(console)
+
+    def keep(record):
+        return True
+
+    logger.addFilter(keep)
+
+    info = logger.describe()
+    assert info["name"] == logger.name
+    assert info["level"] == std_logging.DEBUG
+    assert info["effective_level"] == std_logging.DEBUG
+    assert info["disabled"] is False
+    assert info["propagate"] is True
+    assert info["filters"] == [keep]
+    assert info["handlers"] == [
+        {"type": "StreamHandler", "name": "console", "level": std_logging.INFO}
+    ]
+
+
+def test_effective_level_is_inherited(isolated_logger):
+    app = isolated_logger.name("describe")
+    native_logging.getLogger(app).setLevel(std_logging.ERROR)
+    info = native_logging.getLogger(f"{app}.worker").describe()
+    assert info["level"] == std_logging.NOTSET
+    assert info["effective_level"] == std_logging.ERROR
+
+
+def test_ancestor_chain_ends_at_root(isolated_logger):
+    app = isolated_logger.name("describe")
+    parent = native_logging.getLogger(app)
+    parent.addHandler(logxide.RustStreamHandler())
+    foreign = _Recorder()
+    native_logging.getLogger("root").addHandler(foreign)
+    try:
+        # `{app}.db` was never created, so the walk skips it as dispatch does.
+        info = native_logging.getLogger(f"{app}.db.pool").describe()
+        assert [a["name"] for a in info["ancestors"]] == [app, "root"]
+        assert info["ancestors"][0]["handlers"] == [
+            {"type": "StreamHandler", "name": None, "level": None}
+        ]
+        root_handlers = info["ancestors"][1]["handlers"]
+        assert {"type": "_Recorder", "name": None, "level": 0} in root_handlers
+    finally:
+        native_logging.getLogger("root").removeHandler(foreign)
+
+
+def test_propagate_false_stops_the_chain(isolated_logger):
+    app = isolated_logger.name("describe")
+    middle = native_logging.getLogger(f"{app}.quiet")
+    middle.propagate = False
+    info = native_logging.getLogger(f"{app}.quiet.leaf").describe()
+    assert [a["name"] for a in info["ancestors"]] == [f"{app}.quiet"]
+    assert info["ancestors"][0]["propagate"] is False
+
+    assert middle.describe()["ancestors"] == []
+
+
+def test_removed_handler_is_no_longer_listed(isolated_logger):
+    logger = native_logging.getLogger(isolated_logger.name("describe"))
+    handler = handlers.StreamHandler()
+    logger.addHandler(handler)
+    logger.removeHandler(handler)
+    assert logger.describe()["handlers"] == []
+
+
+def test_installed_logger_forwards_describe():
+    script = textwrap.dedent(
+        """
+        import logging
+        import logxide
+
+        logger = logging.getLogger("app.db")
+        logger.setLevel(logging.WARNING)
+        info = logger.describe()
+        assert info["name"] == "app.db", info
+        assert info["level"] == logging.WARNING, info
+        """
+    )
+    env = dict(os.environ, PYTHONPATH=REPO_ROOT)
+    env.pop("PYTEST_CURRENT_TEST", None)
+    result = subprocess.run(
+        [sys.executable, "-c", script],
+        capture_output=True,
+        text=True,
+        timeout=30,
+        env=env,
+    )
+    assert result.returncode == 0, result.stderr
@@ -0,0 +1,162 @@
+"""
+Tests for Logger.addFilter(): logging.Filter instances, objects with a filter()
+method and plain callables get the record, can change it, and drop it by returning
+False or None. logxide.Filter gives logging.Filter's name matching in Rust.
+"""
+
+import logging as std_logging
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+from logxide.logxide import logging as native_logging
+
+
+@pytest.fixture
+def capture(isolated_logger):
+    mh = handlers.MemoryHandler()
+    logger = isolated_logger(mh, name="app.filters", level=logging.DEBUG)
+    yield logger, mh
+    for f in logger.filters:
+        logger.removeFilter(f)
+
+
+def _messages(mh):
+    return [record.getMessage() for record in mh.records]
+
+
+def test_stdlib_filter_matches_logger_names(capture):
+    logger, mh = capture
+    logger.addFilter(std_logging.Filter("app.filters"))
+    logger.info("kept")
+    logger.addFilter(std_logging.Filter("other"))
+    logger.info("dropped")
+    assert _messages(mh) == ["kept"]
+
+
+def test_callable_returning_none_drops_the_record(capture):
+    logger, mh = capture
+    logger.addFilter(lambda record: None if record.levelno < logging.WARNING else True)
+    logger.info("quiet %s", 1)
+    logger.warning("loud %s", 2)
+    assert _messages(mh) == ["loud 2"]
+
+
+def test_filter_sees_arguments_and_extras(capture):
+    logger, mh = capture
+    seen = []
+
+    class Recorder:
+        def filter(self, record):
+            seen.append((record.name, record.getMessage(), record.user))
+            return True
+
+    logger.addFilter(Recorder())
+    logger.info("hello %s", "ann", extra={"user": "ann"})
+    assert seen == [(logger.name, "hello ann", "ann")]
+
+
+def test_changes_reach_the_handlers(capture):
+    logger, mh = capture
+
+    def redact(record):
+        record.msg = record.msg.replace("hunter2", "***")
+        record.redacted = True
+        return True
+
+    logger.addFilter(redact)
+    logger.info("password hunter2")
+    record = mh.records[-1]
+    assert (record.msg, record.redacted) == ("password ***", True)
+
+
+def test_returned_record_replaces_it(capture):
+    logger, mh = capture
+
+    def replace(record):
+        return native_logging.LogRecord(
+            record.name, record.levelno, record.pathname, record.lineno, "replaced"
+        )
+
+    logger.addFilter(replace)
+    logger.info("original")
+    assert _messages(mh) == ["replaced"]
+
+
+def test_raising_filter_keeps_the_record(capture):
+    logger, mh = capture
+    logger.addFilter(lambda record: 1 / 0)
+    logger.info("kept")
+    assert _messages(mh) == ["kept"]
+
+
+def test_filters_are_listed_once_and_removable(capture):
+    logger, mh = capture
+
+    def drop(record):
+        return False
+
+    logger.addFilter(drop)
+    logger.addFilter(drop)
+    assert logger.filters == [drop]
+    logger.info("dropped")
+    logger.removeFilter(drop)
+    assert logger.filters == []
+    logger.info("kept")
+    assert _messages(mh) == ["kept"]
+
+
+def test_logger_filter_method(capture):
+    logger, _ = capture
+    record = native_logging.LogRecord(logger.name, 20, __file__, 1, "secret")
+    logger.addFilter(lambda record: "secret" not in record.msg)
+    assert logger.filter(record) is False
+    record.msg = "public"
+    assert logger.filter(record) is True
+
+
+@pytest.mark.parametrize(
+    ("name", "passes"),
+    [
+        ("app.db", True),
+        ("app.db.pool", True),
+        ("app.dbx", False),
+        ("app", False),
+        ("other.app.db", False),
+    ],
+)
+def test_native_filter_matches_like_logging_filter(name, passes):
+    record = std_logging.LogRecord(name, 20, __file__, 1, "msg", None, None)
+    assert logxide.Filter("app.db").filter(record) is passes
+    assert bool(std_logging.Filter("app.db").filter(record)) is passes
+    assert logxide.Filter().filter(record) is True
+
+
+def test_native_filters_on_a_logger(capture):
+    logger, mh = capture
+    logger.addFilter(logxide.Filter("app.filters"))
+    logger.info("kept")
+    logger.log_batch(logging.INFO, ["batch 1", "batch 2"])
+    blocking = logxide.Filter("app.other")
+    logger.addFilter(blocking)
+    logger.info("dropped")
+    logger.log_batch(logging.INFO, ["dropped 1", "dropped 2"])
+    logger.removeFilter(blocking)
+    logger.addFilter(lambda record: record.levelno >= logging.WARNING)
+    logger.info("dropped by the callable")
+    logger.warning("kept too")
+    assert _messages(mh) == ["kept", "batch 1", "batch 2", "kept too"]
+
+
+def test_filter_subclass_runs_its_own_filter(capture):
+    logger, mh = capture
+
+    class Quiet(logxide.Filter):
+        def filter(self, record):
+            return super().filter(record) and "noise" not in record.msg
+
+    logger.addFilter(Quiet("app"))
+    logger.info("noise")
+    logger.info("signal")
+    assert _messages(mh) == ["signal"]
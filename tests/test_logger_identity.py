@@ -0,0 +1,59 @@
+"""
+Tests for logger identity in the native module: getLogger, getChild and .root return
+the one registered instance per name, so state set through one reference is seen
+through every other, and getChildren returns those instances.
+"""
+
+from logxide.logxide import logging as native_logging
+from logxide.module_system import _install, _std_logging, uninstall
+
+
+def test_get_logger_returns_the_same_object():
+    logger = native_logging.getLogger("app.identity")
+    assert native_logging.getLogger("app.identity") is logger
+    assert native_logging.getLogger() is native_logging.getLogger("root")
+
+
+def test_get_child_returns_the_registered_logger():
+    parent = native_logging.getLogger("app.identity.parent")
+    child = parent.getChild("db")
+    assert child is native_logging.getLogger("app.identity.parent.db")
+    assert parent.getChild("db") is child
+    assert child.root is native_logging.getLogger()
+
+
+def test_state_is_shared_between_references():
+    child = native_logging.getLogger("app.identity.shared").getChild("worker")
+    child.setLevel(30)
+    child.propagate = False
+    again = native_logging.getLogger("app.identity.shared.worker")
+    assert again.level == 30
+    assert again.propagate is False
+
+
+def test_get_children_returns_direct_children():
+    parent = native_logging.getLogger("app.identity.tree")
+    db = native_logging.getLogger("app.identity.tree.db")
+    api = parent.getChild("api")
+    native_logging.getLogger("app.identity.tree.db.pool")
+    native_logging.getLogger("app.identity.tree.cache.redis")
+    native_logging.getLogger("app.identity.treehouse")
+    assert parent.getChildren() == {db, api}
+    assert api.getChildren() == set()
+    assert native_logging.getLogger("app") in native_logging.getLogger().getChildren()
+
+
+def test_stdlib_logger_shells_have_get_children():
+    # Once installed, logging.getLogger hands libraries stdlib loggers forwarding to
+    # logxide's; on Python 3.11 their class has no getChildren of its own.
+    _install()
+    try:
+        parent = _std_logging.getLogger("app.identity.shell")
+        db = _std_logging.getLogger("app.identity.shell.db")
+        pool = _std_logging.getLogger("app.identity.shell.db.pool")
+        _std_logging.getLogger("app.identity.shellfish")
+        assert parent.getChildren() == {db}
+        assert db.getChildren() == {pool}
+        assert _std_logging.getLogger("app") in _std_logging.getLogger().getChildren()
+    finally:
+        uninstall()
@@ -0,0 +1,79 @@
+"""
+Tests for configure_message_templates(): numbers, UUIDs and hex ids in messages are
+replaced by placeholders and stored as the `message_template` extra.
+"""
+
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def capture(isolated_logger):
+    mh = handlers.MemoryHandler()
+    logger = isolated_logger(mh, level=logging.DEBUG)
+    logxide.configure_message_templates()
+    yield logger, mh
+    logxide.configure_message_templates(enabled=False)
+
+
+@pytest.mark.parametrize(
+    "message, template",
+    [
+        ("user 42 took 10ms", "user <num> took <num>ms"),
+        ("ratio 3.5, version 1.2.3", "ratio <num>, version <num>"),
+        (
+            "job 0b7c2f0e-3c4a-4f6e-9d1a-2b3c4d5e6f70 done",
+            "job <uuid> done",
+        ),
+        ("at 0x7ffd1a2b, commit 9f86d081884c", "at <hex>, commit <hex>"),
+        ("utf8 over ipv4 to host2", "utf8 over ipv4 to host2"),
+        ("cafebabe and deadbeef", "cafebabe and deadbeef"),
+        ("id=17 (retry -3)", "id=<num> (retry -<num>)"),
+        ("naïve 7 ünits", "naïve <num> ünits"),
+        ("", ""),
+    ],
+)
+def test_extraction(message, template):
+    assert logxide.message_template(message) == template
+
+
+def test_disabled_by_default(isolated_logger):
+    mh = handlers.MemoryHandler()
+    logger = isolated_logger(mh)
+    logger.warning("request 1 failed")
+    assert not hasattr(mh.records[-1], "message_template")
+
+
+def test_extra_uses_the_formatted_message(capture):
+    logger, mh = capture
+    logger.info("order %d shipped in %.1fs", 1234, 2.5)
+    logger.info(f"order {5678} shipped in {0.7}s")
+    assert [r.message_template for r in mh.records] == [
+        "order <num> shipped in <num>s",
+        "order <num> shipped in <num>s",
+    ]
+
+
+def test_log_batch_records_get_the_extra(capture):
+    logger, mh = capture
+    logger.log_batch(logging.INFO, ["item 1", "item 22"])
+    assert [r.message_template for r in mh.records] == ["item <num>", "item <num>"]
+
+
+def test_aggregating_handler_groups_by_template(capture):
+    logger, _ = capture
+    target = handlers.MemoryHandler()
+    aggregator = handlers.AggregatingHandler(target, window=30)
+    logger.addHandler(aggregator)
+    for user in (1, 2, 3):
+        logger.info(f"user {user} logged in")
+    logger.info("server started")
+    aggregator.close()
+
+    summary, single = target.records
+    assert summary.getMessage() == "user <num> logged in (logged 3 times in 30s)"
+    assert summary.aggregate_template == "user <num> logged in"
+    assert single.aggregate_count == 1
@@ -108,11 +108,15 @@ def test_custom_formatter_subclass_falls_back(tmp_path):
     assert _lines(str(log_file)) == ["CUSTOM:world"]
 
 
-def test_brace_style_falls_back(tmp_path):
+def test_brace_style_stays_native(tmp_path):
     log_file = tmp_path / "brace.log"
     handler = handlers.FileHandler(str(log_file))
     handler.setFormatter(_std_logging.Formatter("{levelname} {message}", style="{"))
+    assert handler._native is True
+    # A format spec the Rust parser rejects falls back to Python.
+    handler.setFormatter(_std_logging.Formatter("{msecs:.1f}", style="{"))
     assert handler._native is False
+    handler.setFormatter(_std_logging.Formatter("{levelname} {message}", style="{"))
 
     logger = _rust_logger("p6.brace")
     logger.addHandler(handler)
@@ -0,0 +1,112 @@
+"""
+Tests for file paths that are not valid UTF-8: file handlers, dictConfig, basicConfig
+and watch_config take str, bytes or os.PathLike filenames, and keep undecodable
+names (and their backups) byte for byte.
+"""
+
+import json
+import os
+import sys
+import time
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+pytestmark = pytest.mark.skipif(
+    not sys.platform.startswith("linux"),
+    reason="needs a filesystem that accepts any bytes in names",
+)
+
+NAME = b"caf\xe9.log"
+
+
+@pytest.fixture
+def logger():
+    logger = logging.getLogger("test.non_utf8")
+    logger.setLevel(logging.DEBUG)
+    logger.propagate = False
+    yield logger
+    for handler in list(logger.handlers):
+        logger.removeHandler(handler)
+        handler.close()
+
+
+def _files(directory):
+    return sorted(os.listdir(os.fsencode(directory)))
+
+
+@pytest.mark.parametrize("as_type", [bytes, os.fsdecode, "path"])
+def test_file_handler(logger, tmp_path, as_type):
+    path = os.path.join(os.fsencode(tmp_path), NAME)
+    filename = tmp_path / os.fsdecode(NAME) if as_type == "path" else as_type(path)
+    handler = handlers.FileHandler(filename)
+    logger.addHandler(handler)
+    logger.info("written")
+    handler.flush()
+    with open(path) as f:
+        assert f.read() == "written\n"
+
+
+def test_rotating_backups_keep_the_name(logger, tmp_path):
+    handler = handlers.RotatingFileHandler(
+        os.path.join(os.fsencode(tmp_path), NAME), maxBytes=10, backupCount=2
+    )
+    logger.addHandler(handler)
+    for i in range(4):
+        logger.info("record number %d", i)
+    handler.flush()
+    assert _files(tmp_path) == [NAME, NAME + b".1", NAME + b".2"]
+
+
+def test_timed_rotation_prunes_backups(logger, tmp_path):
+    directory = os.fsencode(tmp_path)
+    stale = [NAME + b".2000-01-01_00-00-0" + str(i).encode() for i in range(3)]
+    for name in stale:
+        open(os.path.join(directory, name), "w").close()
+    handler = handlers.TimedRotatingFileHandler(
+        os.path.join(directory, NAME), when="S", interval=1, backupCount=1
+    )
+    logger.addHandler(handler)
+    logger.info("first")
+    handler.flush()
+    time.sleep(1.2)
+    logger.info("second")
+    handler.flush()
+    files = _files(tmp_path)
+    assert NAME in files and len(files) == 2
+    assert not set(stale) & set(files)
+
+
+def test_dict_config_and_basic_config(tmp_path):
+    directory = os.fsencode(tmp_path)
+    logging.config.dictConfig(
+        {
+            "version": 1,
+            "disable_existing_loggers": False,
+            "handlers": {
+                "out": {
+                    "class": "logging.FileHandler",
+                    "filename": os.path.join(directory, NAME),
+                }
+            },
+            "loggers": {"test.non_utf8.dict": {"handlers": ["out"]}},
+        }
+    )
+    logging.basicConfig(filename=os.path.join(directory, b"basic\xff.log"), force=True)
+    try:
+        assert _files(tmp_path) == [b"basic\xff.log", NAME]
+    finally:
+        logxide.reinitialize()
+
+
+@pytest.mark.skipif(logxide.watch_config is None, reason="built without `watch`")
+def test_watch_config(tmp_path):
+    path = os.path.join(os.fsencode(tmp_path), b"logging\xff.json")
+    config = {"version": 1, "loggers": {"test.non_utf8.watch": {"level": "ERROR"}}}
+    with open(path, "w") as f:
+        json.dump(config, f)
+    with logxide.watch_config(path) as watcher:
+        assert os.fsencode(watcher.path) == path
+        assert logging.getLogger("test.non_utf8.watch").level == logging.ERROR
@@ -254,3 +254,37 @@ def test_public_wrapper_get_metrics_and_flush():
         handler.close()
     finally:
         server.shutdown()
+
+
+def test_block_timeout_drops_after_waiting():
+    # No listener drains the queue, so once both lanes' room is used every
+    # further record waits block_timeout and is then dropped.
+    handler = logxide.QueueHandler(capacity=2, overflow="block", block_timeout=0.05)
+    started = time.monotonic()
+    for i in range(5):
+        handler.emit(_record(i))
+    elapsed = time.monotonic() - started
+
+    metrics = handler.get_metrics()
+    assert metrics["enqueued"] == 2, metrics
+    assert metrics["queue_dropped"] == 3, metrics
+    assert 0.15 <= elapsed < 5, elapsed
+
+
+@pytest.mark.parametrize(
+    "make",
+    [
+        lambda **kw: logxide.StreamHandler(**kw),
+        lambda **kw: logxide.QueueHandler(**kw),
+        lambda **kw: RustHTTPHandler("http://127.0.0.1:9", **kw),
+    ],
+)
+def test_overflow_arguments_are_validated(make):
+    make(overflow="drop_oldest")
+    make(overflow="block", block_timeout=0.5)
+    with pytest.raises(ValueError):
+        make(overflow="drop_everything")
+    with pytest.raises(ValueError):
+        make(overflow="drop_newest", block_timeout=0.5)
+    with pytest.raises(ValueError):
+        make(overflow="block", block_timeout=-1)
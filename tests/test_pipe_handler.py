@@ -0,0 +1,153 @@
+"""
+Tests for PipeHandler: NDJSON records streamed to a subprocess's stdin or a
+named pipe, with restart-on-exit and overflow handling.
+"""
+
+import json
+import os
+import sys
+import threading
+import time
+
+import pytest
+
+from logxide import handlers
+
+# Appends every stdin line to argv[1]; exits after argv[2] lines when given.
+CAT_TO_FILE = """
+import sys
+out, limit = sys.argv[1], int(sys.argv[2]) if len(sys.argv) > 2 else None
+with open(out, "a") as f:
+    for n, line in enumerate(sys.stdin, 1):
+        f.write(line)
+        f.flush()
+        if limit and n >= limit:
+            break
+"""
+
+
+def _lines(path):
+    with open(path) as f:
+        return [json.loads(line) for line in f]
+
+
+def _wait_for(predicate, timeout=10.0):
+    deadline = time.monotonic() + timeout
+    while not predicate() and time.monotonic() < deadline:
+        time.sleep(0.02)
+
+
+def test_records_reach_command_stdin_as_ndjson(tmp_path, isolated_logger):
+    out = tmp_path / "out.jsonl"
+    handler = handlers.PipeHandler([sys.executable, "-c", CAT_TO_FILE, str(out)])
+    logger = isolated_logger(handler)
+    logger.info("first")
+    logger.warning("second", extra={"job": "import"})
+    logger.removeHandler(handler)
+    handler.close()
+
+    first, second = _lines(out)
+    assert first["msg"] == "first"
+    assert second["msg"] == "second"
+    assert second["levelname"] == "WARNING"
+    assert second["extra"]["job"] == "import"
+    metrics = handler.get_metrics()
+    assert metrics["sink_acknowledged"] == 2, metrics
+    assert metrics["restarts"] == 0, metrics
+
+
+def test_command_is_restarted_after_it_exits(tmp_path, isolated_logger):
+    out = tmp_path / "out.jsonl"
+    handler = handlers.PipeHandler(
+        f"{sys.executable} -c '{CAT_TO_FILE}' {out} 1", restart_delay=0.05
+    )
+    logger = isolated_logger(handler)
+    try:
+        for i in range(3):
+            logger.info(f"record-{i}")
+            handler.flush()
+            _wait_for(lambda i=i: out.exists() and len(_lines(out)) > i)
+            # Let the command exit before the next record, which then restarts it.
+            time.sleep(0.3)
+        metrics = handler.get_metrics()
+    finally:
+        logger.removeHandler(handler)
+        handler.close()
+
+    assert [r["msg"] for r in _lines(out)] == ["record-0", "record-1", "record-2"]
+    assert metrics["restarts"] == 2, metrics
+    assert metrics["delivery_failed"] == 0, metrics
+
+
+def test_without_restart_failures_are_counted_and_reported(tmp_path, isolated_logger):
+    errors = []
+    handler = handlers.PipeHandler(
+        [sys.executable, "-c", "pass"], restart=False, error_callback=errors.append
+    )
+    logger = isolated_logger(handler)
+    try:
+        # The first write may still land in the pipe before the command exits.
+        def failed():
+            logger.info("lost")
+            handler.flush()
+            return handler.get_metrics()["delivery_failed"] > 0
+
+        _wait_for(failed)
+        metrics = handler.get_metrics()
+    finally:
+        logger.removeHandler(handler)
+        handler.close()
+
+    assert metrics["delivery_failed"] >= 1, metrics
+    assert metrics["restarts"] == 0, metrics
+    assert errors
+
+
+@pytest.mark.skipif(not hasattr(os, "mkfifo"), reason="needs named pipes")
+def test_named_pipe_target(tmp_path, isolated_logger):
+    fifo = tmp_path / "logs.fifo"
+    os.mkfifo(fifo)
+    received = []
+
+    def reader():
+        with open(fifo) as f:
+            received.extend(json.loads(line)["msg"] for line in f)
+
+    thread = threading.Thread(target=reader, daemon=True)
+    thread.start()
+    handler = handlers.PipeHandler(path=str(fifo))
+    logger = isolated_logger(handler)
+    logger.info("via fifo")
+    logger.removeHandler(handler)
+    handler.close()
+    thread.join(timeout=10)
+
+    assert received == ["via fifo"]
+
+
+def test_stalled_command_applies_overflow_policy(tmp_path, isolated_logger):
+    handler = handlers.PipeHandler(
+        [sys.executable, "-c", "import time; time.sleep(2)"],
+        capacity=4,
+        overflow="drop_newest",
+        shutdown_timeout=0.2,
+        spill_path=str(tmp_path / "pending.jsonl"),
+    )
+    logger = isolated_logger(handler)
+    try:
+        for _ in range(2000):
+            logger.info("x" * 1024)
+        metrics = handler.get_metrics()
+    finally:
+        logger.removeHandler(handler)
+        handler.close()
+
+    assert metrics["queue_dropped"] > 0, metrics
+    assert metrics["max_queue_depth"] <= 2 * 4, metrics
+
+
+def test_exactly_one_target_is_required():
+    with pytest.raises(ValueError):
+        handlers.PipeHandler()
+    with pytest.raises(ValueError):
+        handlers.PipeHandler(["cat"], path="/tmp/x")
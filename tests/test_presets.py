@@ -0,0 +1,78 @@
+"""
+Tests for logxide.preset(): named handler defaults for queue capacity, overflow,
+HTTP batching, flush level and sync level, overridden by explicit arguments.
+"""
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+
+@pytest.fixture(autouse=True)
+def reset():
+    yield
+    logxide.preset()
+
+
+def test_returns_the_settings():
+    settings = logxide.preset("durable")
+    assert settings == {
+        "name": "durable",
+        "capacity": 10000,
+        "overflow": "block",
+        "batch_size": 1000,
+        "flush_interval": 5,
+        "flush_level": logging.NOTSET,
+        "sync_level": logging.ERROR,
+    }
+    assert logxide.preset()["name"] == "default"
+
+
+def test_unknown_preset_is_rejected():
+    with pytest.raises(ValueError, match="low_latency"):
+        logxide.preset("fast")
+
+
+def test_flush_level_of_file_handlers(tmp_path):
+    logxide.preset("max_throughput")
+    fh = handlers.FileHandler(str(tmp_path / "a.log"))
+    assert fh.getFlushLevel() == logging.CRITICAL
+    logxide.preset("durable")
+    rh = handlers.RotatingFileHandler(str(tmp_path / "b.log"))
+    assert rh.getFlushLevel() == logging.NOTSET
+    logxide.preset()
+    assert handlers.FileHandler(str(tmp_path / "c.log")).getFlushLevel() == 40
+    for h in (fh, rh):
+        h.close()
+
+
+def test_sync_level_and_explicit_arguments_win(tmp_path):
+    logxide.preset("durable")
+    durable = handlers.HTTPHandler("http://localhost:1/logs", dry_run=lambda req: None)
+    explicit = handlers.HTTPHandler(
+        "http://localhost:1/logs", dry_run=lambda req: None, sync_level="CRITICAL"
+    )
+    assert durable.getSyncLevel() == logging.ERROR
+    assert explicit.getSyncLevel() == logging.CRITICAL
+    durable.setSyncLevel(None)
+    assert durable.getSyncLevel() is None
+    for h in (durable, explicit):
+        h.close()
+
+
+def test_queue_capacity_and_overflow():
+    logxide.preset("low_latency")
+    queue = handlers.QueueHandler(capacity=1)
+    logger = logging.getLogger("app.presets.overflow")
+    logger.propagate = False
+    logger.addHandler(queue)
+    # No listener: with "block" these calls would wait for room forever.
+    for i in range(5):
+        logger.warning("record %d", i)
+    logger.removeHandler(queue)
+    assert queue._inner.get_metrics()["queue_dropped"] == 4
+
+    logxide.preset("max_throughput")
+    metrics = handlers.QueueHandler()._inner.get_metrics()
+    assert metrics["queue_capacity"] == 2 * 100000
@@ -0,0 +1,75 @@
+"""
+Tests for priority lanes in queued handlers: ERROR and CRITICAL records are
+drained ahead of queued lower-level records, and each lane keeps its order.
+"""
+
+import pytest
+
+from logxide import LogRecord, RustHTTPHandler
+
+
+def _record(i, levelno=20):
+    return LogRecord(
+        name="priority.lanes.test",
+        levelno=levelno,
+        pathname="test.py",
+        lineno=i,
+        msg=f"message-{i}",
+    )
+
+
+@pytest.fixture
+def slow_handler(http_collector):
+    http_collector.delay = 0.02
+    handler = RustHTTPHandler(http_collector.url, batch_size=1, flush_interval=3600)
+    yield handler, http_collector
+    handler.shutdown()
+
+
+def test_errors_overtake_queued_records(slow_handler):
+    handler, collector = slow_handler
+    for i in range(30):
+        handler.emit(_record(i))
+    handler.emit(_record(100, levelno=40))
+    handler.emit(_record(101, levelno=50))
+    handler.flush()
+
+    messages = [r["msg"] for r in collector.records]
+    assert len(messages) == 32, messages
+    # At most a couple of INFO records were already taken by the worker.
+    assert messages.index("message-100") < 5, messages
+    assert messages.index("message-101") == messages.index("message-100") + 1
+
+
+def test_order_preserved_within_lane(slow_handler):
+    handler, collector = slow_handler
+    for i in range(10):
+        handler.emit(_record(i))
+        handler.emit(_record(100 + i, levelno=40))
+    handler.flush()
+
+    lines = [r["lineno"] for r in collector.records]
+    assert [n for n in lines if n < 100] == list(range(10))
+    assert [n for n in lines if n >= 100] == list(range(100, 110))
+
+
+def test_full_normal_lane_does_not_drop_errors(http_collector):
+    http_collector.delay = 0.05
+    handler = RustHTTPHandler(
+        http_collector.url,
+        capacity=2,
+        batch_size=1,
+        flush_interval=3600,
+        overflow="drop_newest",
+    )
+    try:
+        for i in range(20):
+            handler.emit(_record(i))
+        handler.emit(_record(100, levelno=40))
+        handler.flush()
+        metrics = handler.get_metrics()
+    finally:
+        handler.shutdown()
+
+    assert metrics["queue_dropped"] > 0, metrics
+    assert "message-100" in [r["msg"] for r in http_collector.records]
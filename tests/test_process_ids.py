@@ -0,0 +1,83 @@
+"""
+Tests for the process and thread id fields: process, ppid and thread_native_id match
+what the OS reports, in threads and in forked children.
+"""
+
+import io
+import logging as std_logging
+import os
+import threading
+
+import pytest
+
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def memory():
+    logger = logging.getLogger("process_ids_tests")
+    mh = handlers.MemoryHandler()
+    logger.addHandler(mh)
+    yield logger, mh
+    logger.removeHandler(mh)
+
+
+def test_ids_match_the_os(memory):
+    logger, mh = memory
+    logger.warning("main")
+    native_ids = []
+
+    def worker():
+        native_ids.append(threading.get_native_id())
+        logger.warning("worker")
+
+    thread = threading.Thread(target=worker)
+    thread.start()
+    thread.join()
+    main, other = mh.records
+    assert (main.process, main.ppid) == (os.getpid(), os.getppid())
+    assert main.thread_native_id == threading.get_native_id()
+    assert other.thread_native_id == native_ids[0]
+
+
+def test_formatters_render_ids(memory, tmp_path):
+    logger, _ = memory
+    fmt = "%(process)d %(ppid)d %(thread_native_id)d %(message)s"
+    path = tmp_path / "ids.log"
+    native = handlers.FileHandler(str(path))
+    native.setFormatter(logging.Formatter(fmt))
+    stream = io.StringIO()
+    python = std_logging.StreamHandler(stream)
+    python.setFormatter(std_logging.Formatter(fmt))
+    logger.addHandler(native)
+    logger.addHandler(python)
+    try:
+        logger.warning("ids")
+        native.flush()
+    finally:
+        logger.removeHandler(native)
+        logger.removeHandler(python)
+    expected = f"{os.getpid()} {os.getppid()} {threading.get_native_id()} ids\n"
+    assert path.read_text() == expected
+    assert stream.getvalue() == expected
+
+
+@pytest.mark.skipif(not hasattr(os, "fork"), reason="needs os.fork")
+def test_forked_child_reports_its_own_ids(memory):
+    logger, mh = memory
+    logger.warning("parent")
+    read, write = os.pipe()
+    pid = os.fork()
+    if pid == 0:
+        logger.warning("child")
+        record = mh.records[-1]
+        fields = (record.process, record.ppid, record.thread_native_id)
+        expected = (os.getpid(), os.getppid(), threading.get_native_id())
+        os.write(write, b"ok" if fields == expected else repr(fields).encode())
+        os._exit(0)
+    os.close(write)
+    result = os.read(read, 200).decode()
+    os.close(read)
+    os.waitpid(pid, 0)
+    assert result == "ok"
+    assert mh.records[-1].process == os.getpid()
@@ -0,0 +1,75 @@
+"""
+Tests for logger.progress(): throttled progress records with rate and ETA.
+"""
+
+import time
+
+import pytest
+
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def capture(isolated_logger):
+    mh = handlers.MemoryHandler()
+    yield isolated_logger(mh, level=logging.DEBUG), mh
+
+
+def test_every_n_items_with_known_total(capture):
+    logger, mh = capture
+    items = list(logger.progress(range(100), name="batch", every=25))
+
+    assert items == list(range(100))
+    updates = [r for r in mh.records if r.progress_event == "update"]
+    assert [r.progress_count for r in updates] == [25, 50, 75, 100]
+    first = updates[0]
+    assert first.progress == "batch"
+    assert first.progress_total == 100
+    assert isinstance(first.rate, float)
+    assert isinstance(first.eta_seconds, float)
+    assert first.getMessage().startswith("batch: processed 25/100 (25.0%) at ")
+    assert first.levelno == logging.INFO
+
+
+def test_done_summary_logged_once(capture):
+    logger, mh = capture
+    progress = logger.progress(iter(range(5)), every=10)
+    assert list(progress) == list(range(5))
+    assert list(progress) == []
+
+    (done,) = mh.records
+    assert done.progress_event == "done"
+    assert done.progress_count == 5
+    assert progress.count == 5
+    assert done.getMessage().startswith("finished 5 in ")
+
+
+def test_unknown_total_has_no_eta(capture):
+    logger, mh = capture
+    for _ in logger.progress((i for i in range(4)), every=2):
+        pass
+
+    update = mh.records[0]
+    assert update.getMessage().startswith("processed 2 at ")
+    assert not hasattr(update, "progress_total")
+    assert not hasattr(update, "eta_seconds")
+
+
+def test_seconds_throttle(capture):
+    logger, mh = capture
+    for _ in logger.progress(range(4), seconds=0.02):
+        time.sleep(0.012)
+
+    updates = [r for r in mh.records if r.progress_event == "update"]
+    assert 1 <= len(updates) < 4
+
+
+def test_level_and_validation(capture):
+    logger, mh = capture
+    list(logger.progress([1], every=1, level="DEBUG"))
+    assert all(r.levelno == logging.DEBUG for r in mh.records)
+
+    with pytest.raises(ValueError):
+        logger.progress([], every=0)
+    with pytest.raises(ValueError):
+        logger.progress([], seconds=0)
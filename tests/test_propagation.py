@@ -0,0 +1,110 @@
+"""
+Tests for propagation on the native dispatch path: records walk up the logger
+hierarchy, and `propagate: False` from dictConfig stops the walk, so a record is
+written once per configured handler rather than once per level of the hierarchy.
+"""
+
+import logging as std_logging
+
+import pytest
+
+from logxide import RustFormatter, handlers
+from logxide.config import dictConfig
+from logxide.logxide import logging as native_logging
+
+
+@pytest.fixture
+def app(isolated_logger):
+    """A logger name no other test uses."""
+    return isolated_logger.name("test.propagation")
+
+
+def _configure(tmp_path, app, app_propagate, root=True):
+    """Handlers on the root logger and on `<app>`."""
+    config = {
+        "version": 1,
+        "disable_existing_loggers": False,
+        "formatters": {"plain": {"format": "%(name)s:%(message)s"}},
+        "handlers": {
+            "app_file": {
+                "class": "logging.FileHandler",
+                "filename": str(tmp_path / "app.log"),
+                "formatter": "plain",
+            },
+        },
+        "loggers": {
+            app: {"handlers": ["app_file"], "level": "INFO", "propagate": app_propagate}
+        },
+    }
+    if root:
+        config["handlers"]["root_file"] = {
+            "class": "logging.FileHandler",
+            "filename": str(tmp_path / "root.log"),
+            "formatter": "plain",
+        }
+        config["root"] = {"handlers": ["root_file"], "level": "INFO"}
+    dictConfig(config)
+
+
+def _lines(tmp_path, app, name):
+    for logger in (std_logging.getLogger(app), std_logging.root):
+        for handler in logger.handlers:
+            handler.flush()
+    path = tmp_path / name
+    return path.read_text().splitlines() if path.exists() else []
+
+
+def test_propagating_logger_reaches_its_handlers_and_root(tmp_path, app):
+    _configure(tmp_path, app, app_propagate=True)
+    native_logging.getLogger(app).info("both")
+
+    assert _lines(tmp_path, app, "app.log") == [f"{app}:both"]
+    assert _lines(tmp_path, app, "root.log") == [f"{app}:both"]
+
+
+def test_propagate_false_writes_each_line_once(tmp_path, app):
+    _configure(tmp_path, app, app_propagate=False)
+    native_logging.getLogger(app).info("once")
+
+    assert _lines(tmp_path, app, "app.log") == [f"{app}:once"]
+    assert _lines(tmp_path, app, "root.log") == []
+
+
+def test_child_records_walk_up_to_the_configured_ancestor(tmp_path, app):
+    _configure(tmp_path, app, app_propagate=False)
+    # Neither the child nor the intermediate logger has handlers of its own.
+    native_logging.getLogger(f"{app}.db")
+    native_logging.getLogger(f"{app}.db.pool").info("from the pool")
+
+    assert _lines(tmp_path, app, "app.log") == [f"{app}.db.pool:from the pool"]
+    assert _lines(tmp_path, app, "root.log") == []
+
+
+def test_propagate_false_on_an_intermediate_logger_stops_the_walk(tmp_path, app):
+    _configure(tmp_path, app, app_propagate=True)
+    middle = native_logging.getLogger(f"{app}.quiet")
+    middle.propagate = False
+    native_logging.getLogger(f"{app}.quiet.leaf").info("dropped")
+    native_logging.getLogger(f"{app}.loud").info("kept")
+
+    assert _lines(tmp_path, app, "app.log") == [f"{app}.loud:kept"]
+    assert _lines(tmp_path, app, "root.log") == [f"{app}.loud:kept"]
+
+
+def test_child_and_ancestor_handlers_each_get_the_record(tmp_path, app):
+    _configure(tmp_path, app, app_propagate=False, root=False)
+    child = native_logging.getLogger(f"{app}.worker")
+    child_handler = handlers.FileHandler(str(tmp_path / "child.log"))
+    child_handler.setFormatter(RustFormatter("%(name)s:%(message)s"))
+    child.addHandler(child_handler)
+
+    child.info("twice")
+    child.propagate = False
+    child.info("once")
+    child_handler.flush()
+
+    assert _lines(tmp_path, app, "child.log") == [
+        f"{app}.worker:twice",
+        f"{app}.worker:once",
+    ]
+    assert _lines(tmp_path, app, "app.log") == [f"{app}.worker:twice"]
@@ -0,0 +1,98 @@
+"""
+Tests for queue diagnostics on queued handlers: enqueue/dequeue/drop totals,
+the queue-depth high-water mark, flush latency histograms, the process-wide
+queue_stats() snapshot, and periodic reporting on logxide.diagnostics.
+"""
+
+import time
+
+import pytest
+
+import logxide
+from logxide import LogRecord, RustHTTPHandler, handlers, logging
+
+
+def _record(i):
+    return LogRecord(
+        name="queue.diagnostics.test",
+        levelno=20,
+        pathname="test.py",
+        lineno=i,
+        msg=f"message-{i}",
+    )
+
+
+@pytest.fixture
+def http_handler(http_collector):
+    http_collector.delay = 0.02
+    handler = RustHTTPHandler(
+        http_collector.url,
+        capacity=4,
+        batch_size=1,
+        flush_interval=3600,
+        overflow="drop_newest",
+    )
+    yield handler
+    handler.shutdown()
+
+
+def test_queue_counters_balance(http_handler):
+    n = 40
+    for i in range(n):
+        http_handler.emit(_record(i))
+    http_handler.flush()
+
+    metrics = http_handler.get_metrics()
+    assert metrics["enqueued"] + metrics["queue_dropped"] == n, metrics
+    assert metrics["dequeued"] == metrics["enqueued"], metrics
+    assert metrics["queue_depth"] == 0, metrics
+    # capacity=4 bounds each of the two lanes.
+    assert metrics["queue_capacity"] == 8, metrics
+    assert 1 <= metrics["max_queue_depth"] <= 4, metrics
+
+
+def test_flush_latency_histogram(http_handler):
+    for _ in range(3):
+        http_handler.emit(_record(0))
+        http_handler.flush()
+
+    histogram = http_handler.get_metrics()["flush_latency_ms"]
+    assert sum(histogram.values()) == 3
+    assert "<=1" in histogram and ">5000" in histogram
+
+
+def test_queue_stats_lists_live_handlers(http_handler):
+    http_handler.emit(_record(0))
+    http_handler.flush()
+    kinds = [stats["kind"] for stats in logxide.queue_stats()]
+    assert "http" in kinds
+
+
+def test_stream_handler_metrics():
+    handler = handlers.StreamHandler()
+    metrics = handler.get_metrics()
+    assert metrics["queue_capacity"] == 2 * 8192
+    assert metrics["queue_dropped"] == 0
+
+
+def test_periodic_diagnostics_logging(http_handler):
+    diagnostics = logging.getLogger("logxide.diagnostics")
+    diagnostics.setLevel(logging.DEBUG)
+    mh = handlers.MemoryHandler()
+    diagnostics.addHandler(mh)
+    try:
+        logxide.configure_queue_diagnostics(interval=0.05)
+        deadline = time.monotonic() + 5
+        while not mh.records and time.monotonic() < deadline:
+            time.sleep(0.02)
+    finally:
+        logxide.configure_queue_diagnostics(None)
+        diagnostics.removeHandler(mh)
+
+    assert mh.records
+    assert "queue: depth=" in mh.records[0].getMessage()
+
+
+def test_diagnostics_rejects_bad_interval():
+    with pytest.raises(ValueError):
+        logxide.configure_queue_diagnostics(interval=0)
@@ -0,0 +1,190 @@
+"""
+Tests for QueueHandler / QueueListener: records queued by the logging call and
+delivered to native and Python handlers on the listener's thread.
+"""
+
+import io
+import logging as std_logging
+import logging.handlers as std_handlers
+import threading
+
+import pytest
+
+from logxide import handlers, logging
+
+
+def _stream(level=std_logging.NOTSET):
+    buffer = io.StringIO()
+    handler = std_logging.StreamHandler(buffer)
+    handler.setLevel(level)
+    handler.setFormatter(std_logging.Formatter("%(levelname)s %(message)s %(user)s"))
+    return handler, buffer
+
+
+def test_records_reach_native_and_python_handlers(isolated_logger):
+    queue = handlers.QueueHandler()
+    memory = handlers.MemoryHandler()
+    stream, buffer = _stream()
+    listener = handlers.QueueListener(queue, memory, stream)
+    listener.start()
+    logger = isolated_logger(queue, level=logging.DEBUG)
+    for i in range(50):
+        logger.info("record %d", i, extra={"user": i})
+    listener.stop()
+
+    assert [r.getMessage() for r in memory.records] == [
+        f"record {i}" for i in range(50)
+    ]
+    assert buffer.getvalue().splitlines() == [
+        f"INFO record {i} {i}" for i in range(50)
+    ]
+    metrics = queue.get_metrics()
+    assert metrics["emitted"] == metrics["enqueued"] == metrics["dequeued"] == 50
+
+
+def test_logging_does_not_wait_for_the_handlers(isolated_logger):
+    release = threading.Event()
+    seen = []
+
+    class Slow(std_logging.Handler):
+        def emit(self, record):
+            release.wait(10)
+            seen.append(record.getMessage())
+
+    queue = handlers.QueueHandler()
+    listener = handlers.QueueListener(queue, Slow())
+    listener.start()
+    logger = isolated_logger(queue, level=logging.DEBUG)
+    for i in range(3):
+        logger.warning("slow %d", i)
+    assert seen == []
+
+    release.set()
+    listener.stop()
+    assert seen == ["slow 0", "slow 1", "slow 2"]
+
+
+@pytest.mark.parametrize("respect", [False, True])
+def test_respect_handler_level(respect, isolated_logger):
+    queue = handlers.QueueHandler()
+    stream, buffer = _stream(std_logging.WARNING)
+    listener = handlers.QueueListener(queue, stream, respect_handler_level=respect)
+    listener.start()
+    logger = isolated_logger(queue, level=logging.DEBUG)
+    logger.info("quiet", extra={"user": 1})
+    logger.error("loud", extra={"user": 2})
+    listener.stop()
+
+    expected = {"ERROR loud 2"} if respect else {"INFO quiet 1", "ERROR loud 2"}
+    # ERROR and above travel on the priority lane, so may arrive first.
+    assert set(buffer.getvalue().splitlines()) == expected
+
+
+def test_records_queued_before_start_are_delivered(isolated_logger):
+    queue = handlers.QueueHandler()
+    memory = handlers.MemoryHandler()
+    listener = handlers.QueueListener(queue, memory)
+    logger = isolated_logger(queue, level=logging.DEBUG)
+    logger.info("early")
+    queue.flush()  # no listener yet: returns at once
+    assert memory.records == []
+
+    listener.start()
+    queue.flush()
+    assert [r.getMessage() for r in memory.records] == ["early"]
+    listener.stop()
+
+
+def test_flush_waits_for_delivery(isolated_logger):
+    queue = handlers.QueueHandler()
+    memory = handlers.MemoryHandler()
+    with handlers.QueueListener(queue, memory) as listener:
+        assert listener.running
+        logger = isolated_logger(queue, level=logging.DEBUG)
+        for i in range(200):
+            logger.debug("n%d", i)
+        queue.flush()
+        assert len(memory.records) == 200
+    assert not listener.running
+
+
+def test_overflow_drops_when_no_listener_drains(isolated_logger):
+    queue = handlers.QueueHandler(capacity=5, overflow="drop_newest")
+    logger = isolated_logger(queue, level=logging.DEBUG)
+    for i in range(8):
+        logger.info("n%d", i)
+
+    metrics = queue.get_metrics()
+    assert metrics["enqueued"] == 5
+    assert metrics["queue_dropped"] == 3
+    # The ERROR lane has room for five more.
+    assert metrics["queue_capacity"] == 10
+
+
+def test_drop_oldest_keeps_flush_requests(isolated_logger):
+    release = threading.Event()
+
+    class Blocked(std_logging.Handler):
+        def emit(self, record):
+            release.wait(10)
+
+    queue = handlers.QueueHandler(capacity=2, overflow="drop_oldest")
+    listener = handlers.QueueListener(queue, Blocked())
+    listener.start()
+    logger = isolated_logger(queue, level=logging.DEBUG)
+    logger.info("held")
+    flushed = threading.Event()
+    flusher = threading.Thread(target=lambda: (queue.flush(), flushed.set()))
+    flusher.start()
+    for i in range(10):
+        logger.info("n%d", i)
+    release.set()
+
+    # The flush request was not evicted by the records that followed it.
+    assert flushed.wait(5)
+    flusher.join()
+    listener.stop()
+    assert not listener.running
+    assert queue.get_metrics()["queue_dropped"] > 0
+
+
+def test_queue_handler_level_and_filters(isolated_logger):
+    queue = handlers.QueueHandler()
+    queue.setLevel(logging.INFO)
+    queue.addFilter(lambda record: "secret" not in record.getMessage())
+    memory = handlers.MemoryHandler()
+    listener = handlers.QueueListener(queue, memory)
+    listener.start()
+    logger = isolated_logger(queue, level=logging.DEBUG)
+    logger.debug("too low")
+    logger.info("secret token")
+    logger.info("kept")
+    listener.stop()
+
+    assert [r.getMessage() for r in memory.records] == ["kept"]
+
+
+def test_start_twice_and_stop_twice():
+    queue = handlers.QueueHandler()
+    listener = handlers.QueueListener(queue)
+    listener.start()
+    with pytest.raises(RuntimeError):
+        listener.start()
+    listener.stop()
+    listener.stop()
+    listener.start()
+    listener.stop()
+
+
+def test_stdlib_types_and_arguments():
+    queue = handlers.QueueHandler()
+    listener = handlers.QueueListener(queue, respect_handler_level=True)
+
+    assert isinstance(queue, std_handlers.QueueHandler)
+    assert isinstance(listener, std_handlers.QueueListener)
+    assert listener.handlers == ()
+    assert listener.respect_handler_level
+    with pytest.raises(TypeError):
+        handlers.QueueListener(std_handlers.QueueHandler(None))
+    with pytest.raises(ValueError):
+        handlers.QueueHandler(capacity=0)
@@ -0,0 +1,121 @@
+"""
+Tests for reading stdlib-formatted log files back into LogRecords and replaying them
+through the handlers configured now.
+"""
+
+import logging as std_logging
+import os
+import subprocess
+import sys
+
+import pytest
+
+import logxide
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+FORMAT = "%(asctime)s %(levelname)s %(name)s [%(request_id)s] %(message)s"
+
+PATTERN = (
+    r"(?P<asctime>\S+ \S+) (?P<levelname>[A-Z]+) (?P<name>\S+) "
+    r"\[(?P<request_id>[^\]]*)\] (?P<message>.*)"
+)
+
+APP_LOG = """\
+2024-05-01 12:00:00,123 INFO app.web [r-1] started
+2024-05-01 12:00:02,000 ERROR app.db [r-2] failed
+Traceback (most recent call last):
+ValueError: boom
+2024-05-01 12:00:03,500 DEBUG app.web [r-3] details
+"""
+
+
+class ListHandler(std_logging.Handler):
+    def __init__(self):
+        super().__init__()
+        self.records = []
+
+    def emit(self, record):
+        self.records.append(record)
+
+
+@pytest.fixture
+def app_log(tmp_path):
+    path = tmp_path / "app.log"
+    path.write_text(APP_LOG)
+    return path
+
+
+def test_format_fields_become_record_fields(app_log):
+    records = logxide.read_log_file(app_log, format=FORMAT)
+    assert [(r.levelname, r.levelno, r.name, r.msg) for r in records] == [
+        ("INFO", 20, "app.web", "started"),
+        ("ERROR", 40, "app.db", "failed"),
+        ("DEBUG", 10, "app.web", "details"),
+    ]
+    assert [r.request_id for r in records] == ["r-1", "r-2", "r-3"]
+    assert records[0].msecs == 123
+    assert records[2].created - records[0].created == pytest.approx(3.377)
+    assert records[1].exc_text.endswith("ValueError: boom")
+    assert records[0].exc_text is None
+
+
+def test_pattern_reads_the_same_records(app_log):
+    by_format = logxide.read_log_file(app_log, format=FORMAT)
+    by_pattern = logxide.read_log_file(str(app_log), pattern=PATTERN)
+    assert by_pattern == by_format
+    assert [r.created for r in by_pattern] == [r.created for r in by_format]
+
+
+def test_bad_patterns_raise(app_log):
+    with pytest.raises(ValueError, match="no named groups"):
+        logxide.read_log_file(app_log, pattern=r"\S+ .*")
+    with pytest.raises(ValueError, match="invalid log pattern"):
+        logxide.read_log_file(app_log, pattern=r"(?P<message>")
+
+
+def test_replay_goes_through_current_handlers(app_log):
+    handler = ListHandler()
+    logger = logxide.logging.getLogger("app")
+    logger.setLevel(logxide.logging.INFO)
+    logger.addHandler(handler)
+    try:
+        replayed = logxide.replay_log_file(app_log, format=FORMAT)
+    finally:
+        logger.removeHandler(handler)
+        logger.setLevel(logxide.logging.NOTSET)
+    assert replayed == 2
+    assert [(r.name, r.getMessage()) for r in handler.records] == [
+        ("app.web", "started"),
+        ("app.db", "failed"),
+    ]
+    assert handler.records[0].request_id == "r-1"
+    assert handler.records[1].exc_text.endswith("ValueError: boom")
+
+
+def test_replay_into_a_given_logger(app_log):
+    handler = ListHandler()
+    logger = logxide.logging.getLogger("replayed")
+    logger.setLevel(logxide.logging.DEBUG)
+    logger.addHandler(handler)
+    logger.propagate = False
+    try:
+        replayed = logxide.replay_log_file(app_log, logger="replayed", pattern=PATTERN)
+    finally:
+        logger.removeHandler(handler)
+        logger.setLevel(logxide.logging.NOTSET)
+        logger.propagate = True
+    assert replayed == 3
+    assert [r.name for r in handler.records] == ["app.web", "app.db", "app.web"]
+
+
+def test_cli_pattern_option(app_log):
+    result = subprocess.run(
+        [sys.executable, "-m", "logxide", "stats", str(app_log), "--pattern", PATTERN],
+        env={**os.environ, "PYTHONPATH": REPO_ROOT},
+        capture_output=True,
+        text=True,
+        timeout=60,
+    )
+    assert result.returncode == 0, result.stderr
+    assert result.stdout.startswith("3 records")
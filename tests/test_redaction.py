@@ -0,0 +1,144 @@
+"""
+Tests for logxide.Redactor: built-in detectors and regex rules replace personal data
+in messages, arguments and extras, globally with configure_redaction() or on one
+handler's copy of the record with addFilter().
+"""
+
+import json
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def capture(isolated_logger):
+    mh = handlers.MemoryHandler()
+    logger = isolated_logger(mh, name="app.redact", level=logging.DEBUG)
+    yield logger, mh
+    for f in logger.filters:
+        logger.removeFilter(f)
+    logxide.configure_redaction()
+
+
+@pytest.mark.parametrize(
+    "text, redacted",
+    [
+        ("mail ann@example.com now", "mail [REDACTED] now"),
+        ("card 4111 1111 1111 1111.", "card [REDACTED]."),
+        ("card 4111-1111-1111-1112", "card 4111-1111-1111-1112"),
+        ("ssn 123-45-6789", "ssn [REDACTED]"),
+        ("ssn 666-45-6789", "ssn 666-45-6789"),
+        ("Authorization: bearer abc.DEF-123=", "Authorization: bearer [REDACTED]"),
+        ("order 12345 shipped", "order 12345 shipped"),
+    ],
+)
+def test_detectors(text, redacted):
+    assert logxide.Redactor().redact(text) == redacted
+
+
+def test_rules_detectors_and_replacement():
+    redactor = logxide.Redactor(
+        detectors=["ssn"], rules=[r"password=(\S+)", r"sk_live_\w+"], replacement="***"
+    )
+    assert redactor.detectors == ["ssn"]
+    text = "password=hunter2 key sk_live_abc ann@example.com 123-45-6789"
+    assert redactor.redact(text) == "password=*** key *** ann@example.com ***"
+    with pytest.raises(ValueError, match="email"):
+        logxide.Redactor(detectors=["phone"])
+    with pytest.raises(ValueError, match="invalid redaction rule"):
+        logxide.Redactor(rules=["("])
+
+
+def test_global_redaction(capture):
+    logger, mh = capture
+    logxide.configure_redaction(logxide.Redactor())
+    logger.warning(
+        "login %s",
+        "ann@example.com",
+        extra={"user": {"emails": ["bob@example.org"], "id": 7}, "card": 4111},
+    )
+    record = mh.records[-1]
+    assert record.getMessage() == "login [REDACTED]"
+    assert record.user == {"emails": ["[REDACTED]"], "id": 7}
+    assert record.card == 4111
+    logxide.configure_redaction()
+    logger.warning("login %s", "ann@example.com")
+    assert mh.records[-1].getMessage() == "login ann@example.com"
+
+
+def test_handler_redacts_its_own_copy(capture, tmp_path):
+    logger, mh = capture
+    redacted = handlers.FileHandler(str(tmp_path / "redacted.log"))
+    redacted.addFilter(logxide.Redactor())
+    assert redacted._native
+    logger.addHandler(redacted)
+    logger.info("reset sent to %s", "ann@example.com")
+    redacted.flush()
+    logger.removeHandler(redacted)
+    redacted.close()
+    assert (tmp_path / "redacted.log").read_text() == "reset sent to [REDACTED]\n"
+    assert mh.records[-1].getMessage() == "reset sent to ann@example.com"
+
+
+
+def _shipped(path):
+    lines = path.read_text().splitlines()
+    return [record for line in lines for record in json.loads(line)["body"]]
+
+
+@pytest.mark.parametrize("python_filter", [False, True])
+def test_http_handler_redacts_what_it_ships(capture, tmp_path, python_filter):
+    logger, mh = capture
+    path = tmp_path / "requests.jsonl"
+    shipped = handlers.HTTPHandler("http://collector.invalid", dry_run=str(path))
+    shipped.addFilter(logxide.Redactor())
+    if python_filter:
+        # Sends the records through handle(), where the redactor runs in Python.
+        shipped.addFilter(lambda record: True)
+    assert shipped._inner.isNative() != python_filter
+    logger.addHandler(shipped)
+    logger.info("reset sent to %s", "ann@example.com", extra={"to": "bob@example.org"})
+    shipped.flush()
+    logger.removeHandler(shipped)
+    shipped.close()
+    (record,) = _shipped(path)
+    assert record["msg"] % tuple(record["args"] or ()) == "reset sent to [REDACTED]"
+    assert record["extra"]["to"] == "[REDACTED]"
+    assert "example" not in path.read_text()
+    assert mh.records[-1].getMessage() == "reset sent to ann@example.com"
+
+
+def test_memory_handler_redacts_its_own_copy(capture):
+    logger, mh = capture
+    redacted = handlers.MemoryHandler()
+    redacted.addFilter(logxide.Redactor())
+    logger.addHandler(redacted)
+    logger.info("reset sent to %s", "ann@example.com")
+    logger.removeHandler(redacted)
+    assert [t[2] for t in redacted.record_tuples] == ["reset sent to [REDACTED]"]
+    assert "ann@example.com" not in redacted.text
+    assert mh.records[-1].getMessage() == "reset sent to ann@example.com"
+
+class _ListHandler(logging.Handler):
+    def __init__(self):
+        super().__init__()
+        self.records = []
+
+    def emit(self, record):
+        self.records.append((record.getMessage(), record.token))
+
+
+def test_python_handler_and_logger_filter(capture):
+    logger, mh = capture
+    handler = _ListHandler()
+    handler.addFilter(logxide.Redactor())
+    logger.addHandler(handler)
+    logger.info("for %s", "ann@example.com", extra={"token": "Bearer abc"})
+    logger.removeHandler(handler)
+    assert handler.records == [("for [REDACTED]", "Bearer [REDACTED]")]
+
+    logger.addFilter(logxide.Redactor(detectors=["ssn"]))
+    logger.info("ssn 123-45-6789")
+    assert mh.records[-1].getMessage() == "ssn [REDACTED]"
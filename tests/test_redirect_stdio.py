@@ -0,0 +1,71 @@
+"""
+Tests for redirect_stdio: lines printed to stdout and stderr become records, handlers
+writing to the redirected streams don't loop, and the original streams come back.
+"""
+
+import io
+import logging as std_logging
+import sys
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def redirected():
+    saved = sys.stdout, sys.stderr
+    sys.stdout, sys.stderr = io.StringIO(), io.StringIO()
+    originals = sys.stdout, sys.stderr
+    out, err = logging.getLogger("stdout"), logging.getLogger("stderr")
+    mh = handlers.MemoryHandler()
+    for logger in (out, err):
+        logger.addHandler(mh)
+        logger.setLevel(logging.INFO)
+    logxide.redirect_stdio()
+    yield mh, originals
+    logxide.redirect_stdio(enabled=False)
+    for logger in (out, err):
+        logger.removeHandler(mh)
+        logger.setLevel(logging.NOTSET)
+    sys.stdout, sys.stderr = saved
+
+
+def test_printed_lines_become_records(redirected):
+    mh, originals = redirected
+    print("hello", "world")
+    print()
+    print("two\nlines", end="")
+    print("progress 50%", file=sys.stderr)
+    sys.stdout.flush()
+    assert [(r.name, r.levelno, r.getMessage(), r.stdio) for r in mh.records] == [
+        ("stdout", logging.INFO, "hello world", "stdout"),
+        ("stdout", logging.INFO, "two", "stdout"),
+        ("stderr", logging.ERROR, "progress 50%", "stderr"),
+        ("stdout", logging.INFO, "lines", "stdout"),
+    ]
+    assert originals[0].getvalue() == originals[1].getvalue() == ""
+
+
+def test_handlers_writing_to_the_streams_do_not_loop(redirected):
+    _, (original_out, _) = redirected
+    handler = std_logging.StreamHandler(sys.stdout)
+    handler.setFormatter(std_logging.Formatter("[%(name)s] %(message)s"))
+    logger = logging.getLogger("stdout")
+    logger.addHandler(handler)
+    try:
+        print("once")
+    finally:
+        logger.removeHandler(handler)
+    assert original_out.getvalue() == "[stdout] once\n"
+
+
+def test_disabling_restores_the_original_streams(redirected):
+    mh, (original_out, original_err) = redirected
+    assert sys.stdout is not original_out
+    logxide.redirect_stdio(enabled=False)
+    assert (sys.stdout, sys.stderr) == (original_out, original_err)
+    print("plain")
+    assert original_out.getvalue() == "plain\n"
+    assert mh.records == []
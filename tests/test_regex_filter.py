@@ -0,0 +1,99 @@
+"""
+Tests for logxide.RegexFilter: a regex over msg, name or an extra field, in include
+or exclude mode, on loggers (run in Rust) and handlers.
+"""
+
+import logging as std_logging
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def capture(isolated_logger):
+    mh = handlers.MemoryHandler()
+    logger = isolated_logger(mh, name="app.regex", level=logging.DEBUG)
+    yield logger, mh
+    for f in logger.filters:
+        logger.removeFilter(f)
+
+
+def _messages(mh):
+    return [r.getMessage() for r in mh.records]
+
+
+def test_exclude_drops_matching_messages(capture):
+    logger, mh = capture
+    logger.addFilter(logxide.RegexFilter(r"^GET /health", mode="exclude"))
+    logger.info("GET /health 200")
+    logger.info("GET /users %d", 200)
+    logger.log_batch(logging.INFO, ["GET /health 200", "POST /users 201"])
+    assert _messages(mh) == ["GET /users 200", "POST /users 201"]
+
+
+def test_include_matches_the_unformatted_message(capture):
+    logger, mh = capture
+    logger.addFilter(logxide.RegexFilter(r"%s failed"))
+    logger.info("%s failed", "job")
+    logger.info("job failed")
+    assert _messages(mh) == ["job failed"]
+
+
+def test_name_field(capture):
+    logger, mh = capture
+    logger.addFilter(logxide.RegexFilter(r"\.regex\.\d+$", field="name"))
+    logger.info("kept")
+    logger.addFilter(logxide.RegexFilter("^other", field="name"))
+    logger.info("dropped")
+    assert _messages(mh) == ["kept"]
+
+
+def test_extra_field(capture):
+    logger, mh = capture
+    logger.addFilter(logxide.RegexFilter("^(prod|staging)$", field="env"))
+    logger.info("prod", extra={"env": "prod"})
+    logger.info("dev", extra={"env": "dev"})
+    logger.info("missing")
+    assert _messages(mh) == ["prod"]
+
+
+def test_non_string_extras_match_as_json(capture):
+    logger, mh = capture
+    logger.addFilter(
+        logxide.RegexFilter(r'"status":5\d\d', field="http", mode="exclude")
+    )
+    logger.info("ok", extra={"http": {"status": 200}})
+    logger.info("error", extra={"http": {"status": 503}})
+    logger.info("no http")
+    assert _messages(mh) == ["ok", "no http"]
+
+
+def test_on_a_handler(isolated_logger):
+    queue = handlers.QueueHandler()
+    queue.addFilter(logxide.RegexFilter("health", mode="exclude"))
+    memory = handlers.MemoryHandler()
+    listener = handlers.QueueListener(queue, memory)
+    listener.start()
+    logger = isolated_logger(queue, name="app.regex")
+    logger.info("health check")
+    logger.info("request")
+    listener.stop()
+    assert _messages(memory) == ["request"]
+
+
+def test_filter_on_a_stdlib_record():
+    record = std_logging.LogRecord("svc", 20, __file__, 1, "GET /health", None, None)
+    assert logxide.RegexFilter("health").filter(record) is True
+    assert logxide.RegexFilter("health", mode="exclude").filter(record) is False
+    assert logxide.RegexFilter(".", field="missing").filter(record) is False
+
+
+def test_attributes_and_errors():
+    f = logxide.RegexFilter("a+", field="name", mode="exclude")
+    assert (f.pattern, f.field, f.mode) == ("a+", "name", "exclude")
+    with pytest.raises(ValueError):
+        logxide.RegexFilter("(")
+    with pytest.raises(ValueError):
+        logxide.RegexFilter("a", mode="drop")
@@ -0,0 +1,148 @@
+"""
+Tests for re-import safety and logxide.reinitialize(). Each scenario runs in a fresh
+interpreter, since both act on process-wide state.
+"""
+
+import os
+import subprocess
+import sys
+import textwrap
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+REIMPORT = """
+def reimport():
+    for name in [m for m in sys.modules if m == "logxide" or m.startswith("logxide.")]:
+        del sys.modules[name]
+    return importlib.import_module("logxide")
+"""
+
+
+def _run(script):
+    return subprocess.run(
+        [sys.executable, "-c", REIMPORT + textwrap.dedent(script)],
+        env={**os.environ, "PYTHONPATH": REPO_ROOT},
+        capture_output=True,
+        text=True,
+        check=True,
+    ).stdout.splitlines()
+
+
+def test_reimport_keeps_basic_config_and_atexit_hooks():
+    out = _run(
+        """
+        import atexit
+        import importlib
+        import sys
+
+        import logxide
+
+        callbacks = atexit._ncallbacks()
+        logxide.basicConfig(level=logxide.INFO, format="%(message)s", stream=sys.stdout)
+        logxide = reimport()
+        logxide.basicConfig(level=logxide.INFO, format="%(message)s", stream=sys.stdout)
+        logxide.getLogger("app").info("once")
+        logxide.flush()
+        print(atexit._ncallbacks() - callbacks)
+        """
+    )
+    assert out == ["once", "0"]
+
+
+def test_reimported_package_stops_the_previous_diagnostics_reporter():
+    out = _run(
+        """
+        import importlib
+        import sys
+        import threading
+
+        import logxide
+
+        logxide.configure_queue_diagnostics(60)
+        logxide = reimport()
+        logxide.configure_queue_diagnostics(None)
+        print([t.name for t in threading.enumerate() if t.name == "logxide-diagnostics"])
+        """
+    )
+    assert out == ["[]"]
+
+
+def test_reinitialize_resets_loggers_and_allows_basic_config_again():
+    out = _run(
+        """
+        import importlib
+        import logging
+        import sys
+
+        import logxide
+
+        logxide.basicConfig(level=logging.INFO, format="first:%(message)s", stream=sys.stdout)
+        app = logging.getLogger("app")
+        app.setLevel(logging.DEBUG)
+        app.addHandler(logxide.StreamHandler(sys.stdout))
+        app.info("before")
+        logxide.flush()
+
+        generation = logxide.reinitialize()
+        print(generation, app.level, app.propagate, app.handlers, logging.root.handlers)
+        print(logging.root.level, app.getEffectiveLevel())
+
+        logxide.basicConfig(level=logging.INFO, format="second:%(message)s", stream=sys.stdout)
+        app.info("after")
+        logxide.flush()
+        print(logxide.reinitialize() - generation)
+        """
+    )
+    # Two queued handlers write to stdout, in either order.
+    assert sorted(out[:2]) == ["before", "first:before"]
+    assert out[2:] == [
+        "1 0 True [] []",
+        "30 30",
+        "second:after",
+        "1",
+    ]
+
+
+def test_reinitialize_retires_background_threads(tmp_path):
+    config = tmp_path / "logging.json"
+    config.write_text('{"version": 1, "disable_existing_loggers": false}')
+    out = _run(
+        f"""
+        import threading
+
+        import logxide
+
+        watcher = logxide.watch_config({str(config)!r})
+        logxide.configure_queue_diagnostics(60)
+        logxide.reinitialize()
+        print(watcher.running)
+        print([t.name for t in threading.enumerate() if t.name.startswith("logxide-")])
+        """
+    )
+    assert out == ["False", "[]"]
+
+
+def test_hooks_and_streams_survive_reimport_until_reinitialize():
+    out = _run(
+        """
+        import importlib
+        import sys
+        import threading
+
+        import logxide
+
+        logxide.capture_unhandled()
+        logxide = reimport()
+        logxide.capture_unhandled()
+        stdout = sys.stdout
+        logxide.redirect_stdio()
+        logxide = reimport()
+        logxide.redirect_stdio(logger="app")
+        print(sys.excepthook.__qualname__, file=sys.__stdout__)
+        logxide.reinitialize()
+        print(sys.excepthook is sys.__excepthook__)
+        print(threading.excepthook is threading.__excepthook__)
+        print(sys.stdout is stdout)
+        """
+    )
+    assert out == ["_hooks.<locals>.excepthook", "True", "True", "True"]
@@ -0,0 +1,80 @@
+"""
+Tests for the `timestamps` formatter option (elapsed and humanized %(asctime)s) and
+for relativeCreated on natively built records.
+"""
+
+import logging as std_logging
+import re
+import time
+
+import pytest
+
+from logxide import ColorFormatter, ColumnFormatter, RustFormatter, handlers
+from logxide.logxide import logging as native_logging
+
+
+def _record(created, relative_created=0.0):
+    record = native_logging.LogRecord("app", 20, "", 0, "hello")
+    record.levelname = "INFO"
+    record.created = created
+    record.relative_created = relative_created
+    return record
+
+
+def test_relative_is_seconds_since_start():
+    fmt = RustFormatter("%(asctime)s %(message)s", timestamps="relative")
+
+    assert fmt.format(_record(0.0, 12345.6789)) == "+12.346s hello"
+    assert fmt.format(_record(0.0, 0.0)) == "+0.000s hello"
+
+
+def test_delta_humanizes_the_gap_to_the_previous_record():
+    fmt = RustFormatter("%(asctime)s", timestamps="delta")
+    # The first record counts from logging start; anything before it shows +0ms.
+    assert fmt.format(_record(1000.0)) == "+0ms"
+
+    shown = []
+    created = 1000.0
+    for gap in (0.35, 2.46, 185.0, 3725.0):
+        created += gap
+        shown.append(fmt.format(_record(created)))
+    assert shown == ["+350ms", "+2.5s", "+3m05s", "+1h02m"]
+
+
+def test_column_and_color_formatters_accept_timestamps():
+    column = ColumnFormatter(timestamps="relative", level_width=5, name_width=4)
+    assert column.format(_record(0.0, 1500.0)) == "+1.500s INFO  app  hello"
+
+    color = ColorFormatter("%(asctime)s %(message)s", timestamps="relative")
+    assert color.format(_record(0.0, 1500.0)) == "+1.500s hello"
+
+
+def test_absolute_is_the_default_and_unknown_names_are_rejected():
+    record = _record(1720000000.0)
+    assert RustFormatter("%(asctime)s", "%Y").format(record) == time.strftime(
+        "%Y", time.localtime(1720000000.0)
+    )
+    with pytest.raises(ValueError):
+        RustFormatter("%(asctime)s", timestamps="elapsed")
+
+
+def test_native_records_carry_relative_created(tmp_path, isolated_logger):
+    path = tmp_path / "relative.log"
+    handler = handlers.FileHandler(str(path))
+    handler.setFormatter(
+        RustFormatter("%(asctime)s %(relativeCreated)s", timestamps="relative")
+    )
+    logger = native_logging.getLogger(isolated_logger.name("test.relative"))
+    logger.propagate = False
+    logger.setLevel(std_logging.INFO)
+    logger.addHandler(handler)
+
+    logger.info("tick")
+    expected_ms = (time.time() - std_logging._startTime) * 1000
+    handler.flush()
+
+    match = re.fullmatch(r"\+(\d+\.\d{3})s ([\d.]+)\n", path.read_text())
+    assert match
+    seconds, millis = float(match.group(1)), float(match.group(2))
+    assert abs(seconds * 1000 - millis) <= 1
+    assert 0 < millis <= expected_ms
@@ -0,0 +1,108 @@
+"""
+Tests for rotating a log file that a reader keeps open. On Windows such a file cannot
+be renamed (Python's open() does not share delete access, like most log shippers and
+tail tools), so the handlers fall back to copy-truncate: the backup gets a copy and
+the file is emptied in place. Elsewhere the rename goes through; the results match.
+When neither works, the backups are left as they were and logging keeps appending.
+"""
+
+import os
+import time
+
+import pytest
+
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def logger():
+    logger = logging.getLogger("test.rotation.locked")
+    logger.setLevel(logging.DEBUG)
+    logger.propagate = False
+    yield logger
+    for handler in list(logger.handlers):
+        logger.removeHandler(handler)
+        handler.close()
+
+
+def _read(path):
+    with open(path) as f:
+        return f.read().splitlines()
+
+
+def test_size_rotation_copies_and_truncates(logger, tmp_path):
+    log_file = str(tmp_path / "app.log")
+    handler = handlers.RotatingFileHandler(log_file, maxBytes=20, backupCount=2)
+    logger.addHandler(handler)
+    logger.info("before rotation #1")
+    handler.flush()
+    with open(log_file):
+        logger.info("triggers rotation")
+        handler.flush()
+    assert _read(log_file + ".1") == ["before rotation #1"]
+    assert _read(log_file) == ["triggers rotation"]
+
+
+def test_timed_rotation_copies_and_truncates(logger, tmp_path):
+    log_file = str(tmp_path / "app.log")
+    handler = handlers.TimedRotatingFileHandler(
+        log_file, when="S", interval=1, backupCount=5
+    )
+    logger.addHandler(handler)
+    logger.info("first period")
+    handler.flush()
+    with open(log_file):
+        time.sleep(1.2)
+        logger.info("second period")
+        handler.flush()
+    backups = [f for f in os.listdir(tmp_path) if f != "app.log"]
+    assert len(backups) == 1
+    assert _read(tmp_path / backups[0]) == ["first period"]
+    assert _read(log_file) == ["second period"]
+
+
+def _block_rotation(log_file):
+    # The handlers move the file to `<file>.rotating` first; a directory there makes
+    # both the rename and the copy fail, as a locked file on a full disk would.
+    os.mkdir(log_file + ".rotating")
+
+
+def test_failed_size_rotation_keeps_backups(logger, tmp_path):
+    log_file = str(tmp_path / "app.log")
+    for i in (1, 2):
+        with open(f"{log_file}.{i}", "w") as f:
+            f.write(f"old {i}\n")
+    _block_rotation(log_file)
+    handler = handlers.RotatingFileHandler(log_file, maxBytes=20, backupCount=2)
+    logger.addHandler(handler)
+    logger.info("before rotation #1")
+    logger.info("rotation fails")
+    logger.info("still appending")
+    handler.flush()
+    lines = ["before rotation #1", "rotation fails", "still appending"]
+    assert _read(log_file) == lines
+    assert _read(log_file + ".1") == ["old 1"]
+    assert _read(log_file + ".2") == ["old 2"]
+
+    # Once the file can be moved again, the next rotation goes through.
+    os.rmdir(log_file + ".rotating")
+    logger.info("rotation works")
+    handler.flush()
+    assert _read(log_file) == ["rotation works"]
+    assert _read(log_file + ".1") == lines
+    assert _read(log_file + ".2") == ["old 1"]
+
+
+def test_failed_timed_rotation_keeps_appending(logger, tmp_path):
+    log_file = str(tmp_path / "app.log")
+    _block_rotation(log_file)
+    handler = handlers.TimedRotatingFileHandler(
+        log_file, when="S", interval=1, backupCount=5
+    )
+    logger.addHandler(handler)
+    logger.info("first period")
+    time.sleep(1.2)
+    logger.info("second period")
+    handler.flush()
+    assert sorted(os.listdir(tmp_path)) == ["app.log", "app.log.rotating"]
+    assert _read(log_file) == ["first period", "second period"]
@@ -0,0 +1,109 @@
+"""
+Tests for logxide.route: a logger namespace gets its own handlers, and a terminal route
+keeps its records away from the root's handlers.
+"""
+
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+from logxide.config import dictConfig
+from logxide.logxide import logging as native_logging
+
+
+@pytest.fixture
+def root_memory():
+    root = logging.getLogger()
+    mh = handlers.MemoryHandler()
+    root.addHandler(mh)
+    yield mh
+    root.removeHandler(mh)
+    logxide.unroute(list(logxide.routes()))
+
+
+def test_terminal_route_keeps_records_from_root(root_memory, isolated_logger):
+    namespace = isolated_logger.name("vendor")
+    routed = handlers.MemoryHandler()
+    logxide.route(namespace, [routed])
+    logger = logging.getLogger(f"{namespace}.client.retries")
+    logger.warning("retrying")
+    assert [r.getMessage() for r in routed.records] == ["retrying"]
+    assert root_memory.records == []
+
+    logging.getLogger(f"{namespace}x").warning("not below the namespace")
+    assert root_memory.records[-1].getMessage() == "not below the namespace"
+
+
+def test_non_terminal_route_propagates(root_memory, isolated_logger):
+    namespace = isolated_logger.name("vendor")
+    routed = handlers.MemoryHandler()
+    logxide.route([namespace], [routed], terminal=False)
+    logging.getLogger(namespace).warning("both")
+    assert routed.records[-1].getMessage() == "both"
+    assert root_memory.records[-1].getMessage() == "both"
+
+
+def test_route_follows_the_namespace_logger(root_memory, isolated_logger):
+    namespace = isolated_logger.name("vendor")
+    own = handlers.MemoryHandler()
+    parent = logging.getLogger(namespace)
+    parent.addHandler(own)
+    routed = handlers.MemoryHandler()
+    logxide.route(namespace, [routed])
+    logging.getLogger(f"{namespace}.child").warning("seen twice")
+    assert own.records[-1].getMessage() == "seen twice"
+    assert routed.records[-1].getMessage() == "seen twice"
+    assert root_memory.records == []
+
+    native_logging.getLogger(namespace).propagate = False
+    logging.getLogger(f"{namespace}.child").warning("stopped at the logger")
+    assert own.records[-1].getMessage() == "stopped at the logger"
+    assert routed.records[-1].getMessage() == "seen twice"
+    parent.removeHandler(own)
+
+
+def test_drop_route_unroute_and_routes(root_memory, isolated_logger):
+    namespace = isolated_logger.name("vendor")
+    logxide.route(namespace)
+    assert logxide.routes()[namespace] == {"handlers": [], "terminal": True}
+    logging.getLogger(namespace).warning("dropped")
+    assert root_memory.records == []
+
+    logxide.unroute(namespace)
+    assert namespace not in logxide.routes()
+    logging.getLogger(namespace).warning("back")
+    assert root_memory.records[-1].getMessage() == "back"
+    for bad in ("", "root", [namespace, "root"]):
+        with pytest.raises(ValueError, match="cannot route"):
+            logxide.route(bad)
+
+
+def test_dict_config_routes_section(root_memory, tmp_path, isolated_logger):
+    namespace = isolated_logger.name("vendor")
+    path = tmp_path / "vendor.log"
+    dictConfig(
+        {
+            "version": 1,
+            "disable_existing_loggers": False,
+            "handlers": {
+                "vendor": {"class": "logging.FileHandler", "filename": str(path)}
+            },
+            "routes": {namespace: {"handlers": ["vendor"]}},
+        }
+    )
+    (vendor,) = logxide.routes()[namespace]["handlers"]
+    assert logxide.routes()[namespace]["terminal"] is True
+    logging.getLogger(f"{namespace}.io").warning("to the file")
+    vendor.flush()
+    assert path.read_text() == "to the file\n"
+    assert root_memory.records == []
+
+    dictConfig({"version": 1, "disable_existing_loggers": False})
+    assert logxide.routes() == {}
+
+
+def test_reinitialize_removes_routes(isolated_logger):
+    logxide.route(isolated_logger.name("vendor"))
+    logxide.reinitialize()
+    assert logxide.routes() == {}
@@ -0,0 +1,62 @@
+"""
+Tests for the run_id extra: one id per process on every record, a new one in a forked
+child, and configure_run_id() to turn it off or replace it.
+"""
+
+import os
+import uuid
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def memory():
+    logger = logging.getLogger("run_id_tests")
+    mh = handlers.MemoryHandler()
+    logger.addHandler(mh)
+    yield logger, mh
+    logger.removeHandler(mh)
+    logxide.configure_run_id()
+
+
+def test_every_record_carries_the_process_run_id(memory):
+    logger, mh = memory
+    run_id = logxide.run_id()
+    assert uuid.UUID(run_id).version == 4
+    logger.warning("first")
+    logging.getLogger("run_id_tests.child").warning("second")
+    logger.warning("own", extra={"run_id": "mine"})
+    assert [r.run_id for r in mh.records] == [run_id, run_id, "mine"]
+
+
+def test_configure_run_id(memory):
+    logger, mh = memory
+    logxide.configure_run_id(run_id="deploy-42")
+    assert logxide.run_id() == "deploy-42"
+    logger.warning("renamed")
+    assert mh.records[-1].run_id == "deploy-42"
+
+    logxide.configure_run_id(enabled=False)
+    logger.warning("off")
+    assert not hasattr(mh.records[-1], "run_id")
+    with pytest.raises(ValueError, match="must not be empty"):
+        logxide.configure_run_id(run_id="")
+
+
+@pytest.mark.skipif(not hasattr(os, "fork"), reason="needs os.fork")
+def test_forked_child_gets_its_own_run_id():
+    parent = logxide.run_id()
+    read, write = os.pipe()
+    pid = os.fork()
+    if pid == 0:
+        os.write(write, logxide.run_id().encode())
+        os._exit(0)
+    os.close(write)
+    child = os.read(read, 100).decode()
+    os.close(read)
+    os.waitpid(pid, 0)
+    assert child and child != parent
+    assert logxide.run_id() == parent
@@ -0,0 +1,76 @@
+"""Tests for logxide.runtime_info() platform capability reporting."""
+
+import sys
+
+import logxide
+
+
+def test_runtime_info_keys():
+    info = logxide.runtime_info()
+    assert set(info) == {"platform", "threads", "network", "console", "features"}
+    assert info["platform"] in ("native", "wasm32")
+    assert info["console"] in ("stdio", "js")
+
+
+def test_runtime_info_features():
+    features = logxide.runtime_info()["features"]
+    assert set(features) <= {
+        "file",
+        "network",
+        "otlp",
+        "compression",
+        "pipe",
+        "smtp",
+        "colors",
+        "json",
+        "watch",
+        "regex-filters",
+        "parquet",
+    }
+    if "otlp" in features or "compression" in features or "smtp" in features:
+        assert "network" in features
+    if "network" in features or "parquet" in features:
+        assert "json" in features
+    if "regex-filters" not in features:
+        assert logxide.RegexFilter is None and logxide.Redactor is None
+
+
+def test_missing_feature_raises_not_implemented():
+    from logxide.handlers import FileHandler
+
+    if "file" in logxide.runtime_info()["features"]:
+        return
+    try:
+        FileHandler("unused.log")
+    except NotImplementedError as e:
+        assert "'file' feature" in str(e)
+    else:
+        raise AssertionError("FileHandler should be unavailable")
+
+
+def test_runtime_info_matches_interpreter_platform():
+    info = logxide.runtime_info()
+    features = info.pop("features")
+    if sys.platform == "emscripten":
+        assert info == {
+            "platform": "wasm32",
+            "threads": False,
+            "network": False,
+            "console": "js",
+        }
+    else:
+        assert info == {
+            "platform": "native",
+            "threads": True,
+            "network": "network" in features,
+            "console": "stdio",
+        }
+
+
+def test_network_handlers_available_when_reported():
+    if not logxide.runtime_info()["network"]:
+        return
+    from logxide.handlers import HTTPHandler
+
+    handler = HTTPHandler(url="http://127.0.0.1:9/logs", shutdown_timeout=0.1)
+    handler.close()
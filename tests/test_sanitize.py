@@ -0,0 +1,98 @@
+"""
+Tests for setSanitize(): control characters and lone surrogates in formatted text are
+escaped or stripped per handler.
+"""
+
+import logging
+
+import pytest
+
+from logxide import handlers
+from logxide.logxide import logging as native_logging
+
+
+def _file_logger(tmp_path, name="app.log"):
+    path = tmp_path / name
+    handler = handlers.FileHandler(str(path))
+    logger = native_logging.getLogger(f"sanitize.{tmp_path.name}.{name}")
+    logger.setLevel(logging.INFO)
+    logger.propagate = False
+    logger.addHandler(handler)
+    return logger, handler, path
+
+
+def _read(handler, path):
+    handler.flush()
+    with open(path, encoding="utf-8") as f:
+        return f.read()
+
+
+def test_unchanged_by_default(tmp_path):
+    logger, handler, path = _file_logger(tmp_path)
+    logger.info("\x1b[31mred\x1b[0m")
+    assert _read(handler, path) == "\x1b[31mred\x1b[0m\n"
+
+
+def test_escape_keeps_newlines(tmp_path):
+    logger, handler, path = _file_logger(tmp_path)
+    handler.setSanitize("escape")
+    logger.info("a\x1b[31mb\x00c\x7fd\x85e\nnext\tline\r")
+    assert _read(handler, path) == "a\\x1b[31mb\\x00c\\x7fd\\x85e\nnext\tline\\r\n"
+
+
+def test_escape_on_one_line(tmp_path):
+    logger, handler, path = _file_logger(tmp_path)
+    handler.setSanitize("escape", newlines=False)
+    logger.info("first\nsecond\tthird")
+    assert _read(handler, path) == "first\\nsecond\\tthird\n"
+
+
+def test_strip(tmp_path):
+    logger, handler, path = _file_logger(tmp_path)
+    handler.setSanitize("strip", newlines=False)
+    logger.info("a\x1b[0mb\x07\nc")
+    assert _read(handler, path) == "a[0mb c\n"
+
+
+def test_lone_surrogates(tmp_path):
+    logger, handler, path = _file_logger(tmp_path)
+    logger.info("raw \udcff %s", "\ud800")
+    handler.setSanitize("escape")
+    logger.info("escaped \udcff")
+    handler.setSanitize("strip")
+    logger.info("stripped \udcff.")
+    assert _read(handler, path).splitlines() == [
+        "raw � �",
+        "escaped \\ufffd",
+        "stripped .",
+    ]
+
+
+def test_python_formatted_records_are_sanitized(tmp_path):
+    logger, handler, path = _file_logger(tmp_path)
+    handler.setFormatter(logging.Formatter("{levelname}: {message}", style="{"))
+    handler.setSanitize("escape")
+    logger.warning("bell\x07")
+    assert _read(handler, path) == "WARNING: bell\\x07\n"
+
+
+def test_level_routed_files_share_the_policy(tmp_path):
+    errors, info = tmp_path / "errors.log", tmp_path / "info.log"
+    handler = handlers.LevelRoutedFileHandler(
+        {logging.ERROR: str(errors), logging.INFO: str(info)}
+    )
+    handler.setSanitize("strip")
+    logger = native_logging.getLogger(f"sanitize.{tmp_path.name}.routed")
+    logger.setLevel(logging.INFO)
+    logger.propagate = False
+    logger.addHandler(handler)
+    logger.info("i\x1b")
+    logger.error("e\x1b")
+    handler.flush()
+    assert errors.read_text() == "e\n"
+    assert info.read_text() == "i\n"
+
+
+def test_unknown_policy_is_rejected():
+    with pytest.raises(ValueError):
+        handlers.StreamHandler().setSanitize("remove")
@@ -0,0 +1,137 @@
+"""
+Tests for schema versions: JsonFormatter(schema_version=...) tags every line, and
+registered migrations bring historical records up to date in migrate_record() and
+convert_log_file() / python -m logxide convert.
+"""
+
+import json
+import os
+import subprocess
+import sys
+
+import pytest
+
+import logxide
+from logxide import FileHandler, JsonFormatter, logging
+from logxide.logxide import logging as native_logging
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+
+def _split_user(record):
+    """Version 0 -> 1: `user` ("id:name") becomes `user_id` and `user_name`."""
+    user_id, _, user_name = record.pop("user", ":").partition(":")
+    record["user_id"] = int(user_id)
+    record["user_name"] = user_name
+
+
+def _rename_level(record):
+    """Version 1 -> 2: `levelname` is written as `level`."""
+    record = dict(record)
+    record["level"] = record.pop("levelname")
+    return record
+
+
+@pytest.fixture
+def migrations():
+    logxide.register_schema_migration(0, _split_user)
+    logxide.register_schema_migration(1, _rename_level)
+
+
+def test_formatter_writes_the_version_first(tmp_path):
+    path = tmp_path / "app.log"
+    handler = FileHandler(str(path))
+    formatter = JsonFormatter(fields=["levelname", "message"], schema_version=3)
+    handler.setFormatter(formatter)
+    logger = logging.getLogger("test.schema.formatter")
+    logger.propagate = False
+    logger.addHandler(handler)
+    try:
+        logger.warning("hello", extra={"schema_version": 1})
+        handler.flush()
+    finally:
+        logger.removeHandler(handler)
+        handler.close()
+    assert formatter.schema_version == 3
+    assert JsonFormatter().schema_version is None
+    assert path.read_text().splitlines() == [
+        '{"schema_version":3,"levelname":"WARNING","message":"hello"}'
+    ]
+
+
+def test_migrations_run_in_order(migrations):
+    assert logxide.schema_migrations() == {0: _split_user, 1: _rename_level}
+    record = {"levelname": "INFO", "message": "login", "user": "42:ada"}
+    assert logxide.migrate_record(record, 2) == {
+        "level": "INFO",
+        "message": "login",
+        "user_id": 42,
+        "user_name": "ada",
+        "schema_version": 2,
+    }
+    assert "schema_version" not in record
+    one = logxide.migrate_record({"levelname": "INFO", "schema_version": 1}, 2)
+    assert one == {"level": "INFO", "schema_version": 2}
+    same = logxide.migrate_record({"levelname": "INFO", "schema_version": 2}, 2)
+    assert same == {"levelname": "INFO", "schema_version": 2}
+
+
+def test_migration_errors(migrations):
+    with pytest.raises(ValueError, match="no schema migration registered from version"):
+        logxide.migrate_record({"schema_version": 2}, 3)
+    with pytest.raises(ValueError, match="newer than 1"):
+        logxide.migrate_record({"schema_version": 2}, 1)
+    with pytest.raises(ValueError, match="whole number"):
+        logxide.migrate_record({"schema_version": "two"}, 2)
+    with pytest.raises(TypeError):
+        logxide.register_schema_migration(5, "not callable")
+    logxide.register_schema_migration(7, lambda record: ["not", "a", "dict"])
+    with pytest.raises(TypeError, match="must return a dict"):
+        logxide.migrate_record({"schema_version": 7}, 8)
+
+
+def test_convert_upgrades_historical_records(tmp_path, migrations):
+    src = tmp_path / "old.jsonl"
+    src.write_text(
+        json.dumps({"levelname": "INFO", "message": "v0", "user": "1:a"})
+        + "\n"
+        + json.dumps({"levelname": "ERROR", "message": "v1", "schema_version": 1})
+        + "\n"
+    )
+    text = native_logging.convert_log_file(src, schema_version=2)
+    lines = [json.loads(line) for line in text.splitlines()]
+    assert [(r["level"], r["message"], r["schema_version"]) for r in lines] == [
+        ("INFO", "v0", 2),
+        ("ERROR", "v1", 2),
+    ]
+    assert (lines[0]["user_id"], lines[0]["user_name"]) == (1, "a")
+    assert all("user" not in r and "levelname" not in r for r in lines)
+
+
+def test_cli_imports_migration_modules(tmp_path):
+    (tmp_path / "app_migrations.py").write_text(
+        "import logxide\n"
+        "logxide.register_schema_migration(0, lambda r: {**r, 'region': 'eu'})\n"
+    )
+    src = tmp_path / "old.jsonl"
+    src.write_text('{"levelname": "INFO", "message": "hi"}\n')
+    result = subprocess.run(
+        [
+            sys.executable,
+            "-m",
+            "logxide",
+            "convert",
+            str(src),
+            "--schema-version",
+            "1",
+            "--migrations",
+            "app_migrations",
+        ],
+        env={**os.environ, "PYTHONPATH": os.pathsep.join([REPO_ROOT, str(tmp_path)])},
+        capture_output=True,
+        text=True,
+        timeout=60,
+    )
+    assert result.returncode == 0, result.stderr
+    (line,) = [json.loads(line) for line in result.stdout.splitlines()]
+    assert (line["message"], line["region"], line["schema_version"]) == ("hi", "eu", 1)
@@ -0,0 +1,73 @@
+"""
+Tests for logxide.configure_secret_keys: extra fields with denylisted names, nested
+ones included, reach every handler with their values replaced.
+"""
+
+import json
+
+import pytest
+
+import logxide
+from logxide import handlers
+
+
+@pytest.fixture
+def capture(isolated_logger):
+    mh = handlers.MemoryHandler()
+    yield isolated_logger(mh, name="app.secret_keys"), mh
+    logxide.configure_secret_keys(enabled=False)
+
+
+def test_default_keys_at_any_depth(capture):
+    logger, mh = capture
+    logxide.configure_secret_keys()
+    logger.warning(
+        "call",
+        extra={
+            "Password": "hunter2",
+            "api-key": 12345,
+            "request": {
+                "headers": [{"Authorization": "Bearer x", "Accept": "*/*"}],
+                "api_key": {"nested": "object"},
+            },
+            "user": "ann",
+        },
+    )
+    record = mh.records[-1]
+    assert record.Password == "***"
+    assert getattr(record, "api-key") == "***"
+    assert record.request == {
+        "headers": [{"Authorization": "***", "Accept": "*/*"}],
+        "api_key": "***",
+    }
+    assert record.user == "ann"
+
+
+def test_custom_keys_and_replacement(capture):
+    logger, mh = capture
+    logxide.configure_secret_keys(keys=["Session-ID"], replacement="<hidden>")
+    logger.warning("x", extra={"session_id": "s1", "password": "kept"})
+    assert mh.records[-1].session_id == "<hidden>"
+    assert mh.records[-1].password == "kept"
+
+    logxide.configure_secret_keys(enabled=False)
+    logger.warning("x", extra={"session_id": "s1"})
+    assert mh.records[-1].session_id == "s1"
+    with pytest.raises(ValueError, match="must not be empty"):
+        logxide.configure_secret_keys(keys=[""])
+
+
+def test_json_formatter_output(capture, tmp_path):
+    logger, _ = capture
+    path = tmp_path / "out.jsonl"
+    handler = handlers.FileHandler(str(path))
+    handler.setFormatter(logxide.JsonFormatter())
+    logger.addHandler(handler)
+    logxide.configure_secret_keys()
+    logger.warning("token", extra={"authorization": "Basic YWxh"})
+    handler.flush()
+    logger.removeHandler(handler)
+    handler.close()
+    line = json.loads(path.read_text())
+    assert line["authorization"] == "***"
+    assert "YWxh" not in path.read_text()
@@ -0,0 +1,73 @@
+"""
+Tests for the seq field: a per-process sequence number on every record, rendered by
+the formatters and carried to Python handlers.
+"""
+
+import io
+import json
+import logging as std_logging
+import os
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def memory():
+    logger = logging.getLogger("seq_tests")
+    mh = handlers.MemoryHandler()
+    logger.addHandler(mh)
+    yield logger, mh
+    logger.removeHandler(mh)
+
+
+def test_records_are_numbered_in_order(memory):
+    logger, mh = memory
+    logger.warning("first")
+    logging.getLogger("seq_tests.child").warning("second")
+    logger.log_batch(logging.WARNING, ["third", "fourth"])
+    seqs = [r.seq for r in mh.records]
+    assert seqs[0] > 0
+    assert seqs == list(range(seqs[0], seqs[0] + 4))
+
+
+def test_formatters_render_seq(memory, tmp_path):
+    logger, mh = memory
+    path = tmp_path / "seq.jsonl"
+    native = handlers.FileHandler(str(path))
+    native.setFormatter(logxide.JsonFormatter(fields=["seq", "message"]))
+    stream = io.StringIO()
+    python = std_logging.StreamHandler(stream)
+    python.setFormatter(logging.Formatter("%(seq)06d %(message)s"))
+    logger.addHandler(native)
+    logger.addHandler(python)
+    try:
+        logger.warning("numbered")
+        native.flush()
+    finally:
+        logger.removeHandler(native)
+        logger.removeHandler(python)
+    seq = mh.records[-1].seq
+    assert json.loads(path.read_text()) == {"seq": seq, "message": "numbered"}
+    assert stream.getvalue() == f"{seq:06d} numbered\n"
+
+
+@pytest.mark.skipif(not hasattr(os, "fork"), reason="needs os.fork")
+def test_forked_child_starts_again(memory):
+    logger, mh = memory
+    logger.warning("parent")
+    logger.warning("parent")
+    read, write = os.pipe()
+    pid = os.fork()
+    if pid == 0:
+        logger.warning("child")
+        os.write(write, str(mh.records[-1].seq).encode())
+        os._exit(0)
+    os.close(write)
+    child = os.read(read, 100).decode()
+    os.close(read)
+    os.waitpid(pid, 0)
+    assert child == "1"
+    assert mh.records[-1].seq > 1
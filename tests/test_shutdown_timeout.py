@@ -0,0 +1,91 @@
+"""
+Tests for bounded shutdown of network handlers: once shutdown_timeout expires,
+records still pending are dropped (or spilled to disk) and counted instead of
+blocking exit on an unreachable or stalled sink.
+"""
+
+import json
+import time
+
+import pytest
+
+from logxide import LogRecord, RustHTTPHandler
+
+
+def _record(i):
+    return LogRecord(
+        name="shutdown.timeout.test",
+        levelno=20,
+        pathname="test.py",
+        lineno=i,
+        msg=f"message-{i}",
+    )
+
+
+@pytest.fixture
+def stalled_server(http_collector):
+    http_collector.delay = 1.0
+    return http_collector.url
+
+
+def _wait_settled(handler, n, timeout=5.0):
+    deadline = time.monotonic() + timeout
+    while time.monotonic() < deadline:
+        metrics = handler.get_metrics()
+        if metrics["in_flight"] == 0:
+            return metrics
+        time.sleep(0.05)
+    return handler.get_metrics()
+
+
+def test_shutdown_returns_within_timeout(stalled_server):
+    handler = RustHTTPHandler(
+        stalled_server,
+        batch_size=1,
+        flush_interval=3600,
+        shutdown_timeout=0.2,
+    )
+    n = 10
+    for i in range(n):
+        handler.emit(_record(i))
+
+    started = time.monotonic()
+    handler.shutdown()
+    assert time.monotonic() - started < 0.9
+
+    metrics = _wait_settled(handler, n)
+    assert metrics["shutdown_dropped"] > 0, metrics
+    assert metrics["shutdown_spilled"] == 0, metrics
+    assert (
+        metrics["sink_acknowledged"]
+        + metrics["delivery_failed"]
+        + metrics["queue_dropped"]
+        + metrics["shutdown_dropped"]
+        == n
+    ), metrics
+
+
+def test_pending_records_spill_to_disk(stalled_server, tmp_path):
+    spill = tmp_path / "pending.jsonl"
+    handler = RustHTTPHandler(
+        stalled_server,
+        batch_size=1,
+        flush_interval=3600,
+        shutdown_timeout=0.2,
+        spill_path=str(spill),
+    )
+    n = 10
+    for i in range(n):
+        handler.emit(_record(i))
+    handler.shutdown()
+
+    metrics = _wait_settled(handler, n)
+    assert metrics["shutdown_spilled"] > 0, metrics
+    lines = spill.read_text().splitlines()
+    assert len(lines) == metrics["shutdown_spilled"]
+    assert json.loads(lines[-1])["msg"] == f"message-{n - 1}"
+
+
+def test_negative_timeout_rejected():
+    with pytest.raises(ValueError):
+        RustHTTPHandler("http://127.0.0.1:9", shutdown_timeout=-1)
@@ -0,0 +1,253 @@
+"""
+Tests for SMTPHandler: one mail per record over an SMTP session run in Rust, with
+the stdlib constructor, subject templates, AUTH and delivery accounting.
+"""
+
+import base64
+import email
+import email.policy
+import logging.handlers as std_handlers
+import queue
+import socketserver
+import threading
+
+import pytest
+
+from logxide import LogRecord, handlers, logging
+from logxide.config import dictConfig
+
+
+class _Session(socketserver.StreamRequestHandler):
+    """Just enough of an SMTP server for the client under test."""
+
+    def reply(self, line):
+        self.wfile.write(line.encode() + b"\r\n")
+
+    def handle(self):
+        server = self.server
+        envelope = {"auth": None, "rcpt": []}
+        self.reply("220 fake ESMTP")
+        while True:
+            line = self.rfile.readline().decode().rstrip("\r\n")
+            if not line:
+                return
+            verb = line.split(" ", 1)[0].upper()
+            if verb == "EHLO":
+                envelope["helo"] = line[5:]
+                self.reply("250-fake greets you")
+                for ext in server.extensions:
+                    self.reply(f"250-{ext}")
+                self.reply("250 OK")
+            elif verb == "AUTH":
+                envelope["auth"] = self.authenticate(line)
+            elif verb == "MAIL":
+                envelope["from"] = line[len("MAIL FROM:") :]
+                self.reply("250 OK")
+            elif verb == "RCPT":
+                if server.reject_rcpt:
+                    self.reply("550 no such user")
+                    continue
+                envelope["rcpt"].append(line[len("RCPT TO:") :])
+                self.reply("250 OK")
+            elif verb == "DATA":
+                self.reply("354 go ahead")
+                data = b""
+                while not data.endswith(b"\r\n.\r\n"):
+                    data += self.rfile.readline()
+                envelope["data"] = data[: -len(b".\r\n")]
+                self.reply("250 queued")
+                server.mails.put(envelope)
+            elif verb == "QUIT":
+                self.reply("221 bye")
+                return
+            else:
+                self.reply("502 not implemented")
+
+    def authenticate(self, line):
+        parts = line.split(" ")
+        if parts[1].upper() == "PLAIN":
+            _, user, password = base64.b64decode(parts[2]).decode().split("\0")
+        else:
+            self.reply("334 VXNlcm5hbWU6")
+            user = base64.b64decode(self.rfile.readline()).decode()
+            self.reply("334 UGFzc3dvcmQ6")
+            password = base64.b64decode(self.rfile.readline()).decode()
+        self.reply("235 authenticated")
+        return (parts[1].upper(), user, password)
+
+
+@pytest.fixture
+def smtp():
+    server = socketserver.ThreadingTCPServer(("127.0.0.1", 0), _Session)
+    server.daemon_threads = True
+    server.mails = queue.Queue()
+    server.extensions = ["8BITMIME", "AUTH PLAIN LOGIN"]
+    server.reject_rcpt = False
+    thread = threading.Thread(target=server.serve_forever, daemon=True)
+    thread.start()
+    yield server
+    server.shutdown()
+    server.server_close()
+
+
+def _handler(smtp, subject="[%(levelname)s] %(name)s", **kwargs):
+    kwargs.setdefault("fromaddr", "app@example.com")
+    kwargs.setdefault("toaddrs", ["ops@example.com", "dev@example.com"])
+    return handlers.SMTPHandler(smtp.server_address, subject=subject, **kwargs)
+
+
+def _receive(smtp):
+    envelope = smtp.mails.get(timeout=10)
+    envelope["message"] = email.message_from_bytes(
+        envelope["data"], policy=email.policy.default
+    )
+    return envelope
+
+
+def _body(message):
+    return message.get_content().replace("\r\n", "\n").rstrip("\n")
+
+
+def _record(msg):
+    return LogRecord("test.smtp", logging.ERROR, __file__, 1, msg)
+
+
+def test_mails_each_record(smtp, isolated_logger):
+    handler = _handler(smtp)
+    logger = isolated_logger(handler)
+    logger.error("payment %s failed", "p-17")
+
+    envelope = _receive(smtp)
+    message = envelope["message"]
+    assert envelope["from"] == "<app@example.com>"
+    assert envelope["rcpt"] == ["<ops@example.com>", "<dev@example.com>"]
+    assert envelope["auth"] is None
+    assert message["From"] == "app@example.com"
+    assert message["To"] == "ops@example.com, dev@example.com"
+    assert message["Subject"] == f"[ERROR] {logger.name}"
+    assert message["Date"]
+    assert message.get_content_type() == "text/plain"
+    assert _body(message) == "payment p-17 failed"
+    assert handler.get_metrics() == {
+        "emitted": 1,
+        "sink_acknowledged": 1,
+        "delivery_failed": 0,
+    }
+
+
+def test_formatter_and_unicode_body(smtp, isolated_logger):
+    handler = _handler(smtp, subject="Ärger in %(name)s", toaddrs="ops@example.com")
+    handler.setFormatter(logging.Formatter("%(levelname)s %(message)s"))
+    logger = isolated_logger(handler)
+    logger.critical("déjà vu\n.leading dot")
+
+    message = _receive(smtp)["message"]
+    assert message["Subject"] == f"Ärger in {logger.name}"
+    assert _body(message) == "CRITICAL déjà vu\n.leading dot"
+
+
+@pytest.mark.parametrize("mechanism", ["PLAIN", "LOGIN"])
+def test_credentials(smtp, mechanism, isolated_logger):
+    smtp.extensions = ["AUTH " + mechanism]
+    handler = _handler(smtp, credentials=("app", "s3cret"))
+    isolated_logger(handler).error("boom")
+
+    assert _receive(smtp)["auth"] == (mechanism, "app", "s3cret")
+
+
+def test_python_dispatch_for_filters_and_get_subject(smtp, isolated_logger):
+    class Handler(handlers.SMTPHandler):
+        def getSubject(self, record):
+            return f"custom {record.levelname}"
+
+    handler = Handler(
+        smtp.server_address, "app@example.com", ["ops@example.com"], "unused"
+    )
+    handler.addFilter(lambda record: "skip" not in record.getMessage())
+    logger = isolated_logger(handler)
+    logger.warning("skip me")
+    logger.warning("keep me")
+
+    message = _receive(smtp)["message"]
+    assert message["Subject"] == "custom WARNING"
+    assert _body(message) == "keep me"
+    assert smtp.mails.empty()
+
+
+def test_failures_are_counted_and_reported(smtp):
+    smtp.reject_rcpt = True
+    errors = []
+    handler = _handler(smtp, error_callback=errors.append)
+    handler.emit(_record("lost"))
+
+    host, port = smtp.server_address
+    assert len(errors) == 1
+    assert errors[0].startswith(f"mail to {host}:{port} failed")
+    assert "550" in errors[0]
+    assert handler.get_metrics()["delivery_failed"] == 1
+
+
+def test_starttls_must_be_offered(smtp):
+    errors = []
+    handler = _handler(smtp, secure=(), error_callback=errors.append)
+    handler.emit(_record("x"))
+
+    assert "does not support STARTTLS" in errors[0]
+
+
+def test_stdlib_signature_and_attributes(smtp):
+    handler = _handler(smtp, credentials=("app", "pw"), timeout=2.0)
+
+    assert isinstance(handler, std_handlers.SMTPHandler)
+    assert (handler.mailhost, handler.mailport) == smtp.server_address
+    assert handler.toaddrs == ["ops@example.com", "dev@example.com"]
+    assert (handler.username, handler.password) == ("app", "pw")
+    record = _record("x")
+    record.levelname = "ERROR"
+    assert handler.getSubject(record) == "[ERROR] test.smtp"
+    handler.close()
+
+
+def test_invalid_arguments():
+    address = ("127.0.0.1", 25)
+    with pytest.raises(ValueError):
+        handlers.SMTPHandler(address, "a@example.com", [], "s")
+    with pytest.raises(ValueError):
+        handlers.SMTPHandler(address, "a@example.com", "b@example.com", "s", timeout=0)
+    with pytest.raises(ValueError):
+        handlers.SMTPHandler(
+            address, "a@example.com", "b@example.com", "s", security="ssl3"
+        )
+    with pytest.raises(ValueError):
+        handlers.SMTPHandler(
+            address, "a@example.com", "b@example.com", "s", secure=("key.pem",)
+        )
+
+
+def test_dict_config_promotes_the_stdlib_handler(smtp, isolated_logger):
+    name = isolated_logger.name("test.smtp.config")
+    dictConfig(
+        {
+            "version": 1,
+            "handlers": {
+                "mail": {
+                    "class": "logging.handlers.SMTPHandler",
+                    "mailhost": list(smtp.server_address),
+                    "fromaddr": "app@example.com",
+                    "toaddrs": ["ops@example.com"],
+                    "subject": "alert",
+                    "level": "ERROR",
+                }
+            },
+            "loggers": {name: {"handlers": ["mail"], "level": "INFO"}},
+            "disable_existing_loggers": False,
+        }
+    )
+    logger = logging.getLogger(name)
+    (handler,) = logger.handlers
+    assert isinstance(handler, handlers.SMTPHandler)
+
+    logger.info("ignored")
+    logger.error("mailed")
+    assert _receive(smtp)["message"]["Subject"] == "alert"
+    assert smtp.mails.empty()
@@ -0,0 +1,177 @@
+"""
+Tests for SocketHandler: records as a stdlib-compatible length-prefixed pickle over TCP
+or a Unix stream socket, with reconnects and connection backoff.
+"""
+
+import logging as std_logging
+import logging.handlers as std_handlers
+import pickle
+import socket
+import struct
+import time
+
+import pytest
+
+from logxide import handlers, logging
+from logxide.config import dictConfig
+
+
+@pytest.fixture
+def server():
+    sock = socket.socket(socket.AF_INET, socket.SOCK_STREAM)
+    sock.bind(("127.0.0.1", 0))
+    sock.listen()
+    sock.settimeout(5)
+    yield sock
+    sock.close()
+
+
+def _read_exact(conn, size):
+    data = b""
+    while len(data) < size:
+        chunk = conn.recv(size - len(data))
+        assert chunk, "connection closed mid-record"
+        data += chunk
+    return data
+
+
+def _receive(conn):
+    """Read one record the way stdlib socket receivers do."""
+    (length,) = struct.unpack(">L", _read_exact(conn, 4))
+    return std_logging.makeLogRecord(pickle.loads(_read_exact(conn, length)))
+
+
+def _free_port():
+    with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as sock:
+        sock.bind(("127.0.0.1", 0))
+        return sock.getsockname()[1]
+
+
+def test_records_are_read_by_stdlib_receivers(server, isolated_logger):
+    handler = handlers.SocketHandler("127.0.0.1", server.getsockname()[1])
+    logger = isolated_logger(handler)
+    logger.warning("disk %s at %d%%", "/var", 93, extra={"host_id": 7})
+    logger.info("second")
+
+    conn, _ = server.accept()
+    with conn:
+        conn.settimeout(5)
+        first, second = _receive(conn), _receive(conn)
+    assert first.getMessage() == "disk /var at 93%"
+    assert first.args is None
+    assert first.levelno == logging.WARNING
+    assert first.name == logger.name
+    assert first.host_id == 7
+    assert second.getMessage() == "second"
+    assert handler.get_metrics()["sink_acknowledged"] == 2
+    handler.close()
+
+
+def test_pickle_dict_has_the_stdlib_keys(server, isolated_logger):
+    handler = handlers.SocketHandler("127.0.0.1", server.getsockname()[1])
+    isolated_logger(handler).info("hello")
+    conn, _ = server.accept()
+    with conn:
+        conn.settimeout(5)
+        (length,) = struct.unpack(">L", _read_exact(conn, 4))
+        ours = pickle.loads(_read_exact(conn, length))
+
+    reference = std_logging.LogRecord("x", logging.INFO, __file__, 1, "m", None, None)
+    expected = pickle.loads(std_handlers.SocketHandler("", 0).makePickle(reference)[4:])
+    assert set(expected) <= set(ours)
+    handler.close()
+
+
+def test_reconnects_after_the_receiver_drops_the_connection(server, isolated_logger):
+    handler = handlers.SocketHandler("127.0.0.1", server.getsockname()[1])
+    logger = isolated_logger(handler)
+    logger.info("first")
+    conn, _ = server.accept()
+    conn.settimeout(5)
+    assert _receive(conn).getMessage() == "first"
+    conn.close()
+    time.sleep(0.1)
+
+    logger.info("after the drop")
+    conn, _ = server.accept()
+    with conn:
+        conn.settimeout(5)
+        assert _receive(conn).getMessage() == "after the drop"
+    metrics = handler.get_metrics()
+    assert metrics["reconnects"] == 1, metrics
+    assert metrics["delivery_failed"] == 0, metrics
+    handler.close()
+
+
+def test_connection_attempts_back_off_while_the_receiver_is_down(isolated_logger):
+    port = _free_port()
+    errors = []
+    handler = handlers.SocketHandler(
+        "127.0.0.1", port, retry_start=0.2, error_callback=errors.append
+    )
+    logger = isolated_logger(handler)
+    for i in range(3):
+        logger.info("dropped %d", i)
+
+    # One failed attempt; the other records fall inside the backoff window.
+    assert len(errors) == 1 and f"127.0.0.1:{port}" in errors[0]
+    assert handler.get_metrics()["delivery_failed"] == 3
+
+    with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as server:
+        server.bind(("127.0.0.1", port))
+        server.listen()
+        server.settimeout(5)
+        time.sleep(0.3)
+        logger.info("delivered")
+        conn, _ = server.accept()
+        with conn:
+            conn.settimeout(5)
+            assert _receive(conn).getMessage() == "delivered"
+    assert handler.get_metrics()["sink_acknowledged"] == 1
+    handler.close()
+
+
+@pytest.mark.skipif(not hasattr(socket, "AF_UNIX"), reason="needs Unix sockets")
+def test_unix_stream_socket(tmp_path, isolated_logger):
+    path = str(tmp_path / "log.sock")
+    with socket.socket(socket.AF_UNIX, socket.SOCK_STREAM) as server:
+        server.bind(path)
+        server.listen()
+        server.settimeout(5)
+        handler = handlers.SocketHandler(path)
+        isolated_logger(handler).info("over unix")
+        conn, _ = server.accept()
+        with conn:
+            conn.settimeout(5)
+            assert _receive(conn).getMessage() == "over unix"
+        handler.close()
+
+
+def test_dict_config_promotes_the_stdlib_class(server, isolated_logger):
+    name = isolated_logger.name("test.socket")
+    dictConfig(
+        {
+            "version": 1,
+            "disable_existing_loggers": False,
+            "handlers": {
+                "net": {
+                    "class": "logging.handlers.SocketHandler",
+                    "host": "127.0.0.1",
+                    "port": server.getsockname()[1],
+                }
+            },
+            "loggers": {name: {"handlers": ["net"], "propagate": False}},
+        }
+    )
+    (handler,) = std_logging.getLogger(name).handlers
+    assert isinstance(handler, handlers.SocketHandler)
+    handler.close()
+
+
+def test_invalid_backoff_is_rejected():
+    with pytest.raises(ValueError):
+        handlers.SocketHandler("127.0.0.1", 9, retry_start=0)
+    with pytest.raises(ValueError):
+        handlers.SocketHandler("127.0.0.1", 9, retry_factor=0.5)
+    with pytest.raises(ValueError):
+        handlers.SocketHandler("127.0.0.1", 9, retry_start=5, retry_max=1)
@@ -0,0 +1,67 @@
+"""
+Tests for stack_info=True: the record carries the Python stack up to the caller, and
+the native and stdlib formatters append it after the message as the stdlib does.
+"""
+
+import io
+import logging as std_logging
+import sys
+
+import pytest
+
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def memory():
+    logger = logging.getLogger("stack_info_tests")
+    mh = handlers.MemoryHandler()
+    logger.addHandler(mh)
+    yield logger, mh
+    logger.removeHandler(mh)
+
+
+def _log_with_stack(logger, **kwargs):
+    logger.warning("here", stack_info=True, **kwargs)
+    return sys._getframe().f_lineno - 1
+
+
+def test_record_carries_the_stack(memory):
+    logger, mh = memory
+    line = _log_with_stack(logger)
+    logger.warning("plain")
+    with_stack, plain = mh.records
+    assert with_stack.stack_info.startswith("Stack (most recent call last):\n")
+    assert with_stack.stack_info.endswith(
+        f'line {line}, in _log_with_stack\n    logger.warning("here", stack_info=True,'
+        " **kwargs)"
+    )
+    assert plain.stack_info is None
+
+
+def test_stacklevel_ends_the_stack_at_the_picked_frame(memory):
+    logger, mh = memory
+    _log_with_stack(logger, stacklevel=2)
+    last = mh.records[-1].stack_info.splitlines()[-2]
+    assert last.endswith("in test_stacklevel_ends_the_stack_at_the_picked_frame")
+
+
+def test_formatters_append_the_stack(memory, tmp_path):
+    logger, mh = memory
+    path = tmp_path / "stack.log"
+    native = handlers.FileHandler(str(path))
+    native.setFormatter(logging.Formatter("%(levelname)s %(message)s"))
+    stream = io.StringIO()
+    python = std_logging.StreamHandler(stream)
+    python.setFormatter(std_logging.Formatter("%(levelname)s %(message)s"))
+    logger.addHandler(native)
+    logger.addHandler(python)
+    try:
+        _log_with_stack(logger)
+        native.flush()
+    finally:
+        logger.removeHandler(native)
+        logger.removeHandler(python)
+    expected = f"WARNING here\n{mh.records[-1].stack_info}\n"
+    assert path.read_text() == expected
+    assert stream.getvalue() == expected
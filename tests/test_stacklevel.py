@@ -0,0 +1,72 @@
+"""
+Tests for caller info: stacklevel=N attributes a record to the N-th frame outside
+logging, and wrappers inside logxide never show up as the caller.
+"""
+
+import io
+import logging as std_logging
+import sys
+
+import pytest
+
+from logxide import logging
+
+FORMAT = "%(funcName)s:%(lineno)d %(message)s"
+
+
+@pytest.fixture
+def stream():
+    logger = logging.getLogger("stacklevel_tests")
+    buffer = io.StringIO()
+    handler = std_logging.StreamHandler(buffer)
+    handler.setFormatter(logging.Formatter(FORMAT))
+    logger.addHandler(handler)
+    logger.setLevel(logging.DEBUG)
+    yield logger, buffer
+    logger.removeHandler(handler)
+    logger.setLevel(logging.NOTSET)
+
+
+def log_helper(logger, msg, **kwargs):
+    logger.warning(msg, stacklevel=2, **kwargs)
+
+
+def _line():
+    return sys._getframe(1).f_lineno
+
+
+def test_stacklevel_skips_wrapper_frames(stream):
+    logger, buffer = stream
+    direct = _line() + 1
+    logger.info("direct")
+    wrapped = _line() + 1
+    log_helper(logger, "wrapped")
+    levelled = _line() + 1
+    logger.log(logging.ERROR, "levelled", stacklevel=1)
+    fn = "test_stacklevel_skips_wrapper_frames"
+    assert buffer.getvalue().splitlines() == [
+        f"{fn}:{direct} direct",
+        f"{fn}:{wrapped} wrapped",
+        f"{fn}:{levelled} levelled",
+    ]
+
+
+def test_stacklevel_beyond_the_stack_uses_the_outermost_frame(stream):
+    logger, buffer = stream
+    logger.warning("deep", stacklevel=10_000)
+    assert buffer.getvalue().endswith(" deep\n")
+    assert not buffer.getvalue().startswith(
+        "test_stacklevel_beyond_the_stack_uses_the_outermost_frame"
+    )
+
+
+def test_logxide_wrappers_are_not_the_caller(stream):
+    logger, buffer = stream
+    adapter = logging.LoggerAdapter(logger, {"user": "ada"})
+    line = _line() + 1
+    adapter.warning("adapted")
+    assert buffer.getvalue() == (
+        f"test_logxide_wrappers_are_not_the_caller:{line} adapted\n"
+    )
+    with pytest.raises(TypeError):
+        logger.warning("bad", stacklevel="2")
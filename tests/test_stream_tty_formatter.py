@@ -0,0 +1,131 @@
+"""
+Tests for StreamHandler's formatter_tty / formatter_plain: one handler writes the
+terminal format when its stream is a TTY and the plain format when redirected.
+"""
+
+import os
+import subprocess
+import sys
+import textwrap
+
+import pytest
+
+from logxide import handlers, logging
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+SCRIPT = textwrap.dedent(
+    """
+    import sys
+    from logxide import handlers, logging
+    {formatters}
+    handler = handlers.StreamHandler(
+        sys.stdout, formatter_tty=tty, formatter_plain=plain
+    )
+    logger = logging.getLogger("tty.demo")
+    logger.propagate = False
+    logger.addHandler(handler)
+    print("native" if handler._native else "python", flush=True)
+    logger.warning("hello")
+    handler.flush()
+    """
+)
+
+STDLIB = """
+import logging as std_logging
+tty = std_logging.Formatter("TTY %(levelname)s %(message)s")
+plain = std_logging.Formatter("PLAIN %(message)s")
+"""
+
+NATIVE_COLOR = """
+import logging as std_logging
+from logxide import ColorFormatter
+tty = ColorFormatter("%(ansi_level_color)s%(levelname)s%(ansi_reset_color)s %(message)s")
+plain = std_logging.Formatter("PLAIN %(message)s")
+"""
+
+CUSTOM = """
+import logging as std_logging
+class Shout(std_logging.Formatter):
+    def format(self, record):
+        return "TTY " + record.getMessage().upper()
+tty = Shout()
+plain = std_logging.Formatter("PLAIN %(message)s")
+"""
+
+
+def _run(formatters, tty):
+    script = SCRIPT.format(formatters=formatters)
+    env = dict(os.environ, PYTHONPATH=REPO_ROOT)
+    if not tty:
+        result = subprocess.run(
+            [sys.executable, "-c", script],
+            capture_output=True,
+            text=True,
+            timeout=30,
+            env=env,
+        )
+        return result.stdout.splitlines()
+    pty = pytest.importorskip("pty")
+    master, slave = pty.openpty()
+    proc = subprocess.Popen(
+        [sys.executable, "-c", script],
+        stdout=slave,
+        stderr=subprocess.DEVNULL,
+        env=env,
+    )
+    os.close(slave)
+    chunks = []
+    while True:
+        try:
+            chunk = os.read(master, 4096)
+        except OSError:  # EIO once the child closes the terminal
+            break
+        if not chunk:
+            break
+        chunks.append(chunk)
+    proc.wait(timeout=30)
+    os.close(master)
+    return b"".join(chunks).decode().replace("\r\n", "\n").splitlines()
+
+
+@pytest.mark.skipif(sys.platform == "win32", reason="needs a pseudo-terminal")
+@pytest.mark.parametrize("tty", [True, False])
+def test_stdlib_formatters_stay_native(tty):
+    lines = _run(STDLIB, tty)
+
+    assert lines == ["native", "TTY WARNING hello" if tty else "PLAIN hello"]
+
+
+@pytest.mark.skipif(sys.platform == "win32", reason="needs a pseudo-terminal")
+def test_color_formatter_on_a_terminal():
+    lines = _run(NATIVE_COLOR, tty=True)
+
+    assert lines[0] == "native"
+    assert lines[1].startswith("\x1b[")
+    assert lines[1].endswith("WARNING\x1b[0m hello")
+    assert _run(NATIVE_COLOR, tty=False) == ["native", "PLAIN hello"]
+
+
+@pytest.mark.skipif(sys.platform == "win32", reason="needs a pseudo-terminal")
+@pytest.mark.parametrize("tty", [True, False])
+def test_custom_formatter_falls_back_to_python(tty):
+    lines = _run(CUSTOM, tty)
+
+    assert lines == ["python", "TTY HELLO" if tty else "PLAIN hello"]
+
+
+def test_formatter_plain_is_the_regular_formatter():
+    plain = logging.Formatter("PLAIN %(message)s")
+    handler = handlers.StreamHandler(formatter_plain=plain)
+    try:
+        assert handler.formatter is plain
+    finally:
+        handler.close()
+
+
+def test_rejects_non_native_objects_on_the_rust_handler():
+    from logxide import RustStreamHandler
+
+    with pytest.raises(TypeError):
+        RustStreamHandler("stderr").setTtyFormatter(object())
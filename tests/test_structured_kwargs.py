@@ -0,0 +1,137 @@
+"""
+Tests for structured keyword arguments: `logger.info("login", user_id=42)` stores the
+keyword arguments the logging methods don't take themselves as typed extras, which the
+JSON formatter and the HTTP and OTLP handlers send on.
+"""
+
+import json
+
+import pytest
+
+import logxide
+from logxide import FileHandler, JsonFormatter, handlers, logging
+
+
+@pytest.fixture
+def json_lines(tmp_path, isolated_logger):
+    """Run ``log(logger)`` on a logger writing JSON lines, and return them parsed."""
+    path = tmp_path / "app.log"
+
+    def run(log):
+        handler = FileHandler(str(path))
+        handler.setFormatter(JsonFormatter())
+        logger = isolated_logger(handler, name="test.kwargs", level=logging.DEBUG)
+        try:
+            log(logger)
+            handler.flush()
+        finally:
+            handler.close()
+        return [json.loads(line) for line in path.read_text().splitlines()]
+
+    return run
+
+
+def test_kwargs_keep_their_json_type(json_lines):
+    (line,) = json_lines(
+        lambda logger: logger.info(
+            "user login",
+            user_id=42,
+            region="eu",
+            ratio=0.5,
+            admin=False,
+            roles=["ops", None],
+            device={"os": "linux"},
+        ),
+    )
+    assert line["message"] == "user login"
+    assert line["user_id"] == 42
+    assert line["region"] == "eu"
+    assert line["ratio"] == 0.5
+    assert line["admin"] is False
+    assert line["roles"] == ["ops", None]
+    assert line["device"] == {"os": "linux"}
+
+
+def test_kwargs_win_over_extra_and_leave_logging_options_alone(json_lines):
+    def log(logger):
+        logger.warning(
+            "%s retried",
+            "job",
+            extra={"attempt": 1, "queue": "default"},
+            attempt=2,
+            stacklevel=1,
+            stack_info=False,
+            exc_info=None,
+        )
+        logger.log(logging.ERROR, "plain")
+
+    first, second = json_lines(log)
+    assert first["message"] == "job retried"
+    assert first["attempt"] == 2
+    assert first["queue"] == "default"
+    assert "extra" not in first and "stacklevel" not in first
+    assert first["stack_info"] is None and first["exc_info"] is None
+    assert "attempt" not in second
+
+
+def test_kwargs_with_bound_context_and_adapters(json_lines):
+    def log(logger):
+        logger.bind(request_id="r-1").info("bound", user_id=7, request_id="r-2")
+        adapter = logging.LoggerAdapter(logger, {"tenant": "acme"})
+        adapter.info("adapted", user_id=8)
+
+    bound, adapted = json_lines(log)
+    assert (bound["request_id"], bound["user_id"]) == ("r-2", 7)
+    assert (adapted["tenant"], adapted["user_id"]) == ("acme", 8)
+
+
+def test_kwargs_count_against_extra_limits(json_lines):
+    logxide.configure_extra_limits(max_keys=2)
+    try:
+        (line,) = json_lines(lambda logger: logger.info("x", a=1, b=2, c=3))
+    finally:
+        logxide.configure_extra_limits(max_keys=None)
+    assert (line["a"], line["b"]) == (1, 2)
+    assert "c" not in line
+    assert line["extra_keys_dropped"] == 1
+
+
+def test_text_formats_use_kwargs(tmp_path, isolated_logger):
+    path = tmp_path / "app.log"
+    handler = FileHandler(str(path))
+    handler.setFormatter(logging.Formatter("%(message)s user=%(user_id)s"))
+    logger = isolated_logger(handler)
+    try:
+        logger.info("login", user_id=42)
+        handler.flush()
+    finally:
+        handler.close()
+    assert path.read_text() == "login user=42\n"
+
+
+def test_http_handler_sends_typed_kwargs(http_collector, isolated_logger):
+    handler = handlers.HTTPHandler(http_collector.url)
+    logger = isolated_logger(handler)
+    try:
+        logger.info("user login", user_id=42, region="eu")
+        handler.flush()
+    finally:
+        handler.close()
+    ((record,),) = [json.loads(body) for body in http_collector.bodies]
+    assert record["extra"]["user_id"] == 42
+    assert record["extra"]["region"] == "eu"
+
+
+def test_otlp_handler_sends_typed_attributes(http_collector, isolated_logger):
+    handler = handlers.OTLPHandler(http_collector.url, service_name="kwargs")
+    logger = isolated_logger(handler)
+    try:
+        logger.info("user login", user_id=42, region="eu", admin=True)
+        handler.flush()
+    finally:
+        handler.close()
+    (body,) = http_collector.bodies
+    # KeyValue{key, value: AnyValue{int_value | string_value | bool_value}}
+    assert b"\x0a\x07user_id\x12\x02\x18\x2a" in body
+    assert b"\x0a\x06region\x12\x04\x0a\x02eu" in body
+    assert b"\x0a\x05admin\x12\x02\x10\x01" in body
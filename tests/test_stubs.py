@@ -0,0 +1,136 @@
+"""
+Tests for logxide/logxide.pyi, the stub `cargo xtask stubs` generates for the Rust
+extension: every class, method and function the extension exposes is declared, with
+the parameter names it accepts at runtime.
+"""
+
+import ast
+import inspect
+import os
+
+import pytest
+
+from logxide import logxide as ext
+
+STUB = os.path.join(
+    os.path.dirname(os.path.dirname(os.path.abspath(__file__))), "logxide", "logxide.pyi"
+)
+
+
+def _stub():
+    with open(STUB, encoding="utf-8") as f:
+        return ast.parse(f.read())
+
+
+def _members(node):
+    """Names declared in a module or class body, mapped to their defs (or None)."""
+    members = {}
+    for item in node.body:
+        if isinstance(item, ast.FunctionDef | ast.ClassDef):
+            # A property setter repeats the getter's name; keep the getter.
+            members.setdefault(item.name, item)
+        elif isinstance(item, ast.AnnAssign) and isinstance(item.target, ast.Name):
+            members[item.target.id] = None
+        elif isinstance(item, ast.Assign):
+            for target in item.targets:
+                if isinstance(target, ast.Name):
+                    members[target.id] = None
+    return members
+
+
+def _stub_params(func):
+    args = func.args
+    names = [a.arg for a in args.posonlyargs + args.args]
+    if names and names[0] in ("self", "cls"):
+        names = names[1:]
+    if args.vararg:
+        names.append("*")
+    names.extend(a.arg for a in args.kwonlyargs)
+    if args.kwarg:
+        names.append("**")
+    return names
+
+
+def _runtime_params(obj):
+    try:
+        signature = inspect.signature(obj)
+    except (TypeError, ValueError):
+        return None
+    names = []
+    for param in signature.parameters.values():
+        if param.kind is param.VAR_POSITIONAL:
+            names.append("*")
+        elif param.kind is param.VAR_KEYWORD:
+            names.append("**")
+        elif param.name not in ("self", "cls", "$self", "$cls"):
+            names.append(param.name)
+    return names
+
+
+def _public(namespace):
+    return [
+        name
+        for name in namespace
+        if not name.startswith("_") and name not in ("logging",)
+    ]
+
+
+STUB_MODULE = _members(_stub())
+
+
+def test_level_alias_lists_the_standard_names():
+    (alias,) = [
+        node
+        for node in _stub().body
+        if isinstance(node, ast.AnnAssign) and getattr(node.target, "id", "") == "Level"
+    ]
+    names = {c.value for c in ast.walk(alias.value) if isinstance(c, ast.Constant)}
+    assert {"DEBUG", "INFO", "WARNING", "ERROR", "CRITICAL"} <= names
+
+
+@pytest.mark.parametrize("name", _public(vars(ext)))
+def test_extension_members_are_declared(name):
+    assert name in STUB_MODULE
+
+
+@pytest.mark.parametrize("name", _public(vars(ext.logging)))
+def test_logging_submodule_members_are_declared(name):
+    assert name in _members(STUB_MODULE["_LoggingModule"])
+
+
+def _callables():
+    classes = {}
+    for namespace, declared in [
+        (ext, STUB_MODULE),
+        (ext.logging, _members(STUB_MODULE["_LoggingModule"])),
+    ]:
+        for name in _public(vars(namespace)):
+            obj = getattr(namespace, name)
+            if isinstance(obj, type):
+                classes[obj.__name__] = obj
+            elif callable(obj):
+                yield f"{namespace.__name__}.{name}", obj, declared[name], None
+    for name, cls in classes.items():
+        yield f"{name}.__init__", cls, STUB_MODULE[name], "__init__"
+        for attr in _public(vars(cls)):
+            yield f"{name}.{attr}", getattr(cls, attr), STUB_MODULE[name], attr
+
+
+@pytest.mark.parametrize(
+    "obj,stub,attr", [c[1:] for c in _callables()], ids=[c[0] for c in _callables()]
+)
+def test_parameters_match_the_runtime_signatures(obj, stub, attr):
+    if attr is not None:
+        members = _members(stub)
+        if attr == "__init__" and attr not in members:
+            return
+        assert attr in members, f"{attr} is not declared"
+        stub = members[attr]
+    if not isinstance(stub, ast.FunctionDef) or any(
+        isinstance(d, ast.Name) and d.id == "property" for d in stub.decorator_list
+    ):
+        return
+    runtime = _runtime_params(obj)
+    if runtime is None:
+        return
+    assert _stub_params(stub) == runtime
@@ -0,0 +1,89 @@
+"""
+Tests for single-interpreter enforcement: logxide's handler registry and worker
+threads are process-global, so importing it from a second interpreter must fail
+with a clear ImportError instead of sharing or corrupting that state.
+"""
+
+import importlib.util
+import subprocess
+import sys
+import textwrap
+
+import pytest
+
+_SUBINTERPRETERS = next(
+    (
+        name
+        for name in ("_xxsubinterpreters", "_interpreters")
+        if importlib.util.find_spec(name) is not None
+    ),
+    None,
+)
+
+pytestmark = pytest.mark.skipif(
+    _SUBINTERPRETERS is None, reason="no low-level subinterpreter module"
+)
+
+_SCRIPT = textwrap.dedent(
+    """
+    import sys, tempfile, os
+    import {module} as interpreters
+
+    run = getattr(interpreters, "run_string", None) or interpreters.exec
+    out = tempfile.mktemp()
+    code = (
+        "import sys\\n"
+        "sys.path[:0] = " + repr(sys.path) + "\\n"
+        "try:\\n"
+        "    import logxide\\n"
+        "    result = 'imported'\\n"
+        "except ImportError as e:\\n"
+        "    result = 'ImportError: ' + str(e)\\n"
+        "open(" + repr(out) + ", 'w').write(result)\\n"
+    )
+
+    if {main_first}:
+        import logxide
+
+    interp = interpreters.create()
+    run(interp, code)
+    interpreters.destroy(interp)
+    print("sub:", open(out).read())
+    os.unlink(out)
+
+    if not {main_first}:
+        try:
+            import logxide
+            print("main: imported")
+        except ImportError as e:
+            print("main: ImportError:", e)
+    """
+)
+
+
+def _run(main_first):
+    script = _SCRIPT.format(module=_SUBINTERPRETERS, main_first=main_first)
+    proc = subprocess.run(
+        [sys.executable, "-c", script],
+        capture_output=True,
+        text=True,
+        timeout=60,
+    )
+    assert proc.returncode == 0, proc.stderr
+    return proc.stdout
+
+
+def test_subinterpreter_import_rejected_when_main_owns_state():
+    out = _run(main_first=True)
+    assert "sub: ImportError: logxide supports a single interpreter" in out
+
+
+def test_main_import_rejected_after_subinterpreter_initialized():
+    out = _run(main_first=False)
+    assert "main: ImportError: logxide supports a single interpreter" in out
+
+
+def test_owning_interpreter_passes_check():
+    from logxide import logxide as ext
+
+    ext.logging.check_interpreter()
@@ -0,0 +1,109 @@
+"""
+Tests for per-handler sync_level: records at or above it bypass the queue and
+are written (and flushed) before the log call returns.
+"""
+
+import os
+import subprocess
+import sys
+import textwrap
+
+import pytest
+
+from logxide import LogRecord, RustHTTPHandler, handlers
+
+REPO_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+
+def _record(i, levelno=20):
+    return LogRecord(
+        name="sync.level.test",
+        levelno=levelno,
+        pathname="test.py",
+        lineno=i,
+        msg=f"message-{i}",
+    )
+
+
+@pytest.fixture
+def server(http_collector):
+    http_collector.delay = 0.02
+    return http_collector
+
+
+def test_critical_is_delivered_before_emit_returns(server):
+    url = server.url
+    handler = RustHTTPHandler(
+        url, batch_size=1000, flush_interval=3600, sync_level="CRITICAL"
+    )
+    try:
+        for i in range(5):
+            handler.emit(_record(i))
+        handler.emit(_record(100, levelno=50))
+        delivered = [r["msg"] for r in server.records]
+        metrics = handler.get_metrics()
+    finally:
+        handler.shutdown()
+
+    assert delivered == ["message-100"]
+    assert metrics["sync_emitted"] == 1, metrics
+    assert metrics["sink_acknowledged"] == 1, metrics
+
+
+def test_records_below_sync_level_stay_queued(server):
+    url = server.url
+    handler = RustHTTPHandler(url, batch_size=1000, flush_interval=3600)
+    handler.setSyncLevel(50)
+    try:
+        handler.emit(_record(0, levelno=40))
+        assert server.records == []
+        handler.flush()
+        metrics = handler.get_metrics()
+    finally:
+        handler.shutdown()
+
+    assert [r["msg"] for r in server.records] == ["message-0"]
+    assert metrics["sync_emitted"] == 0, metrics
+
+
+def test_sync_level_accessors(server):
+    url = server.url
+    handler = RustHTTPHandler(url)
+    try:
+        assert handler.getSyncLevel() is None
+        handler.setSyncLevel("CRITICAL")
+        assert handler.getSyncLevel() == 50
+        handler.setSyncLevel(None)
+        assert handler.getSyncLevel() is None
+    finally:
+        handler.shutdown()
+
+    stream = handlers.StreamHandler()
+    stream.setSyncLevel(40)
+    assert stream.getSyncLevel() == 40
+
+
+def test_stream_critical_survives_hard_exit():
+    script = textwrap.dedent(
+        """
+        import os, sys
+        from logxide import handlers, logging
+
+        handler = handlers.StreamHandler(sys.stdout)
+        handler.setSyncLevel(logging.CRITICAL)
+        logger = logging.getLogger("sync.level.exit")
+        logger.propagate = False
+        logger.addHandler(handler)
+        logger.critical("crash imminent")
+        os._exit(0)
+        """
+    )
+    env = dict(os.environ, PYTHONPATH=REPO_ROOT)
+    result = subprocess.run(
+        [sys.executable, "-c", script],
+        capture_output=True,
+        text=True,
+        timeout=30,
+        env=env,
+    )
+    assert "crash imminent" in result.stdout
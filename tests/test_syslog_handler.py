@@ -0,0 +1,193 @@
+"""
+Tests for SyslogHandler: RFC 3164 / RFC 5424 messages over UDP, TCP and local
+Unix sockets, with stdlib facility and severity mapping.
+"""
+
+import logging.handlers as std_handlers
+import os
+import re
+import socket
+import sys
+import tempfile
+
+import pytest
+
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def udp():
+    sock = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+    sock.bind(("127.0.0.1", 0))
+    sock.settimeout(5)
+    yield sock
+    sock.close()
+
+
+def _udp_handler(udp, **kwargs):
+    return handlers.SyslogHandler(("127.0.0.1", udp.getsockname()[1]), **kwargs)
+
+
+def test_rfc3164_message(udp, isolated_logger):
+    handler = _udp_handler(udp, app_name="billing", hostname="web1")
+    logger = isolated_logger(handler, level=logging.DEBUG)
+    logger.warning("disk %s at %d%%", "/var", 93)
+
+    message = udp.recv(65536).decode()
+    assert re.fullmatch(
+        rf"<12>\w{{3}} [ \d]\d \d\d:\d\d:\d\d web1 billing\[{os.getpid()}\]: "
+        r"disk /var at 93%",
+        message,
+    ), message
+    assert handler.get_metrics()["sink_acknowledged"] == 1
+
+
+def test_rfc5424_message(udp, isolated_logger):
+    handler = _udp_handler(
+        udp, protocol="rfc5424", app_name="billing", hostname="web 1"
+    )
+    isolated_logger(handler, level=logging.DEBUG).error("card declined")
+
+    message = udp.recv(65536).decode()
+    assert re.fullmatch(
+        rf"<11>1 \d{{4}}-\d\d-\d\dT\d\d:\d\d:\d\d\.\d{{6}}[+-]\d\d:\d\d web1 "
+        rf"billing {os.getpid()} - - card declined",
+        message,
+    ), message
+
+
+@pytest.mark.parametrize(
+    "level, severity",
+    [
+        (logging.DEBUG, 7),
+        (logging.INFO, 6),
+        (logging.WARNING, 4),
+        (logging.ERROR, 3),
+        (logging.CRITICAL, 2),
+    ],
+)
+def test_facility_and_severity_match_the_stdlib(udp, level, severity, isolated_logger):
+    handler = _udp_handler(udp, facility="local3")
+    isolated_logger(handler, level=logging.DEBUG).log(level, "hi")
+
+    pri = int(udp.recv(65536).decode()[1:].split(">", 1)[0])
+    assert pri == std_handlers.SysLogHandler.LOG_LOCAL3 * 8 + severity
+    stdlib = std_handlers.SysLogHandler(("127.0.0.1", 9))
+    try:
+        name = stdlib.mapPriority(logging.getLevelName(level))
+        assert stdlib.priority_names[name] == severity
+    finally:
+        stdlib.close()
+
+
+def test_custom_levels_take_the_severity_below(udp):
+    from logxide.logxide import logging as native_logging
+
+    handler = _udp_handler(udp)
+    record = native_logging.LogRecord("app", 25, "", 0, "m")
+
+    assert handler._inner.buildMessage(record, "m").startswith("<14>")
+
+
+def test_custom_formatter_and_filter(udp, isolated_logger):
+    handler = _udp_handler(udp, hostname="h", app_name="a")
+    handler.setFormatter(logging.Formatter("%(name)s %(levelname)s %(message)s"))
+    handler.addFilter(lambda record: "secret" not in record.getMessage())
+    logger = isolated_logger(handler, level=logging.DEBUG)
+    logger.info("secret token")
+    logger.info("hello")
+
+    message = udp.recv(65536).decode()
+    assert message.endswith(f"a[{os.getpid()}]: {logger.name} INFO hello")
+    assert handler.get_metrics()["emitted"] == 1
+
+
+def test_tcp_uses_octet_counting(isolated_logger):
+    server = socket.socket(socket.AF_INET, socket.SOCK_STREAM)
+    server.bind(("127.0.0.1", 0))
+    server.listen(1)
+    server.settimeout(5)
+    try:
+        handler = handlers.SyslogHandler(
+            server.getsockname(), socktype=socket.SOCK_STREAM, protocol="rfc5424"
+        )
+        conn, _ = server.accept()
+        conn.settimeout(5)
+        logger = isolated_logger(handler, level=logging.DEBUG)
+        logger.info("one")
+        logger.info("line one\nline two")
+
+        data = b""
+        while data.count(b"<14>1 ") < 2:
+            data += conn.recv(65536)
+        conn.close()
+    finally:
+        server.close()
+
+    messages = []
+    while data:
+        length, rest = data.split(b" ", 1)
+        messages.append(rest[: int(length)].decode())
+        data = rest[int(length) :]
+    assert messages[0].endswith(" - - one")
+    assert messages[1].endswith(" - - line one\nline two")
+
+
+@pytest.mark.skipif(sys.platform == "win32", reason="needs Unix sockets")
+def test_unix_datagram_socket(isolated_logger):
+    path = os.path.join(tempfile.mkdtemp(), "log")
+    sock = socket.socket(socket.AF_UNIX, socket.SOCK_DGRAM)
+    sock.bind(path)
+    sock.settimeout(5)
+    try:
+        handler = handlers.SyslogHandler(path, app_name="worker")
+        isolated_logger(handler, level=logging.DEBUG).info("local")
+        message = sock.recv(65536).decode()
+    finally:
+        sock.close()
+
+    # No hostname on a local socket: the daemon adds it, as with syslog(3).
+    assert re.fullmatch(
+        rf"<14>\w{{3}} [ \d]\d \d\d:\d\d:\d\d worker\[{os.getpid()}\]: local", message
+    ), message
+
+
+def test_is_a_stdlib_syslog_handler(udp):
+    handler = _udp_handler(udp)
+
+    assert isinstance(handler, std_handlers.SysLogHandler)
+    handler.close()
+
+
+def test_invalid_arguments():
+    with pytest.raises(ValueError):
+        handlers.SyslogHandler(("127.0.0.1", 514), protocol="rfc9999")
+    with pytest.raises(ValueError):
+        handlers.SyslogHandler(("127.0.0.1", 514), facility=24)
+    with pytest.raises(KeyError):
+        handlers.SyslogHandler(("127.0.0.1", 514), facility="nope")
+    with pytest.raises(OSError):
+        handlers.SyslogHandler(os.path.join(tempfile.mkdtemp(), "missing"))
+
+
+def test_dict_config_promotes_the_stdlib_handler(udp, isolated_logger):
+    from logxide.config import dictConfig
+
+    name = isolated_logger.name("test.syslog.config")
+    dictConfig(
+        {
+            "version": 1,
+            "handlers": {
+                "syslog": {
+                    "class": "logging.handlers.SysLogHandler",
+                    "address": ["127.0.0.1", udp.getsockname()[1]],
+                    "facility": "local0",
+                }
+            },
+            "loggers": {name: {"handlers": ["syslog"], "level": "INFO"}},
+            "disable_existing_loggers": False,
+        }
+    )
+    handler = logging.getLogger(name).handlers[0]
+
+    assert isinstance(handler, handlers.SyslogHandler)
@@ -0,0 +1,93 @@
+"""
+Tests for tail: the last lines of a log file, continued into its rotated backups, and
+following a file across a rotation.
+"""
+
+import gzip
+import threading
+
+import pytest
+
+import logxide
+from logxide import handlers
+
+
+def test_last_lines_leave_out_a_partial_line(tmp_path):
+    path = tmp_path / "app.log"
+    path.write_text("".join(f"line {i}\n" for i in range(1000)) + "half writt")
+    assert logxide.tail(path, n=3) == ["line 997", "line 998", "line 999"]
+    assert logxide.tail(str(path), n=0) == []
+    assert len(logxide.tail(path)) == 100
+
+
+def test_long_files_are_read_from_the_end(tmp_path):
+    path = tmp_path / "app.log"
+    line = "x" * 1000
+    path.write_text(f"{line}\n" * 500 + "last\r\n")
+    assert logxide.tail(path, n=2) == [line, "last"]
+
+
+def test_backups_fill_in_older_lines(tmp_path):
+    path = tmp_path / "app.log"
+    path.write_text("e\nf\n")
+    (tmp_path / "app.log.1").write_text("c\nd\n")
+    (tmp_path / "app.log.2").write_text("a\nb\n")
+    (tmp_path / "app.log.lock").write_text("not a backup\n")
+    assert logxide.tail(path, n=5) == ["b", "c", "d", "e", "f"]
+    assert logxide.tail(path, n=50) == ["a", "b", "c", "d", "e", "f"]
+
+
+def test_dated_and_gzipped_backups(tmp_path):
+    if "compression" not in logxide.runtime_info()["features"]:
+        pytest.skip("built without the compression feature")
+    path = tmp_path / "app.log"
+    path.write_text("today\n")
+    (tmp_path / "app.log.2024-05-02").write_text("yesterday\n")
+    with gzip.open(tmp_path / "app.log.2024-05-01.gz", "wt") as f:
+        f.write("before\n")
+    assert logxide.tail(path, n=3) == ["before", "yesterday", "today"]
+
+
+def test_rotating_handler_target(tmp_path):
+    handler = handlers.RotatingFileHandler(
+        str(tmp_path / "app.log"), maxBytes=64, backupCount=5
+    )
+    logger = logxide.logging.getLogger("tail_rotating")
+    logger.addHandler(handler)
+    try:
+        for i in range(20):
+            logger.warning("message %02d", i)
+        logxide.flush()
+        handler.flush()
+    finally:
+        logger.removeHandler(handler)
+    assert logxide.tail(handler, n=5) == [f"message {i}" for i in range(15, 20)]
+
+
+def test_missing_file_raises(tmp_path):
+    with pytest.raises(FileNotFoundError):
+        logxide.tail(tmp_path / "missing.log")
+
+
+def test_follow_continues_across_a_rotation(tmp_path):
+    path = tmp_path / "app.log"
+    path.write_text("old 1\nold 2\nhal")
+    lines = []
+    with logxide.tail(path, n=1, follow=True) as follower:
+        assert next(follower) == "old 2"
+
+        def writer():
+            with open(path, "a") as f:
+                f.write("f written\nold 3\n")
+            path.rename(tmp_path / "app.log.1")
+            path.write_text("new 1\n")
+
+        thread = threading.Thread(target=writer)
+        thread.start()
+        for line in follower:
+            lines.append(line)
+            if line == "new 1":
+                break
+        thread.join()
+    assert lines == ["half written", "old 3", "new 1"]
+    assert list(follower) == []
@@ -0,0 +1,162 @@
+"""
+Tests for background thread names (logxide-<kind>-<n>), configure_threads(nice=...)
+and tune(io_threads=..., pin_to_cores=...), read back from /proc on Linux.
+"""
+
+import os
+import sys
+import time
+from pathlib import Path
+
+import pytest
+
+import logxide
+from logxide import handlers
+
+pytestmark = pytest.mark.skipif(
+    not sys.platform.startswith("linux"), reason="reads thread names from /proc"
+)
+
+
+def _threads():
+    """{name: nice} for every thread of this process."""
+    threads = {}
+    for task in Path(f"/proc/{os.getpid()}/task").iterdir():
+        try:
+            name = (task / "comm").read_text().strip()
+            stat = (task / "stat").read_text()
+        except OSError:
+            continue
+        # Fields after the parenthesized name; nice is field 19.
+        threads[name] = int(stat.rsplit(")", 1)[1].split()[16])
+    return threads
+
+
+def _allowed_cores(name):
+    """The cores the thread called `name` may run on."""
+    for task in Path(f"/proc/{os.getpid()}/task").iterdir():
+        if (task / "comm").read_text().strip() != name:
+            continue
+        for line in (task / "status").read_text().splitlines():
+            if line.startswith("Cpus_allowed_list:"):
+                cores = set()
+                for part in line.split(":", 1)[1].strip().split(","):
+                    low, _, high = part.partition("-")
+                    cores.update(range(int(low), int(high or low) + 1))
+                return cores
+    raise AssertionError(f"no thread {name}")
+
+
+def _started(before):
+    """The thread started since `before`; it names itself once it runs."""
+    deadline = time.monotonic() + 5
+    while time.monotonic() < deadline:
+        started = {
+            name: nice
+            for name, nice in _threads().items()
+            if name.startswith("logxide-") and name not in before
+        }
+        if started:
+            assert len(started) == 1, started
+            return started.popitem()
+        time.sleep(0.01)
+    raise AssertionError("no logxide thread started")
+
+
+@pytest.fixture
+def reset_threads():
+    yield
+    logxide.configure_threads()
+    logxide.tune()
+
+
+def _http_handler(tmp_path):
+    return handlers.HTTPHandler(
+        "http://collector.invalid", dry_run=str(tmp_path / "out.jsonl")
+    )
+
+
+def test_worker_threads_are_named(tmp_path):
+    before = set(_threads())
+    handler = _http_handler(tmp_path)
+    try:
+        name, _ = _started(before)
+        assert name.startswith("logxide-http-")
+    finally:
+        handler.close()
+
+
+def test_threads_start_with_the_configured_nice_value(tmp_path, reset_threads):
+    base = os.nice(0)
+    before = set(_threads())
+    logxide.configure_threads(nice=min(base + 5, 19))
+    handler = _http_handler(tmp_path)
+    try:
+        _, nice = _started(before)
+        assert nice == min(base + 5, 19)
+    finally:
+        handler.close()
+
+
+def test_nice_out_of_range_is_rejected(reset_threads):
+    with pytest.raises(ValueError):
+        logxide.configure_threads(nice=20)
+
+
+def test_tune_pins_new_threads(tmp_path, reset_threads):
+    core = max(os.sched_getaffinity(0))
+    before = set(_threads())
+    assert logxide.tune(pin_to_cores=[core, core]) == {
+        "io_threads": None,
+        "pin_to_cores": [core],
+        "pinned": True,
+    }
+    handler = _http_handler(tmp_path)
+    try:
+        name, _ = _started(before)
+        assert _allowed_cores(name) == {core}
+    finally:
+        handler.close()
+
+
+@pytest.mark.skipif(
+    len(os.sched_getaffinity(0)) < 2, reason="needs two cores to spread threads"
+)
+def test_io_threads_take_core_groups_in_turn(tmp_path, reset_threads):
+    cores = sorted(os.sched_getaffinity(0))[:2]
+    logxide.tune(io_threads=2, pin_to_cores=cores)
+    pinned = []
+    for _ in range(3):
+        before = set(_threads())
+        handler = _http_handler(tmp_path)
+        name, _ = _started(before)
+        pinned.append(_allowed_cores(name))
+        handler.close()
+    assert pinned == [{cores[0]}, {cores[1]}, {cores[0]}]
+
+
+def test_tune_without_arguments_unpins(tmp_path, reset_threads):
+    logxide.tune(pin_to_cores=[max(os.sched_getaffinity(0))])
+    assert logxide.tune()["pinned"] is False
+    before = set(_threads())
+    handler = _http_handler(tmp_path)
+    try:
+        name, _ = _started(before)
+        assert _allowed_cores(name) == os.sched_getaffinity(0)
+    finally:
+        handler.close()
+
+
+@pytest.mark.parametrize(
+    "kwargs",
+    [
+        {"pin_to_cores": []},
+        {"pin_to_cores": [1024]},
+        {"io_threads": 2},
+        {"io_threads": 0, "pin_to_cores": [0]},
+        {"io_threads": 2, "pin_to_cores": [0]},
+    ],
+)
+def test_tune_rejects_bad_arguments(kwargs, reset_threads):
+    with pytest.raises(ValueError):
+        logxide.tune(**kwargs)
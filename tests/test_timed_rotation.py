@@ -7,6 +7,8 @@ import gzip
 import os
 import time
 
+import pytest
+
 from logxide import logging
 from logxide.handlers import TimedRotatingFileHandler
 
@@ -143,3 +145,32 @@ class TestTimedRotatingFileHandler:
         formatter = stdlib_logging.Formatter("%(message)s")
         handler.setFormatter(formatter)
         # Should not raise
+
+    def test_backup_names_follow_stdlib(self, tmp_path):
+        """Backups are named <file>.<date suffix of the finished period>."""
+        log_file = str(tmp_path / "test.log")
+        handler = TimedRotatingFileHandler(log_file, when="S", backupCount=3)
+        logger = logging.getLogger("test.timed.names")
+        logger.addHandler(handler)
+        logger.setLevel(logging.DEBUG)
+
+        logger.info("first")
+        handler.flush()
+        time.sleep(1.2)
+        logger.info("second")
+        handler.flush()
+
+        (backup,) = glob.glob(f"{log_file}.*")
+        suffix = backup[len(log_file) + 1 :]
+        time.strptime(suffix, "%Y-%m-%d_%H-%M-%S")
+        with open(backup) as f:
+            assert "first" in f.read()
+        with open(log_file) as f:
+            assert f.read().strip().endswith("second")
+
+    def test_invalid_when_is_rejected(self, tmp_path):
+        """Unknown `when` values and a zero interval raise ValueError."""
+        log_file = str(tmp_path / "test.log")
+        for kwargs in ({"when": "X"}, {"when": "W7"}, {"when": "S", "interval": 0}):
+            with pytest.raises(ValueError):
+                TimedRotatingFileHandler(log_file, **kwargs)
@@ -0,0 +1,81 @@
+"""
+Tests for logger.timer(): start/end records with monotonic elapsed time, as a
+context manager and as a decorator.
+"""
+
+import time
+
+import pytest
+
+from logxide import handlers, logging
+
+
+@pytest.fixture
+def capture(isolated_logger):
+    mh = handlers.MemoryHandler()
+    yield isolated_logger(mh, level=logging.DEBUG), mh
+
+
+def test_context_manager_logs_start_and_end(capture):
+    logger, mh = capture
+    with logger.timer("db.query", extra={"table": "users"}) as t:
+        time.sleep(0.01)
+
+    start, end = mh.records
+    assert start.getMessage() == "db.query started"
+    assert start.timer_event == "start"
+    assert end.timer_event == "end"
+    assert end.timer == "db.query"
+    assert end.table == "users"
+    assert isinstance(end.elapsed_ms, float)
+    assert end.elapsed_ms >= 10.0
+    assert end.getMessage() == f"db.query finished in {end.elapsed_ms:.3f}ms"
+    assert start.levelno == end.levelno == logging.DEBUG
+    assert t.elapsed_ms == end.elapsed_ms
+
+
+def test_exception_is_recorded_and_propagates(capture):
+    logger, mh = capture
+    with pytest.raises(ValueError), logger.timer("boom"):
+        raise ValueError("x")
+
+    end = mh.records[-1]
+    assert end.timer_error == "ValueError"
+    assert "boom failed after" in end.getMessage()
+
+
+def test_decorator_times_each_call(capture):
+    logger, mh = capture
+
+    @logger.timer("work", level="INFO", log_start=False)
+    def work(x):
+        """Double x."""
+        return x * 2
+
+    assert work(2) == 4
+    assert work(3) == 6
+    assert work.__name__ == "work"
+    assert work.__doc__ == "Double x."
+    assert [r.timer_event for r in mh.records] == ["end", "end"]
+    assert all(r.levelno == logging.INFO for r in mh.records)
+
+
+def test_decorator_on_method(capture):
+    logger, mh = capture
+
+    class Service:
+        @logger.timer("service.call")
+        def call(self, y):
+            return y + 1
+
+    assert Service().call(1) == 2
+    assert [r.timer_event for r in mh.records] == ["start", "end"]
+
+
+def test_disabled_level_still_measures(capture):
+    logger, mh = capture
+    logger.setLevel(logging.WARNING)
+    with logger.timer("quiet") as t:
+        pass
+    assert mh.records == []
+    assert t.elapsed_ms is not None
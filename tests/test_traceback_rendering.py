@@ -0,0 +1,85 @@
+"""
+Tests for configure_tracebacks(): locals capture, frame limits, and
+most-recent-call-first ordering of exc_info tracebacks.
+"""
+
+import pytest
+
+import logxide
+from logxide import handlers, logging
+
+
+def _inner(token):
+    secret_local = token * 2  # noqa: F841
+    raise ValueError("inner failure")
+
+
+def _middle(token):
+    _inner(token)
+
+
+def _outer(token):
+    _middle(token)
+
+
+@pytest.fixture
+def capture():
+    logger = logging.getLogger("test.traceback.render")
+    logger.setLevel(logging.DEBUG)
+    mh = handlers.MemoryHandler()
+    logger.addHandler(mh)
+    yield logger, mh
+    logger.removeHandler(mh)
+    logxide.configure_tracebacks()
+
+
+def _log_failure(logger):
+    try:
+        _outer("abc")
+    except ValueError:
+        logger.exception("failed")
+
+
+def test_default_rendering_matches_stdlib(capture):
+    import traceback
+
+    logger, mh = capture
+    try:
+        _outer("abc")
+    except ValueError:
+        expected = traceback.format_exc()
+        logger.exception("failed")
+    assert mh.records[-1].exc_text == expected
+
+
+def test_show_locals(capture):
+    logger, mh = capture
+    logxide.configure_tracebacks(show_locals=True)
+    _log_failure(logger)
+    text = mh.records[-1].exc_text
+    assert "secret_local = 'abcabc'" in text
+    assert "ValueError: inner failure" in text
+
+
+def test_max_frames_keeps_innermost(capture):
+    logger, mh = capture
+    logxide.configure_tracebacks(max_frames=1)
+    _log_failure(logger)
+    text = mh.records[-1].exc_text
+    assert "in _inner" in text
+    assert "in _middle" not in text
+    assert "in _outer" not in text
+
+
+def test_most_recent_first(capture):
+    logger, mh = capture
+    logxide.configure_tracebacks(most_recent_first=True)
+    _log_failure(logger)
+    text = mh.records[-1].exc_text
+    assert text.startswith("Traceback (most recent call first):")
+    assert text.index("in _inner") < text.index("in _middle") < text.index("in _outer")
+
+
+def test_max_frames_rejects_zero():
+    with pytest.raises(ValueError):
+        logxide.configure_tracebacks(max_frames=0)
@@ -0,0 +1,94 @@
+"""
+Tests for WatchedFileHandler: writes follow the path after the file is moved or
+deleted, the way logrotate leaves it.
+"""
+
+import logging
+import logging.handlers
+import os
+import sys
+
+import pytest
+
+from logxide import handlers
+from logxide.logxide import logging as native_logging
+
+pytestmark = pytest.mark.skipif(
+    sys.platform == "win32", reason="needs device/inode numbers"
+)
+
+
+def _logger(tmp_path, handler):
+    logger = native_logging.getLogger(f"watched.{tmp_path.name}")
+    logger.setLevel(logging.INFO)
+    logger.propagate = False
+    logger.addHandler(handler)
+    return logger
+
+
+def _read(path):
+    with open(path) as f:
+        return f.read().splitlines()
+
+
+def test_reopens_after_rename(tmp_path):
+    path = tmp_path / "app.log"
+    handler = handlers.WatchedFileHandler(str(path))
+    logger = _logger(tmp_path, handler)
+
+    logger.info("before")
+    handler.flush()
+    os.rename(path, tmp_path / "app.log.1")
+    logger.info("after")
+    handler.flush()
+
+    assert _read(tmp_path / "app.log.1") == ["before"]
+    assert _read(path) == ["after"]
+
+
+def test_recreates_deleted_file(tmp_path):
+    path = tmp_path / "app.log"
+    handler = handlers.WatchedFileHandler(str(path))
+    logger = _logger(tmp_path, handler)
+
+    logger.info("first")
+    handler.flush()
+    os.remove(path)
+    logger.info("second")
+    handler.flush()
+
+    assert _read(path) == ["second"]
+
+
+def test_unflushed_lines_stay_in_the_rotated_file(tmp_path):
+    path = tmp_path / "app.log"
+    handler = handlers.WatchedFileHandler(str(path))
+    logger = _logger(tmp_path, handler)
+
+    logger.info("buffered")
+    os.rename(path, tmp_path / "app.log.1")
+    logger.info("after")
+    handler.flush()
+
+    assert _read(tmp_path / "app.log.1") == ["buffered"]
+    assert _read(path) == ["after"]
+
+
+def test_plain_file_handler_keeps_the_open_file(tmp_path):
+    path = tmp_path / "app.log"
+    handler = handlers.FileHandler(str(path))
+    logger = _logger(tmp_path, handler)
+
+    logger.info("before")
+    os.rename(path, tmp_path / "app.log.1")
+    logger.info("after")
+    handler.flush()
+
+    assert _read(tmp_path / "app.log.1") == ["before", "after"]
+    assert not path.exists()
+
+
+def test_is_a_stdlib_watched_file_handler(tmp_path):
+    handler = handlers.WatchedFileHandler(str(tmp_path / "app.log"))
+    assert isinstance(handler, logging.handlers.WatchedFileHandler)
+    assert isinstance(handler, handlers.FileHandler)
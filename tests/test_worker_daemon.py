@@ -0,0 +1,93 @@
+"""
+Tests for daemon vs non-daemon network handler workers: non-daemon workers drain
+at interpreter exit, daemon workers are abandoned so they never delay exit.
+"""
+
+import subprocess
+import sys
+import textwrap
+import time
+
+import pytest
+
+import logxide
+from logxide import RustHTTPHandler
+
+_SCRIPT = textwrap.dedent(
+    """
+    import atexit, json, threading, time
+    from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+
+    received = []
+
+    class Handler(BaseHTTPRequestHandler):
+        protocol_version = "HTTP/1.0"
+
+        def do_POST(self):
+            time.sleep(0.2)
+            body = self.rfile.read(int(self.headers.get("Content-Length", 0)))
+            received.extend(json.loads(body))
+            self.send_response(200)
+            self.end_headers()
+
+        def log_message(self, *args):
+            pass
+
+    server = ThreadingHTTPServer(("127.0.0.1", 0), Handler)
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+
+    # Registered before logxide, so it runs after logxide's exit hook.
+    atexit.register(lambda: print("received", len(received), flush=True))
+
+    import logxide
+    from logxide import LogRecord, RustHTTPHandler
+
+    handler = RustHTTPHandler(
+        f"http://127.0.0.1:{server.server_address[1]}",
+        batch_size=1,
+        flush_interval=3600,
+        shutdown_timeout=None,
+        daemon=DAEMON,
+    )
+    for i in range(5):
+        handler.emit(LogRecord(name="t", levelno=20, pathname="t.py", lineno=i, msg=str(i)))
+    """
+)
+
+
+def _run(daemon):
+    started = time.monotonic()
+    proc = subprocess.run(
+        [sys.executable, "-c", _SCRIPT.replace("DAEMON", repr(daemon))],
+        capture_output=True,
+        text=True,
+        timeout=60,
+    )
+    return proc, time.monotonic() - started
+
+
+def test_non_daemon_worker_drains_at_exit():
+    proc, _ = _run(daemon=False)
+    assert proc.returncode == 0, proc.stderr
+    assert "received 5" in proc.stdout
+
+
+def test_daemon_worker_does_not_block_exit():
+    proc, elapsed = _run(daemon=True)
+    assert proc.returncode == 0, proc.stderr
+    assert "received 5" not in proc.stdout
+    assert "daemon worker abandoned at exit" in proc.stderr
+    assert elapsed < 5
+
+
+@pytest.fixture
+def restore_default():
+    yield
+    logxide.configure_workers(daemon=False)
+
+
+def test_global_default_applies_to_new_handlers(restore_default):
+    assert RustHTTPHandler("http://127.0.0.1:9").daemon is False
+    logxide.configure_workers(daemon=True)
+    assert RustHTTPHandler("http://127.0.0.1:9").daemon is True
+    assert RustHTTPHandler("http://127.0.0.1:9", daemon=False).daemon is False
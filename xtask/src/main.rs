@@ -0,0 +1,865 @@
+//! Repository tasks, run with `cargo xtask <task>`.
+//!
+//! `stubs` writes `logxide/logxide.pyi`, the type stub of the extension module, and
+//! `stubs --check` fails when the committed stub is out of date instead.
+//!
+//! The stub is derived from the `#[pyclass]`, `#[pymethods]` and `#[pyfunction]` items
+//! in `src/` (names, `#[pyo3(signature = ...)]` defaults, Rust parameter and return
+//! types, doc comments) and from the registrations in `src/lib.rs`, which decide what
+//! lives at the top level and what in the `logging` submodule. It describes a build
+//! with every cargo feature enabled. The file is committed and only rewritten when its
+//! content changes, so a run that leaves the signatures alone does not touch it.
+
+use proc_macro2::{TokenStream, TokenTree};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use syn::{Attribute, Expr, FnArg, GenericArgument, Lit, Meta, Pat, PathArguments, Type};
+
+const STUB: &str = "logxide/logxide.pyi";
+
+/// Level names `check_level()` accepts besides integers (case-insensitively at runtime).
+const LEVEL_NAMES: &[&str] = &[
+    "CRITICAL", "FATAL", "ERROR", "WARN", "WARNING", "INFO", "DEBUG", "NOTSET",
+];
+
+const LINE_LENGTH: usize = 88;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["stubs"] => {
+            let path = root().join(STUB);
+            let stub = render_stub();
+            if std::fs::read_to_string(&path).ok().as_deref() != Some(stub.as_str()) {
+                if let Err(e) = std::fs::write(&path, stub) {
+                    eprintln!("cannot write {}: {e}", path.display());
+                    return ExitCode::FAILURE;
+                }
+                println!("wrote {STUB}");
+            }
+            ExitCode::SUCCESS
+        }
+        ["stubs", "--check"] => {
+            if stub_is_current() {
+                ExitCode::SUCCESS
+            } else {
+                eprintln!("{STUB} is out of date: run `cargo xtask stubs` and commit it");
+                ExitCode::FAILURE
+            }
+        }
+        _ => {
+            eprintln!("usage: cargo xtask stubs [--check]");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The repository root, the parent of `xtask/`.
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask/ has a parent")
+        .to_path_buf()
+}
+
+fn stub_is_current() -> bool {
+    std::fs::read_to_string(root().join(STUB)).ok() == Some(render_stub())
+}
+
+/// The stub for the sources in `src/`.
+fn render_stub() -> String {
+    let src = root().join("src");
+    let mut api = Api::default();
+    let mut sources: Vec<_> = std::fs::read_dir(&src)
+        .expect("read src/")
+        .map(|entry| entry.expect("read src/ entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    sources.sort();
+    for path in &sources {
+        let source = std::fs::read_to_string(path).expect("read source file");
+        // Files that do not parse fail the real compile with a better message.
+        let Ok(file) = syn::parse_file(&source) else {
+            continue;
+        };
+        let module = path.file_stem().unwrap().to_string_lossy().into_owned();
+        api.collect(&module, &file.items);
+    }
+    let lib = std::fs::read_to_string(src.join("lib.rs")).expect("read src/lib.rs");
+    let registry = Registry::parse(&lib);
+    api.render(&registry)
+}
+
+/// What `src/lib.rs` adds to the extension module (`m`) and its `logging` submodule.
+#[derive(Default)]
+struct Registry {
+    /// Rust struct names, in registration order.
+    top_classes: Vec<String>,
+    logging_classes: Vec<String>,
+    /// `module::function` paths, in registration order.
+    top_functions: Vec<String>,
+    logging_functions: Vec<String>,
+}
+
+impl Registry {
+    fn parse(lib: &str) -> Self {
+        let code: String = lib.split_whitespace().collect();
+        let mut registry = Registry::default();
+        for (module, classes) in [
+            ("logging_module", &mut registry.logging_classes),
+            ("m", &mut registry.top_classes),
+        ] {
+            let marker = format!("{module}.add_class::<");
+            let mut rest = code.as_str();
+            while let Some(start) = rest.find(&marker) {
+                // `m.` also ends `logging_module.`; require a statement boundary.
+                let boundary = code.len() - rest.len() + start;
+                if boundary > 0 && code.as_bytes()[boundary - 1].is_ascii_alphanumeric() {
+                    rest = &rest[start + marker.len()..];
+                    continue;
+                }
+                rest = &rest[start + marker.len()..];
+                let path = &rest[..rest.find('>').unwrap_or(0)];
+                classes.push(path.rsplit("::").next().unwrap_or(path).to_string());
+            }
+        }
+        let mut rest = code.as_str();
+        while let Some(start) = rest.find("wrap_pyfunction!(") {
+            rest = &rest[start + "wrap_pyfunction!(".len()..];
+            let Some((path, tail)) = rest.split_once(',') else {
+                break;
+            };
+            let target = &tail[..tail.find(')').unwrap_or(0)];
+            if target.trim_start_matches('&') == "logging_module" {
+                registry.logging_functions.push(path.to_string());
+            } else {
+                registry.top_functions.push(path.to_string());
+            }
+        }
+        registry
+    }
+}
+
+#[derive(Default)]
+struct Api {
+    /// By Rust struct name.
+    classes: HashMap<String, Class>,
+    /// Struct names in source order, for classes that are not registered.
+    class_order: Vec<String>,
+    /// By `module::function` path.
+    functions: HashMap<String, Function>,
+}
+
+struct Class {
+    py_name: String,
+    doc: String,
+    fields: Vec<Field>,
+    methods: Vec<Function>,
+}
+
+struct Field {
+    name: String,
+    ty: Type,
+    settable: bool,
+    doc: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Function,
+    Method,
+    StaticMethod,
+    ClassMethod,
+    Constructor,
+    Getter,
+    Setter,
+}
+
+struct Function {
+    py_name: String,
+    kind: Kind,
+    doc: String,
+    /// Python-visible parameters, `self` / `cls` / `py` excluded.
+    params: Vec<Param>,
+    ret: Option<Type>,
+}
+
+enum Param {
+    Named {
+        name: String,
+        ty: Option<Box<Type>>,
+        default: Option<String>,
+    },
+    Args(String),
+    Kwargs(String),
+    PositionalOnly,
+    KeywordOnly,
+}
+
+/// Options read from `#[pyclass(...)]`, `#[pyfunction(...)]` and `#[pyo3(...)]`.
+#[derive(Default)]
+struct Pyo3Options {
+    name: Option<String>,
+    signature: Option<TokenStream>,
+    get: bool,
+    set: bool,
+}
+
+fn has_attr(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr| attr.path().is_ident(name))
+}
+
+fn pyo3_options(attrs: &[Attribute], names: &[&str]) -> Pyo3Options {
+    let mut options = Pyo3Options::default();
+    for attr in attrs {
+        if !names.iter().any(|name| attr.path().is_ident(name)) {
+            continue;
+        }
+        if !matches!(attr.meta, Meta::List(_)) {
+            continue;
+        }
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                options.name = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+            } else if meta.path.is_ident("signature") {
+                if let TokenTree::Group(group) = meta.value()?.parse::<TokenTree>()? {
+                    options.signature = Some(group.stream());
+                }
+            } else if meta.path.is_ident("get") {
+                options.get = true;
+            } else if meta.path.is_ident("set") {
+                options.set = true;
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.input.parse::<TokenTree>()?;
+            }
+            Ok(())
+        });
+    }
+    options
+}
+
+/// The argument of `#[getter(name)]` / `#[setter(name)]`, if any.
+fn accessor_name(attrs: &[Attribute], name: &str) -> Option<String> {
+    let attr = attrs.iter().find(|attr| attr.path().is_ident(name))?;
+    match &attr.meta {
+        Meta::List(list) => Some(list.tokens.to_string()),
+        _ => None,
+    }
+}
+
+fn doc(attrs: &[Attribute]) -> String {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(nv) if nv.path.is_ident("doc") => match &nv.value {
+                Expr::Lit(syn::ExprLit {
+                    lit: Lit::Str(s), ..
+                }) => Some(s.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|line| {
+            line.strip_prefix(' ')
+                .unwrap_or(&line)
+                .trim_end()
+                .to_string()
+        })
+        .collect();
+    lines.join("\n").trim().to_string()
+}
+
+/// The innermost type argument of `Py<T>`, `Bound<'_, T>`, `PyRef<T>`, ... or `&T`.
+fn generic_args(ty: &Type) -> Vec<&Type> {
+    match ty {
+        Type::Path(path) => match &path.path.segments.last().unwrap().arguments {
+            PathArguments::AngleBracketed(args) => args
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+fn type_ident(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Reference(reference) => type_ident(&reference.elem),
+        Type::Path(path) => Some(path.path.segments.last()?.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Whether a Rust parameter is supplied by PyO3 rather than by the caller.
+fn is_implicit(ty: &Type) -> bool {
+    matches!(type_ident(ty).as_deref(), Some("Python"))
+}
+
+/// `slf: PyRef<Self>`, `Py<Self>`, `Bound<Self>`, ...
+fn is_self_type(ty: &Type) -> bool {
+    let ty = match ty {
+        Type::Reference(reference) => &reference.elem,
+        ty => ty,
+    };
+    generic_args(ty)
+        .last()
+        .is_some_and(|inner| type_ident(inner).as_deref() == Some("Self"))
+}
+
+fn function(
+    py_name: String,
+    kind: Kind,
+    attrs: &[Attribute],
+    sig: &syn::Signature,
+    options: &Pyo3Options,
+) -> Function {
+    let mut typed: Vec<(String, Type)> = Vec::new();
+    let mut first = true;
+    for input in &sig.inputs {
+        let FnArg::Typed(arg) = input else {
+            first = false;
+            continue;
+        };
+        let Pat::Ident(ident) = &*arg.pat else {
+            continue;
+        };
+        let skip_receiver = first
+            && match kind {
+                Kind::ClassMethod => true,
+                Kind::Function | Kind::StaticMethod | Kind::Constructor => false,
+                _ => is_self_type(&arg.ty),
+            };
+        first = false;
+        if skip_receiver || is_implicit(&arg.ty) {
+            continue;
+        }
+        typed.push((ident.ident.to_string(), (*arg.ty).clone()));
+    }
+    let type_of = |name: &str| {
+        typed
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, ty)| ty.clone())
+    };
+    let params = match &options.signature {
+        Some(signature) => parse_signature(signature.clone())
+            .into_iter()
+            .map(|param| match param {
+                Param::Named { name, default, .. } => Param::Named {
+                    ty: type_of(&name).map(Box::new),
+                    name,
+                    default,
+                },
+                other => other,
+            })
+            .collect(),
+        None => typed
+            .into_iter()
+            .map(|(name, ty)| Param::Named {
+                name,
+                ty: Some(Box::new(ty)),
+                default: None,
+            })
+            .collect(),
+    };
+    let ret = match &sig.output {
+        syn::ReturnType::Default => None,
+        syn::ReturnType::Type(_, ty) => Some((**ty).clone()),
+    };
+    Function {
+        py_name,
+        kind,
+        doc: doc(attrs),
+        params,
+        ret,
+    }
+}
+
+/// Split `#[pyo3(signature = (...))]` into parameters.
+fn parse_signature(signature: TokenStream) -> Vec<Param> {
+    let mut pieces: Vec<Vec<TokenTree>> = vec![Vec::new()];
+    for token in signature {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => pieces.push(Vec::new()),
+            _ => pieces.last_mut().unwrap().push(token),
+        }
+    }
+    pieces
+        .into_iter()
+        .filter(|piece| !piece.is_empty())
+        .map(|piece| {
+            let stars = piece
+                .iter()
+                .take_while(|token| matches!(token, TokenTree::Punct(p) if p.as_char() == '*'))
+                .count();
+            let name = piece.get(stars).map(|token| token.to_string());
+            match (stars, name) {
+                (1, None) => Param::KeywordOnly,
+                (1, Some(name)) => Param::Args(name),
+                (_, Some(name)) if stars >= 2 => Param::Kwargs(name),
+                (_, Some(name)) if name == "/" => Param::PositionalOnly,
+                (_, Some(name)) => {
+                    let default = match piece.get(1) {
+                        Some(TokenTree::Punct(p)) if p.as_char() == '=' => {
+                            Some(python_default(piece[2..].iter().cloned().collect()))
+                        }
+                        _ => None,
+                    };
+                    Param::Named {
+                        name,
+                        ty: None,
+                        default,
+                    }
+                }
+                _ => Param::KeywordOnly,
+            }
+        })
+        .collect()
+}
+
+/// A Rust default expression as a Python literal, or `...` when it has no simple one.
+fn python_default(tokens: TokenStream) -> String {
+    let Ok(expr) = syn::parse2::<Expr>(tokens) else {
+        return "...".into();
+    };
+    literal(&expr).unwrap_or_else(|| "...".into())
+}
+
+fn literal(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Str(s) => Some(python_str(&s.value())),
+            Lit::Bool(b) => Some(if b.value { "True" } else { "False" }.into()),
+            Lit::Int(i) => Some(i.base10_digits().into()),
+            Lit::Float(f) => Some(f.base10_digits().into()),
+            _ => None,
+        },
+        Expr::Path(path) if path.path.is_ident("None") => Some("None".into()),
+        Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => literal(expr).map(|value| format!("-{value}")),
+        Expr::MethodCall(call)
+            if call.args.is_empty()
+                && ["to_string", "to_owned", "into"].contains(&&*call.method.to_string()) =>
+        {
+            literal(&call.receiver)
+        }
+        Expr::Call(call) if call.args.is_empty() => {
+            let Expr::Path(path) = &*call.func else {
+                return None;
+            };
+            let segments: Vec<String> = path
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect();
+            match segments.join("::").as_str() {
+                "String::new" => Some("\"\"".into()),
+                "Vec::new" => Some("[]".into()),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn python_str(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\x1b' => out.push_str("\\x1b"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl Api {
+    fn collect(&mut self, module: &str, items: &[syn::Item]) {
+        for item in items {
+            match item {
+                syn::Item::Struct(item) if has_attr(&item.attrs, "pyclass") => {
+                    let rust_name = item.ident.to_string();
+                    let options = pyo3_options(&item.attrs, &["pyclass"]);
+                    let fields = item
+                        .fields
+                        .iter()
+                        .filter_map(|field| {
+                            let options = pyo3_options(&field.attrs, &["pyo3"]);
+                            if !options.get {
+                                return None;
+                            }
+                            Some(Field {
+                                name: options
+                                    .name
+                                    .unwrap_or_else(|| field.ident.as_ref().unwrap().to_string()),
+                                ty: field.ty.clone(),
+                                settable: options.set,
+                                doc: doc(&field.attrs),
+                            })
+                        })
+                        .collect();
+                    let class = self.class(&rust_name);
+                    class.py_name = options.name.unwrap_or_else(|| rust_name.clone());
+                    class.doc = doc(&item.attrs);
+                    class.fields = fields;
+                }
+                syn::Item::Impl(item) if has_attr(&item.attrs, "pymethods") => {
+                    let Some(rust_name) = type_ident(&item.self_ty) else {
+                        continue;
+                    };
+                    let methods: Vec<Function> = item
+                        .items
+                        .iter()
+                        .filter_map(|item| match item {
+                            syn::ImplItem::Fn(method) => Some(method),
+                            _ => None,
+                        })
+                        .map(|method| {
+                            let attrs = &method.attrs;
+                            let options = pyo3_options(attrs, &["pyo3"]);
+                            let rust_fn = method.sig.ident.to_string();
+                            let (kind, py_name) = if has_attr(attrs, "new") {
+                                (Kind::Constructor, "__init__".to_string())
+                            } else if has_attr(attrs, "getter") {
+                                let name = accessor_name(attrs, "getter").unwrap_or_else(|| {
+                                    rust_fn.strip_prefix("get_").unwrap_or(&rust_fn).into()
+                                });
+                                (Kind::Getter, name)
+                            } else if has_attr(attrs, "setter") {
+                                let name = accessor_name(attrs, "setter").unwrap_or_else(|| {
+                                    rust_fn.strip_prefix("set_").unwrap_or(&rust_fn).into()
+                                });
+                                (Kind::Setter, name)
+                            } else {
+                                let kind = if has_attr(attrs, "staticmethod") {
+                                    Kind::StaticMethod
+                                } else if has_attr(attrs, "classmethod") {
+                                    Kind::ClassMethod
+                                } else {
+                                    Kind::Method
+                                };
+                                (kind, options.name.clone().unwrap_or(rust_fn))
+                            };
+                            function(py_name, kind, attrs, &method.sig, &options)
+                        })
+                        .collect();
+                    self.class(&rust_name).methods.extend(methods);
+                }
+                syn::Item::Fn(item) if has_attr(&item.attrs, "pyfunction") => {
+                    let rust_fn = item.sig.ident.to_string();
+                    let mut options = pyo3_options(&item.attrs, &["pyfunction", "pyo3"]);
+                    let py_name = options.name.take().unwrap_or_else(|| rust_fn.clone());
+                    self.functions.insert(
+                        format!("{module}::{rust_fn}"),
+                        function(py_name, Kind::Function, &item.attrs, &item.sig, &options),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn class(&mut self, rust_name: &str) -> &mut Class {
+        if !self.classes.contains_key(rust_name) {
+            self.class_order.push(rust_name.to_string());
+        }
+        self.classes
+            .entry(rust_name.to_string())
+            .or_insert_with(|| Class {
+                py_name: rust_name.to_string(),
+                doc: String::new(),
+                fields: Vec::new(),
+                methods: Vec::new(),
+            })
+    }
+
+    /// Python spelling of a Rust type. `returned` picks the type PyO3 converts to
+    /// rather than the types it accepts (e.g. `Path` vs. any path-like).
+    fn py_type(&self, ty: &Type, current: &str, returned: bool) -> String {
+        let Type::Path(_) = ty else {
+            return match ty {
+                Type::Reference(reference) => self.py_type(&reference.elem, current, returned),
+                Type::Tuple(tuple) if tuple.elems.is_empty() => "None".into(),
+                Type::Tuple(tuple) => format!(
+                    "tuple[{}]",
+                    tuple
+                        .elems
+                        .iter()
+                        .map(|elem| self.py_type(elem, current, returned))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                _ => "Any".into(),
+            };
+        };
+        let ident = type_ident(ty).unwrap_or_default();
+        let args = generic_args(ty);
+        let arg = |index: usize| {
+            args.get(index)
+                .map(|arg| self.py_type(arg, current, returned))
+                .unwrap_or_else(|| "Any".into())
+        };
+        match ident.as_str() {
+            "Option" => match arg(0).as_str() {
+                "Any" => "Any".into(),
+                inner => format!("{inner} | None"),
+            },
+            "PyResult" | "Result" | "Py" | "Bound" | "Borrowed" | "PyRef" | "PyRefMut" | "Arc"
+            | "Box" => arg(0),
+            "Vec" if type_ident(args.first().copied().unwrap_or(ty)).as_deref() == Some("u8") => {
+                "bytes".into()
+            }
+            "Vec" | "VecDeque" => format!("list[{}]", arg(0)),
+            "HashSet" | "BTreeSet" => format!("set[{}]", arg(0)),
+            "HashMap" | "BTreeMap" => format!("dict[{}, {}]", arg(0), arg(1)),
+            "String" | "str" | "Cow" | "PyString" | "PyBackedStr" => "str".into(),
+            "bool" | "PyBool" => "bool".into(),
+            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64"
+            | "i128" | "isize" | "PyInt" => "int".into(),
+            "f32" | "f64" | "PyFloat" => "float".into(),
+            "PathBuf" | "Path" if returned => "Path".into(),
+            "PathBuf" | "Path" => "str | os.PathLike[str]".into(),
+            "FsPath" => "str | bytes | os.PathLike[str] | os.PathLike[bytes]".into(),
+            "PyBytes" => "bytes".into(),
+            "PyDict" => "dict[str, Any]".into(),
+            "PyList" => "list[Any]".into(),
+            "PyTuple" => "tuple[Any, ...]".into(),
+            "PyType" => "type".into(),
+            "Self" => current.into(),
+            other => match self.classes.get(other) {
+                Some(class) => class.py_name.clone(),
+                None => "Any".into(),
+            },
+        }
+    }
+
+    fn render(&self, registry: &Registry) -> String {
+        let mut out = String::new();
+        out.push_str(
+            "# Generated by `cargo xtask stubs` from the #[pyclass], #[pymethods] and\n\
+             # #[pyfunction] items in src/. Do not edit: rerun it when they change.\n\
+             # Describes a build with every cargo feature enabled; classes and functions\n\
+             # of a disabled feature are missing at runtime.\n\
+             \n\
+             import os\n\
+             from pathlib import Path\n\
+             from typing import Any, Literal, TypeAlias\n\
+             \n",
+        );
+        out.push_str(
+            "# A level number, or one of the standard level names.\n\
+             Level: TypeAlias = int | Literal[\n",
+        );
+        for name in LEVEL_NAMES {
+            let _ = writeln!(out, "    \"{name}\",");
+        }
+        out.push_str("]\n");
+
+        let mut emitted: Vec<&str> = Vec::new();
+        let registered = registry
+            .logging_classes
+            .iter()
+            .chain(&registry.top_classes)
+            .chain(&self.class_order);
+        for rust_name in registered {
+            if emitted.contains(&rust_name.as_str()) {
+                continue;
+            }
+            let Some(class) = self.classes.get(rust_name) else {
+                continue;
+            };
+            emitted.push(rust_name);
+            out.push('\n');
+            self.render_class(&mut out, class);
+        }
+
+        out.push_str(
+            "\nclass _LoggingModule:\n    \"\"\"The `logxide.logxide.logging` submodule.\"\"\"\n\n",
+        );
+        for rust_name in &registry.logging_classes {
+            if let Some(class) = self.classes.get(rust_name) {
+                let _ = writeln!(out, "    {0} = {0}", class.py_name);
+            }
+        }
+        for path in &registry.logging_functions {
+            if let Some(function) = self.functions.get(path) {
+                out.push_str("    @staticmethod\n");
+                self.render_function(&mut out, function, "", 1);
+            }
+        }
+        out.push_str("\nlogging: _LoggingModule\n");
+        for path in &registry.top_functions {
+            if let Some(function) = self.functions.get(path) {
+                out.push('\n');
+                self.render_function(&mut out, function, "", 0);
+            }
+        }
+        out
+    }
+
+    fn render_class(&self, out: &mut String, class: &Class) {
+        let _ = writeln!(out, "class {}:", class.py_name);
+        let mut empty = true;
+        if !class.doc.is_empty() {
+            write_doc(out, &class.doc, 1);
+            empty = false;
+        }
+        for field in &class.fields {
+            let ty = self.py_type(&field.ty, &class.py_name, true);
+            if field.settable {
+                let _ = writeln!(out, "    {}: {ty}", field.name);
+                if !field.doc.is_empty() {
+                    write_doc(out, &field.doc, 1);
+                }
+            } else {
+                let _ = write!(out, "    @property\n    def {}(self) -> {ty}:", field.name);
+                write_body(out, &field.doc, 2);
+            }
+            empty = false;
+        }
+        for method in &class.methods {
+            match method.kind {
+                Kind::Getter => {
+                    out.push_str("    @property\n");
+                }
+                Kind::Setter => {
+                    let _ = writeln!(out, "    @{}.setter", method.py_name);
+                }
+                Kind::StaticMethod => out.push_str("    @staticmethod\n"),
+                Kind::ClassMethod => out.push_str("    @classmethod\n"),
+                _ => {}
+            }
+            self.render_function(out, method, &class.py_name, 1);
+            empty = false;
+        }
+        if empty {
+            out.push_str("    ...\n");
+        }
+    }
+
+    fn render_function(&self, out: &mut String, function: &Function, class: &str, indent: usize) {
+        let pad = "    ".repeat(indent);
+        let mut params: Vec<String> = match function.kind {
+            Kind::Function | Kind::StaticMethod => Vec::new(),
+            Kind::ClassMethod => vec!["cls".into()],
+            _ => vec!["self".into()],
+        };
+        for param in &function.params {
+            params.push(match param {
+                Param::Named { name, ty, default } => {
+                    let mut ty = ty
+                        .as_ref()
+                        .map(|ty| self.py_type(ty, class, false))
+                        .unwrap_or_else(|| "Any".into());
+                    if (name == "level" || name.ends_with("_level")) && ty == "Any" {
+                        ty = match default.as_deref() {
+                            Some("None") => "Level | None".into(),
+                            _ => "Level".into(),
+                        };
+                    }
+                    // PyO3 answers NotImplemented for other types, as `object` declares.
+                    if matches!(
+                        function.py_name.as_str(),
+                        "__eq__" | "__ne__" | "__lt__" | "__le__" | "__gt__" | "__ge__"
+                    ) {
+                        ty = "object".into();
+                    }
+                    match default {
+                        Some(default) => format!("{name}: {ty} = {default}"),
+                        None => format!("{name}: {ty}"),
+                    }
+                }
+                Param::Args(name) => format!("*{}: Any", name.trim_start_matches('_')),
+                Param::Kwargs(name) => format!("**{}: Any", name.trim_start_matches('_')),
+                Param::PositionalOnly => "/".into(),
+                Param::KeywordOnly => "*".into(),
+            });
+        }
+        let ret = match (function.kind, &function.ret) {
+            (Kind::Constructor | Kind::Setter, _) | (_, None) => "None".to_string(),
+            (_, Some(ty)) => self.py_type(ty, class, true),
+        };
+        let one_line = format!(
+            "{pad}def {}({}) -> {ret}:",
+            function.py_name,
+            params.join(", ")
+        );
+        if one_line.len() <= LINE_LENGTH {
+            out.push_str(&one_line);
+        } else {
+            let _ = writeln!(out, "{pad}def {}(", function.py_name);
+            for param in &params {
+                let _ = writeln!(out, "{pad}    {param},");
+            }
+            let _ = write!(out, "{pad}) -> {ret}:");
+        }
+        write_body(out, &function.doc, indent + 1);
+    }
+}
+
+/// Finish a `def` line with its body: the docstring on the next lines, or ` ...`.
+fn write_body(out: &mut String, doc: &str, indent: usize) {
+    if doc.is_empty() {
+        out.push_str(" ...\n");
+    } else {
+        out.push('\n');
+        write_doc(out, doc, indent);
+    }
+}
+
+fn write_doc(out: &mut String, doc: &str, indent: usize) {
+    let pad = "    ".repeat(indent);
+    let text = doc.replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\"");
+    let mut lines = text.lines();
+    let first = lines.next().unwrap_or_default();
+    let rest: Vec<&str> = lines.collect();
+    if rest.is_empty() {
+        // A closing quote of the text would run into the closing `"""`.
+        let first = match first.strip_suffix('"') {
+            Some(start) => format!("{start}\\\""),
+            None => first.to_string(),
+        };
+        let _ = writeln!(out, "{pad}\"\"\"{first}\"\"\"");
+        return;
+    }
+    let _ = writeln!(out, "{pad}\"\"\"{first}");
+    for line in rest {
+        if line.is_empty() {
+            out.push('\n');
+        } else {
+            let _ = writeln!(out, "{pad}{line}");
+        }
+    }
+    let _ = writeln!(out, "{pad}\"\"\"");
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn committed_stub_is_up_to_date() {
+        assert!(
+            super::stub_is_current(),
+            "{} is out of date: run `cargo xtask stubs` and commit it",
+            super::STUB
+        );
+    }
+}